@@ -208,7 +208,6 @@ v2_enum_from_core!(
     }
 );
 
-// TODO(mbolin): Support in-repo layer.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[ts(tag = "type")]
@@ -239,6 +238,15 @@ pub enum ConfigLayerSource {
         file: AbsolutePathBuf,
     },
 
+    /// Project config layer from the nearest `.codex/config.toml` between the
+    /// working directory and the repository root. Only a safe subset of keys
+    /// (model hints, steering budgets, sandbox tightening) is retained.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Project {
+        file: AbsolutePathBuf,
+    },
+
     /// Session-layer overrides supplied via `-c`/`--config`.
     SessionFlags,
 
@@ -261,6 +269,7 @@ impl ConfigLayerSource {
             ConfigLayerSource::Mdm { .. } => 0,
             ConfigLayerSource::System { .. } => 10,
             ConfigLayerSource::User { .. } => 20,
+            ConfigLayerSource::Project { .. } => 25,
             ConfigLayerSource::SessionFlags => 30,
             ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. } => 40,
             ConfigLayerSource::LegacyManagedConfigTomlFromMdm => 50,
@@ -1460,7 +1469,8 @@ impl From<CoreTurnItem> for ThreadItem {
                     .content
                     .into_iter()
                     .map(|entry| match entry {
-                        CoreAgentMessageContent::Text { text } => text,
+                        CoreAgentMessageContent::Text { text }
+                        | CoreAgentMessageContent::Refusal { refusal: text } => text,
                     })
                     .collect::<String>();
                 ThreadItem::AgentMessage { id: agent.id, text }
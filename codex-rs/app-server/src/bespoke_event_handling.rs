@@ -910,7 +910,9 @@ async fn handle_token_count_event(
     token_count_event: TokenCountEvent,
     outgoing: &OutgoingMessageSender,
 ) {
-    let TokenCountEvent { info, rate_limits } = token_count_event;
+    let TokenCountEvent {
+        info, rate_limits, ..
+    } = token_count_event;
     if let Some(token_usage) = info.map(ThreadTokenUsage::from) {
         let notification = ThreadTokenUsageUpdatedNotification {
             thread_id: conversation_id.to_string(),
@@ -1571,6 +1573,7 @@ mod tests {
             TokenCountEvent {
                 info: Some(info),
                 rate_limits: Some(rate_limits),
+                prompt_breakdown: None,
             },
             &outgoing,
         )
@@ -1624,6 +1627,7 @@ mod tests {
             TokenCountEvent {
                 info: None,
                 rate_limits: None,
+                prompt_breakdown: None,
             },
             &outgoing,
         )
@@ -2,6 +2,7 @@
 
 use codex_common::CliConfigOverrides;
 use codex_core::config::Config;
+use codex_core::secrets::RedactingWriter;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::path::PathBuf;
@@ -24,6 +25,7 @@ use tracing::info;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Layer;
 use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
@@ -85,6 +87,7 @@ pub async fn run_main(
         .map_err(|e| {
             std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
         })?;
+    codex_core::default_client::set_network_config(config.network.clone());
 
     let feedback = CodexFeedback::new();
 
@@ -99,12 +102,13 @@ pub async fn run_main(
     // Install a simple subscriber so `tracing` output is visible.  Users can
     // control the log level with `RUST_LOG`.
     let stderr_fmt = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
+        .with_writer(|| RedactingWriter::new(std::io::stderr()))
         .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
         .with_filter(EnvFilter::from_default_env());
 
+    let feedback_writer = feedback.make_writer();
     let feedback_layer = tracing_subscriber::fmt::layer()
-        .with_writer(feedback.make_writer())
+        .with_writer(move || RedactingWriter::new(feedback_writer.make_writer()))
         .with_ansi(false)
         .with_target(false)
         .with_filter(Targets::new().with_default(Level::TRACE));
@@ -256,6 +256,34 @@ pub fn apply_hunks(
     }
 }
 
+/// Checks whether each hunk would apply to the filesystem as it is now,
+/// without writing anything. Returns one result per hunk, in order. Hunks are
+/// checked independently, so a hunk that depends on an earlier one in the same
+/// patch (such as an update to a file the patch adds) is checked against the
+/// file as it is before the patch.
+pub fn check_hunks(hunks: &[Hunk]) -> Vec<Result<(), ApplyPatchError>> {
+    hunks
+        .iter()
+        .map(|hunk| match hunk {
+            Hunk::AddFile { .. } => Ok(()),
+            Hunk::DeleteFile { path } => match std::fs::metadata(path) {
+                Ok(metadata) if metadata.is_file() => Ok(()),
+                Ok(_) => Err(ApplyPatchError::IoError(IoError {
+                    context: format!("Failed to delete file {}", path.display()),
+                    source: std::io::Error::other("not a file"),
+                })),
+                Err(err) => Err(ApplyPatchError::IoError(IoError {
+                    context: format!("Failed to delete file {}", path.display()),
+                    source: err,
+                })),
+            },
+            Hunk::UpdateFile { path, chunks, .. } => {
+                derive_new_contents_from_chunks(path, chunks).map(|_| ())
+            }
+        })
+        .collect()
+}
+
 /// Applies each parsed patch hunk to the filesystem.
 /// Returns an error if any of the changes could not be applied.
 /// Tracks file paths affected by applying a patch.
@@ -1062,4 +1090,38 @@ g
         let result = apply_patch(&patch, &mut stdout, &mut stderr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_check_hunks_reports_each_hunk_without_writing() {
+        let dir = tempdir().unwrap();
+        let updated = dir.path().join("updated.txt");
+        let stale = dir.path().join("stale.txt");
+        fs::write(&updated, "foo\nbar\n").unwrap();
+        fs::write(&stale, "something else\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Add File: {added}
++new
+*** Update File: {updated}
+@@
+ foo
+-bar
++baz
+*** Update File: {stale}
+@@
+-bar
++baz
+*** Delete File: {missing}"#,
+            added = dir.path().join("added.txt").display(),
+            updated = updated.display(),
+            stale = stale.display(),
+            missing = dir.path().join("missing.txt").display(),
+        ));
+        let hunks = parse_patch(&patch).unwrap().hunks;
+
+        let results = check_hunks(&hunks);
+        let ok: Vec<bool> = results.iter().map(Result::is_ok).collect();
+        assert_eq!(ok, vec![true, true, false, false]);
+        assert!(!dir.path().join("added.txt").exists());
+        assert_eq!(fs::read_to_string(&updated).unwrap(), "foo\nbar\n");
+    }
 }
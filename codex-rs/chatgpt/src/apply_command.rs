@@ -10,11 +10,18 @@ use crate::get_task::OutputItem;
 use crate::get_task::PrOutputItem;
 use crate::get_task::get_task;
 
-/// Applies the latest diff from a Codex agent task.
+/// Applies the latest diff from a Codex agent task, or a patch file.
 #[derive(Debug, Parser)]
 pub struct ApplyCommand {
+    /// A Codex cloud task id, or a patch file (`-` for stdin) in the format
+    /// the agent's `apply_patch` tool uses.
+    #[arg(value_name = "TASK_ID|PATCH_FILE")]
     pub task_id: String,
 
+    /// Check that the diff applies without changing any files.
+    #[arg(long)]
+    pub check: bool,
+
     #[clap(flatten)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -34,14 +41,18 @@ pub async fn run_apply_command(
         .await?;
 
     let task_response = get_task(&config, apply_cli.task_id).await?;
-    apply_diff_from_task(task_response, cwd).await
+    apply_diff(task_diff(&task_response)?, cwd, apply_cli.check).await
 }
 
 pub async fn apply_diff_from_task(
     task_response: GetTaskResponse,
     cwd: Option<PathBuf>,
 ) -> anyhow::Result<()> {
-    let diff_turn = match task_response.current_diff_task_turn {
+    apply_diff(task_diff(&task_response)?, cwd, false).await
+}
+
+fn task_diff(task_response: &GetTaskResponse) -> anyhow::Result<&str> {
+    let diff_turn = match &task_response.current_diff_task_turn {
         Some(turn) => turn,
         None => anyhow::bail!("No diff turn found"),
     };
@@ -50,18 +61,18 @@ pub async fn apply_diff_from_task(
         _ => None,
     });
     match output_diff {
-        Some(output_diff) => apply_diff(&output_diff.diff, cwd).await,
+        Some(output_diff) => Ok(output_diff.diff.as_str()),
         None => anyhow::bail!("No PR output item found"),
     }
 }
 
-async fn apply_diff(diff: &str, cwd: Option<PathBuf>) -> anyhow::Result<()> {
+async fn apply_diff(diff: &str, cwd: Option<PathBuf>, preflight: bool) -> anyhow::Result<()> {
     let cwd = cwd.unwrap_or(std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir()));
     let req = codex_git::ApplyGitRequest {
         cwd,
         diff: diff.to_string(),
         revert: false,
-        preflight: false,
+        preflight,
     };
     let res = codex_git::apply_git_patch(&req)?;
     if res.exit_code != 0 {
@@ -74,6 +85,10 @@ async fn apply_diff(diff: &str, cwd: Option<PathBuf>) -> anyhow::Result<()> {
             res.stderr
         );
     }
-    println!("Successfully applied diff");
+    if preflight {
+        println!("Diff applies cleanly");
+    } else {
+        println!("Successfully applied diff");
+    }
     Ok(())
 }
@@ -0,0 +1,216 @@
+//! `codex apply <PATCH_FILE>`: applies a patch in the agent's `apply_patch`
+//! format with the engine the agent uses, so a patch saved from one run or
+//! machine lands the same way on another.
+//!
+//! Every hunk is checked before anything is written, and the patch is applied
+//! only if all of them apply. As under the default `workspace-write` sandbox,
+//! hunks that touch paths outside the working directory are refused.
+
+use std::io::Read;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_apply_patch::Hunk;
+use codex_apply_patch::apply_hunks;
+use codex_apply_patch::check_hunks;
+use codex_apply_patch::parse_patch;
+
+const BEGIN_PATCH_MARKER: &str = "*** Begin Patch";
+
+/// Whether `arg` names a patch to apply locally rather than a cloud task.
+pub fn is_patch_arg(arg: &str) -> bool {
+    arg == "-" || Path::new(arg).is_file()
+}
+
+#[allow(clippy::print_stdout)]
+pub fn run_apply_patch(arg: &str, check: bool) -> Result<()> {
+    let patch = read_patch(arg)?;
+    if !patch.trim_start().starts_with(BEGIN_PATCH_MARKER) {
+        anyhow::bail!(
+            "{arg} is not an apply_patch patch (it should start with `{BEGIN_PATCH_MARKER}`); use `git apply` for unified diffs"
+        );
+    }
+    let cwd = std::env::current_dir()?;
+    let hunks: Vec<Hunk> = parse_patch(&patch)?
+        .hunks
+        .into_iter()
+        .map(|hunk| absolute(hunk, &cwd))
+        .collect();
+
+    let results: Vec<Result<(), String>> = hunks
+        .iter()
+        .zip(check_hunks(&hunks))
+        .map(|(hunk, result)| match outside(hunk, &cwd) {
+            Some(path) => Err(format!(
+                "{} is outside the working directory",
+                path.display()
+            )),
+            None => result.map_err(|err| err.to_string()),
+        })
+        .collect();
+    let failed = results.iter().filter(|result| result.is_err()).count();
+
+    if failed == 0 && !check {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        if apply_hunks(&hunks, &mut stdout, &mut stderr).is_err() {
+            anyhow::bail!(
+                "failed to write the patch; some files may have changed:\n{}",
+                String::from_utf8_lossy(&stderr).trim_end()
+            );
+        }
+    }
+
+    let applied = if check { "ok" } else { "applied" };
+    for (hunk, result) in hunks.iter().zip(&results) {
+        let (kind, path) = describe(hunk, &cwd);
+        match result {
+            Ok(()) => println!("{applied:<8}{kind} {path}"),
+            Err(err) => {
+                let mut lines = err.lines();
+                println!(
+                    "{:<8}{kind} {path}: {}",
+                    "failed",
+                    lines.next().unwrap_or_default()
+                );
+                for line in lines {
+                    println!("          {line}");
+                }
+            }
+        }
+    }
+
+    let total = hunks.len();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {total} hunk(s) do not apply; no files were changed");
+    }
+    if check {
+        println!("All {total} hunk(s) apply cleanly; no files were changed.");
+    } else {
+        println!("Applied {total} hunk(s).");
+    }
+    Ok(())
+}
+
+fn read_patch(arg: &str) -> Result<String> {
+    if arg == "-" {
+        let mut patch = String::new();
+        std::io::stdin()
+            .read_to_string(&mut patch)
+            .context("failed to read the patch from stdin")?;
+        return Ok(patch);
+    }
+    std::fs::read_to_string(arg).with_context(|| format!("failed to read {arg}"))
+}
+
+/// Resolves the hunk's paths against `cwd`.
+fn absolute(hunk: Hunk, cwd: &Path) -> Hunk {
+    match hunk {
+        Hunk::AddFile { path, contents } => Hunk::AddFile {
+            path: cwd.join(path),
+            contents,
+        },
+        Hunk::DeleteFile { path } => Hunk::DeleteFile {
+            path: cwd.join(path),
+        },
+        Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks,
+        } => Hunk::UpdateFile {
+            path: cwd.join(path),
+            move_path: move_path.map(|dest| cwd.join(dest)),
+            chunks,
+        },
+    }
+}
+
+/// The first path the hunk touches outside `cwd`, compared lexically.
+fn outside<'a>(hunk: &'a Hunk, cwd: &Path) -> Option<&'a Path> {
+    let paths: Vec<&Path> = match hunk {
+        Hunk::AddFile { path, .. } | Hunk::DeleteFile { path } => vec![path.as_path()],
+        Hunk::UpdateFile {
+            path, move_path, ..
+        } => std::iter::once(path.as_path())
+            .chain(move_path.as_deref())
+            .collect(),
+    };
+    paths
+        .into_iter()
+        .find(|path| !normalize(path).starts_with(cwd))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The `A`/`M`/`D` letter and the path to show for the hunk.
+fn describe(hunk: &Hunk, cwd: &Path) -> (&'static str, String) {
+    let display = |path: &Path| path.strip_prefix(cwd).unwrap_or(path).display().to_string();
+    match hunk {
+        Hunk::AddFile { path, .. } => ("A", display(path)),
+        Hunk::DeleteFile { path } => ("D", display(path)),
+        Hunk::UpdateFile {
+            path,
+            move_path: Some(dest),
+            ..
+        } => ("M", format!("{} -> {}", display(path), display(dest))),
+        Hunk::UpdateFile { path, .. } => ("M", display(path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn refuses_paths_that_leave_the_working_directory() {
+        let cwd = Path::new("/repo");
+        let inside = absolute(
+            Hunk::DeleteFile {
+                path: PathBuf::from("src/../README.md"),
+            },
+            cwd,
+        );
+        let escaping = absolute(
+            Hunk::UpdateFile {
+                path: PathBuf::from("src/lib.rs"),
+                move_path: Some(PathBuf::from("../elsewhere/lib.rs")),
+                chunks: Vec::new(),
+            },
+            cwd,
+        );
+        let absolute_path = absolute(
+            Hunk::AddFile {
+                path: PathBuf::from("/etc/passwd"),
+                contents: String::new(),
+            },
+            cwd,
+        );
+
+        assert_eq!(outside(&inside, cwd), None);
+        assert_eq!(
+            outside(&escaping, cwd),
+            Some(Path::new("/repo/../elsewhere/lib.rs"))
+        );
+        assert_eq!(outside(&absolute_path, cwd), Some(Path::new("/etc/passwd")));
+        assert_eq!(
+            describe(&escaping, cwd),
+            ("M", "src/lib.rs -> ../elsewhere/lib.rs".to_string())
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::find_codex_home;
+use codex_core::config::trust_store::TRUSTED_PROJECTS_FILE;
+use codex_core::config::trust_store::TrustStore;
+use codex_core::config::trust_store::trust_key;
+use codex_protocol::approvals::CommandApprovalRule;
+
+/// Manage the commands Codex runs without asking, per project.
+#[derive(Debug, clap::Parser)]
+pub struct ApprovalsCli {
+    #[command(subcommand)]
+    pub subcommand: ApprovalsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ApprovalsSubcommand {
+    /// List the approval rules of every project.
+    List,
+
+    /// Always allow a command in a project.
+    Add(AddArgs),
+
+    /// Remove a rule by its number in `codex approvals list`.
+    Remove(RemoveArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ProjectOption {
+    /// The project directory. Defaults to the current directory; inside a git
+    /// repository the rule covers the whole repository.
+    #[arg(long, value_name = "PATH")]
+    pub project: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct AddArgs {
+    #[clap(flatten)]
+    pub project: ProjectOption,
+
+    /// Allow only this exact command line, not longer ones.
+    #[arg(long, conflicts_with = "pattern")]
+    pub exact: bool,
+
+    /// Treat each argument as a glob (`*`, `?`); a final `**` matches any
+    /// remaining arguments.
+    #[arg(long)]
+    pub pattern: bool,
+
+    /// The command, e.g. `-- cargo test`. By default it also allows the same
+    /// command with more arguments.
+    #[arg(value_name = "COMMAND", trailing_var_arg = true, required = true)]
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct RemoveArgs {
+    #[clap(flatten)]
+    pub project: ProjectOption,
+
+    /// The rule's number, as shown by `codex approvals list`.
+    #[arg(value_name = "NUMBER")]
+    pub number: usize,
+}
+
+impl ApprovalsCli {
+    pub fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let mut store = TrustStore::load(&codex_home)
+            .with_context(|| format!("failed to read {TRUSTED_PROJECTS_FILE}"))?;
+
+        match self.subcommand {
+            ApprovalsSubcommand::List => {
+                if store.approval_rules.is_empty() {
+                    println!("No approval rules recorded.");
+                }
+                for (project, rules) in &store.approval_rules {
+                    println!("{}:", project.display());
+                    for (index, rule) in rules.iter().enumerate() {
+                        println!("  {}. {rule}", index + 1);
+                    }
+                }
+            }
+            ApprovalsSubcommand::Add(args) => {
+                let project = project_key(args.project.project)?;
+                let command = args.command;
+                let rule = if args.exact {
+                    CommandApprovalRule::Exact { command }
+                } else if args.pattern {
+                    CommandApprovalRule::Pattern { command }
+                } else {
+                    CommandApprovalRule::Prefix { command }
+                };
+                let message = format!("{}: {rule}", project.display());
+                if store.add_approval_rule(project, rule) {
+                    store.save(&codex_home)?;
+                    println!("Added {message}");
+                } else {
+                    println!("Already allowed {message}");
+                }
+            }
+            ApprovalsSubcommand::Remove(args) => {
+                let project = project_key(args.project.project)?;
+                let removed = args
+                    .number
+                    .checked_sub(1)
+                    .and_then(|index| store.remove_approval_rule(&project, index));
+                let Some(rule) = removed else {
+                    anyhow::bail!(
+                        "{} has no approval rule {}; see `codex approvals list`",
+                        project.display(),
+                        args.number
+                    );
+                };
+                store.save(&codex_home)?;
+                println!("Removed {}: {rule}", project.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn project_key(path: Option<PathBuf>) -> Result<PathBuf> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir().context("failed to resolve the current directory")?,
+    };
+    let path = std::path::absolute(&path)
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    Ok(trust_key(&path))
+}
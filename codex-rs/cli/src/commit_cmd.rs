@@ -0,0 +1,634 @@
+use std::io::BufRead;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use serde_json::Value;
+use serde_json::json;
+
+/// Diff text beyond this many bytes is left out of the prompt; the hunks are
+/// still listed so the model can place them.
+const MAX_PROMPT_DIFF_BYTES: usize = 100 * 1024;
+
+const COMMIT_PROMPT: &str = "Write a commit message for the staged changes below in the Conventional Commits format: `<type>(<optional scope>): <summary>`, where the type is one of feat, fix, docs, style, refactor, perf, test, build, ci, chore, or revert. Keep the summary under 72 characters, in the imperative mood, without a trailing period. Add a body after a blank line only when the change needs explaining, wrapped at 72 columns.
+
+{grouping}
+
+Everything you need is in this message; do not run commands or edit files. Each hunk below has an id such as `h1`. Reply with the commits and the ids of the hunks in each.
+
+Changes:
+{changes}";
+
+const SINGLE_COMMIT: &str = "Return exactly one commit that lists every hunk id.";
+
+const SPLIT_COMMITS: &str = "The changes may mix unrelated work. Group the hunks into one commit per logical change, ordered so each commit makes sense on top of the previous ones, and list every hunk id in exactly one commit. Return a single commit if the changes belong together.";
+
+/// Write a commit message for the staged changes with the model and commit.
+#[derive(Debug, clap::Parser)]
+pub struct CommitCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Stage all changes, including untracked files, first.
+    #[arg(long, short = 'a')]
+    pub all: bool,
+
+    /// Split unrelated changes into several commits by grouping hunks.
+    #[arg(long)]
+    pub split: bool,
+
+    /// Commit without asking for confirmation.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+
+    /// Model that writes the message.
+    #[arg(long, short = 'm')]
+    pub model: Option<String>,
+}
+
+impl CommitCli {
+    pub fn run(self) -> Result<()> {
+        let cwd = std::env::current_dir()?;
+        let root = git(&cwd, &["rev-parse", "--show-toplevel"], None)
+            .context("`codex commit` must run inside a git repository")?;
+        let root = PathBuf::from(root.trim());
+        let head = git(&root, &["rev-parse", "--verify", "-q", "HEAD"], None)
+            .ok()
+            .map(|sha| sha.trim().to_string());
+        let original_index = git(&root, &["write-tree"], None)?;
+
+        if self.all {
+            git(&root, &["add", "-A"], None)?;
+        }
+        let committed = self.commit_staged(&root, head.as_deref());
+        if self.all && !matches!(committed, Ok(true)) {
+            // Leave the index as we found it.
+            git(&root, &["read-tree", original_index.trim()], None)?;
+        }
+        if !committed? {
+            eprintln!("Nothing committed.");
+        }
+        Ok(())
+    }
+
+    /// Returns whether anything was committed.
+    fn commit_staged(&self, root: &Path, head: Option<&str>) -> Result<bool> {
+        let diff = git(
+            root,
+            &[
+                "diff",
+                "--cached",
+                "--binary",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-renames",
+            ],
+            None,
+        )?;
+        if diff.trim().is_empty() {
+            if self.all {
+                bail!("no changes to commit");
+            }
+            bail!("no staged changes; stage some with `git add` or pass --all");
+        }
+        let files = parse_diff(&diff);
+        let units = units(&files);
+
+        eprintln!("Writing the commit message...");
+        let reply = self.ask_model(&commit_prompt(&files, &units, self.split))?;
+        let plan = resolve_plan(&reply, units.len(), self.split)?;
+
+        print_plan(&plan, &files, &units);
+        if !self.yes && !confirm(plan.len())? {
+            return Ok(false);
+        }
+
+        if plan.len() == 1 {
+            git(root, &["commit", "-q", "-F", "-"], Some(&plan[0].message))?;
+            print_committed(root)?;
+        } else {
+            commit_split(root, head, &plan, &files, &units)?;
+        }
+        Ok(true)
+    }
+
+    /// Runs a one-shot, read-only `codex exec` turn and returns its JSON
+    /// reply.
+    fn ask_model(&self, prompt: &str) -> Result<Value> {
+        let dir = tempfile::tempdir()?;
+        let schema_path = dir.path().join("schema.json");
+        let reply_path = dir.path().join("reply.json");
+        std::fs::write(&schema_path, reply_schema().to_string())?;
+
+        let mut command = Command::new(std::env::current_exe()?);
+        command.args(["exec", "--skip-git-repo-check"]);
+        command.args(["--sandbox", "read-only", "--color", "never"]);
+        command.arg("--output-schema").arg(&schema_path);
+        command.arg("--output-last-message").arg(&reply_path);
+        for raw in &self.config_overrides.raw_overrides {
+            command.args(["-c", raw]);
+        }
+        if let Some(model) = &self.model {
+            command.args(["--model", model]);
+        }
+        // The prompt can be larger than a command-line argument may be, so
+        // pass it on stdin.
+        command.arg("-");
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("failed to start `codex exec`")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(prompt.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let tail: Vec<&str> = stderr.lines().rev().take(20).collect();
+            let tail: Vec<&str> = tail.into_iter().rev().collect();
+            bail!("the model run failed:\n{}", tail.join("\n"));
+        }
+        let reply =
+            std::fs::read_to_string(&reply_path).context("the model run produced no reply")?;
+        serde_json::from_str(&reply).context("the model's reply is not valid JSON")
+    }
+}
+
+/// One file's part of `git diff` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileDiff {
+    /// Path in the new tree.
+    path: String,
+    /// Everything before the first hunk: `diff --git`, mode and index lines,
+    /// and any binary patch.
+    header: String,
+    hunks: Vec<String>,
+}
+
+impl FileDiff {
+    /// Creations, deletions, mode changes, and binary files are kept whole.
+    fn splittable(&self) -> bool {
+        self.hunks.len() > 1
+            && !self.header.lines().any(|line| {
+                line.starts_with("new file mode")
+                    || line.starts_with("deleted file mode")
+                    || line.starts_with("old mode")
+            })
+    }
+}
+
+/// The smallest piece of the diff that can go into a commit: one hunk, or a
+/// whole file that cannot be split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Unit {
+    file: usize,
+    /// `None` for a whole file.
+    hunk: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedCommit {
+    message: String,
+    units: Vec<usize>,
+}
+
+fn parse_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            files.push(FileDiff {
+                path: String::new(),
+                header: String::new(),
+                hunks: Vec::new(),
+            });
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("@@") {
+            file.hunks.push(String::new());
+        }
+        match file.hunks.last_mut() {
+            Some(hunk) => hunk.push_str(line),
+            None => file.header.push_str(line),
+        }
+    }
+    for file in &mut files {
+        file.path = header_path(&file.header);
+    }
+    files
+}
+
+fn header_path(header: &str) -> String {
+    let from_marker = |prefix: &str| {
+        header
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix))
+            .map(str::to_string)
+    };
+    from_marker("+++ b/")
+        .or_else(|| from_marker("--- a/"))
+        .or_else(|| {
+            header
+                .lines()
+                .next()
+                .and_then(|line| line.rsplit_once(" b/"))
+                .map(|(_, path)| path.to_string())
+        })
+        .unwrap_or_default()
+}
+
+fn units(files: &[FileDiff]) -> Vec<Unit> {
+    files
+        .iter()
+        .enumerate()
+        .flat_map(|(file, diff)| {
+            if diff.splittable() {
+                (0..diff.hunks.len())
+                    .map(|hunk| Unit {
+                        file,
+                        hunk: Some(hunk),
+                    })
+                    .collect()
+            } else {
+                vec![Unit { file, hunk: None }]
+            }
+        })
+        .collect()
+}
+
+fn unit_text(files: &[FileDiff], unit: Unit) -> String {
+    let file = &files[unit.file];
+    match unit.hunk {
+        Some(hunk) => file.hunks[hunk].clone(),
+        None => format!("{}{}", file.header, file.hunks.concat()),
+    }
+}
+
+fn commit_prompt(files: &[FileDiff], units: &[Unit], split: bool) -> String {
+    let mut changes = String::new();
+    let mut budget = MAX_PROMPT_DIFF_BYTES;
+    for (index, unit) in units.iter().enumerate() {
+        let text = unit_text(files, *unit);
+        changes.push_str(&format!("\n### h{} {}\n", index + 1, files[unit.file].path));
+        if text.len() <= budget {
+            budget -= text.len();
+            changes.push_str(&format!("```diff\n{}```\n", text));
+        } else {
+            budget = 0;
+            changes.push_str(&format!(
+                "(contents omitted: {} lines)\n",
+                text.lines().count()
+            ));
+        }
+    }
+    COMMIT_PROMPT
+        .replace(
+            "{grouping}",
+            if split { SPLIT_COMMITS } else { SINGLE_COMMIT },
+        )
+        .replace("{changes}", &changes)
+}
+
+fn reply_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "commits": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "message": { "type": "string" },
+                        "hunks": { "type": "array", "items": { "type": "string" } },
+                    },
+                    "required": ["message", "hunks"],
+                    "additionalProperties": false,
+                },
+            },
+        },
+        "required": ["commits"],
+        "additionalProperties": false,
+    })
+}
+
+/// Turns the model's reply into commits covering every unit once. Unknown
+/// ids are ignored, a unit listed twice stays in its first commit, commits
+/// left empty are dropped, and units the model left out go into the last
+/// commit.
+fn resolve_plan(reply: &Value, unit_count: usize, split: bool) -> Result<Vec<PlannedCommit>> {
+    let mut plan = Vec::new();
+    let mut assigned = vec![false; unit_count];
+    for commit in reply["commits"].as_array().into_iter().flatten() {
+        let message = commit["message"].as_str().unwrap_or_default().trim();
+        if message.is_empty() {
+            continue;
+        }
+        let mut units = Vec::new();
+        for id in commit["hunks"].as_array().into_iter().flatten() {
+            let Some(index) = id
+                .as_str()
+                .and_then(|id| id.trim().strip_prefix('h'))
+                .and_then(|n| n.parse::<usize>().ok())
+                .and_then(|n| n.checked_sub(1))
+                .filter(|index| *index < unit_count)
+            else {
+                continue;
+            };
+            if !std::mem::replace(&mut assigned[index], true) {
+                units.push(index);
+            }
+        }
+        plan.push(PlannedCommit {
+            message: format!("{message}\n"),
+            units,
+        });
+    }
+    if plan.is_empty() {
+        bail!("the model did not propose a commit message");
+    }
+    if !split {
+        plan.truncate(1);
+        plan[0].units = (0..unit_count).collect();
+        return Ok(plan);
+    }
+    let first_message = plan[0].message.clone();
+    let missing: Vec<usize> = assigned
+        .iter()
+        .enumerate()
+        .filter(|(_, assigned)| !**assigned)
+        .map(|(index, _)| index)
+        .collect();
+    plan.retain(|commit| !commit.units.is_empty());
+    match plan.last_mut() {
+        Some(last) => last.units.extend(missing),
+        None => plan.push(PlannedCommit {
+            message: first_message,
+            units: missing,
+        }),
+    }
+    for commit in &mut plan {
+        commit.units.sort_unstable();
+    }
+    Ok(plan)
+}
+
+/// A patch with `selected` units, in diff order.
+fn build_patch(files: &[FileDiff], units: &[Unit], selected: &[usize]) -> String {
+    let mut patch = String::new();
+    let mut current_file = None;
+    for unit in selected.iter().map(|index| units[*index]) {
+        match unit.hunk {
+            None => patch.push_str(&unit_text(files, unit)),
+            Some(hunk) => {
+                if current_file != Some(unit.file) {
+                    patch.push_str(&files[unit.file].header);
+                }
+                patch.push_str(&files[unit.file].hunks[hunk]);
+            }
+        }
+        current_file = Some(unit.file);
+    }
+    patch
+}
+
+fn commit_split(
+    root: &Path,
+    head: Option<&str>,
+    plan: &[PlannedCommit],
+    files: &[FileDiff],
+    units: &[Unit],
+) -> Result<()> {
+    let staged = git(root, &["write-tree"], None)?;
+    let result = (|| -> Result<()> {
+        match head {
+            Some(head) => git(root, &["read-tree", head], None)?,
+            None => git(root, &["read-tree", "--empty"], None)?,
+        };
+        for commit in plan {
+            let patch = build_patch(files, units, &commit.units);
+            git(root, &["apply", "--cached", "-"], Some(&patch))?;
+            git(root, &["commit", "-q", "-F", "-"], Some(&commit.message))?;
+            print_committed(root)?;
+        }
+        Ok(())
+    })();
+    if let Err(err) = result {
+        // Undo the commits made so far and restore the staged changes.
+        match head {
+            Some(head) => {
+                git(root, &["reset", "-q", "--soft", head], None)?;
+            }
+            None => {
+                let _ = git(root, &["update-ref", "-d", "HEAD"], None);
+            }
+        }
+        git(root, &["read-tree", staged.trim()], None)?;
+        return Err(err.context("splitting the changes failed; nothing was committed"));
+    }
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+fn print_plan(plan: &[PlannedCommit], files: &[FileDiff], units: &[Unit]) {
+    if plan.len() == 1 {
+        println!("Proposed commit:");
+    } else {
+        println!("Proposed {} commits:", plan.len());
+    }
+    for (index, commit) in plan.iter().enumerate() {
+        println!();
+        let mut lines = commit.message.lines();
+        if plan.len() > 1 {
+            println!("{}. {}", index + 1, lines.next().unwrap_or_default());
+        } else {
+            println!("   {}", lines.next().unwrap_or_default());
+        }
+        for line in lines {
+            println!("   {line}");
+        }
+        let mut paths: Vec<&str> = commit
+            .units
+            .iter()
+            .map(|index| files[units[*index].file].path.as_str())
+            .collect();
+        paths.dedup();
+        println!("   ({})", paths.join(", "));
+    }
+    println!();
+}
+
+fn confirm(commits: usize) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("pass --yes to commit without a terminal to confirm");
+    }
+    if commits == 1 {
+        eprint!("Commit? [y/N] ");
+    } else {
+        eprint!("Create these {commits} commits? [y/N] ");
+    }
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+#[allow(clippy::print_stdout)]
+fn print_committed(root: &Path) -> Result<()> {
+    let summary = git(root, &["log", "-1", "--format=%h %s"], None)?;
+    println!("Committed {}", summary.trim());
+    Ok(())
+}
+
+/// Runs git in `dir` and returns its stdout.
+fn git(dir: &Path, args: &[&str], stdin: Option<&str>) -> Result<String> {
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run git")?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() { todo!() }
+ fn c() {}
+@@ -20,2 +20,3 @@
+ fn y() {}
++fn z() {}
+ fn end() {}
+diff --git a/NOTES.md b/NOTES.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/NOTES.md
+@@ -0,0 +1 @@
++notes
+";
+
+    #[test]
+    fn parses_hunks_and_keeps_new_files_whole() {
+        let files = parse_diff(DIFF);
+        assert_eq!(
+            files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+            vec!["src/lib.rs", "NOTES.md"]
+        );
+        assert_eq!(files[0].hunks.len(), 2);
+        assert_eq!(
+            units(&files),
+            vec![
+                Unit {
+                    file: 0,
+                    hunk: Some(0)
+                },
+                Unit {
+                    file: 0,
+                    hunk: Some(1)
+                },
+                Unit {
+                    file: 1,
+                    hunk: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_a_patch_from_selected_hunks() {
+        let files = parse_diff(DIFF);
+        let units = units(&files);
+        let patch = build_patch(&files, &units, &[1, 2]);
+        assert_eq!(
+            patch,
+            "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -20,2 +20,3 @@
+ fn y() {}
++fn z() {}
+ fn end() {}
+diff --git a/NOTES.md b/NOTES.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/NOTES.md
+@@ -0,0 +1 @@
++notes
+"
+        );
+    }
+
+    #[test]
+    fn plan_covers_every_hunk_once() {
+        let reply = json!({
+            "commits": [
+                { "message": "fix: stub b", "hunks": ["h1", "h9"] },
+                { "message": "docs: add notes", "hunks": ["h3", "h1"] },
+                { "message": "chore: nothing", "hunks": [] },
+            ],
+        });
+        assert_eq!(
+            resolve_plan(&reply, 3, true).expect("plan"),
+            vec![
+                PlannedCommit {
+                    message: "fix: stub b\n".to_string(),
+                    units: vec![0],
+                },
+                PlannedCommit {
+                    message: "docs: add notes\n".to_string(),
+                    units: vec![1, 2],
+                },
+            ]
+        );
+
+        let single = resolve_plan(&reply, 3, false).expect("plan");
+        assert_eq!(
+            single,
+            vec![PlannedCommit {
+                message: "fix: stub b\n".to_string(),
+                units: vec![0, 1, 2],
+            }]
+        );
+    }
+}
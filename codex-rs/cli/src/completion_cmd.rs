@@ -0,0 +1,251 @@
+//! `codex completions SHELL`: shell completion generated from the clap
+//! definitions.
+//!
+//! The script only registers `codex` with the shell. Each time the user
+//! presses Tab, the shell runs `COMPLETE=<shell> codex -- <words>`, and the
+//! candidates come from the same `Command` that parses the arguments, so new
+//! flags and subcommands complete without touching the script. Values that
+//! depend on the machine are looked up then: profiles from `config.toml`,
+//! models from the built-in presets, session ids for `resume` from the
+//! recorded sessions, and slash commands for a prompt starting with `/`.
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+
+use clap::Command;
+use clap_complete::Shell;
+use clap_complete::engine::ArgValueCompleter;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use codex_core::SESSIONS_SUBDIR;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::find_codex_home;
+use codex_core::config::xdg::codex_state_home;
+use codex_core::openai_models::model_presets::builtin_model_presets;
+
+/// The variable the registered script sets when it asks for candidates.
+pub const COMPLETE_ENV_VAR: &str = "COMPLETE";
+
+const BIN_NAME: &str = "codex";
+
+/// Session ids offered for `resume`, newest first.
+const MAX_SESSIONS: usize = 50;
+
+#[derive(Debug, clap::Parser)]
+pub struct CompletionCommand {
+    /// Shell to generate completions for
+    #[clap(value_enum, default_value_t = Shell::Bash)]
+    shell: Shell,
+}
+
+/// Prints the script that registers `codex` with `cmd.shell`.
+pub fn print_completion(cmd: CompletionCommand) -> anyhow::Result<()> {
+    let shells = Shells::builtins();
+    let shell = cmd.shell.to_string();
+    let Some(completer) = shells.completer(&shell) else {
+        anyhow::bail!("completions are not available for {shell}");
+    };
+    completer.write_registration(
+        COMPLETE_ENV_VAR,
+        BIN_NAME,
+        BIN_NAME,
+        BIN_NAME,
+        &mut std::io::stdout().lock(),
+    )?;
+    Ok(())
+}
+
+/// `cli` with value completers attached to the arguments whose values are
+/// only known at run time.
+pub fn with_dynamic_completers(cli: Command) -> Command {
+    add_completers(cli).mut_arg("prompt", |arg| {
+        arg.add(ArgValueCompleter::new(complete_slash_commands))
+    })
+}
+
+fn add_completers(mut cmd: Command) -> Command {
+    let is_resume = cmd.get_name() == "resume";
+    let ids: Vec<String> = cmd
+        .get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in ids {
+        let completer = match id.as_str() {
+            "config_profile" => ArgValueCompleter::new(complete_profiles),
+            "model" => ArgValueCompleter::new(complete_models),
+            "session_id" if is_resume => ArgValueCompleter::new(complete_session_ids),
+            _ => continue,
+        };
+        cmd = cmd.mut_arg(id, |arg| arg.add(completer));
+    }
+    let subcommands: Vec<String> = cmd
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        cmd = cmd.mut_subcommand(name, add_completers);
+    }
+    cmd
+}
+
+fn matching(current: &OsStr, candidates: Vec<(String, String)>) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    candidates
+        .into_iter()
+        .filter(|(value, _)| value.starts_with(current.as_ref()))
+        .map(|(value, help)| CompletionCandidate::new(value).help(Some(help.into())))
+        .collect()
+}
+
+fn complete_profiles(current: &OsStr) -> Vec<CompletionCandidate> {
+    let profiles = find_codex_home()
+        .ok()
+        .and_then(|home| std::fs::read_to_string(home.join(CONFIG_TOML_FILE)).ok())
+        .map(|contents| profile_names(&contents))
+        .unwrap_or_default();
+    matching(
+        current,
+        profiles
+            .into_iter()
+            .map(|name| (name, "profile from config.toml".to_string()))
+            .collect(),
+    )
+}
+
+/// The names of the `[profiles.*]` tables in `config.toml`.
+fn profile_names(config_toml: &str) -> Vec<String> {
+    let Ok(config) = config_toml.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    match config.get("profiles") {
+        Some(toml::Value::Table(profiles)) => profiles.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn complete_models(current: &OsStr) -> Vec<CompletionCandidate> {
+    matching(
+        current,
+        builtin_model_presets(None)
+            .into_iter()
+            .map(|preset| (preset.model, preset.description))
+            .collect(),
+    )
+}
+
+fn complete_session_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let sessions = find_codex_home()
+        .map(|home| recorded_sessions(&codex_state_home(&home).join(SESSIONS_SUBDIR)))
+        .unwrap_or_default();
+    matching(current, sessions)
+}
+
+/// `(id, started at)` for the newest recorded sessions under `sessions_dir`,
+/// which holds `YYYY/MM/DD/rollout-<timestamp>-<id>.jsonl` files.
+fn recorded_sessions(sessions_dir: &Path) -> Vec<(String, String)> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut dirs = vec![(sessions_dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if depth < 3 && path.is_dir() {
+                dirs.push((path, depth + 1));
+            } else if depth == 3 {
+                files.push(path);
+            }
+        }
+    }
+    files.sort_unstable_by(|a, b| b.cmp(a));
+    files
+        .iter()
+        .filter_map(|path| parse_rollout_file_name(path.file_name()?.to_str()?))
+        .take(MAX_SESSIONS)
+        .collect()
+}
+
+/// Splits `rollout-2025-05-07T17-24-21-<uuid>.jsonl` into the id and a
+/// readable start time.
+fn parse_rollout_file_name(name: &str) -> Option<(String, String)> {
+    const UUID_LEN: usize = 36;
+    let stem = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
+    let split = stem.len().checked_sub(UUID_LEN + 1)?;
+    let (timestamp, id) = (stem.get(..split)?, stem.get(split + 1..)?);
+    let (date, time) = timestamp.split_once('T')?;
+    Some((
+        id.to_string(),
+        format!("started {date} {}", time.replacen('-', ":", 2)),
+    ))
+}
+
+fn complete_slash_commands(current: &OsStr) -> Vec<CompletionCandidate> {
+    if !current.to_string_lossy().starts_with('/') {
+        return Vec::new();
+    }
+    matching(
+        current,
+        codex_tui::built_in_slash_commands()
+            .into_iter()
+            .map(|(name, command)| (format!("/{name}"), command.description().to_string()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_profile_names_from_config_toml() {
+        let config = "model = \"o3\"\n\n[profiles.fast]\nmodel = \"gpt-5-mini\"\n\n[profiles.deep]\nmodel_reasoning_effort = \"high\"\n";
+        let mut names = profile_names(config);
+        names.sort();
+        assert_eq!(names, vec!["deep".to_string(), "fast".to_string()]);
+        assert_eq!(profile_names("not = [toml"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn lists_recorded_sessions_newest_first() {
+        let sessions = tempfile::tempdir().unwrap();
+        let day = sessions.path().join("2025/05/07");
+        std::fs::create_dir_all(&day).unwrap();
+        for name in [
+            "rollout-2025-05-07T09-00-00-0196a5a1-0000-7000-8000-000000000001.jsonl",
+            "rollout-2025-05-07T17-24-21-0196a5a1-0000-7000-8000-000000000002.jsonl",
+            "notes.txt",
+        ] {
+            std::fs::write(day.join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            recorded_sessions(sessions.path()),
+            vec![
+                (
+                    "0196a5a1-0000-7000-8000-000000000002".to_string(),
+                    "started 2025-05-07 17:24:21".to_string()
+                ),
+                (
+                    "0196a5a1-0000-7000-8000-000000000001".to_string(),
+                    "started 2025-05-07 09:00:00".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn completes_slash_commands_only_after_a_slash() {
+        let values = |current: &str| -> Vec<String> {
+            complete_slash_commands(OsStr::new(current))
+                .iter()
+                .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+                .collect()
+        };
+        assert!(values("/rev").contains(&"/review".to_string()));
+        assert!(values("/rev").iter().all(|value| value.starts_with("/rev")));
+        assert_eq!(values("rev"), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,492 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::ValueEnum;
+use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::ConfigWriteResponse;
+use codex_app_server_protocol::MergeStrategy;
+use codex_app_server_protocol::WriteStatus;
+use codex_common::CliConfigOverrides;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::explain::ConfigExplanation;
+use codex_core::config::find_codex_home;
+use codex_core::config::migration::migrate_config_file;
+use codex_core::config::migration::migrate_keys;
+use codex_core::config::schema::config_json_schema;
+use codex_core::config::schema::validate_config_toml;
+use codex_core::config::service::ConfigService;
+use codex_core::config::xdg::USE_XDG_DIRS_KEY;
+use codex_core::config::xdg::XdgDirs;
+use codex_core::config::xdg::XdgMove;
+use codex_core::config::xdg::apply_xdg_migration;
+use codex_core::config::xdg::legacy_codex_home;
+use codex_core::config::xdg::plan_xdg_migration;
+use serde_json::Value as JsonValue;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+
+/// Read and edit `~/.codex/config.toml`.
+///
+/// Keys are dotted paths such as `model` or `features.web_search_request`.
+/// Edits keep existing comments and formatting, and are validated against the
+/// config schema before anything is written.
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print the value of a key.
+    Get(GetArgs),
+
+    /// Set a key. Values are parsed as TOML (`true`, `3`, `["a"]`) and fall
+    /// back to a plain string.
+    Set(SetArgs),
+
+    /// Remove a key.
+    Unset(UnsetArgs),
+
+    /// List every key set in config.toml.
+    List(ListArgs),
+
+    /// Print the effective value of a key and every place that sets it.
+    Explain(ExplainArgs),
+
+    /// Check a config file for unknown keys, wrong types, invalid values, and
+    /// deprecated keys.
+    Validate(ValidateArgs),
+
+    /// Rewrite deprecated keys in config.toml to their current names.
+    Migrate(MigrateArgs),
+
+    /// Move `~/.codex` into the XDG config, state, and cache directories and
+    /// turn on XDG mode.
+    MigrateXdg(MigrateXdgArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    #[arg(value_name = "KEY")]
+    pub key: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    #[arg(value_name = "KEY")]
+    pub key: String,
+
+    #[arg(value_name = "VALUE", allow_hyphen_values = true)]
+    pub value: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct UnsetArgs {
+    #[arg(value_name = "KEY")]
+    pub key: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the config as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExplainArgs {
+    #[arg(value_name = "KEY")]
+    pub key: String,
+
+    /// Explain the key as seen with this profile active.
+    #[arg(long = "profile", short = 'p')]
+    pub profile: Option<String>,
+
+    /// Output the explanation as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// Config file to check. Defaults to `$CODEX_HOME/config.toml`.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+
+    /// Print the config schema instead, for editor autocompletion.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub schema: Option<SchemaFormat>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MigrateArgs {
+    /// Show what would change without writing the file.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MigrateXdgArgs {
+    /// Directory to move from. Defaults to `$CODEX_HOME`, or `~/.codex`.
+    #[arg(long, value_name = "PATH")]
+    pub from: Option<PathBuf>,
+
+    /// Show what would move without moving anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaFormat {
+    /// JSON Schema (draft 7).
+    Json,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let service = ConfigService::new(codex_home.clone(), overrides);
+
+        match subcommand {
+            ConfigSubcommand::Get(args) => run_get(&service, args).await,
+            ConfigSubcommand::Set(args) => run_set(&service, args).await,
+            ConfigSubcommand::Unset(args) => run_unset(&service, args).await,
+            ConfigSubcommand::List(args) => run_list(&service, args).await,
+            ConfigSubcommand::Explain(args) => run_explain(&service, args).await,
+            ConfigSubcommand::Validate(args) => {
+                run_validate(codex_home.join(CONFIG_TOML_FILE), args)
+            }
+            ConfigSubcommand::Migrate(args) => run_migrate(&codex_home, args).await,
+            ConfigSubcommand::MigrateXdg(args) => run_migrate_xdg(args).await,
+        }
+    }
+}
+
+async fn run_get(service: &ConfigService, args: GetArgs) -> Result<()> {
+    let Some(value) = service.read_user_value(&args.key).await? else {
+        bail!("`{}` is not set in config.toml", args.key);
+    };
+    match value {
+        TomlValue::String(value) => println!("{value}"),
+        TomlValue::Table(_) => print!("{}", toml::to_string(&value)?),
+        value => println!("{value}"),
+    }
+    Ok(())
+}
+
+async fn run_set(service: &ConfigService, args: SetArgs) -> Result<()> {
+    let value = parse_cli_value(&args.value);
+    let value = serde_json::to_value(&value).context("failed to convert value")?;
+    let response = write(service, &args.key, value).await?;
+    println!("Set {} in {}.", args.key, response.file_path.display());
+    report_override(&response);
+    Ok(())
+}
+
+async fn run_unset(service: &ConfigService, args: UnsetArgs) -> Result<()> {
+    if service.read_user_value(&args.key).await?.is_none() {
+        println!("`{}` is not set in config.toml.", args.key);
+        return Ok(());
+    }
+    let response = write(service, &args.key, JsonValue::Null).await?;
+    println!(
+        "Removed {} from {}.",
+        args.key,
+        response.file_path.display()
+    );
+    report_override(&response);
+    Ok(())
+}
+
+async fn run_list(service: &ConfigService, args: ListArgs) -> Result<()> {
+    let config = service.read_user_config().await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&config)?);
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    flatten(&config, String::new(), &mut entries);
+    for (key, value) in entries {
+        println!("{key} = {value}");
+    }
+    Ok(())
+}
+
+async fn run_explain(service: &ConfigService, args: ExplainArgs) -> Result<()> {
+    let cwd = std::env::current_dir().context("failed to resolve the current directory")?;
+    let ConfigExplanation {
+        key,
+        value,
+        winner,
+        sources,
+    } = service
+        .explain(&args.key, &cwd, args.profile.as_deref())
+        .await?;
+
+    if args.json {
+        let report = serde_json::json!({
+            "key": key,
+            "value": value,
+            "source": winner.map(|index| sources[index].source.to_string()),
+            "sources": sources
+                .iter()
+                .map(|source| serde_json::json!({
+                    "source": source.source.to_string(),
+                    "value": source.value,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let Some(value) = value else {
+        println!("`{key}` is not set; Codex uses its default.");
+        return Ok(());
+    };
+    println!("{key} = {value}");
+    let Some(index) = winner else {
+        println!("  merged from:");
+        for source in &sources {
+            println!("    {}: {}", source.source, source.value);
+        }
+        return Ok(());
+    };
+    println!("  from {}", sources[index].source);
+    // Higher sources only lose to a lower one when the system config
+    // enforces the key.
+    for (label, listed) in [
+        ("overrides", &sources[index + 1..]),
+        ("ignored (enforced by the system config)", &sources[..index]),
+    ] {
+        if listed.is_empty() {
+            continue;
+        }
+        println!("  {label}:");
+        for source in listed {
+            println!("    {}: {}", source.source, source.value);
+        }
+    }
+    Ok(())
+}
+
+fn run_validate(default_path: PathBuf, args: ValidateArgs) -> Result<()> {
+    if let Some(SchemaFormat::Json) = args.schema {
+        println!("{}", serde_json::to_string_pretty(&config_json_schema())?);
+        return Ok(());
+    }
+
+    let path = args.path.unwrap_or(default_path);
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let diagnostics = validate_config_toml(&contents);
+    for diagnostic in &diagnostics {
+        println!("{}: {diagnostic}", path.display());
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors > 0 {
+        bail!("{} has {errors} error(s)", path.display());
+    }
+    if diagnostics.is_empty() {
+        println!("{} is valid.", path.display());
+    }
+    Ok(())
+}
+
+async fn run_migrate(codex_home: &Path, args: MigrateArgs) -> Result<()> {
+    let path = codex_home.join(CONFIG_TOML_FILE);
+    let migrated = if args.dry_run {
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                let mut config: TomlValue = toml::from_str(&contents)
+                    .with_context(|| format!("failed to parse {}", path.display()))?;
+                migrate_keys(&mut config)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()));
+            }
+        }
+    } else {
+        migrate_config_file(codex_home).await?
+    };
+
+    if migrated.is_empty() {
+        println!("{} has no deprecated keys.", path.display());
+        return Ok(());
+    }
+    for key in &migrated {
+        let (from, to) = (key.from_key(), key.to_key());
+        if key.superseded {
+            println!("Removed `{from}`; `{to}` is already set.");
+        } else {
+            println!("Renamed `{from}` to `{to}`.");
+        }
+    }
+    if args.dry_run {
+        println!("Dry run: {} was not changed.", path.display());
+    } else {
+        println!("Updated {}.", path.display());
+    }
+    Ok(())
+}
+
+async fn run_migrate_xdg(args: MigrateXdgArgs) -> Result<()> {
+    let Some(from) = args.from.or_else(legacy_codex_home) else {
+        bail!("could not find the home directory; pass --from");
+    };
+    let Some(dirs) = XdgDirs::from_env() else {
+        bail!("could not find the home directory to resolve the XDG directories");
+    };
+    if from == dirs.config {
+        bail!("{} is already the XDG config directory", from.display());
+    }
+
+    let moves = plan_xdg_migration(&from, &dirs)
+        .with_context(|| format!("failed to read {}", from.display()))?;
+    for XdgMove { from, to, conflict } in &moves {
+        if *conflict {
+            println!(
+                "Skipped {}: {} already exists.",
+                from.display(),
+                to.display()
+            );
+        } else {
+            println!("{} -> {}", from.display(), to.display());
+        }
+    }
+    if args.dry_run {
+        println!("Dry run: nothing was moved.");
+        return Ok(());
+    }
+
+    apply_xdg_migration(&moves).context("failed to move Codex data")?;
+    let service = ConfigService::new(dirs.config.clone(), Vec::new());
+    write(&service, USE_XDG_DIRS_KEY, JsonValue::Bool(true)).await?;
+    println!(
+        "Moved {} entries and set `{USE_XDG_DIRS_KEY} = true` in {}.",
+        moves.iter().filter(|m| !m.conflict).count(),
+        dirs.config.join(CONFIG_TOML_FILE).display()
+    );
+    if moves.iter().any(|m| m.conflict) {
+        println!(
+            "Entries that were skipped are still in {}; merge them by hand.",
+            from.display()
+        );
+    }
+    Ok(())
+}
+
+async fn write(
+    service: &ConfigService,
+    key: &str,
+    value: JsonValue,
+) -> Result<ConfigWriteResponse> {
+    Ok(service
+        .write_value(ConfigValueWriteParams {
+            key_path: key.to_string(),
+            value,
+            merge_strategy: MergeStrategy::Replace,
+            file_path: None,
+            expected_version: None,
+        })
+        .await?)
+}
+
+fn report_override(response: &ConfigWriteResponse) {
+    if response.status == WriteStatus::OkOverridden
+        && let Some(overridden) = &response.overridden_metadata
+    {
+        eprintln!(
+            "Note: {}; the effective value is {}.",
+            overridden.message, overridden.effective_value
+        );
+    }
+}
+
+/// Parses `raw` as a TOML value, treating anything that is not valid TOML
+/// (such as a bare word) as a string.
+fn parse_cli_value(raw: &str) -> TomlValue {
+    let wrapped = format!("_v_ = {raw}");
+    toml::from_str::<toml::Table>(&wrapped)
+        .ok()
+        .and_then(|mut table| table.remove("_v_"))
+        .unwrap_or_else(|| TomlValue::String(raw.to_string()))
+}
+
+/// Collects `dotted.key = value` pairs for every non-table value in `value`.
+fn flatten(value: &TomlValue, prefix: String, out: &mut Vec<(String, TomlValue)>) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(value, key, out);
+            }
+        }
+        value => out.push((prefix, value.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cli_values_parse_as_toml_or_fall_back_to_strings() {
+        assert_eq!(parse_cli_value("true"), TomlValue::Boolean(true));
+        assert_eq!(parse_cli_value("3"), TomlValue::Integer(3));
+        assert_eq!(
+            parse_cli_value("gpt-5.1-codex"),
+            TomlValue::String("gpt-5.1-codex".to_string())
+        );
+        assert_eq!(
+            parse_cli_value("\"quoted\""),
+            TomlValue::String("quoted".to_string())
+        );
+    }
+
+    #[test]
+    fn flatten_produces_dotted_keys() {
+        let config: TomlValue = toml::from_str(
+            r#"
+model = "o3"
+
+[features]
+web_search_request = true
+"#,
+        )
+        .expect("valid toml");
+        let mut entries = Vec::new();
+        flatten(&config, String::new(), &mut entries);
+
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "features.web_search_request".to_string(),
+                    TomlValue::Boolean(true)
+                ),
+                ("model".to_string(), TomlValue::String("o3".to_string())),
+            ]
+        );
+    }
+}
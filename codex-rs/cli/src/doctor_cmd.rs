@@ -0,0 +1,596 @@
+use std::path::Path;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use anyhow::bail;
+use codex_app_server_protocol::AuthMode;
+use codex_common::CliConfigOverrides;
+use codex_core::CodexAuth;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::config::schema::validate_config_toml;
+use codex_core::config::types::ContainerSandboxConfig;
+use codex_core::exec::SandboxType;
+use codex_core::features::Feature;
+use codex_core::get_platform_sandbox;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::mcp::check_mcp_servers;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::provider_health::probe_provider;
+use codex_core::secrets::redact;
+use codex_core::steering::discover_steering;
+use codex_core::token_data::jwt_expiry;
+use serde_json::json;
+
+/// Diagnose connectivity and configuration problems.
+#[derive(Debug, clap::Parser)]
+pub struct DoctorCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Only probe every base URL (including `fallback_base_urls`) of the
+    /// active model provider and of any provider that configures fallbacks.
+    #[arg(long, default_value_t = false)]
+    pub providers: bool,
+
+    /// Print the report as JSON, for attaching to a support ticket.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Skip => "skip",
+        }
+    }
+}
+
+/// The outcome of one check, with the fix to suggest when it is not `ok`.
+#[derive(Debug)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+impl DoctorCli {
+    pub async fn run(self) -> Result<()> {
+        let DoctorCli {
+            config_overrides,
+            providers,
+            json,
+        } = self;
+
+        let mut checks = Vec::new();
+        if !providers {
+            checks.extend(check_config_file());
+        }
+        let config = match config_overrides.parse_overrides() {
+            Ok(overrides) => Config::load_with_cli_overrides(overrides)
+                .await
+                .map_err(|err| {
+                    Check::new(
+                        "config",
+                        CheckStatus::Fail,
+                        format!("failed to load: {err}"),
+                    )
+                    .with_fix("run `codex config validate` and fix the reported keys")
+                }),
+            Err(err) => Err(Check::new("config", CheckStatus::Fail, err)
+                .with_fix("fix the `-c key=value` overrides on the command line")),
+        };
+
+        if let Ok(config) = &config {
+            codex_core::default_client::set_network_config(config.network.clone());
+        }
+        match config {
+            Ok(config) if providers => checks.extend(check_providers(&config).await),
+            Ok(config) => {
+                checks.push(check_credentials(&config).await);
+                checks.extend(check_providers(&config).await);
+                checks.extend(check_mcp(&config).await);
+                checks.push(check_sandbox(&config));
+                checks.extend(check_steering(&config));
+                checks.extend(check_git(&config.cwd));
+            }
+            Err(check) => {
+                checks.push(check);
+                if !providers {
+                    checks.push(check_sandbox_support());
+                    if let Ok(cwd) = std::env::current_dir() {
+                        checks.extend(check_git(&cwd));
+                    }
+                }
+            }
+        }
+
+        for check in &mut checks {
+            check.detail = redact(&check.detail).into_owned();
+        }
+        if json {
+            print_json(&checks)?;
+        } else {
+            print_human(&checks);
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+            .count();
+        if failed > 0 {
+            bail!("{failed} check(s) failed");
+        }
+        Ok(())
+    }
+}
+
+fn print_human(checks: &[Check]) {
+    for check in checks {
+        let label = match check.status {
+            CheckStatus::Ok => "ok  ",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "skip",
+        };
+        println!("{label}  {}  {}", check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("      fix: {fix}");
+        }
+    }
+}
+
+fn print_json(checks: &[Check]) -> Result<()> {
+    let checks: Vec<_> = checks
+        .iter()
+        .map(|check| {
+            json!({
+                "name": check.name,
+                "status": check.status.as_str(),
+                "detail": check.detail,
+                "fix": check.fix,
+            })
+        })
+        .collect();
+    let report = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "checks": checks,
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+/// Checks `$CODEX_HOME/config.toml` against the config schema.
+fn check_config_file() -> Vec<Check> {
+    let path = match find_codex_home() {
+        Ok(codex_home) => codex_home.join(CONFIG_TOML_FILE),
+        Err(err) => {
+            return vec![
+                Check::new(
+                    "config file",
+                    CheckStatus::Fail,
+                    format!("cannot locate CODEX_HOME: {err}"),
+                )
+                .with_fix("set CODEX_HOME to an existing directory"),
+            ];
+        }
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return vec![Check::new(
+                "config file",
+                CheckStatus::Ok,
+                format!("{} does not exist; using defaults", path.display()),
+            )];
+        }
+        Err(err) => {
+            return vec![
+                Check::new(
+                    "config file",
+                    CheckStatus::Fail,
+                    format!("cannot read {}: {err}", path.display()),
+                )
+                .with_fix(format!("check the permissions of {}", path.display())),
+            ];
+        }
+    };
+
+    let diagnostics = validate_config_toml(&contents);
+    if diagnostics.is_empty() {
+        return vec![Check::new(
+            "config file",
+            CheckStatus::Ok,
+            format!("{} is valid", path.display()),
+        )];
+    }
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let status = if diagnostic.is_error() {
+                CheckStatus::Fail
+            } else {
+                CheckStatus::Warn
+            };
+            Check::new(
+                "config file",
+                status,
+                format!("{}: {diagnostic}", path.display()),
+            )
+            .with_fix("run `codex config validate`, or `codex config migrate` for deprecated keys")
+        })
+        .collect()
+}
+
+/// Checks that the active provider has credentials, and that a ChatGPT
+/// login has not expired.
+async fn check_credentials(config: &Config) -> Check {
+    let name = "credentials";
+    let provider = &config.model_provider;
+    if !provider.requires_openai_auth {
+        return match provider.api_key().await {
+            Ok(Some(_)) if provider.api_key_cmd.is_some() => {
+                Check::new(name, CheckStatus::Ok, "API key resolved from `api_key_cmd`")
+            }
+            Ok(Some(_)) => Check::new(
+                name,
+                CheckStatus::Ok,
+                format!(
+                    "API key found in ${}",
+                    provider.env_key.as_deref().unwrap_or_default()
+                ),
+            ),
+            Ok(None) => Check::new(
+                name,
+                CheckStatus::Ok,
+                format!("provider `{}` needs no API key", config.model_provider_id),
+            ),
+            Err(err) => Check::new(name, CheckStatus::Fail, err.to_string()).with_fix(format!(
+                "set the API key for `model_providers.{}` (`env_key` or `api_key_cmd`)",
+                config.model_provider_id
+            )),
+        };
+    }
+
+    let auth = match CodexAuth::from_auth_storage(
+        &config.codex_home,
+        config.cli_auth_credentials_store_mode,
+    ) {
+        Ok(Some(auth)) => auth,
+        Ok(None) => {
+            return Check::new(name, CheckStatus::Fail, "not logged in")
+                .with_fix("run `codex login`");
+        }
+        Err(err) => {
+            return Check::new(
+                name,
+                CheckStatus::Fail,
+                format!("cannot read credentials: {err}"),
+            )
+            .with_fix("run `codex logout` and then `codex login`");
+        }
+    };
+    match auth.mode {
+        AuthMode::ApiKey => Check::new(name, CheckStatus::Ok, "logged in with an API key"),
+        AuthMode::ChatGPT => {
+            let token_data = match auth.get_token_data().await {
+                Ok(token_data) => token_data,
+                Err(err) => {
+                    return Check::new(
+                        name,
+                        CheckStatus::Fail,
+                        format!("ChatGPT login is unusable: {err}"),
+                    )
+                    .with_fix("run `codex login`");
+                }
+            };
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs() as i64)
+                .unwrap_or_default();
+            match jwt_expiry(&token_data.access_token) {
+                Ok(Some(expiry)) if expiry <= now => Check::new(
+                    name,
+                    CheckStatus::Warn,
+                    format!(
+                        "ChatGPT access token expired {} ago; it is refreshed on the next request",
+                        format_duration(now - expiry)
+                    ),
+                )
+                .with_fix("run `codex login` if requests fail with 401 Unauthorized"),
+                Ok(Some(expiry)) => Check::new(
+                    name,
+                    CheckStatus::Ok,
+                    format!(
+                        "logged in with ChatGPT; access token expires in {}",
+                        format_duration(expiry - now)
+                    ),
+                ),
+                Ok(None) | Err(_) => Check::new(name, CheckStatus::Ok, "logged in with ChatGPT"),
+            }
+        }
+    }
+}
+
+/// Probes the active provider and every provider that configures fallbacks.
+async fn check_providers(config: &Config) -> Vec<Check> {
+    let mut targets = vec![(&config.model_provider_id, &config.model_provider)];
+    let mut with_fallbacks: Vec<_> = config
+        .model_providers
+        .iter()
+        .filter(|(id, provider)| {
+            **id != config.model_provider_id && provider.fallback_base_urls.is_some()
+        })
+        .collect();
+    with_fallbacks.sort_by_key(|(id, _)| *id);
+    targets.extend(with_fallbacks);
+
+    let mut checks = Vec::new();
+    for (id, provider) in targets {
+        for probe in probe_provider(provider).await {
+            let latency_ms = probe.latency.as_millis();
+            let result = match (probe.status, &probe.error) {
+                (_, Some(error)) => format!("error: {error}"),
+                (Some(status), None) => format!("HTTP {status}"),
+                (None, None) => "no response".to_string(),
+            };
+            let detail = format!("{}  {result}  {latency_ms}ms", probe.base_url);
+            let name = format!("provider {id}");
+            checks.push(if probe.is_healthy() {
+                Check::new(name, CheckStatus::Ok, detail)
+            } else {
+                Check::new(name, CheckStatus::Fail, detail).with_fix(format!(
+                    "check your network and proxy settings, and `base_url` of `model_providers.{id}`"
+                ))
+            });
+        }
+    }
+    checks
+}
+
+async fn check_mcp(config: &Config) -> Vec<Check> {
+    if config.mcp_servers.is_empty() {
+        return vec![Check::new(
+            "mcp",
+            CheckStatus::Skip,
+            "no MCP servers configured",
+        )];
+    }
+
+    let summary = check_mcp_servers(config).await;
+    let mut checks = Vec::new();
+    for server in summary.ready {
+        checks.push(Check::new(
+            format!("mcp {server}"),
+            CheckStatus::Ok,
+            "handshake completed",
+        ));
+    }
+    for failure in summary.failed {
+        checks.push(
+            Check::new(
+                format!("mcp {}", failure.server),
+                CheckStatus::Fail,
+                failure.error,
+            )
+            .with_fix(format!(
+                "run `codex mcp get {}` to review its command and environment",
+                failure.server
+            )),
+        );
+    }
+    for server in summary.cancelled {
+        checks.push(Check::new(
+            format!("mcp {server}"),
+            CheckStatus::Warn,
+            "startup was cancelled",
+        ));
+    }
+    checks
+}
+
+fn check_sandbox(config: &Config) -> Check {
+    if matches!(config.sandbox_policy, SandboxPolicy::DangerFullAccess) {
+        return Check::new(
+            "sandbox",
+            CheckStatus::Skip,
+            "disabled by `sandbox_mode = \"danger-full-access\"`",
+        );
+    }
+    if let Some(container) = &config.container_sandbox {
+        return check_container_runtime(container);
+    }
+    check_sandbox_support()
+}
+
+/// Checks that the runtime behind `sandbox.backend` is installed and can
+/// reach its daemon (or, for Podman, its storage).
+fn check_container_runtime(container: &ContainerSandboxConfig) -> Check {
+    let name = "sandbox";
+    let program = container.runtime.program();
+    match std::process::Command::new(program).arg("version").output() {
+        Ok(output) if output.status.success() => Check::new(
+            name,
+            CheckStatus::Ok,
+            format!("{program} container, image {}", container.image),
+        ),
+        Ok(output) => Check::new(
+            name,
+            CheckStatus::Fail,
+            format!(
+                "`{program} version` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        )
+        .with_fix(format!(
+            "start the {program} service, or remove `sandbox.backend` to use the platform sandbox"
+        )),
+        Err(err) => Check::new(
+            name,
+            CheckStatus::Fail,
+            format!("{program} not found: {err}"),
+        )
+        .with_fix(format!(
+            "install {program}, or remove `sandbox.backend` to use the platform sandbox"
+        )),
+    }
+}
+
+/// Checks that the platform sandbox can be used on this machine.
+fn check_sandbox_support() -> Check {
+    let name = "sandbox";
+    match get_platform_sandbox() {
+        Some(SandboxType::MacosSeatbelt) => {
+            if Path::new("/usr/bin/sandbox-exec").exists() {
+                Check::new(name, CheckStatus::Ok, "Seatbelt (/usr/bin/sandbox-exec)")
+            } else {
+                Check::new(name, CheckStatus::Fail, "/usr/bin/sandbox-exec is missing")
+                    .with_fix("restore /usr/bin/sandbox-exec, which ships with macOS")
+            }
+        }
+        Some(SandboxType::LinuxSeccomp) => match std::fs::read_to_string(LINUX_LSM_PATH) {
+            Ok(lsms) if lsms.trim().split(',').any(|lsm| lsm == "landlock") => {
+                Check::new(name, CheckStatus::Ok, "Landlock and seccomp")
+            }
+            Ok(lsms) => Check::new(
+                name,
+                CheckStatus::Fail,
+                format!("Landlock is not enabled (active LSMs: {})", lsms.trim()),
+            )
+            .with_fix(
+                "boot a kernel (5.13+) with `landlock` in its `lsm=` list, or run Codex in a \
+                 container with `--sandbox danger-full-access`",
+            ),
+            Err(err) => Check::new(
+                name,
+                CheckStatus::Warn,
+                format!("cannot read {LINUX_LSM_PATH}: {err}"),
+            )
+            .with_fix("mount securityfs, or check that the kernel enables Landlock"),
+        },
+        Some(SandboxType::WindowsRestrictedToken) => {
+            Check::new(name, CheckStatus::Ok, "Windows restricted token")
+        }
+        Some(SandboxType::None | SandboxType::Container) | None => Check::new(
+            name,
+            CheckStatus::Warn,
+            "no sandbox is available on this platform; commands need approval",
+        )
+        .with_fix("enable the experimental Windows sandbox, or run Codex in WSL"),
+    }
+}
+
+const LINUX_LSM_PATH: &str = "/sys/kernel/security/lsm";
+
+fn check_steering(config: &Config) -> Vec<Check> {
+    if !config.features.enabled(Feature::Steering) {
+        return vec![Check::new(
+            "steering",
+            CheckStatus::Skip,
+            "the `steering` feature is disabled",
+        )];
+    }
+    let discovery = discover_steering(config);
+    let mut checks = vec![Check::new(
+        "steering",
+        CheckStatus::Ok,
+        match discovery.files.len() {
+            0 => "no steering files found".to_string(),
+            count => format!("{count} file(s) loaded"),
+        },
+    )];
+    for path in &discovery.skipped {
+        checks.push(
+            Check::new(
+                "steering",
+                CheckStatus::Warn,
+                format!("{} does not fit in the byte budget", path.display()),
+            )
+            .with_fix("shorten it or raise `steering.max_bytes`"),
+        );
+    }
+    for error in discovery.errors {
+        checks.push(
+            Check::new(
+                "steering",
+                CheckStatus::Warn,
+                format!("{}: {}", error.path.display(), error.message),
+            )
+            .with_fix(format!("fix or remove {}", error.path.display())),
+        );
+    }
+    checks
+}
+
+fn check_git(cwd: &Path) -> Vec<Check> {
+    let git = match std::process::Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => Check::new(
+            "git",
+            CheckStatus::Ok,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        Ok(output) => Check::new(
+            "git",
+            CheckStatus::Fail,
+            format!("`git --version` exited with {}", output.status),
+        )
+        .with_fix("reinstall git"),
+        Err(err) => Check::new("git", CheckStatus::Fail, format!("git not found: {err}"))
+            .with_fix("install git and make sure it is on PATH"),
+    };
+    let repo = match get_git_repo_root(cwd) {
+        Some(root) => Check::new(
+            "git repo",
+            CheckStatus::Ok,
+            format!("{} is a git repository", root.display()),
+        ),
+        None => Check::new(
+            "git repo",
+            CheckStatus::Warn,
+            format!("{} is not inside a git repository", cwd.display()),
+        )
+        .with_fix("run `git init`, or pass `--skip-git-repo-check` to `codex exec`"),
+    };
+    vec![git, repo]
+}
+
+fn format_duration(seconds: i64) -> String {
+    match seconds {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::export::BUILTIN_FORMATS;
+use codex_core::export::exporter_for;
+use codex_core::export::load_transcript;
+use codex_core::find_conversation_path_by_id_str;
+
+/// Render a recorded session as a shareable transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id, or the path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Output format: `markdown`, `html`, `json`, or the name of an
+    /// `[exporters.<name>]` entry in config.toml.
+    #[arg(long, short = 'f', default_value = "markdown")]
+    pub format: String,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Replace file paths and drop environment details (working directory,
+    /// CLI version) so the transcript can be shared outside the team.
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+}
+
+impl ExportCli {
+    pub async fn run(self) -> Result<()> {
+        let ExportCli {
+            config_overrides,
+            session,
+            format,
+            output,
+            redact,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let Some(exporter) = exporter_for(&format, &config.exporters) else {
+            let mut available: Vec<&str> = BUILTIN_FORMATS.to_vec();
+            let mut configured: Vec<&str> = config.exporters.keys().map(String::as_str).collect();
+            configured.sort_unstable();
+            available.extend(configured);
+            bail!(
+                "unknown export format `{format}`; available formats: {}",
+                available.join(", ")
+            );
+        };
+
+        let rollout_path = {
+            let candidate = PathBuf::from(&session);
+            if candidate.is_file() {
+                candidate
+            } else {
+                find_conversation_path_by_id_str(&config.codex_home, &session)
+                    .await?
+                    .with_context(|| format!("no recorded session found for `{session}`"))?
+            }
+        };
+
+        let transcript = load_transcript(&rollout_path)
+            .await
+            .with_context(|| format!("failed to read {}", rollout_path.display()))?;
+        let transcript = if redact {
+            transcript.redacted()
+        } else {
+            transcript
+        };
+        let rendered = exporter.export(&transcript)?;
+
+        match output {
+            Some(path) => std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?,
+            None => {
+                use std::io::Write;
+                std::io::stdout().write_all(&rendered)?;
+            }
+        }
+        Ok(())
+    }
+}
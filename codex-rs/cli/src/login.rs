@@ -210,7 +210,10 @@ async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config
     };
 
     match Config::load_with_cli_overrides(cli_overrides).await {
-        Ok(config) => config,
+        Ok(config) => {
+            codex_core::default_client::set_network_config(config.network.clone());
+            config
+        }
         Err(e) => {
             eprintln!("Error loading configuration: {e}");
             std::process::exit(1);
@@ -1,8 +1,7 @@
 use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
-use clap_complete::Shell;
-use clap_complete::generate;
+use clap_complete::env::CompleteEnv;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
@@ -22,6 +21,7 @@ use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
+use codex_serve::ServeCli;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
 use codex_tui::update_action::UpdateAction;
@@ -30,11 +30,33 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod apply_patch_cmd;
+mod approvals_cmd;
+mod commit_cmd;
+mod completion_cmd;
+mod config_cmd;
+mod doctor_cmd;
+mod export_cmd;
 mod mcp_cmd;
+mod replay_cmd;
+mod steering_cmd;
+mod trust_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::approvals_cmd::ApprovalsCli;
+use crate::commit_cmd::CommitCli;
+use crate::completion_cmd::COMPLETE_ENV_VAR;
+use crate::completion_cmd::CompletionCommand;
+use crate::completion_cmd::print_completion;
+use crate::completion_cmd::with_dynamic_completers;
+use crate::config_cmd::ConfigCli;
+use crate::doctor_cmd::DoctorCli;
+use crate::export_cmd::ExportCli;
 use crate::mcp_cmd::McpCli;
+use crate::replay_cmd::ReplayCli;
+use crate::steering_cmd::SteeringCli;
+use crate::trust_cmd::TrustCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -83,6 +105,9 @@ enum Subcommand {
     /// Run a code review non-interactively.
     Review(ReviewArgs),
 
+    /// Write a commit message for the staged changes with the model and commit.
+    Commit(CommitCli),
+
     /// Manage login.
     Login(LoginCommand),
 
@@ -98,7 +123,12 @@ enum Subcommand {
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
-    /// Generate shell completion scripts.
+    /// [experimental] Serve a local HTTP API for driving Codex sessions.
+    Serve(ServeCli),
+
+    /// Print a shell completion script for bash, zsh, fish, elvish, or
+    /// PowerShell.
+    #[clap(name = "completions", alias = "completion")]
     Completion(CompletionCommand),
 
     /// Run commands within a Codex-provided sandbox.
@@ -109,13 +139,17 @@ enum Subcommand {
     #[clap(hide = true)]
     Execpolicy(ExecpolicyCommand),
 
-    /// Apply the latest diff produced by Codex agent as a `git apply` to your local working tree.
+    /// Apply the latest diff produced by Codex agent as a `git apply` to your local working tree,
+    /// or apply a patch file with the agent's patch engine.
     #[clap(visible_alias = "a")]
     Apply(ApplyCommand),
 
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Watch a session running in another Codex TUI, live and without sending input.
+    Attach(AttachCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -130,13 +164,27 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
-}
 
-#[derive(Debug, Parser)]
-struct CompletionCommand {
-    /// Shell to generate completions for
-    #[clap(value_enum, default_value_t = Shell::Bash)]
-    shell: Shell,
+    /// Get, set, and list values in config.toml.
+    Config(ConfigCli),
+
+    /// [experimental] Inspect and snapshot injected steering files.
+    Steering(SteeringCli),
+
+    /// Diagnose connectivity and configuration problems.
+    Doctor(DoctorCli),
+
+    /// Render a recorded session as Markdown, HTML, JSON, or a custom format.
+    Export(ExportCli),
+
+    /// Step through the model requests of a recorded session without calling a model.
+    Replay(ReplayCli),
+
+    /// Record whether Codex trusts a project and how it runs there.
+    Trust(TrustCli),
+
+    /// Manage the commands Codex may run without asking in a project.
+    Approvals(ApprovalsCli),
 }
 
 #[derive(Debug, Parser)]
@@ -158,6 +206,18 @@ struct ResumeCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct AttachCommand {
+    /// Id of the running session, as shown by `/status`.
+    #[arg(value_name = "SESSION_ID")]
+    session_id: String,
+
+    /// Watch without being able to send input. Required: attaching with
+    /// input is not supported.
+    #[arg(long = "read-only", required = true)]
+    read_only: bool,
+}
+
 #[derive(Debug, Parser)]
 struct SandboxArgs {
     #[command(subcommand)]
@@ -426,6 +486,12 @@ fn pre_main_hardening() {
 }
 
 fn main() -> anyhow::Result<()> {
+    // Answers the completion requests of the script from `codex completions`
+    // and exits; does nothing otherwise.
+    CompleteEnv::with_factory(completion_cli)
+        .var(COMPLETE_ENV_VAR)
+        .complete();
+
     arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
         cli_main(codex_linux_sandbox_exe).await?;
         Ok(())
@@ -469,14 +535,69 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
         }
+        Some(Subcommand::Commit(mut commit_cli)) => {
+            prepend_config_flags(
+                &mut commit_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            commit_cli.run()?;
+        }
         Some(Subcommand::McpServer) => {
             codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
+        Some(Subcommand::Serve(mut serve_cli)) => {
+            prepend_config_flags(
+                &mut serve_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_serve::run_main(serve_cli, codex_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::Mcp(mut mcp_cli)) => {
             // Propagate any root-level config overrides (e.g. `-c key=value`).
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Steering(mut steering_cli)) => {
+            prepend_config_flags(
+                &mut steering_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            steering_cli.run(codex_linux_sandbox_exe).await?;
+        }
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
+        Some(Subcommand::Doctor(mut doctor_cli)) => {
+            prepend_config_flags(
+                &mut doctor_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            doctor_cli.run().await?;
+        }
+        Some(Subcommand::Export(mut export_cli)) => {
+            prepend_config_flags(
+                &mut export_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            export_cli.run().await?;
+        }
+        Some(Subcommand::Replay(mut replay_cli)) => {
+            prepend_config_flags(
+                &mut replay_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            replay_cli.run().await?;
+        }
+        Some(Subcommand::Trust(trust_cli)) => {
+            trust_cli.run()?;
+        }
+        Some(Subcommand::Approvals(approvals_cli)) => {
+            approvals_cli.run()?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
@@ -508,6 +629,15 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Attach(AttachCommand {
+            session_id,
+            read_only: _,
+        })) => {
+            prepend_config_flags(&mut interactive.config_overrides, root_config_overrides);
+            interactive.attach_session_id = Some(session_id);
+            // Only the default TUI can watch another session.
+            codex_tui::run_main(interactive, codex_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -547,7 +677,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             run_logout(logout_cli.config_overrides).await;
         }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli)?;
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -595,11 +725,15 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
-            prepend_config_flags(
-                &mut apply_cli.config_overrides,
-                root_config_overrides.clone(),
-            );
-            run_apply_command(apply_cli, None).await?;
+            if apply_patch_cmd::is_patch_arg(&apply_cli.task_id) {
+                apply_patch_cmd::run_apply_patch(&apply_cli.task_id, apply_cli.check)?;
+            } else {
+                prepend_config_flags(
+                    &mut apply_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                run_apply_command(apply_cli, None).await?;
+            }
         }
         Some(Subcommand::ResponsesApiProxy(args)) => {
             tokio::task::spawn_blocking(move || codex_responses_api_proxy::run_main(args))
@@ -757,6 +891,7 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
     if !resume_cli.add_dir.is_empty() {
         interactive.add_dir.extend(resume_cli.add_dir);
     }
+    interactive.mcp_servers.extend(resume_cli.mcp_servers);
     if let Some(prompt) = resume_cli.prompt {
         interactive.prompt = Some(prompt);
     }
@@ -767,10 +902,8 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
         .extend(resume_cli.config_overrides.raw_overrides);
 }
 
-fn print_completion(cmd: CompletionCommand) {
-    let mut app = MultitoolCli::command();
-    let name = "codex";
-    generate(cmd.shell, &mut app, name, &mut std::io::stdout());
+fn completion_cli() -> clap::Command {
+    with_dynamic_completers(MultitoolCli::command())
 }
 
 #[cfg(test)]
@@ -868,6 +1001,23 @@ mod tests {
         assert_eq!(interactive.resume_session_id, None);
     }
 
+    #[test]
+    fn attach_requires_read_only() {
+        let cli =
+            MultitoolCli::try_parse_from(["codex", "attach", "abc", "--read-only"]).expect("parse");
+        let Some(Subcommand::Attach(AttachCommand {
+            session_id,
+            read_only,
+        })) = cli.subcommand
+        else {
+            panic!("expected attach");
+        };
+        assert_eq!(session_id, "abc");
+        assert!(read_only);
+
+        assert!(MultitoolCli::try_parse_from(["codex", "attach", "abc"]).is_err());
+    }
+
     #[test]
     fn resume_picker_logic_none_and_not_last() {
         let interactive = finalize_from_args(["codex", "resume"].as_ref());
@@ -0,0 +1,343 @@
+//! `codex replay SESSION`: step through the model requests of a recorded
+//! session without calling a model.
+//!
+//! Each request is printed with the model and policies it ran with, the
+//! conversation it was sent with, and what was recorded before the next one:
+//! the model's reasoning, messages, and tool calls, then the tool outputs. By
+//! default only the input items that are new since the previous request are
+//! shown, so the log reads as the session unfolded.
+
+use std::io::BufRead;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_common::summarize_sandbox_policy;
+use codex_core::config::Config;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::replay::ModelRequest;
+use codex_core::replay::load_model_requests;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+
+/// Step through a recorded session's model requests without calling a model.
+#[derive(Debug, clap::Parser)]
+pub struct ReplayCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id, or the path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Stop after this point; `turn:5` stops after the fifth model request.
+    #[arg(long, value_name = "turn:N", value_parser = parse_until)]
+    pub until: Option<usize>,
+
+    /// Print the whole input of every request, not only the items that are
+    /// new since the previous one.
+    #[arg(long = "full-context", default_value_t = false)]
+    pub full_context: bool,
+
+    /// Lines printed per item; longer items are cut. 0 prints items in full.
+    #[arg(long = "max-lines", value_name = "N", default_value_t = 20)]
+    pub max_lines: usize,
+
+    /// Wait for Enter before each request; `q` stops.
+    #[arg(long, default_value_t = false)]
+    pub step: bool,
+}
+
+/// Parses `turn:N`, with N counting model requests from 1.
+fn parse_until(value: &str) -> Result<usize, String> {
+    let Some(turn) = value.strip_prefix("turn:") else {
+        return Err(format!("expected turn:N, got `{value}`"));
+    };
+    match turn.parse::<usize>() {
+        Ok(turn) if turn > 0 => Ok(turn),
+        _ => Err(format!("expected a turn number of 1 or more, got `{turn}`")),
+    }
+}
+
+impl ReplayCli {
+    pub async fn run(self) -> Result<()> {
+        let ReplayCli {
+            config_overrides,
+            session,
+            until,
+            full_context,
+            max_lines,
+            step,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let rollout_path = {
+            let candidate = PathBuf::from(&session);
+            if candidate.is_file() {
+                candidate
+            } else {
+                find_conversation_path_by_id_str(&config.codex_home, &session)
+                    .await?
+                    .with_context(|| format!("no recorded session found for `{session}`"))?
+            }
+        };
+        let requests = load_model_requests(&rollout_path)
+            .await
+            .with_context(|| format!("failed to read {}", rollout_path.display()))?;
+        if requests.is_empty() {
+            anyhow::bail!("{} has no model requests", rollout_path.display());
+        }
+
+        let total = requests.len();
+        let last = until.map_or(total, |until| until.min(total));
+        let step = step && std::io::stdin().is_terminal();
+        let mut stdout = std::io::stdout().lock();
+        for request in &requests[..last] {
+            if step && request.number > 1 {
+                write!(
+                    stdout,
+                    "-- Enter for request {}, q to stop -- ",
+                    request.number
+                )?;
+                stdout.flush()?;
+                let mut answer = String::new();
+                std::io::stdin().lock().read_line(&mut answer)?;
+                if answer.trim().eq_ignore_ascii_case("q") {
+                    break;
+                }
+            }
+            stdout.write_all(render_request(request, total, full_context, max_lines).as_bytes())?;
+        }
+        if last < total {
+            writeln!(
+                stdout,
+                "Stopped after request {last} of {total}; {} more recorded.",
+                total - last
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn render_request(
+    request: &ModelRequest,
+    total: usize,
+    full_context: bool,
+    max_lines: usize,
+) -> String {
+    let context = &request.context;
+    let mut text = format!(
+        "=== Request {} of {total} · {} · {} · approval {} · sandbox {}\n",
+        request.number,
+        context.model,
+        context.cwd.display(),
+        context.approval_policy,
+        summarize_sandbox_policy(&context.sandbox_policy),
+    );
+
+    let shown = if full_context {
+        request.input.len()
+    } else {
+        request.new_input
+    };
+    text.push_str(&format!(
+        "Input: {} item(s), {} new\n",
+        request.input.len(),
+        request.new_input
+    ));
+    let earlier = request.input.len() - shown;
+    if earlier > 0 {
+        text.push_str(&format!(
+            "  ... {earlier} earlier item(s); --full-context shows them\n"
+        ));
+    }
+    for item in &request.input[earlier..] {
+        push_item(&mut text, item, max_lines);
+    }
+
+    text.push_str("Recorded:\n");
+    for item in &request.recorded {
+        push_item(&mut text, item, max_lines);
+    }
+    text.push('\n');
+    text
+}
+
+fn push_item(text: &mut String, item: &ResponseItem, max_lines: usize) {
+    let Some((label, body)) = describe_item(item) else {
+        return;
+    };
+    text.push_str(&format!("  [{label}]\n"));
+    let lines: Vec<&str> = body.lines().collect();
+    let limit = if max_lines == 0 {
+        lines.len()
+    } else {
+        max_lines.min(lines.len())
+    };
+    for line in &lines[..limit] {
+        text.push_str(&format!("    {line}\n"));
+    }
+    if limit < lines.len() {
+        text.push_str(&format!("    ... {} more line(s)\n", lines.len() - limit));
+    }
+}
+
+/// A label such as `user` or `shell call-1` and the text of `item`, or
+/// `None` for items that carry nothing to read.
+fn describe_item(item: &ResponseItem) -> Option<(String, String)> {
+    let described = match item {
+        ResponseItem::Message { role, content, .. } => {
+            let text = content
+                .iter()
+                .map(|content| match content {
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        text.clone()
+                    }
+                    ContentItem::InputImage { .. } => "<image>".to_string(),
+                    ContentItem::Refusal { refusal } => format!("<refusal> {refusal}"),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            (role.clone(), text)
+        }
+        ResponseItem::Reasoning { summary, .. } => {
+            let text = summary
+                .iter()
+                .map(|ReasoningItemReasoningSummary::SummaryText { text }| text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let text = if text.is_empty() {
+                "<no summary>".to_string()
+            } else {
+                text
+            };
+            ("reasoning".to_string(), text)
+        }
+        ResponseItem::LocalShellCall {
+            call_id,
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => (
+            format!("local_shell {}", call_id.as_deref().unwrap_or("-")),
+            exec.command.join(" "),
+        ),
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => (format!("{name} {call_id}"), arguments.clone()),
+        ResponseItem::CustomToolCall {
+            name,
+            input,
+            call_id,
+            ..
+        } => (format!("{name} {call_id}"), input.clone()),
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            (format!("output {call_id}"), output.content.clone())
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => {
+            (format!("output {call_id}"), output.clone())
+        }
+        ResponseItem::WebSearchCall { action, .. } => (
+            "web_search".to_string(),
+            serde_json::to_string(action).unwrap_or_default(),
+        ),
+        ResponseItem::Compaction { .. } => {
+            ("compaction".to_string(), "<encrypted summary>".to_string())
+        }
+        ResponseItem::GhostSnapshot { .. } | ResponseItem::Other => return None,
+    };
+    Some(described)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::config_types::ReasoningSummary;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::SandboxPolicy;
+    use codex_protocol::protocol::TurnContextItem;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_until_turn() {
+        assert_eq!(parse_until("turn:5"), Ok(5));
+        assert!(parse_until("turn:0").is_err());
+        assert!(parse_until("5").is_err());
+    }
+
+    #[test]
+    fn renders_new_input_and_recorded_items() {
+        let user = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list the files".to_string(),
+            }],
+        };
+        let call = ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: r#"{"command":["ls"]}"#.to_string(),
+            call_id: "call-1".to_string(),
+        };
+        let output = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: "Cargo.toml\nREADME.md\nsrc".to_string(),
+                ..Default::default()
+            },
+        };
+        let request = ModelRequest {
+            number: 2,
+            context: TurnContextItem {
+                cwd: PathBuf::from("/repo"),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::ReadOnly,
+                model: "gpt-5.1".to_string(),
+                effort: None,
+                summary: ReasoningSummary::Auto,
+            },
+            input: vec![user, call, output],
+            new_input: 2,
+            recorded: vec![ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "There are three entries.".to_string(),
+                }],
+            }],
+        };
+
+        assert_eq!(
+            render_request(&request, 3, false, 2),
+            "=== Request 2 of 3 · gpt-5.1 · /repo · approval never · sandbox read-only
+Input: 3 item(s), 2 new
+  ... 1 earlier item(s); --full-context shows them
+  [shell call-1]
+    {\"command\":[\"ls\"]}
+  [output call-1]
+    Cargo.toml
+    README.md
+    ... 1 more line(s)
+Recorded:
+  [assistant]
+    There are three entries.
+
+"
+        );
+    }
+}
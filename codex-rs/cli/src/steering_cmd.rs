@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_common::SandboxModeCliArg;
+use codex_core::config::Config;
+use codex_core::steering::learned_steering_path;
+use codex_core::steering::remember_prompt;
+use codex_core::steering::render_steering;
+use codex_exec::Cli as ExecCli;
+
+/// [experimental] Inspect the steering files Codex injects into sessions.
+#[derive(Debug, clap::Parser)]
+pub struct SteeringCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SteeringSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SteeringSubcommand {
+    /// Render the combined steering text for the current directory.
+    Snapshot(SnapshotArgs),
+
+    /// Rewrite a rule as an imperative and append it to the project's learned
+    /// steering file, skipping rules that are already covered.
+    Remember(RememberArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SnapshotArgs {
+    /// Write the rendered text to this file instead of printing it.
+    #[arg(long, value_name = "PATH", conflicts_with = "check")]
+    pub write: Option<PathBuf>,
+
+    /// Compare the rendered text against this file and exit non-zero if it
+    /// differs.
+    #[arg(long, value_name = "PATH")]
+    pub check: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct RememberArgs {
+    /// The rule to remember, in your own words.
+    #[arg(value_name = "RULE", required = true, num_args = 1..)]
+    pub rule: Vec<String>,
+}
+
+impl SteeringCli {
+    pub async fn run(self, codex_linux_sandbox_exe: Option<PathBuf>) -> Result<()> {
+        let SteeringCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            SteeringSubcommand::Snapshot(args) => run_snapshot(&config_overrides, args).await,
+            SteeringSubcommand::Remember(args) => {
+                run_remember(config_overrides, args, codex_linux_sandbox_exe).await
+            }
+        }
+    }
+}
+
+async fn run_snapshot(config_overrides: &CliConfigOverrides, args: SnapshotArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let snapshot = snapshot_text(&render_steering(&config));
+
+    if let Some(path) = args.write {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, &snapshot)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        println!("Wrote steering snapshot to {}", path.display());
+    } else if let Some(path) = args.check {
+        let expected = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if expected != snapshot {
+            bail!(
+                "steering snapshot {} is out of date; rerun `codex steering snapshot --write {}`",
+                path.display(),
+                path.display()
+            );
+        }
+        println!("Steering snapshot {} is up to date", path.display());
+    } else {
+        print!("{snapshot}");
+    }
+
+    Ok(())
+}
+
+/// Runs a one-shot `codex exec` turn that asks the model to normalize the rule
+/// and save it through the `remember_steering_rule` tool.
+async fn run_remember(
+    mut config_overrides: CliConfigOverrides,
+    args: RememberArgs,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> Result<()> {
+    let rule = args.rule.join(" ");
+    let rule = rule.trim();
+    if rule.is_empty() {
+        bail!("rule must not be empty");
+    }
+
+    // The tool that writes the learned file is only registered with the
+    // steering feature enabled.
+    config_overrides
+        .raw_overrides
+        .push("features.steering=true".to_string());
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
+    exec_cli.prompt = Some(remember_prompt(&config, Some(rule)));
+    exec_cli.skip_git_repo_check = true;
+    // The tool only writes where the sandbox allows it, and exec cannot ask
+    // for approval, so make the learned file's directory writable.
+    if let Some(dir) = learned_steering_path(&config).parent() {
+        exec_cli.sandbox_mode = Some(SandboxModeCliArg::WorkspaceWrite);
+        exec_cli.add_dir.push(dir.to_path_buf());
+    }
+    exec_cli.config_overrides = config_overrides;
+    codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await
+}
+
+/// Snapshot files end with a newline unless nothing is injected.
+fn snapshot_text(rendered: &str) -> String {
+    if rendered.is_empty() {
+        String::new()
+    } else {
+        format!("{rendered}\n")
+    }
+}
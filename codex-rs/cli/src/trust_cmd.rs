@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::ApprovalModeCliArg;
+use codex_common::SandboxModeCliArg;
+use codex_core::config::find_codex_home;
+use codex_core::config::trust_store::ProjectTrust;
+use codex_core::config::trust_store::TRUSTED_PROJECTS_FILE;
+use codex_core::config::trust_store::TrustStore;
+use codex_core::config::trust_store::trust_key;
+use codex_protocol::config_types::TrustLevel;
+use codex_protocol::config_types::Verbosity;
+use codex_protocol::openai_models::ReasoningEffort;
+
+/// Record per-project trust decisions in `$CODEX_HOME/trusted_projects.json`.
+#[derive(Debug, clap::Parser)]
+pub struct TrustCli {
+    #[command(subcommand)]
+    pub subcommand: TrustSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TrustSubcommand {
+    /// Record a decision for a project, replacing any earlier one.
+    Set(SetArgs),
+
+    /// List the recorded decisions.
+    List,
+
+    /// Forget the decision for a project, so Codex asks again.
+    Forget(ProjectArg),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ProjectArg {
+    /// The project directory. Defaults to the current directory; inside a git
+    /// repository the decision covers the whole repository.
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    #[clap(flatten)]
+    pub project: ProjectArg,
+
+    /// Mark the project untrusted instead of trusted.
+    #[arg(long)]
+    pub untrusted: bool,
+
+    /// Approval policy to use when config.toml does not set one.
+    #[arg(long, value_enum)]
+    pub approval_policy: Option<ApprovalModeCliArg>,
+
+    /// Sandbox mode to use when config.toml does not set one.
+    #[arg(long, value_enum)]
+    pub sandbox: Option<SandboxModeCliArg>,
+
+    /// Model to use in this project unless a profile or `--model` picks one.
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Reasoning effort to use in this project unless a profile sets one.
+    #[arg(long, value_name = "EFFORT", value_parser = parse_reasoning_effort)]
+    pub reasoning_effort: Option<ReasoningEffort>,
+
+    /// Verbosity to use in this project unless a profile sets one.
+    #[arg(long, value_name = "VERBOSITY", value_parser = parse_verbosity)]
+    pub verbosity: Option<Verbosity>,
+
+    /// Load the project's `.codex/config.toml` and `.codex/steering`. Defaults
+    /// to on for trusted projects and off for untrusted ones.
+    #[arg(long, value_name = "BOOL", action = clap::ArgAction::Set)]
+    pub project_files: Option<bool>,
+}
+
+impl TrustCli {
+    pub fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let mut store = TrustStore::load(&codex_home)
+            .with_context(|| format!("failed to read {TRUSTED_PROJECTS_FILE}"))?;
+
+        match self.subcommand {
+            TrustSubcommand::Set(args) => {
+                let project = project_key(args.project.path)?;
+                let trust_level = if args.untrusted {
+                    TrustLevel::Untrusted
+                } else {
+                    TrustLevel::Trusted
+                };
+                let mut trust = ProjectTrust::new(trust_level);
+                trust.approval_policy = args.approval_policy.map(Into::into);
+                trust.sandbox_mode = args.sandbox.map(Into::into);
+                trust.model = args.model;
+                trust.model_reasoning_effort = args.reasoning_effort;
+                trust.model_verbosity = args.verbosity;
+                if let Some(project_files) = args.project_files {
+                    trust.load_project_files = project_files;
+                }
+                println!("{}", describe(&project, &trust));
+                store.set(project, trust);
+                store.save(&codex_home)?;
+            }
+            TrustSubcommand::List => {
+                if store.projects.is_empty() {
+                    println!("No trust decisions recorded.");
+                }
+                for (project, trust) in &store.projects {
+                    println!("{}", describe(project, trust));
+                }
+            }
+            TrustSubcommand::Forget(args) => {
+                let project = project_key(args.path)?;
+                if store.remove(&project).is_some() {
+                    store.save(&codex_home)?;
+                    println!("Forgot the decision for {}.", project.display());
+                } else {
+                    println!("No decision recorded for {}.", project.display());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn project_key(path: Option<PathBuf>) -> Result<PathBuf> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir().context("failed to resolve the current directory")?,
+    };
+    let path = std::path::absolute(&path)
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    Ok(trust_key(&path))
+}
+
+fn parse_reasoning_effort(value: &str) -> Result<ReasoningEffort, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| "expected one of none, minimal, low, medium, high, xhigh".to_string())
+}
+
+fn parse_verbosity(value: &str) -> Result<Verbosity, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| "expected one of low, medium, high".to_string())
+}
+
+fn describe(project: &Path, trust: &ProjectTrust) -> String {
+    let mut line = format!("{}: {}", project.display(), trust.trust_level);
+    if let Some(approval_policy) = trust.approval_policy {
+        line.push_str(&format!(", approval policy {approval_policy}"));
+    }
+    if let Some(sandbox_mode) = trust.sandbox_mode {
+        line.push_str(&format!(", sandbox {sandbox_mode}"));
+    }
+    if let Some(model) = &trust.model {
+        line.push_str(&format!(", model {model}"));
+    }
+    if let Some(effort) = trust.model_reasoning_effort {
+        line.push_str(&format!(", reasoning effort {effort}"));
+    }
+    if let Some(verbosity) = trust.model_verbosity {
+        line.push_str(&format!(", verbosity {verbosity}"));
+    }
+    let project_files = if trust.load_project_files {
+        "loaded"
+    } else {
+        "ignored"
+    };
+    line.push_str(&format!(", .codex/ files {project_files}"));
+    line
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path, cwd: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home).current_dir(cwd);
+    Ok(cmd)
+}
+
+#[test]
+fn check_then_apply_a_patch_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let repo = TempDir::new()?;
+    std::fs::write(repo.path().join("greeting.txt"), "hello\nworld\n")?;
+    std::fs::write(
+        repo.path().join("fix.patch"),
+        "*** Begin Patch\n*** Update File: greeting.txt\n@@\n hello\n-world\n+codex\n*** Add File: notes/todo.txt\n+ship it\n*** End Patch\n",
+    )?;
+
+    codex_command(codex_home.path(), repo.path())?
+        .args(["apply", "fix.patch", "--check"])
+        .assert()
+        .success()
+        .stdout("ok      M greeting.txt\nok      A notes/todo.txt\nAll 2 hunk(s) apply cleanly; no files were changed.\n");
+    assert!(!repo.path().join("notes/todo.txt").exists());
+
+    codex_command(codex_home.path(), repo.path())?
+        .args(["apply", "fix.patch"])
+        .assert()
+        .success()
+        .stdout(contains("Applied 2 hunk(s)."));
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("greeting.txt"))?,
+        "hello\ncodex\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("notes/todo.txt"))?,
+        "ship it\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn applies_nothing_when_a_hunk_fails() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let repo = TempDir::new()?;
+    std::fs::write(repo.path().join("greeting.txt"), "hello\nworld\n")?;
+    std::fs::write(
+        repo.path().join("fix.patch"),
+        "*** Begin Patch\n*** Add File: new.txt\n+new\n*** Update File: greeting.txt\n@@\n-goodbye\n+codex\n*** Delete File: ../outside.txt\n*** End Patch\n",
+    )?;
+
+    codex_command(codex_home.path(), repo.path())?
+        .args(["apply", "fix.patch"])
+        .assert()
+        .failure()
+        .stdout(contains(
+            "failed  M greeting.txt: Failed to find expected lines",
+        ))
+        .stdout(contains("is outside the working directory"))
+        .stderr(contains(
+            "2 of 3 hunk(s) do not apply; no files were changed",
+        ));
+    assert!(!repo.path().join("new.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("greeting.txt"))?,
+        "hello\nworld\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn add_list_and_remove_rules() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let project_path = project.path().to_str().expect("utf-8 path");
+
+    codex_command(codex_home.path())?
+        .args([
+            "approvals",
+            "add",
+            "--project",
+            project_path,
+            "--",
+            "cargo",
+            "test",
+        ])
+        .assert()
+        .success()
+        .stdout(format!("Added {project_path}: prefix: cargo test\n"));
+    codex_command(codex_home.path())?
+        .args([
+            "approvals",
+            "add",
+            "--project",
+            project_path,
+            "--pattern",
+            "--",
+            "npm",
+            "run",
+            "test:*",
+        ])
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["approvals", "list"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{project_path}:\n  1. prefix: cargo test\n  2. pattern: npm run test:*\n"
+        ));
+    let store: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        codex_home.path().join("trusted_projects.json"),
+    )?)?;
+    assert_eq!(
+        store["approval_rules"][project_path][0],
+        serde_json::json!({ "match": "prefix", "command": ["cargo", "test"] })
+    );
+
+    codex_command(codex_home.path())?
+        .args(["approvals", "remove", "3", "--project", project_path])
+        .assert()
+        .failure()
+        .stderr(contains("no approval rule 3"));
+    codex_command(codex_home.path())?
+        .args(["approvals", "remove", "1", "--project", project_path])
+        .assert()
+        .success()
+        .stdout(format!("Removed {project_path}: prefix: cargo test\n"));
+    codex_command(codex_home.path())?
+        .args(["approvals", "remove", "1", "--project", project_path])
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["approvals", "list"])
+        .assert()
+        .success()
+        .stdout("No approval rules recorded.\n");
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn prints_a_script_that_calls_back_into_codex() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["completions", "fish"])
+        .assert()
+        .success()
+        .stdout(contains("COMPLETE=fish codex"));
+    Ok(())
+}
+
+#[test]
+fn completes_profiles_from_config_toml() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[profiles.fast]\nmodel = \"gpt-5-mini\"\n\n[profiles.deep]\nmodel_reasoning_effort = \"high\"\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .env("COMPLETE", "fish")
+        .args(["--", "codex", "exec", "--profile", "f"])
+        .assert()
+        .success()
+        .stdout(contains("fast"))
+        .stdout(contains("deep").not());
+    Ok(())
+}
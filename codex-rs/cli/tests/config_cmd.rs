@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn set_get_unset_round_trip_preserves_comments() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let config_path = codex_home.path().join("config.toml");
+    std::fs::write(&config_path, "# my settings\nmodel = \"o3\"\n")?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "set", "features.web_search_request", "true"])
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["config", "get", "features.web_search_request"])
+        .assert()
+        .success()
+        .stdout("true\n");
+    codex_command(codex_home.path())?
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout("features.web_search_request = true\nmodel = \"o3\"\n");
+
+    codex_command(codex_home.path())?
+        .args(["config", "unset", "features.web_search_request"])
+        .assert()
+        .success();
+    let contents = std::fs::read_to_string(&config_path)?;
+    assert!(
+        contents.starts_with("# my settings\nmodel = \"o3\"\n"),
+        "{contents}"
+    );
+
+    codex_command(codex_home.path())?
+        .args(["config", "get", "features.web_search_request"])
+        .assert()
+        .failure()
+        .stderr(contains("is not set"));
+    Ok(())
+}
+
+#[test]
+fn set_rejects_values_that_do_not_match_the_schema() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "set", "approval_policy", "sometimes"])
+        .assert()
+        .failure()
+        .stderr(contains("Invalid configuration"));
+    let contents =
+        std::fs::read_to_string(codex_home.path().join("config.toml")).unwrap_or_default();
+    assert_eq!(contents, "");
+    Ok(())
+}
+
+#[test]
+fn validate_reports_errors_and_deprecations() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let config_path = codex_home.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "approval_policy = \"sometimes\"\nexperimental_use_rmcp_client = true\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stdout(contains(
+            "error: `approval_policy`: invalid value \"sometimes\"",
+        ))
+        .stdout(contains(
+            "warning: `experimental_use_rmcp_client`: deprecated; use `features.rmcp_client` instead",
+        ))
+        .stderr(contains("1 error(s)"));
+
+    std::fs::write(&config_path, "model = \"o3\"\n")?;
+    codex_command(codex_home.path())?
+        .args([
+            "config",
+            "validate",
+            config_path.to_str().expect("utf-8 path"),
+        ])
+        .assert()
+        .success()
+        .stdout(contains("is valid"));
+    Ok(())
+}
+
+#[test]
+fn validate_prints_json_schema() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["config", "validate", "--schema", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(schema["title"], "Codex config.toml");
+    assert_eq!(
+        schema["properties"]["model"]["type"],
+        serde_json::Value::String("string".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn migrate_rewrites_deprecated_keys() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let config_path = codex_home.path().join("config.toml");
+    let original = "# my settings\nexperimental_use_rmcp_client = true\n";
+    std::fs::write(&config_path, original)?;
+
+    codex_command(codex_home.path())?
+        .args(["config", "migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Renamed `experimental_use_rmcp_client` to `features.rmcp_client`.",
+        ));
+    assert_eq!(std::fs::read_to_string(&config_path)?, original);
+
+    codex_command(codex_home.path())?
+        .args(["config", "migrate"])
+        .assert()
+        .success()
+        .stdout(contains("Updated"));
+    codex_command(codex_home.path())?
+        .args(["config", "get", "features.rmcp_client"])
+        .assert()
+        .success()
+        .stdout("true\n");
+    let contents = std::fs::read_to_string(&config_path)?;
+    assert!(contents.starts_with("# my settings\n"), "{contents}");
+    assert!(
+        !contents.contains("experimental_use_rmcp_client"),
+        "{contents}"
+    );
+
+    codex_command(codex_home.path())?
+        .args(["config", "migrate"])
+        .assert()
+        .success()
+        .stdout(contains("has no deprecated keys"));
+    Ok(())
+}
+
+#[test]
+fn explain_reports_the_winning_layer() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    let config_path = codex_home.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "model = \"gpt-5\"\n[profiles.fast]\nmodel = \"gpt-5.1-codex-mini\"\n",
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .current_dir(cwd.path())
+        .args(["config", "-c", "model=\"o3\"", "explain", "model", "--json"])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["value"], "o3");
+    assert_eq!(report["source"], "-c/--config flag");
+    assert_eq!(report["sources"][1]["value"], "gpt-5");
+
+    codex_command(codex_home.path())?
+        .current_dir(cwd.path())
+        .args(["config", "explain", "model", "--profile", "fast"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "model = \"gpt-5.1-codex-mini\"\n  from profile `fast` in user config",
+        ));
+
+    codex_command(codex_home.path())?
+        .current_dir(cwd.path())
+        .args(["config", "explain", "model_verbosity"])
+        .assert()
+        .success()
+        .stdout("`model_verbosity` is not set; Codex uses its default.\n");
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn status_of<'a>(report: &'a Value, name: &str) -> Option<&'a str> {
+    report["checks"]
+        .as_array()?
+        .iter()
+        .find(|check| check["name"] == name)?["status"]
+        .as_str()
+}
+
+#[test]
+fn json_report_lists_each_check_with_its_fix() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model_provider = "local"
+
+[model_providers.local]
+name = "Local"
+base_url = "http://127.0.0.1:9/v1"
+"#,
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .current_dir(cwd.path())
+        .args(["doctor", "--json"])
+        .output()?;
+    assert!(!output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(status_of(&report, "config file"), Some("ok"));
+    assert_eq!(status_of(&report, "credentials"), Some("ok"));
+    assert_eq!(status_of(&report, "provider local"), Some("fail"));
+    assert_eq!(status_of(&report, "mcp"), Some("skip"));
+
+    let provider = report["checks"]
+        .as_array()
+        .and_then(|checks| {
+            checks
+                .iter()
+                .find(|check| check["name"] == "provider local")
+        })
+        .expect("provider check");
+    assert!(provider["fix"].as_str().is_some(), "{provider}");
+    Ok(())
+}
+
+#[test]
+fn invalid_config_is_reported_as_a_failure() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "approval_policy = \"sometimes\"\n",
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["doctor", "--json"])
+        .output()?;
+    assert!(!output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(status_of(&report, "config file"), Some("fail"));
+    assert_eq!(status_of(&report, "config"), Some("fail"));
+    Ok(())
+}
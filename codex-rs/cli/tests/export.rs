@@ -0,0 +1,108 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<PathBuf> {
+    let sessions = codex_home.join("sessions/2025/01/01");
+    std::fs::create_dir_all(&sessions)?;
+    let path = sessions.join(format!("rollout-2025-01-01T00-00-00-{SESSION_ID}.jsonl"));
+    let lines = [
+        format!(
+            r#"{{"timestamp":"2025-01-01T00:00:00.000Z","type":"session_meta","payload":{{"id":"{SESSION_ID}","timestamp":"2025-01-01T00:00:00Z","instructions":null,"cwd":"/work","originator":"test","cli_version":"test","model_provider":"test-provider"}}}}"#
+        ),
+        r#"{"timestamp":"2025-01-01T00:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"What is in here?"}}"#.to_string(),
+        r#"{"timestamp":"2025-01-01T00:00:02.000Z","type":"event_msg","payload":{"type":"agent_message","message":"A <small> project."}}"#.to_string(),
+    ];
+    std::fs::write(&path, lines.join("\n") + "\n")?;
+    Ok(path)
+}
+
+#[test]
+fn exports_a_session_by_id_as_markdown() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["export", SESSION_ID])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        format!(
+            "# Codex session {SESSION_ID}\n\n- Started: 2025-01-01T00:00:00Z\n- Directory: `/work`\n\
+             \n## User\n\nWhat is in here?\n\
+             \n## Codex\n\nA <small> project.\n"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn exports_a_rollout_path_to_a_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+    let out = codex_home.path().join("session.html");
+
+    codex_command(codex_home.path())?
+        .arg("export")
+        .arg(&rollout)
+        .args(["--format", "html", "-o"])
+        .arg(&out)
+        .assert()
+        .success();
+
+    let html = std::fs::read_to_string(&out)?;
+    assert!(html.contains("A &lt;small&gt; project."), "{html}");
+    Ok(())
+}
+
+#[test]
+fn unknown_formats_list_configured_exporters() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[exporters.confluence]\ncommand = [\"confluence-render\"]\n",
+    )?;
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID, "--format", "pdf"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "unknown export format `pdf`; available formats: markdown, html, json, confluence",
+        ));
+    Ok(())
+}
+
+#[test]
+fn redacted_exports_drop_the_session_directory() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["export", SESSION_ID, "--redact"])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        format!(
+            "# Codex session {SESSION_ID}\n\n- Started: 2025-01-01T00:00:00Z\n\
+             \n## User\n\nWhat is in here?\n\
+             \n## Codex\n\nA <small> project.\n"
+        )
+    );
+    Ok(())
+}
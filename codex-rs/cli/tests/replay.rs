@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<PathBuf> {
+    let sessions = codex_home.join("sessions/2025/01/01");
+    std::fs::create_dir_all(&sessions)?;
+    let path = sessions.join(format!("rollout-2025-01-01T00-00-00-{SESSION_ID}.jsonl"));
+    let turn_context = r#"{"timestamp":"2025-01-01T00:00:01.000Z","type":"turn_context","payload":{"cwd":"/work","approval_policy":"never","sandbox_policy":{"type":"read-only"},"model":"gpt-5.1","summary":"auto"}}"#;
+    let lines = [
+        format!(
+            r#"{{"timestamp":"2025-01-01T00:00:00.000Z","type":"session_meta","payload":{{"id":"{SESSION_ID}","timestamp":"2025-01-01T00:00:00Z","instructions":null,"cwd":"/work","originator":"test","cli_version":"test","model_provider":"test-provider"}}}}"#
+        ),
+        r#"{"timestamp":"2025-01-01T00:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"What is in here?"}]}}"#.to_string(),
+        turn_context.to_string(),
+        r#"{"timestamp":"2025-01-01T00:00:02.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"ls\"]}","call_id":"call-1"}}"#.to_string(),
+        r#"{"timestamp":"2025-01-01T00:00:03.000Z","type":"response_item","payload":{"type":"function_call_output","call_id":"call-1","output":"Cargo.toml"}}"#.to_string(),
+        turn_context.to_string(),
+        r#"{"timestamp":"2025-01-01T00:00:04.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"A Rust project."}]}}"#.to_string(),
+    ];
+    std::fs::write(&path, lines.join("\n") + "\n")?;
+    Ok(path)
+}
+
+#[test]
+fn replays_the_requests_of_a_session_until_a_turn() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["replay", SESSION_ID, "--until", "turn:1"])
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "=== Request 1 of 2 · gpt-5.1 · /work · approval never · sandbox read-only
+Input: 1 item(s), 1 new
+  [user]
+    What is in here?
+Recorded:
+  [shell call-1]
+    {\"command\":[\"ls\"]}
+  [output call-1]
+    Cargo.toml
+
+Stopped after request 1 of 2; 1 more recorded.
+"
+    );
+    Ok(())
+}
+
+#[test]
+fn replays_a_rollout_path_with_full_context() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+
+    codex_command(codex_home.path())?
+        .arg("replay")
+        .arg(&rollout)
+        .arg("--full-context")
+        .assert()
+        .success()
+        .stdout(contains(
+            "=== Request 2 of 2 · gpt-5.1 · /work · approval never · sandbox read-only\nInput: 3 item(s), 2 new\n  [user]\n    What is in here?\n",
+        ))
+        .stdout(contains("  [assistant]\n    A Rust project.\n"));
+    Ok(())
+}
+
+#[test]
+fn rejects_an_until_that_is_not_a_turn() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    codex_command(codex_home.path())?
+        .args(["replay", SESSION_ID, "--until", "5"])
+        .assert()
+        .failure()
+        .stderr(contains("expected turn:N"));
+    Ok(())
+}
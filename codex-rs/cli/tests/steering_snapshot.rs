@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path, cwd: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home).current_dir(cwd);
+    Ok(cmd)
+}
+
+fn enable_steering(codex_home: &Path) -> Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        "[features]\nsteering = true\n",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn snapshot_write_then_check_detects_changes() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let steering_dir = codex_home.path().join("steering");
+    std::fs::create_dir_all(&steering_dir)?;
+    std::fs::write(steering_dir.join("a.md"), "Prefer small commits.\n")?;
+    std::fs::write(steering_dir.join("b.md"), "Run tests before pushing.")?;
+    enable_steering(codex_home.path())?;
+
+    codex_command(codex_home.path(), project.path())?
+        .args(["steering", "snapshot", "--write", "tests/steering.snap"])
+        .assert()
+        .success();
+
+    let snapshot = std::fs::read_to_string(project.path().join("tests/steering.snap"))?;
+    assert_eq!(
+        snapshot,
+        "Prefer small commits.\n\nRun tests before pushing.\n"
+    );
+
+    codex_command(codex_home.path(), project.path())?
+        .args(["steering", "snapshot", "--check", "tests/steering.snap"])
+        .assert()
+        .success();
+
+    std::fs::write(steering_dir.join("b.md"), "Never push without review.")?;
+    codex_command(codex_home.path(), project.path())?
+        .args(["steering", "snapshot", "--check", "tests/steering.snap"])
+        .assert()
+        .failure()
+        .stderr(contains("is out of date"));
+
+    Ok(())
+}
+
+#[test]
+fn snapshot_is_empty_when_steering_is_disabled() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let steering_dir = codex_home.path().join("steering");
+    std::fs::create_dir_all(&steering_dir)?;
+    std::fs::write(steering_dir.join("a.md"), "Prefer small commits.\n")?;
+
+    codex_command(codex_home.path(), project.path())?
+        .args(["steering", "snapshot", "--write", "tests/steering.snap"])
+        .assert()
+        .success();
+
+    let snapshot = std::fs::read_to_string(project.path().join("tests/steering.snap"))?;
+    assert_eq!(snapshot, "");
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("codex")?;
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn set_list_and_forget_decisions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let project_path = project.path().to_str().expect("utf-8 path");
+
+    codex_command(codex_home.path())?
+        .args([
+            "trust",
+            "set",
+            project_path,
+            "--approval-policy",
+            "on-request",
+            "--project-files",
+            "false",
+        ])
+        .assert()
+        .success();
+    codex_command(codex_home.path())?
+        .args(["trust", "list"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{project_path}: trusted, approval policy on-request, .codex/ files ignored\n"
+        ));
+    let store: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        codex_home.path().join("trusted_projects.json"),
+    )?)?;
+    assert_eq!(
+        store["projects"][project_path]["approval_policy"],
+        "on-request"
+    );
+
+    codex_command(codex_home.path())?
+        .args(["trust", "forget", project_path])
+        .assert()
+        .success()
+        .stdout(contains("Forgot"));
+    codex_command(codex_home.path())?
+        .args(["trust", "list"])
+        .assert()
+        .success()
+        .stdout("No trust decisions recorded.\n");
+    Ok(())
+}
+
+#[test]
+fn set_pins_model_defaults() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+    let project_path = project.path().to_str().expect("utf-8 path");
+
+    codex_command(codex_home.path())?
+        .args([
+            "trust",
+            "set",
+            project_path,
+            "--model",
+            "gpt-5.1-codex-mini",
+            "--reasoning-effort",
+            "low",
+            "--verbosity",
+            "low",
+        ])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{project_path}: trusted, model gpt-5.1-codex-mini, reasoning effort low, verbosity low, .codex/ files loaded\n"
+        ));
+    let store: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        codex_home.path().join("trusted_projects.json"),
+    )?)?;
+    assert_eq!(
+        store["projects"][project_path]["model_reasoning_effort"],
+        "low"
+    );
+
+    codex_command(codex_home.path())?
+        .args([
+            "trust",
+            "set",
+            project_path,
+            "--reasoning-effort",
+            "extreme",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("expected one of"));
+    Ok(())
+}
@@ -159,7 +159,8 @@ impl<'a> ChatRequestBuilder<'a> {
                     for c in content {
                         match c {
                             ContentItem::InputText { text: t }
-                            | ContentItem::OutputText { text: t } => {
+                            | ContentItem::OutputText { text: t }
+                            | ContentItem::Refusal { refusal: t } => {
                                 text.push_str(t);
                                 items.push(json!({"type":"text","text": t}));
                             }
@@ -0,0 +1,159 @@
+//! Save the system clipboard to a file. Shared by the TUI's `/paste-as-file`
+//! and `codex exec --from-clipboard` so both name and place captures the same
+//! way.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Subdirectory of the session artifacts area that receives clipboard captures.
+pub const CLIPBOARD_ARTIFACTS_SUBDIR: &str = "clipboard";
+
+/// A clipboard capture written to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardCapture {
+    Image {
+        path: PathBuf,
+        width: u32,
+        height: u32,
+    },
+    Text {
+        path: PathBuf,
+        bytes: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum ClipboardCaptureError {
+    Unavailable(String),
+    NoContent(String),
+    EncodeFailed(String),
+    IoError(String),
+}
+
+impl std::fmt::Display for ClipboardCaptureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardCaptureError::Unavailable(msg) => write!(f, "clipboard unavailable: {msg}"),
+            ClipboardCaptureError::NoContent(msg) => {
+                write!(f, "no text or image on clipboard: {msg}")
+            }
+            ClipboardCaptureError::EncodeFailed(msg) => {
+                write!(f, "could not encode image: {msg}")
+            }
+            ClipboardCaptureError::IoError(msg) => write!(f, "io error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardCaptureError {}
+
+/// Save the clipboard into `dir`, preferring an image over text when both are
+/// present: images as `clipboard-<millis>.png`, text as `clipboard-<millis>.txt`.
+#[cfg(not(target_os = "android"))]
+pub fn capture_clipboard(dir: &Path) -> Result<ClipboardCapture, ClipboardCaptureError> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| ClipboardCaptureError::Unavailable(e.to_string()))?;
+    let stem = capture_stem();
+
+    if let Ok(image) = clipboard.get_image() {
+        let width = image.width as u32;
+        let height = image.height as u32;
+        let rgba = image::RgbaImage::from_raw(width, height, image.bytes.into_owned())
+            .ok_or_else(|| ClipboardCaptureError::EncodeFailed("invalid RGBA buffer".into()))?;
+        create_dir(dir)?;
+        let path = dir.join(format!("{stem}.png"));
+        image::DynamicImage::ImageRgba8(rgba)
+            .save_with_format(&path, image::ImageFormat::Png)
+            .map_err(|e| ClipboardCaptureError::EncodeFailed(e.to_string()))?;
+        return Ok(ClipboardCapture::Image {
+            path,
+            width,
+            height,
+        });
+    }
+
+    let text = clipboard
+        .get_text()
+        .map_err(|e| ClipboardCaptureError::NoContent(e.to_string()))?;
+    save_text(dir, &stem, &text)
+}
+
+#[cfg(target_os = "android")]
+pub fn capture_clipboard(_dir: &Path) -> Result<ClipboardCapture, ClipboardCaptureError> {
+    Err(ClipboardCaptureError::Unavailable(
+        "clipboard capture is unsupported on Android".into(),
+    ))
+}
+
+fn capture_stem() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    format!("clipboard-{millis}")
+}
+
+fn create_dir(dir: &Path) -> Result<(), ClipboardCaptureError> {
+    std::fs::create_dir_all(dir).map_err(|e| ClipboardCaptureError::IoError(e.to_string()))
+}
+
+fn save_text(
+    dir: &Path,
+    stem: &str,
+    text: &str,
+) -> Result<ClipboardCapture, ClipboardCaptureError> {
+    if text.trim().is_empty() {
+        return Err(ClipboardCaptureError::NoContent(
+            "clipboard text is empty".into(),
+        ));
+    }
+    create_dir(dir)?;
+    let path = dir.join(format!("{stem}.txt"));
+    std::fs::write(&path, text).map_err(|e| ClipboardCaptureError::IoError(e.to_string()))?;
+    Ok(ClipboardCapture::Text {
+        path,
+        bytes: text.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn saves_text_under_stem() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path().join(CLIPBOARD_ARTIFACTS_SUBDIR);
+        let capture =
+            save_text(&dir, "clipboard-1", "panic at line 3\n").expect("text should be saved");
+
+        let path = dir.join("clipboard-1.txt");
+        assert_eq!(
+            capture,
+            ClipboardCapture::Text {
+                path: path.clone(),
+                bytes: 16,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(path).expect("read back"),
+            "panic at line 3\n"
+        );
+    }
+
+    #[test]
+    fn rejects_blank_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(save_text(dir.path(), "clipboard-1", "  \n").is_err());
+    }
+
+    #[test]
+    fn stems_are_millisecond_timestamps() {
+        let stem = capture_stem();
+        let millis = stem.strip_prefix("clipboard-").expect("prefix");
+        assert!(millis.parse::<u128>().is_ok(), "{stem}");
+    }
+}
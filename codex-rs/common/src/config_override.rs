@@ -22,10 +22,15 @@ pub struct CliConfigOverrides {
     /// nested values. The `value` portion is parsed as TOML. If it fails to
     /// parse as TOML, the raw string is used as a literal.
     ///
+    /// Index into arrays with `[n]` (`n` equal to the length appends), and
+    /// remove a key with `!key`. Values of the wrong type are rejected.
+    ///
     /// Examples:
     ///   - `-c model="o3"`
     ///   - `-c 'sandbox_permissions=["disk-full-read-access"]'`
     ///   - `-c shell_environment_policy.inherit=all`
+    ///   - `-c 'mcp_servers.docs.args[2]=--verbose'`
+    ///   - `-c '!notify'`
     #[arg(
         short = 'c',
         long = "config",
@@ -38,11 +43,21 @@ pub struct CliConfigOverrides {
 
 impl CliConfigOverrides {
     /// Parse the raw strings captured from the CLI into a list of `(path,
-    /// value)` tuples where `value` is a `serde_json::Value`.
+    /// value)` tuples where `value` is a `serde_json::Value`. A removal
+    /// (`!key`) keeps its `!` prefix in the path and has the value `true`.
     pub fn parse_overrides(&self) -> Result<Vec<(String, Value)>, String> {
         self.raw_overrides
             .iter()
             .map(|s| {
+                if let Some(path) = s.trim().strip_prefix('!') {
+                    if path.contains('=') {
+                        return Err(format!("Removal takes no value: {s}"));
+                    }
+                    if path.trim().is_empty() {
+                        return Err(format!("Empty key in override: {s}"));
+                    }
+                    return Ok((format!("!{}", path.trim()), Value::Boolean(true)));
+                }
                 // Only split on the *first* '=' so values are free to contain
                 // the character.
                 let mut parts = s.splitn(2, '=');
@@ -172,6 +187,34 @@ mod tests {
         assert_eq!(arr.len(), 3);
     }
 
+    #[test]
+    fn parses_removals_and_indexed_paths() {
+        let overrides = CliConfigOverrides {
+            raw_overrides: vec![
+                "!notify".to_string(),
+                "mcp_servers.docs.args[2]=--verbose".to_string(),
+            ],
+        };
+        assert_eq!(
+            overrides.parse_overrides(),
+            Ok(vec![
+                ("!notify".to_string(), Value::Boolean(true)),
+                (
+                    "mcp_servers.docs.args[2]".to_string(),
+                    Value::String("--verbose".to_string())
+                ),
+            ])
+        );
+
+        let invalid = CliConfigOverrides {
+            raw_overrides: vec!["!notify=[]".to_string()],
+        };
+        assert_eq!(
+            invalid.parse_overrides(),
+            Err("Removal takes no value: !notify=[]".to_string())
+        );
+    }
+
     #[test]
     fn parses_inline_table() {
         let v = parse_toml_value("{a = 1, b = 2}").expect("parse");
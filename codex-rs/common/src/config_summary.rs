@@ -12,6 +12,9 @@ pub fn create_config_summary_entries(config: &Config, model: &str) -> Vec<(&'sta
         ("approval", config.approval_policy.value().to_string()),
         ("sandbox", summarize_sandbox_policy(&config.sandbox_policy)),
     ];
+    if let Some(profile) = &config.active_profile {
+        entries.insert(1, ("profile", profile.clone()));
+    }
     if config.model_provider.wire_api == WireApi::Responses {
         let reasoning_effort = config
             .model_reasoning_effort
@@ -25,6 +28,11 @@ pub fn create_config_summary_entries(config: &Config, model: &str) -> Vec<(&'sta
             config.model_reasoning_summary.to_string(),
         ));
     }
+    let experimental = config.features.enabled_opt_in();
+    if !experimental.is_empty() {
+        let keys: Vec<&str> = experimental.iter().map(|feature| feature.key()).collect();
+        entries.push(("experimental", keys.join(", ")));
+    }
 
     entries
 }
@@ -4,6 +4,9 @@ mod approval_mode_cli_arg;
 #[cfg(feature = "elapsed")]
 pub mod elapsed;
 
+#[cfg(feature = "clipboard")]
+pub mod clipboard_capture;
+
 #[cfg(feature = "cli")]
 pub use approval_mode_cli_arg::ApprovalModeCliArg;
 
@@ -104,7 +104,7 @@ pub(crate) async fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
 ) -> crate::error::Result<CoreAuthProvider> {
-    if let Some(api_key) = provider.api_key()? {
+    if let Some(api_key) = provider.api_key().await? {
         return Ok(CoreAuthProvider {
             token: Some(api_key),
             account_id: None,
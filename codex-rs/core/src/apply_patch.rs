@@ -1,10 +1,13 @@
+use crate::approvals_policy::PolicyAction;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
+use crate::workspace_roots::outside_workspace;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
@@ -31,26 +34,107 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// The user accepted only part of the proposed patch, so `action` is
+    /// their modified patch rather than the model's.
+    pub(crate) user_modified_patch: bool,
 }
 
+/// Appended to the tool output when the user applied part of the patch.
+pub(crate) const PARTIAL_PATCH_NOTE: &str = "Note: the user reviewed this patch and rejected some of its hunks; only the accepted changes were applied.";
+
 pub(crate) async fn apply_patch(
     sess: &Session,
     turn_context: &TurnContext,
     call_id: &str,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
-    match assess_patch_safety(
+    // Steering constraints are checked before approval so that neither an
+    // auto-approved patch nor a user-approved one can break them.
+    if let Some(reason) = turn_context
+        .steering_constraints
+        .check_patch(&convert_apply_patch_to_protocol(&action))
+    {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("patch rejected by steering constraints: {reason}"),
+        )));
+    }
+
+    let config = turn_context.client.config();
+    if let Some(reason) = config
+        .patch_policy
+        .as_ref()
+        .and_then(|policy| policy.check_patch(&convert_apply_patch_to_protocol(&action)))
+    {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("patch rejected by the patch policy: {reason}"),
+        )));
+    }
+    let outside = action.changes().iter().find_map(|(path, change)| {
+        let move_path = match change {
+            ApplyPatchFileChange::Update { move_path, .. } => move_path.as_deref(),
+            _ => None,
+        };
+        std::iter::once(path.as_path())
+            .chain(move_path)
+            .find_map(|path| outside_workspace(&config, path))
+    });
+    if let Some(reason) = outside {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("patch rejected: {reason}"),
+        )));
+    }
+
+    let safety = assess_patch_safety(
         &action,
         turn_context.approval_policy,
         &turn_context.sandbox_policy,
         &turn_context.cwd,
-    ) {
+    );
+    // The project's approvals policy can refuse a patch, approve one that
+    // would otherwise be asked about, or insist on asking.
+    let verdict = config
+        .approvals_policy
+        .as_ref()
+        .and_then(|policy| policy.check_patch(&convert_apply_patch_to_protocol(&action)));
+    let safety = match (verdict, safety) {
+        (None, safety) | (Some(_), safety @ SafetyCheck::Reject { .. }) => safety,
+        (Some(verdict), safety) => match verdict.action {
+            PolicyAction::Deny => {
+                return InternalApplyPatchInvocation::Output(Err(
+                    FunctionCallError::RespondToModel(format!(
+                        "patch rejected: {}",
+                        verdict.reason
+                    )),
+                ));
+            }
+            PolicyAction::Allow if matches!(safety, SafetyCheck::AskUser) => {
+                return InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                    action,
+                    user_explicitly_approved_this_action: false,
+                    user_modified_patch: false,
+                });
+            }
+            PolicyAction::Allow => safety,
+            PolicyAction::Ask if turn_context.approval_policy == AskForApproval::Never => {
+                SafetyCheck::Reject {
+                    reason: format!(
+                        "{}, but approval_policy is never, so nobody can approve it",
+                        verdict.reason
+                    ),
+                }
+            }
+            PolicyAction::Ask => SafetyCheck::AskUser,
+        },
+    };
+
+    match safety {
         SafetyCheck::AutoApprove {
             user_explicitly_approved,
             ..
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            user_modified_patch: false,
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -72,12 +156,43 @@ pub(crate) async fn apply_patch(
             match rx_approve.await.unwrap_or_default() {
                 ReviewDecision::Approved
                 | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                | ReviewDecision::ApprovedForSession => {
+                | ReviewDecision::ApprovedForSession
+                | ReviewDecision::ApprovedForProject { .. } => {
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        user_modified_patch: false,
                     })
                 }
+                ReviewDecision::ApprovedWithModifiedPatch { patch } => {
+                    let command = vec!["apply_patch".to_string(), patch];
+                    match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &action.cwd)
+                    {
+                        codex_apply_patch::MaybeApplyPatchVerified::Body(modified) => {
+                            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                                action: modified,
+                                user_explicitly_approved_this_action: true,
+                                user_modified_patch: true,
+                            })
+                        }
+                        codex_apply_patch::MaybeApplyPatchVerified::CorrectnessError(err) => {
+                            InternalApplyPatchInvocation::Output(Err(
+                                FunctionCallError::RespondToModel(format!(
+                                    "patch rejected: the user's partial approval could not be applied: {err}"
+                                )),
+                            ))
+                        }
+                        codex_apply_patch::MaybeApplyPatchVerified::ShellParseError(_)
+                        | codex_apply_patch::MaybeApplyPatchVerified::NotApplyPatch => {
+                            InternalApplyPatchInvocation::Output(Err(
+                                FunctionCallError::RespondToModel(
+                                    "patch rejected: the user's partial approval was not a valid patch"
+                                        .to_string(),
+                                ),
+                            ))
+                        }
+                    }
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
                         "patch rejected by user".to_string(),
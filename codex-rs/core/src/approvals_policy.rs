@@ -0,0 +1,678 @@
+//! `.codex/approvals.toml`: what the agent may do in a project, written down
+//! once for everyone who works in it.
+//!
+//! The file holds an ordered list of rules. Each rule matches commands (one
+//! glob per argument), file paths a patch writes, or domains sandboxed
+//! commands connect to, and says whether to `allow` them without asking,
+//! `deny` them outright, or `ask` every time:
+//!
+//! ```toml
+//! [[rules]]
+//! action = "deny"
+//! command = ["git", "push", "**"]
+//! reason = "Pushes go through CI."
+//!
+//! [[rules]]
+//! action = "allow"
+//! command = ["cargo", "test", "**"]
+//!
+//! [[rules]]
+//! action = "ask"
+//! write = [".github/**", "Cargo.lock"]
+//! ```
+//!
+//! The first matching rule decides; anything no rule matches goes through the
+//! usual approval policy. The policy applies in the TUI and in `codex exec`
+//! alike. In a project whose `.codex/` files the user chose not to trust, only
+//! `deny` and `ask` rules are honored, so a checked-in file can tighten what
+//! Codex does but not loosen it.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::approvals::CommandApprovalRule;
+use codex_protocol::protocol::FileChange;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::config::trust_store::project_files_allowed;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::patch_policy::first_match;
+use crate::protocol::AskForApproval;
+use crate::sandboxing::domain_covers;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+
+/// Where the policy lives, relative to the project root.
+pub const APPROVALS_POLICY_FILE: &str = ".codex/approvals.toml";
+
+/// What a rule does with what it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    Allow,
+    Deny,
+    Ask,
+}
+
+/// One `[[rules]]` entry of `.codex/approvals.toml`. Exactly one of
+/// `command`, `write`, and `network` must be set.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalsPolicyRuleToml {
+    pub action: PolicyAction,
+    /// One glob per argument (`*`, `?`); a final `**` matches any remaining
+    /// arguments.
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    /// Globs for paths a patch creates, edits, moves, or deletes, relative to
+    /// the project root. `*` matches across `/`.
+    #[serde(default)]
+    pub write: Option<Vec<String>>,
+    /// Domains sandboxed commands connect to. An entry also covers its
+    /// subdomains.
+    #[serde(default)]
+    pub network: Option<Vec<String>>,
+    /// Shown to the model and the user when the rule applies.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// The contents of `.codex/approvals.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApprovalsPolicyToml {
+    #[serde(default)]
+    pub rules: Vec<ApprovalsPolicyRuleToml>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RuleTarget {
+    Command(CommandApprovalRule),
+    Write(Vec<String>),
+    Network(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    /// Position in the file, from 1, for messages.
+    number: usize,
+    action: PolicyAction,
+    target: RuleTarget,
+    reason: Option<String>,
+}
+
+/// What the policy decided about a command, patch, or connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyVerdict {
+    pub action: PolicyAction,
+    /// Which rule decided and why, e.g. ``rule 1 in .codex/approvals.toml
+    /// denies `git push`: Pushes go through CI.``
+    pub reason: String,
+}
+
+/// A loaded approvals policy and the project root its paths are relative to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalsPolicy {
+    root: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl ApprovalsPolicy {
+    /// Builds a policy for the project at `root`, checking that each rule
+    /// matches something.
+    pub fn new(root: PathBuf, toml: ApprovalsPolicyToml) -> Result<Self, String> {
+        let rules = toml
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let number = index + 1;
+                let target = match (rule.command, rule.write, rule.network) {
+                    (Some(command), None, None) => {
+                        RuleTarget::Command(CommandApprovalRule::Pattern { command })
+                    }
+                    (None, Some(globs), None) => RuleTarget::Write(globs),
+                    (None, None, Some(domains)) => RuleTarget::Network(domains),
+                    _ => {
+                        return Err(format!(
+                            "rule {number} must set exactly one of `command`, `write`, and `network`"
+                        ));
+                    }
+                };
+                let empty = match &target {
+                    RuleTarget::Command(rule) => rule.command().is_empty(),
+                    RuleTarget::Write(list) | RuleTarget::Network(list) => list.is_empty(),
+                };
+                if empty {
+                    return Err(format!("rule {number} matches nothing"));
+                }
+                Ok(Rule {
+                    number,
+                    action: rule.action,
+                    target,
+                    reason: rule.reason,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { root, rules })
+    }
+
+    /// Reads the policy of the project containing `cwd`: the nearest
+    /// `.codex/approvals.toml` between `cwd` and the root of its git
+    /// repository. Returns `None` when there is none.
+    pub fn load(codex_home: &Path, cwd: &Path) -> std::io::Result<Option<Self>> {
+        let repo_root = resolve_root_git_project_for_trust(cwd);
+        let mut found = None;
+        for dir in cwd.ancestors() {
+            let candidate = dir.join(APPROVALS_POLICY_FILE);
+            if candidate.is_file() {
+                found = Some((dir.to_path_buf(), candidate));
+                break;
+            }
+            match repo_root.as_deref() {
+                Some(repo_root) if dir != repo_root => continue,
+                _ => break,
+            }
+        }
+        let Some((root, path)) = found else {
+            return Ok(None);
+        };
+
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let invalid = |message: String| {
+            std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid approvals policy {}: {message}", path.display()),
+            )
+        };
+        let toml = toml::from_str(&contents).map_err(|err| invalid(err.to_string()))?;
+        let mut policy = Self::new(root, toml).map_err(invalid)?;
+        if !project_files_allowed(codex_home, cwd) {
+            let before = policy.rules.len();
+            policy
+                .rules
+                .retain(|rule| rule.action != PolicyAction::Allow);
+            if policy.rules.len() < before {
+                warn!(
+                    "ignoring the allow rules of {}: the project's .codex/ files are not trusted",
+                    path.display()
+                );
+            }
+        }
+        Ok(Some(policy))
+    }
+
+    /// The policy's verdict on running `command`. A `bash -lc` script is
+    /// judged by its commands: one denied or needing approval decides for the
+    /// whole script, and it is allowed only when all of them are. A script
+    /// too complex to split is asked about when the policy denies any command.
+    pub fn check_command(&self, command: &[String]) -> Option<PolicyVerdict> {
+        if let Some(verdict) = self.first_command_match(command) {
+            return Some(verdict);
+        }
+        match parse_shell_lc_plain_commands(command) {
+            Some(commands) => combine(
+                commands
+                    .iter()
+                    .map(|command| self.first_command_match(command)),
+            ),
+            None if extract_bash_command(command).is_some() => self
+                .rules
+                .iter()
+                .find(|rule| {
+                    rule.action == PolicyAction::Deny
+                        && matches!(rule.target, RuleTarget::Command(_))
+                })
+                .map(|_| PolicyVerdict {
+                    action: PolicyAction::Ask,
+                    reason: format!(
+                        "{APPROVALS_POLICY_FILE} denies some commands, and this script is too complex to check"
+                    ),
+                }),
+            None => None,
+        }
+    }
+
+    /// The policy's verdict on a patch making `changes`: denied or asked
+    /// about when any path it touches is, allowed when all of them are.
+    /// Patches may never change the policy file itself.
+    pub fn check_patch(&self, changes: &HashMap<PathBuf, FileChange>) -> Option<PolicyVerdict> {
+        let mut paths: Vec<String> = changes
+            .iter()
+            .flat_map(|(path, change)| {
+                let moved_to = match change {
+                    FileChange::Update {
+                        move_path: Some(move_path),
+                        ..
+                    } => Some(move_path.as_path()),
+                    _ => None,
+                };
+                std::iter::once(path.as_path()).chain(moved_to)
+            })
+            .map(|path| self.relative(path))
+            .collect();
+        paths.sort();
+
+        if paths.iter().any(|path| path == APPROVALS_POLICY_FILE) {
+            return Some(PolicyVerdict {
+                action: PolicyAction::Deny,
+                reason: format!("patches may not change {APPROVALS_POLICY_FILE}"),
+            });
+        }
+        combine(paths.iter().map(|path| {
+            self.first_match(path, |target| match target {
+                RuleTarget::Write(globs) => first_match(globs, path).is_some(),
+                _ => false,
+            })
+        }))
+    }
+
+    /// The policy's verdict on a sandboxed command connecting to `host`.
+    pub fn check_host(&self, host: &str) -> Option<PolicyVerdict> {
+        self.first_match(host, |target| match target {
+            RuleTarget::Network(domains) => {
+                domains.iter().any(|domain| domain_covers(domain, host))
+            }
+            _ => false,
+        })
+    }
+
+    /// Whether some rule lets sandboxed commands reach a domain.
+    pub fn allows_network(&self) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.action == PolicyAction::Allow && matches!(rule.target, RuleTarget::Network(_))
+        })
+    }
+
+    /// Combines the policy's verdict on `command` with `requirement`, what
+    /// the approval policy and execpolicy decided on their own. Commands
+    /// execpolicy forbids stay forbidden; an `ask` verdict under
+    /// `approval_policy = "never"` forbids the command, since nobody can
+    /// answer.
+    pub(crate) fn apply_to_command(
+        &self,
+        command: &[String],
+        approval_policy: AskForApproval,
+        requirement: ExecApprovalRequirement,
+    ) -> ExecApprovalRequirement {
+        let Some(verdict) = self.check_command(command) else {
+            return requirement;
+        };
+        if matches!(requirement, ExecApprovalRequirement::Forbidden { .. }) {
+            return requirement;
+        }
+        match verdict.action {
+            PolicyAction::Deny => ExecApprovalRequirement::Forbidden {
+                reason: verdict.reason,
+            },
+            PolicyAction::Allow => match requirement {
+                ExecApprovalRequirement::NeedsApproval {
+                    proposed_execpolicy_amendment,
+                    ..
+                } => ExecApprovalRequirement::Skip {
+                    bypass_sandbox: false,
+                    proposed_execpolicy_amendment,
+                },
+                requirement => requirement,
+            },
+            PolicyAction::Ask if approval_policy == AskForApproval::Never => {
+                ExecApprovalRequirement::Forbidden {
+                    reason: format!(
+                        "{}, but approval_policy is never, so nobody can approve it",
+                        verdict.reason
+                    ),
+                }
+            }
+            PolicyAction::Ask => ExecApprovalRequirement::NeedsApproval {
+                reason: Some(verdict.reason),
+                proposed_execpolicy_amendment: requirement.proposed_execpolicy_amendment().cloned(),
+            },
+        }
+    }
+
+    fn first_command_match(&self, command: &[String]) -> Option<PolicyVerdict> {
+        self.first_match(&command.join(" "), |target| match target {
+            RuleTarget::Command(rule) => rule.matches(command),
+            _ => false,
+        })
+    }
+
+    fn first_match(
+        &self,
+        subject: &str,
+        matches: impl Fn(&RuleTarget) -> bool,
+    ) -> Option<PolicyVerdict> {
+        let rule = self.rules.iter().find(|rule| matches(&rule.target))?;
+        let verb = match rule.action {
+            PolicyAction::Allow => "allows",
+            PolicyAction::Deny => "denies",
+            PolicyAction::Ask => "requires approval for",
+        };
+        let mut reason = format!(
+            "rule {} in {APPROVALS_POLICY_FILE} {verb} `{subject}`",
+            rule.number
+        );
+        if let Some(why) = &rule.reason {
+            reason.push_str(&format!(": {why}"));
+        }
+        Some(PolicyVerdict {
+            action: rule.action,
+            reason,
+        })
+    }
+
+    /// `path` relative to the root with `.` and `..` resolved lexically, so
+    /// `src/../.codex/approvals.toml` is judged as `.codex/approvals.toml`.
+    fn relative(&self, path: &Path) -> String {
+        let path = normalize(path);
+        let path = path.strip_prefix(&self.root).unwrap_or(&path);
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Combines the verdicts on the parts of one action: the first denial, else
+/// the first request for approval, else an allowance if every part is
+/// allowed.
+fn combine(verdicts: impl Iterator<Item = Option<PolicyVerdict>>) -> Option<PolicyVerdict> {
+    let verdicts: Vec<Option<PolicyVerdict>> = verdicts.collect();
+    for action in [PolicyAction::Deny, PolicyAction::Ask] {
+        if let Some(verdict) = verdicts
+            .iter()
+            .flatten()
+            .find(|verdict| verdict.action == action)
+        {
+            return Some(verdict.clone());
+        }
+    }
+    if verdicts.iter().all(Option::is_some) {
+        verdicts.into_iter().flatten().next()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn policy(toml: &str) -> ApprovalsPolicy {
+        ApprovalsPolicy::new(
+            PathBuf::from("/repo"),
+            toml::from_str(toml).expect("valid policy"),
+        )
+        .expect("valid rules")
+    }
+
+    fn bash(script: &str) -> Vec<String> {
+        vec!["bash".to_string(), "-lc".to_string(), script.to_string()]
+    }
+
+    fn action(verdict: Option<PolicyVerdict>) -> Option<PolicyAction> {
+        verdict.map(|verdict| verdict.action)
+    }
+
+    const RULES: &str = r#"
+        [[rules]]
+        action = "deny"
+        command = ["git", "push", "**"]
+        reason = "Pushes go through CI."
+
+        [[rules]]
+        action = "allow"
+        command = ["git", "**"]
+
+        [[rules]]
+        action = "ask"
+        command = ["npm", "publish", "**"]
+
+        [[rules]]
+        action = "ask"
+        write = [".github/**"]
+
+        [[rules]]
+        action = "allow"
+        write = ["src/**"]
+
+        [[rules]]
+        action = "deny"
+        network = ["gist.github.com"]
+
+        [[rules]]
+        action = "allow"
+        network = ["github.com"]
+    "#;
+
+    #[test]
+    fn the_first_matching_command_rule_decides() {
+        let policy = policy(RULES);
+        assert_eq!(
+            policy.check_command(&bash("git push origin main")),
+            Some(PolicyVerdict {
+                action: PolicyAction::Deny,
+                reason: "rule 1 in .codex/approvals.toml denies `git push origin main`: Pushes go through CI."
+                    .to_string(),
+            })
+        );
+        assert_eq!(
+            action(policy.check_command(&bash("git status && git diff"))),
+            Some(PolicyAction::Allow)
+        );
+        assert_eq!(
+            action(policy.check_command(&bash("git status && npm publish"))),
+            Some(PolicyAction::Ask)
+        );
+        assert_eq!(
+            action(policy.check_command(&bash("git status && cargo build"))),
+            None
+        );
+        assert_eq!(
+            action(policy.check_command(&bash("git status > $(git push)"))),
+            Some(PolicyAction::Ask)
+        );
+    }
+
+    #[test]
+    fn verdicts_adjust_the_approval_requirement() {
+        let policy = policy(RULES);
+        let needs_approval = || ExecApprovalRequirement::NeedsApproval {
+            reason: None,
+            proposed_execpolicy_amendment: None,
+        };
+        let skip = || ExecApprovalRequirement::Skip {
+            bypass_sandbox: false,
+            proposed_execpolicy_amendment: None,
+        };
+
+        assert_eq!(
+            policy.apply_to_command(
+                &bash("git diff"),
+                AskForApproval::UnlessTrusted,
+                needs_approval()
+            ),
+            skip()
+        );
+        assert!(matches!(
+            policy.apply_to_command(&bash("git push"), AskForApproval::OnRequest, skip()),
+            ExecApprovalRequirement::Forbidden { .. }
+        ));
+        assert!(matches!(
+            policy.apply_to_command(&bash("npm publish"), AskForApproval::OnRequest, skip()),
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            policy.apply_to_command(&bash("npm publish"), AskForApproval::Never, skip()),
+            ExecApprovalRequirement::Forbidden { .. }
+        ));
+        assert_eq!(
+            policy.apply_to_command(&bash("cargo build"), AskForApproval::OnRequest, skip()),
+            skip()
+        );
+    }
+
+    #[test]
+    fn patches_are_judged_by_every_path() {
+        let policy = policy(RULES);
+        let add = |path: &str| {
+            (
+                PathBuf::from(path),
+                FileChange::Add {
+                    content: String::new(),
+                },
+            )
+        };
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([add("/repo/src/lib.rs")]))),
+            Some(PolicyAction::Allow)
+        );
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([
+                add("/repo/src/lib.rs"),
+                add("/repo/.github/workflows/ci.yml"),
+            ]))),
+            Some(PolicyAction::Ask)
+        );
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([
+                add("/repo/src/lib.rs"),
+                add("/repo/README.md"),
+            ]))),
+            None
+        );
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([add("/repo/.codex/approvals.toml")]))),
+            Some(PolicyAction::Deny)
+        );
+    }
+
+    #[test]
+    fn patch_paths_are_normalized_before_matching() {
+        let policy = policy(RULES);
+        let add = |path: &str| {
+            (
+                PathBuf::from(path),
+                FileChange::Add {
+                    content: String::new(),
+                },
+            )
+        };
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([add("/repo/src/../.codex/approvals.toml")]))),
+            Some(PolicyAction::Deny)
+        );
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([add(
+                "/repo/./src/../.github/workflows/ci.yml"
+            )]))),
+            Some(PolicyAction::Ask)
+        );
+        assert_eq!(
+            action(policy.check_patch(&HashMap::from([add("/repo/src/./../src/lib.rs")]))),
+            Some(PolicyAction::Allow)
+        );
+    }
+
+    #[test]
+    fn network_rules_cover_subdomains_in_order() {
+        let policy = policy(RULES);
+        assert!(policy.allows_network());
+        assert_eq!(
+            action(policy.check_host("api.github.com")),
+            Some(PolicyAction::Allow)
+        );
+        assert_eq!(
+            action(policy.check_host("gist.github.com")),
+            Some(PolicyAction::Deny)
+        );
+        assert_eq!(action(policy.check_host("example.com")), None);
+    }
+
+    #[test]
+    fn rules_must_match_exactly_one_kind_of_thing() {
+        let toml = |rules: &str| -> ApprovalsPolicyToml { toml::from_str(rules).expect("toml") };
+        assert_eq!(
+            ApprovalsPolicy::new(
+                PathBuf::from("/repo"),
+                toml("[[rules]]\naction = \"allow\"\ncommand = [\"ls\"]\nwrite = [\"*\"]\n"),
+            ),
+            Err("rule 1 must set exactly one of `command`, `write`, and `network`".to_string())
+        );
+        assert_eq!(
+            ApprovalsPolicy::new(
+                PathBuf::from("/repo"),
+                toml("[[rules]]\naction = \"deny\"\nnetwork = []\n"),
+            ),
+            Err("rule 1 matches nothing".to_string())
+        );
+    }
+
+    #[test]
+    fn untrusted_projects_keep_only_restrictions() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let project = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            ApprovalsPolicy::load(codex_home.path(), project.path()).expect("load"),
+            None
+        );
+
+        std::fs::create_dir(project.path().join(".codex")).expect("mkdir");
+        std::fs::write(project.path().join(APPROVALS_POLICY_FILE), RULES).expect("write");
+        let policy = ApprovalsPolicy::load(codex_home.path(), project.path())
+            .expect("load")
+            .expect("policy");
+        assert_eq!(action(policy.check_command(&bash("git diff"))), None);
+        assert_eq!(
+            action(policy.check_command(&bash("git push"))),
+            Some(PolicyAction::Deny)
+        );
+        assert!(!policy.allows_network());
+
+        crate::config::trust_store::record_project_trust(
+            codex_home.path(),
+            project.path(),
+            crate::config::trust_store::ProjectTrust::new(
+                codex_protocol::config_types::TrustLevel::Trusted,
+            ),
+        )
+        .expect("record trust");
+        let policy = ApprovalsPolicy::load(codex_home.path(), project.path())
+            .expect("load")
+            .expect("policy");
+        assert_eq!(
+            action(policy.check_command(&bash("git diff"))),
+            Some(PolicyAction::Allow)
+        );
+
+        std::fs::write(project.path().join(APPROVALS_POLICY_FILE), "[[rule]]\n").expect("write");
+        assert!(ApprovalsPolicy::load(codex_home.path(), project.path()).is_err());
+    }
+}
@@ -0,0 +1,719 @@
+//! Read-only inspection of zip and tar (optionally gzip-compressed) archives.
+//!
+//! This intentionally supports only the subset of each format needed to list
+//! members and pull individual files out of build artifacts and downloaded
+//! packages: stored/deflated zip entries (no zip64, no encryption) and ustar
+//! tarballs with GNU long names or PAX `path` records.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use flate2::read::DeflateDecoder;
+use flate2::read::GzDecoder;
+
+const ZIP_LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const ZIP_CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0605_4b50;
+/// Fixed EOCD record size plus the largest possible trailing comment.
+const ZIP_EOCD_SEARCH_WINDOW: u64 = 22 + u16::MAX as u64;
+const ZIP_METHOD_STORED: u16 = 0;
+const ZIP_METHOD_DEFLATED: u16 = 8;
+/// Largest central directory read into memory. Even 65,535 entries with long
+/// names fit comfortably.
+const ZIP_MAX_CENTRAL_DIRECTORY_BYTES: u64 = 16 * 1024 * 1024;
+
+const TAR_BLOCK_SIZE: usize = 512;
+/// Largest GNU long-name or PAX header body read into memory.
+const TAR_MAX_EXTENDED_HEADER_BYTES: u64 = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArchiveEntryKind {
+    File,
+    Directory,
+    Symlink,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArchiveEntry {
+    pub path: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    pub kind: ArchiveEntryKind,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ArchiveError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("unsupported archive: {0}")]
+    Unsupported(String),
+    #[error("malformed archive: {0}")]
+    Malformed(String),
+    #[error("member `{0}` not found in archive")]
+    MemberNotFound(String),
+    #[error("member `{0}` is not a regular file")]
+    NotAFile(String),
+    #[error("member `{member}` is {size} bytes, which exceeds the {limit} byte limit")]
+    TooLarge {
+        member: String,
+        size: u64,
+        limit: u64,
+    },
+}
+
+/// Sniffs the archive format from the file's magic bytes rather than trusting
+/// the extension.
+pub(crate) fn detect_format(path: &Path) -> Result<ArchiveFormat, ArchiveError> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; TAR_BLOCK_SIZE];
+    let read = read_up_to(&mut file, &mut header)?;
+    let header = &header[..read];
+
+    if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        return Ok(ArchiveFormat::Zip);
+    }
+    if header.starts_with(&[0x1f, 0x8b]) {
+        return Ok(ArchiveFormat::TarGz);
+    }
+    if header.len() >= 262 && &header[257..262] == b"ustar" {
+        return Ok(ArchiveFormat::Tar);
+    }
+    Err(ArchiveError::Unsupported(
+        "expected a zip, tar, or tar.gz file".to_string(),
+    ))
+}
+
+pub(crate) fn list_entries(
+    path: &Path,
+    format: ArchiveFormat,
+) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    match format {
+        ArchiveFormat::Zip => Ok(read_zip_central_directory(&mut File::open(path)?)?
+            .into_iter()
+            .map(|entry| entry.entry)
+            .collect()),
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            let mut entries = Vec::new();
+            walk_tar(open_tar_stream(path, format)?, |entry, _| {
+                entries.push(entry.clone());
+                Ok(TarVisit::Skip)
+            })?;
+            Ok(entries)
+        }
+    }
+}
+
+/// Reads a single regular-file member, failing with [`ArchiveError::TooLarge`]
+/// instead of buffering anything past `max_bytes`.
+pub(crate) fn read_member(
+    path: &Path,
+    format: ArchiveFormat,
+    member: &str,
+    max_bytes: u64,
+) -> Result<Vec<u8>, ArchiveError> {
+    match format {
+        ArchiveFormat::Zip => read_zip_member(path, member, max_bytes),
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            let mut found: Option<Vec<u8>> = None;
+            walk_tar(open_tar_stream(path, format)?, |entry, body| {
+                if entry.path != member {
+                    return Ok(TarVisit::Skip);
+                }
+                check_member(entry, max_bytes)?;
+                let mut data = Vec::with_capacity(entry.size as usize);
+                body.take(entry.size).read_to_end(&mut data)?;
+                found = Some(data);
+                Ok(TarVisit::Stop)
+            })?;
+            found.ok_or_else(|| ArchiveError::MemberNotFound(member.to_string()))
+        }
+    }
+}
+
+fn check_member(entry: &ArchiveEntry, max_bytes: u64) -> Result<(), ArchiveError> {
+    if entry.kind != ArchiveEntryKind::File {
+        return Err(ArchiveError::NotAFile(entry.path.clone()));
+    }
+    if entry.size > max_bytes {
+        return Err(ArchiveError::TooLarge {
+            member: entry.path.clone(),
+            size: entry.size,
+            limit: max_bytes,
+        });
+    }
+    Ok(())
+}
+
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn le_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn le_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        buf[offset],
+        buf[offset + 1],
+        buf[offset + 2],
+        buf[offset + 3],
+    ])
+}
+
+struct ZipEntry {
+    entry: ArchiveEntry,
+    method: u16,
+    flags: u16,
+    crc32: u32,
+    compressed_size: u64,
+    local_header_offset: u64,
+}
+
+fn read_zip_central_directory<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<Vec<ZipEntry>, ArchiveError> {
+    let file_len = reader.seek(SeekFrom::End(0))?;
+    let window = file_len.min(ZIP_EOCD_SEARCH_WINDOW);
+    reader.seek(SeekFrom::Start(file_len - window))?;
+    let mut tail = vec![0u8; window as usize];
+    reader.read_exact(&mut tail)?;
+
+    let signature = ZIP_END_OF_CENTRAL_DIR_SIGNATURE.to_le_bytes();
+    let eocd = tail
+        .windows(4)
+        .rposition(|candidate| candidate == signature)
+        .filter(|pos| pos + 22 <= tail.len())
+        .ok_or_else(|| {
+            ArchiveError::Malformed("zip end of central directory not found".to_string())
+        })?;
+    let eocd = &tail[eocd..];
+    let entry_count = le_u16(eocd, 10);
+    let directory_size = le_u32(eocd, 12);
+    let directory_offset = le_u32(eocd, 16);
+    if entry_count == u16::MAX || directory_size == u32::MAX || directory_offset == u32::MAX {
+        return Err(ArchiveError::Unsupported("zip64 archives".to_string()));
+    }
+    // The EOCD is untrusted; check its bounds before allocating for them.
+    if u64::from(directory_offset) + u64::from(directory_size) > file_len {
+        return Err(ArchiveError::Malformed(
+            "zip central directory extends past the end of the file".to_string(),
+        ));
+    }
+    if u64::from(directory_size) > ZIP_MAX_CENTRAL_DIRECTORY_BYTES {
+        return Err(ArchiveError::Unsupported(format!(
+            "zip central directory larger than {ZIP_MAX_CENTRAL_DIRECTORY_BYTES} bytes"
+        )));
+    }
+
+    reader.seek(SeekFrom::Start(u64::from(directory_offset)))?;
+    let mut directory = vec![0u8; directory_size as usize];
+    reader.read_exact(&mut directory)?;
+
+    let mut entries = Vec::with_capacity(entry_count as usize);
+    let mut cursor = 0usize;
+    for _ in 0..entry_count {
+        if cursor + 46 > directory.len()
+            || le_u32(&directory, cursor) != ZIP_CENTRAL_HEADER_SIGNATURE
+        {
+            return Err(ArchiveError::Malformed(
+                "truncated zip central directory".to_string(),
+            ));
+        }
+        let header = &directory[cursor..];
+        let flags = le_u16(header, 8);
+        let method = le_u16(header, 10);
+        let crc32 = le_u32(header, 16);
+        let compressed_size = le_u32(header, 20);
+        let uncompressed_size = le_u32(header, 24);
+        let name_len = le_u16(header, 28) as usize;
+        let extra_len = le_u16(header, 30) as usize;
+        let comment_len = le_u16(header, 32) as usize;
+        let external_attributes = le_u32(header, 38);
+        let local_header_offset = le_u32(header, 42);
+        if cursor + 46 + name_len > directory.len() {
+            return Err(ArchiveError::Malformed(
+                "truncated zip entry name".to_string(),
+            ));
+        }
+        let path = String::from_utf8_lossy(&header[46..46 + name_len]).into_owned();
+
+        // The upper 16 bits carry unix mode bits when the archive was made on
+        // a unix host; use them to spot symlinks.
+        let unix_mode = external_attributes >> 16;
+        let kind = if path.ends_with('/') {
+            ArchiveEntryKind::Directory
+        } else if unix_mode & 0o170000 == 0o120000 {
+            ArchiveEntryKind::Symlink
+        } else {
+            ArchiveEntryKind::File
+        };
+
+        entries.push(ZipEntry {
+            entry: ArchiveEntry {
+                path,
+                size: u64::from(uncompressed_size),
+                kind,
+            },
+            method,
+            flags,
+            crc32,
+            compressed_size: u64::from(compressed_size),
+            local_header_offset: u64::from(local_header_offset),
+        });
+        cursor += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(entries)
+}
+
+fn read_zip_member(path: &Path, member: &str, max_bytes: u64) -> Result<Vec<u8>, ArchiveError> {
+    let mut file = File::open(path)?;
+    let entries = read_zip_central_directory(&mut file)?;
+    let zip_entry = entries
+        .into_iter()
+        .find(|candidate| candidate.entry.path == member)
+        .ok_or_else(|| ArchiveError::MemberNotFound(member.to_string()))?;
+    check_member(&zip_entry.entry, max_bytes)?;
+    if zip_entry.flags & 0x1 != 0 {
+        return Err(ArchiveError::Unsupported(format!(
+            "member `{member}` is encrypted"
+        )));
+    }
+
+    file.seek(SeekFrom::Start(zip_entry.local_header_offset))?;
+    let mut local_header = [0u8; 30];
+    file.read_exact(&mut local_header)?;
+    if le_u32(&local_header, 0) != ZIP_LOCAL_HEADER_SIGNATURE {
+        return Err(ArchiveError::Malformed(format!(
+            "bad local header for `{member}`"
+        )));
+    }
+    let name_len = u64::from(le_u16(&local_header, 26));
+    let extra_len = u64::from(le_u16(&local_header, 28));
+    file.seek(SeekFrom::Current((name_len + extra_len) as i64))?;
+
+    let compressed = BufReader::new(file).take(zip_entry.compressed_size);
+    let limit = zip_entry.entry.size;
+    let mut data = Vec::with_capacity(limit as usize);
+    match zip_entry.method {
+        ZIP_METHOD_STORED => {
+            compressed.take(limit).read_to_end(&mut data)?;
+        }
+        ZIP_METHOD_DEFLATED => {
+            DeflateDecoder::new(compressed)
+                .take(limit)
+                .read_to_end(&mut data)?;
+        }
+        other => {
+            return Err(ArchiveError::Unsupported(format!(
+                "compression method {other} for `{member}`"
+            )));
+        }
+    }
+
+    let mut crc = flate2::Crc::new();
+    crc.update(&data);
+    if data.len() as u64 != limit || crc.sum() != zip_entry.crc32 {
+        return Err(ArchiveError::Malformed(format!(
+            "checksum mismatch for `{member}`"
+        )));
+    }
+    Ok(data)
+}
+
+fn open_tar_stream(path: &Path, format: ArchiveFormat) -> Result<Box<dyn Read>, ArchiveError> {
+    let file = BufReader::new(File::open(path)?);
+    Ok(match format {
+        ArchiveFormat::TarGz => Box::new(GzDecoder::new(file)),
+        _ => Box::new(file),
+    })
+}
+
+enum TarVisit {
+    Skip,
+    Stop,
+}
+
+/// Iterates over tar members, handing each one and its body reader to
+/// `visit`. Bodies the visitor does not consume are skipped.
+fn walk_tar<R, F>(mut reader: R, mut visit: F) -> Result<(), ArchiveError>
+where
+    R: Read,
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> Result<TarVisit, ArchiveError>,
+{
+    let mut header = [0u8; TAR_BLOCK_SIZE];
+    let mut pending_name: Option<String> = None;
+    loop {
+        match read_up_to(&mut reader, &mut header)? {
+            0 => return Ok(()),
+            TAR_BLOCK_SIZE => {}
+            _ => return Err(ArchiveError::Malformed("truncated tar header".to_string())),
+        }
+        if header.iter().all(|byte| *byte == 0) {
+            return Ok(());
+        }
+
+        let size = parse_tar_size(&header[124..136])?;
+        let padded_size = size.div_ceil(TAR_BLOCK_SIZE as u64) * TAR_BLOCK_SIZE as u64;
+        let type_flag = header[156];
+
+        match type_flag {
+            // GNU long name: the body holds the name of the next member.
+            b'L' => {
+                check_extended_header_size(size)?;
+                let mut name = Vec::new();
+                (&mut reader).take(size).read_to_end(&mut name)?;
+                skip_bytes(&mut reader, padded_size - size)?;
+                pending_name = Some(tar_string(&name));
+                continue;
+            }
+            // PAX extended header: honor `path` and ignore everything else.
+            b'x' => {
+                check_extended_header_size(size)?;
+                let mut records = Vec::new();
+                (&mut reader).take(size).read_to_end(&mut records)?;
+                skip_bytes(&mut reader, padded_size - size)?;
+                if let Some(path) = pax_path(&records) {
+                    pending_name = Some(path);
+                }
+                continue;
+            }
+            b'g' => {
+                skip_bytes(&mut reader, padded_size)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        let path = pending_name.take().unwrap_or_else(|| {
+            let name = tar_string(&header[0..100]);
+            let prefix = if &header[257..262] == b"ustar" {
+                tar_string(&header[345..500])
+            } else {
+                String::new()
+            };
+            if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            }
+        });
+        let kind = match type_flag {
+            b'0' | 0 | b'7' => ArchiveEntryKind::File,
+            b'5' => ArchiveEntryKind::Directory,
+            b'2' => ArchiveEntryKind::Symlink,
+            _ => ArchiveEntryKind::Other,
+        };
+        let entry = ArchiveEntry { path, size, kind };
+
+        let mut body = (&mut reader).take(size);
+        let visit_result = visit(&entry, &mut body)?;
+        if matches!(visit_result, TarVisit::Stop) {
+            return Ok(());
+        }
+        let unread = body.limit();
+        skip_bytes(&mut reader, unread + (padded_size - size))?;
+    }
+}
+
+fn check_extended_header_size(size: u64) -> Result<(), ArchiveError> {
+    if size > TAR_MAX_EXTENDED_HEADER_BYTES {
+        return Err(ArchiveError::Unsupported(format!(
+            "tar extended header of {size} bytes; at most {TAR_MAX_EXTENDED_HEADER_BYTES} are read"
+        )));
+    }
+    Ok(())
+}
+
+fn skip_bytes(reader: &mut impl Read, count: u64) -> Result<(), ArchiveError> {
+    let skipped = std::io::copy(&mut reader.take(count), &mut std::io::sink())?;
+    if skipped != count {
+        return Err(ArchiveError::Malformed("truncated tar member".to_string()));
+    }
+    Ok(())
+}
+
+fn tar_string(field: &[u8]) -> String {
+    let end = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn parse_tar_size(field: &[u8]) -> Result<u64, ArchiveError> {
+    // GNU base-256 encoding for sizes that do not fit in 11 octal digits.
+    if field[0] & 0x80 != 0 {
+        let mut value: u64 = u64::from(field[0] & 0x7f);
+        for byte in &field[1..] {
+            value = value
+                .checked_mul(256)
+                .and_then(|value| value.checked_add(u64::from(*byte)))
+                .ok_or_else(|| ArchiveError::Malformed("tar size overflow".to_string()))?;
+        }
+        return Ok(value);
+    }
+
+    let text = tar_string(field);
+    let text = text.trim_matches(|c: char| c == ' ' || c == '\0');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8)
+        .map_err(|_| ArchiveError::Malformed(format!("invalid tar size field `{text}`")))
+}
+
+/// PAX records look like `"<len> <key>=<value>\n"`.
+fn pax_path(records: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(records);
+    text.lines().find_map(|line| {
+        let (_, record) = line.split_once(' ')?;
+        record.strip_prefix("path=").map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::DeflateEncoder;
+    use flate2::write::GzEncoder;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn tar_header(name: &str, size: usize, type_flag: u8) -> Vec<u8> {
+        let mut header = vec![0u8; TAR_BLOCK_SIZE];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        header[156] = type_flag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[148..156].copy_from_slice(b"        ");
+        let checksum: u32 = header.iter().map(|b| u32::from(*b)).sum();
+        header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+        header
+    }
+
+    fn build_tar(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = tar_header("pkg/", 0, b'5');
+        for (name, body) in members {
+            out.extend(tar_header(name, body.len(), b'0'));
+            out.extend_from_slice(body);
+            let padding = (TAR_BLOCK_SIZE - body.len() % TAR_BLOCK_SIZE) % TAR_BLOCK_SIZE;
+            out.extend(std::iter::repeat_n(0u8, padding));
+        }
+        out.extend(std::iter::repeat_n(0u8, TAR_BLOCK_SIZE * 2));
+        out
+    }
+
+    fn build_zip(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, body) in members {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(body).unwrap();
+            let compressed = encoder.finish().unwrap();
+            let mut crc = flate2::Crc::new();
+            crc.update(body);
+            let offset = out.len() as u32;
+
+            out.extend(ZIP_LOCAL_HEADER_SIGNATURE.to_le_bytes());
+            out.extend(20u16.to_le_bytes());
+            out.extend(0u16.to_le_bytes());
+            out.extend(ZIP_METHOD_DEFLATED.to_le_bytes());
+            out.extend([0u8; 4]);
+            out.extend(crc.sum().to_le_bytes());
+            out.extend((compressed.len() as u32).to_le_bytes());
+            out.extend((body.len() as u32).to_le_bytes());
+            out.extend((name.len() as u16).to_le_bytes());
+            out.extend(0u16.to_le_bytes());
+            out.extend(name.as_bytes());
+            out.extend(&compressed);
+
+            central.extend(ZIP_CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            central.extend(20u16.to_le_bytes());
+            central.extend(20u16.to_le_bytes());
+            central.extend(0u16.to_le_bytes());
+            central.extend(ZIP_METHOD_DEFLATED.to_le_bytes());
+            central.extend([0u8; 4]);
+            central.extend(crc.sum().to_le_bytes());
+            central.extend((compressed.len() as u32).to_le_bytes());
+            central.extend((body.len() as u32).to_le_bytes());
+            central.extend((name.len() as u16).to_le_bytes());
+            central.extend([0u8; 12]);
+            central.extend(offset.to_le_bytes());
+            central.extend(name.as_bytes());
+        }
+        let central_offset = out.len() as u32;
+        out.extend(&central);
+        out.extend(ZIP_END_OF_CENTRAL_DIR_SIGNATURE.to_le_bytes());
+        out.extend([0u8; 4]);
+        out.extend((members.len() as u16).to_le_bytes());
+        out.extend((members.len() as u16).to_le_bytes());
+        out.extend((central.len() as u32).to_le_bytes());
+        out.extend(central_offset.to_le_bytes());
+        out.extend(0u16.to_le_bytes());
+        out
+    }
+
+    fn write_fixture(dir: &TempDir, name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn lists_and_reads_tar_gz_members() {
+        let dir = TempDir::new().unwrap();
+        let tar = build_tar(&[("pkg/README.md", b"hello"), ("pkg/lib.rs", b"fn main() {}")]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar).unwrap();
+        let path = write_fixture(&dir, "pkg.tgz", &encoder.finish().unwrap());
+
+        let format = detect_format(&path).unwrap();
+        assert_eq!(format, ArchiveFormat::TarGz);
+        assert_eq!(
+            list_entries(&path, format).unwrap(),
+            vec![
+                ArchiveEntry {
+                    path: "pkg/".to_string(),
+                    size: 0,
+                    kind: ArchiveEntryKind::Directory,
+                },
+                ArchiveEntry {
+                    path: "pkg/README.md".to_string(),
+                    size: 5,
+                    kind: ArchiveEntryKind::File,
+                },
+                ArchiveEntry {
+                    path: "pkg/lib.rs".to_string(),
+                    size: 12,
+                    kind: ArchiveEntryKind::File,
+                },
+            ]
+        );
+        assert_eq!(
+            read_member(&path, format, "pkg/lib.rs", 1024).unwrap(),
+            b"fn main() {}".to_vec()
+        );
+    }
+
+    #[test]
+    fn reads_deflated_zip_members() {
+        let dir = TempDir::new().unwrap();
+        let body = "line\n".repeat(100);
+        let path = write_fixture(
+            &dir,
+            "artifact.zip",
+            &build_zip(&[("a.txt", b"alpha"), ("logs/build.log", body.as_bytes())]),
+        );
+
+        let format = detect_format(&path).unwrap();
+        assert_eq!(format, ArchiveFormat::Zip);
+        let names: Vec<String> = list_entries(&path, format)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["a.txt".to_string(), "logs/build.log".to_string()]
+        );
+        assert_eq!(
+            read_member(&path, format, "logs/build.log", 1024).unwrap(),
+            body.into_bytes()
+        );
+    }
+
+    #[test]
+    fn enforces_member_size_limit() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir, "big.tar", &build_tar(&[("pkg/big.bin", &[7u8; 600])]));
+
+        let err = read_member(&path, ArchiveFormat::Tar, "pkg/big.bin", 100).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "member `pkg/big.bin` is 600 bytes, which exceeds the 100 byte limit"
+        );
+    }
+
+    #[test]
+    fn missing_member_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir, "a.zip", &build_zip(&[("a.txt", b"alpha")]));
+
+        let err = read_member(&path, ArchiveFormat::Zip, "b.txt", 1024).unwrap_err();
+        assert_eq!(err.to_string(), "member `b.txt` not found in archive");
+    }
+
+    #[test]
+    fn rejects_zip_directory_past_end_of_file() {
+        let dir = TempDir::new().unwrap();
+        let mut zip = build_zip(&[("a.txt", b"alpha")]);
+        // Claim a ~4 GiB central directory in the EOCD record.
+        let size_offset = zip.len() - 10;
+        zip[size_offset..size_offset + 4].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        let path = write_fixture(&dir, "huge.zip", &zip);
+
+        assert!(matches!(
+            list_entries(&path, ArchiveFormat::Zip),
+            Err(ArchiveError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_tar_extended_headers() {
+        let dir = TempDir::new().unwrap();
+        let mut tar = tar_header("././@LongLink", 1 << 30, b'L');
+        tar.extend(std::iter::repeat_n(0u8, TAR_BLOCK_SIZE * 2));
+        let path = write_fixture(&dir, "long.tar", &tar);
+
+        assert!(matches!(
+            list_entries(&path, ArchiveFormat::Tar),
+            Err(ArchiveError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_archives() {
+        let dir = TempDir::new().unwrap();
+        let path = write_fixture(&dir, "notes.txt", b"just text");
+
+        assert!(matches!(
+            detect_format(&path),
+            Err(ArchiveError::Unsupported(_))
+        ));
+    }
+}
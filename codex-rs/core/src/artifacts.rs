@@ -0,0 +1,20 @@
+//! Per-session scratch area for files produced by tools (for example members
+//! extracted from archives). Artifacts live under
+//! `$CODEX_HOME/artifacts/<conversation id>/` so that collecting them never
+//! writes into the user's workspace.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ConversationId;
+
+use crate::config::xdg::codex_state_home;
+
+pub const ARTIFACTS_SUBDIR: &str = "artifacts";
+
+/// Directory that holds every artifact captured for `conversation_id`.
+pub fn session_artifacts_dir(codex_home: &Path, conversation_id: ConversationId) -> PathBuf {
+    codex_state_home(codex_home)
+        .join(ARTIFACTS_SUBDIR)
+        .join(conversation_id.to_string())
+}
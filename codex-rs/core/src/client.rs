@@ -42,7 +42,6 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::features::FEATURES;
@@ -50,6 +49,7 @@ use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::openai_models::model_family::ModelFamily;
+use crate::provider_health;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
@@ -159,8 +159,9 @@ impl ModelClient {
             let api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            let base_url = api_provider.base_url.clone();
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = ReqwestTransport::new(self.provider.http_client());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -175,14 +176,22 @@ impl ModelClient {
                 .await;
 
             match stream_result {
-                Ok(stream) => return Ok(stream),
+                Ok(stream) => {
+                    provider_health::record_success(&base_url);
+                    return Ok(stream);
+                }
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
                     if status == StatusCode::UNAUTHORIZED =>
                 {
                     handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
                     continue;
                 }
-                Err(err) => return Err(map_api_error(err)),
+                Err(err) => {
+                    if self.should_fail_over(&base_url, &err) {
+                        continue;
+                    }
+                    return Err(map_api_error(err));
+                }
             }
         }
     }
@@ -248,8 +257,9 @@ impl ModelClient {
             let api_provider = self
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            let base_url = api_provider.base_url.clone();
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = ReqwestTransport::new(self.provider.http_client());
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -271,6 +281,7 @@ impl ModelClient {
 
             match stream_result {
                 Ok(stream) => {
+                    provider_health::record_success(&base_url);
                     return Ok(map_response_stream(stream, self.otel_manager.clone()));
                 }
                 Err(ApiError::Transport(TransportError::Http { status, .. }))
@@ -279,11 +290,23 @@ impl ModelClient {
                     handle_unauthorized(status, &mut refreshed, &auth_manager, &auth).await?;
                     continue;
                 }
-                Err(err) => return Err(map_api_error(err)),
+                Err(err) => {
+                    if self.should_fail_over(&base_url, &err) {
+                        continue;
+                    }
+                    return Err(map_api_error(err));
+                }
             }
         }
     }
 
+    /// Records a failed request and reports whether to retry it against the
+    /// next healthy endpoint. Each endpoint can trigger failover at most once
+    /// per cooldown, so the request loop always terminates.
+    fn should_fail_over(&self, base_url: &str, err: &ApiError) -> bool {
+        self.provider.fallback_base_urls.is_some() && provider_health::record_failure(base_url, err)
+    }
+
     pub fn get_provider(&self) -> ModelProviderInfo {
         self.provider.clone()
     }
@@ -334,7 +357,7 @@ impl ModelClient {
             .provider
             .to_api_provider(auth.as_ref().map(|a| a.mode))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = ReqwestTransport::new(self.provider.http_client());
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
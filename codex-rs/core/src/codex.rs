@@ -1,28 +1,46 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::SandboxState;
+use crate::approvals_policy::ApprovalsPolicy;
 use crate::client_common::REVIEW_PROMPT;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
+use crate::config::reload as config_reload;
+use crate::config::reload::ConfigWatchState;
+use crate::config::reload::LiveSetting;
+use crate::config::reload::WatchedConfigFiles;
+use crate::connection_prewarm::ConnectionPrewarmer;
 use crate::exec_policy::load_exec_policy_for_features;
+use crate::exploration::EXPLORATION_ENDED_MESSAGE;
+use crate::exploration::EXPLORATION_REJECTION;
+use crate::exploration::EXPLORATION_STARTED_MESSAGE;
+use crate::exploration::ExplorationStatus;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::idle_summarization::IdleSummarizer;
 use crate::openai_models::model_family::ModelFamily;
 use crate::openai_models::models_manager::ModelsManager;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::plan_mode::PLAN_MODE_ENDED_MESSAGE;
+use crate::plan_mode::PLAN_MODE_REJECTION;
+use crate::plan_mode::PLAN_MODE_STARTED_MESSAGE;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
+use crate::stream_events_utils::record_interrupted_reply;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
 use crate::user_notification::UserNotifier;
@@ -30,6 +48,7 @@ use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
 use codex_protocol::ConversationId;
+use codex_protocol::approvals::CommandApprovalRule;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::items::TurnItem;
 use codex_protocol::protocol::FileChange;
@@ -42,6 +61,7 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TaskStartedEvent;
 use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::protocol::TurnCapabilityGrant;
 use codex_protocol::protocol::TurnContextItem;
 use codex_rmcp_client::ElicitationResponse;
 use futures::future::BoxFuture;
@@ -61,6 +81,7 @@ use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
+use toml::Value as TomlValue;
 use tracing::Instrument;
 use tracing::debug;
 use tracing::error;
@@ -89,6 +110,7 @@ use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::file_versions::FileVersions;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
@@ -98,6 +120,7 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::ConfigReloadedEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
@@ -108,8 +131,11 @@ use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
 use crate::protocol::ReviewDecision;
+use crate::protocol::RunLimit;
+use crate::protocol::RunLimitReachedEvent;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
+use crate::protocol::SessionTitleEvent;
 use crate::protocol::SkillErrorInfo;
 use crate::protocol::SkillMetadata as ProtocolSkillMetadata;
 use crate::protocol::StreamErrorEvent;
@@ -122,6 +148,9 @@ use crate::protocol::WarningEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
+use crate::run_limits;
+use crate::sandboxing::ContainerSandbox;
+use crate::sandboxing::SandboxNetworkProxy;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -132,6 +161,10 @@ use crate::skills::build_skill_injections;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
 use crate::state::SessionState;
+use crate::steering::SteeringConstraintSet;
+use crate::steering::discover_steering;
+use crate::steering::render_steering_section;
+use crate::steering::steering_project_root;
 use crate::tasks::GhostSnapshotTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
@@ -143,11 +176,17 @@ use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
+use crate::turn_grants::apply_turn_grants;
+use crate::turn_grants::grants_touch_sandbox;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_instructions::DeveloperInstructions;
+use crate::user_instructions::SteeringInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
+use crate::workspace_events::WorkspaceEvent;
+use crate::workspace_events::WorkspaceEventBus;
+use crate::workspace_events::WorkspaceEventCtx;
 use codex_async_utils::OrCancelExt;
 use codex_execpolicy::Policy as ExecPolicy;
 use codex_otel::otel_manager::OtelManager;
@@ -206,6 +245,67 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Starts the proxy that lets sandboxed commands reach
+/// `network.allowed_domains`. Without it, commands simply have no network.
+async fn start_sandbox_network_proxy(
+    config: &Config,
+    post_session_configured_events: &mut Vec<Event>,
+) -> Option<SandboxNetworkProxy> {
+    let policy_allows_network = config
+        .approvals_policy
+        .as_ref()
+        .is_some_and(ApprovalsPolicy::allows_network);
+    if config.network.allowed_domains.is_empty() && !policy_allows_network {
+        return None;
+    }
+    let message = if !cfg!(target_os = "macos") {
+        "`network.allowed_domains` is only supported by the macOS sandbox; sandboxed commands have no network access.".to_string()
+    } else {
+        match SandboxNetworkProxy::start(
+            &config.network.allowed_domains,
+            config.approvals_policy.clone(),
+        )
+        .await
+        {
+            Ok(proxy) => return Some(proxy),
+            Err(err) => format!(
+                "Failed to start the proxy for `network.allowed_domains`; sandboxed commands have no network access: {err}"
+            ),
+        }
+    };
+    warn!("{message}");
+    post_session_configured_events.push(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::Warning(WarningEvent { message }),
+    });
+    None
+}
+
+/// Discovers the enabled steering files for `config`, logging the ones that
+/// could not be loaded.
+fn load_steering(config: &Config) -> (Option<String>, SteeringConstraintSet) {
+    if !config.features.enabled(Feature::Steering) {
+        return (None, SteeringConstraintSet::default());
+    }
+    let discovery = discover_steering(config);
+    for err in &discovery.errors {
+        error!(
+            "failed to load steering file {}: {}",
+            err.path.display(),
+            err.message
+        );
+    }
+    for path in &discovery.skipped {
+        warn!(
+            "steering file {} skipped: exceeds the steering byte budget",
+            path.display()
+        );
+    }
+    let constraints =
+        SteeringConstraintSet::new(steering_project_root(&config.cwd), discovery.constraints);
+    (render_steering_section(&discovery.files), constraints)
+}
+
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub async fn spawn(
@@ -242,6 +342,8 @@ impl Codex {
         )
         .await;
 
+        let (steering_instructions, steering_constraints) = load_steering(&config);
+
         let exec_policy = load_exec_policy_for_features(&config.features, &config.codex_home)
             .await
             .map_err(|err| CodexErr::Fatal(format!("failed to load execpolicy: {err}")))?;
@@ -261,6 +363,8 @@ impl Codex {
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions,
+            steering_instructions,
+            steering_constraints: Arc::new(steering_constraints),
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -364,6 +468,9 @@ pub(crate) struct TurnContext {
     pub(crate) base_instructions: Option<String>,
     pub(crate) compact_prompt: Option<String>,
     pub(crate) user_instructions: Option<String>,
+    pub(crate) steering_instructions: Option<String>,
+    /// Rules from steering frontmatter that every patch must satisfy.
+    pub(crate) steering_constraints: Arc<SteeringConstraintSet>,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) shell_environment_policy: ShellEnvironmentPolicy,
@@ -374,6 +481,8 @@ pub(crate) struct TurnContext {
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) exec_policy: Arc<RwLock<ExecPolicy>>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Capabilities granted for this turn only via `Op::GrantTurnCapability`.
+    pub(crate) turn_grants: Vec<TurnCapabilityGrant>,
 }
 
 impl TurnContext {
@@ -407,6 +516,12 @@ pub(crate) struct SessionConfiguration {
     /// Model instructions that are appended to the base instructions.
     user_instructions: Option<String>,
 
+    /// Contents of the enabled steering files, injected after the user instructions.
+    steering_instructions: Option<String>,
+
+    /// Constraints declared in steering frontmatter, enforced on patches.
+    steering_constraints: Arc<SteeringConstraintSet>,
+
     /// Base instructions override.
     base_instructions: Option<String>,
 
@@ -526,6 +641,8 @@ impl Session {
             base_instructions: session_configuration.base_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
             user_instructions: session_configuration.user_instructions.clone(),
+            steering_instructions: session_configuration.steering_instructions.clone(),
+            steering_constraints: session_configuration.steering_constraints.clone(),
             approval_policy: session_configuration.approval_policy.value(),
             sandbox_policy: session_configuration.sandbox_policy.clone(),
             shell_environment_policy: per_turn_config.shell_environment_policy.clone(),
@@ -539,6 +656,7 @@ impl Session {
                 per_turn_config.as_ref(),
                 model_family.truncation_policy,
             ),
+            turn_grants: Vec::new(),
         }
     }
 
@@ -563,6 +681,8 @@ impl Session {
                 session_configuration.cwd
             ));
         }
+        // Sub-agents share their parent's config, which already watches it.
+        let watch_config_files = !matches!(session_source, SessionSource::SubAgent(_));
 
         let (conversation_id, rollout_params) = match &initial_history {
             InitialHistory::New | InitialHistory::Forked(_) => {
@@ -622,7 +742,35 @@ impl Session {
                 msg: EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }),
             });
         }
+        for key in &config.deprecated_keys {
+            let (from, to) = (key.from_key(), key.to_key());
+            let summary = format!("`{from}` is deprecated. Use `{to}` instead.");
+            let details = if key.superseded {
+                format!(
+                    "`{to}` is also set, so `{from}` is ignored. Run `codex config migrate` to clean up config.toml."
+                )
+            } else {
+                "Run `codex config migrate` to update config.toml.".to_string()
+            };
+            post_session_configured_events.push(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::DeprecationNotice(DeprecationNoticeEvent {
+                    summary,
+                    details: Some(details),
+                }),
+            });
+        }
         maybe_push_chat_wire_api_deprecation(&config, &mut post_session_configured_events);
+        for message in &config.feature_warnings {
+            post_session_configured_events.push(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: message.clone(),
+                }),
+            });
+        }
+        let sandbox_network_proxy =
+            start_sandbox_network_proxy(&config, &mut post_session_configured_events).await;
 
         // todo(aibrahim): why are we passing model here while it can change?
         let otel_manager = OtelManager::new(
@@ -662,6 +810,7 @@ impl Session {
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
+            config_reload_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
             notifier: UserNotifier::new(config.notify.clone()),
             rollout: Mutex::new(Some(rollout_recorder)),
@@ -672,6 +821,12 @@ impl Session {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            connection_prewarmer: ConnectionPrewarmer::default(),
+            idle_summarizer: IdleSummarizer::default(),
+            workspace_events: WorkspaceEventBus::with_default_subscribers(),
+            file_versions: FileVersions::default(),
+            sandbox_network_proxy,
+            container_sandbox: config.container_sandbox.clone().map(ContainerSandbox::new),
         };
 
         let sess = Arc::new(Session {
@@ -708,6 +863,12 @@ impl Session {
             sess.send_event_raw(event).await;
         }
 
+        if let Some(base_url) = sess.prewarm_base_url(&session_configuration.provider) {
+            sess.services
+                .connection_prewarmer
+                .keep_warm(base_url, session_configuration.provider.http_client());
+        }
+
         // Construct sandbox_state before initialize() so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
         let sandbox_state = SandboxState {
@@ -720,7 +881,7 @@ impl Session {
             .write()
             .await
             .initialize(
-                config.mcp_servers.clone(),
+                config.mcp_servers_for_session(),
                 config.mcp_oauth_credentials_store_mode,
                 auth_statuses.clone(),
                 tx_event.clone(),
@@ -732,13 +893,266 @@ impl Session {
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
 
+        if watch_config_files {
+            sess.spawn_config_reload_watcher(config.codex_home.clone());
+        }
+
         Ok(sess)
     }
 
+    pub(crate) fn conversation_id(&self) -> ConversationId {
+        self.conversation_id
+    }
+
     pub(crate) fn get_tx_event(&self) -> Sender<Event> {
         self.tx_event.clone()
     }
 
+    /// The endpoint to keep warm for `provider`, when `connection_prewarm` is
+    /// enabled.
+    pub(crate) fn prewarm_base_url(&self, provider: &ModelProviderInfo) -> Option<String> {
+        if !self.enabled(Feature::ConnectionPrewarm) {
+            return None;
+        }
+        let auth_mode = self.services.auth_manager.auth().map(|auth| auth.mode);
+        Some(provider.active_base_url(auth_mode))
+    }
+
+    /// Warms the provider connection while the submitted turn is assembled.
+    async fn prewarm_connection_for_turn(&self) {
+        let provider = self
+            .state
+            .lock()
+            .await
+            .session_configuration
+            .provider
+            .clone();
+        if let Some(base_url) = self.prewarm_base_url(&provider) {
+            self.services
+                .connection_prewarmer
+                .on_submit(base_url, provider.http_client());
+        }
+    }
+
+    /// Polls the config files and applies edits to this session until it
+    /// shuts down.
+    fn spawn_config_reload_watcher(self: &Arc<Self>, codex_home: PathBuf) {
+        let session = Arc::downgrade(self);
+        let cancel = self.services.config_reload_cancellation_token.clone();
+        tokio::spawn(async move {
+            let mut watch = ConfigWatchState::default();
+            loop {
+                let Some(sess) = session.upgrade() else {
+                    break;
+                };
+                sess.reload_config_files(&codex_home, &mut watch).await;
+                drop(sess);
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(config_reload::POLL_INTERVAL) => {}
+                }
+            }
+        });
+    }
+
+    async fn reload_config_files(&self, codex_home: &Path, watch: &mut ConfigWatchState) {
+        let cwd = self.state.lock().await.session_configuration.cwd.clone();
+        let files = match config_reload::load_config_files(codex_home, &cwd).await {
+            Ok(files) => files,
+            Err(err) => return self.report_config_reload_error(watch, err).await,
+        };
+        if watch
+            .watched
+            .as_ref()
+            .is_some_and(|watched| watched.files == files)
+        {
+            return;
+        }
+        let config = match config_reload::load_config_from_files(codex_home, &cwd).await {
+            Ok(config) => config,
+            Err(err) => return self.report_config_reload_error(watch, err).await,
+        };
+        watch.last_error = None;
+
+        // The first snapshot is what the session started from.
+        if let Some(previous) = watch.watched.take() {
+            let event = self
+                .apply_config_file_changes(&previous.files, &previous.config, &files, &config)
+                .await;
+            self.send_event_raw(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::ConfigReloaded(event),
+            })
+            .await;
+        }
+        watch.watched = Some(WatchedConfigFiles { files, config });
+    }
+
+    /// Warns once per distinct error, typically while a file is mid-edit.
+    async fn report_config_reload_error(&self, watch: &mut ConfigWatchState, err: io::Error) {
+        let message = err.to_string();
+        if watch.last_error.as_deref() == Some(message.as_str()) {
+            return;
+        }
+        warn!("failed to reload config: {message}");
+        self.send_event_raw(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::Warning(WarningEvent {
+                message: format!("Could not reload config: {message}. Keeping current settings."),
+            }),
+        })
+        .await;
+        watch.last_error = Some(message);
+    }
+
+    async fn apply_config_file_changes(
+        &self,
+        old_files: &TomlValue,
+        old_config: &Config,
+        new_files: &TomlValue,
+        new_config: &Config,
+    ) -> ConfigReloadedEvent {
+        let mut applied = Vec::new();
+        let mut requires_restart = Vec::new();
+        let mut overridden = Vec::new();
+        let mut settings = Vec::new();
+        for key in config_reload::changed_keys(old_files, new_files) {
+            let Some(setting) = LiveSetting::for_key(&key) else {
+                requires_restart.push(key);
+                continue;
+            };
+            if setting.differs(old_config, new_config)
+                && self.follows_config_files(setting, old_config).await
+            {
+                if !settings.contains(&setting) {
+                    settings.push(setting);
+                }
+                applied.push(key);
+            } else {
+                overridden.push(key);
+            }
+        }
+
+        let mut updates = SessionSettingsUpdate::default();
+        for setting in settings {
+            match setting {
+                LiveSetting::Model => {
+                    let model = self
+                        .services
+                        .models_manager
+                        .get_model(&new_config.model, new_config)
+                        .await;
+                    updates.model = Some(model);
+                }
+                LiveSetting::ReasoningEffort => {
+                    updates.reasoning_effort = Some(new_config.model_reasoning_effort);
+                }
+                LiveSetting::ReasoningSummary => {
+                    updates.reasoning_summary = Some(new_config.model_reasoning_summary);
+                }
+                LiveSetting::Notify => {
+                    self.services
+                        .notifier
+                        .set_notify_command(new_config.notify.clone());
+                }
+                LiveSetting::Steering => self.reload_steering(new_config).await,
+            }
+        }
+        // Model and reasoning settings carry no constraints, and a rejected
+        // update is already logged.
+        let _ = self.update_settings(updates).await;
+
+        let state = self.state.lock().await;
+        ConfigReloadedEvent {
+            applied,
+            requires_restart,
+            overridden,
+            model: state.session_configuration.model.clone(),
+            reasoning_effort: state.session_configuration.model_reasoning_effort,
+        }
+    }
+
+    /// Whether the session still uses the value the config files gave
+    /// `setting`, rather than one from a flag, a profile, or `/model`.
+    async fn follows_config_files(&self, setting: LiveSetting, files: &Config) -> bool {
+        let session_configuration = self.state.lock().await.session_configuration.clone();
+        match setting {
+            LiveSetting::Model => {
+                let model = self
+                    .services
+                    .models_manager
+                    .get_model(&files.model, files)
+                    .await;
+                session_configuration.model == model
+            }
+            LiveSetting::ReasoningEffort => {
+                session_configuration.model_reasoning_effort == files.model_reasoning_effort
+            }
+            LiveSetting::ReasoningSummary => {
+                session_configuration.model_reasoning_summary == files.model_reasoning_summary
+            }
+            LiveSetting::Notify => self.services.notifier.notify_command() == files.notify,
+            LiveSetting::Steering => {
+                let config = &session_configuration.original_config_do_not_use;
+                config.steering == files.steering
+                    && config.features.enabled(Feature::Steering)
+                        == files.features.enabled(Feature::Steering)
+            }
+        }
+    }
+
+    /// Re-discovers steering files with the reloaded settings. Constraints
+    /// apply to the next patch; new steering text is recorded when the next
+    /// turn starts.
+    async fn reload_steering(&self, files: &Config) {
+        let mut config = (*self
+            .state
+            .lock()
+            .await
+            .session_configuration
+            .original_config_do_not_use)
+            .clone();
+        config.steering = files.steering.clone();
+        if files.features.enabled(Feature::Steering) {
+            config.features.enable(Feature::Steering);
+        } else {
+            config.features.disable(Feature::Steering);
+        }
+        let (instructions, constraints) = load_steering(&config);
+
+        let mut state = self.state.lock().await;
+        if instructions != state.session_configuration.steering_instructions {
+            state.steering_update = instructions.clone();
+        }
+        state.session_configuration.steering_instructions = instructions;
+        state.session_configuration.steering_constraints = Arc::new(constraints);
+        state.session_configuration.original_config_do_not_use = Arc::new(config);
+        drop(state);
+
+        self.services
+            .workspace_events
+            .publish(
+                WorkspaceEventCtx::outside_turn(self),
+                WorkspaceEvent::SteeringReloaded,
+            )
+            .await;
+    }
+
+    async fn take_steering_update(&self) -> Option<ResponseItem> {
+        let text = self.state.lock().await.steering_update.take()?;
+        Some(SteeringInstructions { text }.into())
+    }
+
+    async fn take_plan_mode_update(&self) -> Option<ResponseItem> {
+        let enabled = self.state.lock().await.plan_mode_update.take()?;
+        let instructions = if enabled {
+            PLAN_MODE_STARTED_MESSAGE
+        } else {
+            PLAN_MODE_ENDED_MESSAGE
+        };
+        Some(DeveloperInstructions::new(instructions).into())
+    }
+
     /// Ensure all rollout writes are durably flushed.
     pub(crate) async fn flush_rollout(&self) {
         let recorder = {
@@ -759,6 +1173,83 @@ impl Session {
         format!("auto-compact-{id}")
     }
 
+    /// Counts the next model request against the exploration budget and tells
+    /// the model (and the user) when the read-only phase starts or ends.
+    async fn next_exploration_status(&self, turn_context: &TurnContext) -> ExplorationStatus {
+        let budget = turn_context.client.config().autonomy.exploration_budget;
+        let autonomous = turn_context.approval_policy == AskForApproval::Never;
+        let status = {
+            let mut state = self.state.lock().await;
+            let tokens_used = state
+                .token_info()
+                .map(|info| info.total_token_usage.blended_total())
+                .unwrap_or(0);
+            state
+                .exploration
+                .next_request(budget, autonomous, tokens_used)
+        };
+
+        let (instructions, message) = match status {
+            ExplorationStatus::Started => (
+                EXPLORATION_STARTED_MESSAGE,
+                "Exploration phase started: editing tools are disabled until the budget is spent.",
+            ),
+            ExplorationStatus::Ended => (
+                EXPLORATION_ENDED_MESSAGE,
+                "Exploration phase ended: editing tools are enabled.",
+            ),
+            ExplorationStatus::Inactive | ExplorationStatus::Exploring => return status,
+        };
+        self.record_conversation_items(
+            turn_context,
+            &[DeveloperInstructions::new(instructions).into()],
+        )
+        .await;
+        self.send_event(
+            turn_context,
+            EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: message.to_string(),
+            }),
+        )
+        .await;
+        status
+    }
+
+    /// Counts the next model request against the run limits. Returns the
+    /// deadline the request must finish by, or the limit that ends the task
+    /// before it.
+    async fn next_run_limit_check(
+        &self,
+        turn_context: &TurnContext,
+    ) -> Result<Option<Instant>, RunLimitReachedEvent> {
+        let limits = turn_context.client.config().autonomy.run_limits;
+        if limits.is_empty() {
+            return Ok(None);
+        }
+        let cost_usd = self.estimated_cost_usd(turn_context).await;
+        let mut state = self.state.lock().await;
+        state.run_progress.next_request(&limits, cost_usd)
+    }
+
+    async fn run_limit_event(
+        &self,
+        turn_context: &TurnContext,
+        limit: RunLimit,
+    ) -> RunLimitReachedEvent {
+        let cost_usd = self.estimated_cost_usd(turn_context).await;
+        let state = self.state.lock().await;
+        state.run_progress.event(limit, cost_usd)
+    }
+
+    /// The session's spend so far, priced with `model_prices`. `None` when
+    /// the model has no price.
+    async fn estimated_cost_usd(&self, turn_context: &TurnContext) -> Option<f64> {
+        let config = turn_context.client.config();
+        let price = config.model_prices.get(&turn_context.client.get_model())?;
+        let usage = self.state.lock().await.token_info()?.total_token_usage;
+        Some(price.cost_usd(&usage))
+    }
+
     async fn get_total_token_usage(&self) -> i64 {
         let state = self.state.lock().await;
         state.get_total_token_usage()
@@ -806,6 +1297,9 @@ impl Session {
                     }
                 }
 
+                // A recorded title also keeps later turns from generating one.
+                self.state.lock().await.title = crate::session_title::latest_title(&rollout_items);
+
                 // Always add response items to conversation history
                 let reconstructed_history =
                     self.reconstruct_history_from_rollout(&turn_context, &rollout_items);
@@ -850,14 +1344,19 @@ impl Session {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed) = {
+        let (session_configuration, turn_grants, sandbox_policy_changed) = {
             let mut state = self.state.lock().await;
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    let turn_grants = std::mem::take(&mut state.pending_turn_grants);
+                    let sandbox_granted = grants_touch_sandbox(&turn_grants);
+                    let sandbox_policy_changed = state.session_configuration.sandbox_policy
+                        != next.sandbox_policy
+                        || sandbox_granted
+                        || state.last_turn_sandbox_granted;
+                    state.last_turn_sandbox_granted = sandbox_granted;
                     state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed)
+                    (next, turn_grants, sandbox_policy_changed)
                 }
                 Err(err) => {
                     drop(state);
@@ -881,6 +1380,7 @@ impl Session {
             .new_turn_from_configuration(
                 sub_id,
                 session_configuration,
+                turn_grants,
                 updates.final_output_json_schema,
                 sandbox_policy_changed,
             )
@@ -890,11 +1390,24 @@ impl Session {
     async fn new_turn_from_configuration(
         &self,
         sub_id: String,
-        session_configuration: SessionConfiguration,
+        mut session_configuration: SessionConfiguration,
+        turn_grants: Vec<TurnCapabilityGrant>,
         final_output_json_schema: Option<Option<Value>>,
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
-        let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        let mut per_turn_config = Self::build_per_turn_config(&session_configuration);
+        if !turn_grants.is_empty() {
+            apply_turn_grants(
+                &turn_grants,
+                &mut session_configuration.sandbox_policy,
+                &mut per_turn_config.features,
+            );
+            per_turn_config.sandbox_policy = session_configuration.sandbox_policy.clone();
+            info!(
+                ?turn_grants,
+                "applying capability grants for this turn only"
+            );
+        }
 
         if sandbox_policy_changed {
             let sandbox_state = SandboxState {
@@ -932,6 +1445,7 @@ impl Session {
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
         }
+        turn_context.turn_grants = turn_grants;
         Arc::new(turn_context)
     }
 
@@ -945,7 +1459,7 @@ impl Session {
             let state = self.state.lock().await;
             state.session_configuration.clone()
         };
-        self.new_turn_from_configuration(sub_id, session_configuration, None, false)
+        self.new_turn_from_configuration(sub_id, session_configuration, Vec::new(), None, false)
             .await
     }
 
@@ -1059,6 +1573,28 @@ impl Session {
         Ok(())
     }
 
+    /// `CODEX_HOME` and the project directory whose approval rules apply.
+    async fn approval_rules_location(&self) -> (PathBuf, PathBuf) {
+        let state = self.state.lock().await;
+        (
+            state
+                .session_configuration
+                .original_config_do_not_use
+                .codex_home
+                .clone(),
+            state.session_configuration.cwd.clone(),
+        )
+    }
+
+    /// Records `rule` as always allowed in the session's project.
+    pub(crate) async fn persist_approval_rule(
+        &self,
+        rule: &CommandApprovalRule,
+    ) -> std::io::Result<()> {
+        let (codex_home, cwd) = self.approval_rules_location().await;
+        crate::config::trust_store::record_approval_rule(&codex_home, &cwd, rule.clone())
+    }
+
     /// Emit an exec approval request event and await the user's decision.
     ///
     /// The request is keyed by `sub_id`/`call_id` so matching responses are delivered
@@ -1074,6 +1610,13 @@ impl Session {
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     ) -> ReviewDecision {
+        let (codex_home, project) = self.approval_rules_location().await;
+        let rules = crate::config::trust_store::load_approval_rules(&codex_home, &project);
+        if crate::config::trust_store::approval_rules_allow(&rules, &command) {
+            info!("command approved by a project approval rule: {command:?}");
+            return ReviewDecision::Approved;
+        }
+
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
         let (tx_approve, rx_approve) = oneshot::channel();
@@ -1254,6 +1797,7 @@ impl Session {
     pub(crate) async fn replace_history(&self, items: Vec<ResponseItem>) {
         let mut state = self.state.lock().await;
         state.replace_history(items);
+        self.services.file_versions.clear();
     }
 
     async fn persist_rollout_response_items(&self, items: &[ResponseItem]) {
@@ -1284,7 +1828,7 @@ impl Session {
     }
 
     pub(crate) fn build_initial_context(&self, turn_context: &TurnContext) -> Vec<ResponseItem> {
-        let mut items = Vec::<ResponseItem>::with_capacity(3);
+        let mut items = Vec::<ResponseItem>::with_capacity(4);
         let shell = self.user_shell();
         if let Some(developer_instructions) = turn_context.developer_instructions.as_deref() {
             items.push(DeveloperInstructions::new(developer_instructions.to_string()).into());
@@ -1298,6 +1842,14 @@ impl Session {
                 .into(),
             );
         }
+        if let Some(steering_instructions) = turn_context.steering_instructions.as_deref() {
+            items.push(
+                SteeringInstructions {
+                    text: steering_instructions.to_string(),
+                }
+                .into(),
+            );
+        }
         items.push(ResponseItem::from(EnvironmentContext::new(
             Some(turn_context.cwd.clone()),
             Some(turn_context.approval_policy),
@@ -1324,6 +1876,58 @@ impl Session {
         state.clone_history()
     }
 
+    /// Swaps a tool output in history for `replacement` unless it changed
+    /// since it was read as `expected`.
+    pub(crate) async fn replace_tool_output(
+        &self,
+        call_id: &str,
+        expected: &str,
+        replacement: String,
+    ) -> bool {
+        let mut state = self.state.lock().await;
+        let replaced = state
+            .history
+            .replace_tool_output(call_id, expected, replacement);
+        if replaced {
+            self.services.file_versions.clear();
+        }
+        replaced
+    }
+
+    /// Claims this session's title request. False when the session has a
+    /// title already or a request is in flight.
+    pub(crate) async fn claim_title_request(&self) -> bool {
+        let mut state = self.state.lock().await;
+        if state.title.is_some() || state.title_requested {
+            return false;
+        }
+        state.title_requested = true;
+        true
+    }
+
+    /// Lets a later turn try again after a title request came back empty.
+    pub(crate) async fn release_title_request(&self) {
+        self.state.lock().await.title_requested = false;
+    }
+
+    /// Records `title` and announces it, which also writes it to the rollout.
+    /// Unless `replace` is set, an existing title wins, so a rename that lands
+    /// while a generated title is in flight sticks.
+    pub(crate) async fn set_title(&self, sub_id: String, title: String, replace: bool) {
+        {
+            let mut state = self.state.lock().await;
+            if !replace && state.title.is_some() {
+                return;
+            }
+            state.title = Some(title.clone());
+        }
+        self.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::SessionTitle(SessionTitleEvent { title }),
+        })
+        .await;
+    }
+
     pub(crate) async fn update_token_usage_info(
         &self,
         turn_context: &TurnContext,
@@ -1387,11 +1991,16 @@ impl Session {
     }
 
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
-        let (info, rate_limits) = {
+        let (info, rate_limits, prompt_breakdown) = {
             let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+            let (info, rate_limits) = state.token_info_and_rate_limits();
+            (info, rate_limits, state.prompt_token_breakdown())
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            prompt_breakdown: Some(prompt_breakdown),
+        });
         self.send_event(turn_context, event).await;
     }
 
@@ -1624,6 +2233,12 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 )
                 .await;
             }
+            Op::GrantTurnCapability { grant } => {
+                handlers::grant_turn_capability(&sess, sub.id.clone(), grant).await;
+            }
+            Op::SetPlanMode { enabled } => {
+                handlers::set_plan_mode(&sess, sub.id.clone(), enabled).await;
+            }
             Op::UserInput { .. } | Op::UserTurn { .. } => {
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
@@ -1641,12 +2256,21 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::get_history_entry_request(&sess, &config, sub.id.clone(), offset, log_id)
                     .await;
             }
+            Op::SearchHistoryRequest { query } => {
+                handlers::search_history_request(&sess, &config, sub.id.clone(), query).await;
+            }
             Op::ListMcpTools => {
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
+            Op::AddMcpServer { name, command } => {
+                handlers::add_mcp_server(&sess, &config, sub.id.clone(), name, command).await;
+            }
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::SetSessionTitle { title } => {
+                handlers::set_session_title(&sess, sub.id.clone(), title).await;
+            }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
@@ -1692,17 +2316,21 @@ mod handlers {
     use crate::codex::SessionSettingsUpdate;
     use crate::codex::TurnContext;
 
+    use crate::SandboxState;
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
     use crate::features::Feature;
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
+    use crate::mcp::session_mcp_server_config;
     use crate::review_prompts::resolve_review_request;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::turn_grants::validate_turn_grant;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::protocol::BackgroundEventEvent;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
@@ -1714,6 +2342,7 @@ mod handlers {
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::TurnCapabilityGrant;
     use codex_protocol::protocol::WarningEvent;
 
     use codex_protocol::user_input::UserInput;
@@ -1746,6 +2375,76 @@ mod handlers {
         }
     }
 
+    /// Queue a capability grant for the next turn. Grants are rejected while a
+    /// task is running because follow-up input would be injected into the
+    /// current turn rather than starting the one the grant is meant for.
+    pub async fn grant_turn_capability(
+        sess: &Arc<Session>,
+        sub_id: String,
+        grant: TurnCapabilityGrant,
+    ) {
+        let result = if sess.active_turn.lock().await.is_some() {
+            Err("capabilities can only be granted between turns".to_string())
+        } else {
+            let mut state = sess.state.lock().await;
+            let configuration = &state.session_configuration;
+            validate_turn_grant(
+                grant,
+                &configuration.sandbox_policy,
+                configuration.cwd.as_path(),
+            )
+            .map(|grant| {
+                if !state.pending_turn_grants.contains(&grant) {
+                    state.pending_turn_grants.push(grant.clone());
+                }
+                grant
+            })
+        };
+
+        let msg = match result {
+            Ok(grant) => {
+                info!(%grant, "granted capability for the next turn");
+                EventMsg::BackgroundEvent(BackgroundEventEvent {
+                    message: format!("Granted {grant} for the next turn only."),
+                })
+            }
+            Err(message) => EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn set_plan_mode(sess: &Arc<Session>, sub_id: String, enabled: bool) {
+        let msg = if sess.active_turn.lock().await.is_some() {
+            EventMsg::Error(ErrorEvent {
+                message: "plan mode can only be changed between turns".to_string(),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            })
+        } else {
+            let mut state = sess.state.lock().await;
+            if state.plan_mode != enabled {
+                state.plan_mode = enabled;
+                // Toggling back before the next turn leaves nothing to tell
+                // the model.
+                state.plan_mode_update = match state.plan_mode_update {
+                    Some(_) => None,
+                    None => Some(enabled),
+                };
+            }
+            let message = if enabled {
+                "Plan mode on: editing tools are disabled and the model will reply with a plan."
+            } else {
+                "Plan mode off: editing tools are enabled."
+            };
+            EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: message.to_string(),
+            })
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn user_input_or_turn(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1777,6 +2476,7 @@ mod handlers {
             Op::UserInput { items } => (items, SessionSettingsUpdate::default()),
             _ => unreachable!(),
         };
+        sess.prewarm_connection_for_turn().await;
 
         let Ok(current_context) = sess.new_turn_with_sub_id(sub_id, updates).await else {
             // new_turn_with_sub_id already emits the error event.
@@ -1795,6 +2495,20 @@ mod handlers {
                 sess.record_conversation_items(&current_context, std::slice::from_ref(&env_item))
                     .await;
             }
+            if let Some(steering_item) = sess.take_steering_update().await {
+                sess.record_conversation_items(
+                    &current_context,
+                    std::slice::from_ref(&steering_item),
+                )
+                .await;
+            }
+            if let Some(plan_mode_item) = sess.take_plan_mode_update().await {
+                sess.record_conversation_items(
+                    &current_context,
+                    std::slice::from_ref(&plan_mode_item),
+                )
+                .await;
+            }
 
             sess.spawn_task(Arc::clone(&current_context), items, RegularTask)
                 .await;
@@ -1863,6 +2577,18 @@ mod handlers {
             })
             .await;
         }
+        if let ReviewDecision::ApprovedForProject { rule } = &decision
+            && let Err(err) = sess.persist_approval_rule(rule).await
+        {
+            let message = format!("Failed to save approval rule: {err}");
+            tracing::warn!("{message}");
+            let warning = EventMsg::Warning(WarningEvent { message });
+            sess.send_event_raw(Event {
+                id: id.clone(),
+                msg: warning,
+            })
+            .await;
+        }
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -1927,6 +2653,42 @@ mod handlers {
         });
     }
 
+    pub async fn search_history_request(
+        sess: &Arc<Session>,
+        config: &Arc<Config>,
+        sub_id: String,
+        query: String,
+    ) {
+        let config = Arc::clone(config);
+        let sess_clone = Arc::clone(sess);
+
+        tokio::spawn(async move {
+            let search_query = query.clone();
+            let entries = tokio::task::spawn_blocking(move || {
+                crate::message_history::search(&search_query, &config)
+            })
+            .await
+            .unwrap_or_default();
+
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::SearchHistoryResponse(crate::protocol::SearchHistoryResponseEvent {
+                    query,
+                    entries: entries
+                        .into_iter()
+                        .map(|e| codex_protocol::message_history::HistoryEntry {
+                            conversation_id: e.session_id,
+                            ts: e.ts,
+                            text: e.text,
+                        })
+                        .collect(),
+                }),
+            };
+
+            sess_clone.send_event_raw(event).await;
+        });
+    }
+
     pub async fn list_mcp_tools(sess: &Session, config: &Arc<Config>, sub_id: String) {
         let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
         let snapshot = collect_mcp_snapshot_from_manager(
@@ -1945,6 +2707,54 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn add_mcp_server(
+        sess: &Session,
+        config: &Arc<Config>,
+        sub_id: String,
+        name: String,
+        command: Vec<String>,
+    ) {
+        let result = match session_mcp_server_config(&name, command) {
+            Ok(server_config) => {
+                let sandbox_state = {
+                    let state = sess.state.lock().await;
+                    SandboxState {
+                        sandbox_policy: state.session_configuration.sandbox_policy.clone(),
+                        codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+                        sandbox_cwd: state.session_configuration.cwd.clone(),
+                    }
+                };
+                sess.services
+                    .mcp_connection_manager
+                    .write()
+                    .await
+                    .add_server(
+                        name,
+                        server_config,
+                        config.mcp_oauth_credentials_store_mode,
+                        None,
+                        sess.get_tx_event(),
+                        sess.services.mcp_startup_cancellation_token.child_token(),
+                        sandbox_state,
+                    )
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+            Err(message) => Err(message),
+        };
+
+        if let Err(message) = result {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+        }
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -1952,16 +2762,45 @@ mod handlers {
             } else {
                 Vec::new()
             };
+        let (cwd, config) = {
+            let state = sess.state.lock().await;
+            (
+                state.session_configuration.cwd.clone(),
+                Arc::clone(&state.session_configuration.original_config_do_not_use),
+            )
+        };
+        let custom_commands = crate::custom_prompts::discover_commands(
+            &config.codex_home,
+            &cwd,
+            config.load_project_files,
+        )
+        .await;
 
         let event = Event {
             id: sub_id,
             msg: EventMsg::ListCustomPromptsResponse(ListCustomPromptsResponseEvent {
                 custom_prompts,
+                custom_commands,
             }),
         };
         sess.send_event_raw(event).await;
     }
 
+    pub async fn set_session_title(sess: &Session, sub_id: String, title: String) {
+        let Some(title) = crate::session_title::normalize_title(&title) else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Session title cannot be empty.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        };
+        sess.set_title(sub_id, title, true).await;
+    }
+
     pub async fn list_skills(
         sess: &Session,
         sub_id: String,
@@ -2025,6 +2864,9 @@ mod handlers {
 
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
+        sess.services.connection_prewarmer.stop();
+        sess.services.idle_summarizer.stop();
+        sess.services.config_reload_cancellation_token.cancel();
         sess.services
             .unified_exec_manager
             .terminate_all_sessions()
@@ -2152,6 +2994,8 @@ async fn spawn_review_thread(
         ghost_snapshot: parent_turn_context.ghost_snapshot.clone(),
         developer_instructions: None,
         user_instructions: None,
+        steering_instructions: None,
+        steering_constraints: parent_turn_context.steering_constraints.clone(),
         base_instructions: Some(base_instructions.clone()),
         compact_prompt: parent_turn_context.compact_prompt.clone(),
         approval_policy: parent_turn_context.approval_policy,
@@ -2163,6 +3007,7 @@ async fn spawn_review_thread(
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         exec_policy: parent_turn_context.exec_policy.clone(),
         truncation_policy: TruncationPolicy::new(&per_turn_config, model_family.truncation_policy),
+        turn_grants: Vec::new(),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -2275,6 +3120,14 @@ pub(crate) async fn run_task(
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
 
     loop {
+        let deadline = match sess.next_run_limit_check(&turn_context).await {
+            Ok(deadline) => deadline,
+            Err(reached) => {
+                sess.send_event(&turn_context, EventMsg::RunLimitReached(reached))
+                    .await;
+                break;
+            }
+        };
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2284,6 +3137,14 @@ pub(crate) async fn run_task(
             .into_iter()
             .map(ResponseItem::from)
             .collect::<Vec<ResponseItem>>();
+        let exploration = sess.next_exploration_status(&turn_context).await;
+        let read_only = if sess.state.lock().await.plan_mode {
+            Some(PLAN_MODE_REJECTION)
+        } else if exploration.is_read_only() {
+            Some(EXPLORATION_REJECTION)
+        } else {
+            None
+        };
 
         // Construct the input that we will send to the model.
         let turn_input: Vec<ResponseItem> = {
@@ -2300,12 +3161,16 @@ pub(crate) async fn run_task(
             })
             .map(|user_message| user_message.message())
             .collect::<Vec<String>>();
+        let turn_cancellation_token = cancellation_token.child_token();
+        let _deadline = deadline
+            .map(|deadline| run_limits::cancel_at(deadline, turn_cancellation_token.clone()));
         match run_turn(
             Arc::clone(&sess),
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
             turn_input,
-            cancellation_token.child_token(),
+            read_only,
+            turn_cancellation_token,
         )
         .await
         {
@@ -2338,7 +3203,17 @@ pub(crate) async fn run_task(
                 continue;
             }
             Err(CodexErr::TurnAborted) => {
-                // Aborted turn is reported via a different event.
+                // Aborted turn is reported via a different event, unless the
+                // run's time limit cut it short.
+                if !cancellation_token.is_cancelled()
+                    && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    let reached = sess
+                        .run_limit_event(&turn_context, RunLimit::Duration)
+                        .await;
+                    sess.send_event(&turn_context, EventMsg::RunLimitReached(reached))
+                        .await;
+                }
                 break;
             }
             Err(CodexErr::InvalidImageRequest()) => {
@@ -2382,25 +3257,30 @@ async fn run_turn(
     turn_context: Arc<TurnContext>,
     turn_diff_tracker: SharedTurnDiffTracker,
     input: Vec<ResponseItem>,
+    read_only: Option<&'static str>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
-    let mcp_tools = sess
-        .services
-        .mcp_connection_manager
-        .read()
-        .await
-        .list_all_tools()
-        .or_cancel(&cancellation_token)
-        .await?;
-    let router = Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
-        Some(
-            mcp_tools
-                .into_iter()
-                .map(|(name, tool)| (name, tool.tool))
-                .collect(),
-        ),
-    ));
+    let router = if let Some(rejection) = read_only {
+        Arc::new(ToolRouter::read_only(&turn_context.tools_config, rejection))
+    } else {
+        let mcp_tools = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_tools()
+            .or_cancel(&cancellation_token)
+            .await?;
+        Arc::new(ToolRouter::from_config(
+            &turn_context.tools_config,
+            Some(
+                mcp_tools
+                    .into_iter()
+                    .map(|(name, tool)| (name, tool.tool))
+                    .collect(),
+            ),
+        ))
+    };
 
     let model_supports_parallel = turn_context
         .client
@@ -2434,6 +3314,7 @@ async fn run_turn(
             }
             Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
             Err(CodexErr::EnvVar(var)) => return Err(CodexErr::EnvVar(var)),
+            Err(e @ CodexErr::SecretCmd(_)) => return Err(e),
             Err(e @ CodexErr::Fatal(_)) => return Err(e),
             Err(e @ CodexErr::ContextWindowExceeded) => {
                 sess.set_total_tokens_full(&turn_context).await;
@@ -2553,6 +3434,9 @@ async fn try_run_turn(
     let mut needs_follow_up = false;
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
+    // Text streamed for the active assistant message, kept if the turn is
+    // interrupted before the message completes.
+    let mut partial_reply = String::new();
     let mut should_emit_turn_diff = false;
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<TurnRunResult> = loop {
@@ -2571,7 +3455,10 @@ async fn try_run_turn(
             .await
         {
             Ok(event) => event,
-            Err(codex_async_utils::CancelErr::Cancelled) => break Err(CodexErr::TurnAborted),
+            Err(codex_async_utils::CancelErr::Cancelled) => {
+                record_interrupted_reply(&sess, &turn_context, &partial_reply).await;
+                break Err(CodexErr::TurnAborted);
+            }
         };
 
         let event = match event {
@@ -2591,6 +3478,7 @@ async fn try_run_turn(
         match event {
             ResponseEvent::Created => {}
             ResponseEvent::OutputItemDone(item) => {
+                partial_reply.clear();
                 let previously_active_item = active_item.take();
                 let mut ctx = HandleOutputCtx {
                     sess: sess.clone(),
@@ -2611,6 +3499,7 @@ async fn try_run_turn(
                 needs_follow_up |= output_result.needs_follow_up;
             }
             ResponseEvent::OutputItemAdded(item) => {
+                partial_reply.clear();
                 if let Some(turn_item) = handle_non_tool_response_item(&item).await {
                     let tracked_item = turn_item.clone();
                     sess.emit_turn_item_started(&turn_context, &turn_item).await;
@@ -2640,6 +3529,7 @@ async fn try_run_turn(
                 // In review child threads, suppress assistant text deltas; the
                 // UI will show a selection popup from the final ReviewOutput.
                 if let Some(active) = active_item.as_ref() {
+                    partial_reply.push_str(&delta);
                     let event = AgentMessageContentDeltaEvent {
                         thread_id: sess.conversation_id.to_string(),
                         turn_id: turn_context.sub_id.clone(),
@@ -2764,6 +3654,7 @@ mod tests {
     use crate::protocol::RateLimitWindow;
     use crate::protocol::ResumedHistory;
     use crate::state::TaskKind;
+    use crate::stream_events_utils::INTERRUPTED_REPLY_MARKER;
     use crate::tasks::SessionTask;
     use crate::tasks::SessionTaskContext;
     use crate::tools::ToolRouter;
@@ -2831,6 +3722,32 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn interrupted_reply_is_kept_in_history_with_a_marker() {
+        let (session, turn_context) = make_session_and_context();
+
+        tokio_test::block_on(record_interrupted_reply(
+            &session,
+            &turn_context,
+            "The flake comes from \n",
+        ));
+        tokio_test::block_on(record_interrupted_reply(&session, &turn_context, "  \n"));
+
+        let history = tokio_test::block_on(async {
+            session.state.lock().await.clone_history().get_history()
+        });
+        assert_eq!(
+            history,
+            vec![ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: format!("The flake comes from\n\n{INTERRUPTED_REPLY_MARKER}"),
+                }],
+            }]
+        );
+    }
+
     #[test]
     fn set_rate_limits_retains_previous_credits() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -2849,6 +3766,8 @@ mod tests {
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            steering_instructions: None,
+            steering_constraints: Arc::default(),
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -2921,6 +3840,8 @@ mod tests {
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            steering_instructions: None,
+            steering_constraints: Arc::default(),
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -3125,6 +4046,8 @@ mod tests {
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            steering_instructions: None,
+            steering_constraints: Arc::default(),
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -3152,6 +4075,7 @@ mod tests {
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
+            config_reload_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
             notifier: UserNotifier::new(None),
             rollout: Mutex::new(None),
@@ -3162,6 +4086,12 @@ mod tests {
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            connection_prewarmer: ConnectionPrewarmer::default(),
+            idle_summarizer: IdleSummarizer::default(),
+            workspace_events: WorkspaceEventBus::with_default_subscribers(),
+            file_versions: FileVersions::default(),
+            sandbox_network_proxy: None,
+            container_sandbox: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -3216,6 +4146,8 @@ mod tests {
             model_reasoning_summary: config.model_reasoning_summary,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            steering_instructions: None,
+            steering_constraints: Arc::default(),
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -3243,6 +4175,7 @@ mod tests {
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
+            config_reload_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
             notifier: UserNotifier::new(None),
             rollout: Mutex::new(None),
@@ -3253,6 +4186,12 @@ mod tests {
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            connection_prewarmer: ConnectionPrewarmer::default(),
+            idle_summarizer: IdleSummarizer::default(),
+            workspace_events: WorkspaceEventBus::with_default_subscribers(),
+            file_versions: FileVersions::default(),
+            sandbox_network_proxy: None,
+            container_sandbox: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -186,7 +186,9 @@ pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
     let mut pieces = Vec::new();
     for item in content {
         match item {
-            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            ContentItem::InputText { text }
+            | ContentItem::OutputText { text }
+            | ContentItem::Refusal { refusal: text } => {
                 if !text.is_empty() {
                     pieces.push(text.as_str());
                 }
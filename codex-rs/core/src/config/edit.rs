@@ -664,6 +664,15 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Select the TUI color theme by name (`tui.theme`).
+    pub fn set_tui_theme(mut self, name: &str) -> Self {
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["tui".to_string(), "theme".to_string()],
+            value: value(name),
+        });
+        self
+    }
+
     pub fn with_edits<I>(mut self, edits: I) -> Self
     where
         I: IntoIterator<Item = ConfigEdit>,
@@ -0,0 +1,323 @@
+//! Where the effective value of a config key comes from.
+//!
+//! A key can be set by several layers at once (system, user, and project
+//! config, `-c` flags, managed config), by the active profile, by a project's
+//! trust decision, or by an environment variable. [`explain_key`] lists every
+//! source that sets it, highest precedence first, and marks the one that won.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use toml::Value as TomlValue;
+
+use super::migration::migrate_keys;
+use super::trust_store::ProjectTrust;
+use super::xdg::CODEX_USE_XDG_ENV_VAR;
+use super::xdg::USE_XDG_DIRS_KEY;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::toml_value_at;
+
+/// Environment variables that replace a config value, and how their value
+/// reads as TOML.
+const ENV_OVERRIDES: &[(&str, &str, fn(&str) -> TomlValue)] = &[
+    (
+        "model_providers.openai.base_url",
+        "OPENAI_BASE_URL",
+        env_string,
+    ),
+    (
+        "model_providers.oss.base_url",
+        "CODEX_OSS_BASE_URL",
+        env_string,
+    ),
+    (USE_XDG_DIRS_KEY, CODEX_USE_XDG_ENV_VAR, env_flag),
+];
+
+/// Keys a project's trust decision pins above the config files. The others
+/// it pins (`approval_policy`, `sandbox_mode`) only fill in unset values.
+const TRUST_KEYS_ABOVE_CONFIG: &[&str] = &["model", "model_reasoning_effort", "model_verbosity"];
+
+/// One place that sets a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueSource {
+    /// A config layer: a file, MDM, or `-c` flags.
+    Layer(ConfigLayerSource),
+    /// `profiles.<name>.<key>` of the active profile, set in `layer`.
+    Profile {
+        name: String,
+        layer: ConfigLayerSource,
+    },
+    /// A decision recorded with `codex trust` for the current project.
+    ProjectTrust { file: PathBuf },
+    /// An environment variable.
+    Env(&'static str),
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Layer(layer) => write_layer(f, layer),
+            ValueSource::Profile { name, layer } => {
+                write!(f, "profile `{name}` in ")?;
+                write_layer(f, layer)
+            }
+            ValueSource::ProjectTrust { file } => {
+                write!(f, "project trust decision in {}", file.display())
+            }
+            ValueSource::Env(var) => write!(f, "environment variable {var}"),
+        }
+    }
+}
+
+fn write_layer(f: &mut fmt::Formatter<'_>, layer: &ConfigLayerSource) -> fmt::Result {
+    match layer {
+        ConfigLayerSource::Mdm { domain, .. } => write!(f, "managed preferences ({domain})"),
+        ConfigLayerSource::System { file } => write!(f, "system config {}", file.display()),
+        ConfigLayerSource::User { file } => write!(f, "user config {}", file.display()),
+        ConfigLayerSource::Project { file } => write!(f, "project config {}", file.display()),
+        ConfigLayerSource::SessionFlags => write!(f, "-c/--config flag"),
+        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+            write!(f, "managed config {}", file.display())
+        }
+        ConfigLayerSource::LegacyManagedConfigTomlFromMdm => {
+            write!(f, "managed config (MDM)")
+        }
+    }
+}
+
+/// A value of the key and where it was set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedValue {
+    pub source: ValueSource,
+    pub value: TomlValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigExplanation {
+    pub key: String,
+    /// The value Codex uses, or `None` when no source sets the key.
+    pub value: Option<TomlValue>,
+    /// Index into `sources` of the source `value` comes from. `None` when
+    /// the key is unset, or when `value` is a table merged from several
+    /// sources.
+    pub winner: Option<usize>,
+    /// Every source that sets the key, highest precedence first.
+    pub sources: Vec<SourcedValue>,
+}
+
+/// Explains `key` (a dotted path) for the config in `stack`. `profile`
+/// replaces the `profile` key of the config, like `--profile` does, and
+/// `trust` is the decision stored for the current project.
+pub fn explain_key(
+    stack: &ConfigLayerStack,
+    profile: Option<&str>,
+    trust: Option<(&ProjectTrust, PathBuf)>,
+    key: &str,
+) -> ConfigExplanation {
+    let path: Vec<&str> = key.split('.').collect();
+    let layers: Vec<(ConfigLayerSource, TomlValue)> = stack
+        .layers_high_to_low()
+        .into_iter()
+        .map(|layer| {
+            let mut config = layer.config.clone();
+            migrate_keys(&mut config);
+            (layer.name.clone(), config)
+        })
+        .collect();
+    let effective = stack.effective_config();
+
+    let mut sources = Vec::new();
+    let mut value = None;
+
+    if let Some((_, var, parse)) = ENV_OVERRIDES.iter().find(|(k, ..)| *k == key)
+        && let Ok(raw) = std::env::var(var)
+        && !raw.trim().is_empty()
+    {
+        let env_value = parse(&raw);
+        value = Some(env_value.clone());
+        sources.push(SourcedValue {
+            source: ValueSource::Env(var),
+            value: env_value,
+        });
+    }
+
+    let profile = profile
+        .map(str::to_string)
+        .or_else(|| effective.get("profile")?.as_str().map(str::to_string));
+    if let Some(name) = profile.filter(|_| path.first() != Some(&"profiles")) {
+        let profile_path: Vec<&str> = ["profiles", name.as_str()]
+            .into_iter()
+            .chain(path.iter().copied())
+            .collect();
+        for (layer, config) in &layers {
+            if let Some(found) = toml_value_at(config, &profile_path) {
+                sources.push(SourcedValue {
+                    source: ValueSource::Profile {
+                        name: name.clone(),
+                        layer: layer.clone(),
+                    },
+                    value: found.clone(),
+                });
+            }
+        }
+        if value.is_none() {
+            value = toml_value_at(&effective, &profile_path).cloned();
+        }
+    }
+
+    let trust_value = trust.and_then(|(trust, file)| {
+        let value = match key {
+            "model" => TomlValue::String(trust.model.clone()?),
+            "model_reasoning_effort" => TomlValue::try_from(trust.model_reasoning_effort?).ok()?,
+            "model_verbosity" => TomlValue::try_from(trust.model_verbosity?).ok()?,
+            "approval_policy" => TomlValue::try_from(trust.approval_policy?).ok()?,
+            "sandbox_mode" => TomlValue::try_from(trust.sandbox_mode?).ok()?,
+            _ => return None,
+        };
+        Some(SourcedValue {
+            source: ValueSource::ProjectTrust { file },
+            value,
+        })
+    });
+    let trust_above_config = TRUST_KEYS_ABOVE_CONFIG.contains(&key);
+    if trust_above_config && let Some(trust_value) = &trust_value {
+        sources.push(trust_value.clone());
+        if value.is_none() {
+            value = Some(trust_value.value.clone());
+        }
+    }
+
+    for (layer, config) in &layers {
+        if let Some(found) = toml_value_at(config, &path) {
+            sources.push(SourcedValue {
+                source: ValueSource::Layer(layer.clone()),
+                value: found.clone(),
+            });
+        }
+    }
+    if value.is_none() {
+        value = toml_value_at(&effective, &path).cloned();
+    }
+
+    if !trust_above_config && let Some(trust_value) = trust_value {
+        if value.is_none() {
+            value = Some(trust_value.value.clone());
+        }
+        sources.push(trust_value);
+    }
+
+    let winner = value
+        .as_ref()
+        .and_then(|value| sources.iter().position(|source| &source.value == value));
+    ConfigExplanation {
+        key: key.to_string(),
+        value,
+        winner,
+        sources,
+    }
+}
+
+fn env_string(raw: &str) -> TomlValue {
+    TomlValue::String(raw.to_string())
+}
+
+fn env_flag(raw: &str) -> TomlValue {
+    TomlValue::Boolean(matches!(raw, "1" | "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::ConfigLayerEntry;
+    use crate::config_loader::ConfigRequirements;
+    use codex_protocol::config_types::TrustLevel;
+    use codex_protocol::protocol::AskForApproval;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+
+    fn layer(name: ConfigLayerSource, contents: &str) -> ConfigLayerEntry {
+        ConfigLayerEntry::new(name, toml::from_str(contents).expect("valid toml"))
+    }
+
+    #[test]
+    fn explains_layers_profiles_and_trust() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let file = |name: &str| {
+            AbsolutePathBuf::from_absolute_path(tmp.path().join(name)).expect("absolute path")
+        };
+        let system = ConfigLayerSource::System {
+            file: file("system.toml"),
+        };
+        let user = ConfigLayerSource::User {
+            file: file("config.toml"),
+        };
+        let stack = ConfigLayerStack::new(
+            vec![
+                layer(
+                    system.clone(),
+                    "model = \"o3\"\napproval_policy = \"never\"",
+                ),
+                layer(
+                    user.clone(),
+                    "model = \"gpt-5\"\n[profiles.fast]\nmodel = \"gpt-5.1-codex-mini\"",
+                ),
+                layer(ConfigLayerSource::SessionFlags, "model = \"gpt-4.1\""),
+            ],
+            ConfigRequirements::default(),
+        )
+        .expect("stack");
+        let string = |value: &str| TomlValue::String(value.to_string());
+
+        let explanation = explain_key(&stack, None, None, "model");
+        assert_eq!(explanation.value, Some(string("gpt-4.1")));
+        assert_eq!(explanation.winner, Some(0));
+        assert_eq!(
+            explanation
+                .sources
+                .iter()
+                .map(|source| source.source.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                ValueSource::Layer(ConfigLayerSource::SessionFlags),
+                ValueSource::Layer(user.clone()),
+                ValueSource::Layer(system.clone()),
+            ]
+        );
+
+        // The active profile beats every top-level value.
+        let explanation = explain_key(&stack, Some("fast"), None, "model");
+        assert_eq!(explanation.value, Some(string("gpt-5.1-codex-mini")));
+        assert_eq!(
+            explanation.sources[0].source,
+            ValueSource::Profile {
+                name: "fast".to_string(),
+                layer: user,
+            }
+        );
+        assert_eq!(explanation.winner, Some(0));
+
+        // A trust decision pins the model above the config files, but only
+        // fills in an approval policy they leave unset.
+        let mut trust = ProjectTrust::new(TrustLevel::Trusted);
+        trust.model = Some("gpt-5.1".to_string());
+        trust.approval_policy = Some(AskForApproval::OnRequest);
+        let trust_file = tmp.path().join("trusted_projects.json");
+        let explanation = explain_key(&stack, None, Some((&trust, trust_file.clone())), "model");
+        assert_eq!(explanation.value, Some(string("gpt-5.1")));
+        assert_eq!(
+            explanation.sources[0].source,
+            ValueSource::ProjectTrust {
+                file: trust_file.clone()
+            }
+        );
+        let explanation = explain_key(&stack, None, Some((&trust, trust_file)), "approval_policy");
+        assert_eq!(explanation.value, Some(string("never")));
+        assert_eq!(explanation.winner, Some(0));
+        assert_eq!(explanation.sources.len(), 2);
+
+        let explanation = explain_key(&stack, None, None, "model_verbosity");
+        assert_eq!(explanation.value, None);
+        assert_eq!(explanation.sources, Vec::new());
+    }
+}
@@ -0,0 +1,330 @@
+//! Renamed and retired config keys.
+//!
+//! Every config layer is migrated before the layers are merged: a deprecated
+//! key is moved to its replacement, unless the replacement is already set in
+//! the same layer. The keys that were moved are kept on [`super::Config`] so
+//! sessions can point them out, and `codex config migrate` rewrites them in
+//! `config.toml` itself.
+
+use std::path::Path;
+
+use toml::Table as TomlTable;
+use toml::Value as TomlValue;
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+
+use super::CONFIG_TOML_FILE;
+use super::edit::ConfigEdit;
+use super::edit::ConfigEditsBuilder;
+use crate::config_loader::toml_value_at;
+use crate::features::legacy_key_replacement;
+
+/// Keys renamed in place. Feature toggles that predate `[features]` are
+/// covered by [`legacy_key_replacement`] instead.
+const RENAMED_KEYS: &[(&str, &str)] = &[
+    (
+        "ghost_snapshot.ignore_untracked_files_over_bytes",
+        "ghost_snapshot.ignore_large_untracked_files",
+    ),
+    (
+        "ghost_snapshot.large_untracked_dir_warning_threshold",
+        "ghost_snapshot.ignore_large_untracked_dirs",
+    ),
+];
+
+/// A deprecated key found in a config layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigratedKey {
+    /// Path of the deprecated key, such as `["tools", "view_image"]`.
+    pub from: Vec<String>,
+    /// Path of the key that replaces it.
+    pub to: Vec<String>,
+    /// The replacement was already set, so the deprecated value was dropped.
+    pub superseded: bool,
+}
+
+impl MigratedKey {
+    pub fn from_key(&self) -> String {
+        self.from.join(".")
+    }
+
+    pub fn to_key(&self) -> String {
+        self.to.join(".")
+    }
+}
+
+/// Returns the key that replaces the deprecated `path`, a dotted key relative
+/// to the config root or to a `[profiles.<name>]` table.
+pub fn replacement_for(path: &str) -> Option<String> {
+    if let Some((_, to)) = RENAMED_KEYS.iter().find(|(from, _)| *from == path) {
+        return Some((*to).to_string());
+    }
+    legacy_key_replacement(path).map(|feature| format!("features.{}", feature.key()))
+}
+
+/// Moves every deprecated key in `config`, including those in profiles, to
+/// its replacement.
+pub fn migrate_keys(config: &mut TomlValue) -> Vec<MigratedKey> {
+    let mut migrated = Vec::new();
+    let Some(root) = config.as_table_mut() else {
+        return migrated;
+    };
+    migrate_table(root, &[], &mut migrated);
+    if let Some(TomlValue::Table(profiles)) = root.get_mut("profiles") {
+        for (name, profile) in profiles.iter_mut() {
+            if let TomlValue::Table(profile) = profile {
+                let prefix = ["profiles".to_string(), name.clone()];
+                migrate_table(profile, &prefix, &mut migrated);
+            }
+        }
+    }
+    migrated
+}
+
+/// Rewrites the deprecated keys in `$CODEX_HOME/config.toml`, keeping its
+/// comments and formatting. Files named by `include` are left alone.
+pub async fn migrate_config_file(codex_home: &Path) -> anyhow::Result<Vec<MigratedKey>> {
+    let contents = match tokio::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut config: TomlValue = toml::from_str(&contents)?;
+    let migrated = migrate_keys(&mut config);
+    if migrated.is_empty() {
+        return Ok(migrated);
+    }
+
+    let doc = contents.parse::<DocumentMut>()?;
+    let mut edits = Vec::new();
+    for key in &migrated {
+        if !key.superseded
+            && let Some(item) = lookup(doc.as_item(), &key.from)
+        {
+            edits.push(ConfigEdit::SetPath {
+                segments: key.to.clone(),
+                value: item.clone(),
+            });
+        }
+        edits.push(ConfigEdit::ClearPath {
+            segments: key.from.clone(),
+        });
+    }
+    ConfigEditsBuilder::new(codex_home)
+        .with_edits(edits)
+        .apply()
+        .await?;
+    Ok(migrated)
+}
+
+fn migrate_table(table: &mut TomlTable, prefix: &[String], out: &mut Vec<MigratedKey>) {
+    let mut moves = Vec::new();
+    collect_deprecated(table, &mut Vec::new(), &mut moves);
+    for (from, to) in moves {
+        let Some(value) = remove_path(table, &from) else {
+            continue;
+        };
+        let superseded = to
+            .split_first()
+            .and_then(|(first, rest)| toml_value_at(table.get(first)?, rest))
+            .is_some();
+        if !superseded {
+            insert_path(table, &to, value);
+        }
+        out.push(MigratedKey {
+            from: prefix.iter().cloned().chain(from).collect(),
+            to: prefix.iter().cloned().chain(to).collect(),
+            superseded,
+        });
+    }
+}
+
+fn collect_deprecated(
+    table: &TomlTable,
+    path: &mut Vec<String>,
+    moves: &mut Vec<(Vec<String>, Vec<String>)>,
+) {
+    for (key, value) in table {
+        path.push(key.clone());
+        if let Some(to) = replacement_for(&path.join(".")) {
+            moves.push((path.clone(), to.split('.').map(str::to_string).collect()));
+        } else if let TomlValue::Table(child) = value
+            && path.as_slice() != ["profiles"]
+        {
+            collect_deprecated(child, path, moves);
+        }
+        path.pop();
+    }
+}
+
+fn remove_path(table: &mut TomlTable, path: &[String]) -> Option<TomlValue> {
+    let (last, parents) = path.split_last()?;
+    let mut current = table;
+    for segment in parents {
+        current = current.get_mut(segment)?.as_table_mut()?;
+    }
+    current.remove(last)
+}
+
+fn insert_path(table: &mut TomlTable, path: &[String], value: TomlValue) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = table;
+    for segment in parents {
+        let child = current
+            .entry(segment.clone())
+            .or_insert_with(|| TomlValue::Table(TomlTable::new()));
+        let TomlValue::Table(child) = child else {
+            return;
+        };
+        current = child;
+    }
+    current.insert(last.clone(), value);
+}
+
+fn lookup<'a>(item: &'a TomlItem, path: &[String]) -> Option<&'a TomlItem> {
+    path.iter()
+        .try_fold(item, |item, segment| item.get(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    fn key(from: &str, to: &str, superseded: bool) -> MigratedKey {
+        MigratedKey {
+            from: from.split('.').map(str::to_string).collect(),
+            to: to.split('.').map(str::to_string).collect(),
+            superseded,
+        }
+    }
+
+    #[test]
+    fn moves_renamed_and_legacy_feature_keys() {
+        let mut config = toml(
+            r#"
+experimental_use_rmcp_client = true
+
+[tools]
+view_image = false
+
+[ghost_snapshot]
+ignore_untracked_files_over_bytes = 1024
+
+[profiles.ci]
+include_apply_patch_tool = true
+
+[profiles.ci.features]
+apply_patch_freeform = false
+"#,
+        );
+
+        let mut migrated = migrate_keys(&mut config);
+        migrated.sort_by_key(MigratedKey::from_key);
+
+        assert_eq!(
+            migrated,
+            vec![
+                key(
+                    "experimental_use_rmcp_client",
+                    "features.rmcp_client",
+                    false
+                ),
+                key(
+                    "ghost_snapshot.ignore_untracked_files_over_bytes",
+                    "ghost_snapshot.ignore_large_untracked_files",
+                    false
+                ),
+                key("tools.view_image", "features.view_image_tool", false),
+                key(
+                    "profiles.ci.include_apply_patch_tool",
+                    "profiles.ci.features.apply_patch_freeform",
+                    true
+                ),
+            ]
+        );
+        assert_eq!(
+            config,
+            toml(
+                r#"
+[features]
+rmcp_client = true
+view_image_tool = false
+
+[tools]
+
+[ghost_snapshot]
+ignore_large_untracked_files = 1024
+
+[profiles.ci.features]
+apply_patch_freeform = false
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn leaves_current_keys_alone() {
+        let mut config = toml(
+            r#"
+model = "o3"
+
+[features]
+web_search_request = true
+"#,
+        );
+        let expected = config.clone();
+
+        assert_eq!(migrate_keys(&mut config), Vec::new());
+        assert_eq!(config, expected);
+    }
+
+    #[tokio::test]
+    async fn migrate_config_file_keeps_comments() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let path = codex_home.path().join(CONFIG_TOML_FILE);
+        std::fs::write(
+            &path,
+            r#"# my settings
+model = "o3"
+
+[features]
+web_search = true # search the web
+"#,
+        )
+        .expect("write config");
+
+        let migrated = migrate_config_file(codex_home.path())
+            .await
+            .expect("migrate");
+
+        assert_eq!(
+            migrated,
+            vec![key(
+                "features.web_search",
+                "features.web_search_request",
+                false
+            )]
+        );
+        let contents = std::fs::read_to_string(&path).expect("read config");
+        assert!(contents.starts_with("# my settings\n"));
+        assert!(contents.contains("# search the web"));
+        assert_eq!(
+            toml(&contents),
+            toml(
+                r#"
+model = "o3"
+
+[features]
+web_search_request = true
+"#
+            )
+        );
+    }
+}
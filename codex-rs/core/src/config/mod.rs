@@ -1,19 +1,51 @@
+use crate::approvals_policy::ApprovalsPolicy;
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::explain::ValueSource;
+use crate::config::migration::MigratedKey;
+use crate::config::trust_store::load_project_trust;
+use crate::config::types::AutonomyConfig;
+use crate::config::types::AutonomyToml;
+use crate::config::types::ContainerSandboxConfig;
+use crate::config::types::DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES;
+use crate::config::types::DEFAULT_FILE_ATTACHMENT_MAX_BYTES;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::DEFAULT_TUI_THEME;
+use crate::config::types::DatabaseConnection;
+use crate::config::types::ExporterConfig;
 use crate::config::types::History;
+use crate::config::types::HttpRequestToml;
+use crate::config::types::IdleSummarizationConfig;
+use crate::config::types::IdleSummarizationToml;
+use crate::config::types::Keymap;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelPrice;
+use crate::config::types::NetworkConfig;
+use crate::config::types::NetworkToml;
 use crate::config::types::Notice;
+use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::RunLimits;
+use crate::config::types::SandboxToml;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionTitlesConfig;
+use crate::config::types::SessionTitlesToml;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::ShowReasoning;
+use crate::config::types::SteeringConfig;
+use crate::config::types::SteeringToml;
+use crate::config::types::ThemeToml;
+use crate::config::types::ToolTimeouts;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::LockedKeyViolation;
+use crate::config_loader::apply_project_config_layer;
+use crate::config_loader::clamp_sandbox_mode;
 use crate::config_loader::load_config_layers_state;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
@@ -24,6 +56,7 @@ use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 use crate::model_provider_info::built_in_model_providers;
+use crate::patch_policy::PatchPolicy;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
@@ -41,6 +74,7 @@ use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use dirs::home_dir;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use similar::DiffableStr;
 use std::collections::BTreeMap;
@@ -52,14 +86,22 @@ use std::path::PathBuf;
 use tempfile::tempdir;
 
 use crate::config::profile::ConfigProfile;
+use crate::config::profile::deep_merge_into;
+use crate::config::profile::resolve_profile;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
 mod constraint;
 pub mod edit;
+pub mod explain;
+pub mod migration;
 pub mod profile;
+pub(crate) mod reload;
+pub mod schema;
 pub mod service;
+pub mod trust_store;
 pub mod types;
+pub mod xdg;
 pub use constraint::Constrained;
 pub use constraint::ConstraintError;
 pub use constraint::ConstraintResult;
@@ -115,6 +157,10 @@ pub struct Config {
 
     pub sandbox_policy: SandboxPolicy,
 
+    /// Container runtime and image for sandboxed commands, when
+    /// `sandbox.backend` is `docker` or `podman`.
+    pub container_sandbox: Option<ContainerSandboxConfig>,
+
     /// True if the user passed in an override or set a value in config.toml
     /// for either of approval_policy or sandbox_mode.
     pub did_user_set_custom_approval_policy_or_sandbox_mode: bool,
@@ -172,17 +218,76 @@ pub struct Config {
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
 
+    /// How the TUI delivers desktop notifications.
+    pub tui_notification_method: NotificationMethod,
+
+    /// Minimum turn length, in seconds, for a turn-complete notification.
+    pub tui_turn_notification_min_seconds: u64,
+
     /// Enable ASCII animations and shimmer effects in the TUI.
     pub animations: bool,
 
     /// Show startup tooltips in the TUI welcome screen.
     pub show_tooltips: bool,
 
+    /// Syntax-highlight fenced code blocks in the TUI.
+    pub syntax_highlighting: bool,
+
+    /// Link file references in the TUI transcript with OSC 8 hyperlinks.
+    pub tui_file_hyperlinks: bool,
+
+    /// Start the TUI with mouse capture on.
+    pub tui_mouse: bool,
+
+    /// How the TUI shows reasoning summaries.
+    pub tui_show_reasoning: ShowReasoning,
+
+    /// Screen-reader friendly TUI output (`tui.accessible`). Turns
+    /// `animations` off.
+    pub tui_accessible: bool,
+
+    /// Serve the running session to `codex attach` viewers
+    /// (`tui.allow_attach`).
+    pub tui_allow_attach: bool,
+
+    /// Collapse tool-call cells in the transcript overlay past this many
+    /// lines; `0` never collapses.
+    pub collapse_tool_output_lines: usize,
+
+    /// Bytes of each `@`-picked file attached to a message; `0` attaches
+    /// nothing.
+    pub file_attachment_max_bytes: usize,
+
+    /// Name of the TUI color theme; see [`Tui::theme`].
+    pub tui_theme: String,
+
+    /// User-defined TUI themes, keyed by name.
+    pub tui_themes: BTreeMap<String, ThemeToml>,
+
+    /// Key bindings for the TUI composer.
+    pub tui_keymap: Keymap,
+
+    /// Extra normal-mode bindings for the `vim` keymap; see [`Tui::vim_keys`].
+    pub tui_vim_keys: BTreeMap<String, String>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
     pub cwd: PathBuf,
 
+    /// Further repository roots the session spans besides `cwd`, canonical
+    /// and writable under `workspace-write`. When any are set, file tools and
+    /// patches are confined to `cwd` and these roots.
+    pub workspace_roots: Vec<PathBuf>,
+
+    /// The project's `.codex/patch-policy.toml`, loaded only for headless
+    /// runs; every patch must satisfy it.
+    pub patch_policy: Option<PatchPolicy>,
+
+    /// The project's `.codex/approvals.toml`: ordered rules that allow, deny,
+    /// or ask about commands, patched paths, and sandboxed network access.
+    pub approvals_policy: Option<ApprovalsPolicy>,
+
     /// Preferred store for CLI auth credentials.
     /// file (default): Use a file in the Codex home directory.
     /// keyring: Use an OS-specific keyring service.
@@ -192,6 +297,15 @@ pub struct Config {
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Named SQLite databases the `query_sqlite` tool may open by connection name.
+    pub databases: HashMap<String, DatabaseConnection>,
+
+    /// Transcript exporters backed by external commands, by `--format` name.
+    pub exporters: HashMap<String, ExporterConfig>,
+
+    /// Hosts besides localhost that the `http_request` tool may reach.
+    pub http_request_allowed_hosts: Vec<String>,
+
     /// Preferred store for MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          Credentials stored in the keyring will only be readable by Codex unless the user explicitly grants access via OS-level keyring access.
@@ -204,6 +318,9 @@ pub struct Config {
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Per-model prices used to estimate session cost, keyed by model slug.
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
@@ -276,6 +393,32 @@ pub struct Config {
     /// Settings for ghost snapshots (used for undo).
     pub ghost_snapshot: GhostSnapshotConfig,
 
+    /// Budget and per-scope reservations for injected steering files.
+    pub steering: SteeringConfig,
+
+    /// Settings for unattended runs, such as the exploration budget.
+    pub autonomy: AutonomyConfig,
+
+    /// Model and size threshold for summarizing tool outputs while idle.
+    pub idle_summarization: IdleSummarizationConfig,
+
+    /// Whether and with which model to generate session titles.
+    pub session_titles: SessionTitlesConfig,
+
+    /// Proxy and CA settings for outbound HTTP requests.
+    pub network: NetworkConfig,
+
+    /// Default timeouts for shell commands and MCP tool calls.
+    pub tool_timeouts: ToolTimeouts,
+
+    /// Deprecated keys found while loading, already moved to their
+    /// replacements.
+    pub deprecated_keys: Vec<MigratedKey>,
+
+    /// Settings found while loading that have no effect because a feature
+    /// they need is off.
+    pub feature_warnings: Vec<String>,
+
     /// Centralized feature flags; source of truth for feature gating.
     pub features: Features,
 
@@ -286,6 +429,10 @@ pub struct Config {
     /// is (1) part of a git repo, (2) a git worktree, or (3) just using the cwd
     pub active_project: ProjectConfig,
 
+    /// Whether the project's `.codex/config.toml` and `.codex/steering` are
+    /// honored. Off only when a stored trust decision says so.
+    pub load_project_files: bool,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
     pub windows_wsl_setup_acknowledged: bool,
 
@@ -344,25 +491,115 @@ impl ConfigBuilder {
         } = self;
         let codex_home = codex_home.map_or_else(find_codex_home, std::io::Result::Ok)?;
         let cli_overrides = cli_overrides.unwrap_or_default();
-        let harness_overrides = harness_overrides.unwrap_or_default();
+        let mut harness_overrides = harness_overrides.unwrap_or_default();
         let loader_overrides = loader_overrides.unwrap_or_default();
         let config_layer_stack =
             load_config_layers_state(&codex_home, &cli_overrides, loader_overrides).await?;
+        // `--sandbox` may not loosen a sandbox mode the system config enforces.
+        if let Some(floor) = config_layer_stack.sandbox_mode_floor()
+            && let Some(mode) = harness_overrides.sandbox_mode
+        {
+            harness_overrides.sandbox_mode = Some(clamp_sandbox_mode(mode, floor));
+        }
+        let project_cwd = match harness_overrides.cwd.as_deref() {
+            Some(cwd) if cwd.is_absolute() => cwd.to_path_buf(),
+            Some(cwd) => std::env::current_dir()?.join(cwd),
+            None => std::env::current_dir()?,
+        };
+        let config_layer_stack =
+            apply_project_config_layer(config_layer_stack, &codex_home, &project_cwd).await?;
         let merged_toml = config_layer_stack.effective_config();
+        let profile = harness_overrides
+            .config_profile
+            .as_deref()
+            .or_else(|| merged_toml.get("profile")?.as_str());
+        let violations = config_layer_stack
+            .locked_key_violations(&harness_override_values(&harness_overrides), profile);
+        if !violations.is_empty() {
+            let message = violations
+                .iter()
+                .map(locked_key_message)
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                message,
+            ));
+        }
 
         // Note that each layer in ConfigLayerStack should have resolved
         // relative paths to absolute paths based on the parent folder of the
         // respective config file, so we should be safe to deserialize without
         // AbsolutePathBufGuard here.
         let config_toml: ConfigToml = merged_toml
+            .clone()
             .try_into()
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        Config::load_config_with_requirements(
+        let mut config = Config::load_config_with_requirements(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack.requirements().clone(),
-        )
+        )?;
+        config.deprecated_keys = config_layer_stack.deprecated_keys();
+        config.feature_warnings = config.features.requirement_warnings(&merged_toml);
+        Ok(config)
+    }
+}
+
+/// The config values set by command-line flags in `overrides`, as a table
+/// keyed like `config.toml`.
+fn harness_override_values(overrides: &ConfigOverrides) -> TomlValue {
+    let mut table = toml::map::Map::new();
+    let mut insert = |key: &str, value: Option<TomlValue>| {
+        if let Some(value) = value {
+            table.insert(key.to_string(), value);
+        }
+    };
+    insert("model", overrides.model.clone().map(TomlValue::String));
+    insert(
+        "review_model",
+        overrides.review_model.clone().map(TomlValue::String),
+    );
+    insert(
+        "model_provider",
+        overrides.model_provider.clone().map(TomlValue::String),
+    );
+    insert(
+        "approval_policy",
+        overrides
+            .approval_policy
+            .and_then(|policy| TomlValue::try_from(policy).ok()),
+    );
+    insert(
+        "sandbox_mode",
+        overrides
+            .sandbox_mode
+            .and_then(|mode| TomlValue::try_from(mode).ok()),
+    );
+    TomlValue::Table(table)
+}
+
+fn locked_key_message(violation: &LockedKeyViolation) -> String {
+    let LockedKeyViolation {
+        key,
+        locked_value,
+        value,
+        locked_by,
+        set_by,
+    } = violation;
+    let set_by = match set_by {
+        Some(layer) => format!("the {}", ValueSource::Layer(layer.clone())),
+        None => "a command-line flag".to_string(),
+    };
+    let locked_by = ValueSource::Layer(locked_by.clone());
+    match locked_value {
+        Some(locked_value) => format!(
+            "`{key}` is locked to {locked_value} by the {locked_by}, but {set_by} sets it to {value}"
+        ),
+        None => {
+            format!("`{key}` is locked unset by the {locked_by}, but {set_by} sets it to {value}")
+        }
     }
 }
 
@@ -600,8 +837,13 @@ pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::R
 }
 
 /// Base config deserialized from ~/.codex/config.toml.
-#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 pub struct ConfigToml {
+    /// Other config files merged beneath this one, in order. Resolved when
+    /// the user config is loaded, so it is always empty after loading.
+    #[serde(default)]
+    pub include: Vec<String>,
+
     /// Optional override of model selection.
     pub model: Option<String>,
     /// Review model override used by the `/review` feature.
@@ -628,6 +870,11 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Which sandbox runs commands, and the container image for the
+    /// container backends.
+    #[serde(default)]
+    pub sandbox: Option<SandboxToml>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -661,6 +908,19 @@ pub struct ConfigToml {
     #[serde(default)]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Named SQLite databases the `query_sqlite` tool may open by connection name.
+    #[serde(default)]
+    pub databases: HashMap<String, DatabaseConnection>,
+
+    /// Transcript exporters backed by external commands, used with
+    /// `codex export --format <name>`.
+    #[serde(default)]
+    pub exporters: HashMap<String, ExporterConfig>,
+
+    /// Settings for the `http_request` tool.
+    #[serde(default)]
+    pub http_request: Option<HttpRequestToml>,
+
     /// Preferred backend for storing MCP OAuth credentials.
     /// keyring: Use an OS-specific keyring service.
     ///          https://github.com/openai/codex/blob/main/codex-rs/rmcp-client/src/oauth.rs#L2
@@ -673,6 +933,11 @@ pub struct ConfigToml {
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Per-model prices used to estimate the cost shown in the TUI, keyed by
+    /// model slug.
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
@@ -735,11 +1000,40 @@ pub struct ConfigToml {
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
 
+    /// Settings that govern how steering files are loaded.
+    #[serde(default)]
+    pub steering: Option<SteeringToml>,
+
+    /// Settings for unattended runs.
+    #[serde(default)]
+    pub autonomy: Option<AutonomyToml>,
+
+    /// Settings for summarizing older tool outputs while the session is idle.
+    #[serde(default)]
+    pub idle_summarization: Option<IdleSummarizationToml>,
+
+    /// Settings for generating a short title after the first exchange.
+    #[serde(default)]
+    pub session_titles: Option<SessionTitlesToml>,
+
+    /// Proxy and CA settings for outbound HTTP requests.
+    #[serde(default)]
+    pub network: Option<NetworkToml>,
+
+    /// Default timeouts for shell commands and MCP tool calls.
+    #[serde(default)]
+    pub tool_timeouts: Option<ToolTimeouts>,
+
     /// When `true`, checks for Codex updates on startup and surfaces update prompts.
     /// Set to `false` only if your Codex updates are centrally managed.
     /// Defaults to `true`.
     pub check_for_update_on_startup: Option<bool>,
 
+    /// Only read from `$XDG_CONFIG_HOME/codex/config.toml`: when `true`,
+    /// Codex keeps its config, state, and caches in the XDG base
+    /// directories instead of `~/.codex`.
+    pub use_xdg_dirs: Option<bool>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -790,7 +1084,7 @@ impl From<ConfigToml> for UserSavedConfig {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
 }
@@ -805,7 +1099,7 @@ impl ProjectConfig {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 pub struct ToolsToml {
     #[serde(default, alias = "web_search_request")]
     pub web_search: Option<bool>,
@@ -824,7 +1118,7 @@ impl From<ToolsToml> for Tools {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
 pub struct GhostSnapshotToml {
     /// Exclude untracked files larger than this many bytes from ghost snapshots.
     #[serde(alias = "ignore_untracked_files_over_bytes")]
@@ -927,16 +1221,7 @@ impl ConfigToml {
         let profile = override_profile.or_else(|| self.profile.clone());
 
         match profile {
-            Some(key) => {
-                if let Some(profile) = self.profiles.get(key.as_str()) {
-                    return Ok(profile.clone());
-                }
-
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    format!("config profile `{key}` not found"),
-                ))
-            }
+            Some(key) => resolve_profile(&self.profiles, &key),
             None => Ok(ConfigProfile::default()),
         }
     }
@@ -961,6 +1246,13 @@ pub struct ConfigOverrides {
     pub tools_web_search_request: Option<bool>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
+    /// Further repository roots for a multi-repository session, relative to
+    /// `cwd`; see [`Config::workspace_roots`].
+    pub workspace_roots: Vec<PathBuf>,
+    /// Enforce the `.codex/patch-policy.toml` under `cwd`, if there is one.
+    /// Set by `codex exec`, where nobody is there to approve patches.
+    pub load_patch_policy: bool,
+    pub run_limits: RunLimits,
 }
 
 /// Resolves the OSS provider from CLI override, profile config, or global config.
@@ -1029,23 +1321,17 @@ impl Config {
             show_raw_agent_reasoning,
             tools_web_search_request: override_tools_web_search_request,
             additional_writable_roots,
+            workspace_roots,
+            load_patch_policy,
+            run_limits,
         } = overrides;
 
         let active_profile_name = config_profile_key
             .as_ref()
             .or(cfg.profile.as_ref())
             .cloned();
-        let config_profile = match active_profile_name.as_ref() {
-            Some(key) => cfg
-                .profiles
-                .get(key)
-                .ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        format!("config profile `{key}` not found"),
-                    )
-                })?
-                .clone(),
+        let mut config_profile = match active_profile_name.as_ref() {
+            Some(key) => resolve_profile(&cfg.profiles, key)?,
             None => ConfigProfile::default(),
         };
 
@@ -1055,6 +1341,40 @@ impl Config {
         };
 
         let features = Features::from_config(&cfg, &config_profile, feature_overrides);
+
+        let mut cfg = cfg;
+        if let Some(overlay) = config_profile.sandbox_workspace_write.take() {
+            cfg.sandbox_workspace_write = Some(deep_merge_into(
+                cfg.sandbox_workspace_write.as_ref(),
+                overlay,
+                "sandbox_workspace_write",
+                &codex_home,
+            )?);
+        }
+        if let Some(overlay) = config_profile.steering.take() {
+            cfg.steering = Some(deep_merge_into(
+                cfg.steering.as_ref(),
+                overlay,
+                "steering",
+                &codex_home,
+            )?);
+        }
+        if let Some(overlay) = config_profile.tool_timeouts.take() {
+            cfg.tool_timeouts = Some(deep_merge_into(
+                cfg.tool_timeouts.as_ref(),
+                overlay,
+                "tool_timeouts",
+                &codex_home,
+            )?);
+        }
+        if let Some(overlay) = config_profile.autonomy.take() {
+            cfg.autonomy = Some(deep_merge_into(
+                cfg.autonomy.as_ref(),
+                overlay,
+                "autonomy",
+                &codex_home,
+            )?);
+        }
         #[cfg(target_os = "windows")]
         {
             // Base flag controls sandbox on/off; elevated only applies when base is enabled.
@@ -1087,16 +1407,61 @@ impl Config {
             .into_iter()
             .map(|path| AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd))
             .collect::<Result<Vec<_>, _>>()?;
-        let active_project = cfg
-            .get_active_project(&resolved_cwd)
-            .unwrap_or(ProjectConfig { trust_level: None });
+        let workspace_roots = workspace_roots
+            .into_iter()
+            .map(|root| {
+                let root = resolved_cwd.join(root);
+                match dunce::canonicalize(&root) {
+                    Ok(root) if root.is_dir() => Ok(root),
+                    _ => Err(std::io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("repository root {} is not a directory", root.display()),
+                    )),
+                }
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let patch_policy = if load_patch_policy {
+            PatchPolicy::load(&resolved_cwd)?
+        } else {
+            None
+        };
+        let approvals_policy = ApprovalsPolicy::load(&codex_home, &resolved_cwd)?;
+        let project_trust = load_project_trust(&codex_home, &resolved_cwd);
+        let active_project = match &project_trust {
+            Some(trust) => ProjectConfig {
+                trust_level: Some(trust.trust_level),
+            },
+            None => cfg
+                .get_active_project(&resolved_cwd)
+                .unwrap_or(ProjectConfig { trust_level: None }),
+        };
+        // `.codex/` files load only once the project has a trust decision
+        // that allows them.
+        let load_project_files = match &project_trust {
+            Some(trust) => trust.load_project_files,
+            None => active_project.is_trusted(),
+        };
+        // A stored trust decision fills in the sandbox mode when the config
+        // files leave it unset.
+        let trusted_sandbox_mode = project_trust
+            .as_ref()
+            .filter(|_| config_profile.sandbox_mode.is_none() && cfg.sandbox_mode.is_none())
+            .map(|trust| trust.sandbox_mode.unwrap_or(SandboxMode::WorkspaceWrite));
 
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
-        } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        } = cfg.derive_sandbox_policy(
+            sandbox_mode.or(trusted_sandbox_mode),
+            config_profile.sandbox_mode,
+            &resolved_cwd,
+        );
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
-            for path in additional_writable_roots {
+            let workspace_roots = workspace_roots
+                .iter()
+                .map(AbsolutePathBuf::from_absolute_path)
+                .collect::<Result<Vec<_>, _>>()?;
+            for path in additional_writable_roots.into_iter().chain(workspace_roots) {
                 if !writable_roots.iter().any(|existing| existing == &path) {
                     writable_roots.push(path);
                 }
@@ -1105,6 +1470,9 @@ impl Config {
         let approval_policy = approval_policy_override
             .or(config_profile.approval_policy)
             .or(cfg.approval_policy)
+            .or(project_trust
+                .as_ref()
+                .and_then(|trust| trust.approval_policy))
             .unwrap_or_else(|| {
                 if active_project.is_trusted() {
                     AskForApproval::OnRequest
@@ -1129,6 +1497,21 @@ impl Config {
         for (key, provider) in cfg.model_providers.into_iter() {
             model_providers.entry(key).or_insert(provider);
         }
+        for (key, overlay) in config_profile.model_providers.take().unwrap_or_default() {
+            let TomlValue::Table(overlay) = overlay else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("profile override for `model_providers.{key}` must be a table"),
+                ));
+            };
+            let provider = deep_merge_into(
+                model_providers.get(&key),
+                overlay,
+                &format!("model_providers.{key}"),
+                &codex_home,
+            )?;
+            model_providers.insert(key, provider);
+        }
 
         let model_provider_id = model_provider
             .or(config_profile.model_provider)
@@ -1143,6 +1526,12 @@ impl Config {
                 )
             })?
             .clone();
+        model_provider.validate_http_headers().map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("model_providers.{model_provider_id}.{err}"),
+            )
+        })?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1190,7 +1579,22 @@ impl Config {
 
         let forced_login_method = cfg.forced_login_method;
 
-        let model = model.or(config_profile.model).or(cfg.model);
+        // A model pinned for this project in the trust store beats the
+        // defaults of the config files, but not a profile or `--model`.
+        let (trusted_model, trusted_reasoning_effort, trusted_verbosity) = project_trust
+            .as_ref()
+            .map(|trust| {
+                (
+                    trust.model.clone(),
+                    trust.model_reasoning_effort,
+                    trust.model_verbosity,
+                )
+            })
+            .unwrap_or_default();
+        let model = model
+            .or(config_profile.model)
+            .or(trusted_model)
+            .or(cfg.model);
 
         let compact_prompt = compact_prompt.or(cfg.compact_prompt).and_then(|value| {
             let trimmed = value.trim();
@@ -1232,6 +1636,8 @@ impl Config {
 
         let check_for_update_on_startup = cfg.check_for_update_on_startup.unwrap_or(true);
 
+        let network = cfg.network.map(NetworkConfig::from).unwrap_or_default();
+
         // Ensure that every field of ConfigRequirements is applied to the final
         // Config.
         let ConfigRequirements {
@@ -1242,6 +1648,12 @@ impl Config {
             .set(approval_policy)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let container_sandbox = cfg
+            .sandbox
+            .map(SandboxToml::container_config)
+            .transpose()?
+            .flatten();
+
         let config = Self {
             model,
             review_model,
@@ -1250,8 +1662,12 @@ impl Config {
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
+            workspace_roots,
+            patch_policy,
+            approvals_policy,
             approval_policy: constrained_approval_policy,
             sandbox_policy,
+            container_sandbox,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
@@ -1264,10 +1680,14 @@ impl Config {
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
             mcp_servers: cfg.mcp_servers,
+            databases: cfg.databases,
+            exporters: cfg.exporters,
+            http_request_allowed_hosts: cfg.http_request.unwrap_or_default().allowed_hosts,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             model_providers,
+            model_prices: cfg.model_prices,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
@@ -1282,7 +1702,9 @@ impl Config {
                     }
                 })
                 .collect(),
-            tool_output_token_limit: cfg.tool_output_token_limit,
+            tool_output_token_limit: config_profile
+                .tool_output_token_limit
+                .or(cfg.tool_output_token_limit),
             codex_home,
             history,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
@@ -1295,6 +1717,7 @@ impl Config {
                 .unwrap_or(false),
             model_reasoning_effort: config_profile
                 .model_reasoning_effort
+                .or(trusted_reasoning_effort)
                 .or(cfg.model_reasoning_effort),
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
@@ -1302,7 +1725,10 @@ impl Config {
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
             model_reasoning_summary_format: cfg.model_reasoning_summary_format.clone(),
-            model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            model_verbosity: config_profile
+                .model_verbosity
+                .or(trusted_verbosity)
+                .or(cfg.model_verbosity),
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1314,9 +1740,27 @@ impl Config {
             use_experimental_unified_exec_tool,
             use_experimental_use_rmcp_client,
             ghost_snapshot,
+            steering: cfg.steering.map(SteeringConfig::from).unwrap_or_default(),
+            autonomy: AutonomyConfig {
+                run_limits,
+                ..cfg.autonomy.map(AutonomyConfig::from).unwrap_or_default()
+            },
+            idle_summarization: cfg
+                .idle_summarization
+                .map(IdleSummarizationConfig::from)
+                .unwrap_or_default(),
+            session_titles: cfg
+                .session_titles
+                .map(SessionTitlesConfig::from)
+                .unwrap_or_default(),
+            network,
+            tool_timeouts: cfg.tool_timeouts.unwrap_or_default(),
+            deprecated_keys: Vec::new(),
+            feature_warnings: Vec::new(),
             features,
             active_profile: active_profile_name,
             active_project,
+            load_project_files,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
@@ -1326,8 +1770,62 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
-            animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            tui_notification_method: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.notification_method)
+                .unwrap_or_default(),
+            tui_turn_notification_min_seconds: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.turn_notification_min_seconds)
+                .unwrap_or_default(),
+            animations: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.animations && !t.accessible)
+                .unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
+            syntax_highlighting: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.syntax_highlighting)
+                .unwrap_or(true),
+            tui_file_hyperlinks: cfg.tui.as_ref().map(|t| t.file_hyperlinks).unwrap_or(true),
+            tui_mouse: cfg.tui.as_ref().is_some_and(|t| t.mouse),
+            tui_show_reasoning: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.show_reasoning)
+                .unwrap_or_default(),
+            tui_accessible: cfg.tui.as_ref().is_some_and(|t| t.accessible),
+            tui_allow_attach: cfg.tui.as_ref().is_some_and(|t| t.allow_attach),
+            collapse_tool_output_lines: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.collapse_tool_output_lines)
+                .unwrap_or(DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES),
+            file_attachment_max_bytes: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.file_attachment_max_bytes)
+                .unwrap_or(DEFAULT_FILE_ATTACHMENT_MAX_BYTES),
+            tui_theme: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.theme.clone())
+                .unwrap_or_else(|| DEFAULT_TUI_THEME.to_string()),
+            tui_themes: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.themes.clone())
+                .unwrap_or_default(),
+            tui_keymap: cfg.tui.as_ref().map(|t| t.keymap).unwrap_or_default(),
+            tui_vim_keys: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.vim_keys.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1391,6 +1889,16 @@ impl Config {
         }
     }
 
+    /// `mcp_servers` as a session starts them: servers without their own
+    /// `tool_timeout_sec` use `tool_timeouts.mcp_tool_sec`.
+    pub fn mcp_servers_for_session(&self) -> HashMap<String, McpServerConfig> {
+        let mut servers = self.mcp_servers.clone();
+        for server in servers.values_mut() {
+            server.tool_timeout_sec = server.tool_timeout_sec.or(self.tool_timeouts.mcp_tool_sec);
+        }
+        servers
+    }
+
     pub fn set_windows_sandbox_globally(&mut self, value: bool) {
         crate::safety::set_windows_sandbox_enabled(value);
         if value {
@@ -1408,7 +1916,7 @@ fn default_review_model() -> String {
 
 /// Returns the path to the Codex configuration directory, which can be
 /// specified by the `CODEX_HOME` environment variable. If not set, defaults to
-/// `~/.codex`.
+/// `$XDG_CONFIG_HOME/codex` in [XDG mode](xdg), else `~/.codex`.
 ///
 /// - If `CODEX_HOME` is set, the value will be canonicalized and this
 ///   function will Err if the path does not exist.
@@ -1423,6 +1931,10 @@ pub fn find_codex_home() -> std::io::Result<PathBuf> {
         return PathBuf::from(val).canonicalize();
     }
 
+    if let Some(dirs) = xdg::active_xdg_dirs() {
+        return Ok(dirs.config.clone());
+    }
+
     let mut p = home_dir().ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
@@ -1436,7 +1948,7 @@ pub fn find_codex_home() -> std::io::Result<PathBuf> {
 /// Returns the path to the folder where Codex logs are stored. Does not verify
 /// that the directory exists.
 pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
-    let mut p = cfg.codex_home.clone();
+    let mut p = xdg::codex_state_home(&cfg.codex_home);
     p.push("log");
     Ok(p)
 }
@@ -1446,6 +1958,7 @@ mod tests {
     use crate::config::edit::ConfigEdit;
     use crate::config::edit::ConfigEditsBuilder;
     use crate::config::edit::apply_blocking;
+    use crate::config::types::ExplorationBudget;
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
@@ -1490,6 +2003,136 @@ persistence = "none"
         );
     }
 
+    #[test]
+    fn steering_settings_are_parsed_and_clamped() -> std::io::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[steering]
+max_bytes = 4096
+project_min_fraction = 1.5
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.steering,
+            SteeringConfig {
+                max_bytes: 4096,
+                project_min_fraction: 1.0,
+                learned_file: None,
+            }
+        );
+        assert_eq!(config.steering.project_reserved_bytes(), 4096);
+        Ok(())
+    }
+
+    #[test]
+    fn exploration_budget_requires_a_limit() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |contents: &str| {
+            let cfg = toml::from_str::<ConfigToml>(contents)
+                .expect("TOML deserialization should succeed");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load(
+            r#"
+[autonomy.exploration_budget]
+turns = 4
+"#,
+        )?;
+        assert_eq!(
+            config.autonomy.exploration_budget,
+            Some(ExplorationBudget {
+                turns: Some(4),
+                tokens: None,
+            })
+        );
+
+        let config = load("[autonomy.exploration_budget]\n")?;
+        assert_eq!(config.autonomy.exploration_budget, None);
+        Ok(())
+    }
+
+    #[test]
+    fn profile_bundles_timeouts_truncation_and_budget() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "ci-fast"
+tool_output_token_limit = 4000
+
+[tool_timeouts]
+mcp_tool_sec = 30
+
+[mcp_servers.docs]
+command = "docs-server"
+
+[mcp_servers.search]
+command = "search-server"
+tool_timeout_sec = 5
+
+[profiles.ci-fast]
+model = "gpt-5.1-codex-mini"
+tool_output_token_limit = 1000
+tool_timeouts = { shell_ms = 120000 }
+autonomy = { exploration_budget = { turns = 2 } }
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                model: Some("o3".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        // Explicit flags win over the profile.
+        assert_eq!(config.model, "o3");
+        assert_eq!(config.tool_output_token_limit, Some(1000));
+        assert_eq!(
+            config.tool_timeouts,
+            ToolTimeouts {
+                shell_ms: Some(120_000),
+                shell_max_ms: None,
+                shell_retries: None,
+                mcp_tool_sec: Some(Duration::from_secs(30)),
+            }
+        );
+        assert_eq!(
+            config.autonomy.exploration_budget,
+            Some(ExplorationBudget {
+                turns: Some(2),
+                tokens: None,
+            })
+        );
+        let servers = config.mcp_servers_for_session();
+        assert_eq!(
+            servers["docs"].tool_timeout_sec,
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            servers["search"].tool_timeout_sec,
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(config.mcp_servers["docs"].tool_timeout_sec, None);
+        Ok(())
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -1501,7 +2144,65 @@ persistence = "none"
         let tui = parsed.tui.expect("config should include tui section");
 
         assert_eq!(tui.notifications, Notifications::Enabled(true));
+        assert_eq!(tui.notification_method, NotificationMethod::Auto);
+        assert_eq!(tui.turn_notification_min_seconds, 0);
         assert!(tui.show_tooltips);
+        assert!(tui.syntax_highlighting);
+        assert!(tui.file_hyperlinks);
+        assert!(!tui.mouse);
+        assert_eq!(tui.show_reasoning, ShowReasoning::Auto);
+        assert!(!tui.accessible);
+        assert!(!tui.allow_attach);
+        assert_eq!(
+            tui.collapse_tool_output_lines,
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES
+        );
+        assert_eq!(
+            tui.file_attachment_max_bytes,
+            DEFAULT_FILE_ATTACHMENT_MAX_BYTES
+        );
+        assert_eq!(tui.theme, DEFAULT_TUI_THEME);
+        assert_eq!(tui.themes, BTreeMap::new());
+        assert_eq!(tui.keymap, Keymap::Default);
+        assert_eq!(tui.vim_keys, BTreeMap::new());
+    }
+
+    #[test]
+    fn model_prices_parse_per_model() {
+        let cfg = r#"
+[model_prices."gpt-5.1-codex"]
+input = 1.25
+cached_input = 0.125
+output = 10.0
+
+[model_prices.local]
+input = 0.0
+output = 0.0
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("model prices should parse");
+        assert_eq!(
+            parsed.model_prices,
+            HashMap::from([
+                (
+                    "gpt-5.1-codex".to_string(),
+                    ModelPrice {
+                        input: 1.25,
+                        cached_input: Some(0.125),
+                        output: 10.0,
+                    },
+                ),
+                (
+                    "local".to_string(),
+                    ModelPrice {
+                        input: 0.0,
+                        cached_input: None,
+                        output: 0.0,
+                    },
+                ),
+            ])
+        );
+        assert!(toml::from_str::<ConfigToml>("[model_prices.x]\ninput = 1.0\n").is_err());
     }
 
     #[test]
@@ -1818,6 +2519,77 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profiles_inherit_and_deep_merge_nested_tables() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+profile = "ci"
+
+[sandbox_workspace_write]
+network_access = true
+exclude_slash_tmp = true
+
+[steering]
+max_bytes = 1000
+
+[model_providers.corp]
+name = "Corp"
+base_url = "https://corp.example/v1"
+
+[profiles.base]
+model = "o3"
+model_provider = "corp"
+sandbox_mode = "workspace-write"
+
+[profiles.base.model_providers.corp]
+request_max_retries = 2
+
+[profiles.base.steering]
+project_min_fraction = 0.25
+
+[profiles.ci]
+extends = "base"
+model_reasoning_effort = "high"
+
+[profiles.ci.model_providers.corp]
+stream_max_retries = 1
+
+[profiles.ci.sandbox_workspace_write]
+network_access = false
+"#,
+        )
+        .expect("valid config");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.model.as_deref(), Some("o3"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(config.model_provider_id, "corp");
+        assert_eq!(
+            config.model_provider.base_url.as_deref(),
+            Some("https://corp.example/v1")
+        );
+        assert_eq!(config.model_provider.request_max_retries, Some(2));
+        assert_eq!(config.model_provider.stream_max_retries, Some(1));
+        assert!(matches!(
+            config.sandbox_policy,
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                exclude_slash_tmp: true,
+                ..
+            }
+        ));
+        assert_eq!(config.steering.max_bytes, 1000);
+        assert_eq!(config.steering.project_min_fraction, 0.25);
+
+        Ok(())
+    }
+
     #[test]
     fn cli_override_takes_precedence_over_profile_sandbox_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1943,6 +2715,7 @@ trust_level = "trusted"
 
         let overrides = LoaderOverrides {
             managed_config_path: Some(managed_path.clone()),
+            system_config_path: None,
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
         };
@@ -2062,6 +2835,7 @@ trust_level = "trusted"
 
         let overrides = LoaderOverrides {
             managed_config_path: Some(managed_path),
+            system_config_path: None,
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
         };
@@ -2982,12 +3756,15 @@ model_verbosity = "high"
             wire_api: crate::WireApi::Chat,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         };
         let model_provider_map = {
@@ -3050,16 +3827,24 @@ model_verbosity = "high"
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                container_sandbox: None,
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
                 cwd: fixture.cwd(),
+                workspace_roots: Vec::new(),
+                patch_policy: None,
+                approvals_policy: None,
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: HashMap::new(),
+                databases: HashMap::new(),
+                exporters: HashMap::new(),
+                http_request_allowed_hosts: Vec::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 model_providers: fixture.model_provider_map.clone(),
+                model_prices: HashMap::new(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
@@ -3085,16 +3870,39 @@ model_verbosity = "high"
                 use_experimental_unified_exec_tool: false,
                 use_experimental_use_rmcp_client: false,
                 ghost_snapshot: GhostSnapshotConfig::default(),
+                steering: SteeringConfig::default(),
+                autonomy: AutonomyConfig::default(),
+                idle_summarization: IdleSummarizationConfig::default(),
+                session_titles: SessionTitlesConfig::default(),
+                network: NetworkConfig::default(),
+                tool_timeouts: ToolTimeouts::default(),
+                deprecated_keys: Vec::new(),
+                feature_warnings: Vec::new(),
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
                 active_project: ProjectConfig { trust_level: None },
+                load_project_files: false,
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_notification_method: NotificationMethod::Auto,
+                tui_turn_notification_min_seconds: 0,
                 animations: true,
                 show_tooltips: true,
+                syntax_highlighting: true,
+                tui_file_hyperlinks: true,
+                tui_mouse: false,
+                tui_show_reasoning: ShowReasoning::Auto,
+                tui_accessible: false,
+                tui_allow_attach: false,
+                collapse_tool_output_lines: DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+                file_attachment_max_bytes: DEFAULT_FILE_ATTACHMENT_MAX_BYTES,
+                tui_theme: DEFAULT_TUI_THEME.to_string(),
+                tui_themes: BTreeMap::new(),
+                tui_keymap: Keymap::Default,
+                tui_vim_keys: BTreeMap::new(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3125,16 +3933,24 @@ model_verbosity = "high"
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            container_sandbox: None,
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            patch_policy: None,
+            approvals_policy: None,
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
+            databases: HashMap::new(),
+            exporters: HashMap::new(),
+            http_request_allowed_hosts: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3160,16 +3976,39 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            steering: SteeringConfig::default(),
+            autonomy: AutonomyConfig::default(),
+            idle_summarization: IdleSummarizationConfig::default(),
+            session_titles: SessionTitlesConfig::default(),
+            network: NetworkConfig::default(),
+            tool_timeouts: ToolTimeouts::default(),
+            deprecated_keys: Vec::new(),
+            feature_warnings: Vec::new(),
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            load_project_files: false,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: NotificationMethod::Auto,
+            tui_turn_notification_min_seconds: 0,
             animations: true,
             show_tooltips: true,
+            syntax_highlighting: true,
+            tui_file_hyperlinks: true,
+            tui_mouse: false,
+            tui_show_reasoning: ShowReasoning::Auto,
+            tui_accessible: false,
+            tui_allow_attach: false,
+            collapse_tool_output_lines: DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+            file_attachment_max_bytes: DEFAULT_FILE_ATTACHMENT_MAX_BYTES,
+            tui_theme: DEFAULT_TUI_THEME.to_string(),
+            tui_themes: BTreeMap::new(),
+            tui_keymap: Keymap::Default,
+            tui_vim_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -3215,16 +4054,24 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            container_sandbox: None,
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            patch_policy: None,
+            approvals_policy: None,
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
+            databases: HashMap::new(),
+            exporters: HashMap::new(),
+            http_request_allowed_hosts: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3250,16 +4097,39 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            steering: SteeringConfig::default(),
+            autonomy: AutonomyConfig::default(),
+            idle_summarization: IdleSummarizationConfig::default(),
+            session_titles: SessionTitlesConfig::default(),
+            network: NetworkConfig::default(),
+            tool_timeouts: ToolTimeouts::default(),
+            deprecated_keys: Vec::new(),
+            feature_warnings: Vec::new(),
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            load_project_files: false,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: NotificationMethod::Auto,
+            tui_turn_notification_min_seconds: 0,
             animations: true,
             show_tooltips: true,
+            syntax_highlighting: true,
+            tui_file_hyperlinks: true,
+            tui_mouse: false,
+            tui_show_reasoning: ShowReasoning::Auto,
+            tui_accessible: false,
+            tui_allow_attach: false,
+            collapse_tool_output_lines: DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+            file_attachment_max_bytes: DEFAULT_FILE_ATTACHMENT_MAX_BYTES,
+            tui_theme: DEFAULT_TUI_THEME.to_string(),
+            tui_themes: BTreeMap::new(),
+            tui_keymap: Keymap::Default,
+            tui_vim_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -3291,16 +4161,24 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            container_sandbox: None,
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            workspace_roots: Vec::new(),
+            patch_policy: None,
+            approvals_policy: None,
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
+            databases: HashMap::new(),
+            exporters: HashMap::new(),
+            http_request_allowed_hosts: Vec::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
             model_providers: fixture.model_provider_map.clone(),
+            model_prices: HashMap::new(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
@@ -3326,16 +4204,39 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            steering: SteeringConfig::default(),
+            autonomy: AutonomyConfig::default(),
+            idle_summarization: IdleSummarizationConfig::default(),
+            session_titles: SessionTitlesConfig::default(),
+            network: NetworkConfig::default(),
+            tool_timeouts: ToolTimeouts::default(),
+            deprecated_keys: Vec::new(),
+            feature_warnings: Vec::new(),
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            load_project_files: false,
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: NotificationMethod::Auto,
+            tui_turn_notification_min_seconds: 0,
             animations: true,
             show_tooltips: true,
+            syntax_highlighting: true,
+            tui_file_hyperlinks: true,
+            tui_mouse: false,
+            tui_show_reasoning: ShowReasoning::Auto,
+            tui_accessible: false,
+            tui_allow_attach: false,
+            collapse_tool_output_lines: DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+            file_attachment_max_bytes: DEFAULT_FILE_ATTACHMENT_MAX_BYTES,
+            tui_theme: DEFAULT_TUI_THEME.to_string(),
+            tui_themes: BTreeMap::new(),
+            tui_keymap: Keymap::Default,
+            tui_vim_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -3362,6 +4263,104 @@ model_verbosity = "high"
         Ok(())
     }
 
+    #[test]
+    fn stored_trust_decision_fills_in_policies() -> std::io::Result<()> {
+        use crate::config::trust_store::ProjectTrust;
+        use crate::config::trust_store::record_project_trust;
+
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        record_project_trust(
+            codex_home.path(),
+            project.path(),
+            ProjectTrust {
+                approval_policy: Some(AskForApproval::Never),
+                sandbox_mode: Some(SandboxMode::ReadOnly),
+                load_project_files: false,
+                ..ProjectTrust::new(TrustLevel::Trusted)
+            },
+        )?;
+        let load = |cfg: ConfigToml| {
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides {
+                    cwd: Some(project.path().to_path_buf()),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load(ConfigToml::default())?;
+        assert!(config.active_project.is_trusted());
+        assert_eq!(config.approval_policy.value(), AskForApproval::Never);
+        assert_eq!(config.sandbox_policy, SandboxPolicy::new_read_only_policy());
+        assert!(!config.load_project_files);
+
+        let config = load(ConfigToml {
+            approval_policy: Some(AskForApproval::OnRequest),
+            ..Default::default()
+        })?;
+        assert_eq!(config.approval_policy.value(), AskForApproval::OnRequest);
+        Ok(())
+    }
+
+    #[test]
+    fn stored_trust_decision_pins_model_defaults() -> std::io::Result<()> {
+        use crate::config::trust_store::ProjectTrust;
+        use crate::config::trust_store::record_project_trust;
+
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        record_project_trust(
+            codex_home.path(),
+            project.path(),
+            ProjectTrust {
+                model: Some("gpt-5.1-codex-mini".to_string()),
+                model_reasoning_effort: Some(ReasoningEffort::Low),
+                model_verbosity: Some(Verbosity::Low),
+                ..ProjectTrust::new(TrustLevel::Trusted)
+            },
+        )?;
+        let load = |cfg: ConfigToml| {
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides {
+                    cwd: Some(project.path().to_path_buf()),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load(ConfigToml {
+            model: Some("gpt-5.1-codex".to_string()),
+            model_reasoning_effort: Some(ReasoningEffort::High),
+            ..Default::default()
+        })?;
+        assert_eq!(config.model.as_deref(), Some("gpt-5.1-codex-mini"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::Low));
+        assert_eq!(config.model_verbosity, Some(Verbosity::Low));
+
+        // A profile still picks its own model.
+        let config = load(ConfigToml {
+            profile: Some("deep".to_string()),
+            profiles: HashMap::from([(
+                "deep".to_string(),
+                ConfigProfile {
+                    model: Some("gpt-5.1-codex-max".to_string()),
+                    model_reasoning_effort: Some(ReasoningEffort::XHigh),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        })?;
+        assert_eq!(config.model.as_deref(), Some("gpt-5.1-codex-max"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::XHigh));
+        assert_eq!(config.model_verbosity, Some(Verbosity::Low));
+        Ok(())
+    }
+
     #[test]
     fn test_set_project_trusted_writes_explicit_tables() -> anyhow::Result<()> {
         let project_dir = Path::new("/some/path");
@@ -1,6 +1,20 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_absolute_path::AbsolutePathBufGuard;
+use schemars::JsonSchema;
 use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use toml::Value as TomlValue;
 
+use crate::config::types::AutonomyToml;
+use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SteeringToml;
+use crate::config::types::ToolTimeouts;
+use crate::config_loader::merge_toml_values;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
@@ -9,8 +23,11 @@ use codex_protocol::openai_models::ReasoningEffort;
 
 /// Collection of common configuration options that a user can define as a unit
 /// in `config.toml`.
-#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema)]
 pub struct ConfigProfile {
+    /// Name of another profile this one starts from. Settings made here win
+    /// over the inherited ones; tables are merged key by key.
+    pub extends: Option<String>,
     pub model: Option<String>,
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
@@ -29,10 +46,170 @@ pub struct ConfigProfile {
     pub experimental_use_freeform_apply_patch: Option<bool>,
     pub tools_web_search: Option<bool>,
     pub tools_view_image: Option<bool>,
+    pub tool_output_token_limit: Option<usize>,
     /// Optional feature toggles scoped to this profile.
     #[serde(default)]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Provider settings deep-merged over the top-level and built-in
+    /// `model_providers` entries of the same id.
+    #[serde(default)]
+    #[schemars(with = "Option<HashMap<String, HashMap<String, serde_json::Value>>>")]
+    pub model_providers: Option<toml::Table>,
+    /// Deep-merged over the top-level `[sandbox_workspace_write]` table.
+    #[serde(default)]
+    #[schemars(with = "Option<SandboxWorkspaceWrite>")]
+    pub sandbox_workspace_write: Option<toml::Table>,
+    /// Deep-merged over the top-level `[steering]` table.
+    #[serde(default)]
+    #[schemars(with = "Option<SteeringToml>")]
+    pub steering: Option<toml::Table>,
+    /// Deep-merged over the top-level `[tool_timeouts]` table.
+    #[serde(default)]
+    #[schemars(with = "Option<ToolTimeouts>")]
+    pub tool_timeouts: Option<toml::Table>,
+    /// Deep-merged over the top-level `[autonomy]` table.
+    #[serde(default)]
+    #[schemars(with = "Option<AutonomyToml>")]
+    pub autonomy: Option<toml::Table>,
+}
+
+impl ConfigProfile {
+    /// Layers `self` over `parent`: values set in `self` win, and `features`
+    /// and the nested tables are merged key by key.
+    fn inherit_from(self, parent: ConfigProfile) -> ConfigProfile {
+        let features = match (parent.features, self.features) {
+            (Some(mut parent), Some(child)) => {
+                parent.entries.extend(child.entries);
+                Some(parent)
+            }
+            (parent, child) => child.or(parent),
+        };
+        ConfigProfile {
+            extends: None,
+            model: self.model.or(parent.model),
+            model_provider: self.model_provider.or(parent.model_provider),
+            approval_policy: self.approval_policy.or(parent.approval_policy),
+            sandbox_mode: self.sandbox_mode.or(parent.sandbox_mode),
+            model_reasoning_effort: self
+                .model_reasoning_effort
+                .or(parent.model_reasoning_effort),
+            model_reasoning_summary: self
+                .model_reasoning_summary
+                .or(parent.model_reasoning_summary),
+            model_verbosity: self.model_verbosity.or(parent.model_verbosity),
+            chatgpt_base_url: self.chatgpt_base_url.or(parent.chatgpt_base_url),
+            experimental_instructions_file: self
+                .experimental_instructions_file
+                .or(parent.experimental_instructions_file),
+            experimental_compact_prompt_file: self
+                .experimental_compact_prompt_file
+                .or(parent.experimental_compact_prompt_file),
+            include_apply_patch_tool: self
+                .include_apply_patch_tool
+                .or(parent.include_apply_patch_tool),
+            experimental_use_unified_exec_tool: self
+                .experimental_use_unified_exec_tool
+                .or(parent.experimental_use_unified_exec_tool),
+            experimental_use_rmcp_client: self
+                .experimental_use_rmcp_client
+                .or(parent.experimental_use_rmcp_client),
+            experimental_use_freeform_apply_patch: self
+                .experimental_use_freeform_apply_patch
+                .or(parent.experimental_use_freeform_apply_patch),
+            tools_web_search: self.tools_web_search.or(parent.tools_web_search),
+            tools_view_image: self.tools_view_image.or(parent.tools_view_image),
+            tool_output_token_limit: self
+                .tool_output_token_limit
+                .or(parent.tool_output_token_limit),
+            features,
+            oss_provider: self.oss_provider.or(parent.oss_provider),
+            model_providers: merge_tables(parent.model_providers, self.model_providers),
+            sandbox_workspace_write: merge_tables(
+                parent.sandbox_workspace_write,
+                self.sandbox_workspace_write,
+            ),
+            steering: merge_tables(parent.steering, self.steering),
+            tool_timeouts: merge_tables(parent.tool_timeouts, self.tool_timeouts),
+            autonomy: merge_tables(parent.autonomy, self.autonomy),
+        }
+    }
+}
+
+/// Looks up profile `name` and folds in every profile it `extends`, nearest
+/// last, so the named profile has the final say.
+pub(crate) fn resolve_profile(
+    profiles: &HashMap<String, ConfigProfile>,
+    name: &str,
+) -> io::Result<ConfigProfile> {
+    let mut chain: Vec<&str> = Vec::new();
+    let mut next = Some(name);
+    while let Some(current) = next {
+        if chain.contains(&current) {
+            chain.push(current);
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "config profile `{name}` has an `extends` cycle: {}",
+                    chain.join(" -> ")
+                ),
+            ));
+        }
+        let Some(profile) = profiles.get(current) else {
+            let message = match chain.last() {
+                Some(child) => {
+                    format!("config profile `{child}` extends unknown profile `{current}`")
+                }
+                None => format!("config profile `{current}` not found"),
+            };
+            return Err(io::Error::new(io::ErrorKind::NotFound, message));
+        };
+        chain.push(current);
+        next = profile.extends.as_deref();
+    }
+
+    let mut resolved = ConfigProfile::default();
+    for current in chain.into_iter().rev() {
+        resolved = profiles[current].clone().inherit_from(resolved);
+    }
+    Ok(resolved)
+}
+
+/// Deep-merges a profile's `overlay` table over `base` and deserializes the
+/// result. Relative paths in the merged table resolve against `base_dir`.
+pub(crate) fn deep_merge_into<T: Serialize + DeserializeOwned>(
+    base: Option<&T>,
+    overlay: toml::Table,
+    key: &str,
+    base_dir: &Path,
+) -> io::Result<T> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid profile override for `{key}`: {e}"),
+        )
+    };
+    let mut merged = match base {
+        Some(base) => TomlValue::try_from(base).map_err(|e| invalid(&e))?,
+        None => TomlValue::Table(toml::Table::new()),
+    };
+    merge_toml_values(&mut merged, &TomlValue::Table(overlay));
+    let _guard = AbsolutePathBufGuard::new(base_dir);
+    merged.try_into().map_err(|e| invalid(&e))
+}
+
+fn merge_tables(base: Option<toml::Table>, overlay: Option<toml::Table>) -> Option<toml::Table> {
+    match (base, overlay) {
+        (Some(base), Some(overlay)) => {
+            let mut merged = TomlValue::Table(base);
+            merge_toml_values(&mut merged, &TomlValue::Table(overlay));
+            match merged {
+                TomlValue::Table(merged) => Some(merged),
+                _ => None,
+            }
+        }
+        (base, overlay) => overlay.or(base),
+    }
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
@@ -48,3 +225,77 @@ impl From<ConfigProfile> for codex_app_server_protocol::Profile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn profiles(contents: &str) -> HashMap<String, ConfigProfile> {
+        toml::from_str(contents).expect("valid profiles")
+    }
+
+    #[test]
+    fn child_settings_win_and_tables_merge() {
+        let profiles = profiles(
+            r#"
+[base]
+model = "o3"
+approval_policy = "on-request"
+features = { view_image_tool = false, web_search_request = true }
+steering = { max_bytes = 1000, project_min_fraction = 0.5 }
+
+[fast]
+extends = "base"
+model = "gpt-5.1-codex-mini"
+features = { view_image_tool = true }
+steering = { project_min_fraction = 0.25 }
+"#,
+        );
+
+        let resolved = resolve_profile(&profiles, "fast").expect("resolves");
+
+        assert_eq!(resolved.extends, None);
+        assert_eq!(resolved.model.as_deref(), Some("gpt-5.1-codex-mini"));
+        assert_eq!(resolved.approval_policy, Some(AskForApproval::OnRequest));
+        assert_eq!(
+            resolved.features.expect("features").entries,
+            [
+                ("view_image_tool".to_string(), true),
+                ("web_search_request".to_string(), true),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            resolved.steering,
+            Some(toml::from_str("max_bytes = 1000\nproject_min_fraction = 0.25").expect("toml"))
+        );
+    }
+
+    #[test]
+    fn reports_cycles_and_unknown_parents() {
+        let profiles = profiles(
+            r#"
+a = { extends = "b" }
+b = { extends = "a" }
+orphan = { extends = "missing" }
+"#,
+        );
+
+        assert_eq!(
+            resolve_profile(&profiles, "a").unwrap_err().to_string(),
+            "config profile `a` has an `extends` cycle: a -> b -> a"
+        );
+        assert_eq!(
+            resolve_profile(&profiles, "orphan")
+                .unwrap_err()
+                .to_string(),
+            "config profile `orphan` extends unknown profile `missing`"
+        );
+        assert_eq!(
+            resolve_profile(&profiles, "nope").unwrap_err().to_string(),
+            "config profile `nope` not found"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Live reload of `$CODEX_HOME/config.toml` (with its includes) and the
+//! project `.codex/config.toml` for a running session.
+//!
+//! The session polls the merged file layers every [`POLL_INTERVAL`]. When
+//! they change, the settings in [`LiveSetting`] are applied to the session
+//! and every other changed key is reported as requiring a restart.
+
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use toml::Value as TomlValue;
+
+use super::Config;
+use super::ConfigBuilder;
+use super::ConfigOverrides;
+use crate::config_loader::LoaderOverrides;
+use crate::config_loader::apply_project_config_layer;
+use crate::config_loader::load_config_layers_state;
+use crate::features::Feature;
+
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What a session's watcher last saw on disk.
+#[derive(Debug, Default)]
+pub(crate) struct ConfigWatchState {
+    pub(crate) watched: Option<WatchedConfigFiles>,
+    /// The last load error reported, so a broken file warns only once.
+    pub(crate) last_error: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct WatchedConfigFiles {
+    pub(crate) files: TomlValue,
+    pub(crate) config: Config,
+}
+
+/// Settings a running session picks up without a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LiveSetting {
+    Model,
+    ReasoningEffort,
+    ReasoningSummary,
+    Notify,
+    /// `features.steering` and the `[steering]` table.
+    Steering,
+}
+
+impl LiveSetting {
+    /// Maps a dotted config key to the setting it controls, if that setting
+    /// can change mid-session.
+    pub(crate) fn for_key(key: &str) -> Option<Self> {
+        match key {
+            "model" => Some(Self::Model),
+            "model_reasoning_effort" => Some(Self::ReasoningEffort),
+            "model_reasoning_summary" => Some(Self::ReasoningSummary),
+            "notify" => Some(Self::Notify),
+            "features.steering" | "steering" => Some(Self::Steering),
+            key if key.starts_with("steering.") => Some(Self::Steering),
+            _ => None,
+        }
+    }
+
+    /// Whether `old` and `new` disagree on this setting.
+    pub(crate) fn differs(self, old: &Config, new: &Config) -> bool {
+        match self {
+            Self::Model => old.model != new.model,
+            Self::ReasoningEffort => old.model_reasoning_effort != new.model_reasoning_effort,
+            Self::ReasoningSummary => old.model_reasoning_summary != new.model_reasoning_summary,
+            Self::Notify => old.notify != new.notify,
+            Self::Steering => {
+                old.steering != new.steering
+                    || old.features.enabled(Feature::Steering)
+                        != new.features.enabled(Feature::Steering)
+            }
+        }
+    }
+}
+
+/// The merged user and project config files for `cwd`, before command-line
+/// overrides. Used to notice edits.
+pub(crate) async fn load_config_files(codex_home: &Path, cwd: &Path) -> io::Result<TomlValue> {
+    let stack = load_config_layers_state(codex_home, &[], LoaderOverrides::default()).await?;
+    let stack = apply_project_config_layer(stack, codex_home, cwd).await?;
+    Ok(stack.effective_config())
+}
+
+/// The config the files alone produce for `cwd`. Comparing it with the
+/// session's own values tells settings that follow the files apart from
+/// ones pinned by command-line flags or in-session commands such as `/model`.
+pub(crate) async fn load_config_from_files(codex_home: &Path, cwd: &Path) -> io::Result<Config> {
+    ConfigBuilder::default()
+        .codex_home(codex_home.to_path_buf())
+        .harness_overrides(ConfigOverrides {
+            cwd: Some(cwd.to_path_buf()),
+            ..Default::default()
+        })
+        .build()
+        .await
+}
+
+/// Dotted paths of every value that was added, removed, or modified between
+/// `old` and `new`, descending into tables but treating arrays as values.
+pub(crate) fn changed_keys(old: &TomlValue, new: &TomlValue) -> Vec<String> {
+    let mut changed = Vec::new();
+    collect_changed_keys("", Some(old), Some(new), &mut changed);
+    changed.sort();
+    changed
+}
+
+fn collect_changed_keys(
+    prefix: &str,
+    old: Option<&TomlValue>,
+    new: Option<&TomlValue>,
+    changed: &mut Vec<String>,
+) {
+    match (old, new) {
+        (Some(TomlValue::Table(old)), Some(TomlValue::Table(new))) => {
+            let keys = old
+                .keys()
+                .chain(new.keys().filter(|key| !old.contains_key(*key)));
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                collect_changed_keys(&path, old.get(key), new.get(key), changed);
+            }
+        }
+        (Some(TomlValue::Table(_)), None) => {
+            let empty = TomlValue::Table(toml::map::Map::new());
+            collect_changed_keys(prefix, old, Some(&empty), changed);
+        }
+        (None, Some(TomlValue::Table(_))) => {
+            let empty = TomlValue::Table(toml::map::Map::new());
+            collect_changed_keys(prefix, Some(&empty), new, changed);
+        }
+        (old, new) if old != new => changed.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified_leaves() {
+        let old = toml(
+            r#"
+model = "o3"
+notify = ["notify-send"]
+
+[tui]
+notifications = true
+
+[mcp_servers.docs]
+command = "docs"
+"#,
+        );
+        let new = toml(
+            r#"
+model = "gpt-5.1-codex"
+notify = ["notify-send"]
+
+[tui]
+notifications = true
+
+[steering]
+max_bytes = 4096
+project_min_fraction = 0.5
+"#,
+        );
+
+        assert_eq!(
+            changed_keys(&old, &new),
+            vec![
+                "mcp_servers.docs.command".to_string(),
+                "model".to_string(),
+                "steering.max_bytes".to_string(),
+                "steering.project_min_fraction".to_string(),
+            ]
+        );
+        assert_eq!(changed_keys(&new, &new), Vec::<String>::new());
+    }
+
+    #[test]
+    fn classifies_live_settings() {
+        assert_eq!(LiveSetting::for_key("model"), Some(LiveSetting::Model));
+        assert_eq!(
+            LiveSetting::for_key("features.steering"),
+            Some(LiveSetting::Steering)
+        );
+        assert_eq!(
+            LiveSetting::for_key("steering.max_bytes"),
+            Some(LiveSetting::Steering)
+        );
+        assert_eq!(LiveSetting::for_key("steeringx"), None);
+        assert_eq!(LiveSetting::for_key("approval_policy"), None);
+        assert_eq!(LiveSetting::for_key("profiles.fast.model"), None);
+    }
+}
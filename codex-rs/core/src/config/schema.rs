@@ -0,0 +1,748 @@
+//! JSON Schema for `config.toml` and the checks behind `codex config validate`.
+//!
+//! The schema is generated from [`ConfigToml`], so it always matches what
+//! Codex actually reads. Objects with a fixed set of keys are closed
+//! (`additionalProperties: false`): serde silently ignores unknown keys, which
+//! is how typos go unnoticed, so both editors and the validator report them.
+
+use std::fmt;
+
+use schemars::r#gen::SchemaSettings;
+use serde_json::Map as JsonMap;
+use serde_json::Value as JsonValue;
+use toml::Value as TomlValue;
+
+use crate::config::ConfigToml;
+use crate::config::migration::replacement_for;
+use crate::features::FEATURES;
+use crate::features::is_known_feature_key;
+use crate::features::legacy_key_replacement;
+
+/// Returns the JSON Schema (draft 7) describing `config.toml`.
+pub fn config_json_schema() -> JsonValue {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| settings.option_add_null_type = false)
+        .into_generator();
+    let root = generator.into_root_schema_for::<ConfigToml>();
+    let mut schema = serde_json::to_value(root).unwrap_or_default();
+    close_objects(&mut schema);
+    mark_deprecated(&mut schema);
+    if let Some(schema) = schema.as_object_mut() {
+        schema.insert(
+            "title".to_string(),
+            JsonValue::String("Codex config.toml".to_string()),
+        );
+    }
+    schema
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A single problem found in a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: ConfigDiagnosticSeverity,
+    /// Dotted path of the offending key, such as `tui.notifications`. Empty
+    /// when the problem is not tied to a key (for example a syntax error).
+    pub key_path: String,
+    /// 1-based line number, when known.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == ConfigDiagnosticSeverity::Error
+    }
+
+    fn error(key_path: String, message: String) -> Self {
+        Self {
+            severity: ConfigDiagnosticSeverity::Error,
+            key_path,
+            line: None,
+            message,
+        }
+    }
+
+    fn warning(key_path: String, message: String) -> Self {
+        Self {
+            severity: ConfigDiagnosticSeverity::Warning,
+            key_path,
+            line: None,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            ConfigDiagnosticSeverity::Error => write!(f, "error: ")?,
+            ConfigDiagnosticSeverity::Warning => write!(f, "warning: ")?,
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        if !self.key_path.is_empty() {
+            write!(f, "`{}`: ", self.key_path)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Checks the contents of a `config.toml` file against the config schema.
+///
+/// Reports syntax errors, unknown keys, values of the wrong type, invalid
+/// enum values, and deprecated keys (as warnings, with their replacement).
+pub fn validate_config_toml(contents: &str) -> Vec<ConfigDiagnostic> {
+    let table = match toml::from_str::<toml::Table>(contents) {
+        Ok(table) => table,
+        Err(err) => {
+            return vec![ConfigDiagnostic {
+                line: err.span().map(|span| line_of(contents, span.start)),
+                ..ConfigDiagnostic::error(String::new(), err.message().trim().to_string())
+            }];
+        }
+    };
+
+    let mut diagnostics =
+        check_against_schema(&TomlValue::Table(table), ConfigDiagnosticSeverity::Error);
+
+    // The schema cannot express every rule (for example which MCP server keys
+    // go together), so fall back to the deserializer for anything it missed.
+    if !diagnostics.iter().any(ConfigDiagnostic::is_error)
+        && let Err(err) = toml::from_str::<ConfigToml>(contents)
+    {
+        diagnostics.push(ConfigDiagnostic {
+            line: err.span().map(|span| line_of(contents, span.start)),
+            ..ConfigDiagnostic::error(String::new(), err.message().trim().to_string())
+        });
+    }
+    diagnostics
+}
+
+/// Checks `-c`/`--config` overrides, collected into a partial config table,
+/// against the config schema. Unknown keys are only warnings: overrides have
+/// always been free to set keys this version of Codex does not read.
+pub fn validate_config_overrides(overrides: &TomlValue) -> Vec<ConfigDiagnostic> {
+    check_against_schema(overrides, ConfigDiagnosticSeverity::Warning)
+}
+
+fn check_against_schema(
+    value: &TomlValue,
+    unknown_key_severity: ConfigDiagnosticSeverity,
+) -> Vec<ConfigDiagnostic> {
+    let schema = config_json_schema();
+    let empty = JsonMap::new();
+    let definitions = schema
+        .get("definitions")
+        .and_then(JsonValue::as_object)
+        .unwrap_or(&empty);
+    let mut validator = Validator {
+        definitions,
+        unknown_key_severity,
+        diagnostics: Vec::new(),
+    };
+    validator.check(&[], value, &schema);
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    definitions: &'a JsonMap<String, JsonValue>,
+    unknown_key_severity: ConfigDiagnosticSeverity,
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn check(&mut self, path: &[String], value: &TomlValue, schema: &'a JsonValue) {
+        let schema = self.resolve(schema);
+        if let Some(branches) = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(JsonValue::as_array)
+        {
+            self.check_alternatives(path, value, branches);
+            return;
+        }
+        if let Some(allowed) = schema.get("enum").and_then(JsonValue::as_array) {
+            if !allowed
+                .iter()
+                .any(|allowed| toml_matches_json(value, allowed))
+            {
+                self.diagnostics.push(ConfigDiagnostic::error(
+                    render_path(path),
+                    format!(
+                        "invalid value {}; expected one of {}",
+                        describe_value(value),
+                        describe_choices(allowed)
+                    ),
+                ));
+            }
+            return;
+        }
+        if let Some(expected) = schema.get("type")
+            && !type_matches(value, expected)
+        {
+            self.diagnostics.push(ConfigDiagnostic::error(
+                render_path(path),
+                format!(
+                    "expected {}, found {}",
+                    describe_types(expected),
+                    type_name(value)
+                ),
+            ));
+            return;
+        }
+
+        match value {
+            TomlValue::Table(table) => self.check_table(path, table, schema),
+            TomlValue::Array(items) => {
+                if let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) {
+                    for (index, item) in items.iter().enumerate() {
+                        let mut item_path = path.to_vec();
+                        item_path.push(format!("[{index}]"));
+                        self.check(&item_path, item, item_schema);
+                    }
+                }
+            }
+            TomlValue::Integer(number) => {
+                if let Some(minimum) = schema.get("minimum").and_then(JsonValue::as_f64)
+                    && (*number as f64) < minimum
+                {
+                    self.diagnostics.push(ConfigDiagnostic::error(
+                        render_path(path),
+                        format!("must be at least {minimum}"),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_table(&mut self, path: &[String], table: &toml::Table, schema: &'a JsonValue) {
+        let properties = schema.get("properties").and_then(JsonValue::as_object);
+        let additional = schema.get("additionalProperties");
+        for (key, value) in table {
+            let mut key_path = path.to_vec();
+            key_path.push(key.clone());
+            let deprecated = self.check_deprecated(&key_path);
+
+            let property = properties.and_then(|properties| {
+                properties
+                    .get(key)
+                    .or_else(|| renamed_key(&key_path).and_then(|name| properties.get(&name)))
+            });
+            if let Some(property) = property {
+                self.check(&key_path, value, property);
+                continue;
+            }
+            match additional {
+                Some(JsonValue::Bool(false)) if !deprecated => {
+                    let known = properties
+                        .map(|properties| properties.keys().map(String::as_str).collect())
+                        .unwrap_or_default();
+                    self.unknown_key(path, key, known);
+                }
+                Some(extra) if extra.is_object() => {
+                    self.check_feature_key(path, key, deprecated);
+                    self.check(&key_path, value, extra);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Tries each branch of an `anyOf`/`oneOf` and reports the most specific
+    /// failure when none of them accepts the value.
+    fn check_alternatives(
+        &mut self,
+        path: &[String],
+        value: &TomlValue,
+        branches: &'a [JsonValue],
+    ) {
+        let mut attempts = Vec::new();
+        for branch in branches {
+            let mut attempt = Validator {
+                definitions: self.definitions,
+                unknown_key_severity: self.unknown_key_severity,
+                diagnostics: Vec::new(),
+            };
+            attempt.check(path, value, branch);
+            if !attempt.diagnostics.iter().any(ConfigDiagnostic::is_error) {
+                self.diagnostics.extend(attempt.diagnostics);
+                return;
+            }
+            attempts.push((self.resolve(branch), attempt.diagnostics));
+        }
+
+        // Unit enum variants each get their own branch; list them together.
+        let choices: Option<Vec<JsonValue>> = attempts
+            .iter()
+            .map(|(branch, _)| branch.get("enum").and_then(JsonValue::as_array).cloned())
+            .collect::<Option<Vec<_>>>()
+            .map(|choices| choices.into_iter().flatten().collect());
+        if let Some(choices) = choices {
+            self.diagnostics.push(ConfigDiagnostic::error(
+                render_path(path),
+                format!(
+                    "invalid value {}; expected one of {}",
+                    describe_value(value),
+                    describe_choices(&choices)
+                ),
+            ));
+            return;
+        }
+
+        // When only one branch has the right shape, its errors are the useful ones.
+        let mut same_type = attempts
+            .into_iter()
+            .filter(|(branch, _)| branch.get("type").is_none_or(|ty| type_matches(value, ty)));
+        match (same_type.next(), same_type.next()) {
+            (Some((_, diagnostics)), None) => self.diagnostics.extend(diagnostics),
+            _ => {
+                let expected = branches
+                    .iter()
+                    .filter_map(|branch| self.resolve(branch).get("type"))
+                    .map(describe_types)
+                    .collect::<Vec<_>>()
+                    .join(" or ");
+                self.diagnostics.push(ConfigDiagnostic::error(
+                    render_path(path),
+                    format!("expected {expected}, found {}", type_name(value)),
+                ));
+            }
+        }
+    }
+
+    /// Warns about a deprecated key. Returns `true` when `path` is one.
+    fn check_deprecated(&mut self, path: &[String]) -> bool {
+        let (profile, relative) = split_profile(path);
+        let Some(replacement) = replacement_for(&relative.join(".")) else {
+            return false;
+        };
+        let replacement = match profile {
+            Some(profile) => format!("profiles.{profile}.{replacement}"),
+            None => replacement,
+        };
+        self.diagnostics.push(ConfigDiagnostic::warning(
+            render_path(path),
+            format!("deprecated; use `{replacement}` instead"),
+        ));
+        true
+    }
+
+    /// Feature tables accept any key in the schema; flag names Codex does not know.
+    fn check_feature_key(&mut self, parent: &[String], key: &str, deprecated: bool) {
+        let (_, relative) = split_profile(parent);
+        if deprecated || relative != ["features"] || is_known_feature_key(key) {
+            return;
+        }
+        let known = FEATURES.iter().map(|spec| spec.key).collect();
+        self.unknown_key(parent, key, known);
+    }
+
+    fn unknown_key(&mut self, parent: &[String], key: &str, known: Vec<&str>) {
+        let mut message = if parent.is_empty() {
+            format!("unknown key `{key}`")
+        } else {
+            format!("unknown key `{key}` in `[{}]`", render_path(parent))
+        };
+        if let Some(suggestion) = closest_match(key, known) {
+            message.push_str(&format!("; did you mean `{suggestion}`?"));
+        }
+        let mut path = parent.to_vec();
+        path.push(key.to_string());
+        self.diagnostics.push(ConfigDiagnostic {
+            severity: self.unknown_key_severity,
+            ..ConfigDiagnostic::error(render_path(&path), message)
+        });
+    }
+
+    /// Follows `$ref`s and the single-element `allOf` wrappers schemars emits
+    /// around documented references.
+    fn resolve(&self, mut schema: &'a JsonValue) -> &'a JsonValue {
+        loop {
+            if let Some(target) = schema
+                .get("$ref")
+                .and_then(JsonValue::as_str)
+                .and_then(|reference| reference.strip_prefix("#/definitions/"))
+                .and_then(|name| self.definitions.get(name))
+            {
+                schema = target;
+                continue;
+            }
+            if let Some([only]) = schema
+                .get("allOf")
+                .and_then(JsonValue::as_array)
+                .map(Vec::as_slice)
+            {
+                schema = only;
+                continue;
+            }
+            return schema;
+        }
+    }
+}
+
+/// The current name of a key that was renamed within its own table.
+fn renamed_key(path: &[String]) -> Option<String> {
+    let (_, relative) = split_profile(path);
+    let (_, parent) = relative.split_last()?;
+    let replacement = replacement_for(&relative.join("."))?;
+    let (new_parent, name) = replacement.rsplit_once('.').unwrap_or(("", &replacement));
+    (new_parent == parent.join(".")).then(|| name.to_string())
+}
+
+/// Splits `profiles.<name>.rest` into the profile name and `rest`.
+fn split_profile(path: &[String]) -> (Option<&str>, &[String]) {
+    match path {
+        [profiles, name, rest @ ..] if profiles == "profiles" => (Some(name.as_str()), rest),
+        _ => (None, path),
+    }
+}
+
+/// Sets `additionalProperties: false` on every object schema that lists its
+/// properties and does not already say what to do with other keys.
+fn close_objects(schema: &mut JsonValue) {
+    match schema {
+        JsonValue::Object(object) => {
+            if object.contains_key("properties") && !object.contains_key("additionalProperties") {
+                object.insert("additionalProperties".to_string(), JsonValue::Bool(false));
+            }
+            object.values_mut().for_each(close_objects);
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(close_objects),
+        _ => {}
+    }
+}
+
+/// Flags legacy keys so editors can strike them through.
+fn mark_deprecated(schema: &mut JsonValue) {
+    mark_properties("", schema.get_mut("properties"));
+    let Some(definitions) = schema
+        .get_mut("definitions")
+        .and_then(JsonValue::as_object_mut)
+    else {
+        return;
+    };
+    for (name, prefix) in [("ConfigProfile", ""), ("ToolsToml", "tools.")] {
+        mark_properties(
+            prefix,
+            definitions
+                .get_mut(name)
+                .and_then(|definition| definition.get_mut("properties")),
+        );
+    }
+}
+
+fn mark_properties(prefix: &str, properties: Option<&mut JsonValue>) {
+    let Some(properties) = properties.and_then(JsonValue::as_object_mut) else {
+        return;
+    };
+    for (key, property) in properties.iter_mut() {
+        if let Some(feature) = legacy_key_replacement(&format!("{prefix}{key}"))
+            && let Some(property) = property.as_object_mut()
+        {
+            property.insert("deprecated".to_string(), JsonValue::Bool(true));
+            property.insert(
+                "description".to_string(),
+                JsonValue::String(format!(
+                    "Deprecated: use `[features].{}` instead.",
+                    feature.key()
+                )),
+            );
+        }
+    }
+}
+
+fn render_path(path: &[String]) -> String {
+    let mut rendered = String::new();
+    for segment in path {
+        if segment.starts_with('[') {
+            rendered.push_str(segment);
+            continue;
+        }
+        if !rendered.is_empty() {
+            rendered.push('.');
+        }
+        let bare = !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if bare {
+            rendered.push_str(segment);
+        } else {
+            rendered.push_str(&format!("{segment:?}"));
+        }
+    }
+    rendered
+}
+
+fn type_matches(value: &TomlValue, expected: &JsonValue) -> bool {
+    let accepts = |name: &str| match (name, value) {
+        ("string", TomlValue::String(_) | TomlValue::Datetime(_))
+        | ("integer", TomlValue::Integer(_))
+        | ("number", TomlValue::Integer(_) | TomlValue::Float(_))
+        | ("boolean", TomlValue::Boolean(_))
+        | ("array", TomlValue::Array(_))
+        | ("object", TomlValue::Table(_)) => true,
+        _ => false,
+    };
+    match expected {
+        JsonValue::String(name) => accepts(name),
+        JsonValue::Array(names) => names.iter().filter_map(JsonValue::as_str).any(accepts),
+        _ => true,
+    }
+}
+
+fn type_name(value: &TomlValue) -> &'static str {
+    match value {
+        TomlValue::String(_) => "a string",
+        TomlValue::Integer(_) => "an integer",
+        TomlValue::Float(_) => "a float",
+        TomlValue::Boolean(_) => "a boolean",
+        TomlValue::Datetime(_) => "a datetime",
+        TomlValue::Array(_) => "an array",
+        TomlValue::Table(_) => "a table",
+    }
+}
+
+fn describe_types(expected: &JsonValue) -> String {
+    let describe = |name: &str| match name {
+        "string" => "a string",
+        "integer" => "an integer",
+        "number" => "a number",
+        "boolean" => "a boolean",
+        "array" => "an array",
+        "object" => "a table",
+        _ => "a value",
+    };
+    match expected {
+        JsonValue::String(name) => describe(name).to_string(),
+        JsonValue::Array(names) => names
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .map(describe)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        _ => "a value".to_string(),
+    }
+}
+
+fn describe_value(value: &TomlValue) -> String {
+    match value {
+        TomlValue::String(value) => format!("{value:?}"),
+        TomlValue::Table(_) => "a table".to_string(),
+        TomlValue::Array(_) => "an array".to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn describe_choices(choices: &[JsonValue]) -> String {
+    choices
+        .iter()
+        .map(|choice| match choice {
+            JsonValue::String(choice) => format!("`{choice}`"),
+            choice => format!("`{choice}`"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn toml_matches_json(value: &TomlValue, expected: &JsonValue) -> bool {
+    match (value, expected) {
+        (TomlValue::String(value), JsonValue::String(expected)) => value == expected,
+        (TomlValue::Boolean(value), JsonValue::Bool(expected)) => value == expected,
+        (TomlValue::Integer(value), JsonValue::Number(expected)) => {
+            expected.as_i64() == Some(*value)
+        }
+        _ => false,
+    }
+}
+
+/// Suggests the known key closest to a misspelled one, if any is close enough.
+fn closest_match<'k>(key: &str, known: Vec<&'k str>) -> Option<&'k str> {
+    let max_distance = (key.len() / 3).max(2);
+    known
+        .into_iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())]
+        .bytes()
+        .filter(|byte| *byte == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Diagnostics as strings, sorted so tests do not depend on table order.
+    fn messages(contents: &str) -> Vec<String> {
+        let mut messages: Vec<String> = validate_config_toml(contents)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn valid_config_has_no_diagnostics() {
+        let contents = r#"
+model = "gpt-5.1-codex"
+approval_policy = "on-request"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+network_access = true
+
+[tui]
+notifications = ["agent-turn-complete"]
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--stdio"]
+
+[profiles.fast]
+model_reasoning_effort = "low"
+
+[features]
+web_search_request = true
+"#;
+        assert_eq!(messages(contents), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        let contents = r#"
+modle = "o3"
+
+[tui]
+animation = false
+
+[features]
+web_serch_request = true
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "error: `features.web_serch_request`: unknown key `web_serch_request` in `[features]`; did you mean `web_search_request`?".to_string(),
+                "error: `modle`: unknown key `modle`; did you mean `model`?".to_string(),
+                "error: `tui.animation`: unknown key `animation` in `[tui]`; did you mean `animations`?".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_wrong_types_and_invalid_enum_values() {
+        let contents = r#"
+approval_policy = "sometimes"
+project_doc_max_bytes = "lots"
+
+[tui]
+notifications = 3
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "error: `approval_policy`: invalid value \"sometimes\"; expected one of `untrusted`, `on-failure`, `on-request`, `never`".to_string(),
+                "error: `project_doc_max_bytes`: expected an integer, found a string".to_string(),
+                "error: `tui.notifications`: expected a boolean or an array, found an integer".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_deprecated_keys_with_replacements() {
+        let contents = r#"
+experimental_use_rmcp_client = true
+
+[tools]
+view_image = true
+
+[profiles.ci]
+include_apply_patch_tool = true
+
+[ghost_snapshot]
+ignore_untracked_files_over_bytes = 1024
+
+[features]
+web_search = true
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "warning: `experimental_use_rmcp_client`: deprecated; use `features.rmcp_client` instead".to_string(),
+                "warning: `features.web_search`: deprecated; use `features.web_search_request` instead".to_string(),
+                "warning: `ghost_snapshot.ignore_untracked_files_over_bytes`: deprecated; use `ghost_snapshot.ignore_large_untracked_files` instead".to_string(),
+                "warning: `profiles.ci.include_apply_patch_tool`: deprecated; use `profiles.ci.features.apply_patch_freeform` instead".to_string(),
+                "warning: `tools.view_image`: deprecated; use `features.view_image_tool` instead".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_line_numbers() {
+        let diagnostics = validate_config_toml("model = \"o3\"\n\nmodel_provider = \n");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].is_error());
+        assert_eq!(diagnostics[0].line, Some(3));
+    }
+
+    #[test]
+    fn falls_back_to_deserializer_errors() {
+        let contents = r#"
+[mcp_servers.docs]
+command = "docs-server"
+url = "https://example.com/mcp"
+"#;
+        let diagnostics = validate_config_toml(contents);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            diagnostics[0]
+                .message
+                .contains("url is not supported for stdio"),
+            "{diagnostics:?}"
+        );
+    }
+
+    #[test]
+    fn schema_closes_objects_and_marks_deprecated_keys() {
+        let schema = config_json_schema();
+        assert_eq!(schema["additionalProperties"], JsonValue::Bool(false));
+        assert_eq!(
+            schema["properties"]["experimental_use_rmcp_client"]["deprecated"],
+            JsonValue::Bool(true)
+        );
+        assert_eq!(
+            schema["definitions"]["Tui"]["additionalProperties"],
+            JsonValue::Bool(false)
+        );
+    }
+}
@@ -2,9 +2,14 @@ use super::CONFIG_TOML_FILE;
 use super::ConfigToml;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::explain::ConfigExplanation;
+use crate::config::explain::explain_key;
+use crate::config::trust_store::TRUSTED_PROJECTS_FILE;
+use crate::config::trust_store::load_project_trust;
 use crate::config_loader::ConfigLayerEntry;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::apply_project_config_layer;
 use crate::config_loader::load_config_layers_state;
 use crate::config_loader::merge_toml_values;
 use crate::path_utils;
@@ -181,6 +186,59 @@ impl ConfigService {
             .await
     }
 
+    /// Reads the user's `config.toml` on its own, without the other layers.
+    /// A missing file reads as an empty table.
+    pub async fn read_user_config(&self) -> Result<TomlValue, ConfigServiceError> {
+        let layers = self
+            .load_layers_state()
+            .await
+            .map_err(|err| ConfigServiceError::io("failed to load configuration", err))?;
+        Ok(layers
+            .get_user_layer()
+            .map(|layer| layer.config.clone())
+            .unwrap_or_else(|| TomlValue::Table(toml::map::Map::new())))
+    }
+
+    /// Reads the dotted `key_path` from the user's `config.toml`.
+    pub async fn read_user_value(
+        &self,
+        key_path: &str,
+    ) -> Result<Option<TomlValue>, ConfigServiceError> {
+        let segments = parse_key_path(key_path).map_err(|message| {
+            ConfigServiceError::write(ConfigWriteErrorCode::ConfigValidationError, message)
+        })?;
+        let user_config = self.read_user_config().await?;
+        Ok(value_at_path(&user_config, &segments).cloned())
+    }
+
+    /// Explains where the effective value of the dotted `key_path` comes
+    /// from, including the project config and trust decision for `cwd`.
+    pub async fn explain(
+        &self,
+        key_path: &str,
+        cwd: &Path,
+        profile: Option<&str>,
+    ) -> Result<ConfigExplanation, ConfigServiceError> {
+        parse_key_path(key_path).map_err(|message| {
+            ConfigServiceError::write(ConfigWriteErrorCode::ConfigValidationError, message)
+        })?;
+        let layers = self
+            .load_layers_state()
+            .await
+            .map_err(|err| ConfigServiceError::io("failed to load configuration", err))?;
+        let layers = apply_project_config_layer(layers, &self.codex_home, cwd)
+            .await
+            .map_err(|err| ConfigServiceError::io("failed to load project configuration", err))?;
+        let trust = load_project_trust(&self.codex_home, cwd);
+        let trust_file = self.codex_home.join(TRUSTED_PROJECTS_FILE);
+        Ok(explain_key(
+            &layers,
+            profile,
+            trust.as_ref().map(|trust| (trust, trust_file)),
+            key_path,
+        ))
+    }
+
     pub async fn load_user_saved_config(
         &self,
     ) -> Result<codex_app_server_protocol::UserSavedConfig, ConfigServiceError> {
@@ -555,6 +613,9 @@ fn override_message(layer: &ConfigLayerSource) -> String {
         ConfigLayerSource::User { file } => {
             format!("Overridden by user config: {}", file.display())
         }
+        ConfigLayerSource::Project { file } => {
+            format!("Overridden by project config: {}", file.display())
+        }
         ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
             format!(
                 "Overridden by legacy managed_config.toml: {}",
@@ -744,6 +805,7 @@ remote_compaction = true
             vec![],
             LoaderOverrides {
                 managed_config_path: Some(managed_path.clone()),
+                system_config_path: None,
                 #[cfg(target_os = "macos")]
                 managed_preferences_base64: None,
             },
@@ -798,6 +860,7 @@ remote_compaction = true
             vec![],
             LoaderOverrides {
                 managed_config_path: Some(managed_path.clone()),
+                system_config_path: None,
                 #[cfg(target_os = "macos")]
                 managed_preferences_base64: None,
             },
@@ -900,6 +963,7 @@ remote_compaction = true
             vec![],
             LoaderOverrides {
                 managed_config_path: Some(managed_path.clone()),
+                system_config_path: None,
                 #[cfg(target_os = "macos")]
                 managed_preferences_base64: None,
             },
@@ -947,6 +1011,7 @@ remote_compaction = true
             cli_overrides,
             LoaderOverrides {
                 managed_config_path: Some(managed_path.clone()),
+                system_config_path: None,
                 #[cfg(target_os = "macos")]
                 managed_preferences_base64: None,
             },
@@ -992,6 +1057,7 @@ remote_compaction = true
             vec![],
             LoaderOverrides {
                 managed_config_path: Some(managed_path.clone()),
+                system_config_path: None,
                 #[cfg(target_os = "macos")]
                 managed_preferences_base64: None,
             },
@@ -0,0 +1,457 @@
+//! Per-project trust decisions, persisted in
+//! `$CODEX_HOME/trusted_projects.json`.
+//!
+//! A decision is keyed by the root of the git repository containing the
+//! working directory (or the directory itself outside a repository), so
+//! worktrees share the decision made for their main checkout. Besides the
+//! trust level it can pin the approval policy and sandbox mode used when
+//! `config.toml` sets neither, the default model, reasoning effort, and
+//! verbosity for the project, and whether the project's `.codex/` directory
+//! (`config.toml` and `steering/`) is honored.
+//!
+//! The store also keeps each project's command approval rules: commands the
+//! user chose to always allow there, which Codex approves without asking.
+//!
+//! Decisions recorded as `[projects."<path>"] trust_level = ...` in
+//! `config.toml` by older versions are still read when the store has no entry
+//! for a project.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::approvals::CommandApprovalRule;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::TrustLevel;
+use codex_protocol::config_types::Verbosity;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::ConfigToml;
+use crate::config::ProjectConfig;
+use crate::config_loader::find_project_config_file;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::protocol::AskForApproval;
+use crate::steering::loader::project_steering_root;
+
+pub const TRUSTED_PROJECTS_FILE: &str = "trusted_projects.json";
+
+/// What the user decided about one project.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProjectTrust {
+    pub trust_level: TrustLevel,
+    /// Approval policy used when neither `config.toml` nor the active
+    /// profile sets one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<AskForApproval>,
+    /// Sandbox mode used when neither `config.toml` nor the active profile
+    /// sets one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<SandboxMode>,
+    /// Model used in this project unless the active profile or `--model`
+    /// picks one. Takes precedence over `config.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Reasoning effort used in this project unless the active profile sets
+    /// one. Takes precedence over `config.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// Verbosity used in this project unless the active profile sets one.
+    /// Takes precedence over `config.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_verbosity: Option<Verbosity>,
+    /// Whether the project's `.codex/config.toml` and `.codex/steering` are
+    /// loaded.
+    pub load_project_files: bool,
+}
+
+impl ProjectTrust {
+    /// A decision with no pinned policies. Project files load only for
+    /// trusted projects.
+    pub fn new(trust_level: TrustLevel) -> Self {
+        Self {
+            trust_level,
+            approval_policy: None,
+            sandbox_mode: None,
+            model: None,
+            model_reasoning_effort: None,
+            model_verbosity: None,
+            load_project_files: trust_level == TrustLevel::Trusted,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TrustStore {
+    #[serde(default)]
+    pub projects: BTreeMap<PathBuf, ProjectTrust>,
+    /// Commands approved for good in a project, keyed like `projects`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub approval_rules: BTreeMap<PathBuf, Vec<CommandApprovalRule>>,
+}
+
+impl TrustStore {
+    /// Reads the store from `codex_home`. A missing file is an empty store.
+    pub fn load(codex_home: &Path) -> io::Result<Self> {
+        let path = codex_home.join(TRUSTED_PROJECTS_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str(&contents).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("failed to parse {}: {err}", path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, codex_home: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(codex_home)?;
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let path = codex_home.join(TRUSTED_PROJECTS_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, format!("{contents}\n"))?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// The decision covering `cwd`: an entry for `cwd` itself, else one for
+    /// the root of its git repository.
+    pub fn get(&self, cwd: &Path) -> Option<&ProjectTrust> {
+        self.projects.get(cwd).or_else(|| {
+            resolve_root_git_project_for_trust(cwd).and_then(|root| self.projects.get(&root))
+        })
+    }
+
+    pub fn set(&mut self, project: PathBuf, trust: ProjectTrust) {
+        self.projects.insert(project, trust);
+    }
+
+    pub fn remove(&mut self, project: &Path) -> Option<ProjectTrust> {
+        self.projects.remove(project)
+    }
+
+    /// The approval rules covering `cwd`: those recorded for `cwd` itself,
+    /// else those for the root of its git repository.
+    pub fn approval_rules(&self, cwd: &Path) -> &[CommandApprovalRule] {
+        self.approval_rules
+            .get(cwd)
+            .or_else(|| {
+                resolve_root_git_project_for_trust(cwd)
+                    .and_then(|root| self.approval_rules.get(&root))
+            })
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Adds `rule` for `project` unless it is already there. Returns whether
+    /// it was added.
+    pub fn add_approval_rule(&mut self, project: PathBuf, rule: CommandApprovalRule) -> bool {
+        let rules = self.approval_rules.entry(project).or_default();
+        if rules.contains(&rule) {
+            return false;
+        }
+        rules.push(rule);
+        true
+    }
+
+    /// Removes the rule at `index` (as listed, from 0) for `project`.
+    pub fn remove_approval_rule(
+        &mut self,
+        project: &Path,
+        index: usize,
+    ) -> Option<CommandApprovalRule> {
+        let rules = self.approval_rules.get_mut(project)?;
+        if index >= rules.len() {
+            return None;
+        }
+        let rule = rules.remove(index);
+        if rules.is_empty() {
+            self.approval_rules.remove(project);
+        }
+        Some(rule)
+    }
+}
+
+/// The directory a decision made in `cwd` is recorded under.
+pub fn trust_key(cwd: &Path) -> PathBuf {
+    resolve_root_git_project_for_trust(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// Records `trust` for the project containing `cwd`, replacing any earlier
+/// decision.
+pub fn record_project_trust(codex_home: &Path, cwd: &Path, trust: ProjectTrust) -> io::Result<()> {
+    let mut store = TrustStore::load(codex_home)?;
+    store.set(trust_key(cwd), trust);
+    store.save(codex_home)
+}
+
+/// The stored decision for `cwd`. An unreadable store is reported and
+/// treated as empty, so the user is asked again rather than trusted silently.
+pub fn load_project_trust(codex_home: &Path, cwd: &Path) -> Option<ProjectTrust> {
+    match TrustStore::load(codex_home) {
+        Ok(store) => store.get(cwd).cloned(),
+        Err(err) => {
+            warn!("ignoring {TRUSTED_PROJECTS_FILE}: {err}");
+            None
+        }
+    }
+}
+
+/// Saves `rule` for the project containing `cwd`.
+pub fn record_approval_rule(
+    codex_home: &Path,
+    cwd: &Path,
+    rule: CommandApprovalRule,
+) -> io::Result<()> {
+    let mut store = TrustStore::load(codex_home)?;
+    if store.add_approval_rule(trust_key(cwd), rule) {
+        store.save(codex_home)?;
+    }
+    Ok(())
+}
+
+/// The approval rules for `cwd`. An unreadable store is reported and treated
+/// as empty, so commands are asked about as usual.
+pub fn load_approval_rules(codex_home: &Path, cwd: &Path) -> Vec<CommandApprovalRule> {
+    match TrustStore::load(codex_home) {
+        Ok(store) => store.approval_rules(cwd).to_vec(),
+        Err(err) => {
+            warn!("ignoring {TRUSTED_PROJECTS_FILE}: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Whether `rules` approve `command`: some rule matches it as a whole, or it
+/// is a plain `bash -lc` script and each of its commands matches a rule.
+pub fn approval_rules_allow(rules: &[CommandApprovalRule], command: &[String]) -> bool {
+    if rules.iter().any(|rule| rule.matches(command)) {
+        return true;
+    }
+    parse_shell_lc_plain_commands(command).is_some_and(|commands| {
+        !commands.is_empty()
+            && commands
+                .iter()
+                .all(|command| rules.iter().any(|rule| rule.matches(command)))
+    })
+}
+
+/// The rule offered when the user approves `command` for the project: a
+/// prefix rule for a single command, so later runs with more arguments are
+/// covered, or the exact command line for anything more complex.
+pub fn suggested_approval_rule(command: &[String]) -> CommandApprovalRule {
+    match parse_shell_lc_plain_commands(command).as_deref() {
+        Some([single]) => CommandApprovalRule::Prefix {
+            command: single.clone(),
+        },
+        None if extract_bash_command(command).is_none() => CommandApprovalRule::Prefix {
+            command: command.to_vec(),
+        },
+        _ => CommandApprovalRule::Exact {
+            command: command.to_vec(),
+        },
+    }
+}
+
+/// Whether `.codex/` content for `cwd` may be loaded. It takes a recorded
+/// decision: a stored one that allows it, or a legacy `config.toml` entry that
+/// trusts the project. Projects without either are not loaded.
+pub fn project_files_allowed(codex_home: &Path, cwd: &Path) -> bool {
+    match load_project_trust(codex_home, cwd) {
+        Some(trust) => trust.load_project_files,
+        None => legacy_trust_level(codex_home, cwd) == Some(TrustLevel::Trusted),
+    }
+}
+
+/// Trust level recorded for `cwd` (or its repository root) under
+/// `[projects."<path>"]` in `config.toml` by older versions.
+fn legacy_trust_level(codex_home: &Path, cwd: &Path) -> Option<TrustLevel> {
+    #[derive(Deserialize)]
+    struct LegacyConfig {
+        projects: Option<HashMap<String, ProjectConfig>>,
+    }
+
+    let contents = std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).ok()?;
+    let legacy: LegacyConfig = toml::from_str(&contents).ok()?;
+    ConfigToml {
+        projects: legacy.projects,
+        ..Default::default()
+    }
+    .get_active_project(cwd)?
+    .trust_level
+}
+
+/// Whether `cwd` has a `.codex/config.toml` or `.codex/steering` that a trust
+/// decision governs.
+pub fn has_project_files(codex_home: &Path, cwd: &Path) -> bool {
+    find_project_config_file(cwd, codex_home).is_some() || project_steering_root(cwd).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trips_decisions() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let project = tempfile::tempdir().expect("tempdir");
+        let trust = ProjectTrust {
+            approval_policy: Some(AskForApproval::OnRequest),
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            ..ProjectTrust::new(TrustLevel::Trusted)
+        };
+
+        record_project_trust(codex_home.path(), project.path(), trust.clone())
+            .expect("record trust");
+
+        assert_eq!(
+            load_project_trust(codex_home.path(), project.path()),
+            Some(trust)
+        );
+        assert_eq!(
+            load_project_trust(codex_home.path(), codex_home.path()),
+            None
+        );
+        let contents = std::fs::read_to_string(codex_home.path().join(TRUSTED_PROJECTS_FILE))
+            .expect("read store");
+        assert!(
+            contents.contains("\"approval_policy\": \"on-request\""),
+            "{contents}"
+        );
+    }
+
+    #[test]
+    fn approval_rules_round_trip_and_cover_each_script_command() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let project = tempfile::tempdir().expect("tempdir");
+        let cargo_test = CommandApprovalRule::Prefix {
+            command: vec!["cargo".to_string(), "test".to_string()],
+        };
+        record_approval_rule(codex_home.path(), project.path(), cargo_test.clone())
+            .expect("record rule");
+        record_approval_rule(codex_home.path(), project.path(), cargo_test.clone())
+            .expect("record rule again");
+
+        let rules = load_approval_rules(codex_home.path(), project.path());
+        assert_eq!(rules, vec![cargo_test]);
+        assert_eq!(
+            load_approval_rules(codex_home.path(), codex_home.path()),
+            Vec::new()
+        );
+        // Recording rules does not make a trust decision.
+        assert_eq!(load_project_trust(codex_home.path(), project.path()), None);
+
+        let bash = |script: &str| vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+        assert!(approval_rules_allow(&rules, &bash("cargo test -p core")));
+        assert!(approval_rules_allow(
+            &rules,
+            &["cargo".to_string(), "test".to_string()]
+        ));
+        assert!(!approval_rules_allow(
+            &rules,
+            &bash("cargo test && curl evil.example")
+        ));
+        assert!(!approval_rules_allow(
+            &rules,
+            &bash("cargo test > $(whoami)")
+        ));
+    }
+
+    #[test]
+    fn suggests_a_prefix_rule_for_single_commands() {
+        let bash = |script: &str| vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+        assert_eq!(
+            suggested_approval_rule(&bash("cargo test -p core")),
+            CommandApprovalRule::Prefix {
+                command: vec!["cargo", "test", "-p", "core"]
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            }
+        );
+        assert_eq!(
+            suggested_approval_rule(&bash("make lint | tee out")),
+            CommandApprovalRule::Exact {
+                command: bash("make lint | tee out"),
+            }
+        );
+        let script_rule = [suggested_approval_rule(&bash("make lint | tee out"))];
+        assert!(approval_rules_allow(
+            &script_rule,
+            &bash("make lint | tee out")
+        ));
+        assert!(!approval_rules_allow(&script_rule, &bash("make lint")));
+    }
+
+    #[test]
+    fn removing_the_last_rule_drops_the_project() {
+        let mut store = TrustStore::default();
+        let project = PathBuf::from("/work/project");
+        let rule = CommandApprovalRule::Exact {
+            command: vec!["make".to_string()],
+        };
+        assert!(store.add_approval_rule(project.clone(), rule.clone()));
+
+        assert_eq!(store.remove_approval_rule(&project, 1), None);
+        assert_eq!(store.remove_approval_rule(&project, 0), Some(rule));
+        assert!(store.approval_rules.is_empty());
+    }
+
+    #[test]
+    fn project_files_need_a_trust_decision() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let project = tempfile::tempdir().expect("tempdir");
+        assert!(!project_files_allowed(codex_home.path(), project.path()));
+
+        record_project_trust(
+            codex_home.path(),
+            project.path(),
+            ProjectTrust::new(TrustLevel::Trusted),
+        )
+        .expect("record trust");
+        assert!(project_files_allowed(codex_home.path(), project.path()));
+
+        record_project_trust(
+            codex_home.path(),
+            project.path(),
+            ProjectTrust::new(TrustLevel::Untrusted),
+        )
+        .expect("record trust");
+        assert!(!project_files_allowed(codex_home.path(), project.path()));
+
+        std::fs::write(codex_home.path().join(TRUSTED_PROJECTS_FILE), "not json")
+            .expect("write store");
+        assert_eq!(load_project_trust(codex_home.path(), project.path()), None);
+        assert!(!project_files_allowed(codex_home.path(), project.path()));
+    }
+
+    #[test]
+    fn legacy_config_entries_decide_project_files() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let project = tempfile::tempdir().expect("tempdir");
+        let write_legacy = |level: &str| {
+            let contents = format!(
+                "[projects.{:?}]\ntrust_level = \"{level}\"\n",
+                project.path().to_string_lossy()
+            );
+            std::fs::write(codex_home.path().join(CONFIG_TOML_FILE), contents)
+                .expect("write config");
+        };
+
+        write_legacy("untrusted");
+        assert!(!project_files_allowed(codex_home.path(), project.path()));
+
+        write_legacy("trusted");
+        assert!(project_files_allowed(codex_home.path(), project.path()));
+    }
+}
@@ -0,0 +1,252 @@
+//! XDG base directory mode.
+//!
+//! By default everything Codex keeps lives under one `$CODEX_HOME`
+//! (`~/.codex`). In XDG mode that directory is split in three:
+//!
+//! - `$XDG_CONFIG_HOME/codex` (`~/.config/codex`) becomes the Codex home and
+//!   holds what the user edits or would back up: `config.toml`, credentials,
+//!   trust decisions, rules, steering, skills, and prompts.
+//! - `$XDG_STATE_HOME/codex` (`~/.local/state/codex`) holds sessions, the
+//!   message history, logs, and session artifacts.
+//! - `$XDG_CACHE_HOME/codex` (`~/.cache/codex`) holds data Codex can rebuild:
+//!   the model list cache, the update check, and shell snapshots.
+//!
+//! The mode is on when `CODEX_USE_XDG=1`, or when
+//! `$XDG_CONFIG_HOME/codex/config.toml` sets `use_xdg_dirs = true`.
+//! `CODEX_USE_XDG=0` turns it off, and setting `CODEX_HOME` always wins.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use dirs::home_dir;
+
+use super::CONFIG_TOML_FILE;
+use crate::artifacts::ARTIFACTS_SUBDIR;
+use crate::message_history::HISTORY_FILENAME;
+use crate::rollout::ARCHIVED_SESSIONS_SUBDIR;
+use crate::rollout::SESSIONS_SUBDIR;
+
+pub const CODEX_USE_XDG_ENV_VAR: &str = "CODEX_USE_XDG";
+
+/// Config key that turns XDG mode on from `$XDG_CONFIG_HOME/codex/config.toml`.
+pub const USE_XDG_DIRS_KEY: &str = "use_xdg_dirs";
+
+const XDG_SUBDIR: &str = "codex";
+
+/// Entries of a legacy Codex home that belong in the state directory.
+const STATE_ENTRIES: &[&str] = &[
+    SESSIONS_SUBDIR,
+    ARCHIVED_SESSIONS_SUBDIR,
+    HISTORY_FILENAME,
+    "log",
+    ARTIFACTS_SUBDIR,
+];
+
+/// Entries of a legacy Codex home that belong in the cache directory.
+const CACHE_ENTRIES: &[&str] = &["models_cache.json", "version.json", "shell_snapshots"];
+
+/// The three directories Codex uses in XDG mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XdgDirs {
+    pub config: PathBuf,
+    pub state: PathBuf,
+    pub cache: PathBuf,
+}
+
+impl XdgDirs {
+    /// The directories from the `XDG_*_HOME` variables, falling back to the
+    /// defaults of the XDG spec when a variable is unset or not absolute.
+    pub fn from_env() -> Option<Self> {
+        let home = home_dir()?;
+        let base = |var: &str, default: &[&str]| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .unwrap_or_else(|| {
+                    default
+                        .iter()
+                        .fold(home.clone(), |path, dir| path.join(dir))
+                })
+                .join(XDG_SUBDIR)
+        };
+        Some(Self {
+            config: base("XDG_CONFIG_HOME", &[".config"]),
+            state: base("XDG_STATE_HOME", &[".local", "state"]),
+            cache: base("XDG_CACHE_HOME", &[".cache"]),
+        })
+    }
+}
+
+static ACTIVE_XDG_DIRS: LazyLock<Option<XdgDirs>> = LazyLock::new(|| {
+    if std::env::var_os("CODEX_HOME").is_some_and(|home| !home.is_empty()) {
+        return None;
+    }
+    let dirs = XdgDirs::from_env()?;
+    let enabled = match std::env::var(CODEX_USE_XDG_ENV_VAR) {
+        Ok(value) if !value.is_empty() => matches!(value.as_str(), "1" | "true"),
+        _ => enabled_in_config(&dirs.config),
+    };
+    enabled.then_some(dirs)
+});
+
+/// The XDG directories when XDG mode is on.
+pub fn active_xdg_dirs() -> Option<&'static XdgDirs> {
+    ACTIVE_XDG_DIRS.as_ref()
+}
+
+/// The single-directory Codex home that [`plan_xdg_migration`] moves from:
+/// `$CODEX_HOME` when set, else `~/.codex`.
+pub fn legacy_codex_home() -> Option<PathBuf> {
+    match std::env::var_os("CODEX_HOME") {
+        Some(home) if !home.is_empty() => Some(PathBuf::from(home)),
+        _ => Some(home_dir()?.join(".codex")),
+    }
+}
+
+/// Where sessions, history, logs, and artifacts go for `codex_home`.
+pub fn codex_state_home(codex_home: &Path) -> PathBuf {
+    match active_xdg_dirs() {
+        Some(dirs) if dirs.config == codex_home => dirs.state.clone(),
+        _ => codex_home.to_path_buf(),
+    }
+}
+
+/// Where rebuildable caches go for `codex_home`.
+pub fn codex_cache_home(codex_home: &Path) -> PathBuf {
+    match active_xdg_dirs() {
+        Some(dirs) if dirs.config == codex_home => dirs.cache.clone(),
+        _ => codex_home.to_path_buf(),
+    }
+}
+
+fn enabled_in_config(config_dir: &Path) -> bool {
+    let Ok(contents) = std::fs::read_to_string(config_dir.join(CONFIG_TOML_FILE)) else {
+        return false;
+    };
+    toml::from_str::<toml::Table>(&contents)
+        .ok()
+        .and_then(|table| table.get(USE_XDG_DIRS_KEY)?.as_bool())
+        .unwrap_or(false)
+}
+
+/// One entry of a legacy Codex home and where the migration puts it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XdgMove {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// The destination already exists, so the entry is left in place.
+    pub conflict: bool,
+}
+
+/// Plans moving every entry of `legacy_home` into `dirs`. Nothing is moved.
+pub fn plan_xdg_migration(legacy_home: &Path, dirs: &XdgDirs) -> io::Result<Vec<XdgMove>> {
+    let mut moves = Vec::new();
+    let entries = match std::fs::read_dir(legacy_home) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(moves),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let from = entry?.path();
+        let Some(name) = from.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let target = if STATE_ENTRIES.contains(&name) {
+            &dirs.state
+        } else if CACHE_ENTRIES.contains(&name) {
+            &dirs.cache
+        } else {
+            &dirs.config
+        };
+        let to = target.join(name);
+        let conflict = to.symlink_metadata().is_ok();
+        moves.push(XdgMove { from, to, conflict });
+    }
+    moves.sort_by(|a, b| a.from.cmp(&b.from));
+    Ok(moves)
+}
+
+/// Moves every non-conflicting entry of `moves`. Entries on another file
+/// system are copied, then removed.
+pub fn apply_xdg_migration(moves: &[XdgMove]) -> io::Result<()> {
+    for XdgMove { from, to, conflict } in moves {
+        if *conflict {
+            continue;
+        }
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if std::fs::rename(from, to).is_err() {
+            copy_recursively(from, to)?;
+            if from.is_dir() {
+                std::fs::remove_dir_all(from)?;
+            } else {
+                std::fs::remove_file(from)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.is_dir() {
+        std::fs::copy(from, to)?;
+        return Ok(());
+    }
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn migration_splits_the_legacy_home() {
+        let legacy = tempfile::tempdir().expect("tempdir");
+        let xdg = tempfile::tempdir().expect("tempdir");
+        let dirs = XdgDirs {
+            config: xdg.path().join("config/codex"),
+            state: xdg.path().join("state/codex"),
+            cache: xdg.path().join("cache/codex"),
+        };
+        std::fs::write(legacy.path().join("config.toml"), "model = \"o3\"\n").expect("write");
+        std::fs::write(legacy.path().join("history.jsonl"), "{}\n").expect("write");
+        std::fs::create_dir_all(legacy.path().join("sessions/2025/01/01")).expect("mkdir");
+        std::fs::write(legacy.path().join("models_cache.json"), "{}").expect("write");
+        std::fs::create_dir_all(&dirs.config).expect("mkdir");
+        std::fs::write(dirs.config.join("config.toml"), "").expect("write");
+
+        let moves = plan_xdg_migration(legacy.path(), &dirs).expect("plan");
+        assert_eq!(
+            moves
+                .iter()
+                .map(|m| (m.to.clone(), m.conflict))
+                .collect::<Vec<_>>(),
+            vec![
+                (dirs.config.join("config.toml"), true),
+                (dirs.state.join("history.jsonl"), false),
+                (dirs.cache.join("models_cache.json"), false),
+                (dirs.state.join("sessions"), false),
+            ]
+        );
+
+        apply_xdg_migration(&moves).expect("migrate");
+        assert!(dirs.state.join("sessions/2025/01/01").is_dir());
+        assert!(dirs.cache.join("models_cache.json").is_file());
+        assert!(!legacy.path().join("history.jsonl").exists());
+        // Conflicting entries stay where they were.
+        assert!(legacy.path().join("config.toml").is_file());
+        assert_eq!(
+            std::fs::read_to_string(dirs.config.join("config.toml")).expect("read"),
+            ""
+        );
+    }
+}
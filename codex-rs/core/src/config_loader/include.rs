@@ -0,0 +1,227 @@
+//! The top-level `include` key of the user config.
+//!
+//! ```toml
+//! include = ["providers.toml", "~/.config/codex/work.toml"]
+//! ```
+//!
+//! Included files are merged in order, later files overriding earlier ones,
+//! and the including file is merged over all of them. Relative paths resolve
+//! against the directory of the file that names them, and included files may
+//! include further files.
+
+use super::merge::merge_toml_values;
+use dirs::home_dir;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+
+pub(crate) const INCLUDE_KEY: &str = "include";
+
+/// Guards against runaway chains that are not literal cycles, such as a file
+/// that includes a freshly generated path.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Replaces the `include` key of `config`, read from `file`, with the merged
+/// contents of the files it names.
+pub(super) async fn resolve_includes(config: TomlValue, file: &Path) -> io::Result<TomlValue> {
+    let mut stack = vec![file.to_path_buf()];
+    resolve_with_stack(config, file, &mut stack).await
+}
+
+async fn resolve_with_stack(
+    mut config: TomlValue,
+    file: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> io::Result<TomlValue> {
+    let includes = match config.as_table_mut().and_then(|t| t.remove(INCLUDE_KEY)) {
+        Some(value) => parse_include_list(value, file)?,
+        None => return Ok(config),
+    };
+
+    let base_dir = file.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = TomlValue::Table(toml::map::Map::new());
+    for include in includes {
+        let path = resolve_include_path(&include, base_dir)?;
+        if stack.contains(&path) {
+            let chain = stack
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(invalid_data(format!("config include cycle: {chain}")));
+        }
+        if stack.len() > MAX_INCLUDE_DEPTH {
+            return Err(invalid_data(format!(
+                "config includes are nested more than {MAX_INCLUDE_DEPTH} levels deep at {}",
+                path.display()
+            )));
+        }
+
+        let contents = tokio::fs::read_to_string(&path).await.map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "Failed to read config file {} included from {}: {e}",
+                    path.display(),
+                    file.display()
+                ),
+            )
+        })?;
+        let included: TomlValue = toml::from_str(&contents).map_err(|e| {
+            invalid_data(format!(
+                "Error parsing config file {} included from {}: {e}",
+                path.display(),
+                file.display()
+            ))
+        })?;
+
+        stack.push(path.clone());
+        let included = Box::pin(resolve_with_stack(included, &path, stack)).await?;
+        stack.pop();
+        merge_toml_values(&mut merged, &included);
+    }
+
+    merge_toml_values(&mut merged, &config);
+    Ok(merged)
+}
+
+fn parse_include_list(value: TomlValue, file: &Path) -> io::Result<Vec<String>> {
+    let not_a_list = || {
+        invalid_data(format!(
+            "`{INCLUDE_KEY}` in {} must be a list of file paths",
+            file.display()
+        ))
+    };
+    let TomlValue::Array(entries) = value else {
+        return Err(not_a_list());
+    };
+    entries
+        .into_iter()
+        .map(|entry| match entry {
+            TomlValue::String(path) => Ok(path),
+            _ => Err(not_a_list()),
+        })
+        .collect()
+}
+
+fn resolve_include_path(include: &str, base_dir: &Path) -> io::Result<PathBuf> {
+    let path = match include.strip_prefix("~/") {
+        Some(rest) => home_dir()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("cannot resolve `{include}`: could not find home directory"),
+                )
+            })?
+            .join(rest),
+        None => base_dir.join(include),
+    };
+    Ok(dunce::canonicalize(&path).unwrap_or(path))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    #[tokio::test]
+    async fn merges_includes_in_order_under_the_including_file() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("conf.d")).expect("mkdir");
+        std::fs::write(
+            dir.path().join("conf.d/providers.toml"),
+            r#"
+include = ["mcp.toml"]
+model = "from-providers"
+
+[model_providers.corp]
+name = "Corp"
+base_url = "https://corp.example/v1"
+"#,
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("conf.d/mcp.toml"),
+            "[mcp_servers.docs]\ncommand = \"docs-mcp\"\n",
+        )
+        .expect("write");
+        std::fs::write(
+            dir.path().join("work.toml"),
+            "model = \"from-work\"\n\n[model_providers.corp]\nbase_url = \"https://proxy.example/v1\"\n",
+        )
+        .expect("write");
+        let main = dir.path().join("config.toml");
+
+        let resolved = resolve_includes(
+            toml(
+                r#"
+include = ["conf.d/providers.toml", "work.toml"]
+approval_policy = "never"
+"#,
+            ),
+            &main,
+        )
+        .await
+        .expect("resolves");
+
+        assert_eq!(
+            resolved,
+            toml(
+                r#"
+model = "from-work"
+approval_policy = "never"
+
+[model_providers.corp]
+name = "Corp"
+base_url = "https://proxy.example/v1"
+
+[mcp_servers.docs]
+command = "docs-mcp"
+"#
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_cycles_and_missing_files() {
+        let dir = tempdir().expect("tempdir");
+        let dir_path = dunce::canonicalize(dir.path()).expect("canonical");
+        std::fs::write(dir_path.join("a.toml"), "include = [\"b.toml\"]\n").expect("write");
+        std::fs::write(dir_path.join("b.toml"), "include = [\"a.toml\"]\n").expect("write");
+        let main = dir_path.join("config.toml");
+
+        let err = resolve_includes(toml("include = [\"a.toml\"]"), &main)
+            .await
+            .expect_err("cycle");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "config include cycle: {main} -> {a} -> {b} -> {a}",
+                main = main.display(),
+                a = dir_path.join("a.toml").display(),
+                b = dir_path.join("b.toml").display(),
+            )
+        );
+
+        let err = resolve_includes(toml("include = [\"missing.toml\"]"), &main)
+            .await
+            .expect_err("missing");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        let err = resolve_includes(toml("include = \"a.toml\""), &main)
+            .await
+            .expect_err("not a list");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -33,11 +33,13 @@ pub(super) async fn load_config_layers_internal(
     let LoaderOverrides {
         managed_config_path,
         managed_preferences_base64,
+        system_config_path: _,
     } = overrides;
 
     #[cfg(not(target_os = "macos"))]
     let LoaderOverrides {
         managed_config_path,
+        system_config_path: _,
     } = overrides;
 
     let managed_config_path = AbsolutePathBuf::from_absolute_path(
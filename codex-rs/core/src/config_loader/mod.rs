@@ -1,11 +1,15 @@
 mod config_requirements;
 mod fingerprint;
+mod include;
 mod layer_io;
 #[cfg(target_os = "macos")]
 mod macos;
 mod merge;
 mod overrides;
+mod project;
 mod state;
+mod system;
+mod toml_path;
 
 #[cfg(test)]
 mod tests;
@@ -23,9 +27,14 @@ use toml::Value as TomlValue;
 
 pub use config_requirements::ConfigRequirements;
 pub use merge::merge_toml_values;
+pub(crate) use project::apply_project_config_layer;
+pub(crate) use project::find_project_config_file;
 pub use state::ConfigLayerEntry;
 pub use state::ConfigLayerStack;
 pub use state::LoaderOverrides;
+pub use system::LockedKeyViolation;
+pub(crate) use system::clamp_sandbox_mode;
+pub(crate) use toml_path::toml_value_at;
 
 /// On Unix systems, load requirements from this file path, if present.
 const DEFAULT_REQUIREMENTS_TOML_FILE_UNIX: &str = "/etc/codex/requirements.toml";
@@ -44,11 +53,12 @@ const DEFAULT_REQUIREMENTS_TOML_FILE_UNIX: &str = "/etc/codex/requirements.toml"
 /// Configuration is built up from multiple layers in the following order:
 ///
 /// - admin:    managed preferences (*)
-/// - system    `/etc/codex/config.toml`
-/// - user      `${CODEX_HOME}/config.toml`
-/// - cwd       `${PWD}/config.toml`
-/// - tree      parent directories up to root looking for `./.codex/config.toml`
-/// - repo      `$(git rev-parse --show-toplevel)/.codex/config.toml`
+/// - system    `/etc/codex/config.toml` (`%ProgramData%\Codex\config.toml` on
+///   Windows); its `enforced_keys` cannot be weakened by the layers above
+/// - user      `${CODEX_HOME}/config.toml`, with the files named by its
+///   `include` key merged beneath it
+/// - project   nearest `./.codex/config.toml` from `cwd` up to the repo root
+///   (added by [apply_project_config_layer]; safe keys only)
 /// - runtime   e.g., --config flags, model selector in UI
 ///
 /// (*) Only available on macOS via managed device profiles.
@@ -75,6 +85,7 @@ pub async fn load_config_layers_state(
 
     // Make a best-effort to support the legacy `managed_config.toml` as a
     // requirements specification.
+    let system_config_path = overrides.system_config_path.clone();
     let loaded_config_layers = layer_io::load_config_layers_internal(codex_home, overrides).await?;
     load_requirements_from_legacy_scheme(
         &mut config_requirements_toml,
@@ -85,7 +96,20 @@ pub async fn load_config_layers_state(
     let mut layers = Vec::<ConfigLayerEntry>::new();
 
     // TODO(mbolin): Honor managed preferences (macOS only).
-    // TODO(mbolin): Honor /etc/codex/config.toml.
+
+    // Add a layer for the system config, e.g. /etc/codex/config.toml, if it
+    // exists.
+    if let Some(system_file) = system_config_path.or_else(system::system_config_default_path) {
+        let system_file = AbsolutePathBuf::from_absolute_path(&system_file)?;
+        if let Some(system_config) =
+            layer_io::read_config_from_path(system_file.as_path(), false).await?
+        {
+            layers.push(ConfigLayerEntry::new(
+                ConfigLayerSource::System { file: system_file },
+                system_config,
+            ));
+        }
+    }
 
     // Add a layer for $CODEX_HOME/config.toml if it exists. Note if the file
     // exists, but is malformed, then this error should be propagated to the
@@ -102,6 +126,7 @@ pub async fn load_config_layers_state(
                     ),
                 )
             })?;
+            let user_config = include::resolve_includes(user_config, user_file.as_path()).await?;
             layers.push(ConfigLayerEntry::new(
                 ConfigLayerSource::User { file: user_file },
                 user_config,
@@ -120,14 +145,19 @@ pub async fn load_config_layers_state(
         }
     }
 
-    // TODO(mbolin): Add layers for cwd, tree, and repo config files.
-
     // Add a layer for runtime overrides from the CLI or UI, if any exist.
+    // Array indices in the overrides refer to the config files merged so far.
+    let mut cli_removals = Vec::new();
     if !cli_overrides.is_empty() {
-        let cli_overrides_layer = overrides::build_cli_overrides_layer(cli_overrides);
+        let mut lower = overrides::default_empty_table();
+        for layer in &layers {
+            merge_toml_values(&mut lower, &layer.config);
+        }
+        let cli_overrides_layer = overrides::build_cli_overrides_layer(cli_overrides, &lower)?;
+        cli_removals = cli_overrides_layer.removals;
         layers.push(ConfigLayerEntry::new(
             ConfigLayerSource::SessionFlags,
-            cli_overrides_layer,
+            cli_overrides_layer.config,
         ));
     }
 
@@ -155,7 +185,10 @@ pub async fn load_config_layers_state(
         ));
     }
 
-    ConfigLayerStack::new(layers, config_requirements_toml.try_into()?)
+    Ok(
+        ConfigLayerStack::new(layers, config_requirements_toml.try_into()?)?
+            .with_cli_removals(cli_removals),
+    )
 }
 
 /// If available, apply requirements from `/etc/codex/requirements.toml` to
@@ -1,55 +1,417 @@
+//! The session-flags layer built from `-c`/`--config` overrides.
+//!
+//! Each override is a dotted path and a TOML value. Besides plain keys, a path
+//! may index into arrays (`mcp_servers.docs.args[2]=--verbose`), and a path
+//! prefixed with `!` removes the key instead of setting it (`-c !notify`).
+//! Overrides apply in order, so a later one can refill a table an earlier one
+//! removed.
+//!
+//! Plain keys are written into the layer and merged over the config files
+//! like before. An indexed path copies the whole array from the files into the
+//! layer and edits the copy. Removed keys cannot be expressed as a layer, so
+//! they are returned separately and deleted from the lower layers when the
+//! stack is merged.
+
+use std::io;
+
 use toml::Value as TomlValue;
+use toml::value::Table;
+
+use crate::config::schema::validate_config_overrides;
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// The session-flags layer and the keys removed before merging it.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct CliOverridesLayer {
+    pub config: TomlValue,
+    pub removals: Vec<Vec<String>>,
+}
 
 pub(super) fn default_empty_table() -> TomlValue {
     TomlValue::Table(Default::default())
 }
 
-pub(super) fn build_cli_overrides_layer(cli_overrides: &[(String, TomlValue)]) -> TomlValue {
-    let mut root = default_empty_table();
-    for (path, value) in cli_overrides {
-        apply_toml_override(&mut root, path, value.clone());
+/// Builds the session-flags layer from `cli_overrides`. `lower` is the merged
+/// config files, which indexed paths resolve against. Malformed paths, out of
+/// range indices, and values of the wrong type for the config schema are
+/// errors.
+pub(super) fn build_cli_overrides_layer(
+    cli_overrides: &[(String, TomlValue)],
+    lower: &TomlValue,
+) -> io::Result<CliOverridesLayer> {
+    let mut layer = CliOverridesLayer {
+        config: default_empty_table(),
+        removals: Vec::new(),
+    };
+    for (raw_path, value) in cli_overrides {
+        apply_cli_override(&mut layer, lower, raw_path, value.clone())
+            .map_err(|err| invalid_override(raw_path, &err))?;
+    }
+
+    for diagnostic in validate_config_overrides(&layer.config) {
+        if diagnostic.is_error() {
+            return Err(invalid_override(&diagnostic.key_path, &diagnostic.message));
+        }
+        tracing::warn!("-c {}: {}", diagnostic.key_path, diagnostic.message);
+    }
+    Ok(layer)
+}
+
+/// Deletes the key at `path` from `root`, if present.
+pub(super) fn remove_path(root: &mut TomlValue, path: &[String]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let parent = parents
+        .iter()
+        .try_fold(root, |value, key| value.get_mut(key.as_str()));
+    if let Some(TomlValue::Table(table)) = parent {
+        table.remove(last);
+    }
+}
+
+fn invalid_override(path: &str, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid -c override `{path}`: {message}"),
+    )
+}
+
+fn apply_cli_override(
+    layer: &mut CliOverridesLayer,
+    lower: &TomlValue,
+    raw_path: &str,
+    value: TomlValue,
+) -> Result<(), String> {
+    let (remove, raw_path) = match raw_path.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, raw_path),
+    };
+    let path = parse_path(raw_path)?;
+    let first_index = path
+        .iter()
+        .position(|segment| matches!(segment, PathSegment::Index(_)));
+    let Some(first_index) = first_index else {
+        let keys: Vec<String> = path
+            .into_iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Key(key) => Some(key),
+                PathSegment::Index(_) => None,
+            })
+            .collect();
+        if remove {
+            remove_path(&mut layer.config, &keys);
+            layer.removals.push(keys);
+        } else {
+            set_keys(&mut layer.config, &keys, value);
+        }
+        return Ok(());
+    };
+
+    // Arrays are replaced as a whole when layers merge, so edit a copy of the
+    // array the lower layers (or an earlier override) resolve to.
+    let (array_path, rest) = path.split_at(first_index);
+    let keys: Vec<String> = array_path
+        .iter()
+        .filter_map(|segment| match segment {
+            PathSegment::Key(key) => Some(key.clone()),
+            PathSegment::Index(_) => None,
+        })
+        .collect();
+    let mut array = get_keys(&layer.config, &keys)
+        .or_else(|| {
+            let removed = layer
+                .removals
+                .iter()
+                .any(|removed| keys.starts_with(removed));
+            if removed {
+                None
+            } else {
+                get_keys(lower, &keys)
+            }
+        })
+        .cloned()
+        .unwrap_or_else(|| TomlValue::Array(Vec::new()));
+    if remove {
+        remove_at(&mut array, rest)?;
+    } else {
+        set_at(&mut array, rest, value)?;
+    }
+    set_keys(&mut layer.config, &keys, array);
+    Ok(())
+}
+
+/// Parses `a.b[2].c` into keys and array indices.
+fn parse_path(raw: &str) -> Result<Vec<PathSegment>, String> {
+    let mut segments = Vec::new();
+    for part in raw.split('.') {
+        let (key, mut indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if key.is_empty() {
+            return Err("empty key in path".to_string());
+        }
+        segments.push(PathSegment::Key(key.to_string()));
+        while !indices.is_empty() {
+            let Some((index, rest)) = indices
+                .strip_prefix('[')
+                .and_then(|indices| indices.split_once(']'))
+            else {
+                return Err(format!("malformed array index in `{part}`"));
+            };
+            let index = index
+                .parse()
+                .map_err(|_| format!("array index `{index}` is not a number"))?;
+            segments.push(PathSegment::Index(index));
+            indices = rest;
+        }
     }
-    root
+    Ok(segments)
 }
 
-/// Apply a single dotted-path override onto a TOML value.
-fn apply_toml_override(root: &mut TomlValue, path: &str, value: TomlValue) {
-    use toml::value::Table;
+fn get_keys<'a>(value: &'a TomlValue, keys: &[String]) -> Option<&'a TomlValue> {
+    keys.iter()
+        .try_fold(value, |value, key| value.get(key.as_str()))
+}
 
+/// Writes `value` at `keys`, replacing any non-table parents with tables.
+fn set_keys(root: &mut TomlValue, keys: &[String], value: TomlValue) {
+    let Some((last, parents)) = keys.split_last() else {
+        *root = value;
+        return;
+    };
     let mut current = root;
-    let mut segments_iter = path.split('.').peekable();
-
-    while let Some(segment) = segments_iter.next() {
-        let is_last = segments_iter.peek().is_none();
-
-        if is_last {
-            match current {
-                TomlValue::Table(table) => {
-                    table.insert(segment.to_string(), value);
-                }
-                _ => {
-                    let mut table = Table::new();
-                    table.insert(segment.to_string(), value);
-                    *current = TomlValue::Table(table);
-                }
+    for key in parents {
+        current = as_table(current)
+            .entry(key.clone())
+            .or_insert_with(|| TomlValue::Table(Table::new()));
+    }
+    as_table(current).insert(last.clone(), value);
+}
+
+/// `value` as a table, replacing it with an empty one if it is not a table.
+fn as_table(value: &mut TomlValue) -> &mut Table {
+    if !value.is_table() {
+        *value = TomlValue::Table(Table::new());
+    }
+    match value {
+        TomlValue::Table(table) => table,
+        _ => unreachable!("replaced with a table above"),
+    }
+}
+
+/// Writes `value` at `path` (which starts with an index) inside `array`. An
+/// index one past the end appends.
+fn set_at(array: &mut TomlValue, path: &[PathSegment], value: TomlValue) -> Result<(), String> {
+    let Some((segment, rest)) = path.split_first() else {
+        *array = value;
+        return Ok(());
+    };
+    match segment {
+        PathSegment::Index(index) => {
+            let TomlValue::Array(items) = array else {
+                return Err(format!("cannot index into {}", array.type_str()));
+            };
+            if *index == items.len() && rest.is_empty() {
+                items.push(value);
+                return Ok(());
             }
-            return;
+            let len = items.len();
+            let item = items
+                .get_mut(*index)
+                .ok_or_else(|| format!("index {index} is out of range for {len} items"))?;
+            set_at(item, rest, value)
         }
+        PathSegment::Key(key) => {
+            let child = as_table(array)
+                .entry(key.clone())
+                .or_insert_with(|| TomlValue::Table(Table::new()));
+            set_at(child, rest, value)
+        }
+    }
+}
 
-        match current {
-            TomlValue::Table(table) => {
-                current = table
-                    .entry(segment.to_string())
-                    .or_insert_with(|| TomlValue::Table(Table::new()));
+/// Removes the item or key at `path` (which starts with an index) inside
+/// `array`.
+fn remove_at(array: &mut TomlValue, path: &[PathSegment]) -> Result<(), String> {
+    match path {
+        [] => Ok(()),
+        [PathSegment::Index(index)] => {
+            let TomlValue::Array(items) = array else {
+                return Err(format!("cannot index into {}", array.type_str()));
+            };
+            if *index >= items.len() {
+                return Err(format!(
+                    "index {index} is out of range for {} items",
+                    items.len()
+                ));
             }
-            _ => {
-                *current = TomlValue::Table(Table::new());
-                if let TomlValue::Table(tbl) = current {
-                    current = tbl
-                        .entry(segment.to_string())
-                        .or_insert_with(|| TomlValue::Table(Table::new()));
-                }
+            items.remove(*index);
+            Ok(())
+        }
+        [PathSegment::Key(key)] => {
+            if let TomlValue::Table(table) = array {
+                table.remove(key);
             }
+            Ok(())
         }
+        [PathSegment::Index(index), rest @ ..] => {
+            let TomlValue::Array(items) = array else {
+                return Err(format!("cannot index into {}", array.type_str()));
+            };
+            let len = items.len();
+            let item = items
+                .get_mut(*index)
+                .ok_or_else(|| format!("index {index} is out of range for {len} items"))?;
+            remove_at(item, rest)
+        }
+        [PathSegment::Key(key), rest @ ..] => match array.get_mut(key.as_str()) {
+            Some(child) => remove_at(child, rest),
+            None => Ok(()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    fn string(value: &str) -> TomlValue {
+        TomlValue::String(value.to_string())
+    }
+
+    #[test]
+    fn parses_keys_and_indices() {
+        assert_eq!(
+            parse_path("mcp_servers.docs.args[2]"),
+            Ok(vec![
+                PathSegment::Key("mcp_servers".to_string()),
+                PathSegment::Key("docs".to_string()),
+                PathSegment::Key("args".to_string()),
+                PathSegment::Index(2),
+            ])
+        );
+        assert_eq!(
+            parse_path("a[0][1].b"),
+            Ok(vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Index(1),
+                PathSegment::Key("b".to_string()),
+            ])
+        );
+        assert_eq!(
+            parse_path("args[x]"),
+            Err("array index `x` is not a number".to_string())
+        );
+        assert_eq!(
+            parse_path("args[1"),
+            Err("malformed array index in `args[1`".to_string())
+        );
+        assert_eq!(parse_path("a..b"), Err("empty key in path".to_string()));
+    }
+
+    #[test]
+    fn indexes_arrays_from_the_config_files_and_removes_keys() {
+        let lower = toml(
+            r#"
+notify = ["notify-send", "Codex"]
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--stdio", "--port", "8080"]
+"#,
+        );
+
+        let layer = build_cli_overrides_layer(
+            &[
+                ("mcp_servers.docs.args[2]".to_string(), string("--verbose")),
+                ("mcp_servers.docs.args[3]".to_string(), string("--color")),
+                (
+                    "!mcp_servers.docs.args[0]".to_string(),
+                    TomlValue::Boolean(true),
+                ),
+                ("model".to_string(), string("o3")),
+                ("!notify".to_string(), TomlValue::Boolean(true)),
+            ],
+            &lower,
+        )
+        .expect("valid overrides");
+
+        assert_eq!(
+            layer,
+            CliOverridesLayer {
+                config: toml(
+                    r#"
+model = "o3"
+
+[mcp_servers.docs]
+args = ["--port", "--verbose", "--color"]
+"#
+                ),
+                removals: vec![vec!["notify".to_string()]],
+            }
+        );
+    }
+
+    #[test]
+    fn later_overrides_refill_removed_tables() {
+        let lower = toml(
+            r#"
+[tui]
+notifications = ["agent-turn-complete"]
+"#,
+        );
+
+        let layer = build_cli_overrides_layer(
+            &[
+                ("tui.notifications".to_string(), TomlValue::Boolean(true)),
+                ("!tui".to_string(), TomlValue::Boolean(true)),
+                ("tui.animations".to_string(), TomlValue::Boolean(false)),
+            ],
+            &lower,
+        )
+        .expect("valid overrides");
+
+        assert_eq!(layer.config, toml("tui = { animations = false }"));
+        assert_eq!(layer.removals, vec![vec!["tui".to_string()]]);
+
+        let mut merged = lower;
+        remove_path(&mut merged, &layer.removals[0]);
+        assert_eq!(merged, default_empty_table());
+    }
+
+    #[test]
+    fn rejects_out_of_range_indices_and_mistyped_values() {
+        let lower = toml(r#"notify = ["notify-send"]"#);
+
+        let err = build_cli_overrides_layer(&[("notify[3]".to_string(), string("x"))], &lower)
+            .expect_err("out of range");
+        assert_eq!(
+            err.to_string(),
+            "invalid -c override `notify[3]`: index 3 is out of range for 1 items"
+        );
+
+        let err = build_cli_overrides_layer(
+            &[("model_reasoning_effort".to_string(), string("hgih"))],
+            &lower,
+        )
+        .expect_err("invalid enum value");
+        assert!(
+            err.to_string()
+                .starts_with("invalid -c override `model_reasoning_effort`: invalid value"),
+            "{err}"
+        );
+
+        build_cli_overrides_layer(&[("not_a_codex_key".to_string(), string("x"))], &lower)
+            .expect("unknown keys are only warnings");
     }
 }
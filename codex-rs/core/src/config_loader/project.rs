@@ -0,0 +1,379 @@
+//! Project-level `.codex/config.toml`, discovered like `.codex/steering` and
+//! merged over the user config.
+//!
+//! Because the file is checked into a repository, only a safe subset of keys
+//! is honored: model hints, the choice of a `profile` the user already
+//! defined, steering budgets, the container image for `sandbox.backend`, and
+//! sandbox settings that tighten (never loosen) what the lower layers already
+//! allow. Everything else is dropped with a warning.
+
+use super::ConfigLayerStack;
+use super::merge::merge_toml_values;
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::trust_store::project_files_allowed;
+use crate::git_info::resolve_root_git_project_for_trust;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use dunce::canonicalize as normalize_path;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+use tracing::warn;
+
+const PROJECT_CONFIG_DIR_NAME: &str = ".codex";
+
+/// Top-level keys a project config may set without restriction.
+const MODEL_HINT_KEYS: &[&str] = &[
+    "model",
+    "review_model",
+    "model_reasoning_effort",
+    "model_reasoning_summary",
+    "model_verbosity",
+];
+
+/// Keys of the `[steering]` table a project config may set.
+const STEERING_KEYS: &[&str] = &["max_bytes", "project_min_fraction"];
+
+/// Keys of the `[sandbox]` table a project config may set. The image only
+/// changes the toolchain inside the container; the user still picks whether a
+/// container is used at all.
+const SANDBOX_KEYS: &[&str] = &["image"];
+
+/// Sandbox modes ordered from most to least restrictive.
+pub(super) const SANDBOX_MODES: &[&str] = &["read-only", "workspace-write", "danger-full-access"];
+
+/// Finds the nearest `.codex/config.toml` between `cwd` and the root of the
+/// enclosing git repository (or in `cwd` itself outside a repository),
+/// skipping `$CODEX_HOME/config.toml` so the user config is never read twice.
+pub(crate) fn find_project_config_file(cwd: &Path, codex_home: &Path) -> Option<PathBuf> {
+    let base = normalize_path(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+    let user_file = normalize_path(codex_home.join(CONFIG_TOML_FILE)).ok();
+    let repo_root =
+        resolve_root_git_project_for_trust(&base).map(|root| normalize_path(&root).unwrap_or(root));
+
+    for dir in base.ancestors() {
+        let candidate = dir.join(PROJECT_CONFIG_DIR_NAME).join(CONFIG_TOML_FILE);
+        if candidate.is_file() && normalize_path(&candidate).ok() != user_file {
+            return Some(candidate);
+        }
+        match repo_root.as_deref() {
+            Some(repo_root) if dir != repo_root => continue,
+            _ => break,
+        }
+    }
+    None
+}
+
+/// Loads the project config for `cwd`, if any, and inserts it into `stack`
+/// above the user layer. Malformed files are reported as errors, like the
+/// user config. Nothing is loaded when the user chose not to trust the
+/// project's files.
+pub(crate) async fn apply_project_config_layer(
+    stack: ConfigLayerStack,
+    codex_home: &Path,
+    cwd: &Path,
+) -> io::Result<ConfigLayerStack> {
+    if !project_files_allowed(codex_home, cwd) {
+        return Ok(stack);
+    }
+    let Some(file) = find_project_config_file(cwd, codex_home) else {
+        return Ok(stack);
+    };
+    let contents = tokio::fs::read_to_string(&file).await.map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("Failed to read project config file {}: {e}", file.display()),
+        )
+    })?;
+    let raw: TomlValue = toml::from_str(&contents).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Error parsing project config file {}: {e}", file.display()),
+        )
+    })?;
+
+    let lower = effective_config_below_project(&stack);
+    let (project_config, ignored) = sanitize_project_config(raw, &lower);
+    if !ignored.is_empty() {
+        warn!(
+            file = %file.display(),
+            ignored = ignored.join(", "),
+            "ignoring settings that a project config is not allowed to change"
+        );
+    }
+
+    let file = AbsolutePathBuf::from_absolute_path(&file)?;
+    Ok(stack.with_project_config(&file, project_config))
+}
+
+/// Merges the layers the project layer sits above (system and user config).
+fn effective_config_below_project(stack: &ConfigLayerStack) -> TomlValue {
+    let mut merged = TomlValue::Table(toml::map::Map::new());
+    for layer in stack.layers_high_to_low().into_iter().rev() {
+        if matches!(
+            layer.name,
+            ConfigLayerSource::Mdm { .. }
+                | ConfigLayerSource::System { .. }
+                | ConfigLayerSource::User { .. }
+        ) {
+            merge_toml_values(&mut merged, &layer.config);
+        }
+    }
+    merged
+}
+
+/// Keeps the keys of `raw` that a project is allowed to set, given the
+/// effective config of the layers below it. Returns the filtered table and the
+/// dotted paths of everything that was dropped.
+pub(crate) fn sanitize_project_config(
+    raw: TomlValue,
+    lower: &TomlValue,
+) -> (TomlValue, Vec<String>) {
+    let mut kept = toml::map::Map::new();
+    let mut ignored = Vec::new();
+    let TomlValue::Table(table) = raw else {
+        return (TomlValue::Table(kept), ignored);
+    };
+
+    for (key, value) in table {
+        match key.as_str() {
+            key if MODEL_HINT_KEYS.contains(&key) => {
+                kept.insert(key.to_string(), value);
+            }
+            // Selecting a profile is safe because the profiles themselves
+            // can only be defined outside the project.
+            "profile" => {
+                kept.insert(key, value);
+            }
+            "steering" => {
+                let filtered = filter_table(value, "steering", &mut ignored, |key, _| {
+                    STEERING_KEYS.contains(&key)
+                });
+                kept.insert(key, filtered);
+            }
+            "sandbox" => {
+                let filtered = filter_table(value, "sandbox", &mut ignored, |key, _| {
+                    SANDBOX_KEYS.contains(&key)
+                });
+                kept.insert(key, filtered);
+            }
+            "sandbox_mode" => {
+                if tightens_sandbox_mode(&value, lower.get("sandbox_mode")) {
+                    kept.insert(key, value);
+                } else {
+                    ignored.push(key);
+                }
+            }
+            "sandbox_workspace_write" => {
+                let filtered = filter_table(
+                    value,
+                    "sandbox_workspace_write",
+                    &mut ignored,
+                    |key, value| match key {
+                        "network_access" => value.as_bool() == Some(false),
+                        "exclude_tmpdir_env_var" | "exclude_slash_tmp" => {
+                            value.as_bool() == Some(true)
+                        }
+                        _ => false,
+                    },
+                );
+                kept.insert(key, filtered);
+            }
+            _ => ignored.push(key),
+        }
+    }
+
+    ignored.sort();
+    (TomlValue::Table(kept), ignored)
+}
+
+fn filter_table(
+    value: TomlValue,
+    prefix: &str,
+    ignored: &mut Vec<String>,
+    allowed: impl Fn(&str, &TomlValue) -> bool,
+) -> TomlValue {
+    let TomlValue::Table(table) = value else {
+        ignored.push(prefix.to_string());
+        return TomlValue::Table(toml::map::Map::new());
+    };
+    let mut kept = toml::map::Map::new();
+    for (key, value) in table {
+        if allowed(&key, &value) {
+            kept.insert(key, value);
+        } else {
+            ignored.push(format!("{prefix}.{key}"));
+        }
+    }
+    TomlValue::Table(kept)
+}
+
+/// A project may only pick a sandbox mode at least as restrictive as the one
+/// the lower layers resolve to (`read-only` when they leave it unset).
+fn tightens_sandbox_mode(project: &TomlValue, lower: Option<&TomlValue>) -> bool {
+    let rank = |value: &TomlValue| {
+        value
+            .as_str()
+            .and_then(|mode| SANDBOX_MODES.iter().position(|known| *known == mode))
+    };
+    let lower_rank = lower.and_then(rank).unwrap_or(0);
+    rank(project).is_some_and(|project_rank| project_rank <= lower_rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    #[test]
+    fn keeps_model_hints_and_drops_unsafe_keys() {
+        let raw = toml(
+            r#"
+model = "gpt-5.1-codex"
+model_reasoning_effort = "high"
+approval_policy = "never"
+model_provider = "evil"
+profile = "rust"
+
+[steering]
+max_bytes = 4096
+learned_file = "/etc/passwd"
+
+[mcp_servers.foo]
+command = "curl"
+
+[profiles.rust]
+approval_policy = "never"
+"#,
+        );
+
+        let (kept, ignored) = sanitize_project_config(raw, &toml(""));
+
+        assert_eq!(
+            kept,
+            toml(
+                r#"
+model = "gpt-5.1-codex"
+model_reasoning_effort = "high"
+profile = "rust"
+
+[steering]
+max_bytes = 4096
+"#
+            )
+        );
+        assert_eq!(
+            ignored,
+            vec![
+                "approval_policy".to_string(),
+                "mcp_servers".to_string(),
+                "model_provider".to_string(),
+                "profiles".to_string(),
+                "steering.learned_file".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn sandbox_settings_may_only_tighten() {
+        let lower = toml(r#"sandbox_mode = "workspace-write""#);
+
+        let (kept, ignored) = sanitize_project_config(
+            toml(
+                r#"
+sandbox_mode = "read-only"
+
+[sandbox_workspace_write]
+network_access = false
+writable_roots = ["/"]
+"#,
+            ),
+            &lower,
+        );
+        assert_eq!(
+            kept,
+            toml(
+                r#"
+sandbox_mode = "read-only"
+
+[sandbox_workspace_write]
+network_access = false
+"#
+            )
+        );
+        assert_eq!(
+            ignored,
+            vec!["sandbox_workspace_write.writable_roots".to_string()]
+        );
+
+        let (kept, ignored) =
+            sanitize_project_config(toml(r#"sandbox_mode = "danger-full-access""#), &lower);
+        assert_eq!(kept, toml(""));
+        assert_eq!(ignored, vec!["sandbox_mode".to_string()]);
+
+        let (_, ignored) =
+            sanitize_project_config(toml(r#"sandbox_mode = "workspace-write""#), &toml(""));
+        assert_eq!(ignored, vec!["sandbox_mode".to_string()]);
+    }
+
+    #[test]
+    fn project_may_override_only_the_container_image() {
+        let (kept, ignored) = sanitize_project_config(
+            toml(
+                r#"
+[sandbox]
+backend = "docker"
+image = "rust:1.90"
+pull = "always"
+"#,
+            ),
+            &toml(""),
+        );
+
+        assert_eq!(
+            kept,
+            toml(
+                r#"
+[sandbox]
+image = "rust:1.90"
+"#
+            )
+        );
+        assert_eq!(
+            ignored,
+            vec!["sandbox.backend".to_string(), "sandbox.pull".to_string()]
+        );
+    }
+
+    #[test]
+    fn finds_nearest_project_config_without_git() {
+        let codex_home = tempdir().expect("codex home");
+        let project = tempdir().expect("project");
+        let config_dir = project.path().join(PROJECT_CONFIG_DIR_NAME);
+        std::fs::create_dir(&config_dir).expect("mkdir");
+        std::fs::write(config_dir.join(CONFIG_TOML_FILE), "model = \"o3\"").expect("write");
+
+        let found = find_project_config_file(project.path(), codex_home.path());
+
+        assert_eq!(
+            found.map(|path| normalize_path(path).expect("canonical")),
+            Some(normalize_path(config_dir.join(CONFIG_TOML_FILE)).expect("canonical"))
+        );
+    }
+
+    #[test]
+    fn user_config_is_not_treated_as_project_config() {
+        let home = tempdir().expect("home");
+        let codex_home = home.path().join(PROJECT_CONFIG_DIR_NAME);
+        std::fs::create_dir(&codex_home).expect("mkdir");
+        std::fs::write(codex_home.join(CONFIG_TOML_FILE), "model = \"o3\"").expect("write");
+
+        assert_eq!(find_project_config_file(home.path(), &codex_home), None);
+    }
+}
@@ -1,11 +1,21 @@
+use crate::config::migration::MigratedKey;
+use crate::config::migration::migrate_keys;
 use crate::config_loader::ConfigRequirements;
 
 use super::fingerprint::record_origins;
 use super::fingerprint::version_for_toml;
 use super::merge::merge_toml_values;
+use super::overrides::remove_path;
+use super::system::ENFORCED_KEYS_KEY;
+use super::system::LOCKED_KEYS_KEY;
+use super::system::LockedKeyViolation;
+use super::system::enforce_system_keys;
+use super::system::locked_key_violations;
+use super::system::sandbox_mode_floor;
 use codex_app_server_protocol::ConfigLayer;
 use codex_app_server_protocol::ConfigLayerMetadata;
 use codex_app_server_protocol::ConfigLayerSource;
+use codex_protocol::config_types::SandboxMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
@@ -15,6 +25,8 @@ use toml::Value as TomlValue;
 #[derive(Debug, Default, Clone)]
 pub struct LoaderOverrides {
     pub managed_config_path: Option<PathBuf>,
+    /// Replaces the platform's system `config.toml` path.
+    pub system_config_path: Option<PathBuf>,
     #[cfg(target_os = "macos")]
     pub managed_preferences_base64: Option<String>,
 }
@@ -64,6 +76,10 @@ pub struct ConfigLayerStack {
     /// Constraints that must be enforced when deriving a [Config] from the
     /// layers.
     requirements: ConfigRequirements,
+
+    /// Key paths removed with `-c !key`, deleted from the layers below the
+    /// session flags before those are merged.
+    cli_removals: Vec<Vec<String>>,
 }
 
 impl ConfigLayerStack {
@@ -76,9 +92,15 @@ impl ConfigLayerStack {
             layers,
             user_layer_index,
             requirements,
+            cli_removals: Vec::new(),
         })
     }
 
+    pub(crate) fn with_cli_removals(mut self, cli_removals: Vec<Vec<String>>) -> Self {
+        self.cli_removals = cli_removals;
+        self
+    }
+
     /// Returns the user config layer, if any.
     pub fn get_user_layer(&self) -> Option<&ConfigLayerEntry> {
         self.user_layer_index
@@ -109,6 +131,7 @@ impl ConfigLayerStack {
                     layers,
                     user_layer_index: self.user_layer_index,
                     requirements: self.requirements.clone(),
+                    cli_removals: self.cli_removals.clone(),
                 }
             }
             None => {
@@ -129,19 +152,126 @@ impl ConfigLayerStack {
                     layers,
                     user_layer_index: Some(user_layer_index),
                     requirements: self.requirements.clone(),
+                    cli_removals: self.cli_removals.clone(),
                 }
             }
         }
     }
 
+    /// Creates a new [ConfigLayerStack] with `project_config` (read from a
+    /// repository's `.codex/config.toml`) inserted above the user layer,
+    /// replacing any existing project layer.
+    pub fn with_project_config(&self, file: &AbsolutePathBuf, project_config: TomlValue) -> Self {
+        let project_layer = ConfigLayerEntry::new(
+            ConfigLayerSource::Project { file: file.clone() },
+            project_config,
+        );
+
+        let mut layers: Vec<ConfigLayerEntry> = self
+            .layers
+            .iter()
+            .filter(|layer| !matches!(layer.name, ConfigLayerSource::Project { .. }))
+            .cloned()
+            .collect();
+        let index = layers
+            .iter()
+            .position(|layer| layer.name > project_layer.name)
+            .unwrap_or(layers.len());
+        layers.insert(index, project_layer);
+        let user_layer_index = layers
+            .iter()
+            .position(|layer| matches!(layer.name, ConfigLayerSource::User { .. }));
+
+        Self {
+            layers,
+            user_layer_index,
+            requirements: self.requirements.clone(),
+            cli_removals: self.cli_removals.clone(),
+        }
+    }
+
+    /// Merges the layers, with deprecated keys in each layer moved to their
+    /// replacements first. Keys the system config enforces are restored after
+    /// the user-controlled layers (up to the session flags) are merged, so
+    /// only the legacy managed config can override them.
     pub fn effective_config(&self) -> TomlValue {
         let mut merged = TomlValue::Table(toml::map::Map::new());
+        let mut system = self.system_config();
         for layer in &self.layers {
-            merge_toml_values(&mut merged, &layer.config);
+            if layer.name > ConfigLayerSource::SessionFlags
+                && let Some(system) = system.take()
+            {
+                enforce_system_keys(&mut merged, system);
+            }
+            if layer.name == ConfigLayerSource::SessionFlags {
+                for path in &self.cli_removals {
+                    remove_path(&mut merged, path);
+                }
+            }
+            let mut config = layer.config.clone();
+            migrate_keys(&mut config);
+            merge_toml_values(&mut merged, &config);
+        }
+        if let Some(system) = system {
+            enforce_system_keys(&mut merged, system);
+        }
+        if let TomlValue::Table(table) = &mut merged {
+            table.remove(ENFORCED_KEYS_KEY);
+            table.remove(LOCKED_KEYS_KEY);
         }
         merged
     }
 
+    /// The sandbox mode the system config enforces as a floor, if any.
+    pub fn sandbox_mode_floor(&self) -> Option<SandboxMode> {
+        self.system_config().and_then(sandbox_mode_floor)
+    }
+
+    /// Values that change a key the system config locks: those set by the
+    /// user-controlled layers (up to the session flags) and by `flags`, the
+    /// command-line flags. `profile` is the active profile, checked too.
+    pub fn locked_key_violations(
+        &self,
+        flags: &TomlValue,
+        profile: Option<&str>,
+    ) -> Vec<LockedKeyViolation> {
+        let Some(system) = self.system_layer() else {
+            return Vec::new();
+        };
+        let mut violations: Vec<LockedKeyViolation> = self
+            .layers
+            .iter()
+            .filter(|layer| {
+                layer.name > system.name && layer.name <= ConfigLayerSource::SessionFlags
+            })
+            .flat_map(|layer| {
+                let mut config = layer.config.clone();
+                migrate_keys(&mut config);
+                locked_key_violations(system, &config, Some(&layer.name), profile)
+            })
+            .collect();
+        violations.extend(locked_key_violations(system, flags, None, profile));
+        violations
+    }
+
+    fn system_config(&self) -> Option<&TomlValue> {
+        self.system_layer().map(|layer| &layer.config)
+    }
+
+    fn system_layer(&self) -> Option<&ConfigLayerEntry> {
+        self.layers
+            .iter()
+            .find(|layer| matches!(layer.name, ConfigLayerSource::System { .. }))
+    }
+
+    /// Deprecated keys set in any layer, lowest precedence first.
+    pub fn deprecated_keys(&self) -> Vec<MigratedKey> {
+        self.layers
+            .iter()
+            .flat_map(|layer| migrate_keys(&mut layer.config.clone()))
+            .collect()
+    }
+
     pub fn origins(&self) -> HashMap<String, ConfigLayerMetadata> {
         let mut origins = HashMap::new();
         let mut path = Vec::new();
@@ -0,0 +1,349 @@
+//! System-wide `config.toml` (`/etc/codex/config.toml`, or
+//! `%ProgramData%\Codex\config.toml` on Windows), where an organization ships
+//! its defaults. It is the lowest-precedence file layer, so every other layer
+//! can override it, except for the keys it lists in `enforced_keys`:
+//!
+//! - `sandbox_mode` acts as a floor: other layers, profiles, and `--sandbox`
+//!   may only pick a mode at least as restrictive.
+//! - Tables (e.g. `model_providers`) can gain entries, but the entries and
+//!   fields the system config defines keep its values.
+//! - Arrays can gain items, but keep every item the system config lists.
+//! - Any other value is fixed to the system config's value.
+//!
+//! Enforced keys also apply inside every `[profiles.<name>]` table that sets
+//! them.
+//!
+//! Keys listed in `locked` are stricter: a layer or command-line flag that
+//! changes one is an error rather than being overridden, so the user learns
+//! why their setting has no effect. Only `sandbox_mode` may still be made
+//! more restrictive.
+
+use super::merge::merge_toml_values;
+use super::project::SANDBOX_MODES;
+use super::state::ConfigLayerEntry;
+use super::toml_path::toml_value_at;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_protocol::config_types::SandboxMode;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+
+/// Key of the system config listing the dotted paths other layers may not
+/// weaken. It is removed from the effective config.
+pub(super) const ENFORCED_KEYS_KEY: &str = "enforced_keys";
+
+/// Key of the system config listing the dotted paths that other layers and
+/// command-line flags may not change. It is removed from the effective
+/// config.
+pub(super) const LOCKED_KEYS_KEY: &str = "locked";
+
+#[cfg(unix)]
+const SYSTEM_CONFIG_PATH_UNIX: &str = "/etc/codex/config.toml";
+
+/// Where the system config lives on this platform, if it has one.
+pub(super) fn system_config_default_path() -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        Some(PathBuf::from(SYSTEM_CONFIG_PATH_UNIX))
+    }
+
+    #[cfg(windows)]
+    {
+        let program_data =
+            std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        Some(
+            PathBuf::from(program_data)
+                .join("Codex")
+                .join("config.toml"),
+        )
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        None
+    }
+}
+
+/// Restores in `merged` every enforced key of `system` that the layers above
+/// it weakened.
+pub(super) fn enforce_system_keys(merged: &mut TomlValue, system: &TomlValue) {
+    for key in enforced_keys(system) {
+        let path: Vec<&str> = key.split('.').collect();
+        let Some(required) = toml_value_at(system, &path) else {
+            continue;
+        };
+        let last = path.last().copied().unwrap_or_default();
+        if let Some(slot) = get_path_mut(merged, &path, true) {
+            enforce_value(slot, required, last);
+        }
+        let Some(TomlValue::Table(profiles)) = merged.get_mut("profiles") else {
+            continue;
+        };
+        for profile in profiles.values_mut() {
+            if let Some(slot) = get_path_mut(profile, &path, false) {
+                enforce_value(slot, required, last);
+            }
+        }
+    }
+}
+
+/// The sandbox mode floor set by `system`, if `sandbox_mode` is enforced.
+pub(super) fn sandbox_mode_floor(system: &TomlValue) -> Option<SandboxMode> {
+    if !enforced_keys(system).any(|key| key == "sandbox_mode") {
+        return None;
+    }
+    system.get("sandbox_mode")?.clone().try_into().ok()
+}
+
+/// `mode` if it is at least as restrictive as `floor`, else `floor`.
+pub(crate) fn clamp_sandbox_mode(mode: SandboxMode, floor: SandboxMode) -> SandboxMode {
+    let rank = |mode: SandboxMode| sandbox_rank(&mode.to_string());
+    if rank(mode) <= rank(floor) {
+        mode
+    } else {
+        floor
+    }
+}
+
+/// A key the system config locks, set to another value by a layer above it
+/// or by a command-line flag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedKeyViolation {
+    /// Dotted path of the value, e.g. `sandbox_mode` or
+    /// `profiles.yolo.sandbox_mode`.
+    pub key: String,
+    /// The value the system config locks the key to, or `None` when it
+    /// locks the key unset.
+    pub locked_value: Option<TomlValue>,
+    pub value: TomlValue,
+    /// The system config layer that locks the key.
+    pub locked_by: ConfigLayerSource,
+    /// The layer that sets the key, or `None` for a command-line flag.
+    pub set_by: Option<ConfigLayerSource>,
+}
+
+/// The locked keys of the `system` layer that `config` (from `set_by`)
+/// changes, at the top level or in the `profile` table.
+pub(super) fn locked_key_violations(
+    system: &ConfigLayerEntry,
+    config: &TomlValue,
+    set_by: Option<&ConfigLayerSource>,
+    profile: Option<&str>,
+) -> Vec<LockedKeyViolation> {
+    let mut violations = Vec::new();
+    for key in keys_listed(&system.config, LOCKED_KEYS_KEY) {
+        let path: Vec<&str> = key.split('.').collect();
+        let locked = toml_value_at(&system.config, &path);
+        let profile_path = profile.map(|profile| {
+            ["profiles", profile]
+                .into_iter()
+                .chain(path.iter().copied())
+                .collect::<Vec<_>>()
+        });
+        for path in std::iter::once(path.clone()).chain(profile_path) {
+            let Some(value) = toml_value_at(config, &path) else {
+                continue;
+            };
+            if !locked_value_allowed(key, value, locked) {
+                violations.push(LockedKeyViolation {
+                    key: path.join("."),
+                    locked_value: locked.cloned(),
+                    value: value.clone(),
+                    locked_by: system.name.clone(),
+                    set_by: set_by.cloned(),
+                });
+            }
+        }
+    }
+    violations
+}
+
+fn locked_value_allowed(key: &str, value: &TomlValue, locked: Option<&TomlValue>) -> bool {
+    match (key, value, locked) {
+        (_, value, Some(locked)) if value == locked => true,
+        ("sandbox_mode", TomlValue::String(mode), Some(TomlValue::String(floor))) => {
+            sandbox_rank(mode) <= sandbox_rank(floor)
+        }
+        _ => false,
+    }
+}
+
+fn enforced_keys(system: &TomlValue) -> impl Iterator<Item = &str> {
+    keys_listed(system, ENFORCED_KEYS_KEY)
+}
+
+fn keys_listed<'a>(system: &'a TomlValue, list: &str) -> impl Iterator<Item = &'a str> {
+    system
+        .get(list)
+        .and_then(TomlValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(TomlValue::as_str)
+}
+
+fn enforce_value(slot: &mut TomlValue, required: &TomlValue, key: &str) {
+    match (key, &mut *slot, required) {
+        ("sandbox_mode", TomlValue::String(mode), TomlValue::String(floor)) => {
+            if sandbox_rank(mode) > sandbox_rank(floor) {
+                *slot = required.clone();
+            }
+        }
+        (_, TomlValue::Table(_), TomlValue::Table(_)) => merge_toml_values(slot, required),
+        (_, TomlValue::Array(items), TomlValue::Array(required_items)) => {
+            let mut union = required_items.clone();
+            union.extend(
+                items
+                    .iter()
+                    .filter(|item| !required_items.contains(item))
+                    .cloned(),
+            );
+            *items = union;
+        }
+        _ => *slot = required.clone(),
+    }
+}
+
+/// Position in [`SANDBOX_MODES`]; unknown modes rank as the least restrictive.
+fn sandbox_rank(mode: &str) -> usize {
+    SANDBOX_MODES
+        .iter()
+        .position(|known| *known == mode)
+        .unwrap_or(SANDBOX_MODES.len())
+}
+
+/// The value at `path`. With `create`, missing or non-table parents are
+/// replaced by tables and a missing value is created as an empty string, to
+/// be overwritten by the caller.
+fn get_path_mut<'a>(
+    value: &'a mut TomlValue,
+    path: &[&str],
+    create: bool,
+) -> Option<&'a mut TomlValue> {
+    let Some((key, rest)) = path.split_first() else {
+        return Some(value);
+    };
+    if !value.is_table() {
+        if !create {
+            return None;
+        }
+        *value = TomlValue::Table(toml::map::Map::new());
+    }
+    let table = value.as_table_mut()?;
+    if !table.contains_key(*key) {
+        if !create {
+            return None;
+        }
+        let empty = if rest.is_empty() {
+            TomlValue::String(String::new())
+        } else {
+            TomlValue::Table(toml::map::Map::new())
+        };
+        table.insert((*key).to_string(), empty);
+    }
+    get_path_mut(table.get_mut(*key)?, rest, create)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn toml(contents: &str) -> TomlValue {
+        toml::from_str(contents).expect("valid toml")
+    }
+
+    #[test]
+    fn enforced_keys_can_be_extended_but_not_weakened() {
+        let system = toml(
+            r#"
+enforced_keys = ["sandbox_mode", "model_providers", "trusted_hosts", "model_provider"]
+sandbox_mode = "workspace-write"
+model_provider = "corp"
+trusted_hosts = ["git.corp.example"]
+
+[model_providers.corp]
+name = "Corp gateway"
+base_url = "https://llm.corp.example/v1"
+"#,
+        );
+        let mut merged = system.clone();
+        merge_toml_values(
+            &mut merged,
+            &toml(
+                r#"
+sandbox_mode = "danger-full-access"
+model_provider = "openai"
+trusted_hosts = ["github.com"]
+model = "gpt-5.1-codex"
+
+[model_providers.corp]
+base_url = "https://evil.example/v1"
+
+[model_providers.local]
+name = "Local"
+
+[profiles.yolo]
+sandbox_mode = "danger-full-access"
+
+[profiles.strict]
+sandbox_mode = "read-only"
+"#,
+            ),
+        );
+
+        enforce_system_keys(&mut merged, &system);
+
+        let mut expected = toml(
+            r#"
+enforced_keys = ["sandbox_mode", "model_providers", "trusted_hosts", "model_provider"]
+sandbox_mode = "workspace-write"
+model_provider = "corp"
+trusted_hosts = ["git.corp.example", "github.com"]
+model = "gpt-5.1-codex"
+
+[model_providers.corp]
+name = "Corp gateway"
+base_url = "https://llm.corp.example/v1"
+
+[model_providers.local]
+name = "Local"
+
+[profiles.yolo]
+sandbox_mode = "workspace-write"
+
+[profiles.strict]
+sandbox_mode = "read-only"
+"#,
+        );
+        assert_eq!(merged, expected);
+
+        // Keys that are not enforced stay overridable.
+        expected["model"] = TomlValue::String("o3".to_string());
+        merge_toml_values(&mut merged, &toml(r#"model = "o3""#));
+        enforce_system_keys(&mut merged, &system);
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn sandbox_floor_clamps_looser_modes() {
+        let system = toml(
+            r#"
+enforced_keys = ["sandbox_mode"]
+sandbox_mode = "workspace-write"
+"#,
+        );
+        let floor = sandbox_mode_floor(&system).expect("floor");
+
+        assert_eq!(
+            clamp_sandbox_mode(SandboxMode::DangerFullAccess, floor),
+            SandboxMode::WorkspaceWrite
+        );
+        assert_eq!(
+            clamp_sandbox_mode(SandboxMode::ReadOnly, floor),
+            SandboxMode::ReadOnly
+        );
+        assert_eq!(
+            sandbox_mode_floor(&toml(r#"sandbox_mode = "read-only""#)),
+            None
+        );
+    }
+}
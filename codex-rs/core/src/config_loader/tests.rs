@@ -4,7 +4,9 @@ use crate::config::CONFIG_TOML_FILE;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::config_requirements::ConfigRequirementsToml;
 use crate::config_loader::load_requirements_toml;
+use codex_app_server_protocol::ConfigLayerSource;
 use codex_protocol::protocol::AskForApproval;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
 use toml::Value as TomlValue;
@@ -36,6 +38,7 @@ extra = true
 
     let overrides = LoaderOverrides {
         managed_config_path: Some(managed_path),
+        system_config_path: None,
         #[cfg(target_os = "macos")]
         managed_preferences_base64: None,
     };
@@ -64,6 +67,7 @@ async fn returns_empty_when_all_layers_missing() {
     let managed_path = tmp.path().join("managed_config.toml");
     let overrides = LoaderOverrides {
         managed_config_path: Some(managed_path),
+        system_config_path: Some(tmp.path().join("system_config.toml")),
         #[cfg(target_os = "macos")]
         managed_preferences_base64: None,
     };
@@ -103,6 +107,150 @@ async fn returns_empty_when_all_layers_missing() {
     }
 }
 
+#[tokio::test]
+async fn system_config_is_lowest_layer_with_enforced_keys() {
+    let tmp = tempdir().expect("tempdir");
+    let system_path = tmp.path().join("system_config.toml");
+    std::fs::write(
+        &system_path,
+        r#"enforced_keys = ["sandbox_mode", "model_providers"]
+model = "gpt-5.1-codex"
+sandbox_mode = "workspace-write"
+
+[model_providers.corp]
+name = "Corp gateway"
+base_url = "https://llm.corp.example/v1"
+"#,
+    )
+    .expect("write system config");
+    std::fs::write(
+        tmp.path().join(CONFIG_TOML_FILE),
+        r#"model = "o3"
+sandbox_mode = "danger-full-access"
+
+[model_providers.corp]
+base_url = "https://elsewhere.example/v1"
+
+[model_providers.local]
+name = "Local"
+"#,
+    )
+    .expect("write user config");
+
+    let overrides = LoaderOverrides {
+        managed_config_path: Some(tmp.path().join("managed_config.toml")),
+        system_config_path: Some(system_path),
+        #[cfg(target_os = "macos")]
+        managed_preferences_base64: None,
+    };
+    let cli_overrides = vec![(
+        "sandbox_mode".to_string(),
+        TomlValue::String("danger-full-access".to_string()),
+    )];
+    let layers = load_config_layers_state(tmp.path(), &cli_overrides, overrides)
+        .await
+        .expect("load layers");
+
+    let expected: TomlValue = toml::from_str(
+        r#"model = "o3"
+sandbox_mode = "workspace-write"
+
+[model_providers.corp]
+name = "Corp gateway"
+base_url = "https://llm.corp.example/v1"
+
+[model_providers.local]
+name = "Local"
+"#,
+    )
+    .expect("expected toml");
+    assert_eq!(layers.effective_config(), expected);
+    assert_eq!(
+        layers.sandbox_mode_floor(),
+        Some(codex_protocol::config_types::SandboxMode::WorkspaceWrite)
+    );
+}
+
+#[tokio::test]
+async fn locked_keys_report_the_layers_that_change_them() {
+    let tmp = tempdir().expect("tempdir");
+    let system_path = tmp.path().join("system_config.toml");
+    std::fs::write(
+        &system_path,
+        r#"locked = ["approval_policy", "sandbox_mode", "model_provider"]
+approval_policy = "on-request"
+sandbox_mode = "workspace-write"
+"#,
+    )
+    .expect("write system config");
+    std::fs::write(
+        tmp.path().join(CONFIG_TOML_FILE),
+        r#"approval_policy = "never"
+sandbox_mode = "read-only"
+
+[profiles.yolo]
+sandbox_mode = "danger-full-access"
+"#,
+    )
+    .expect("write user config");
+
+    let overrides = LoaderOverrides {
+        managed_config_path: Some(tmp.path().join("managed_config.toml")),
+        system_config_path: Some(system_path.clone()),
+        #[cfg(target_os = "macos")]
+        managed_preferences_base64: None,
+    };
+    let cli_overrides = vec![(
+        "model_provider".to_string(),
+        TomlValue::String("oss".to_string()),
+    )];
+    let layers = load_config_layers_state(tmp.path(), &cli_overrides, overrides)
+        .await
+        .expect("load layers");
+    assert_eq!(layers.effective_config().get("locked"), None);
+
+    let flags: TomlValue = toml::from_str(r#"sandbox_mode = "workspace-write""#).expect("flags");
+    let violations: Vec<_> = layers
+        .locked_key_violations(&flags, Some("yolo"))
+        .into_iter()
+        .map(|violation| (violation.key, violation.set_by))
+        .collect();
+    let user_file = AbsolutePathBuf::try_from(tmp.path().join(CONFIG_TOML_FILE)).expect("path");
+    // A stricter `sandbox_mode` is allowed, a looser one is not.
+    assert_eq!(
+        violations,
+        vec![
+            (
+                "approval_policy".to_string(),
+                Some(ConfigLayerSource::User {
+                    file: user_file.clone()
+                })
+            ),
+            (
+                "profiles.yolo.sandbox_mode".to_string(),
+                Some(ConfigLayerSource::User { file: user_file })
+            ),
+            (
+                "model_provider".to_string(),
+                Some(ConfigLayerSource::SessionFlags)
+            ),
+        ]
+    );
+
+    let flags: TomlValue = toml::from_str(r#"approval_policy = "never""#).expect("flags");
+    let violation = layers
+        .locked_key_violations(&flags, None)
+        .pop()
+        .expect("violation");
+    assert_eq!(violation.set_by, None);
+    assert_eq!(
+        violation.locked_by,
+        ConfigLayerSource::System {
+            file: AbsolutePathBuf::try_from(system_path).expect("path")
+        }
+    );
+}
+
 #[cfg(target_os = "macos")]
 #[tokio::test]
 async fn managed_preferences_take_highest_precedence() {
@@ -135,6 +283,7 @@ flag = true
 
     let overrides = LoaderOverrides {
         managed_config_path: Some(managed_path),
+        system_config_path: None,
         managed_preferences_base64: Some(encoded),
     };
 
@@ -0,0 +1,39 @@
+use toml::Value as TomlValue;
+
+/// The value at the dotted `path` inside `value`. Segments index tables by
+/// key and arrays by position (`["mcp_servers", "docs", "args", "0"]`).
+pub(crate) fn toml_value_at<'a, S: AsRef<str>>(
+    value: &'a TomlValue,
+    path: &[S],
+) -> Option<&'a TomlValue> {
+    path.iter().try_fold(value, |value, key| {
+        let key = key.as_ref();
+        match value {
+            TomlValue::Array(items) => items.get(key.parse::<usize>().ok()?),
+            value => value.get(key),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn follows_tables_and_array_indices() {
+        let config: TomlValue =
+            toml::from_str("[mcp_servers.docs]\nargs = [\"serve\", \"--verbose\"]\n").unwrap();
+
+        assert_eq!(
+            toml_value_at(&config, &["mcp_servers", "docs", "args", "1"]),
+            Some(&TomlValue::String("--verbose".to_string()))
+        );
+        assert_eq!(
+            toml_value_at(&config, &["mcp_servers", "docs", "args", "x"]),
+            None
+        );
+        assert_eq!(toml_value_at(&config, &["model"]), None);
+        assert_eq!(toml_value_at::<&str>(&config, &[]), Some(&config));
+    }
+}
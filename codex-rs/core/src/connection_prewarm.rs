@@ -0,0 +1,177 @@
+//! Keeps a connection to the active provider open between turns
+//! (`features.connection_prewarm`).
+//!
+//! Model requests go through the provider's pooled client
+//! (`ModelProviderInfo::http_client`), whose pool keeps idle connections for
+//! [`POOL_IDLE_TIMEOUT`]. While the session is idle, a cheap `HEAD` request to
+//! the provider base URL every [`KEEPALIVE_INTERVAL`] resolves DNS and
+//! completes the TCP and TLS handshakes ahead of time, so the pool always
+//! holds a ready connection. When the user submits, the warm-up
+//! runs alongside context assembly instead of after it.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::default_client::POOL_IDLE_TIMEOUT;
+
+/// Comfortably below [`POOL_IDLE_TIMEOUT`] so the pooled connection never
+/// expires while the session is idle.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(45);
+/// Stop pinging after this much idle time; the next submit warms again.
+const IDLE_LIMIT: Duration = Duration::from_secs(10 * 60);
+/// Upper bound for a single warm-up request.
+const WARM_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionPrewarmer {
+    idle_loop: Mutex<Option<CancellationToken>>,
+    last_warmed: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ConnectionPrewarmer {
+    /// Warms `base_url` in `client`'s pool now and keeps it warm until the
+    /// next turn starts or the session has been idle for [`IDLE_LIMIT`].
+    pub(crate) fn keep_warm(&self, base_url: String, client: reqwest::Client) {
+        let token = CancellationToken::new();
+        if let Some(previous) = self.lock_idle_loop().replace(token.clone()) {
+            previous.cancel();
+        }
+        let last_warmed = Arc::clone(&self.last_warmed);
+        tokio::spawn(async move {
+            let deadline = Instant::now() + IDLE_LIMIT;
+            loop {
+                warm(&client, &base_url, &last_warmed).await;
+                if Instant::now() + KEEPALIVE_INTERVAL > deadline {
+                    break;
+                }
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = tokio::time::sleep(KEEPALIVE_INTERVAL) => {}
+                }
+            }
+        });
+    }
+
+    /// Stops the idle loop for the turn the user just submitted and, unless
+    /// the connection was refreshed recently, warms it while the turn's
+    /// context is assembled.
+    pub(crate) fn on_submit(&self, base_url: String, client: reqwest::Client) {
+        self.stop();
+        let fresh = self
+            .last_warmed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some_and(|at| at.elapsed() < KEEPALIVE_INTERVAL);
+        if fresh {
+            return;
+        }
+        let last_warmed = Arc::clone(&self.last_warmed);
+        tokio::spawn(async move { warm(&client, &base_url, &last_warmed).await });
+    }
+
+    pub(crate) fn stop(&self) {
+        if let Some(token) = self.lock_idle_loop().take() {
+            token.cancel();
+        }
+    }
+
+    fn lock_idle_loop(&self) -> std::sync::MutexGuard<'_, Option<CancellationToken>> {
+        self.idle_loop
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for ConnectionPrewarmer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Any response, even an error status, leaves an open connection in the
+/// pool, so only transport failures are worth reporting.
+async fn warm(client: &reqwest::Client, base_url: &str, last_warmed: &Mutex<Option<Instant>>) {
+    let started = Instant::now();
+    match client.head(base_url).timeout(WARM_TIMEOUT).send().await {
+        Ok(response) => {
+            *last_warmed.lock().unwrap_or_else(PoisonError::into_inner) = Some(Instant::now());
+            debug!(
+                base_url,
+                status = response.status().as_u16(),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "prewarmed provider connection"
+            );
+        }
+        Err(err) => debug!(base_url, %err, "failed to prewarm provider connection"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_client::shared_reqwest_client;
+    use core_test_support::skip_if_no_network;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+
+    async fn head_requests(server: &MockServer) -> usize {
+        server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.method.as_str() == "HEAD")
+            .count()
+    }
+
+    async fn wait_for_head_requests(server: &MockServer, expected: usize) {
+        for _ in 0..100 {
+            if head_requests(server).await >= expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("expected {expected} HEAD request(s)");
+    }
+
+    #[tokio::test]
+    async fn keep_warm_pings_and_submit_skips_a_fresh_connection() {
+        skip_if_no_network!();
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let prewarmer = ConnectionPrewarmer::default();
+
+        prewarmer.keep_warm(server.uri(), shared_reqwest_client());
+        for _ in 0..100 {
+            if prewarmer.last_warmed.lock().expect("lock").is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(head_requests(&server).await, 1);
+
+        // The connection was just warmed, so submitting does not ping again.
+        prewarmer.on_submit(server.uri(), shared_reqwest_client());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(head_requests(&server).await, 1);
+        assert!(prewarmer.lock_idle_loop().is_none());
+
+        // A stale connection is warmed again on submit.
+        *prewarmer.last_warmed.lock().expect("lock") = None;
+        prewarmer.on_submit(server.uri(), shared_reqwest_client());
+        wait_for_head_requests(&server, 2).await;
+    }
+}
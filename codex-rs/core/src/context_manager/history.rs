@@ -5,10 +5,13 @@ use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
+use crate::user_instructions::SteeringInstructions;
+use crate::user_instructions::UserInstructions;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::PromptTokenBreakdown;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use std::ops::Deref;
@@ -86,25 +89,36 @@ impl ContextManager {
                 .unwrap_or(i64::MAX);
 
         let items_tokens = self.items.iter().fold(0i64, |acc, item| {
-            acc + match item {
-                ResponseItem::GhostSnapshot { .. } => 0,
-                ResponseItem::Reasoning {
-                    encrypted_content: Some(content),
-                    ..
-                }
-                | ResponseItem::Compaction {
-                    encrypted_content: content,
-                } => estimate_reasoning_length(content.len()) as i64,
-                item => {
-                    let serialized = serde_json::to_string(item).unwrap_or_default();
-                    i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
-                }
-            }
+            acc.saturating_add(estimate_item_tokens(item))
         });
 
         Some(base_tokens.saturating_add(items_tokens))
     }
 
+    /// Splits the estimated prompt size between steering files, AGENTS.md
+    /// instructions, and the rest of the conversation history.
+    pub(crate) fn estimate_prompt_breakdown(&self) -> PromptTokenBreakdown {
+        let mut breakdown = PromptTokenBreakdown::default();
+        for item in &self.items {
+            let tokens = estimate_item_tokens(item);
+            let bucket = match item {
+                ResponseItem::Message { content, .. }
+                    if SteeringInstructions::is_steering_instructions(content) =>
+                {
+                    &mut breakdown.steering_tokens
+                }
+                ResponseItem::Message { content, .. }
+                    if UserInstructions::is_user_instructions(content) =>
+                {
+                    &mut breakdown.agents_md_tokens
+                }
+                _ => &mut breakdown.history_tokens,
+            };
+            *bucket = bucket.saturating_add(tokens);
+        }
+        breakdown
+    }
+
     pub(crate) fn remove_first_item(&mut self) {
         if !self.items.is_empty() {
             // Remove the oldest item (front of the list). Items are ordered from
@@ -121,6 +135,42 @@ impl ContextManager {
         self.items = items;
     }
 
+    /// Swaps the text output of `call_id` for `replacement`, provided it
+    /// still reads `expected`. Returns whether the output was replaced.
+    pub(crate) fn replace_tool_output(
+        &mut self,
+        call_id: &str,
+        expected: &str,
+        replacement: String,
+    ) -> bool {
+        for item in &mut self.items {
+            match item {
+                ResponseItem::FunctionCallOutput {
+                    call_id: id,
+                    output,
+                } if id == call_id => {
+                    if output.content_items.is_some() || output.content != expected {
+                        return false;
+                    }
+                    output.content = replacement;
+                    return true;
+                }
+                ResponseItem::CustomToolCallOutput {
+                    call_id: id,
+                    output,
+                } if id == call_id => {
+                    if output != expected {
+                        return false;
+                    }
+                    *output = replacement;
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
     pub(crate) fn replace_last_turn_images(&mut self, placeholder: &str) {
         let Some(last_item) = self.items.last_mut() else {
             return;
@@ -283,6 +333,23 @@ fn is_api_message(message: &ResponseItem) -> bool {
     }
 }
 
+fn estimate_item_tokens(item: &ResponseItem) -> i64 {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        ResponseItem::Reasoning {
+            encrypted_content: Some(content),
+            ..
+        }
+        | ResponseItem::Compaction {
+            encrypted_content: content,
+        } => estimate_reasoning_length(content.len()) as i64,
+        item => {
+            let serialized = serde_json::to_string(item).unwrap_or_default();
+            i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
+        }
+    }
+}
+
 fn estimate_reasoning_length(encoded_len: usize) -> usize {
     encoded_len
         .saturating_mul(3)
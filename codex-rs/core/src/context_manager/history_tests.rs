@@ -155,6 +155,36 @@ fn get_history_for_prompt_drops_ghost_commits() {
     assert_eq!(filtered, vec![]);
 }
 
+#[test]
+fn prompt_breakdown_attributes_injected_instructions() {
+    let agents_md: ResponseItem = UserInstructions {
+        directory: "/repo".to_string(),
+        text: "run the linter before committing".to_string(),
+    }
+    .into();
+    let steering: ResponseItem = SteeringInstructions {
+        text: "prefer small, focused functions".to_string(),
+    }
+    .into();
+    let user = user_msg("hello");
+    let assistant = assistant_msg("hi there");
+    let history = create_history_with_items(vec![
+        agents_md.clone(),
+        steering.clone(),
+        user.clone(),
+        assistant.clone(),
+    ]);
+
+    assert_eq!(
+        history.estimate_prompt_breakdown(),
+        PromptTokenBreakdown {
+            steering_tokens: estimate_item_tokens(&steering),
+            agents_md_tokens: estimate_item_tokens(&agents_md),
+            history_tokens: estimate_item_tokens(&user) + estimate_item_tokens(&assistant),
+        }
+    );
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -247,6 +277,52 @@ fn remove_first_item_handles_custom_tool_pair() {
     assert_eq!(h.contents(), vec![]);
 }
 
+#[test]
+fn replace_tool_output_only_swaps_unchanged_outputs() {
+    let items = vec![
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-1".to_string(),
+        },
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: "long output".to_string(),
+                ..Default::default()
+            },
+        },
+        ResponseItem::CustomToolCallOutput {
+            call_id: "call-2".to_string(),
+            output: "patch output".to_string(),
+        },
+    ];
+    let mut h = create_history_with_items(items);
+
+    assert!(!h.replace_tool_output("call-1", "stale output", "summary".to_string()));
+    assert!(h.replace_tool_output("call-1", "long output", "summary".to_string()));
+    assert!(h.replace_tool_output("call-2", "patch output", "patched".to_string()));
+    assert!(!h.replace_tool_output("call-3", "", "missing".to_string()));
+
+    assert_eq!(
+        h.contents()[1..],
+        [
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "summary".to_string(),
+                    ..Default::default()
+                },
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".to_string(),
+                output: "patched".to_string(),
+            },
+        ]
+    );
+}
+
 #[test]
 fn normalization_retains_local_shell_outputs() {
     let items = vec![
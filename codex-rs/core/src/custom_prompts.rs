@@ -1,3 +1,4 @@
+use crate::git_info::resolve_root_git_project_for_trust;
 use codex_protocol::custom_prompts::CustomPrompt;
 use std::collections::HashSet;
 use std::path::Path;
@@ -12,6 +13,46 @@ pub fn default_prompts_dir() -> Option<PathBuf> {
         .map(|home| home.join("prompts"))
 }
 
+/// Directory holding custom slash commands, under `$CODEX_HOME` and under a
+/// project's `.codex`.
+const COMMANDS_DIR_NAME: &str = "commands";
+
+const PROJECT_CONFIG_DIR_NAME: &str = ".codex";
+
+/// Discover custom slash commands for `cwd`, sorted by name: the
+/// `.codex/commands` directories from `cwd` up to the root of its git
+/// repository (only when `load_project_files`), then
+/// `$CODEX_HOME/commands`. The nearer file wins when two share a name.
+pub async fn discover_commands(
+    codex_home: &Path,
+    cwd: &Path,
+    load_project_files: bool,
+) -> Vec<CustomPrompt> {
+    let mut dirs = Vec::new();
+    if load_project_files {
+        let cwd = dunce::canonicalize(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+        let repo_root = resolve_root_git_project_for_trust(&cwd)
+            .map(|root| dunce::canonicalize(&root).unwrap_or(root));
+        for dir in cwd.ancestors() {
+            dirs.push(dir.join(PROJECT_CONFIG_DIR_NAME).join(COMMANDS_DIR_NAME));
+            if repo_root.as_deref().is_none_or(|root| dir == root) {
+                break;
+            }
+        }
+    }
+    dirs.push(codex_home.join(COMMANDS_DIR_NAME));
+
+    let mut out: Vec<CustomPrompt> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for dir in dirs {
+        let found = discover_prompts_in_excluding(&dir, &seen).await;
+        seen.extend(found.iter().map(|command| command.name.clone()));
+        out.extend(found);
+    }
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
 /// Discover prompt files in the given directory, returning entries sorted by name.
 /// Non-files are ignored. If the directory does not exist or cannot be read, returns empty.
 pub async fn discover_prompts_in(dir: &Path) -> Vec<CustomPrompt> {
@@ -150,6 +191,35 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn project_commands_shadow_global_ones() {
+        let tmp = tempdir().expect("create TempDir");
+        let home = tmp.path().join("home");
+        let project = tmp.path().join("project");
+        fs::create_dir_all(home.join("commands")).unwrap();
+        fs::create_dir_all(project.join(".codex/commands")).unwrap();
+        fs::write(home.join("commands/release-notes.md"), b"global").unwrap();
+        fs::write(home.join("commands/triage.md"), b"triage $1").unwrap();
+        fs::write(project.join(".codex/commands/release-notes.md"), b"project").unwrap();
+
+        let found = discover_commands(&home, &project, true).await;
+        let commands: Vec<(String, String)> = found
+            .into_iter()
+            .map(|command| (command.name, command.content))
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                ("release-notes".to_string(), "project".to_string()),
+                ("triage".to_string(), "triage $1".to_string()),
+            ]
+        );
+
+        // Untrusted projects only get the global commands.
+        let found = discover_commands(&home, &project, false).await;
+        assert_eq!(found[0].content, "global");
+    }
+
     #[tokio::test]
     async fn empty_when_dir_missing() {
         let tmp = tempdir().expect("create TempDir");
@@ -1,10 +1,16 @@
+use crate::config::types::NetworkConfig;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
 use reqwest::header::HeaderValue;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
 
 /// Set this to add a suffix to the User-Agent string.
 ///
@@ -23,6 +29,11 @@ use std::sync::OnceLock;
 /// Parenthesis will be added by Codex. This should only specify what goes inside of the parenthesis.
 pub static USER_AGENT_SUFFIX: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
 pub const DEFAULT_ORIGINATOR: &str = "codex_cli_rs";
+/// How long an idle pooled connection is kept open before it is closed.
+pub const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// TCP keepalive for pooled connections, so middleboxes do not silently drop
+/// them between turns.
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
 pub const CODEX_INTERNAL_ORIGINATOR_OVERRIDE_ENV_VAR: &str = "CODEX_INTERNAL_ORIGINATOR_OVERRIDE";
 
 #[derive(Debug, Clone)]
@@ -31,6 +42,14 @@ pub struct Originator {
     pub header_value: HeaderValue,
 }
 static ORIGINATOR: OnceLock<Originator> = OnceLock::new();
+/// The `[network]` settings of the most recently loaded config.
+static NETWORK_CONFIG: LazyLock<RwLock<NetworkConfig>> =
+    LazyLock::new(|| RwLock::new(NetworkConfig::default()));
+/// The pooled clients behind [`shared_reqwest_client_for`], one per network
+/// configuration (providers may override the proxy), each tagged with the
+/// User-Agent it was built with.
+static SHARED_CLIENTS: LazyLock<Mutex<HashMap<NetworkConfig, (String, reqwest::Client)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 #[derive(Debug)]
 pub enum SetOriginatorError {
@@ -134,19 +153,116 @@ pub fn create_client() -> CodexHttpClient {
 }
 
 pub fn build_reqwest_client() -> reqwest::Client {
+    build_reqwest_client_with_user_agent(get_codex_user_agent(), &network_config())
+}
+
+/// Sets the proxy and CA settings used by every client built afterwards.
+/// Each binary calls this once at startup with the `network` settings of the
+/// config it loaded; loading a [`crate::config::Config`] does not.
+pub fn set_network_config(config: NetworkConfig) {
+    *NETWORK_CONFIG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = config;
+}
+
+pub fn network_config() -> NetworkConfig {
+    NETWORK_CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Returns a process-wide client whose connection pool is shared by every
+/// caller, so consecutive model requests reuse an open connection instead of
+/// repeating DNS, TCP, and TLS setup.
+pub fn shared_reqwest_client() -> reqwest::Client {
+    shared_reqwest_client_for(&network_config())
+}
+
+/// Like [`shared_reqwest_client`], for callers that override the network
+/// settings, such as a provider with its own proxy. The client is rebuilt if
+/// the User-Agent changes.
+pub fn shared_reqwest_client_for(network: &NetworkConfig) -> reqwest::Client {
+    let ua = get_codex_user_agent();
+    let mut shared = SHARED_CLIENTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some((shared_ua, client)) = shared.get(network)
+        && *shared_ua == ua
+    {
+        return client.clone();
+    }
+    let client = build_reqwest_client_with_user_agent(ua.clone(), network);
+    shared.insert(network.clone(), (ua, client.clone()));
+    client
+}
+
+/// Applies the proxy, `no_proxy` list, and extra root certificates from
+/// `network` to `builder`. Settings that cannot be used are logged and
+/// skipped, so a bad proxy URL fails the request rather than startup.
+pub fn apply_network_config(
+    mut builder: reqwest::ClientBuilder,
+    network: &NetworkConfig,
+) -> reqwest::ClientBuilder {
+    match network.proxy.as_deref() {
+        Some("") => builder = builder.no_proxy(),
+        Some(url) => match build_proxy(url, network) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(err) => tracing::warn!("ignoring network.proxy: {err}"),
+        },
+        None => {}
+    }
+    if let Some(path) = &network.ca_cert_path {
+        match load_ca_certs(path) {
+            Ok(certs) => {
+                for cert in certs {
+                    builder = builder.add_root_certificate(cert);
+                }
+            }
+            Err(err) => tracing::warn!("ignoring network.ca_cert_path: {err}"),
+        }
+    }
+    builder
+}
+
+fn build_proxy(url: &str, network: &NetworkConfig) -> Result<reqwest::Proxy, String> {
+    let mut proxy =
+        reqwest::Proxy::all(url).map_err(|err| format!("invalid proxy {url}: {err}"))?;
+    if !network.no_proxy.is_empty() {
+        proxy = proxy.no_proxy(reqwest::NoProxy::from_string(&network.no_proxy.join(",")));
+    }
+    if let Some(var) = network.proxy_auth_env.as_deref() {
+        let credentials =
+            std::env::var(var).map_err(|_| format!("environment variable {var} is not set"))?;
+        let (username, password) = credentials
+            .split_once(':')
+            .unwrap_or((credentials.as_str(), ""));
+        proxy = proxy.basic_auth(username, password);
+    }
+    Ok(proxy)
+}
+
+fn load_ca_certs(path: &Path) -> Result<Vec<reqwest::Certificate>, String> {
+    let pem = std::fs::read(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn build_reqwest_client_with_user_agent(ua: String, network: &NetworkConfig) -> reqwest::Client {
     use reqwest::header::HeaderMap;
 
     let mut headers = HeaderMap::new();
     headers.insert("originator", originator().header_value.clone());
-    let ua = get_codex_user_agent();
 
     let mut builder = reqwest::Client::builder()
         // Set UA via dedicated helper to avoid header validation pitfalls
         .user_agent(ua)
-        .default_headers(headers);
+        .default_headers(headers)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE);
     if is_sandboxed() {
         builder = builder.no_proxy();
     }
+    builder = apply_network_config(builder, network);
 
     builder.build().unwrap_or_else(|_| reqwest::Client::new())
 }
@@ -216,6 +332,58 @@ mod tests {
         assert_eq!(ua_header.to_str().unwrap(), expected_ua);
     }
 
+    #[tokio::test]
+    async fn network_proxy_receives_requests_with_credentials() {
+        skip_if_no_network!();
+
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("host", "models.example.invalid"))
+            .and(header("proxy-authorization", "Basic dXNlcjpzZWNyZXQ="))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+        let network = NetworkConfig {
+            proxy: Some(proxy.uri().replace("http://", "http://user:secret@")),
+            ..NetworkConfig::default()
+        };
+
+        let client = build_reqwest_client_with_user_agent("test".to_string(), &network);
+        let resp = client
+            .get("http://models.example.invalid/v1/models")
+            .send()
+            .await
+            .expect("request through proxy");
+
+        assert_eq!(resp.status().as_u16(), 204);
+    }
+
+    #[test]
+    fn provider_proxy_override_replaces_only_the_proxy() {
+        let network = NetworkConfig {
+            proxy: Some("http://proxy.example.com:3128".to_string()),
+            no_proxy: vec!["localhost".to_string()],
+            ..NetworkConfig::default()
+        };
+
+        assert_eq!(network.with_proxy_override(None), network);
+        assert_eq!(
+            network.with_proxy_override(Some("")),
+            NetworkConfig {
+                proxy: Some(String::new()),
+                no_proxy: vec!["localhost".to_string()],
+                ..NetworkConfig::default()
+            }
+        );
+    }
+
     #[test]
     fn test_invalid_suffix_is_sanitized() {
         let prefix = "codex_cli_rs/0.0.0";
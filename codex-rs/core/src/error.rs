@@ -1,4 +1,6 @@
 use crate::exec::ExecToolCallOutput;
+use crate::secrets::SecretCmdError;
+use crate::secrets::redact;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use crate::truncate::TruncationPolicy;
@@ -173,6 +175,9 @@ pub enum CodexErr {
 
     #[error("{0}")]
     EnvVar(EnvVarError),
+
+    #[error(transparent)]
+    SecretCmd(#[from] SecretCmdError),
 }
 
 impl From<CancelErr> for CodexErr {
@@ -464,7 +469,7 @@ impl CodexErr {
     }
 
     pub fn to_error_event(&self, message_prefix: Option<String>) -> ErrorEvent {
-        let error_message = self.to_string();
+        let error_message = redact(&self.to_string()).into_owned();
         let message: String = match message_prefix {
             Some(prefix) => format!("{prefix}: {error_message}"),
             None => error_message,
@@ -518,7 +523,7 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
     };
 
     truncate_text(
-        &message,
+        &redact(&message),
         TruncationPolicy::Bytes(ERROR_MESSAGE_UI_MAX_BYTES),
     )
 }
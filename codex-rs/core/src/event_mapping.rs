@@ -14,6 +14,7 @@ use tracing::warn;
 use uuid::Uuid;
 
 use crate::user_instructions::SkillInstructions;
+use crate::user_instructions::SteeringInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_shell_command::is_user_shell_command_text;
 
@@ -26,6 +27,7 @@ fn is_session_prefix(text: &str) -> bool {
 fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
     if UserInstructions::is_user_instructions(message)
         || SkillInstructions::is_skill_instructions(message)
+        || SteeringInstructions::is_steering_instructions(message)
     {
         return None;
     }
@@ -51,6 +53,9 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                 }
                 warn!("Output text in user message: {}", text);
             }
+            ContentItem::Refusal { refusal } => {
+                warn!("Refusal in user message: {}", refusal);
+            }
         }
     }
 
@@ -64,6 +69,11 @@ fn parse_agent_message(id: Option<&String>, message: &[ContentItem]) -> AgentMes
             ContentItem::OutputText { text } => {
                 content.push(AgentMessageContent::Text { text: text.clone() });
             }
+            ContentItem::Refusal { refusal } => {
+                content.push(AgentMessageContent::Refusal {
+                    refusal: refusal.clone(),
+                });
+            }
             _ => {
                 warn!(
                     "Unexpected content item in agent message: {:?}",
@@ -216,6 +226,14 @@ mod tests {
                     text: "<user_shell_command>echo 42</user_shell_command>".to_string(),
                 }],
             },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "<steering_instructions>\nbe terse\n</steering_instructions>"
+                        .to_string(),
+                }],
+            },
         ];
 
         for item in items {
@@ -247,6 +265,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_agent_refusal() {
+        let item = ResponseItem::Message {
+            id: Some("msg-1".to_string()),
+            role: "assistant".to_string(),
+            content: vec![ContentItem::Refusal {
+                refusal: "I can't help with that.".to_string(),
+            }],
+        };
+
+        let turn_item = parse_turn_item(&item).expect("expected agent message turn item");
+
+        match turn_item {
+            TurnItem::AgentMessage(message) => {
+                let Some(AgentMessageContent::Refusal { refusal }) = message.content.first() else {
+                    panic!("expected agent message refusal content");
+                };
+                assert_eq!(refusal, "I can't help with that.");
+            }
+            other => panic!("expected TurnItem::AgentMessage, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parses_reasoning_summary_and_raw_content() {
         let item = ResponseItem::Reasoning {
@@ -118,6 +118,9 @@ pub enum SandboxType {
 
     /// Only available on Windows.
     WindowsRestrictedToken,
+
+    /// A Docker or Podman container, selected with `sandbox.backend`.
+    Container,
 }
 
 #[derive(Clone)]
@@ -175,6 +178,8 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            None,
+            None,
         )
         .map_err(CodexErr::from)?;
 
@@ -386,6 +391,9 @@ pub(crate) mod errors {
                 SandboxTransformError::SeatbeltUnavailable => CodexErr::UnsupportedOperation(
                     "seatbelt sandbox is only available on macOS".to_string(),
                 ),
+                SandboxTransformError::MissingContainerSandbox => CodexErr::UnsupportedOperation(
+                    "container sandbox requested without `sandbox.backend`".to_string(),
+                ),
             }
         }
     }
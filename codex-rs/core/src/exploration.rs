@@ -0,0 +1,140 @@
+//! Read-only exploration phase at the start of an autonomous run.
+//!
+//! When `autonomy.exploration_budget` is set and the session never asks for
+//! approval, the first model requests only see read-only tools and any
+//! mutating call is refused. Once the budget is spent the full toolset is
+//! offered again and the model is told it may start editing.
+
+use crate::config::types::ExplorationBudget;
+
+/// Returned to the model when it tries a mutating tool call while exploring.
+pub(crate) const EXPLORATION_REJECTION: &str = "This run is still in its read-only exploration phase. Inspect the code with read-only commands first; editing tools become available once the exploration budget is spent.";
+
+pub(crate) const EXPLORATION_STARTED_MESSAGE: &str = "This run starts with a read-only exploration phase. Use it to read the relevant code, tests, and docs and to form a plan; file edits and other mutating commands are refused until the phase ends, and you will be told when it does.";
+
+pub(crate) const EXPLORATION_ENDED_MESSAGE: &str = "The read-only exploration phase is over. Editing tools are now available; apply the plan you formed while exploring.";
+
+/// Where a model request falls relative to the exploration phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExplorationStatus {
+    /// No exploration phase is configured, or it ended earlier.
+    Inactive,
+    /// First request of the phase.
+    Started,
+    Exploring,
+    /// The budget ran out before this request, which gets the full toolset.
+    Ended,
+}
+
+impl ExplorationStatus {
+    pub(crate) fn is_read_only(self) -> bool {
+        matches!(self, Self::Started | Self::Exploring)
+    }
+}
+
+/// How much of the exploration budget the session has used.
+#[derive(Debug, Default)]
+pub(crate) struct ExplorationState {
+    turns_used: u32,
+    finished: bool,
+}
+
+impl ExplorationState {
+    /// Accounts for the next model request. `tokens_used` is the session's
+    /// cumulative token usage so far.
+    pub(crate) fn next_request(
+        &mut self,
+        budget: Option<ExplorationBudget>,
+        autonomous: bool,
+        tokens_used: i64,
+    ) -> ExplorationStatus {
+        let Some(budget) = budget.filter(|_| !self.finished) else {
+            return ExplorationStatus::Inactive;
+        };
+        // The phase only opens on the first request of the session, and
+        // closes for good once the budget is spent or approvals are turned on.
+        if !autonomous || budget.is_exhausted(self.turns_used, tokens_used) {
+            self.finished = true;
+            return if self.turns_used == 0 {
+                ExplorationStatus::Inactive
+            } else {
+                ExplorationStatus::Ended
+            };
+        }
+        self.turns_used += 1;
+        if self.turns_used == 1 {
+            ExplorationStatus::Started
+        } else {
+            ExplorationStatus::Exploring
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn phase_ends_after_turn_budget() {
+        let budget = Some(ExplorationBudget {
+            turns: Some(2),
+            tokens: None,
+        });
+        let mut state = ExplorationState::default();
+
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Started
+        );
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Exploring
+        );
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Ended
+        );
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Inactive
+        );
+    }
+
+    #[test]
+    fn phase_ends_when_token_budget_is_spent() {
+        let budget = Some(ExplorationBudget {
+            turns: None,
+            tokens: Some(1_000),
+        });
+        let mut state = ExplorationState::default();
+
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Started
+        );
+        assert_eq!(
+            state.next_request(budget, true, 1_500),
+            ExplorationStatus::Ended
+        );
+    }
+
+    #[test]
+    fn interactive_sessions_never_explore() {
+        let budget = Some(ExplorationBudget {
+            turns: Some(2),
+            tokens: None,
+        });
+        let mut state = ExplorationState::default();
+
+        assert_eq!(
+            state.next_request(budget, false, 0),
+            ExplorationStatus::Inactive
+        );
+        // Turning approvals off later does not open the phase mid-session.
+        assert_eq!(
+            state.next_request(budget, true, 0),
+            ExplorationStatus::Inactive
+        );
+    }
+}
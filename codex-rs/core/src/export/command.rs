@@ -0,0 +1,111 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+use super::ExportError;
+use super::JsonExporter;
+use super::Transcript;
+use super::TranscriptExporter;
+
+/// An exporter declared under `[exporters.<name>]`. The command receives the
+/// `json` export on stdin, and whatever it writes to stdout is the artifact.
+#[derive(Debug, Clone)]
+pub struct CommandExporter {
+    command: Vec<String>,
+}
+
+impl CommandExporter {
+    pub fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+
+    fn display(&self) -> String {
+        shlex::try_join(self.command.iter().map(String::as_str))
+            .unwrap_or_else(|_| self.command.join(" "))
+    }
+}
+
+impl TranscriptExporter for CommandExporter {
+    fn export(&self, transcript: &Transcript) -> Result<Vec<u8>, ExportError> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(ExportError::EmptyCommand);
+        };
+        let input = JsonExporter.export(transcript)?;
+        let spawn_error = |source| ExportError::Spawn {
+            command: self.display(),
+            source,
+        };
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(spawn_error)?;
+
+        // Write from another thread so a command that streams output before
+        // reading all of its input cannot deadlock against us.
+        let mut stdin = child.stdin.take();
+        let writer = std::thread::spawn(move || {
+            if let Some(stdin) = stdin.as_mut() {
+                // A command that ignores its input closes the pipe early;
+                // its exit status decides whether the export failed.
+                let _ = stdin.write_all(&input);
+            }
+        });
+        let output = child.wait_with_output().map_err(spawn_error)?;
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(ExportError::Failed {
+                command: self.display(),
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::export::tests::sample_transcript;
+    use pretty_assertions::assert_eq;
+
+    fn sh(script: &str) -> CommandExporter {
+        CommandExporter::new(vec!["sh".to_string(), "-c".to_string(), script.to_string()])
+    }
+
+    #[test]
+    fn pipes_the_json_transcript_through_the_command() {
+        let rendered = sh("wc -c").export(&sample_transcript()).expect("export");
+        let expected_len = JsonExporter
+            .export(&sample_transcript())
+            .expect("json")
+            .len();
+
+        assert_eq!(
+            String::from_utf8(rendered).expect("utf8").trim(),
+            expected_len.to_string()
+        );
+    }
+
+    #[test]
+    fn failing_commands_report_stderr() {
+        let err = sh("echo 'no template' >&2; exit 3")
+            .export(&sample_transcript())
+            .expect_err("command fails");
+
+        assert!(matches!(err, ExportError::Failed { ref stderr, .. } if stderr == "no template"));
+    }
+
+    #[test]
+    fn empty_commands_are_rejected() {
+        let err = CommandExporter::new(Vec::new())
+            .export(&sample_transcript())
+            .expect_err("empty command");
+        assert!(matches!(err, ExportError::EmptyCommand));
+    }
+}
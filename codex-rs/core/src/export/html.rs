@@ -0,0 +1,141 @@
+use super::ExportError;
+use super::Transcript;
+use super::TranscriptEntry;
+use super::TranscriptExporter;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+.user,.agent{white-space:pre-wrap;padding:.75rem 1rem;border-radius:6px;margin:1rem 0}\
+.user{background:#eef3fb}.agent{background:#f4f4f4}\
+pre{background:#1e1e1e;color:#ddd;padding:.75rem;overflow-x:auto;border-radius:6px}\
+.reasoning{color:#555;font-style:italic;white-space:pre-wrap}\
+.add{color:#8fd18f}.del{color:#f29b9b}";
+
+/// The `html` format: a standalone page with no external assets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlExporter;
+
+impl TranscriptExporter for HtmlExporter {
+    fn export(&self, transcript: &Transcript) -> Result<Vec<u8>, ExportError> {
+        let title = match &transcript.session {
+            Some(meta) => format!("Codex session {}", meta.id),
+            None => "Codex session".to_string(),
+        };
+
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", escape(&title)));
+        out.push_str(&format!("<style>{STYLE}</style>\n</head>\n<body>\n"));
+        out.push_str(&format!("<h1>{}</h1>\n", escape(&title)));
+        if let Some(meta) = transcript
+            .session
+            .as_ref()
+            .filter(|meta| !meta.cwd.as_os_str().is_empty())
+        {
+            out.push_str(&format!(
+                "<p>Started {} in <code>{}</code></p>\n",
+                escape(&meta.timestamp),
+                escape(&meta.cwd.display().to_string())
+            ));
+        }
+
+        for entry in &transcript.entries {
+            match entry {
+                TranscriptEntry::UserMessage { text } => {
+                    out.push_str(&format!(
+                        "<section class=\"user\"><h2>User</h2>{}</section>\n",
+                        escape(text.trim_end())
+                    ));
+                }
+                TranscriptEntry::AgentMessage { text } => {
+                    out.push_str(&format!(
+                        "<section class=\"agent\"><h2>Codex</h2>{}</section>\n",
+                        escape(text.trim_end())
+                    ));
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    out.push_str(&format!(
+                        "<details class=\"reasoning\"><summary>Reasoning</summary>{}</details>\n",
+                        escape(text.trim_end())
+                    ));
+                }
+                TranscriptEntry::Patch { patch, .. } => {
+                    out.push_str(&format!(
+                        "<details class=\"patch\" open><summary>Patch</summary><pre>{}</pre></details>\n",
+                        diff_lines(patch.trim_end())
+                    ));
+                }
+                TranscriptEntry::ToolCall { name, input, .. } => {
+                    out.push_str(&format!(
+                        "<details class=\"tool-call\"><summary>Tool call: <code>{}</code></summary><pre>{}</pre></details>\n",
+                        escape(name),
+                        escape(input.trim_end())
+                    ));
+                }
+                TranscriptEntry::ToolOutput { output, .. } => {
+                    out.push_str(&format!(
+                        "<details class=\"tool-output\"><summary>Output</summary><pre>{}</pre></details>\n",
+                        escape(output.trim_end())
+                    ));
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        Ok(out.into_bytes())
+    }
+}
+
+/// Escapes `patch`, coloring added and removed lines.
+fn diff_lines(patch: &str) -> String {
+    patch
+        .lines()
+        .map(|line| {
+            let class = if line.starts_with('+') {
+                Some("add")
+            } else if line.starts_with('-') {
+                Some("del")
+            } else {
+                None
+            };
+            match class {
+                Some(class) => format!("<span class=\"{class}\">{}</span>", escape(line)),
+                None => escape(line),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::sample_transcript;
+
+    #[test]
+    fn escapes_message_and_tool_content() {
+        let rendered =
+            String::from_utf8(HtmlExporter.export(&sample_transcript()).expect("export"))
+                .expect("utf8");
+
+        assert!(rendered.starts_with("<!DOCTYPE html>"));
+        assert!(rendered.contains(
+            "<section class=\"agent\"><h2>Codex</h2>There is a `Cargo.toml` and &lt;src&gt;.</section>"
+        ));
+        assert!(rendered.contains("<pre>{&quot;command&quot;:[&quot;ls&quot;]}</pre>"));
+        assert!(!rendered.contains("<src>"));
+    }
+}
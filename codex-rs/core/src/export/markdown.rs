@@ -0,0 +1,131 @@
+use super::ExportError;
+use super::Transcript;
+use super::TranscriptEntry;
+use super::TranscriptExporter;
+
+/// The `markdown` format: one section per message, with tool calls and
+/// their output in code fences.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownExporter;
+
+impl TranscriptExporter for MarkdownExporter {
+    fn export(&self, transcript: &Transcript) -> Result<Vec<u8>, ExportError> {
+        let mut out = String::new();
+        match &transcript.session {
+            Some(meta) => {
+                out.push_str(&format!("# Codex session {}\n\n", meta.id));
+                out.push_str(&format!("- Started: {}\n", meta.timestamp));
+                if !meta.cwd.as_os_str().is_empty() {
+                    out.push_str(&format!("- Directory: `{}`\n", meta.cwd.display()));
+                }
+            }
+            None => out.push_str("# Codex session\n"),
+        }
+
+        for entry in &transcript.entries {
+            out.push('\n');
+            match entry {
+                TranscriptEntry::UserMessage { text } => {
+                    out.push_str(&format!("## User\n\n{}\n", text.trim_end()));
+                }
+                TranscriptEntry::AgentMessage { text } => {
+                    out.push_str(&format!("## Codex\n\n{}\n", text.trim_end()));
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    out.push_str("**Reasoning:**\n\n");
+                    for line in text.trim_end().lines() {
+                        if line.is_empty() {
+                            out.push_str(">\n");
+                        } else {
+                            out.push_str(&format!("> {line}\n"));
+                        }
+                    }
+                }
+                TranscriptEntry::Patch { patch, .. } => {
+                    out.push_str("**Patch:**\n\n");
+                    push_fenced_with_info(&mut out, patch, "diff");
+                }
+                TranscriptEntry::ToolCall { name, input, .. } => {
+                    out.push_str(&format!("**Tool call:** `{name}`\n\n"));
+                    push_fenced(&mut out, input);
+                }
+                TranscriptEntry::ToolOutput { output, .. } => {
+                    out.push_str("**Output:**\n\n");
+                    push_fenced(&mut out, output);
+                }
+            }
+        }
+        Ok(out.into_bytes())
+    }
+}
+
+/// Wraps `text` in a fence longer than any backtick run inside it, so tool
+/// output that contains Markdown cannot close the block early.
+fn push_fenced(out: &mut String, text: &str) {
+    push_fenced_with_info(out, text, "");
+}
+
+/// [`push_fenced`] with an info string (the language) on the opening fence.
+fn push_fenced_with_info(out: &mut String, text: &str, info: &str) {
+    let longest_run = text
+        .split(|c: char| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    out.push_str(&format!("{fence}{info}\n{}\n{fence}\n", text.trim_end()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::sample_transcript;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_messages_and_fenced_tool_calls() {
+        let rendered = MarkdownExporter
+            .export(&sample_transcript())
+            .expect("export");
+
+        assert_eq!(
+            String::from_utf8(rendered).expect("utf8"),
+            "# Codex session\n\
+             \n## User\n\nList the files\n\
+             \n**Tool call:** `shell`\n\n```\n{\"command\":[\"ls\"]}\n```\n\
+             \n**Output:**\n\n```\nCargo.toml\nsrc\n```\n\
+             \n## Codex\n\nThere is a `Cargo.toml` and <src>.\n"
+        );
+    }
+
+    #[test]
+    fn renders_reasoning_as_a_quote_and_patches_as_diffs() {
+        let transcript = Transcript {
+            session: None,
+            entries: vec![
+                TranscriptEntry::Reasoning {
+                    text: "Check the tests.\n\nThen fix them.".to_string(),
+                },
+                TranscriptEntry::Patch {
+                    call_id: None,
+                    patch: "*** Begin Patch\n+new line\n*** End Patch".to_string(),
+                },
+            ],
+        };
+        let rendered = MarkdownExporter.export(&transcript).expect("export");
+
+        assert_eq!(
+            String::from_utf8(rendered).expect("utf8"),
+            "# Codex session\n\
+             \n**Reasoning:**\n\n> Check the tests.\n>\n> Then fix them.\n\
+             \n**Patch:**\n\n```diff\n*** Begin Patch\n+new line\n*** End Patch\n```\n"
+        );
+    }
+
+    #[test]
+    fn fences_outgrow_backticks_in_the_content() {
+        let mut out = String::new();
+        push_fenced(&mut out, "````rust\nfn main() {}\n````");
+        assert_eq!(out, "`````\n````rust\nfn main() {}\n````\n`````\n");
+    }
+}
@@ -0,0 +1,341 @@
+//! Rendering recorded sessions as shareable transcripts.
+//!
+//! `codex export` loads a rollout file into a [`Transcript`] and hands it to a
+//! [`TranscriptExporter`]. Markdown, HTML, and JSON are built in. Other
+//! formats are declared in config.toml as `[exporters.<name>]` commands that
+//! read the JSON export on stdin and write the rendered artifact to stdout.
+//! [`Transcript::redacted`] strips paths and environment details before
+//! sharing.
+
+mod command;
+mod html;
+mod markdown;
+mod redact;
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::process::ExitStatus;
+
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionMeta;
+use serde::Serialize;
+
+pub use command::CommandExporter;
+pub use html::HtmlExporter;
+pub use markdown::MarkdownExporter;
+
+use crate::config::types::ExporterConfig;
+use crate::rollout::RolloutRecorder;
+
+/// Formats that are always available, in the order `--help` lists them.
+pub const BUILTIN_FORMATS: &[&str] = &["markdown", "html", "json"];
+
+/// Renders a transcript into some artifact, such as a document or a file
+/// another tool imports.
+pub trait TranscriptExporter {
+    fn export(&self, transcript: &Transcript) -> Result<Vec<u8>, ExportError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("failed to serialize transcript: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("exporter command is empty")]
+    EmptyCommand,
+
+    #[error("failed to run exporter `{command}`: {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("exporter `{command}` exited with {status}: {stderr}")]
+    Failed {
+        command: String,
+        status: ExitStatus,
+        stderr: String,
+    },
+}
+
+/// The conversation in a session, reduced to what a reader cares about.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Transcript {
+    pub session: Option<SessionMeta>,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    UserMessage {
+        text: String,
+    },
+    AgentMessage {
+        text: String,
+    },
+    /// A reasoning summary the model shared before acting.
+    Reasoning {
+        text: String,
+    },
+    /// An `apply_patch` call; `patch` is the patch text.
+    Patch {
+        call_id: Option<String>,
+        patch: String,
+    },
+    ToolCall {
+        call_id: Option<String>,
+        name: String,
+        input: String,
+    },
+    ToolOutput {
+        call_id: String,
+        output: String,
+    },
+}
+
+impl Transcript {
+    /// Builds a transcript from rollout items in file order. Messages come
+    /// from the user and agent message events, so injected context such as
+    /// `AGENTS.md` does not show up as something the user typed.
+    pub fn from_rollout_items(items: &[RolloutItem]) -> Self {
+        let mut transcript = Transcript::default();
+        for item in items {
+            let entry = match item {
+                RolloutItem::SessionMeta(line) => {
+                    if transcript.session.is_none() {
+                        transcript.session = Some(line.meta.clone());
+                    }
+                    continue;
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                    TranscriptEntry::UserMessage {
+                        text: event.message.clone(),
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                    TranscriptEntry::AgentMessage {
+                        text: event.message.clone(),
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::AgentReasoning(event)) => {
+                    TranscriptEntry::Reasoning {
+                        text: event.text.clone(),
+                    }
+                }
+                RolloutItem::ResponseItem(item) => match tool_entry(item) {
+                    Some(entry) => entry,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            transcript.entries.push(entry);
+        }
+        transcript
+    }
+}
+
+/// The tool name apply_patch calls are recorded under.
+const APPLY_PATCH_TOOL: &str = "apply_patch";
+
+fn tool_entry(item: &ResponseItem) -> Option<TranscriptEntry> {
+    let entry = match item {
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } if name == APPLY_PATCH_TOOL => {
+            // The function form wraps the patch as `{"input": "..."}`.
+            let patch = serde_json::from_str::<serde_json::Value>(arguments)
+                .ok()
+                .and_then(|args| args.get("input")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| arguments.clone());
+            TranscriptEntry::Patch {
+                call_id: Some(call_id.clone()),
+                patch,
+            }
+        }
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } if name == APPLY_PATCH_TOOL => TranscriptEntry::Patch {
+            call_id: Some(call_id.clone()),
+            patch: input.clone(),
+        },
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => TranscriptEntry::ToolCall {
+            call_id: Some(call_id.clone()),
+            name: name.clone(),
+            input: arguments.clone(),
+        },
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => TranscriptEntry::ToolCall {
+            call_id: Some(call_id.clone()),
+            name: name.clone(),
+            input: input.clone(),
+        },
+        ResponseItem::LocalShellCall {
+            call_id,
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => TranscriptEntry::ToolCall {
+            call_id: call_id.clone(),
+            name: "local_shell".to_string(),
+            input: shlex::try_join(exec.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| exec.command.join(" ")),
+        },
+        ResponseItem::FunctionCallOutput { call_id, output } => TranscriptEntry::ToolOutput {
+            call_id: call_id.clone(),
+            output: output.content.clone(),
+        },
+        ResponseItem::CustomToolCallOutput { call_id, output } => TranscriptEntry::ToolOutput {
+            call_id: call_id.clone(),
+            output: output.clone(),
+        },
+        _ => return None,
+    };
+    Some(entry)
+}
+
+/// Reads the rollout file at `path` into a transcript.
+pub async fn load_transcript(path: &Path) -> io::Result<Transcript> {
+    let history = RolloutRecorder::get_rollout_history(path).await?;
+    Ok(Transcript::from_rollout_items(&history.get_rollout_items()))
+}
+
+/// The `json` format. It is also what command exporters receive on stdin.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonExporter;
+
+impl TranscriptExporter for JsonExporter {
+    fn export(&self, transcript: &Transcript) -> Result<Vec<u8>, ExportError> {
+        let mut rendered = serde_json::to_vec_pretty(transcript)?;
+        rendered.push(b'\n');
+        Ok(rendered)
+    }
+}
+
+/// Resolves `format` to an exporter. Commands declared under
+/// `[exporters.<name>]` take precedence, so a team can replace a built-in
+/// format with its own renderer.
+pub fn exporter_for(
+    format: &str,
+    exporters: &HashMap<String, ExporterConfig>,
+) -> Option<Box<dyn TranscriptExporter>> {
+    if let Some(config) = exporters.get(format) {
+        return Some(Box::new(CommandExporter::new(config.command.clone())));
+    }
+    match format {
+        "markdown" | "md" => Some(Box::new(MarkdownExporter)),
+        "html" => Some(Box::new(HtmlExporter)),
+        "json" => Some(Box::new(JsonExporter)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    pub(super) fn sample_transcript() -> Transcript {
+        Transcript {
+            session: None,
+            entries: vec![
+                TranscriptEntry::UserMessage {
+                    text: "List the files".to_string(),
+                },
+                TranscriptEntry::ToolCall {
+                    call_id: Some("call-1".to_string()),
+                    name: "shell".to_string(),
+                    input: r#"{"command":["ls"]}"#.to_string(),
+                },
+                TranscriptEntry::ToolOutput {
+                    call_id: "call-1".to_string(),
+                    output: "Cargo.toml\nsrc".to_string(),
+                },
+                TranscriptEntry::AgentMessage {
+                    text: "There is a `Cargo.toml` and <src>.".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn transcript_keeps_conversation_and_tool_calls_in_order() {
+        let items = vec![
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "<user_instructions>be terse</user_instructions>".to_string(),
+                }],
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "List the files".to_string(),
+                images: None,
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "Cargo.toml\nsrc".to_string(),
+                    ..Default::default()
+                },
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "There is a `Cargo.toml` and <src>.".to_string(),
+            })),
+        ];
+
+        let transcript = Transcript::from_rollout_items(&items);
+
+        assert_eq!(transcript.entries, sample_transcript().entries);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn configured_commands_take_precedence_over_builtins() {
+        let exporters = HashMap::from([(
+            "markdown".to_string(),
+            ExporterConfig {
+                command: vec!["cat".to_string()],
+            },
+        )]);
+
+        assert!(exporter_for("html", &exporters).is_some());
+        assert!(exporter_for("confluence", &exporters).is_none());
+
+        let markdown = exporter_for("markdown", &exporters).expect("exporter");
+        let rendered = markdown.export(&sample_transcript()).expect("export");
+        assert_eq!(
+            rendered,
+            JsonExporter.export(&sample_transcript()).expect("json")
+        );
+    }
+}
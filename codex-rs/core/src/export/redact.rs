@@ -0,0 +1,174 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use codex_protocol::protocol::SessionMeta;
+use regex_lite::Regex;
+
+use super::Transcript;
+use super::TranscriptEntry;
+
+/// What the session directory is replaced with.
+const PROJECT_PLACEHOLDER: &str = "<project>";
+
+/// Absolute paths outside the project and home directory. The leading group
+/// keeps `<project>/src` and `~/src` intact and skips URLs.
+static ABSOLUTE_PATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(^|[\s"'`(=\[,])((?:/[\w.@+-]+)+|[A-Za-z]:\\(?:[\w.@+ -]+\\)*[\w.@+-]+)"#)
+        .unwrap_or_else(|_| std::process::abort())
+});
+
+/// `<environment_context>` blocks that tool output and messages may echo.
+static ENVIRONMENT_CONTEXT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<environment_context>.*?</environment_context>")
+        .unwrap_or_else(|_| std::process::abort())
+});
+
+impl Transcript {
+    /// A copy for sharing outside the team: the session directory becomes
+    /// `<project>`, the home directory `~`, and other absolute paths keep only
+    /// their file name. Session metadata other than the id and start time is
+    /// dropped, as are echoed `<environment_context>` blocks.
+    pub fn redacted(&self) -> Transcript {
+        let cwd = self.session.as_ref().map(|meta| meta.cwd.clone());
+        let redactor = Redactor {
+            cwd: cwd.filter(|cwd| !cwd.as_os_str().is_empty()),
+            home: dirs::home_dir(),
+        };
+        Transcript {
+            session: self.session.as_ref().map(|meta| SessionMeta {
+                id: meta.id,
+                timestamp: meta.timestamp.clone(),
+                ..SessionMeta::default()
+            }),
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| redactor.entry(entry))
+                .collect(),
+        }
+    }
+}
+
+struct Redactor {
+    cwd: Option<PathBuf>,
+    home: Option<PathBuf>,
+}
+
+impl Redactor {
+    fn entry(&self, entry: &TranscriptEntry) -> TranscriptEntry {
+        match entry {
+            TranscriptEntry::UserMessage { text } => TranscriptEntry::UserMessage {
+                text: self.text(text),
+            },
+            TranscriptEntry::AgentMessage { text } => TranscriptEntry::AgentMessage {
+                text: self.text(text),
+            },
+            TranscriptEntry::Reasoning { text } => TranscriptEntry::Reasoning {
+                text: self.text(text),
+            },
+            TranscriptEntry::Patch { call_id, patch } => TranscriptEntry::Patch {
+                call_id: call_id.clone(),
+                patch: self.text(patch),
+            },
+            TranscriptEntry::ToolCall {
+                call_id,
+                name,
+                input,
+            } => TranscriptEntry::ToolCall {
+                call_id: call_id.clone(),
+                name: name.clone(),
+                input: self.text(input),
+            },
+            TranscriptEntry::ToolOutput { call_id, output } => TranscriptEntry::ToolOutput {
+                call_id: call_id.clone(),
+                output: self.text(output),
+            },
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        let mut text = ENVIRONMENT_CONTEXT.replace_all(text, "").into_owned();
+        if let Some(cwd) = self.cwd.as_deref() {
+            text = replace_dir(&text, cwd, PROJECT_PLACEHOLDER);
+        }
+        if let Some(home) = self.home.as_deref() {
+            text = replace_dir(&text, home, "~");
+        }
+        ABSOLUTE_PATH
+            .replace_all(&text, |caps: &regex_lite::Captures<'_>| {
+                let path = &caps[2];
+                let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+                format!("{}…/{name}", &caps[1])
+            })
+            .into_owned()
+    }
+}
+
+/// Replaces `dir` in `text` with `placeholder`, for both the plain path and
+/// its JSON-escaped form (tool call arguments are JSON).
+fn replace_dir(text: &str, dir: &Path, placeholder: &str) -> String {
+    let plain = dir.display().to_string();
+    if plain.len() <= 1 {
+        return text.to_string();
+    }
+    let escaped = plain.replace('\\', "\\\\");
+    text.replace(&escaped, placeholder)
+        .replace(&plain, placeholder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn redactor() -> Redactor {
+        Redactor {
+            cwd: Some(PathBuf::from("/work/app")),
+            home: Some(PathBuf::from("/home/ana")),
+        }
+    }
+
+    #[test]
+    fn replaces_project_home_and_other_paths() {
+        let redacted = redactor().text(
+            "Edited /work/app/src/main.rs, read /home/ana/.gitconfig and /etc/hosts; see https://example.com/a/b",
+        );
+        assert_eq!(
+            redacted,
+            "Edited <project>/src/main.rs, read ~/.gitconfig and …/hosts; see https://example.com/a/b"
+        );
+    }
+
+    #[test]
+    fn drops_environment_context_and_session_details() {
+        let transcript = Transcript {
+            session: Some(SessionMeta {
+                cwd: PathBuf::from("/work/app"),
+                originator: "codex_cli_rs".to_string(),
+                cli_version: "1.2.3".to_string(),
+                timestamp: "2025-01-01T00:00:00Z".to_string(),
+                ..SessionMeta::default()
+            }),
+            entries: vec![TranscriptEntry::ToolOutput {
+                call_id: "call-1".to_string(),
+                output: "<environment_context>\n  <cwd>/work/app</cwd>\n</environment_context>ok"
+                    .to_string(),
+            }],
+        };
+
+        let redacted = transcript.redacted();
+
+        let meta = redacted.session.expect("session");
+        assert_eq!(meta.timestamp, "2025-01-01T00:00:00Z");
+        assert_eq!(meta.cwd, PathBuf::new());
+        assert_eq!(meta.cli_version, "");
+        assert_eq!(
+            redacted.entries,
+            vec![TranscriptEntry::ToolOutput {
+                call_id: "call-1".to_string(),
+                output: "ok".to_string(),
+            }]
+        );
+    }
+}
@@ -7,9 +7,11 @@
 
 use crate::config::ConfigToml;
 use crate::config::profile::ConfigProfile;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use toml::Value as TomlValue;
 
 mod legacy;
 pub(crate) use legacy::LegacyFeatureToggles;
@@ -93,6 +95,22 @@ pub enum Feature {
     Tui2,
     /// Enable discovery and injection of skills.
     Skills,
+    /// Include the read-only zip/tar inspection tools.
+    ArchiveTools,
+    /// Inject markdown files from the global and project steering directories.
+    Steering,
+    /// Include the read-only `query_sqlite` tool.
+    DatabaseTools,
+    /// Include the `http_request` tool for calling localhost and allowlisted hosts.
+    HttpRequestTool,
+    /// Include the read-only listening port and process lookup tools.
+    ProcessTools,
+    /// Keep a warm connection to the model provider between turns.
+    ConnectionPrewarm,
+    /// Summarize bulky tool outputs from earlier turns while waiting for input.
+    IdleSummarization,
+    /// Answer `read_file` re-reads with a diff and the lines not yet shown.
+    DifferentialReads,
 }
 
 impl Feature {
@@ -108,6 +126,23 @@ impl Feature {
         self.info().default_enabled
     }
 
+    /// Whether this feature adds tools to the model's tool list, which makes
+    /// it grantable for a single turn.
+    pub fn gates_tool(self) -> bool {
+        matches!(
+            self,
+            Feature::ViewImageTool
+                | Feature::ShellTool
+                | Feature::UnifiedExec
+                | Feature::ApplyPatchFreeform
+                | Feature::WebSearchRequest
+                | Feature::ArchiveTools
+                | Feature::DatabaseTools
+                | Feature::HttpRequestTool
+                | Feature::ProcessTools
+        )
+    }
+
     fn info(self) -> &'static FeatureSpec {
         FEATURES
             .iter()
@@ -195,6 +230,56 @@ impl Features {
             .map(|usage| (usage.alias.as_str(), usage.feature))
     }
 
+    /// Experimental and beta features that are on but off by default, for
+    /// surfaces that report which opt-in subsystems a session runs with.
+    pub fn enabled_opt_in(&self) -> Vec<Feature> {
+        let mut opt_in = Vec::new();
+        for spec in FEATURES {
+            if !spec.default_enabled
+                && matches!(spec.stage, Stage::Experimental | Stage::Beta { .. })
+                && self.enabled(spec.id)
+                && !opt_in.contains(&spec.id)
+            {
+                opt_in.push(spec.id);
+            }
+        }
+        opt_in
+    }
+
+    /// Warnings for settings that have no effect because a feature they need
+    /// is off: an enabled feature whose `requires` are not all enabled, or a
+    /// table of `config` (the merged config files) listed in the
+    /// `config_keys` of a disabled feature.
+    pub fn requirement_warnings(&self, config: &TomlValue) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for spec in FEATURES {
+            let key = spec.key;
+            if self.enabled(spec.id) {
+                for required in spec.requires.iter().filter(|f| !self.enabled(**f)) {
+                    let required = required.key();
+                    let warning = format!(
+                        "`features.{key}` has no effect unless `features.{required}` is enabled too."
+                    );
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+                continue;
+            }
+            for table in spec.config_keys {
+                if config.get(table).is_some() {
+                    let warning = format!(
+                        "`[{table}]` is set but has no effect while the `{key}` feature is off. Enable it with `--enable {key}` or `[features].{key} = true` in config.toml."
+                    );
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            }
+        }
+        warnings
+    }
+
     /// Apply a table of key -> bool toggles (e.g. from TOML).
     pub fn apply_map(&mut self, m: &BTreeMap<String, bool>) {
         for (k, v) in m {
@@ -259,7 +344,7 @@ impl Features {
 }
 
 /// Keys accepted in `[features]` tables.
-fn feature_for_key(key: &str) -> Option<Feature> {
+pub(crate) fn feature_for_key(key: &str) -> Option<Feature> {
     for spec in FEATURES {
         if spec.key == key {
             return Some(spec.id);
@@ -273,8 +358,20 @@ pub fn is_known_feature_key(key: &str) -> bool {
     feature_for_key(key).is_some()
 }
 
+/// Returns the feature that replaces a deprecated config key. `path` is a
+/// dotted key relative to the config root or to a `[profiles.<name>]` table:
+/// either a key that predates `[features]` (such as `tools.view_image`) or a
+/// legacy name inside `[features]` (such as `features.web_search`).
+pub fn legacy_key_replacement(path: &str) -> Option<Feature> {
+    match path.strip_prefix("features.") {
+        Some(key) if FEATURES.iter().all(|spec| spec.key != key) => legacy::feature_for_alias(key),
+        Some(_) => None,
+        None => legacy::feature_for_config_key(path),
+    }
+}
+
 /// Deserializable features table for TOML.
-#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
 pub struct FeaturesToml {
     #[serde(flatten)]
     pub entries: BTreeMap<String, bool>,
@@ -287,6 +384,10 @@ pub struct FeatureSpec {
     pub key: &'static str,
     pub stage: Stage,
     pub default_enabled: bool,
+    /// Features that must also be enabled for this one to take effect.
+    pub requires: &'static [Feature],
+    /// Top-level config tables that only take effect with this feature.
+    pub config_keys: &'static [&'static str],
 }
 
 pub const FEATURES: &[FeatureSpec] = &[
@@ -296,36 +397,48 @@ pub const FEATURES: &[FeatureSpec] = &[
         key: "undo",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ParallelToolCalls,
         key: "parallel",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ViewImageTool,
         key: "view_image_tool",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ShellTool,
         key: "shell_tool",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ModelWarnings,
         key: "warnings",
         stage: Stage::Stable,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::WebSearchRequest,
         key: "web_search_request",
         stage: Stage::Stable,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     // Beta program. Rendered in the `/experimental` menu for users.
     FeatureSpec {
@@ -337,6 +450,8 @@ pub const FEATURES: &[FeatureSpec] = &[
             announcement: "NEW! Try Background terminals for long running processes. Enable in /experimental!",
         },
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ShellSnapshot,
@@ -347,6 +462,8 @@ pub const FEATURES: &[FeatureSpec] = &[
             announcement: "NEW! Try shell snapshotting to make your Codex faster. Enable in /experimental!",
         },
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     // Unstable features.
     FeatureSpec {
@@ -354,59 +471,195 @@ pub const FEATURES: &[FeatureSpec] = &[
         key: "rmcp_client",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ApplyPatchFreeform,
         key: "apply_patch_freeform",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ExecPolicy,
         key: "exec_policy",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::WindowsSandbox,
         key: "experimental_windows_sandbox",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::WindowsSandboxElevated,
         key: "elevated_windows_sandbox",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[Feature::WindowsSandbox],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::RemoteCompaction,
         key: "remote_compaction",
         stage: Stage::Experimental,
         default_enabled: true,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::RemoteModels,
         key: "remote_models",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::Skills,
         key: "skills",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::ShellSnapshot,
         key: "shell_snapshot",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
     FeatureSpec {
         id: Feature::Tui2,
         key: "tui2",
         stage: Stage::Experimental,
         default_enabled: false,
+        requires: &[],
+        config_keys: &[],
+    },
+    FeatureSpec {
+        id: Feature::ArchiveTools,
+        key: "archive_tools",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &[],
+    },
+    FeatureSpec {
+        id: Feature::Steering,
+        key: "steering",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &["steering"],
+    },
+    FeatureSpec {
+        id: Feature::DatabaseTools,
+        key: "database_tools",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &["databases"],
+    },
+    FeatureSpec {
+        id: Feature::HttpRequestTool,
+        key: "http_request_tool",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &["http_request"],
+    },
+    FeatureSpec {
+        id: Feature::ProcessTools,
+        key: "process_tools",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &[],
+    },
+    FeatureSpec {
+        id: Feature::ConnectionPrewarm,
+        key: "connection_prewarm",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &[],
+    },
+    FeatureSpec {
+        id: Feature::IdleSummarization,
+        key: "idle_summarization",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &["idle_summarization"],
+    },
+    FeatureSpec {
+        id: Feature::DifferentialReads,
+        key: "differential_reads",
+        stage: Stage::Experimental,
+        default_enabled: false,
+        requires: &[],
+        config_keys: &[],
     },
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn warns_about_settings_a_disabled_feature_would_use() {
+        let config: TomlValue = toml::from_str(
+            r#"
+[steering]
+max_bytes = 4096
+
+[databases.app]
+path = "app.sqlite3"
+"#,
+        )
+        .expect("valid toml");
+        let mut features = Features::with_defaults();
+        features
+            .enable(Feature::DatabaseTools)
+            .enable(Feature::WindowsSandboxElevated);
+
+        assert_eq!(
+            features.requirement_warnings(&config),
+            vec![
+                "`features.elevated_windows_sandbox` has no effect unless `features.experimental_windows_sandbox` is enabled too.".to_string(),
+                "`[steering]` is set but has no effect while the `steering` feature is off. Enable it with `--enable steering` or `[features].steering = true` in config.toml.".to_string(),
+            ]
+        );
+
+        features
+            .enable(Feature::Steering)
+            .enable(Feature::WindowsSandbox);
+        assert_eq!(features.requirement_warnings(&config), Vec::<String>::new());
+    }
+
+    #[test]
+    fn opt_in_lists_enabled_features_that_are_off_by_default() {
+        let mut features = Features::with_defaults();
+        assert_eq!(features.enabled_opt_in(), Vec::new());
+
+        features
+            .enable(Feature::ShellSnapshot)
+            .enable(Feature::Steering)
+            .enable(Feature::WebSearchRequest);
+        assert_eq!(
+            features.enabled_opt_in(),
+            vec![Feature::ShellSnapshot, Feature::Steering]
+        );
+    }
+}
@@ -35,14 +35,67 @@ const ALIASES: &[Alias] = &[
     },
 ];
 
+/// Config keys outside `[features]` that predate it. Paths are relative to
+/// the config root or to a `[profiles.<name>]` table.
+const LEGACY_CONFIG_KEYS: &[Alias] = &[
+    Alias {
+        legacy_key: "experimental_use_unified_exec_tool",
+        feature: Feature::UnifiedExec,
+    },
+    Alias {
+        legacy_key: "experimental_use_rmcp_client",
+        feature: Feature::RmcpClient,
+    },
+    Alias {
+        legacy_key: "experimental_use_freeform_apply_patch",
+        feature: Feature::ApplyPatchFreeform,
+    },
+    Alias {
+        legacy_key: "include_apply_patch_tool",
+        feature: Feature::ApplyPatchFreeform,
+    },
+    Alias {
+        legacy_key: "tools.web_search",
+        feature: Feature::WebSearchRequest,
+    },
+    Alias {
+        legacy_key: "tools.web_search_request",
+        feature: Feature::WebSearchRequest,
+    },
+    Alias {
+        legacy_key: "tools.view_image",
+        feature: Feature::ViewImageTool,
+    },
+    Alias {
+        legacy_key: "tools_web_search",
+        feature: Feature::WebSearchRequest,
+    },
+    Alias {
+        legacy_key: "tools_view_image",
+        feature: Feature::ViewImageTool,
+    },
+];
+
 pub(crate) fn feature_for_key(key: &str) -> Option<Feature> {
-    ALIASES
+    let feature = find_alias(ALIASES, key)?;
+    log_alias(key, feature);
+    Some(feature)
+}
+
+/// Like [`feature_for_key`], without logging.
+pub(super) fn feature_for_alias(key: &str) -> Option<Feature> {
+    find_alias(ALIASES, key)
+}
+
+pub(super) fn feature_for_config_key(path: &str) -> Option<Feature> {
+    find_alias(LEGACY_CONFIG_KEYS, path)
+}
+
+fn find_alias(aliases: &[Alias], key: &str) -> Option<Feature> {
+    aliases
         .iter()
         .find(|alias| alias.legacy_key == key)
-        .map(|alias| {
-            log_alias(alias.legacy_key, alias.feature);
-            alias.feature
-        })
+        .map(|alias| alias.feature)
 }
 
 #[derive(Debug, Default)]
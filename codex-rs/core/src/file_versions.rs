@@ -0,0 +1,180 @@
+//! What earlier `read_file` calls have already shown the model
+//! (`features.differential_reads`).
+//!
+//! For every file read in the session, the session remembers the content as
+//! of the last read and which of its lines the model has seen. A later read
+//! of the same file only needs to carry what is new to the model: a diff
+//! against the version it saw, plus requested lines it has not seen yet. The
+//! memory is dropped whenever history is rewritten (compaction, undo, idle
+//! summarization), because the earlier reads may no longer be in context.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use similar::ChangeTag;
+use similar::TextDiff;
+
+#[derive(Debug, Default)]
+pub(crate) struct FileVersions {
+    files: Mutex<HashMap<PathBuf, FileVersion>>,
+}
+
+#[derive(Debug)]
+struct FileVersion {
+    lines: Vec<String>,
+    /// `seen[i]` tells whether line `i + 1` has been shown to the model.
+    seen: Vec<bool>,
+}
+
+/// A re-read expressed relative to what the model already has.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Reread {
+    /// Unified diff from the previously read version, or `None` when the
+    /// file is unchanged.
+    pub diff: Option<String>,
+    /// Requested line numbers that neither an earlier read nor `diff` shows.
+    pub unseen: Vec<usize>,
+}
+
+impl FileVersions {
+    /// Compares the current `lines` of `path` with the version read earlier.
+    /// Returns `None` when the file has not been read in this session.
+    pub(crate) fn reread(
+        &self,
+        path: &Path,
+        lines: &[String],
+        range: RangeInclusive<usize>,
+    ) -> Option<Reread> {
+        let files = self.lock();
+        let previous = files.get(path)?;
+        let (diff, seen) = previous.carry_over(lines, true);
+        let unseen = range.filter(|number| !seen[number - 1]).collect();
+        Some(Reread { diff, unseen })
+    }
+
+    /// Records that lines `range` of `lines` were just shown for `path`, and
+    /// whether the reply included the diff from the previous version.
+    pub(crate) fn record(
+        &self,
+        path: &Path,
+        lines: Vec<String>,
+        range: RangeInclusive<usize>,
+        diff_shown: bool,
+    ) {
+        let mut files = self.lock();
+        let mut seen = match files.get(path) {
+            Some(previous) => previous.carry_over(&lines, diff_shown).1,
+            None => vec![false; lines.len()],
+        };
+        for number in range {
+            seen[number - 1] = true;
+        }
+        files.insert(path.to_path_buf(), FileVersion { lines, seen });
+    }
+
+    pub(crate) fn forget(&self, path: &Path) {
+        self.lock().remove(path);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, FileVersion>> {
+        self.files.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl FileVersion {
+    /// The diff from this version to `lines` (`None` when identical) and
+    /// which lines of `lines` the model has seen: unchanged lines it saw
+    /// before, plus the lines the diff adds when `diff_shown`.
+    fn carry_over(&self, lines: &[String], diff_shown: bool) -> (Option<String>, Vec<bool>) {
+        if self.lines == lines {
+            return (None, self.seen.clone());
+        }
+        let old = as_text(&self.lines);
+        let new = as_text(lines);
+        let diff = TextDiff::from_lines(&old, &new);
+        let mut seen = vec![false; lines.len()];
+        for change in diff.iter_all_changes() {
+            match (change.tag(), change.old_index(), change.new_index()) {
+                (ChangeTag::Equal, Some(old_index), Some(new_index)) => {
+                    seen[new_index] = self.seen[old_index];
+                }
+                (ChangeTag::Insert, _, Some(new_index)) => seen[new_index] = diff_shown,
+                _ => {}
+            }
+        }
+        let unified = diff
+            .unified_diff()
+            .context_radius(1)
+            .header("previously read", "current")
+            .to_string();
+        (Some(unified), seen)
+    }
+}
+
+fn as_text(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn unchanged_rereads_only_report_unseen_lines() {
+        let versions = FileVersions::default();
+        let path = Path::new("/repo/src/lib.rs");
+        let file = lines("a\nb\nc\nd\ne");
+        assert_eq!(versions.reread(path, &file, 1..=3), None);
+
+        versions.record(path, file.clone(), 1..=3, false);
+
+        assert_eq!(
+            versions.reread(path, &file, 1..=5),
+            Some(Reread {
+                diff: None,
+                unseen: vec![4, 5],
+            })
+        );
+    }
+
+    #[test]
+    fn changed_files_diff_and_track_moved_lines() {
+        let versions = FileVersions::default();
+        let path = Path::new("/repo/src/lib.rs");
+        versions.record(path, lines("a\nb\nc\nd"), 1..=2, false);
+
+        // A line inserted at the top shifts what was seen down by one.
+        let current = lines("new\na\nb\nc\nd");
+        let reread = versions.reread(path, &current, 1..=5).expect("read before");
+
+        assert_eq!(reread.unseen, vec![4, 5]);
+        let diff = reread.diff.expect("diff");
+        assert!(diff.contains("+new\n"), "{diff}");
+
+        // Lines only shown through the diff count once the diff was sent.
+        versions.record(path, current.clone(), 4..=4, true);
+        assert_eq!(
+            versions
+                .reread(path, &current, 1..=5)
+                .map(|reread| reread.unseen),
+            Some(vec![5])
+        );
+
+        versions.clear();
+        assert_eq!(versions.reread(path, &current, 1..=5), None);
+    }
+}
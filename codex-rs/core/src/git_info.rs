@@ -596,6 +596,61 @@ pub async fn current_branch_name(cwd: &Path) -> Option<String> {
         .filter(|name| !name.is_empty())
 }
 
+/// The branch and worktree state shown in the TUI's git line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GitStatusSummary {
+    /// `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// Paths that are modified, staged, untracked, or conflicted.
+    pub changed: usize,
+    /// Commits ahead of and behind the upstream; `None` without an upstream.
+    pub ahead_behind: Option<(usize, usize)>,
+}
+
+/// Summarizes `git status` for `cwd`. Returns `None` outside a git repository
+/// or when git fails or times out.
+pub async fn git_status_summary(cwd: &Path) -> Option<GitStatusSummary> {
+    let out = run_git_command_with_timeout(&["status", "--porcelain=v2", "--branch"], cwd).await?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(parse_status_porcelain_v2(&String::from_utf8_lossy(
+        &out.stdout,
+    )))
+}
+
+fn parse_status_porcelain_v2(text: &str) -> GitStatusSummary {
+    let mut summary = GitStatusSummary::default();
+    for line in text.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            summary.branch = (head != "(detached)").then(|| head.to_string());
+        } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+            let mut counts = counts
+                .split_whitespace()
+                .map(|count| count.trim_start_matches(['+', '-']).parse::<usize>().ok());
+            if let (Some(Some(ahead)), Some(Some(behind))) = (counts.next(), counts.next()) {
+                summary.ahead_behind = Some((ahead, behind));
+            }
+        } else if !line.is_empty() && !line.starts_with('#') && !line.starts_with('!') {
+            summary.changed += 1;
+        }
+    }
+    summary
+}
+
+/// `git status --short --branch` for `cwd`, with git's colors as ANSI escapes.
+pub async fn git_status_short(cwd: &Path) -> Option<String> {
+    let out = run_git_command_with_timeout(
+        &["-c", "color.status=always", "status", "--short", "--branch"],
+        cwd,
+    )
+    .await?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1132,4 +1187,30 @@ mod tests {
         assert!(!parsed.as_object().unwrap().contains_key("branch"));
         assert!(!parsed.as_object().unwrap().contains_key("repository_url"));
     }
+
+    #[test]
+    fn parses_branch_counts_and_changes_from_porcelain_v2() {
+        let status = "# branch.oid 0123456789abcdef0123456789abcdef01234567\n\
+# branch.head feature/parser\n\
+# branch.upstream origin/feature/parser\n\
+# branch.ab +2 -1\n\
+1 .M N... 100644 100644 100644 0123 4567 src/lib.rs\n\
+2 R. N... 100644 100644 100644 0123 4567 R100 new.rs\told.rs\n\
+? notes.txt\n";
+        assert_eq!(
+            parse_status_porcelain_v2(status),
+            GitStatusSummary {
+                branch: Some("feature/parser".to_string()),
+                changed: 3,
+                ahead_behind: Some((2, 1)),
+            }
+        );
+
+        let detached =
+            "# branch.oid 0123456789abcdef0123456789abcdef01234567\n# branch.head (detached)\n";
+        assert_eq!(
+            parse_status_porcelain_v2(detached),
+            GitStatusSummary::default()
+        );
+    }
 }
@@ -0,0 +1,347 @@
+//! Summarizes bulky tool outputs from earlier turns while the session waits
+//! for input (`features.idle_summarization`).
+//!
+//! Work happens one output at a time: a request to the cheap
+//! `idle_summarization.model`, then a swap of the summary into history. The
+//! next submit stops the loop, so at most one in-flight request is thrown away
+//! and the turn never waits on a compaction. Each original is saved under the
+//! session artifacts directory and its summary points there, so the model can
+//! read the full text again when it needs it.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use futures::prelude::*;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+use tracing::warn;
+
+use crate::ModelClient;
+use crate::Prompt;
+use crate::artifacts::session_artifacts_dir;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_token_count;
+use crate::truncate::truncate_text;
+
+const SUMMARIZATION_PROMPT: &str = include_str!("../templates/idle_summarization/prompt.md");
+/// Starts every summary swapped into history, so an output is summarized
+/// only once.
+const SUMMARY_HEADER: &str = "[Tool output summarized while idle";
+/// Subdirectory of the session artifacts directory that keeps the originals.
+const TOOL_OUTPUTS_SUBDIR: &str = "tool-outputs";
+/// Upper bound on the output text sent to the summarization model.
+const MAX_INPUT_TOKENS: usize = 16_000;
+
+#[derive(Debug, Default)]
+pub(crate) struct IdleSummarizer {
+    run: Mutex<Option<CancellationToken>>,
+}
+
+impl IdleSummarizer {
+    /// Summarizes older tool outputs in the background until [`Self::stop`]
+    /// is called or nothing is left to summarize.
+    pub(crate) fn start(&self, sess: Arc<Session>) {
+        let token = CancellationToken::new();
+        if let Some(previous) = self.lock_run().replace(token.clone()) {
+            previous.cancel();
+        }
+        tokio::spawn(summarize_idle_tool_outputs(sess, token));
+    }
+
+    pub(crate) fn stop(&self) {
+        if let Some(token) = self.lock_run().take() {
+            token.cancel();
+        }
+    }
+
+    fn lock_run(&self) -> std::sync::MutexGuard<'_, Option<CancellationToken>> {
+        self.run.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for IdleSummarizer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// A tool output worth summarizing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Candidate {
+    pub(crate) call_id: String,
+    pub(crate) output: String,
+}
+
+/// Text tool outputs recorded before the latest user message that are at
+/// least `min_tokens` long and not summarized yet, oldest first. Outputs from
+/// the latest turn stay verbatim because the model is most likely to need
+/// them.
+pub(crate) fn find_candidates(items: &[ResponseItem], min_tokens: usize) -> Vec<Candidate> {
+    let Some(last_user_index) = items
+        .iter()
+        .rposition(|item| matches!(item, ResponseItem::Message { role, .. } if role == "user"))
+    else {
+        return Vec::new();
+    };
+
+    items[..last_user_index]
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCallOutput { call_id, output }
+                if output.content_items.is_none() =>
+            {
+                Some((call_id, &output.content))
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => Some((call_id, output)),
+            _ => None,
+        })
+        .filter(|(_, output)| {
+            !output.starts_with(SUMMARY_HEADER) && approx_token_count(output) >= min_tokens
+        })
+        .map(|(call_id, output)| Candidate {
+            call_id: call_id.clone(),
+            output: output.clone(),
+        })
+        .collect()
+}
+
+fn render_summary(summary: &str, original_tokens: usize, original_path: &Path) -> String {
+    format!(
+        "{SUMMARY_HEADER}; the full output (~{original_tokens} tokens) is saved at {}]\n{}",
+        original_path.display(),
+        summary.trim()
+    )
+}
+
+async fn summarize_idle_tool_outputs(sess: Arc<Session>, cancel: CancellationToken) {
+    let turn_context = sess.new_default_turn().await;
+    let config = turn_context.client.config();
+    let settings = &config.idle_summarization;
+    let history = sess.clone_history().await.get_history();
+    let candidates = find_candidates(&history, settings.min_output_tokens);
+    if candidates.is_empty() {
+        return;
+    }
+
+    let client = summarization_client(&sess, &turn_context, &settings.model).await;
+    let originals_dir =
+        session_artifacts_dir(&config.codex_home, sess.conversation_id()).join(TOOL_OUTPUTS_SUBDIR);
+    let mut summarized = 0usize;
+    for candidate in candidates {
+        let summary = tokio::select! {
+            _ = cancel.cancelled() => break,
+            summary = summarize(&client, &candidate.output) => summary,
+        };
+        let summary = match summary {
+            Ok(summary) if !summary.trim().is_empty() => summary,
+            Ok(_) => continue,
+            Err(err) => {
+                debug!(%err, "idle summarization request failed");
+                break;
+            }
+        };
+        let original_path = match save_original(&originals_dir, &candidate).await {
+            Ok(path) => path,
+            Err(err) => {
+                warn!(%err, "failed to save tool output before summarizing it");
+                break;
+            }
+        };
+        if cancel.is_cancelled() {
+            break;
+        }
+        let original_tokens = approx_token_count(&candidate.output);
+        let replacement = render_summary(&summary, original_tokens, &original_path);
+        if sess
+            .replace_tool_output(&candidate.call_id, &candidate.output, replacement)
+            .await
+        {
+            summarized += 1;
+        }
+    }
+    if summarized > 0 {
+        debug!(summarized, "summarized idle tool outputs");
+    }
+}
+
+/// A client for a cheap side model, such as the summarization model, that
+/// shares the session's provider and auth.
+pub(crate) async fn summarization_client(
+    sess: &Session,
+    turn_context: &TurnContext,
+    model: &str,
+) -> ModelClient {
+    let config = turn_context.client.config();
+    let model_family = sess
+        .services
+        .models_manager
+        .construct_model_family(model, &config)
+        .await;
+    let otel_manager = turn_context
+        .client
+        .get_otel_manager()
+        .with_model(model, model_family.slug.as_str());
+    ModelClient::new(
+        config,
+        turn_context.client.get_auth_manager(),
+        model_family,
+        otel_manager,
+        turn_context.client.get_provider(),
+        Some(ReasoningEffortConfig::Low),
+        ReasoningSummaryConfig::None,
+        sess.conversation_id(),
+        turn_context.client.get_session_source(),
+    )
+}
+
+async fn summarize(client: &ModelClient, output: &str) -> CodexResult<String> {
+    let input = truncate_text(output, TruncationPolicy::Tokens(MAX_INPUT_TOKENS));
+    request_text(client, SUMMARIZATION_PROMPT, input).await
+}
+
+/// Sends `input` as a single user message under `instructions` and returns
+/// the assistant's text.
+pub(crate) async fn request_text(
+    client: &ModelClient,
+    instructions: &str,
+    input: String,
+) -> CodexResult<String> {
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text: input }],
+        }],
+        base_instructions_override: Some(instructions.to_string()),
+        ..Default::default()
+    };
+    let mut stream = client.stream(&prompt).await?;
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" =>
+            {
+                if let Some(part) = content_items_to_text(&content) {
+                    text.push_str(&part);
+                }
+            }
+            ResponseEvent::Completed { .. } => return Ok(text),
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+async fn save_original(dir: &Path, candidate: &Candidate) -> std::io::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+    let file_name: String = candidate
+        .call_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = dir.join(format!("{file_name}.txt"));
+    tokio::fs::write(&path, &candidate.output).await?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_msg(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn function_output(call_id: &str, content: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content: content.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn finds_large_unsummarized_outputs_before_the_latest_turn() {
+        let bulky = "error: build failed\n".repeat(200);
+        let summarized = render_summary("build failed", 1_000, Path::new("/tmp/call-2.txt"));
+        let items = vec![
+            user_msg("run the build"),
+            function_output("call-1", &bulky),
+            function_output("call-2", &summarized),
+            function_output("call-3", "ok"),
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call-4".to_string(),
+                output: bulky.clone(),
+            },
+            user_msg("now fix it"),
+            function_output("call-5", &bulky),
+        ];
+
+        assert_eq!(
+            find_candidates(&items, 100),
+            vec![
+                Candidate {
+                    call_id: "call-1".to_string(),
+                    output: bulky.clone(),
+                },
+                Candidate {
+                    call_id: "call-4".to_string(),
+                    output: bulky,
+                },
+            ]
+        );
+        assert_eq!(find_candidates(&items[..1], 100), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn saves_originals_under_a_sanitized_call_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let candidate = Candidate {
+            call_id: "call/../1".to_string(),
+            output: "full output".to_string(),
+        };
+
+        let path = save_original(dir.path(), &candidate)
+            .await
+            .expect("save original");
+
+        assert_eq!(path, dir.path().join("call____1.txt"));
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read original"),
+            "full output"
+        );
+    }
+}
@@ -7,6 +7,9 @@
 
 pub mod api_bridge;
 mod apply_patch;
+pub mod approvals_policy;
+mod archive;
+pub mod artifacts;
 pub mod auth;
 pub mod bash;
 mod client;
@@ -19,6 +22,7 @@ mod codex_delegate;
 mod command_safety;
 pub mod config;
 pub mod config_loader;
+mod connection_prewarm;
 mod context_manager;
 pub mod custom_prompts;
 pub mod env;
@@ -27,9 +31,13 @@ pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+mod exploration;
+pub mod export;
 pub mod features;
+mod file_versions;
 mod flags;
 pub mod git_info;
+mod idle_summarization;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
@@ -41,15 +49,23 @@ mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
+pub mod patch_policy;
 pub mod path_utils;
+pub mod plan_mode;
 pub mod powershell;
+mod process_inspect;
+pub mod provider_health;
+mod run_limits;
 pub mod sandboxing;
+pub mod secrets;
+mod session_title;
 mod stream_events_utils;
 mod text_encoding;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
 mod user_instructions;
+mod workspace_roots;
 pub use model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
@@ -71,6 +87,7 @@ pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
 pub mod project_doc;
+pub mod replay;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
@@ -78,9 +95,11 @@ pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
+pub mod steering;
 pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
+mod turn_grants;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
@@ -98,6 +117,7 @@ mod tasks;
 mod user_notification;
 mod user_shell_command;
 pub mod util;
+mod workspace_events;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use command_safety::is_dangerous_command;
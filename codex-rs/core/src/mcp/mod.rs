@@ -1,10 +1,13 @@
 pub mod auth;
+mod session;
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
 
 use async_channel::unbounded;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::McpListToolsResponseEvent;
+use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use mcp_types::Tool as McpTool;
 use tokio_util::sync::CancellationToken;
@@ -14,6 +17,11 @@ use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::SandboxState;
 
+pub use session::session_mcp_server_config;
+pub use session::session_mcp_server_overrides;
+pub use session::split_mcp_command;
+pub use session::validate_mcp_server_name;
+
 const MCP_TOOL_NAME_PREFIX: &str = "mcp";
 const MCP_TOOL_NAME_DELIMITER: &str = "__";
 
@@ -64,6 +72,52 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     snapshot
 }
 
+/// Starts every configured MCP server and reports which completed the
+/// handshake, for `codex doctor`.
+pub async fn check_mcp_servers(config: &Config) -> McpStartupCompleteEvent {
+    if config.mcp_servers.is_empty() {
+        return McpStartupCompleteEvent::default();
+    }
+
+    let auth_status_entries = compute_auth_statuses(
+        config.mcp_servers.iter(),
+        config.mcp_oauth_credentials_store_mode,
+    )
+    .await;
+
+    let mut mcp_connection_manager = McpConnectionManager::default();
+    let (tx_event, rx_event) = unbounded();
+    let cancel_token = CancellationToken::new();
+    let sandbox_state = SandboxState {
+        sandbox_policy: SandboxPolicy::ReadOnly,
+        codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+        sandbox_cwd: env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
+    };
+
+    mcp_connection_manager
+        .initialize(
+            config.mcp_servers.clone(),
+            config.mcp_oauth_credentials_store_mode,
+            auth_status_entries,
+            tx_event,
+            cancel_token.clone(),
+            sandbox_state,
+        )
+        .await;
+
+    let mut summary = McpStartupCompleteEvent::default();
+    while let Ok(event) = rx_event.recv().await {
+        if let EventMsg::McpStartupComplete(complete) = event.msg {
+            summary = complete;
+            break;
+        }
+    }
+
+    cancel_token.cancel();
+
+    summary
+}
+
 pub fn split_qualified_tool_name(qualified_name: &str) -> Option<(String, String)> {
     let mut parts = qualified_name.split(MCP_TOOL_NAME_DELIMITER);
     let prefix = parts.next()?;
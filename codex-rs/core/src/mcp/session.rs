@@ -0,0 +1,171 @@
+//! MCP servers attached for a single session, with `--mcp NAME=COMMAND` on
+//! the command line or `/mcp add NAME COMMAND` in the TUI. They run under the
+//! same sandbox notifications and timeouts as configured servers but are
+//! never written to config.toml.
+
+use toml::Value as TomlValue;
+
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerTransportConfig;
+
+/// Server names become part of tool names, so they are limited to the
+/// characters the model API accepts there.
+pub fn validate_mcp_server_name(name: &str) -> Result<(), String> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid MCP server name '{name}' (use letters, numbers, '-', '_')"
+        ))
+    }
+}
+
+/// Splits a shell-quoted command line such as `npx -y @acme/docs-mcp`.
+pub fn split_mcp_command(command: &str) -> Result<Vec<String>, String> {
+    let words = shlex::split(command)
+        .ok_or_else(|| format!("could not parse MCP server command `{command}`"))?;
+    if words.is_empty() {
+        return Err("MCP server command is empty".to_string());
+    }
+    Ok(words)
+}
+
+/// Builds the config for a session-only server. `command` is a program and
+/// its arguments, or a single `http(s)://` URL for a streamable HTTP server.
+pub fn session_mcp_server_config(
+    name: &str,
+    command: Vec<String>,
+) -> Result<McpServerConfig, String> {
+    validate_mcp_server_name(name)?;
+    let mut words = command.into_iter();
+    let Some(program) = words.next() else {
+        return Err("MCP server command is empty".to_string());
+    };
+    let args: Vec<String> = words.collect();
+    let transport = if args.is_empty() && is_http_url(&program) {
+        McpServerTransportConfig::StreamableHttp {
+            url: program,
+            bearer_token_env_var: None,
+            http_headers: None,
+            env_http_headers: None,
+        }
+    } else {
+        McpServerTransportConfig::Stdio {
+            command: program,
+            args,
+            env: None,
+            env_vars: Vec::new(),
+            cwd: None,
+        }
+    };
+    Ok(McpServerConfig {
+        transport,
+        enabled: true,
+        startup_timeout_sec: None,
+        tool_timeout_sec: None,
+        enabled_tools: None,
+        disabled_tools: None,
+    })
+}
+
+/// Turns a `--mcp NAME=COMMAND` argument into `-c` style overrides, so the
+/// server joins `mcp_servers` for this run without touching config.toml.
+pub fn session_mcp_server_overrides(spec: &str) -> Result<Vec<(String, TomlValue)>, String> {
+    let Some((name, command)) = spec.split_once('=') else {
+        return Err(format!(
+            "invalid --mcp value `{spec}` (expected NAME=COMMAND)"
+        ));
+    };
+    let name = name.trim();
+    let config = session_mcp_server_config(name, split_mcp_command(command)?)?;
+    let key = |field: &str| format!("mcp_servers.{name}.{field}");
+    let overrides = match config.transport {
+        McpServerTransportConfig::StreamableHttp { url, .. } => {
+            vec![(key("url"), TomlValue::String(url))]
+        }
+        McpServerTransportConfig::Stdio { command, args, .. } => vec![
+            (key("command"), TomlValue::String(command)),
+            (
+                key("args"),
+                TomlValue::Array(args.into_iter().map(TomlValue::String).collect()),
+            ),
+        ],
+    };
+    Ok(overrides)
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn commands_become_stdio_servers_and_urls_http_servers() {
+        let stdio = session_mcp_server_config(
+            "docs",
+            split_mcp_command("npx -y '@acme/docs mcp'").expect("split"),
+        )
+        .expect("config");
+        assert_eq!(
+            stdio.transport,
+            McpServerTransportConfig::Stdio {
+                command: "npx".to_string(),
+                args: vec!["-y".to_string(), "@acme/docs mcp".to_string()],
+                env: None,
+                env_vars: Vec::new(),
+                cwd: None,
+            }
+        );
+
+        let http = session_mcp_server_config("remote", vec!["https://mcp.example/v1".to_string()])
+            .expect("config");
+        assert!(matches!(
+            http.transport,
+            McpServerTransportConfig::StreamableHttp { ref url, .. } if url == "https://mcp.example/v1"
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_names_and_empty_commands() {
+        assert_eq!(
+            session_mcp_server_config("my.server", vec!["x".to_string()]).unwrap_err(),
+            "invalid MCP server name 'my.server' (use letters, numbers, '-', '_')"
+        );
+        assert_eq!(
+            split_mcp_command("   ").unwrap_err(),
+            "MCP server command is empty"
+        );
+        assert_eq!(
+            session_mcp_server_overrides("docs").unwrap_err(),
+            "invalid --mcp value `docs` (expected NAME=COMMAND)"
+        );
+    }
+
+    #[test]
+    fn cli_flag_becomes_config_overrides() {
+        assert_eq!(
+            session_mcp_server_overrides("docs=docs-mcp --port 0").expect("overrides"),
+            vec![
+                (
+                    "mcp_servers.docs.command".to_string(),
+                    TomlValue::String("docs-mcp".to_string())
+                ),
+                (
+                    "mcp_servers.docs.args".to_string(),
+                    TomlValue::Array(vec![
+                        TomlValue::String("--port".to_string()),
+                        TomlValue::String("0".to_string()),
+                    ])
+                ),
+            ]
+        );
+    }
+}
@@ -64,6 +64,8 @@ use tracing::warn;
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::default_client::apply_network_config;
+use crate::default_client::network_config;
 
 /// Delimiter used to separate the server name from the tool name in a fully
 /// qualified tool name.
@@ -289,91 +291,64 @@ impl McpConnectionManager {
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::default();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
-            let cancel_token = cancel_token.child_token();
-            let _ = emit_update(
-                &tx_event,
-                McpStartupUpdateEvent {
-                    server: server_name.clone(),
-                    status: McpStartupStatus::Starting,
-                },
-            )
-            .await;
-            let async_managed_client = AsyncManagedClient::new(
+            let auth_entry = auth_entries.get(&server_name).cloned();
+            let async_managed_client = start_server(
                 server_name.clone(),
                 cfg,
                 store_mode,
-                cancel_token.clone(),
-                tx_event.clone(),
-                elicitation_requests.clone(),
-            );
-            clients.insert(server_name.clone(), async_managed_client.clone());
-            let tx_event = tx_event.clone();
-            let auth_entry = auth_entries.get(&server_name).cloned();
-            let sandbox_state = initial_sandbox_state.clone();
-            join_set.spawn(async move {
-                let outcome = async_managed_client.client().await;
-                if cancel_token.is_cancelled() {
-                    return (server_name, Err(StartupOutcomeError::Cancelled));
-                }
-                let status = match &outcome {
-                    Ok(_) => {
-                        // Send sandbox state notification immediately after Ready
-                        if let Err(e) = async_managed_client
-                            .notify_sandbox_state_change(&sandbox_state)
-                            .await
-                        {
-                            warn!(
-                                "Failed to notify sandbox state to MCP server {server_name}: {e:#}",
-                            );
-                        }
-                        McpStartupStatus::Ready
-                    }
-                    Err(error) => {
-                        let error_str = mcp_init_error_display(
-                            server_name.as_str(),
-                            auth_entry.as_ref(),
-                            error,
-                        );
-                        McpStartupStatus::Failed { error: error_str }
-                    }
-                };
-
-                let _ = emit_update(
-                    &tx_event,
-                    McpStartupUpdateEvent {
-                        server: server_name.clone(),
-                        status,
-                    },
-                )
-                .await;
-
-                (server_name, outcome)
-            });
+                auth_entry,
+                &tx_event,
+                cancel_token.child_token(),
+                &elicitation_requests,
+                initial_sandbox_state.clone(),
+                &mut join_set,
+            )
+            .await;
+            clients.insert(server_name, async_managed_client);
         }
         self.clients = clients;
         self.elicitation_requests = elicitation_requests.clone();
-        tokio::spawn(async move {
-            let outcomes = join_set.join_all().await;
-            let mut summary = McpStartupCompleteEvent::default();
-            for (server_name, outcome) in outcomes {
-                match outcome {
-                    Ok(_) => summary.ready.push(server_name),
-                    Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
-                    Err(StartupOutcomeError::Failed { error }) => {
-                        summary.failed.push(McpStartupFailure {
-                            server: server_name,
-                            error,
-                        })
-                    }
-                }
-            }
-            let _ = tx_event
-                .send(Event {
-                    id: INITIAL_SUBMIT_ID.to_owned(),
-                    msg: EventMsg::McpStartupComplete(summary),
-                })
-                .await;
-        });
+        tokio::spawn(emit_startup_complete(join_set, tx_event));
+    }
+
+    /// Starts one more server next to the ones passed to [`Self::initialize`],
+    /// such as a server attached to a single session with `/mcp add`. Startup
+    /// is reported with the same events, ending in its own
+    /// `McpStartupComplete`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_server(
+        &mut self,
+        server_name: String,
+        cfg: McpServerConfig,
+        store_mode: OAuthCredentialsStoreMode,
+        auth_entry: Option<McpAuthStatusEntry>,
+        tx_event: Sender<Event>,
+        cancel_token: CancellationToken,
+        sandbox_state: SandboxState,
+    ) -> Result<()> {
+        if self.clients.contains_key(&server_name) {
+            return Err(anyhow!("MCP server '{server_name}' is already running"));
+        }
+        let mut join_set = JoinSet::new();
+        let async_managed_client = start_server(
+            server_name.clone(),
+            cfg,
+            store_mode,
+            auth_entry,
+            &tx_event,
+            cancel_token,
+            &self.elicitation_requests,
+            sandbox_state,
+            &mut join_set,
+        )
+        .await;
+        self.clients.insert(server_name, async_managed_client);
+        tokio::spawn(emit_startup_complete(join_set, tx_event));
+        Ok(())
+    }
+
+    pub fn has_server(&self, server_name: &str) -> bool {
+        self.clients.contains_key(server_name)
     }
 
     async fn client_by_name(&self, name: &str) -> Result<ManagedClient> {
@@ -654,6 +629,96 @@ impl McpConnectionManager {
     }
 }
 
+/// Announces that `server_name` is starting and spawns its startup onto
+/// `join_set`, which resolves once the server is ready or has failed.
+#[allow(clippy::too_many_arguments)]
+async fn start_server(
+    server_name: String,
+    cfg: McpServerConfig,
+    store_mode: OAuthCredentialsStoreMode,
+    auth_entry: Option<McpAuthStatusEntry>,
+    tx_event: &Sender<Event>,
+    cancel_token: CancellationToken,
+    elicitation_requests: &ElicitationRequestManager,
+    sandbox_state: SandboxState,
+    join_set: &mut JoinSet<(String, Result<ManagedClient, StartupOutcomeError>)>,
+) -> AsyncManagedClient {
+    let _ = emit_update(
+        tx_event,
+        McpStartupUpdateEvent {
+            server: server_name.clone(),
+            status: McpStartupStatus::Starting,
+        },
+    )
+    .await;
+    let async_managed_client = AsyncManagedClient::new(
+        server_name.clone(),
+        cfg,
+        store_mode,
+        cancel_token.clone(),
+        tx_event.clone(),
+        elicitation_requests.clone(),
+    );
+    let tx_event = tx_event.clone();
+    let client = async_managed_client.clone();
+    join_set.spawn(async move {
+        let outcome = client.client().await;
+        if cancel_token.is_cancelled() {
+            return (server_name, Err(StartupOutcomeError::Cancelled));
+        }
+        let status = match &outcome {
+            Ok(_) => {
+                // Send sandbox state notification immediately after Ready
+                if let Err(e) = client.notify_sandbox_state_change(&sandbox_state).await {
+                    warn!("Failed to notify sandbox state to MCP server {server_name}: {e:#}",);
+                }
+                McpStartupStatus::Ready
+            }
+            Err(error) => {
+                let error_str =
+                    mcp_init_error_display(server_name.as_str(), auth_entry.as_ref(), error);
+                McpStartupStatus::Failed { error: error_str }
+            }
+        };
+
+        let _ = emit_update(
+            &tx_event,
+            McpStartupUpdateEvent {
+                server: server_name.clone(),
+                status,
+            },
+        )
+        .await;
+
+        (server_name, outcome)
+    });
+    async_managed_client
+}
+
+async fn emit_startup_complete(
+    join_set: JoinSet<(String, Result<ManagedClient, StartupOutcomeError>)>,
+    tx_event: Sender<Event>,
+) {
+    let outcomes = join_set.join_all().await;
+    let mut summary = McpStartupCompleteEvent::default();
+    for (server_name, outcome) in outcomes {
+        match outcome {
+            Ok(_) => summary.ready.push(server_name),
+            Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
+            Err(StartupOutcomeError::Failed { error }) => summary.failed.push(McpStartupFailure {
+                server: server_name,
+                error,
+            }),
+        }
+    }
+    let _ = tx_event
+        .send(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::McpStartupComplete(summary),
+        })
+        .await;
+}
+
 async fn emit_update(
     tx_event: &Sender<Event>,
     update: McpStartupUpdateEvent,
@@ -849,6 +914,7 @@ async fn make_rmcp_client(
                 http_headers,
                 env_http_headers,
                 store_mode,
+                apply_network_config(reqwest::Client::builder(), &network_config()),
             )
             .await
             .map_err(StartupOutcomeError::from)
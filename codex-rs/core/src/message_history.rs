@@ -35,6 +35,7 @@ use tokio::io::AsyncReadExt;
 
 use crate::config::Config;
 use crate::config::types::HistoryPersistence;
+use crate::config::xdg::codex_state_home;
 
 use codex_protocol::ConversationId;
 #[cfg(unix)]
@@ -43,11 +44,14 @@ use std::os::unix::fs::OpenOptionsExt;
 use std::os::unix::fs::PermissionsExt;
 
 /// Filename that stores the message history inside `~/.codex`.
-const HISTORY_FILENAME: &str = "history.jsonl";
+pub(crate) const HISTORY_FILENAME: &str = "history.jsonl";
 
 /// When history exceeds the hard cap, trim it down to this fraction of `max_bytes`.
 const HISTORY_SOFT_CAP_RATIO: f64 = 0.8;
 
+/// Most entries a history search returns.
+const MAX_SEARCH_RESULTS: usize = 100;
+
 const MAX_RETRIES: usize = 10;
 const RETRY_SLEEP: Duration = Duration::from_millis(100);
 
@@ -59,7 +63,7 @@ pub struct HistoryEntry {
 }
 
 fn history_filepath(config: &Config) -> PathBuf {
-    let mut path = config.codex_home.clone();
+    let mut path = codex_state_home(&config.codex_home);
     path.push(HISTORY_FILENAME);
     path
 }
@@ -261,6 +265,15 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
     lookup_history_entry(&path, log_id, offset)
 }
 
+/// Entries whose text contains `query`, ignoring case, newest first and one
+/// per distinct text. An empty query matches everything.
+///
+/// Like [`lookup`], this is not async because it locks the file.
+pub(crate) fn search(query: &str, config: &Config) -> Vec<HistoryEntry> {
+    let path = history_filepath(config);
+    search_history_file(&path, query, MAX_SEARCH_RESULTS)
+}
+
 /// On Unix systems, ensure the file permissions are `0o600` (rw-------). If the
 /// permissions cannot be changed the error is propagated to the caller.
 #[cfg(unix)]
@@ -381,6 +394,48 @@ fn lookup_history_entry(path: &Path, log_id: u64, offset: usize) -> Option<Histo
     None
 }
 
+fn search_history_file(path: &Path, query: &str, limit: usize) -> Vec<HistoryEntry> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to open history file");
+            return Vec::new();
+        }
+    };
+
+    let mut lines = Vec::new();
+    for _ in 0..MAX_RETRIES {
+        match file.try_lock_shared() {
+            Ok(()) => {
+                lines = BufReader::new(&file)
+                    .lines()
+                    .map_while(std::result::Result::ok)
+                    .collect();
+                break;
+            }
+            Err(std::fs::TryLockError::WouldBlock) => {
+                std::thread::sleep(RETRY_SLEEP);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to acquire shared lock on history file");
+                return Vec::new();
+            }
+        }
+    }
+
+    let query = query.to_lowercase();
+    let mut seen = std::collections::HashSet::new();
+    lines
+        .iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+        .filter(|entry| entry.text.to_lowercase().contains(&query))
+        .filter(|entry| seen.insert(entry.text.clone()))
+        .take(limit)
+        .collect()
+}
+
 #[cfg(unix)]
 fn history_log_id(metadata: &std::fs::Metadata) -> Option<u64> {
     use std::os::unix::fs::MetadataExt;
@@ -446,6 +501,52 @@ mod tests {
         assert_eq!(second_entry, entries[1]);
     }
 
+    #[test]
+    fn search_returns_distinct_matches_newest_first() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let history_path = temp_dir.path().join(HISTORY_FILENAME);
+
+        let mut file = File::create(&history_path).expect("create history file");
+        for (ts, text) in [
+            (1, "run the Tests"),
+            (2, "fix lint"),
+            (3, "run the tests again"),
+            (4, "run the Tests"),
+        ] {
+            let entry = HistoryEntry {
+                session_id: "session".to_string(),
+                ts,
+                text: text.to_string(),
+            };
+            writeln!(
+                file,
+                "{}",
+                serde_json::to_string(&entry).expect("serialize history entry")
+            )
+            .expect("write history entry");
+        }
+
+        let texts = |query: &str, limit: usize| -> Vec<String> {
+            search_history_file(&history_path, query, limit)
+                .into_iter()
+                .map(|entry| entry.text)
+                .collect()
+        };
+        assert_eq!(
+            texts("TESTS", 10),
+            vec![
+                "run the Tests".to_string(),
+                "run the tests again".to_string()
+            ]
+        );
+        assert_eq!(texts("", 2), vec!["run the Tests", "run the tests again"]);
+        assert_eq!(texts("deploy", 10), Vec::<String>::new());
+        assert_eq!(
+            search_history_file(&temp_dir.path().join("missing.jsonl"), "x", 10),
+            Vec::new()
+        );
+    }
+
     #[tokio::test]
     async fn lookup_uses_stable_log_id_after_appends() {
         let temp_dir = TempDir::new().expect("create temp dir");
@@ -12,13 +12,18 @@ use codex_app_server_protocol::AuthMode;
 use http::HeaderMap;
 use http::header::HeaderName;
 use http::header::HeaderValue;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::env::VarError;
 use std::time::Duration;
 
+use crate::default_client::network_config;
+use crate::default_client::shared_reqwest_client_for;
 use crate::error::EnvVarError;
+use crate::provider_health;
+use crate::secrets::resolve_secret_cmd_async;
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
@@ -36,7 +41,7 @@ const OPENAI_PROVIDER_NAME: &str = "OpenAI";
 /// *Responses* API. The two protocols use different request/response shapes
 /// and *cannot* be auto-detected at runtime, therefore each provider entry
 /// must declare which one it expects.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
@@ -48,7 +53,7 @@ pub enum WireApi {
 }
 
 /// Serializable representation of a provider definition.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
 pub struct ModelProviderInfo {
     /// Friendly display name.
     pub name: String,
@@ -61,6 +66,11 @@ pub struct ModelProviderInfo {
     /// variable and set it.
     pub env_key_instructions: Option<String>,
 
+    /// Command whose stdout is the API key, e.g.
+    /// `["op", "read", "op://vault/openai/key"]`. Runs once per process and
+    /// takes precedence over `env_key`.
+    pub api_key_cmd: Option<Vec<String>>,
+
     /// Value to use with `Authorization: Bearer <token>` header. Use of this
     /// config is discouraged in favor of `env_key` for security reasons, but
     /// this may be necessary when using this programmatically.
@@ -81,6 +91,8 @@ pub struct ModelProviderInfo {
     /// (key, value) pairs are the header name and _environment variable_ whose
     /// value should be used. If the environment variable is not set, or the
     /// value is empty, the header will not be included in the request.
+    /// `http_headers_from_env` is accepted as an alias.
+    #[serde(alias = "http_headers_from_env")]
     pub env_http_headers: Option<HashMap<String, String>>,
 
     /// Maximum number of times to retry a failed HTTP request to this provider.
@@ -93,6 +105,14 @@ pub struct ModelProviderInfo {
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
 
+    /// Additional base URLs (e.g. other regions of the same gateway) to fail
+    /// over to, in order, when `base_url` keeps failing after retries.
+    pub fallback_base_urls: Option<Vec<String>>,
+
+    /// Proxy for requests to this provider, replacing `network.proxy`. An
+    /// empty string connects directly.
+    pub proxy: Option<String>,
+
     /// Does this provider require an OpenAI API Key or ChatGPT login token? If true,
     /// user is presented with login screen on first run, and login preference and token/key
     /// are stored in auth.json. If false (which is the default), login screen is skipped,
@@ -102,7 +122,24 @@ pub struct ModelProviderInfo {
 }
 
 impl ModelProviderInfo {
-    fn build_header_map(&self) -> crate::error::Result<HeaderMap> {
+    /// Checks that every configured header name, and every static value, is
+    /// valid HTTP, so a typo is reported when the config loads instead of the
+    /// header being dropped from each request.
+    pub(crate) fn validate_http_headers(&self) -> Result<(), String> {
+        for (name, value) in self.http_headers.iter().flatten() {
+            HeaderName::try_from(name)
+                .map_err(|err| format!("http_headers: invalid header name `{name}`: {err}"))?;
+            HeaderValue::try_from(value)
+                .map_err(|err| format!("http_headers: invalid value for `{name}`: {err}"))?;
+        }
+        for name in self.env_http_headers.iter().flat_map(HashMap::keys) {
+            HeaderName::try_from(name)
+                .map_err(|err| format!("env_http_headers: invalid header name `{name}`: {err}"))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn build_header_map(&self) -> crate::error::Result<HeaderMap> {
         let mut headers = HeaderMap::new();
         if let Some(extra) = &self.http_headers {
             for (k, v) in extra {
@@ -127,20 +164,46 @@ impl ModelProviderInfo {
         Ok(headers)
     }
 
-    pub(crate) fn to_api_provider(
-        &self,
-        auth_mode: Option<AuthMode>,
-    ) -> crate::error::Result<ApiProvider> {
+    /// The pooled HTTP client for requests to this provider, honoring its
+    /// `proxy` override.
+    pub(crate) fn http_client(&self) -> reqwest::Client {
+        shared_reqwest_client_for(&network_config().with_proxy_override(self.proxy.as_deref()))
+    }
+
+    /// The primary base URL followed by any `fallback_base_urls`, in failover
+    /// order.
+    pub fn base_urls(&self, auth_mode: Option<AuthMode>) -> Vec<String> {
         let default_base_url = if matches!(auth_mode, Some(AuthMode::ChatGPT)) {
             "https://chatgpt.com/backend-api/codex"
         } else {
             "https://api.openai.com/v1"
         };
-        let base_url = self
+        let primary = self
             .base_url
             .clone()
             .unwrap_or_else(|| default_base_url.to_string());
 
+        let mut urls = vec![primary];
+        for url in self.fallback_base_urls.iter().flatten() {
+            if !url.trim().is_empty() && !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+        urls
+    }
+
+    /// The base URL the next request will go to, skipping endpoints that are
+    /// cooling down after repeated failures.
+    pub(crate) fn active_base_url(&self, auth_mode: Option<AuthMode>) -> String {
+        provider_health::select_base_url(&self.base_urls(auth_mode))
+    }
+
+    pub(crate) fn to_api_provider(
+        &self,
+        auth_mode: Option<AuthMode>,
+    ) -> crate::error::Result<ApiProvider> {
+        let base_url = self.active_base_url(auth_mode);
+
         let headers = self.build_header_map()?;
         let retry = ApiRetryConfig {
             max_attempts: self.request_max_retries(),
@@ -164,10 +227,14 @@ impl ModelProviderInfo {
         })
     }
 
-    /// If `env_key` is Some, returns the API key for this provider if present
+    /// If `api_key_cmd` is Some, returns what the command prints. Otherwise,
+    /// if `env_key` is Some, returns the API key for this provider if present
     /// (and non-empty) in the environment. If `env_key` is required but
     /// cannot be found, returns an error.
-    pub fn api_key(&self) -> crate::error::Result<Option<String>> {
+    pub async fn api_key(&self) -> crate::error::Result<Option<String>> {
+        if let Some(argv) = &self.api_key_cmd {
+            return Ok(Some(resolve_secret_cmd_async(argv).await?));
+        }
         match &self.env_key {
             Some(env_key) => {
                 let env_value = std::env::var(env_key);
@@ -224,6 +291,7 @@ impl ModelProviderInfo {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(
@@ -246,6 +314,8 @@ impl ModelProviderInfo {
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: true,
         }
     }
@@ -312,6 +382,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api,
         query_params: None,
         http_headers: None,
@@ -319,6 +390,8 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     }
 }
@@ -340,6 +413,7 @@ base_url = "http://localhost:11434/v1"
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: None,
@@ -347,6 +421,8 @@ base_url = "http://localhost:11434/v1"
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         };
 
@@ -354,6 +430,75 @@ base_url = "http://localhost:11434/v1"
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn gateway_headers_are_validated_and_sent() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+http_headers = { "X-Tenant-Id" = "acme" }
+http_headers_from_env = { "X-Route" = "GATEWAY_ROUTE" }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            provider.env_http_headers,
+            Some(HashMap::from([(
+                "X-Route".to_string(),
+                "GATEWAY_ROUTE".to_string()
+            )]))
+        );
+        assert_eq!(provider.validate_http_headers(), Ok(()));
+        let headers = provider.build_header_map().unwrap();
+        assert_eq!(
+            headers.get("x-tenant-id").and_then(|v| v.to_str().ok()),
+            Some("acme")
+        );
+
+        let invalid = ModelProviderInfo {
+            http_headers: Some(HashMap::from([(
+                "X Tenant".to_string(),
+                "acme".to_string(),
+            )])),
+            ..provider
+        };
+        assert!(
+            invalid
+                .validate_http_headers()
+                .unwrap_err()
+                .starts_with("http_headers: invalid header name `X Tenant`")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn api_key_cmd_takes_precedence_over_env_key() {
+        let provider: ModelProviderInfo = toml::from_str(
+            r#"
+name = "Gateway"
+env_key = "CODEX_TEST_UNSET_GATEWAY_KEY"
+api_key_cmd = ["sh", "-c", "printf 'sk-gateway-key\n'"]
+"#,
+        )
+        .expect("deserialize provider");
+
+        assert_eq!(
+            provider.api_key().await.expect("api key"),
+            Some("sk-gateway-key".to_string())
+        );
+
+        let failing = ModelProviderInfo {
+            api_key_cmd: Some(vec!["sh".into(), "-c".into(), "exit 7".into()]),
+            ..provider
+        };
+        let err = failing.api_key().await.expect_err("command fails");
+        assert_eq!(
+            err.to_string(),
+            "secret command `sh` exited with exit status: 7: "
+        );
+    }
+
     #[test]
     fn test_deserialize_azure_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -368,6 +513,7 @@ query_params = { api-version = "2025-04-01-preview" }
             env_key: Some("AZURE_OPENAI_API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Chat,
             query_params: Some(maplit::hashmap! {
                 "api-version".to_string() => "2025-04-01-preview".to_string(),
@@ -377,6 +523,8 @@ query_params = { api-version = "2025-04-01-preview" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         };
 
@@ -399,6 +547,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: Some("API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Chat,
             query_params: None,
             http_headers: Some(maplit::hashmap! {
@@ -410,6 +559,8 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         };
 
@@ -434,6 +585,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 env_key: None,
                 env_key_instructions: None,
                 experimental_bearer_token: None,
+                api_key_cmd: None,
                 wire_api: WireApi::Responses,
                 query_params: None,
                 http_headers: None,
@@ -441,6 +593,8 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 request_max_retries: None,
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
+                fallback_base_urls: None,
+                proxy: None,
                 requires_openai_auth: false,
             };
             let api = provider.to_api_provider(None).expect("api provider");
@@ -456,6 +610,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -463,6 +618,8 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         };
         let named_api = named_provider.to_api_provider(None).expect("api provider");
@@ -480,6 +637,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 env_key: None,
                 env_key_instructions: None,
                 experimental_bearer_token: None,
+                api_key_cmd: None,
                 wire_api: WireApi::Responses,
                 query_params: None,
                 http_headers: None,
@@ -487,6 +645,8 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 request_max_retries: None,
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
+                fallback_base_urls: None,
+                proxy: None,
                 requires_openai_auth: false,
             };
             let api = provider.to_api_provider(None).expect("api provider");
@@ -20,7 +20,7 @@ use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::config::xdg::codex_cache_home;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
 use crate::model_provider_info::ModelProviderInfo;
@@ -90,7 +90,7 @@ impl ModelsManager {
         let auth = self.auth_manager.auth();
         let api_provider = self.provider.to_api_provider(Some(AuthMode::ChatGPT))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = ReqwestTransport::new(self.provider.http_client());
         let client = ModelsClient::new(transport, api_provider, api_auth);
 
         let client_version = format_client_version_to_whole();
@@ -282,7 +282,7 @@ impl ModelsManager {
     }
 
     fn cache_path(&self) -> PathBuf {
-        self.codex_home.join(MODEL_CACHE_FILE)
+        codex_cache_home(&self.codex_home).join(MODEL_CACHE_FILE)
     }
 }
 
@@ -369,6 +369,7 @@ mod tests {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            api_key_cmd: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -376,6 +377,8 @@ mod tests {
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
+            fallback_base_urls: None,
+            proxy: None,
             requires_openai_auth: false,
         }
     }
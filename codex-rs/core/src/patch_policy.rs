@@ -0,0 +1,276 @@
+//! `.codex/patch-policy.toml`: which patches a headless run may apply.
+//!
+//! There is nobody to approve patches in `codex exec`, so a project can list
+//! what the agent may change instead. Every patch is checked before it is
+//! applied, and one that breaks the policy is sent back to the model with the
+//! reason so it can try another way:
+//!
+//! ```toml
+//! allow = ["src/**", "tests/**"]
+//! deny = [".github/**", "*.pem"]
+//! max_deleted_lines = 200
+//! ```
+//!
+//! Globs are matched against `/`-separated paths relative to the working
+//! directory, and `*` matches across `/`. The policy file itself can never be
+//! changed by a patch it governs.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+use serde::Deserialize;
+use wildmatch::WildMatchPattern;
+
+/// Where the policy lives, relative to the working directory.
+pub const PATCH_POLICY_FILE: &str = ".codex/patch-policy.toml";
+
+/// The contents of `.codex/patch-policy.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatchPolicyToml {
+    /// When non-empty, every path a patch touches must match one of these.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Paths no patch may create, edit, move, or delete. Wins over `allow`.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Most lines one patch may remove, counting every line of a deleted file.
+    #[serde(default)]
+    pub max_deleted_lines: Option<usize>,
+}
+
+/// A loaded patch policy and the directory its globs are relative to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchPolicy {
+    root: PathBuf,
+    rules: PatchPolicyToml,
+}
+
+impl PatchPolicy {
+    pub fn new(root: PathBuf, rules: PatchPolicyToml) -> Self {
+        Self { root, rules }
+    }
+
+    /// Reads the policy under `root`, or `None` when the project has none.
+    pub fn load(root: &Path) -> std::io::Result<Option<Self>> {
+        let path = root.join(PATCH_POLICY_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let rules = toml::from_str(&contents).map_err(|err| {
+            std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid patch policy {}: {err}", path.display()),
+            )
+        })?;
+        Ok(Some(Self::new(root.to_path_buf(), rules)))
+    }
+
+    /// Returns why `changes` may not be applied, or `None` when the policy
+    /// allows them.
+    pub fn check_patch(&self, changes: &HashMap<PathBuf, FileChange>) -> Option<String> {
+        let mut paths: Vec<String> = changes
+            .iter()
+            .flat_map(|(path, change)| {
+                let moved_to = match change {
+                    FileChange::Update {
+                        move_path: Some(move_path),
+                        ..
+                    } => Some(move_path.as_path()),
+                    _ => None,
+                };
+                std::iter::once(path.as_path()).chain(moved_to)
+            })
+            .map(|path| self.relative(path))
+            .collect();
+        paths.sort();
+
+        for path in &paths {
+            if path == PATCH_POLICY_FILE {
+                return Some(format!(
+                    "`{path}` is the patch policy for this run; it may not be changed"
+                ));
+            }
+            if let Some(glob) = first_match(&self.rules.deny, path) {
+                return Some(format!(
+                    "`{path}` matches deny `{glob}` in {PATCH_POLICY_FILE}; it may not be changed"
+                ));
+            }
+            if !self.rules.allow.is_empty() && first_match(&self.rules.allow, path).is_none() {
+                return Some(format!(
+                    "`{path}` is outside the allowed paths in {PATCH_POLICY_FILE} ({}); only change files under them",
+                    self.rules.allow.join(", ")
+                ));
+            }
+        }
+
+        if let Some(max) = self.rules.max_deleted_lines {
+            let deleted: usize = changes.values().map(deleted_line_count).sum();
+            if deleted > max {
+                return Some(format!(
+                    "patch deletes {deleted} lines, more than max_deleted_lines ({max}) in {PATCH_POLICY_FILE}; delete less or split the change"
+                ));
+            }
+        }
+        None
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+pub(crate) fn first_match<'a>(globs: &'a [String], path: &str) -> Option<&'a str> {
+    globs
+        .iter()
+        .map(|glob| glob.trim())
+        .find(|glob| WildMatchPattern::<'*', '?'>::new(glob).matches(path))
+}
+
+fn deleted_line_count(change: &FileChange) -> usize {
+    match change {
+        FileChange::Add { .. } => 0,
+        FileChange::Delete { content } => content.lines().count(),
+        FileChange::Update { unified_diff, .. } => unified_diff
+            .lines()
+            .filter(|line| line.starts_with('-') && !line.starts_with("---"))
+            .count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn policy(rules: PatchPolicyToml) -> PatchPolicy {
+        PatchPolicy::new(PathBuf::from("/repo"), rules)
+    }
+
+    fn add(path: &str) -> (PathBuf, FileChange) {
+        (
+            PathBuf::from(path),
+            FileChange::Add {
+                content: "fn main() {}\n".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn allows_only_listed_paths_and_denies_win() {
+        let policy = policy(PatchPolicyToml {
+            allow: vec!["src/**".to_string(), ".github/**".to_string()],
+            deny: vec![".github/**".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            policy.check_patch(&HashMap::from([add("/repo/src/lib.rs")])),
+            None
+        );
+        assert_eq!(
+            policy.check_patch(&HashMap::from([add("/repo/build.rs")])),
+            Some(
+                "`build.rs` is outside the allowed paths in .codex/patch-policy.toml (src/**, .github/**); only change files under them"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            policy.check_patch(&HashMap::from([add("/repo/.github/workflows/ci.yml")])),
+            Some(
+                "`.github/workflows/ci.yml` matches deny `.github/**` in .codex/patch-policy.toml; it may not be changed"
+                    .to_string()
+            )
+        );
+
+        let moved = HashMap::from([(
+            PathBuf::from("/repo/src/old.rs"),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: Some(PathBuf::from("/repo/lib/old.rs")),
+            },
+        )]);
+        assert!(policy.check_patch(&moved).is_some());
+    }
+
+    #[test]
+    fn the_policy_file_cannot_be_changed() {
+        let policy = policy(PatchPolicyToml::default());
+        assert_eq!(
+            policy.check_patch(&HashMap::from([add("/repo/.codex/patch-policy.toml")])),
+            Some(
+                "`.codex/patch-policy.toml` is the patch policy for this run; it may not be changed"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn large_deletions_are_rejected() {
+        let policy = policy(PatchPolicyToml {
+            max_deleted_lines: Some(2),
+            ..Default::default()
+        });
+        let update = (
+            PathBuf::from("/repo/src/lib.rs"),
+            FileChange::Update {
+                unified_diff: "--- a\n+++ b\n@@ -1,2 +1 @@\n-old\n-older\n+new\n".to_string(),
+                move_path: None,
+            },
+        );
+        let delete = (
+            PathBuf::from("/repo/src/unused.rs"),
+            FileChange::Delete {
+                content: "fn unused() {}\n".to_string(),
+            },
+        );
+
+        assert_eq!(
+            policy.check_patch(&HashMap::from([update.clone(), add("/repo/src/new.rs")])),
+            None
+        );
+        assert_eq!(
+            policy.check_patch(&HashMap::from([update, delete])),
+            Some(
+                "patch deletes 3 lines, more than max_deleted_lines (2) in .codex/patch-policy.toml; delete less or split the change"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn loads_the_policy_from_the_project() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(PatchPolicy::load(dir.path()).unwrap(), None);
+
+        std::fs::create_dir(dir.path().join(".codex")).unwrap();
+        std::fs::write(
+            dir.path().join(PATCH_POLICY_FILE),
+            "allow = [\"src/**\"]\nmax_deleted_lines = 50\n",
+        )
+        .unwrap();
+        assert_eq!(
+            PatchPolicy::load(dir.path()).unwrap(),
+            Some(PatchPolicy::new(
+                dir.path().to_path_buf(),
+                PatchPolicyToml {
+                    allow: vec!["src/**".to_string()],
+                    deny: Vec::new(),
+                    max_deleted_lines: Some(50),
+                }
+            ))
+        );
+
+        std::fs::write(dir.path().join(PATCH_POLICY_FILE), "allowed = []\n").unwrap();
+        assert!(PatchPolicy::load(dir.path()).is_err());
+    }
+}
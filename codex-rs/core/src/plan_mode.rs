@@ -0,0 +1,197 @@
+//! Plan-only mode.
+//!
+//! While plan mode is on, the model only sees read-only tools and is asked to
+//! answer with a structured plan (steps, affected files, risks) instead of
+//! making changes. `codex exec --plan-only` requests the plan as JSON matching
+//! [`plan_output_schema`], and `codex exec --from-plan` turns a saved plan back
+//! into the prompt for the run that carries it out.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+
+/// Returned to the model when it tries a mutating tool call in plan mode.
+pub(crate) const PLAN_MODE_REJECTION: &str = "Plan mode is on, so changes are not allowed. Inspect the code with read-only commands and reply with a plan instead.";
+
+pub(crate) const PLAN_MODE_STARTED_MESSAGE: &str = "Plan mode is on. Do not modify files or run mutating commands; file edits are refused. Investigate with read-only commands, then reply with a plan: a short summary, the ordered steps you would take, the files each step touches, and the risks or open questions the user should know about.";
+
+pub(crate) const PLAN_MODE_ENDED_MESSAGE: &str =
+    "Plan mode is off. Editing tools are available again.";
+
+/// A plan produced in plan mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanDocument {
+    pub summary: String,
+    pub steps: Vec<PlanStep>,
+    /// Every file the plan expects to create, modify, or delete.
+    pub affected_files: Vec<String>,
+    pub risks: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub title: String,
+    pub details: String,
+    /// Files this step touches.
+    pub files: Vec<String>,
+}
+
+/// JSON schema the final message must match when a plan is requested as
+/// structured output.
+pub fn plan_output_schema() -> Value {
+    let string_list = json!({ "type": "array", "items": { "type": "string" } });
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "steps": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "title": { "type": "string" },
+                        "details": { "type": "string" },
+                        "files": string_list,
+                    },
+                    "required": ["title", "details", "files"],
+                    "additionalProperties": false,
+                },
+            },
+            "affected_files": string_list,
+            "risks": string_list,
+        },
+        "required": ["summary", "steps", "affected_files", "risks"],
+        "additionalProperties": false,
+    })
+}
+
+impl PlanDocument {
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Plan\n\n{}\n\n## Steps\n\n", self.summary.trim());
+        if self.steps.is_empty() {
+            out.push_str("No steps.\n");
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("{}. **{}**", index + 1, step.title.trim()));
+            let details = step.details.trim();
+            if !details.is_empty() {
+                out.push_str(&format!("\n   {}", details.replace('\n', "\n   ")));
+            }
+            if !step.files.is_empty() {
+                out.push_str(&format!("\n   Files: {}", code_list(&step.files)));
+            }
+            out.push('\n');
+        }
+        push_list_section(&mut out, "Affected files", &self.affected_files, true);
+        push_list_section(&mut out, "Risks", &self.risks, false);
+        out
+    }
+
+    /// Prompt for a run that carries out this plan. `instructions` is extra
+    /// text from the user, added after the plan.
+    pub fn execution_prompt(&self, instructions: Option<&str>) -> String {
+        let mut prompt = format!(
+            "Carry out the following plan. Follow the steps in order, keep changes to the files it lists unless a step turns out to need more, and say so when you deviate from it.\n\n{}",
+            self.to_markdown()
+        );
+        if let Some(instructions) = instructions.map(str::trim).filter(|s| !s.is_empty()) {
+            prompt.push_str(&format!("\n## Additional instructions\n\n{instructions}\n"));
+        }
+        prompt
+    }
+}
+
+fn code_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn push_list_section(out: &mut String, heading: &str, items: &[String], code: bool) {
+    out.push_str(&format!("\n## {heading}\n\n"));
+    if items.is_empty() {
+        out.push_str("None.\n");
+        return;
+    }
+    for item in items {
+        if code {
+            out.push_str(&format!("- `{item}`\n"));
+        } else {
+            out.push_str(&format!("- {item}\n"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn sample_plan() -> PlanDocument {
+        PlanDocument {
+            summary: "Add a retry to the fetcher.".to_string(),
+            steps: vec![
+                PlanStep {
+                    title: "Wrap the request".to_string(),
+                    details: "Retry twice on 5xx.".to_string(),
+                    files: vec!["src/fetch.rs".to_string()],
+                },
+                PlanStep {
+                    title: "Test it".to_string(),
+                    details: String::new(),
+                    files: Vec::new(),
+                },
+            ],
+            affected_files: vec!["src/fetch.rs".to_string()],
+            risks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            sample_plan().to_markdown(),
+            "# Plan\n\nAdd a retry to the fetcher.\n\n## Steps\n\n\
+             1. **Wrap the request**\n   Retry twice on 5xx.\n   Files: `src/fetch.rs`\n\
+             2. **Test it**\n\n\
+             ## Affected files\n\n- `src/fetch.rs`\n\n\
+             ## Risks\n\nNone.\n"
+        );
+    }
+
+    #[test]
+    fn plan_round_trips_through_json() {
+        let plan = sample_plan();
+        let json = serde_json::to_string(&plan).unwrap();
+        assert_eq!(serde_json::from_str::<PlanDocument>(&json).unwrap(), plan);
+    }
+
+    #[test]
+    fn schema_requires_every_field() {
+        let schema = plan_output_schema();
+        assert_eq!(
+            schema["required"],
+            json!(["summary", "steps", "affected_files", "risks"])
+        );
+        assert_eq!(
+            schema["properties"]["steps"]["items"]["required"],
+            json!(["title", "details", "files"])
+        );
+    }
+
+    #[test]
+    fn execution_prompt_appends_instructions() {
+        let prompt = sample_plan().execution_prompt(Some("  Skip step 2. "));
+        assert!(prompt.starts_with("Carry out the following plan."));
+        assert!(prompt.contains("1. **Wrap the request**"));
+        assert!(prompt.ends_with("## Additional instructions\n\nSkip step 2.\n"));
+        assert!(
+            !sample_plan()
+                .execution_prompt(Some(" "))
+                .contains("Additional instructions")
+        );
+    }
+}
@@ -0,0 +1,546 @@
+//! Read-only inspection of running processes and listening TCP sockets.
+//!
+//! Process details come from `sysinfo`. Listening sockets are read from
+//! `/proc/net/tcp{,6}` on Linux, `lsof` on other Unix systems, and `netstat`
+//! on Windows, none of which need elevated privileges. Sockets owned by other
+//! users are still listed, but their owning process may be unknown.
+//!
+//! Process lookups only return the current user's processes, and their
+//! command lines are shortened and scrubbed of likely credentials before they
+//! reach the model.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use serde::Serialize;
+use sysinfo::Pid;
+use sysinfo::ProcessRefreshKind;
+use sysinfo::ProcessesToUpdate;
+use sysinfo::System;
+use sysinfo::UpdateKind;
+
+use crate::secrets::REDACTED;
+use crate::secrets::redact;
+
+/// Longest command line returned for a process, in characters.
+const MAX_COMMAND_CHARS: usize = 300;
+
+/// Argument names whose values are masked in returned command lines.
+const SENSITIVE_ARG_MARKERS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "key",
+    "auth",
+    "credential",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct ListeningSocket {
+    pub(crate) address: IpAddr,
+    pub(crate) port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) process_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct ProcessInfo {
+    pub(crate) pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) parent_pid: Option<u32>,
+    pub(crate) name: String,
+    pub(crate) command: String,
+    /// Ports this process is listening on.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) listening_ports: Vec<u16>,
+}
+
+fn load_processes() -> System {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet),
+    );
+    system
+}
+
+/// Lists listening TCP sockets, optionally restricted to `port`, sorted by
+/// port and address.
+pub(crate) fn list_listening_sockets(port: Option<u16>) -> std::io::Result<Vec<ListeningSocket>> {
+    let mut sockets = platform::listening_sockets()?;
+    if let Some(port) = port {
+        sockets.retain(|socket| socket.port == port);
+    }
+
+    let system = load_processes();
+    for socket in &mut sockets {
+        if socket.process_name.is_none()
+            && let Some(process) = socket
+                .pid
+                .and_then(|pid| system.process(Pid::from_u32(pid)))
+        {
+            socket.process_name = Some(process.name().to_string_lossy().into_owned());
+        }
+    }
+
+    sockets.sort_by(|a, b| (a.port, a.address).cmp(&(b.port, b.address)));
+    sockets.dedup();
+    Ok(sockets)
+}
+
+/// Finds the current user's processes whose name contains `name`
+/// (case-insensitive) and, when `port` is set, that listen on that port.
+/// Sorted by pid.
+pub(crate) fn find_processes(
+    name: Option<&str>,
+    port: Option<u16>,
+) -> std::io::Result<Vec<ProcessInfo>> {
+    let mut ports_by_pid: BTreeMap<u32, Vec<u16>> = BTreeMap::new();
+    for socket in platform::listening_sockets()? {
+        if let Some(pid) = socket.pid {
+            let ports = ports_by_pid.entry(pid).or_default();
+            if !ports.contains(&socket.port) {
+                ports.push(socket.port);
+            }
+        }
+    }
+
+    let needle = name.map(str::to_lowercase);
+    let system = load_processes();
+    // Without a known owner nothing can be shown to belong to this user.
+    let current_user = sysinfo::get_current_pid()
+        .ok()
+        .and_then(|pid| system.process(pid))
+        .and_then(|process| process.user_id().cloned());
+    let mut processes: Vec<ProcessInfo> = system
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            if current_user.is_none() || process.user_id() != current_user.as_ref() {
+                return None;
+            }
+            let pid = pid.as_u32();
+            let process_name = process.name().to_string_lossy().into_owned();
+            let mut listening_ports = ports_by_pid.get(&pid).cloned().unwrap_or_default();
+            listening_ports.sort_unstable();
+
+            if let Some(needle) = &needle
+                && !process_name.to_lowercase().contains(needle)
+            {
+                return None;
+            }
+            if let Some(port) = port
+                && !listening_ports.contains(&port)
+            {
+                return None;
+            }
+
+            let args: Vec<String> = process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect();
+            let command = sanitize_command(&args);
+            Some(ProcessInfo {
+                pid,
+                parent_pid: process.parent().map(Pid::as_u32),
+                name: process_name,
+                command,
+                listening_ports,
+            })
+        })
+        .collect();
+    processes.sort_by_key(|process| process.pid);
+    Ok(processes)
+}
+
+/// Joins `args` into a command line with the values of credential-looking
+/// arguments (`--token x`, `--password=x`, `API_KEY=x`) masked, known secrets
+/// redacted, and the result cut to [`MAX_COMMAND_CHARS`].
+fn sanitize_command(args: &[String]) -> String {
+    let is_sensitive = |name: &str| {
+        let name = name.to_ascii_lowercase();
+        SENSITIVE_ARG_MARKERS
+            .iter()
+            .any(|marker| name.contains(marker))
+    };
+    let mut parts = Vec::with_capacity(args.len());
+    let mut mask_next = false;
+    for arg in args {
+        if std::mem::take(&mut mask_next) && !arg.starts_with('-') {
+            parts.push(REDACTED.to_string());
+            continue;
+        }
+        match arg.split_once('=') {
+            Some((name, _)) if is_sensitive(name) => parts.push(format!("{name}={REDACTED}")),
+            Some(_) => parts.push(arg.clone()),
+            None => {
+                mask_next = arg.starts_with('-') && is_sensitive(arg);
+                parts.push(arg.clone());
+            }
+        }
+    }
+    let command = redact(&parts.join(" ")).into_owned();
+    if command.chars().count() <= MAX_COMMAND_CHARS {
+        return command;
+    }
+    let mut truncated: String = command.chars().take(MAX_COMMAND_CHARS).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::net::IpAddr;
+    use std::net::Ipv4Addr;
+    use std::net::Ipv6Addr;
+
+    use super::ListeningSocket;
+
+    const TCP_LISTEN_STATE: &str = "0A";
+
+    pub(super) fn listening_sockets() -> std::io::Result<Vec<ListeningSocket>> {
+        let mut entries = Vec::new();
+        for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            match fs::read_to_string(table) {
+                Ok(contents) => entries.extend(super::parse_proc_net_tcp(&contents)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        let owners = socket_owners();
+        Ok(entries
+            .into_iter()
+            .map(|(address, port, inode)| ListeningSocket {
+                address,
+                port,
+                pid: owners.get(&inode).copied(),
+                process_name: None,
+            })
+            .collect())
+    }
+
+    /// Maps socket inodes to the pid holding them, for every process whose
+    /// file descriptors we are allowed to read.
+    fn socket_owners() -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+        let Ok(procs) = fs::read_dir("/proc") else {
+            return owners;
+        };
+        for entry in procs.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                continue;
+            };
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                if let Ok(target) = fs::read_link(fd.path())
+                    && let Some(inode) = target
+                        .to_str()
+                        .and_then(|s| s.strip_prefix("socket:["))
+                        .and_then(|s| s.strip_suffix(']'))
+                        .and_then(|s| s.parse().ok())
+                {
+                    owners.insert(inode, pid);
+                }
+            }
+        }
+        owners
+    }
+
+    pub(super) fn parse_hex_address(hex: &str) -> Option<IpAddr> {
+        match hex.len() {
+            8 => {
+                // The kernel prints the network-order address as a host-order
+                // integer, so its native bytes are the address octets.
+                let raw = u32::from_str_radix(hex, 16).ok()?;
+                Some(IpAddr::V4(Ipv4Addr::from(raw.to_ne_bytes())))
+            }
+            32 => {
+                let mut octets = [0u8; 16];
+                for (word_idx, chunk) in hex.as_bytes().chunks(8).enumerate() {
+                    let word = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+                    octets[word_idx * 4..word_idx * 4 + 4].copy_from_slice(&word.to_ne_bytes());
+                }
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn is_listen_state(state: &str) -> bool {
+        state == TCP_LISTEN_STATE
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+mod platform {
+    use std::process::Command;
+
+    use super::ListeningSocket;
+
+    pub(super) fn listening_sockets() -> std::io::Result<Vec<ListeningSocket>> {
+        let output = Command::new("lsof")
+            .args(["-nP", "-iTCP", "-sTCP:LISTEN", "-F", "pcn"])
+            .output()?;
+        // lsof exits with 1 when nothing matches.
+        Ok(super::parse_lsof_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::process::Command;
+
+    use super::ListeningSocket;
+
+    pub(super) fn listening_sockets() -> std::io::Result<Vec<ListeningSocket>> {
+        let output = Command::new("netstat")
+            .args(["-ano", "-p", "TCP"])
+            .output()?;
+        let mut sockets = super::parse_netstat_output(&String::from_utf8_lossy(&output.stdout));
+        let output = Command::new("netstat")
+            .args(["-ano", "-p", "TCPv6"])
+            .output()?;
+        sockets.extend(super::parse_netstat_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )));
+        Ok(sockets)
+    }
+}
+
+/// Parses `/proc/net/tcp` or `/proc/net/tcp6` into `(address, port, inode)`
+/// triples for sockets in the LISTEN state.
+#[cfg(target_os = "linux")]
+fn parse_proc_net_tcp(contents: &str) -> Vec<(IpAddr, u16, u64)> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (local, state, inode) = (fields.get(1)?, fields.get(3)?, fields.get(9)?);
+            if !platform::is_listen_state(state) {
+                return None;
+            }
+            let (address, port) = local.split_once(':')?;
+            Some((
+                platform::parse_hex_address(address)?,
+                u16::from_str_radix(port, 16).ok()?,
+                inode.parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Parses `lsof -F pcn` output: a `p<pid>` line starts each process, `c` holds
+/// its command name, and each `n<address>:<port>` line is one socket.
+#[cfg(any(test, all(unix, not(target_os = "linux"))))]
+fn parse_lsof_output(output: &str) -> Vec<ListeningSocket> {
+    let mut sockets = Vec::new();
+    let mut pid = None;
+    let mut process_name = None;
+    for line in output.lines() {
+        let (tag, value) = line.split_at(line.len().min(1));
+        match tag {
+            "p" => {
+                pid = value.parse().ok();
+                process_name = None;
+            }
+            "c" => process_name = Some(value.to_string()),
+            "n" => {
+                if let Some((address, port)) = parse_host_port(value) {
+                    sockets.push(ListeningSocket {
+                        address,
+                        port,
+                        pid,
+                        process_name: process_name.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    sockets
+}
+
+/// Parses `netstat -ano` rows such as
+/// `TCP    0.0.0.0:135    0.0.0.0:0    LISTENING    1234`.
+#[cfg(any(test, windows))]
+fn parse_netstat_output(output: &str) -> Vec<ListeningSocket> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 5 || fields[0] != "TCP" || fields[3] != "LISTENING" {
+                return None;
+            }
+            let (address, port) = parse_host_port(fields[1])?;
+            Some(ListeningSocket {
+                address,
+                port,
+                pid: fields[4].parse().ok(),
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses `127.0.0.1:8080`, `[::1]:8080`, or `*:8080` (any address).
+#[cfg(any(test, not(target_os = "linux")))]
+fn parse_host_port(value: &str) -> Option<(IpAddr, u16)> {
+    let (host, port) = value.rsplit_once(':')?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let address = if host == "*" {
+        IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
+    } else {
+        host.split('%').next()?.parse().ok()?
+    };
+    Some((address, port.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::net::Ipv4Addr;
+    use std::net::Ipv6Addr;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_proc_net_tcp_listeners() {
+        let contents = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:A2C4 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1 0000000000000000 20 4 30 10 -1
+";
+        let contents6 = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0BB8 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 77 1 0000000000000000 100 0 0 10 0
+";
+
+        assert_eq!(
+            parse_proc_net_tcp(contents),
+            vec![(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080, 4242)]
+        );
+        assert_eq!(
+            parse_proc_net_tcp(contents6),
+            vec![(IpAddr::V6(Ipv6Addr::LOCALHOST), 3000, 77)]
+        );
+    }
+
+    #[test]
+    fn parses_lsof_field_output() {
+        let output = "p501\ncnode\nn*:3000\nn[::1]:3000\np77\ncpostgres\nn127.0.0.1:5432\n";
+
+        assert_eq!(
+            parse_lsof_output(output),
+            vec![
+                ListeningSocket {
+                    address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                    port: 3000,
+                    pid: Some(501),
+                    process_name: Some("node".to_string()),
+                },
+                ListeningSocket {
+                    address: IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    port: 3000,
+                    pid: Some(501),
+                    process_name: Some("node".to_string()),
+                },
+                ListeningSocket {
+                    address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    port: 5432,
+                    pid: Some(77),
+                    process_name: Some("postgres".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_netstat_listening_rows() {
+        let output = "\
+Active Connections
+
+  Proto  Local Address          Foreign Address        State           PID
+  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1044
+  TCP    127.0.0.1:49670        127.0.0.1:49671        ESTABLISHED     4321
+  TCP    [::]:445               [::]:0                 LISTENING       4
+";
+
+        assert_eq!(
+            parse_netstat_output(output),
+            vec![
+                ListeningSocket {
+                    address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                    port: 135,
+                    pid: Some(1044),
+                    process_name: None,
+                },
+                ListeningSocket {
+                    address: IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                    port: 445,
+                    pid: Some(4),
+                    process_name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn masks_credentials_in_command_lines() {
+        let args: Vec<String> = [
+            "server",
+            "--port",
+            "8080",
+            "--api-token",
+            "abc123",
+            "--password=hunter2",
+            "DATABASE_AUTH=s3cret",
+            "--verbose",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        assert_eq!(
+            sanitize_command(&args),
+            "server --port 8080 --api-token [REDACTED] --password=[REDACTED] \
+             DATABASE_AUTH=[REDACTED] --verbose"
+        );
+    }
+
+    #[test]
+    fn truncates_long_command_lines() {
+        let args = vec!["x".repeat(MAX_COMMAND_CHARS + 50)];
+        let command = sanitize_command(&args);
+        assert_eq!(command.chars().count(), MAX_COMMAND_CHARS + 3);
+        assert!(command.ends_with("..."));
+    }
+
+    #[test]
+    fn finds_current_process_by_name() {
+        let current = std::process::id();
+        let system = load_processes();
+        let name = system
+            .process(Pid::from_u32(current))
+            .map(|process| process.name().to_string_lossy().into_owned())
+            .unwrap();
+
+        let processes = find_processes(Some(&name), None).unwrap();
+
+        assert!(processes.iter().any(|process| process.pid == current));
+    }
+}
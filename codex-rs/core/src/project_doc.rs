@@ -12,11 +12,16 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! In a session spanning several repositories (`Config::workspace_roots`), the
+//! same search runs from each extra root and its docs follow the others under
+//! a header naming the root.
 
 use crate::config::Config;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -30,6 +35,10 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
+/// Introduces the docs of each extra repository root in a multi-repository
+/// session, followed by the root's path.
+const REPOSITORY_DOC_HEADER: &str = "Instructions for the repository at";
+
 /// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
 /// string of instructions.
 pub(crate) async fn get_user_instructions(
@@ -81,16 +90,40 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         return Ok(None);
     }
 
-    let paths = discover_project_doc_paths(config)?;
-    if paths.is_empty() {
-        return Ok(None);
+    let mut remaining: u64 = max_total as u64;
+    let mut parts = read_docs(
+        discover_doc_paths_from(config, &config.cwd)?,
+        &mut remaining,
+    )
+    .await?;
+
+    // Docs from the other repository roots share the same budget and are
+    // labelled so the model can tell which repository they apply to.
+    for root in &config.workspace_roots {
+        let docs = read_docs(discover_doc_paths_from(config, root)?, &mut remaining).await?;
+        if !docs.is_empty() {
+            parts.push(format!(
+                "{REPOSITORY_DOC_HEADER} {}\n\n{}",
+                root.display(),
+                docs.join("\n\n")
+            ));
+        }
     }
 
-    let mut remaining: u64 = max_total as u64;
+    if parts.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(parts.join("\n\n")))
+    }
+}
+
+/// Reads `paths` in order until `remaining` bytes have been used up, skipping
+/// missing and empty files.
+async fn read_docs(paths: Vec<PathBuf>, remaining: &mut u64) -> std::io::Result<Vec<String>> {
     let mut parts: Vec<String> = Vec::new();
 
     for p in paths {
-        if remaining == 0 {
+        if *remaining == 0 {
             break;
         }
 
@@ -101,11 +134,11 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         };
 
         let size = file.metadata().await?.len();
-        let mut reader = tokio::io::BufReader::new(file).take(remaining);
+        let mut reader = tokio::io::BufReader::new(file).take(*remaining);
         let mut data: Vec<u8> = Vec::new();
         reader.read_to_end(&mut data).await?;
 
-        if size > remaining {
+        if size > *remaining {
             tracing::warn!(
                 "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
                 p.display(),
@@ -116,24 +149,34 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         let text = String::from_utf8_lossy(&data).to_string();
         if !text.trim().is_empty() {
             parts.push(text);
-            remaining = remaining.saturating_sub(data.len() as u64);
+            *remaining = remaining.saturating_sub(data.len() as u64);
         }
     }
 
-    if parts.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(parts.join("\n\n")))
-    }
+    Ok(parts)
 }
 
 /// Discover the list of AGENTS.md files using the same search rules as
 /// `read_project_docs`, but return the file paths instead of concatenated
 /// contents. The list is ordered from repository root to the current working
-/// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
-/// is zero, returns an empty list.
+/// directory (inclusive), followed by those of each of
+/// `Config::workspace_roots`. Symlinks are allowed. When
+/// `project_doc_max_bytes` is zero, returns an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
-    let mut dir = config.cwd.clone();
+    let mut found = discover_doc_paths_from(config, &config.cwd)?;
+    for root in &config.workspace_roots {
+        for path in discover_doc_paths_from(config, root)? {
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// The AGENTS.md files from the Git root above `start` down to `start`.
+fn discover_doc_paths_from(config: &Config, start: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut dir = start.to_path_buf();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
     }
@@ -174,7 +217,7 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
         dirs
     } else {
-        vec![config.cwd.clone()]
+        vec![start.to_path_buf()]
     };
 
     let mut found: Vec<PathBuf> = Vec::new();
@@ -350,6 +393,35 @@ mod tests {
         assert_eq!(res, "root level doc");
     }
 
+    /// In a multi-repository session each extra root contributes its own
+    /// AGENTS.md, labelled with the root it came from.
+    #[tokio::test]
+    async fn reads_docs_from_every_workspace_root() {
+        let service_a = tempfile::tempdir().expect("tempdir");
+        let service_b = tempfile::tempdir().expect("tempdir");
+        fs::write(service_a.path().join("AGENTS.md"), "service a doc").unwrap();
+        fs::write(service_b.path().join("AGENTS.md"), "service b doc").unwrap();
+
+        let mut cfg = make_config(&service_a, 4096, None);
+        cfg.workspace_roots = vec![service_b.path().to_path_buf()];
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(
+            res,
+            format!(
+                "service a doc\n\n{REPOSITORY_DOC_HEADER} {}\n\nservice b doc",
+                service_b.path().display()
+            )
+        );
+        assert_eq!(
+            discover_project_doc_paths(&cfg).unwrap().len(),
+            2,
+            "status should list the docs of every root"
+        );
+    }
+
     /// Explicitly setting the byte-limit to zero disables project docs.
     #[tokio::test]
     async fn zero_byte_limit_disables_docs() {
@@ -0,0 +1,263 @@
+//! Passive health tracking for provider base URLs, used to fail over to a
+//! provider's `fallback_base_urls`, plus the active probe behind
+//! `codex doctor --providers`.
+//!
+//! Every request already retries transient failures on its own, so a request
+//! that still fails is a strong signal. After [`FAILOVER_ERROR_THRESHOLD`]
+//! such failures in a row an endpoint is skipped for [`UNHEALTHY_COOLDOWN`]
+//! and requests go to the next configured URL instead.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_api::TransportError;
+use codex_api::error::ApiError;
+use reqwest::header::AUTHORIZATION;
+use tracing::warn;
+
+use crate::model_provider_info::ModelProviderInfo;
+
+/// Consecutive failed requests after which an endpoint is considered down.
+const FAILOVER_ERROR_THRESHOLD: u32 = 2;
+/// How long an endpoint that crossed the threshold is skipped.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(60);
+/// Upper bound for a single `codex doctor --providers` probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+static REGISTRY: LazyLock<Mutex<HealthRegistry>> =
+    LazyLock::new(|| Mutex::new(HealthRegistry::default()));
+
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct HealthRegistry {
+    endpoints: HashMap<String, EndpointHealth>,
+}
+
+impl HealthRegistry {
+    fn is_healthy(&self, base_url: &str, now: Instant) -> bool {
+        self.endpoints
+            .get(base_url)
+            .and_then(|health| health.unhealthy_until)
+            .is_none_or(|until| until <= now)
+    }
+
+    fn select(&self, candidates: &[String], now: Instant) -> Option<String> {
+        candidates
+            .iter()
+            .find(|url| self.is_healthy(url, now))
+            .or_else(|| candidates.first())
+            .cloned()
+    }
+
+    fn record_success(&mut self, base_url: &str) {
+        self.endpoints.remove(base_url);
+    }
+
+    /// Returns `true` when this failure newly marks the endpoint unhealthy.
+    fn record_failure(&mut self, base_url: &str, now: Instant) -> bool {
+        let health = self.endpoints.entry(base_url.to_string()).or_default();
+        health.consecutive_failures += 1;
+        if health.consecutive_failures < FAILOVER_ERROR_THRESHOLD
+            || health.unhealthy_until.is_some_and(|until| until > now)
+        {
+            return false;
+        }
+        health.unhealthy_until = Some(now + UNHEALTHY_COOLDOWN);
+        true
+    }
+}
+
+/// Picks the first candidate that is not cooling down, falling back to the
+/// primary URL when every endpoint is marked unhealthy.
+pub(crate) fn select_base_url(candidates: &[String]) -> String {
+    let registry = REGISTRY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    registry
+        .select(candidates, Instant::now())
+        .unwrap_or_default()
+}
+
+pub(crate) fn record_success(base_url: &str) {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .record_success(base_url);
+}
+
+/// Records a failed request against `base_url`. Returns `true` when the
+/// failure should trigger failover to the next configured endpoint.
+pub(crate) fn record_failure(base_url: &str, err: &ApiError) -> bool {
+    if !is_endpoint_failure(err) {
+        return false;
+    }
+    let marked_unhealthy = REGISTRY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .record_failure(base_url, Instant::now());
+    if marked_unhealthy {
+        warn!(
+            base_url,
+            "provider endpoint keeps failing; skipping it for {}s",
+            UNHEALTHY_COOLDOWN.as_secs()
+        );
+    }
+    marked_unhealthy
+}
+
+/// Errors that say something about the endpoint rather than the request.
+fn is_endpoint_failure(err: &ApiError) -> bool {
+    match err {
+        ApiError::Transport(TransportError::Http { status, .. }) => status.is_server_error(),
+        ApiError::Transport(
+            TransportError::Timeout | TransportError::Network(_) | TransportError::RetryLimit,
+        ) => true,
+        ApiError::Api { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
+/// Result of probing one base URL of a provider.
+#[derive(Debug, Clone)]
+pub struct EndpointProbe {
+    pub base_url: String,
+    /// HTTP status returned by `GET {base_url}/models`, if a response arrived.
+    pub status: Option<u16>,
+    pub latency: Duration,
+    pub error: Option<String>,
+}
+
+impl EndpointProbe {
+    /// Any non-5xx response means the endpoint is up; auth or routing
+    /// problems are reported by status but do not count as an outage.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none() && self.status.is_some_and(|status| status < 500)
+    }
+}
+
+/// Actively probes every base URL of `provider` with `GET /models`.
+pub async fn probe_provider(provider: &ModelProviderInfo) -> Vec<EndpointProbe> {
+    let client = provider.http_client();
+    let headers = provider.build_header_map().unwrap_or_default();
+    let api_key = provider.api_key().await.ok().flatten();
+
+    let mut probes = Vec::new();
+    for base_url in provider.base_urls(None) {
+        let url = format!("{}/models", base_url.trim_end_matches('/'));
+        let mut request = client
+            .get(&url)
+            .headers(headers.clone())
+            .timeout(PROBE_TIMEOUT);
+        if let Some(api_key) = &api_key {
+            request = request.header(AUTHORIZATION, format!("Bearer {api_key}"));
+        }
+
+        let started = Instant::now();
+        let result = request.send().await;
+        let latency = started.elapsed();
+        probes.push(match result {
+            Ok(response) => EndpointProbe {
+                base_url,
+                status: Some(response.status().as_u16()),
+                latency,
+                error: None,
+            },
+            Err(err) => EndpointProbe {
+                base_url,
+                status: None,
+                latency,
+                error: Some(err.to_string()),
+            },
+        });
+    }
+    probes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn urls() -> Vec<String> {
+        vec![
+            "https://us.example.com/v1".to_string(),
+            "https://eu.example.com/v1".to_string(),
+        ]
+    }
+
+    #[test]
+    fn fails_over_after_sustained_errors_and_recovers_after_cooldown() {
+        let mut registry = HealthRegistry::default();
+        let now = Instant::now();
+        let candidates = urls();
+
+        assert!(!registry.record_failure(&candidates[0], now));
+        assert_eq!(
+            registry.select(&candidates, now),
+            Some(candidates[0].clone())
+        );
+
+        assert!(registry.record_failure(&candidates[0], now));
+        assert_eq!(
+            registry.select(&candidates, now),
+            Some(candidates[1].clone())
+        );
+
+        let later = now + UNHEALTHY_COOLDOWN;
+        assert_eq!(
+            registry.select(&candidates, later),
+            Some(candidates[0].clone())
+        );
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let mut registry = HealthRegistry::default();
+        let now = Instant::now();
+        let candidates = urls();
+
+        assert!(!registry.record_failure(&candidates[0], now));
+        registry.record_success(&candidates[0]);
+        assert!(!registry.record_failure(&candidates[0], now));
+        assert_eq!(
+            registry.select(&candidates, now),
+            Some(candidates[0].clone())
+        );
+    }
+
+    #[test]
+    fn primary_is_used_when_every_endpoint_is_down() {
+        let mut registry = HealthRegistry::default();
+        let now = Instant::now();
+        let candidates = urls();
+        for url in &candidates {
+            registry.record_failure(url, now);
+            registry.record_failure(url, now);
+        }
+
+        assert_eq!(
+            registry.select(&candidates, now),
+            Some(candidates[0].clone())
+        );
+    }
+
+    #[test]
+    fn client_errors_do_not_count_against_the_endpoint() {
+        let err = ApiError::Api {
+            status: http::StatusCode::BAD_REQUEST,
+            message: "bad request".to_string(),
+        };
+        assert!(!is_endpoint_failure(&err));
+        assert!(is_endpoint_failure(&ApiError::Transport(
+            TransportError::Timeout
+        )));
+    }
+}
@@ -0,0 +1,195 @@
+//! The model requests of a recorded session, rebuilt for `codex replay`.
+//!
+//! Before every model request, core records a `TurnContext` line in the
+//! rollout. Replaying the recorded response items up to that line, with
+//! compactions applied, gives the conversation the request was sent with; the
+//! items recorded after it, up to the next request, are the model's answer
+//! and the results of its tool calls. Nothing is sent to a model.
+
+use std::io;
+use std::path::Path;
+
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::TurnContextItem;
+
+use crate::rollout::RolloutRecorder;
+
+/// One request to the model, as recorded.
+#[derive(Debug, Clone)]
+pub struct ModelRequest {
+    /// 1-based, in the order the requests were made.
+    pub number: usize,
+    /// The model, working directory, and policies the request ran with.
+    pub context: TurnContextItem,
+    /// The conversation sent with the request, oldest first.
+    pub input: Vec<ResponseItem>,
+    /// How many items at the end of `input` the previous request did not
+    /// have. After a compaction, every item is new.
+    pub new_input: usize,
+    /// What was recorded until the next request: the model's messages,
+    /// reasoning, and tool calls, then the tool outputs.
+    pub recorded: Vec<ResponseItem>,
+}
+
+/// Rebuilds the model requests from rollout items in file order.
+pub fn model_requests(items: &[RolloutItem]) -> Vec<ModelRequest> {
+    let mut history: Vec<ResponseItem> = Vec::new();
+    let mut requests: Vec<ModelRequest> = Vec::new();
+    let mut previous_len = 0;
+    for item in items {
+        match item {
+            RolloutItem::ResponseItem(response_item) => {
+                history.push(response_item.clone());
+                if let Some(request) = requests.last_mut() {
+                    request.recorded.push(response_item.clone());
+                }
+            }
+            RolloutItem::Compacted(compacted) => {
+                history = match &compacted.replacement_history {
+                    Some(replacement) => replacement.clone(),
+                    None => vec![ResponseItem::from(compacted.clone())],
+                };
+                previous_len = 0;
+            }
+            RolloutItem::TurnContext(context) => {
+                requests.push(ModelRequest {
+                    number: requests.len() + 1,
+                    context: context.clone(),
+                    input: history.clone(),
+                    new_input: history.len().saturating_sub(previous_len),
+                    recorded: Vec::new(),
+                });
+                previous_len = history.len();
+            }
+            RolloutItem::SessionMeta(_) | RolloutItem::EventMsg(_) => {}
+        }
+    }
+    requests
+}
+
+/// Reads the rollout file at `path` and rebuilds its model requests.
+pub async fn load_model_requests(path: &Path) -> io::Result<Vec<ModelRequest>> {
+    let history = RolloutRecorder::get_rollout_history(path).await?;
+    Ok(model_requests(&history.get_rollout_items()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::config_types::ReasoningSummary;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::CompactedItem;
+    use codex_protocol::protocol::SandboxPolicy;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn turn_context() -> RolloutItem {
+        RolloutItem::TurnContext(TurnContextItem {
+            cwd: PathBuf::from("/repo"),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: "gpt-5.1".to_string(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+    }
+
+    fn message(role: &str, text: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        })
+    }
+
+    fn texts(items: &[ResponseItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match item {
+                ResponseItem::Message { content, .. } => match &content[0] {
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        text.clone()
+                    }
+                    _ => String::new(),
+                },
+                ResponseItem::FunctionCall { call_id, .. } => format!("call {call_id}"),
+                ResponseItem::FunctionCallOutput { call_id, .. } => format!("output {call_id}"),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rebuilds_the_input_of_each_request() {
+        let items = vec![
+            message("user", "list the files"),
+            turn_context(),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "Cargo.toml".to_string(),
+                    ..Default::default()
+                },
+            }),
+            turn_context(),
+            message("assistant", "There is a Cargo.toml."),
+            RolloutItem::Compacted(CompactedItem {
+                message: "The user listed the files.".to_string(),
+                replacement_history: None,
+            }),
+            message("user", "now build it"),
+            turn_context(),
+        ];
+
+        let requests = model_requests(&items);
+
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| (request.number, texts(&request.input), request.new_input))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, vec!["list the files".to_string()], 1),
+                (
+                    2,
+                    vec![
+                        "list the files".to_string(),
+                        "call call-1".to_string(),
+                        "output call-1".to_string()
+                    ],
+                    2
+                ),
+                (
+                    3,
+                    vec![
+                        "The user listed the files.".to_string(),
+                        "now build it".to_string()
+                    ],
+                    2
+                ),
+            ]
+        );
+        assert_eq!(
+            texts(&requests[0].recorded),
+            vec!["call call-1".to_string(), "output call-1".to_string()]
+        );
+        assert_eq!(
+            texts(&requests[1].recorded),
+            vec![
+                "There is a Cargo.toml.".to_string(),
+                "now build it".to_string()
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::io::ErrorKind;
 use std::path::Path;
 
+use crate::config::xdg::codex_state_home;
 use crate::error::CodexErr;
 use crate::rollout::SESSIONS_SUBDIR;
 
@@ -17,12 +18,13 @@ pub(crate) fn map_session_init_error(err: &anyhow::Error, codex_home: &Path) ->
 }
 
 fn map_rollout_io_error(io_err: &std::io::Error, codex_home: &Path) -> Option<CodexErr> {
-    let sessions_dir = codex_home.join(SESSIONS_SUBDIR);
+    let state_home = codex_state_home(codex_home);
+    let sessions_dir = state_home.join(SESSIONS_SUBDIR);
     let hint = match io_err.kind() {
         ErrorKind::PermissionDenied => format!(
             "Codex cannot access session files at {} (permission denied). If sessions were created using sudo, fix ownership: sudo chown -R $(whoami) {}",
             sessions_dir.display(),
-            codex_home.display()
+            state_home.display()
         ),
         ErrorKind::NotFound => format!(
             "Session storage missing at {}. Create the directory or choose a different Codex home.",
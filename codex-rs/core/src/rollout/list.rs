@@ -14,6 +14,7 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use super::SESSIONS_SUBDIR;
+use crate::config::xdg::codex_state_home;
 use crate::protocol::EventMsg;
 use codex_file_search as file_search;
 use codex_protocol::protocol::RolloutItem;
@@ -44,6 +45,11 @@ pub struct ConversationItem {
     pub created_at: Option<String>,
     /// RFC3339 timestamp string for the most recent update (from file mtime).
     pub updated_at: Option<String>,
+    /// Tokens used by the whole session, from its last token count event.
+    pub total_tokens: Option<i64>,
+    /// The session title, generated after the first exchange or set with
+    /// `/title`.
+    pub title: Option<String>,
 }
 
 #[derive(Default)]
@@ -110,7 +116,7 @@ pub(crate) async fn get_conversations(
     model_providers: Option<&[String]>,
     default_provider: &str,
 ) -> io::Result<ConversationsPage> {
-    let mut root = codex_home.to_path_buf();
+    let mut root = codex_state_home(codex_home);
     root.push(SESSIONS_SUBDIR);
 
     if !root.exists() {
@@ -232,11 +238,20 @@ async fn traverse_directories_for_paths(
                                 .unwrap_or(None)
                                 .or_else(|| created_at.clone());
                         }
+                        let TailSummary {
+                            total_tokens,
+                            mut title,
+                        } = read_tail_summary(&path).await.unwrap_or_default();
+                        if title.is_none() {
+                            title = read_early_title(&path).await.unwrap_or(None);
+                        }
                         items.push(ConversationItem {
                             path,
                             head,
                             created_at,
                             updated_at,
+                            total_tokens,
+                            title,
                         });
                     }
                 }
@@ -445,6 +460,84 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
     Ok(summary.head)
 }
 
+/// How much of the end of a rollout file to search for a token count and
+/// the latest title.
+const TAIL_READ_BYTES: u64 = 64 * 1024;
+/// How much of the start of a rollout file to search for a title when the
+/// tail has none. Titles are usually generated after the first exchange.
+const TITLE_SCAN_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Default, PartialEq)]
+struct TailSummary {
+    total_tokens: Option<i64>,
+    title: Option<String>,
+}
+
+/// The session's total tokens and title from the last token count and title
+/// events near the end of the rollout file at `path`.
+async fn read_tail_summary(path: &Path) -> io::Result<TailSummary> {
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(TAIL_READ_BYTES);
+    file.seek(io::SeekFrom::Start(start)).await?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).await?;
+    let tail = String::from_utf8_lossy(&tail);
+
+    // The first line may be cut off when reading from the middle.
+    let mut summary = TailSummary::default();
+    for line in tail.lines().rev() {
+        if summary.total_tokens.is_some() && summary.title.is_some() {
+            break;
+        }
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(line.trim()) else {
+            continue;
+        };
+        match rollout_line.item {
+            RolloutItem::EventMsg(EventMsg::TokenCount(event))
+                if summary.total_tokens.is_none() =>
+            {
+                summary.total_tokens = event
+                    .info
+                    .map(|info| info.total_token_usage.blended_total());
+            }
+            RolloutItem::EventMsg(EventMsg::SessionTitle(event)) if summary.title.is_none() => {
+                summary.title = Some(event.title);
+            }
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+
+/// The last title recorded in the first [`TITLE_SCAN_BYTES`] of the rollout
+/// file at `path`.
+async fn read_early_title(path: &Path) -> io::Result<Option<String>> {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncReadExt;
+
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file.take(TITLE_SCAN_BYTES)).lines();
+    let mut title = None;
+    while let Some(line) = lines.next_line().await? {
+        // Skip parsing the many lines that cannot be a title event.
+        if !line.contains("\"session_title\"") {
+            continue;
+        }
+        if let Ok(RolloutLine {
+            item: RolloutItem::EventMsg(EventMsg::SessionTitle(event)),
+            ..
+        }) = serde_json::from_str::<RolloutLine>(&line)
+        {
+            title = Some(event.title);
+        }
+    }
+    Ok(title)
+}
+
 async fn file_modified_rfc3339(path: &Path) -> io::Result<Option<String>> {
     let meta = tokio::fs::metadata(path).await?;
     let modified = meta.modified().ok();
@@ -467,7 +560,7 @@ pub async fn find_conversation_path_by_id_str(
         return Ok(None);
     }
 
-    let mut root = codex_home.to_path_buf();
+    let mut root = codex_state_home(codex_home);
     root.push(SESSIONS_SUBDIR);
     if !root.exists() {
         return Ok(None);
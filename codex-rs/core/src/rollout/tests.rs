@@ -234,18 +234,24 @@ async fn test_list_conversations_latest_first() {
                 head: head_3,
                 created_at: Some("2025-01-03T12-00-00".into()),
                 updated_at: updated_times.first().cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
             ConversationItem {
                 path: p2,
                 head: head_2,
                 created_at: Some("2025-01-02T12-00-00".into()),
                 updated_at: updated_times.get(1).cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
             ConversationItem {
                 path: p3,
                 head: head_1,
                 created_at: Some("2025-01-01T12-00-00".into()),
                 updated_at: updated_times.get(2).cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
         ],
         next_cursor: None,
@@ -364,12 +370,16 @@ async fn test_pagination_cursor() {
                 head: head_5,
                 created_at: Some("2025-03-05T09-00-00".into()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
             ConversationItem {
                 path: p4,
                 head: head_4,
                 created_at: Some("2025-03-04T09-00-00".into()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -431,12 +441,16 @@ async fn test_pagination_cursor() {
                 head: head_3,
                 created_at: Some("2025-03-03T09-00-00".into()),
                 updated_at: updated_page2.first().cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
             ConversationItem {
                 path: p2,
                 head: head_2,
                 created_at: Some("2025-03-02T09-00-00".into()),
                 updated_at: updated_page2.get(1).cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
         ],
         next_cursor: Some(expected_cursor2.clone()),
@@ -479,6 +493,8 @@ async fn test_pagination_cursor() {
             head: head_1,
             created_at: Some("2025-03-01T09-00-00".into()),
             updated_at: updated_page3.first().cloned().flatten(),
+            total_tokens: None,
+            title: None,
         }],
         next_cursor: None,
         num_scanned_files: 5, // scanned 05, 04 (anchor), 03, 02 (anchor), 01
@@ -534,6 +550,8 @@ async fn test_get_conversation_contents() {
             head: expected_head,
             created_at: Some(ts.into()),
             updated_at: page.items[0].updated_at.clone(),
+            total_tokens: None,
+            title: None,
         }],
         next_cursor: None,
         num_scanned_files: 1,
@@ -708,12 +726,16 @@ async fn test_stable_ordering_same_second_pagination() {
                 head: head(u3),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
             ConversationItem {
                 path: p2,
                 head: head(u2),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                total_tokens: None,
+                title: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -746,6 +768,8 @@ async fn test_stable_ordering_same_second_pagination() {
             head: head(u1),
             created_at: Some(ts.to_string()),
             updated_at: updated_page2.first().cloned().flatten(),
+            total_tokens: None,
+            title: None,
         }],
         next_cursor: None,
         num_scanned_files: 3, // scanned u3, u2 (anchor), u1
@@ -916,3 +940,100 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_total_tokens_come_from_the_last_token_count() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let ts = "2025-01-04T12-00-00";
+    let uuid = Uuid::from_u128(42);
+    write_session_file(home, ts, uuid, 1, Some(SessionSource::VSCode))?;
+    let path = home
+        .join("sessions")
+        .join("2025")
+        .join("01")
+        .join("04")
+        .join(format!("rollout-{ts}-{uuid}.jsonl"));
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    assert_eq!(page.items[0].total_tokens, None);
+
+    let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+    for (input, output) in [(1_000, 200), (5_000, 700)] {
+        let usage = serde_json::json!({
+            "input_tokens": input,
+            "cached_input_tokens": 400,
+            "output_tokens": output,
+            "reasoning_output_tokens": 0,
+            "total_tokens": input + output,
+        });
+        let token_count = serde_json::json!({
+            "timestamp": ts,
+            "type": "event_msg",
+            "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": usage,
+                    "last_token_usage": usage,
+                    "model_context_window": null,
+                },
+                "rate_limits": null,
+            }
+        });
+        writeln!(file, "{token_count}")?;
+    }
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    // Uncached input plus output of the last count.
+    assert_eq!(page.items[0].total_tokens, Some(5_000 - 400 + 700));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_title_comes_from_the_latest_title_event() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let ts = "2025-01-05T12-00-00";
+    let uuid = Uuid::from_u128(43);
+    write_session_file(home, ts, uuid, 1, Some(SessionSource::VSCode))?;
+    let path = home
+        .join("sessions")
+        .join("2025")
+        .join("01")
+        .join("05")
+        .join(format!("rollout-{ts}-{uuid}.jsonl"));
+    let event_line = |payload: serde_json::Value| {
+        serde_json::json!({
+            "timestamp": ts,
+            "type": "event_msg",
+            "payload": payload,
+        })
+    };
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    assert_eq!(page.items[0].title, None);
+
+    // A title early in a long session is found past the tail read.
+    let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+    let generated = event_line(serde_json::json!({
+        "type": "session_title",
+        "title": "Fix flaky login test",
+    }));
+    writeln!(file, "{generated}")?;
+    let filler = event_line(serde_json::json!({
+        "type": "agent_message",
+        "message": "x".repeat(100 * 1024),
+    }));
+    writeln!(file, "{filler}")?;
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    assert_eq!(page.items[0].title.as_deref(), Some("Fix flaky login test"));
+
+    let renamed = event_line(serde_json::json!({
+        "type": "session_title",
+        "title": "Login test race",
+    }));
+    writeln!(file, "{renamed}")?;
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    assert_eq!(page.items[0].title.as_deref(), Some("Login test race"));
+    Ok(())
+}
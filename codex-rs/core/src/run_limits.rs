@@ -0,0 +1,150 @@
+//! Turn, time, and cost limits for headless runs.
+//!
+//! `codex exec --max-turns`, `--max-duration`, and `--max-cost-usd` set
+//! [`RunLimits`] for the session. Before each model request the turn loop
+//! asks [`RunProgress`] whether a limit has been reached; if so, the task ends
+//! with [`EventMsg::RunLimitReached`] instead of sending the request. The time
+//! limit also cuts short a request or tool call that is running when the
+//! deadline passes.
+//!
+//! [`EventMsg::RunLimitReached`]: crate::protocol::EventMsg::RunLimitReached
+
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::AbortOnDropHandle;
+
+use crate::config::types::RunLimits;
+use crate::protocol::RunLimit;
+use crate::protocol::RunLimitReachedEvent;
+
+/// How much of its limits the session has used.
+#[derive(Debug)]
+pub(crate) struct RunProgress {
+    started: Instant,
+    turns: u32,
+}
+
+impl Default for RunProgress {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            turns: 0,
+        }
+    }
+}
+
+impl RunProgress {
+    /// Accounts for the next model request. `cost_usd` is the session's
+    /// estimated spend so far. Returns the deadline the request must finish
+    /// by, or the limit that stops the task before it.
+    pub(crate) fn next_request(
+        &mut self,
+        limits: &RunLimits,
+        cost_usd: Option<f64>,
+    ) -> Result<Option<Instant>, RunLimitReachedEvent> {
+        if let Some(limit) = self.reached(limits, cost_usd, Instant::now()) {
+            return Err(self.event(limit, cost_usd));
+        }
+        self.turns += 1;
+        Ok(limits.max_duration.map(|duration| self.started + duration))
+    }
+
+    pub(crate) fn event(&self, limit: RunLimit, cost_usd: Option<f64>) -> RunLimitReachedEvent {
+        RunLimitReachedEvent {
+            limit,
+            turns: self.turns,
+            elapsed_seconds: self.started.elapsed().as_secs(),
+            cost_usd,
+        }
+    }
+
+    fn reached(&self, limits: &RunLimits, cost_usd: Option<f64>, now: Instant) -> Option<RunLimit> {
+        if limits
+            .max_duration
+            .is_some_and(|duration| now.duration_since(self.started) >= duration)
+        {
+            Some(RunLimit::Duration)
+        } else if limits
+            .max_cost_usd
+            .zip(cost_usd)
+            .is_some_and(|(max, cost)| cost >= max)
+        {
+            Some(RunLimit::Cost)
+        } else if limits.max_turns.is_some_and(|max| self.turns >= max) {
+            Some(RunLimit::Turns)
+        } else {
+            None
+        }
+    }
+}
+
+/// Cancels `token` at `deadline` unless the returned handle is dropped first.
+pub(crate) fn cancel_at(deadline: Instant, token: CancellationToken) -> AbortOnDropHandle<()> {
+    AbortOnDropHandle::new(tokio::spawn(async move {
+        tokio::time::sleep_until(deadline.into()).await;
+        token.cancel();
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn turn_limit_stops_after_the_allowed_requests() {
+        let limits = RunLimits {
+            max_turns: Some(2),
+            ..RunLimits::default()
+        };
+        let mut progress = RunProgress::default();
+        assert_eq!(progress.next_request(&limits, None), Ok(None));
+        assert_eq!(progress.next_request(&limits, None), Ok(None));
+        let reached = progress
+            .next_request(&limits, None)
+            .expect_err("third request");
+        assert_eq!(reached.limit, RunLimit::Turns);
+        assert_eq!(reached.turns, 2);
+    }
+
+    #[test]
+    fn cost_limit_needs_a_price() {
+        let limits = RunLimits {
+            max_cost_usd: Some(1.0),
+            ..RunLimits::default()
+        };
+        let mut progress = RunProgress::default();
+        assert!(progress.next_request(&limits, None).is_ok());
+        assert!(progress.next_request(&limits, Some(0.5)).is_ok());
+        let reached = progress
+            .next_request(&limits, Some(1.25))
+            .expect_err("over budget");
+        assert_eq!(reached.limit, RunLimit::Cost);
+        assert_eq!(reached.cost_usd, Some(1.25));
+    }
+
+    #[test]
+    fn duration_limit_sets_a_deadline_and_wins_once_it_passes() {
+        let limits = RunLimits {
+            max_turns: Some(1),
+            max_duration: Some(Duration::from_secs(60)),
+            ..RunLimits::default()
+        };
+        let mut progress = RunProgress::default();
+        let deadline = progress
+            .next_request(&limits, None)
+            .expect("first request")
+            .expect("deadline");
+        assert_eq!(deadline, progress.started + Duration::from_secs(60));
+        assert_eq!(
+            progress.reached(&limits, None, deadline),
+            Some(RunLimit::Duration)
+        );
+        assert_eq!(
+            progress.reached(&limits, None, progress.started),
+            Some(RunLimit::Turns)
+        );
+    }
+}
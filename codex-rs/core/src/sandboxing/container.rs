@@ -0,0 +1,356 @@
+//! Container-backed sandbox: with `sandbox.backend = "docker"` or `"podman"`,
+//! each sandboxed command runs in a fresh container from `sandbox.image`.
+//!
+//! The workspace and any other writable roots are bind-mounted at their host
+//! paths, so paths in commands and their output mean the same thing on both
+//! sides. Everything else on the host stays invisible to the command.
+
+use crate::config::types::ContainerRuntime;
+use crate::config::types::ContainerSandboxConfig;
+use crate::config::types::ImagePullPolicy;
+use crate::protocol::SandboxPolicy;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::sync::OnceCell;
+
+/// Host variables that describe the host rather than the command; forwarding
+/// them would break the image's own toolchain and home directory.
+const HOST_ONLY_ENV_VARS: &[&str] = &[
+    "HOME", "HOSTNAME", "OLDPWD", "PATH", "PWD", "SHELL", "TMPDIR", "USER",
+];
+
+#[derive(Debug)]
+pub(crate) struct ContainerSandbox {
+    config: ContainerSandboxConfig,
+    /// Set once the image is in the local image store, so the check (and any
+    /// pull) happens once per session rather than once per command.
+    image_ready: OnceCell<()>,
+}
+
+impl ContainerSandbox {
+    pub(crate) fn new(config: ContainerSandboxConfig) -> Self {
+        Self {
+            config,
+            image_ready: OnceCell::new(),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &ContainerSandboxConfig {
+        &self.config
+    }
+
+    /// Makes sure the image is available, pulling it according to
+    /// `sandbox.pull`. Failures are not cached, so a later command retries.
+    pub(crate) async fn ensure_image(&self) -> Result<(), String> {
+        self.image_ready
+            .get_or_try_init(|| prepare_image(&self.config))
+            .await
+            .map(|_| ())
+    }
+}
+
+async fn prepare_image(config: &ContainerSandboxConfig) -> Result<(), String> {
+    let image = &config.image;
+    if config.pull != ImagePullPolicy::Always
+        && run_runtime(config.runtime, &["image", "inspect", image])
+            .await?
+            .is_ok()
+    {
+        return Ok(());
+    }
+    if config.pull == ImagePullPolicy::Never {
+        return Err(format!(
+            "container image `{image}` is not available locally and `sandbox.pull` is `never`; pull it with `{} pull {image}`",
+            config.runtime.program()
+        ));
+    }
+    run_runtime(config.runtime, &["pull", image])
+        .await?
+        .map_err(|stderr| format!("failed to pull container image `{image}`: {stderr}"))
+}
+
+/// Runs the runtime's client. The outer error means the client could not be
+/// started at all; the inner one carries its stderr when it failed.
+async fn run_runtime(
+    runtime: ContainerRuntime,
+    args: &[&str],
+) -> Result<Result<(), String>, String> {
+    let program = runtime.program();
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => {
+                format!("`sandbox.backend` is `{program}`, but `{program}` was not found on PATH")
+            }
+            _ => format!("failed to run `{program}`: {err}"),
+        })?;
+    if output.status.success() {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .to_string()))
+    }
+}
+
+/// Builds the full `docker run`/`podman run` invocation for `command`.
+///
+/// Mounts mirror `sandbox_policy`: writable roots are mounted read-write with
+/// their read-only subpaths (such as `.git`) mounted read-only on top, and
+/// under `read-only` the workspace is mounted read-only. The network is
+/// disabled unless the policy grants it. Only the names of `env` appear in
+/// the arguments; the runtime copies their values from its own environment.
+pub(crate) fn create_container_command_args(
+    config: &ContainerSandboxConfig,
+    command: Vec<String>,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_policy_cwd: &Path,
+    command_cwd: &Path,
+    env: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut args = vec![
+        config.runtime.program().to_string(),
+        "run".to_string(),
+        "--rm".to_string(),
+        "--interactive".to_string(),
+        "--init".to_string(),
+    ];
+    if !sandbox_policy.has_full_network_access() {
+        args.push("--network=none".to_string());
+    }
+    args.extend(user_args(config.runtime));
+
+    let mut mounts: Vec<(PathBuf, bool)> = Vec::new();
+    if sandbox_policy.has_full_disk_write_access() {
+        mounts.push((sandbox_policy_cwd.to_path_buf(), false));
+    } else {
+        let writable_roots = sandbox_policy.get_writable_roots_with_cwd(sandbox_policy_cwd);
+        if !writable_roots
+            .iter()
+            .any(|root| sandbox_policy_cwd.starts_with(root.root.as_path()))
+        {
+            mounts.push((sandbox_policy_cwd.to_path_buf(), true));
+        }
+        for root in writable_roots {
+            if !root.root.as_path().exists() {
+                continue;
+            }
+            mounts.push((root.root.to_path_buf(), false));
+            mounts.extend(
+                root.read_only_subpaths
+                    .iter()
+                    .filter(|subpath| subpath.as_path().exists())
+                    .map(|subpath| (subpath.to_path_buf(), true)),
+            );
+        }
+    }
+    if !mounts.iter().any(|(path, _)| command_cwd.starts_with(path)) {
+        mounts.push((command_cwd.to_path_buf(), true));
+    }
+    for (path, read_only) in mounts {
+        let path = path.to_string_lossy();
+        let suffix = if read_only { ":ro" } else { "" };
+        args.push(format!("--volume={path}:{path}{suffix}"));
+    }
+    args.push(format!("--workdir={}", command_cwd.to_string_lossy()));
+
+    let mut names: Vec<&String> = env
+        .keys()
+        .filter(|name| !HOST_ONLY_ENV_VARS.contains(&name.as_str()))
+        .collect();
+    names.sort();
+    for name in names {
+        args.push(format!("--env={name}"));
+    }
+
+    args.push(config.image.clone());
+    args.extend(container_command(command));
+    args
+}
+
+/// Runs the container as the invoking user so files it creates in the
+/// workspace are not owned by root.
+#[cfg(unix)]
+fn user_args(runtime: ContainerRuntime) -> Vec<String> {
+    // SAFETY: getuid and getgid cannot fail and have no preconditions.
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    if uid == 0 {
+        return Vec::new();
+    }
+    match runtime {
+        ContainerRuntime::Docker => vec![format!("--user={uid}:{gid}")],
+        // Rootless Podman maps the invoking user into the container itself.
+        ContainerRuntime::Podman => vec!["--userns=keep-id".to_string()],
+    }
+}
+
+#[cfg(not(unix))]
+fn user_args(_runtime: ContainerRuntime) -> Vec<String> {
+    Vec::new()
+}
+
+/// The image has its own filesystem layout, so a program given by host path
+/// (such as the user's shell, `/opt/homebrew/bin/bash`) is looked up on the
+/// image's `PATH` by name instead.
+fn container_command(mut command: Vec<String>) -> Vec<String> {
+    if let Some(program) = command.first_mut()
+        && Path::new(program.as_str()).is_absolute()
+        && let Some(name) = Path::new(program.as_str())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    {
+        *program = name;
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn config(runtime: ContainerRuntime) -> ContainerSandboxConfig {
+        ContainerSandboxConfig {
+            runtime,
+            image: "rust:1.90".to_string(),
+            pull: ImagePullPolicy::Missing,
+        }
+    }
+
+    fn command() -> Vec<String> {
+        vec![
+            "/bin/bash".to_string(),
+            "-lc".to_string(),
+            "cargo test".to_string(),
+        ]
+    }
+
+    /// The arguments after the user mapping, which depends on who runs the
+    /// tests.
+    fn without_user_args(args: Vec<String>) -> Vec<String> {
+        args.into_iter()
+            .filter(|arg| !arg.starts_with("--user"))
+            .collect()
+    }
+
+    #[test]
+    fn workspace_write_mounts_writable_roots_and_protects_git() {
+        let workspace = tempdir().expect("workspace");
+        let cwd = workspace.path();
+        std::fs::create_dir(cwd.join(".git")).expect("mkdir .git");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        let env = HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("RUST_LOG".to_string(), "debug".to_string()),
+        ]);
+
+        let args = create_container_command_args(
+            &config(ContainerRuntime::Docker),
+            command(),
+            &policy,
+            cwd,
+            cwd,
+            &env,
+        );
+
+        let cwd = cwd.to_string_lossy();
+        assert_eq!(
+            without_user_args(args),
+            vec![
+                "docker".to_string(),
+                "run".to_string(),
+                "--rm".to_string(),
+                "--interactive".to_string(),
+                "--init".to_string(),
+                "--network=none".to_string(),
+                format!("--volume={cwd}:{cwd}"),
+                format!("--volume={cwd}/.git:{cwd}/.git:ro"),
+                format!("--workdir={cwd}"),
+                "--env=RUST_LOG".to_string(),
+                "rust:1.90".to_string(),
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cargo test".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_only_mounts_the_workspace_read_only() {
+        let workspace = tempdir().expect("workspace");
+        let cwd = workspace.path();
+        let subdir = cwd.join("crates");
+
+        let args = create_container_command_args(
+            &config(ContainerRuntime::Podman),
+            vec!["ls".to_string()],
+            &SandboxPolicy::ReadOnly,
+            cwd,
+            &subdir,
+            &HashMap::new(),
+        );
+
+        let cwd = cwd.to_string_lossy();
+        let subdir = subdir.to_string_lossy();
+        assert_eq!(
+            without_user_args(args),
+            vec![
+                "podman".to_string(),
+                "run".to_string(),
+                "--rm".to_string(),
+                "--interactive".to_string(),
+                "--init".to_string(),
+                "--network=none".to_string(),
+                format!("--volume={cwd}:{cwd}:ro"),
+                format!("--workdir={subdir}"),
+                "rust:1.90".to_string(),
+                "ls".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn network_access_and_extra_roots_follow_the_policy() {
+        let workspace = tempdir().expect("workspace");
+        let cache = tempdir().expect("cache");
+        let cwd = workspace.path();
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![
+                AbsolutePathBuf::from_absolute_path(cache.path()).expect("absolute"),
+            ],
+            network_access: true,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let args = create_container_command_args(
+            &config(ContainerRuntime::Docker),
+            vec!["true".to_string()],
+            &policy,
+            cwd,
+            cwd,
+            &HashMap::new(),
+        );
+
+        assert!(!args.iter().any(|arg| arg.starts_with("--network")));
+        let cache = cache.path().to_string_lossy();
+        assert!(
+            args.contains(&format!("--volume={cache}:{cache}")),
+            "missing cache mount: {args:?}"
+        );
+    }
+}
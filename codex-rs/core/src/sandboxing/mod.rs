@@ -26,6 +26,14 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+mod container;
+mod network_proxy;
+
+pub(crate) use container::ContainerSandbox;
+use container::create_container_command_args;
+pub(crate) use network_proxy::SandboxNetworkProxy;
+pub(crate) use network_proxy::domain_covers;
+
 #[derive(Debug)]
 pub struct CommandSpec {
     pub program: String,
@@ -59,6 +67,8 @@ pub enum SandboxPreference {
 pub(crate) enum SandboxTransformError {
     #[error("missing codex-linux-sandbox executable path")]
     MissingLinuxSandboxExecutable,
+    #[error("container sandbox requested without `sandbox.backend`")]
+    MissingContainerSandbox,
     #[cfg(not(target_os = "macos"))]
     #[error("seatbelt sandbox is only available on macOS")]
     SeatbeltUnavailable,
@@ -72,21 +82,33 @@ impl SandboxManager {
         Self
     }
 
+    /// Picks the sandbox for a first attempt. `container` is the session's
+    /// container sandbox, when `sandbox.backend` selects one and the tool can
+    /// run inside it; it replaces the platform sandbox.
     pub(crate) fn select_initial(
         &self,
         policy: &SandboxPolicy,
         pref: SandboxablePreference,
+        container: Option<&ContainerSandbox>,
     ) -> SandboxType {
+        let sandbox = || {
+            if container.is_some() {
+                SandboxType::Container
+            } else {
+                crate::safety::get_platform_sandbox().unwrap_or(SandboxType::None)
+            }
+        };
         match pref {
             SandboxablePreference::Forbid => SandboxType::None,
             SandboxablePreference::Require => {
-                // Require a platform sandbox when available; on Windows this
-                // respects the experimental_windows_sandbox feature.
-                crate::safety::get_platform_sandbox().unwrap_or(SandboxType::None)
+                // Require a sandbox when available; for the platform sandbox
+                // on Windows this respects the experimental_windows_sandbox
+                // feature.
+                sandbox()
             }
             SandboxablePreference::Auto => match policy {
                 SandboxPolicy::DangerFullAccess => SandboxType::None,
-                _ => crate::safety::get_platform_sandbox().unwrap_or(SandboxType::None),
+                _ => sandbox(),
             },
         }
     }
@@ -98,6 +120,8 @@ impl SandboxManager {
         sandbox: SandboxType,
         sandbox_policy_cwd: &Path,
         codex_linux_sandbox_exe: Option<&PathBuf>,
+        network_proxy: Option<&SandboxNetworkProxy>,
+        container: Option<&ContainerSandbox>,
     ) -> Result<ExecEnv, SandboxTransformError> {
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -107,6 +131,16 @@ impl SandboxManager {
             );
         }
 
+        // Only Seatbelt can confine commands to the proxy's address. Landlock
+        // matches ports, not addresses, so elsewhere, containers included,
+        // the network stays blocked outright.
+        let network_proxy = network_proxy.filter(|_| {
+            !policy.has_full_network_access() && matches!(sandbox, SandboxType::MacosSeatbelt)
+        });
+        if let Some(network_proxy) = network_proxy {
+            network_proxy.apply_env(&mut env);
+        }
+
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
@@ -117,8 +151,12 @@ impl SandboxManager {
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy.map(SandboxNetworkProxy::port),
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -148,6 +186,18 @@ impl SandboxManager {
             // When building for non-Windows targets, this variant is never constructed.
             #[cfg(not(target_os = "windows"))]
             SandboxType::WindowsRestrictedToken => (command, HashMap::new(), None),
+            SandboxType::Container => {
+                let container = container.ok_or(SandboxTransformError::MissingContainerSandbox)?;
+                let full_command = create_container_command_args(
+                    container.config(),
+                    command,
+                    policy,
+                    sandbox_policy_cwd,
+                    &spec.cwd,
+                    &env,
+                );
+                (full_command, HashMap::new(), None)
+            }
         };
 
         env.extend(sandbox_env);
@@ -0,0 +1,523 @@
+//! The forwarding proxy behind `network.allowed_domains`.
+//!
+//! When the sandbox blocks network access but the config lists domains that
+//! commands may still reach, the session runs this proxy on loopback.
+//! Sandboxed commands get the `HTTPS_PROXY` family of variables pointing at
+//! it, and the platform sandbox only lets them connect to its port. The proxy
+//! tunnels `CONNECT` requests and forwards plain HTTP requests to allowed
+//! domains and answers everything else with `403`, so `cargo build` can fetch
+//! crates while other destinations stay unreachable. The `network` rules of
+//! `.codex/approvals.toml` are checked first and can allow or refuse a domain
+//! before the allowlist is consulted.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+
+use crate::approvals_policy::ApprovalsPolicy;
+use crate::approvals_policy::PolicyAction;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+use tracing::info;
+
+/// Proxy variables set for sandboxed commands. Both spellings are set because
+/// tools disagree on which one they read.
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+
+/// Removed so that no host bypasses the proxy; a direct connection would be
+/// blocked by the sandbox anyway.
+const NO_PROXY_ENV_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+
+/// Request heads larger than this are rejected.
+const MAX_REQUEST_HEAD_BYTES: usize = 64 * 1024;
+
+/// A running proxy. It stops when dropped.
+#[derive(Debug)]
+pub(crate) struct SandboxNetworkProxy {
+    port: u16,
+    cancel: CancellationToken,
+}
+
+impl SandboxNetworkProxy {
+    /// Starts a proxy on `127.0.0.1` that only forwards to
+    /// `allowed_domains` and their subdomains, and to whatever the network
+    /// rules of `approvals_policy` allow.
+    pub(crate) async fn start(
+        allowed_domains: &[String],
+        approvals_policy: Option<ApprovalsPolicy>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let port = listener.local_addr()?.port();
+        let allowlist = Arc::new(DomainAllowlist::new(allowed_domains, approvals_policy));
+        info!(
+            "sandbox network proxy on 127.0.0.1:{port} allows {:?}",
+            allowlist.domains
+        );
+
+        let cancel = CancellationToken::new();
+        let stopped = cancel.clone();
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = stopped.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            debug!("sandbox network proxy failed to accept: {err}");
+                            continue;
+                        }
+                    },
+                };
+                let allowlist = Arc::clone(&allowlist);
+                let stopped = stopped.clone();
+                tokio::spawn(async move {
+                    tokio::select! {
+                        _ = stopped.cancelled() => {}
+                        result = handle_connection(stream, &allowlist) => {
+                            if let Err(err) = result {
+                                debug!("sandbox network proxy connection failed: {err}");
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(Self { port, cancel })
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Points the proxy variables in `env` at this proxy.
+    pub(crate) fn apply_env(&self, env: &mut HashMap<String, String>) {
+        let url = format!("http://127.0.0.1:{}", self.port);
+        for name in PROXY_ENV_VARS {
+            env.insert(name.to_string(), url.clone());
+        }
+        for name in NO_PROXY_ENV_VARS {
+            env.remove(name);
+        }
+    }
+}
+
+impl Drop for SandboxNetworkProxy {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Domains the proxy forwards to. An entry matches itself and its
+/// subdomains, so `github.com` also allows `api.github.com`.
+#[derive(Debug)]
+struct DomainAllowlist {
+    domains: Vec<String>,
+    approvals_policy: Option<ApprovalsPolicy>,
+}
+
+impl DomainAllowlist {
+    fn new(domains: &[String], approvals_policy: Option<ApprovalsPolicy>) -> Self {
+        let domains = domains
+            .iter()
+            .map(|domain| normalize_domain(domain))
+            .filter(|domain| !domain.is_empty())
+            .collect();
+        Self {
+            domains,
+            approvals_policy,
+        }
+    }
+
+    /// Ok when requests to `host` may be forwarded; otherwise the reason sent
+    /// back with the `403`.
+    fn check(&self, host: &str) -> Result<(), String> {
+        if let Some(verdict) = self
+            .approvals_policy
+            .as_ref()
+            .and_then(|policy| policy.check_host(host))
+        {
+            return match verdict.action {
+                PolicyAction::Allow => Ok(()),
+                PolicyAction::Deny => Err(format!("codex sandbox: {}", verdict.reason)),
+                PolicyAction::Ask => Err(format!(
+                    "codex sandbox: {}; rerun the command with escalated permissions to ask the user",
+                    verdict.reason
+                )),
+            };
+        }
+        if self.domains.iter().any(|domain| covers(domain, host)) {
+            Ok(())
+        } else {
+            Err(format!(
+                "codex sandbox: {host} is not in network.allowed_domains"
+            ))
+        }
+    }
+}
+
+/// Whether the domain entry `domain` (`github.com`, `*.github.com`, or
+/// `.github.com`) covers `host`: the domain itself or one of its subdomains.
+pub(crate) fn domain_covers(domain: &str, host: &str) -> bool {
+    let domain = normalize_domain(domain);
+    !domain.is_empty() && covers(&domain, host)
+}
+
+fn covers(domain: &str, host: &str) -> bool {
+    let host = normalize_host(host);
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim();
+    let domain = domain
+        .strip_prefix("*.")
+        .or_else(|| domain.strip_prefix('.'))
+        .unwrap_or(domain);
+    normalize_host(domain)
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// Where a client asked the proxy to connect, and what to send there first.
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyRequest {
+    host: String,
+    port: u16,
+    /// `None` for a `CONNECT` tunnel; otherwise the request head rewritten
+    /// for the origin server.
+    forward_head: Option<String>,
+}
+
+async fn handle_connection(mut client: TcpStream, allowlist: &DomainAllowlist) -> io::Result<()> {
+    let Some((head, rest)) = read_request_head(&mut client).await? else {
+        return Ok(());
+    };
+    let request = match parse_request_head(&head) {
+        Ok(request) => request,
+        Err(reason) => return respond(&mut client, "400 Bad Request", reason).await,
+    };
+    if let Err(reason) = allowlist.check(&request.host) {
+        info!(
+            "sandbox network proxy blocked a request to {}",
+            request.host
+        );
+        return respond(&mut client, "403 Forbidden", &reason).await;
+    }
+
+    let mut upstream = match TcpStream::connect((request.host.as_str(), request.port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let reason = format!(
+                "codex sandbox: failed to connect to {}:{}: {err}",
+                request.host, request.port
+            );
+            return respond(&mut client, "502 Bad Gateway", &reason).await;
+        }
+    };
+    match &request.forward_head {
+        None => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+        }
+        Some(head) => upstream.write_all(head.as_bytes()).await?,
+    }
+    upstream.write_all(&rest).await?;
+    // Whatever else the client sends goes to the same, allowed, host.
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to the end of the request head. Returns the head and any bytes
+/// the client already sent after it, or `None` when the client hung up.
+async fn read_request_head(client: &mut TcpStream) -> io::Result<Option<(String, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            let head = String::from_utf8(buf).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "request head is not UTF-8")
+            })?;
+            return Ok(Some((head, rest)));
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head is too large",
+            ));
+        }
+    }
+}
+
+fn parse_request_head(head: &str) -> Result<ProxyRequest, &'static str> {
+    let (request_line, headers) = head.split_once("\r\n").unwrap_or((head, ""));
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("malformed request line");
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_host_port(target).ok_or("CONNECT needs host:port")?;
+        return Ok(ProxyRequest {
+            host,
+            port,
+            forward_head: None,
+        });
+    }
+
+    let url = url::Url::parse(target).map_err(|_| "expected an absolute http:// URL")?;
+    if url.scheme() != "http" {
+        return Err("only http:// URLs can be forwarded; use CONNECT for https");
+    }
+    let host = url.host_str().ok_or("URL has no host")?.to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    let mut forward_head = format!("{method} {path} {version}\r\n");
+    for line in headers.split("\r\n").filter(|line| !line.is_empty()) {
+        let name = line.split(':').next().unwrap_or_default().trim();
+        if name.eq_ignore_ascii_case("proxy-connection")
+            || name.eq_ignore_ascii_case("proxy-authorization")
+        {
+            continue;
+        }
+        forward_head.push_str(line);
+        forward_head.push_str("\r\n");
+    }
+    forward_head.push_str("\r\n");
+    Ok(ProxyRequest {
+        host: normalize_host(&host),
+        port,
+        forward_head: Some(forward_head),
+    })
+}
+
+fn split_host_port(target: &str) -> Option<(String, u16)> {
+    let (host, port) = target.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let host = normalize_host(host);
+    (!host.is_empty()).then_some((host, port))
+}
+
+async fn respond(client: &mut TcpStream, status: &str, reason: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reason}\n",
+        reason.len() + 1
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn allowlist(domains: &[&str]) -> DomainAllowlist {
+        let domains: Vec<String> = domains.iter().map(ToString::to_string).collect();
+        DomainAllowlist::new(&domains, None)
+    }
+
+    #[test]
+    fn allowlist_matches_domains_and_their_subdomains() {
+        let allowlist = allowlist(&["crates.io", "*.github.com", ".Example.ORG."]);
+
+        assert!(allowlist.check("crates.io").is_ok());
+        assert!(allowlist.check("static.crates.io").is_ok());
+        assert!(allowlist.check("CRATES.IO.").is_ok());
+        assert!(allowlist.check("github.com").is_ok());
+        assert!(allowlist.check("api.github.com").is_ok());
+        assert!(allowlist.check("www.example.org").is_ok());
+
+        assert!(allowlist.check("notcrates.io").is_err());
+        assert!(allowlist.check("crates.io.evil.com").is_err());
+        assert!(allowlist.check("githubusercontent.com").is_err());
+        assert!(allowlist.check("127.0.0.1").is_err());
+    }
+
+    #[test]
+    fn approvals_policy_network_rules_come_first() {
+        let policy = crate::approvals_policy::ApprovalsPolicy::new(
+            std::path::PathBuf::from("/repo"),
+            toml::from_str(
+                r#"
+                [[rules]]
+                action = "deny"
+                network = ["gist.github.com"]
+
+                [[rules]]
+                action = "allow"
+                network = ["registry.npmjs.org"]
+                "#,
+            )
+            .expect("valid policy"),
+        )
+        .expect("valid rules");
+        let allowlist = DomainAllowlist::new(&["github.com".to_string()], Some(policy));
+
+        assert!(allowlist.check("api.github.com").is_ok());
+        assert!(allowlist.check("registry.npmjs.org").is_ok());
+        assert_eq!(
+            allowlist.check("gist.github.com"),
+            Err(
+                "codex sandbox: rule 1 in .codex/approvals.toml denies `gist.github.com`"
+                    .to_string()
+            )
+        );
+        assert!(allowlist.check("example.com").is_err());
+        assert!(domain_covers("*.npmjs.org", "registry.npmjs.org"));
+        assert!(!domain_covers("", "registry.npmjs.org"));
+    }
+
+    #[test]
+    fn parses_connect_requests() {
+        let request = parse_request_head(
+            "CONNECT index.crates.io:443 HTTP/1.1\r\nHost: index.crates.io:443\r\n\r\n",
+        );
+
+        assert_eq!(
+            request,
+            Ok(ProxyRequest {
+                host: "index.crates.io".to_string(),
+                port: 443,
+                forward_head: None,
+            })
+        );
+        assert_eq!(
+            parse_request_head("CONNECT [::1]:8443 HTTP/1.1\r\n\r\n").map(|request| request.host),
+            Ok("::1".to_string())
+        );
+        assert!(parse_request_head("CONNECT crates.io HTTP/1.1\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn rewrites_plain_http_requests_for_the_origin() {
+        let request = parse_request_head(
+            "GET http://example.com:8080/a/b?c=d HTTP/1.1\r\nHost: example.com:8080\r\nProxy-Connection: keep-alive\r\nAccept: */*\r\n\r\n",
+        );
+
+        assert_eq!(
+            request,
+            Ok(ProxyRequest {
+                host: "example.com".to_string(),
+                port: 8080,
+                forward_head: Some(
+                    "GET /a/b?c=d HTTP/1.1\r\nHost: example.com:8080\r\nAccept: */*\r\n\r\n"
+                        .to_string()
+                ),
+            })
+        );
+        assert!(parse_request_head("GET /relative HTTP/1.1\r\n\r\n").is_err());
+        assert!(parse_request_head("GET https://example.com/ HTTP/1.1\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn apply_env_routes_everything_through_the_proxy() {
+        let proxy = SandboxNetworkProxy {
+            port: 4321,
+            cancel: CancellationToken::new(),
+        };
+        let mut env = HashMap::from([
+            ("NO_PROXY".to_string(), "localhost".to_string()),
+            ("https_proxy".to_string(), "http://corp:3128".to_string()),
+        ]);
+
+        proxy.apply_env(&mut env);
+
+        assert_eq!(
+            env.get("https_proxy").map(String::as_str),
+            Some("http://127.0.0.1:4321")
+        );
+        assert_eq!(
+            env.get("ALL_PROXY").map(String::as_str),
+            Some("http://127.0.0.1:4321")
+        );
+        assert_eq!(env.get("NO_PROXY"), None);
+    }
+
+    #[tokio::test]
+    async fn tunnels_to_allowed_hosts_and_refuses_others() {
+        let upstream = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("bind upstream");
+        let upstream_port = upstream.local_addr().expect("upstream addr").port();
+        tokio::spawn(async move {
+            let (mut stream, _) = upstream.accept().await.expect("accept");
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).await.expect("read ping");
+            stream.write_all(b"pong").await.expect("write pong");
+        });
+        let proxy = SandboxNetworkProxy::start(&["localhost".to_string()], None)
+            .await
+            .expect("start proxy");
+
+        let mut client = TcpStream::connect((Ipv4Addr::LOCALHOST, proxy.port()))
+            .await
+            .expect("connect to proxy");
+        client
+            .write_all(format!("CONNECT localhost:{upstream_port} HTTP/1.1\r\n\r\nping").as_bytes())
+            .await
+            .expect("send CONNECT");
+        let mut response = vec![0u8; "HTTP/1.1 200 Connection Established\r\n\r\npong".len()];
+        client.read_exact(&mut response).await.expect("read tunnel");
+        assert_eq!(
+            String::from_utf8_lossy(&response),
+            "HTTP/1.1 200 Connection Established\r\n\r\npong"
+        );
+
+        let mut denied = TcpStream::connect((Ipv4Addr::LOCALHOST, proxy.port()))
+            .await
+            .expect("connect to proxy");
+        denied
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n")
+            .await
+            .expect("send CONNECT");
+        let mut response = String::new();
+        denied
+            .read_to_string(&mut response)
+            .await
+            .expect("read refusal");
+        assert!(
+            response.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+            "{response}"
+        );
+        assert!(
+            response.ends_with("codex sandbox: example.com is not in network.allowed_domains\n"),
+            "{response}"
+        );
+    }
+}
@@ -13,6 +13,8 @@ use crate::spawn::spawn_child_async;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
+const MACOS_SEATBELT_NETWORK_PROXY_POLICY: &str =
+    include_str!("seatbelt_network_proxy_policy.sbpl");
 
 /// When working with `sandbox-exec`, only consider `sandbox-exec` in `/usr/bin`
 /// to defend against an attacker trying to inject a malicious version on the
@@ -28,7 +30,7 @@ pub async fn spawn_command_under_seatbelt(
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd, None);
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
     spawn_child_async(
@@ -43,10 +45,13 @@ pub async fn spawn_command_under_seatbelt(
     .await
 }
 
+/// With `network_proxy_port`, commands may connect to that loopback port even
+/// though the policy blocks network access.
 pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy_port: Option<u16>,
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -113,9 +118,13 @@ pub(crate) fn create_seatbelt_command_args(
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        MACOS_SEATBELT_NETWORK_POLICY
+        MACOS_SEATBELT_NETWORK_POLICY.to_string()
+    } else if let Some(port) = network_proxy_port {
+        format!(
+            "(allow network-outbound (remote ip \"localhost:{port}\"))\n{MACOS_SEATBELT_NETWORK_PROXY_POLICY}"
+        )
     } else {
-        ""
+        String::new()
     };
 
     let full_policy = format!(
@@ -217,7 +226,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd, None);
 
         // Build the expected policy text using a raw string for readability.
         // Note that the policy includes:
@@ -308,7 +317,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let write_hooks_file_args = create_seatbelt_command_args(shell_command_git, &policy, &cwd);
+        let write_hooks_file_args =
+            create_seatbelt_command_args(shell_command_git, &policy, &cwd, None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_hooks_file_args)
             .current_dir(&cwd)
@@ -345,7 +355,7 @@ mod tests {
         .map(std::string::ToString::to_string)
         .collect();
         let write_allowed_file_args =
-            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd);
+            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd, None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_allowed_file_args)
             .current_dir(&cwd)
@@ -400,8 +410,12 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args =
-            create_seatbelt_command_args(shell_command.clone(), &policy, vulnerable_root.as_path());
+        let args = create_seatbelt_command_args(
+            shell_command.clone(),
+            &policy,
+            vulnerable_root.as_path(),
+            None,
+        );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
             .ok()
@@ -470,6 +484,34 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn network_proxy_port_allows_only_that_port() {
+        let cwd = TempDir::new().expect("tempdir");
+        let command = vec!["true".to_string()];
+
+        let args = create_seatbelt_command_args(
+            command.clone(),
+            &SandboxPolicy::ReadOnly,
+            cwd.path(),
+            Some(4321),
+        );
+        assert!(
+            args[1].contains("(allow network-outbound (remote ip \"localhost:4321\"))"),
+            "{}",
+            args[1]
+        );
+        assert!(!args[1].contains("(allow network-outbound)"));
+
+        let args = create_seatbelt_command_args(
+            command,
+            &SandboxPolicy::DangerFullAccess,
+            cwd.path(),
+            Some(4321),
+        );
+        assert!(args[1].contains("(allow network-outbound)"));
+        assert!(!args[1].contains("localhost:4321"));
+    }
+
     struct PopulatedTmp {
         /// Path containing a .git and .codex subfolder.
         /// For the purposes of this test, we consider this a "vulnerable" root
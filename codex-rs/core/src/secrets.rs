@@ -0,0 +1,264 @@
+//! Secrets resolved by running a command, such as a provider's
+//! `api_key_cmd = ["op", "read", "op://vault/openai/key"]`.
+//!
+//! Each command runs at most once per process: its output is cached for the
+//! process lifetime, so a password manager prompts only once. Async callers
+//! use [`resolve_secret_cmd_async`], which runs the command on the blocking
+//! pool. Every resolved value is also remembered for [`redact`], which error
+//! messages and log writers pass their text through so the secret never
+//! reaches a log file or the UI.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+/// Replaces every resolved secret in redacted text.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Resolved values by command. Each command has its own slot, locked while
+/// the command runs so concurrent callers wait for that run instead of
+/// prompting again, without blocking callers of other commands.
+static CACHE: LazyLock<Mutex<HashMap<Vec<String>, Arc<Mutex<Option<String>>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Every value resolved so far, for [`redact`].
+static RESOLVED: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum SecretCmdError {
+    #[error("secret command is empty")]
+    Empty,
+
+    #[error("failed to run secret command `{program}`: {message}")]
+    Spawn { program: String, message: String },
+
+    #[error("secret command `{program}` exited with {status}: {stderr}")]
+    Failed {
+        program: String,
+        status: String,
+        stderr: String,
+    },
+
+    #[error("secret command `{program}` printed nothing")]
+    NoOutput { program: String },
+}
+
+/// Runs `argv` and returns what it prints on stdout, without the trailing
+/// newline. The result is cached, so later calls with the same command return
+/// it without running anything. This blocks while the command runs; async
+/// code should call [`resolve_secret_cmd_async`].
+pub fn resolve_secret_cmd(argv: &[String]) -> Result<String, SecretCmdError> {
+    let Some((program, args)) = argv.split_first() else {
+        return Err(SecretCmdError::Empty);
+    };
+    let slot = Arc::clone(lock(&CACHE).entry(argv.to_vec()).or_default());
+    let mut cached = lock(&slot);
+    if let Some(secret) = cached.as_ref() {
+        return Ok(secret.clone());
+    }
+
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| SecretCmdError::Spawn {
+            program: program.clone(),
+            message: err.to_string(),
+        })?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let secret = stdout.trim_end_matches(['\r', '\n']);
+    if !output.status.success() {
+        // The command may echo what it printed on stdout into its error.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = if secret.is_empty() {
+            stderr.trim().to_string()
+        } else {
+            stderr.trim().replace(secret, REDACTED)
+        };
+        return Err(SecretCmdError::Failed {
+            program: program.clone(),
+            status: output.status.to_string(),
+            stderr,
+        });
+    }
+    if secret.trim().is_empty() {
+        return Err(SecretCmdError::NoOutput {
+            program: program.clone(),
+        });
+    }
+
+    let secret = secret.to_string();
+    lock(&RESOLVED).push(secret.clone());
+    *cached = Some(secret.clone());
+    Ok(secret)
+}
+
+/// [`resolve_secret_cmd`] on the blocking thread pool, so a slow command (or
+/// one waiting on a password manager prompt) never stalls the runtime.
+pub async fn resolve_secret_cmd_async(argv: &[String]) -> Result<String, SecretCmdError> {
+    let argv = argv.to_vec();
+    let program = argv.first().cloned().unwrap_or_default();
+    tokio::task::spawn_blocking(move || resolve_secret_cmd(&argv))
+        .await
+        .unwrap_or_else(|err| {
+            Err(SecretCmdError::Spawn {
+                program,
+                message: err.to_string(),
+            })
+        })
+}
+
+/// `text` with every secret resolved so far replaced by [`REDACTED`].
+pub fn redact(text: &str) -> Cow<'_, str> {
+    let resolved = lock(&RESOLVED);
+    let mut text = Cow::Borrowed(text);
+    for secret in resolved.iter() {
+        if text.contains(secret.as_str()) {
+            text = Cow::Owned(text.replace(secret.as_str(), REDACTED));
+        }
+    }
+    text
+}
+
+/// An [`io::Write`] that redacts resolved secrets before passing output on,
+/// for wrapping log writers. Redaction works on each `write` call, which is
+/// one formatted record for `tracing` writers.
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: io::Write> RedactingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => {
+                if let Cow::Owned(redacted) = redact(text) {
+                    self.inner.write_all(redacted.as_bytes())?;
+                    return Ok(buf.len());
+                }
+                self.inner.write(buf)
+            }
+            Err(_) => self.inner.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn resolves_once_and_redacts_the_value() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let counter = dir.path().join("runs");
+        let argv = sh(&format!(
+            "echo run >> '{}'; echo sk-secret-from-vault",
+            counter.display()
+        ));
+
+        assert_eq!(
+            resolve_secret_cmd(&argv),
+            Ok("sk-secret-from-vault".to_string())
+        );
+        assert_eq!(
+            resolve_secret_cmd(&argv),
+            Ok("sk-secret-from-vault".to_string())
+        );
+        assert_eq!(
+            std::fs::read_to_string(&counter).expect("read counter"),
+            "run\n"
+        );
+
+        assert_eq!(
+            redact("401 for key sk-secret-from-vault"),
+            "401 for key [REDACTED]"
+        );
+        let mut log = RedactingWriter::new(Vec::new());
+        write!(log, "bearer sk-secret-from-vault").expect("write");
+        assert_eq!(log.inner, b"bearer [REDACTED]");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn async_callers_share_a_run_without_blocking_other_commands() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let counter = dir.path().join("runs");
+        let slow = sh(&format!(
+            "echo run >> '{}'; sleep 2; echo sk-slow-secret",
+            counter.display()
+        ));
+
+        let first = tokio::spawn({
+            let slow = slow.clone();
+            async move { resolve_secret_cmd_async(&slow).await }
+        });
+        let second = tokio::spawn({
+            let slow = slow.clone();
+            async move { resolve_secret_cmd_async(&slow).await }
+        });
+        // Another command resolves while the slow one is still running.
+        let fast = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            resolve_secret_cmd_async(&sh("echo sk-fast-secret")),
+        )
+        .await
+        .expect("not blocked by the slow command");
+        assert_eq!(fast, Ok("sk-fast-secret".to_string()));
+
+        for handle in [first, second] {
+            assert_eq!(
+                handle.await.expect("join"),
+                Ok("sk-slow-secret".to_string())
+            );
+        }
+        assert_eq!(
+            std::fs::read_to_string(&counter).expect("read counter"),
+            "run\n"
+        );
+        assert_eq!(redact("sk-fast-secret"), REDACTED);
+    }
+
+    #[test]
+    fn failures_keep_the_output_out_of_the_error() {
+        assert_eq!(resolve_secret_cmd(&[]), Err(SecretCmdError::Empty));
+        assert_eq!(
+            resolve_secret_cmd(&sh("echo sk-leaked; echo 'denied: sk-leaked' >&2; exit 3")),
+            Err(SecretCmdError::Failed {
+                program: "sh".to_string(),
+                status: "exit status: 3".to_string(),
+                stderr: "denied: [REDACTED]".to_string(),
+            })
+        );
+        assert_eq!(
+            resolve_secret_cmd(&sh("true")),
+            Err(SecretCmdError::NoOutput {
+                program: "sh".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Short titles that tell sessions apart in the resume picker and the TUI
+//! header (`[session_titles]`).
+//!
+//! When the first turn finishes, the cheap `session_titles.model` names the
+//! session from its opening exchange. Titles go out as
+//! [`EventMsg::SessionTitle`], which the rollout records, so the picker can
+//! list them without replaying the session. `Op::SetSessionTitle` renames the
+//! session at any time, and a generated title never replaces a rename.
+
+use std::sync::Arc;
+
+use codex_protocol::items::AgentMessageContent;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionSource;
+use tracing::debug;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::event_mapping::parse_turn_item;
+use crate::idle_summarization::request_text;
+use crate::idle_summarization::summarization_client;
+use crate::protocol::EventMsg;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+const TITLE_PROMPT: &str = include_str!("../templates/session_title/prompt.md");
+/// Upper bound on each side of the exchange sent to the title model.
+const MAX_EXCHANGE_TOKENS: usize = 1_000;
+/// Titles longer than this are cut at a word boundary.
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Names the session in the background after a finished turn, unless titles
+/// are off, the session has one already, or a request is in flight. Only
+/// interactive sessions get titles; `exec` runs and sub-agents are not
+/// browsed.
+pub(crate) async fn maybe_generate(sess: &Arc<Session>, turn_context: Arc<TurnContext>) {
+    let config = turn_context.client.config();
+    let interactive = matches!(
+        turn_context.client.get_session_source(),
+        SessionSource::Cli | SessionSource::VSCode
+    );
+    if !config.session_titles.enabled || !interactive {
+        return;
+    }
+    let history = sess.clone_history().await.get_history();
+    let Some(exchange) = first_exchange(&history) else {
+        return;
+    };
+    if !sess.claim_title_request().await {
+        return;
+    }
+
+    let sess = Arc::clone(sess);
+    tokio::spawn(async move {
+        let client = summarization_client(&sess, &turn_context, &config.session_titles.model).await;
+        match request_text(&client, TITLE_PROMPT, exchange).await {
+            Ok(reply) => match clean_title(&reply) {
+                Some(title) => {
+                    sess.set_title(turn_context.sub_id.clone(), title, false)
+                        .await;
+                }
+                None => sess.release_title_request().await,
+            },
+            Err(err) => {
+                debug!(%err, "session title request failed");
+                sess.release_title_request().await;
+            }
+        }
+    });
+}
+
+/// The first user request and the assistant reply after it, formatted as
+/// input for the title model.
+fn first_exchange(items: &[ResponseItem]) -> Option<String> {
+    let mut turn_items = items.iter().filter_map(parse_turn_item);
+    let request = turn_items.by_ref().find_map(|item| match item {
+        TurnItem::UserMessage(user) if !user.message().trim().is_empty() => Some(user.message()),
+        _ => None,
+    })?;
+    let reply = turn_items
+        .find_map(|item| match item {
+            TurnItem::AgentMessage(agent) => Some(
+                agent
+                    .content
+                    .iter()
+                    .map(|content| match content {
+                        AgentMessageContent::Text { text }
+                        | AgentMessageContent::Refusal { refusal: text } => text.as_str(),
+                    })
+                    .collect::<String>(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let policy = TruncationPolicy::Tokens(MAX_EXCHANGE_TOKENS);
+    Some(format!(
+        "User:\n{}\n\nAssistant:\n{}",
+        truncate_text(request.trim(), policy),
+        truncate_text(reply.trim(), policy)
+    ))
+}
+
+/// The first line of a model reply without quotes, a `Title:` label, or
+/// trailing punctuation.
+fn clean_title(reply: &str) -> Option<String> {
+    let line = reply.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = line.strip_prefix("Title:").unwrap_or(line);
+    let line = line
+        .trim()
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`' | '*'))
+        .trim_end_matches(|c| matches!(c, '.' | '!' | ':' | ';' | ','));
+    normalize_title(line)
+}
+
+/// Collapses whitespace and cuts `title` to [`MAX_TITLE_CHARS`] at a word
+/// boundary. `None` when nothing is left.
+pub(crate) fn normalize_title(title: &str) -> Option<String> {
+    let mut normalized = String::new();
+    for word in title.split_whitespace() {
+        let separator = usize::from(!normalized.is_empty());
+        if normalized.chars().count() + separator + word.chars().count() > MAX_TITLE_CHARS {
+            if normalized.is_empty() {
+                normalized = word.chars().take(MAX_TITLE_CHARS).collect();
+            }
+            break;
+        }
+        if separator == 1 {
+            normalized.push(' ');
+        }
+        normalized.push_str(word);
+    }
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// The most recent title recorded in a rollout.
+pub(crate) fn latest_title(items: &[RolloutItem]) -> Option<String> {
+    items.iter().rev().find_map(|item| match item {
+        RolloutItem::EventMsg(EventMsg::SessionTitle(event)) => Some(event.title.clone()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::protocol::SessionTitleEvent;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        let text = text.to_string();
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![if role == "assistant" {
+                ContentItem::OutputText { text }
+            } else {
+                ContentItem::InputText { text }
+            }],
+        }
+    }
+
+    #[test]
+    fn cleans_model_replies_into_titles() {
+        assert_eq!(
+            clean_title("\n\"Fix flaky login test.\"\nExtra line"),
+            Some("Fix flaky login test".to_string())
+        );
+        assert_eq!(
+            clean_title("Title: Add CSV export"),
+            Some("Add CSV export".to_string())
+        );
+        assert_eq!(clean_title("  \n\"\"  "), None);
+    }
+
+    #[test]
+    fn normalize_title_cuts_long_titles_at_a_word_boundary() {
+        let long = "word ".repeat(40);
+        let title = normalize_title(&long).expect("title");
+        assert!(title.chars().count() <= MAX_TITLE_CHARS);
+        assert!(title.ends_with("word"));
+        assert_eq!(
+            normalize_title("  Rename   the\tsession "),
+            Some("Rename the session".to_string())
+        );
+    }
+
+    #[test]
+    fn first_exchange_pairs_the_first_request_with_its_reply() {
+        let items = vec![
+            message("user", "why does the login test flake?"),
+            message("assistant", "It races the session cookie."),
+            message("user", "now fix the build"),
+        ];
+        assert_eq!(
+            first_exchange(&items),
+            Some(
+                "User:\nwhy does the login test flake?\n\nAssistant:\nIt races the session cookie."
+                    .to_string()
+            )
+        );
+        assert_eq!(first_exchange(&[message("assistant", "hello")]), None);
+    }
+
+    #[test]
+    fn latest_title_prefers_the_most_recent_rename() {
+        let title = |title: &str| {
+            RolloutItem::EventMsg(EventMsg::SessionTitle(SessionTitleEvent {
+                title: title.to_string(),
+            }))
+        };
+        assert_eq!(
+            latest_title(&[title("Generated"), title("Renamed")]),
+            Some("Renamed".to_string())
+        );
+        assert_eq!(latest_title(&[]), None);
+    }
+}
@@ -2,6 +2,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::config::xdg::codex_cache_home;
 use crate::shell::Shell;
 use crate::shell::ShellType;
 use crate::shell::get_shell;
@@ -27,10 +28,9 @@ impl ShellSnapshot {
             ShellType::PowerShell => "ps1",
             _ => "sh",
         };
-        let path =
-            codex_home
-                .join("shell_snapshots")
-                .join(format!("{}.{}", Uuid::new_v4(), extension));
+        let path = codex_cache_home(codex_home)
+            .join("shell_snapshots")
+            .join(format!("{}.{}", Uuid::new_v4(), extension));
         match write_shell_snapshot(shell.shell_type.clone(), &path).await {
             Ok(path) => {
                 tracing::info!("Shell snapshot successfully created: {}", path.display());
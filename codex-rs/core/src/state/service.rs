@@ -2,12 +2,18 @@ use std::sync::Arc;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
+use crate::connection_prewarm::ConnectionPrewarmer;
+use crate::file_versions::FileVersions;
+use crate::idle_summarization::IdleSummarizer;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::openai_models::models_manager::ModelsManager;
+use crate::sandboxing::ContainerSandbox;
+use crate::sandboxing::SandboxNetworkProxy;
 use crate::skills::SkillsManager;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_notification::UserNotifier;
+use crate::workspace_events::WorkspaceEventBus;
 use codex_otel::otel_manager::OtelManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -16,6 +22,7 @@ use tokio_util::sync::CancellationToken;
 pub(crate) struct SessionServices {
     pub(crate) mcp_connection_manager: Arc<RwLock<McpConnectionManager>>,
     pub(crate) mcp_startup_cancellation_token: CancellationToken,
+    pub(crate) config_reload_cancellation_token: CancellationToken,
     pub(crate) unified_exec_manager: UnifiedExecSessionManager,
     pub(crate) notifier: UserNotifier,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
@@ -26,4 +33,10 @@ pub(crate) struct SessionServices {
     pub(crate) otel_manager: OtelManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
+    pub(crate) connection_prewarmer: ConnectionPrewarmer,
+    pub(crate) idle_summarizer: IdleSummarizer,
+    pub(crate) workspace_events: WorkspaceEventBus,
+    pub(crate) file_versions: FileVersions,
+    pub(crate) sandbox_network_proxy: Option<SandboxNetworkProxy>,
+    pub(crate) container_sandbox: Option<ContainerSandbox>,
 }
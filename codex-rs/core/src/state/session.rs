@@ -1,12 +1,16 @@
 //! Session-wide mutable state.
 
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TurnCapabilityGrant;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::exploration::ExplorationState;
+use crate::protocol::PromptTokenBreakdown;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
+use crate::run_limits::RunProgress;
 use crate::truncate::TruncationPolicy;
 
 /// Persistent, session-scoped state previously stored directly on `Session`.
@@ -14,6 +18,28 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Capabilities granted for the next turn only; drained when it starts.
+    pub(crate) pending_turn_grants: Vec<TurnCapabilityGrant>,
+    /// Whether the most recent turn ran with a sandbox-widening grant, so the
+    /// following turn knows to re-notify MCP servers of the reverted policy.
+    pub(crate) last_turn_sandbox_granted: bool,
+    /// Progress through the read-only exploration phase of autonomous runs.
+    pub(crate) exploration: ExplorationState,
+    /// Whether turns run in read-only plan mode.
+    pub(crate) plan_mode: bool,
+    /// Set when plan mode was toggled; the model is told when the next turn
+    /// starts.
+    pub(crate) plan_mode_update: Option<bool>,
+    /// Requests and time used against `codex exec` run limits.
+    pub(crate) run_progress: RunProgress,
+    /// Steering text reloaded from disk mid-session, recorded when the next
+    /// turn starts.
+    pub(crate) steering_update: Option<String>,
+    /// The session title, whether generated or set with `Op::SetSessionTitle`.
+    pub(crate) title: Option<String>,
+    /// Set once a title request is in flight, so later turns do not start
+    /// another.
+    pub(crate) title_requested: bool,
 }
 
 impl SessionState {
@@ -24,6 +50,15 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            pending_turn_grants: Vec::new(),
+            last_turn_sandbox_granted: false,
+            exploration: ExplorationState::default(),
+            plan_mode: false,
+            plan_mode_update: None,
+            run_progress: RunProgress::default(),
+            steering_update: None,
+            title: None,
+            title_requested: false,
         }
     }
 
@@ -74,6 +109,10 @@ impl SessionState {
         (self.token_info(), self.latest_rate_limits.clone())
     }
 
+    pub(crate) fn prompt_token_breakdown(&self) -> PromptTokenBreakdown {
+        self.history.estimate_prompt_breakdown()
+    }
+
     pub(crate) fn set_token_usage_full(&mut self, context_window: i64) {
         self.history.set_token_usage_full(context_window);
     }
@@ -0,0 +1,297 @@
+//! Machine-enforced rules from the `constraints` section of steering
+//! frontmatter.
+//!
+//! Prose steering only works if the model follows it. Constraints are checked
+//! by core against every patch before it is applied or sent for approval, so a
+//! patch that breaks one is refused no matter what the model intended:
+//!
+//! ```markdown
+//! ---
+//! constraints:
+//!   forbid_paths: ["migrations/**", "*.pem"]
+//!   require_tests_for: ["src/**"]
+//!   max_patch_lines: 400
+//! ---
+//! ```
+//!
+//! Globs are matched against `/`-separated paths relative to the project root,
+//! and `*` matches across `/`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+use serde::Deserialize;
+use wildmatch::WildMatchPattern;
+
+/// The `constraints` section of a steering file's frontmatter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SteeringConstraints {
+    /// Paths the agent may not create, edit, move, or delete.
+    #[serde(default)]
+    pub forbid_paths: Vec<String>,
+    /// A patch that changes one of these paths must also change a test file.
+    #[serde(default)]
+    pub require_tests_for: Vec<String>,
+    /// Largest patch, in added plus removed lines, that may be applied at once.
+    #[serde(default)]
+    pub max_patch_lines: Option<usize>,
+}
+
+impl SteeringConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.forbid_paths.is_empty()
+            && self.require_tests_for.is_empty()
+            && self.max_patch_lines.is_none()
+    }
+}
+
+/// Constraints declared by one steering file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteeringConstraintRule {
+    pub source: PathBuf,
+    pub constraints: SteeringConstraints,
+}
+
+/// Every constraint that applies to a session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SteeringConstraintSet {
+    /// Directory that constraint globs are relative to.
+    root: PathBuf,
+    rules: Vec<SteeringConstraintRule>,
+}
+
+impl SteeringConstraintSet {
+    pub fn new(root: PathBuf, rules: Vec<SteeringConstraintRule>) -> Self {
+        Self { root, rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns why `changes` may not be applied, or `None` when every
+    /// constraint is satisfied.
+    pub fn check_patch(&self, changes: &HashMap<PathBuf, FileChange>) -> Option<String> {
+        if self.rules.is_empty() {
+            return None;
+        }
+
+        let mut paths: Vec<String> = changes
+            .iter()
+            .flat_map(|(path, change)| {
+                let moved_to = match change {
+                    FileChange::Update {
+                        move_path: Some(move_path),
+                        ..
+                    } => Some(move_path.as_path()),
+                    _ => None,
+                };
+                std::iter::once(path.as_path()).chain(moved_to)
+            })
+            .map(|path| self.relative(path))
+            .collect();
+        paths.sort();
+        let changed_lines: usize = changes.values().map(changed_line_count).sum();
+        let touches_tests = paths.iter().any(|path| is_test_path(path));
+
+        for rule in &self.rules {
+            let source = rule.source.display();
+            let constraints = &rule.constraints;
+            for path in &paths {
+                if let Some(glob) = first_match(&constraints.forbid_paths, path) {
+                    return Some(format!(
+                        "`{path}` matches forbid_paths `{glob}` in {source}; it may not be changed"
+                    ));
+                }
+            }
+            if let Some(max) = constraints.max_patch_lines
+                && changed_lines > max
+            {
+                return Some(format!(
+                    "patch changes {changed_lines} lines, more than max_patch_lines ({max}) in {source}; split it into smaller patches"
+                ));
+            }
+            if !touches_tests
+                && let Some((path, glob)) = paths.iter().find_map(|path| {
+                    first_match(&constraints.require_tests_for, path).map(|glob| (path, glob))
+                })
+            {
+                return Some(format!(
+                    "`{path}` matches require_tests_for `{glob}` in {source}; add or update a test in the same patch"
+                ));
+            }
+        }
+        None
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn first_match<'a>(globs: &'a [String], path: &str) -> Option<&'a str> {
+    globs
+        .iter()
+        .map(|glob| glob.trim())
+        .find(|glob| WildMatchPattern::<'*', '?'>::new(glob).matches(path))
+}
+
+fn changed_line_count(change: &FileChange) -> usize {
+    match change {
+        FileChange::Add { content } | FileChange::Delete { content } => content.lines().count(),
+        FileChange::Update { unified_diff, .. } => unified_diff
+            .lines()
+            .filter(|line| {
+                (line.starts_with('+') && !line.starts_with("+++"))
+                    || (line.starts_with('-') && !line.starts_with("---"))
+            })
+            .count(),
+    }
+}
+
+/// Recognizes the common test layouts: a `test`/`tests`/`spec`/`__tests__`
+/// directory, or a file named like `test_x`, `x_test`, `x.test`, or `x.spec`.
+fn is_test_path(path: &str) -> bool {
+    let mut components = path.split('/').collect::<Vec<_>>();
+    let Some(file_name) = components.pop() else {
+        return false;
+    };
+    if components
+        .iter()
+        .any(|dir| matches!(*dir, "test" | "tests" | "spec" | "__tests__"))
+    {
+        return true;
+    }
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_tests")
+        || stem.ends_with("_spec")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn set(constraints: SteeringConstraints) -> SteeringConstraintSet {
+        SteeringConstraintSet::new(
+            PathBuf::from("/repo"),
+            vec![SteeringConstraintRule {
+                source: PathBuf::from("/repo/.codex/steering/rules.md"),
+                constraints,
+            }],
+        )
+    }
+
+    fn add(path: &str, content: &str) -> (PathBuf, FileChange) {
+        (
+            PathBuf::from(path),
+            FileChange::Add {
+                content: content.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn forbidden_paths_are_rejected_including_move_targets() {
+        let constraints = set(SteeringConstraints {
+            forbid_paths: vec!["migrations/**".to_string()],
+            ..Default::default()
+        });
+
+        let changes = HashMap::from([add("/repo/migrations/001.sql", "drop table users;\n")]);
+        assert_eq!(
+            constraints.check_patch(&changes),
+            Some(
+                "`migrations/001.sql` matches forbid_paths `migrations/**` in /repo/.codex/steering/rules.md; it may not be changed"
+                    .to_string()
+            )
+        );
+
+        let moved = HashMap::from([(
+            PathBuf::from("/repo/old.sql"),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: Some(PathBuf::from("/repo/migrations/002.sql")),
+            },
+        )]);
+        assert!(constraints.check_patch(&moved).is_some());
+
+        let allowed = HashMap::from([add("/repo/src/lib.rs", "fn main() {}\n")]);
+        assert_eq!(constraints.check_patch(&allowed), None);
+    }
+
+    #[test]
+    fn large_patches_are_rejected() {
+        let constraints = set(SteeringConstraints {
+            max_patch_lines: Some(2),
+            ..Default::default()
+        });
+        let update = (
+            PathBuf::from("/repo/src/lib.rs"),
+            FileChange::Update {
+                unified_diff: "--- a\n+++ b\n@@ -1 +1 @@\n-old\n+new\n".to_string(),
+                move_path: None,
+            },
+        );
+
+        assert_eq!(
+            constraints.check_patch(&HashMap::from([update.clone()])),
+            None
+        );
+        assert_eq!(
+            constraints.check_patch(&HashMap::from([update, add("/repo/src/new.rs", "a\n")])),
+            Some(
+                "patch changes 3 lines, more than max_patch_lines (2) in /repo/.codex/steering/rules.md; split it into smaller patches"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn covered_paths_need_a_test_change_in_the_same_patch() {
+        let constraints = set(SteeringConstraints {
+            require_tests_for: vec!["src/**".to_string()],
+            ..Default::default()
+        });
+
+        let untested = HashMap::from([add("/repo/src/parser.rs", "pub fn parse() {}\n")]);
+        assert_eq!(
+            constraints.check_patch(&untested),
+            Some(
+                "`src/parser.rs` matches require_tests_for `src/**` in /repo/.codex/steering/rules.md; add or update a test in the same patch"
+                    .to_string()
+            )
+        );
+
+        let tested = HashMap::from([
+            add("/repo/src/parser.rs", "pub fn parse() {}\n"),
+            add("/repo/tests/parser.rs", "#[test]\nfn parses() {}\n"),
+        ]);
+        assert_eq!(constraints.check_patch(&tested), None);
+
+        let docs_only = HashMap::from([add("/repo/README.md", "docs\n")]);
+        assert_eq!(constraints.check_patch(&docs_only), None);
+    }
+
+    #[test]
+    fn recognizes_test_paths() {
+        assert!(is_test_path("tests/cli.rs"));
+        assert!(is_test_path("src/__tests__/app.tsx"));
+        assert!(is_test_path("pkg/server_test.go"));
+        assert!(is_test_path("lib/test_utils.py"));
+        assert!(is_test_path("web/app.spec.ts"));
+        assert!(!is_test_path("src/testing.rs"));
+        assert!(!is_test_path("src/main.rs"));
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+/// Marker files that identify a project's language, checked in every
+/// directory from the working directory up to the repository root.
+const LANGUAGE_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "javascript"),
+    ("go.mod", "go"),
+    ("pyproject.toml", "python"),
+];
+
+/// Returns the languages detected in `dirs`, in [`LANGUAGE_MARKERS`] order and
+/// without duplicates.
+pub(crate) fn detect_languages<'a, I>(dirs: I) -> Vec<&'static str>
+where
+    I: IntoIterator<Item = &'a Path>,
+{
+    let dirs: Vec<&Path> = dirs.into_iter().collect();
+    LANGUAGE_MARKERS
+        .iter()
+        .filter(|(marker, _)| dirs.iter().any(|dir| dir.join(marker).is_file()))
+        .map(|(_, language)| *language)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_markers_across_directories() {
+        let root = TempDir::new().unwrap();
+        let nested = root.path().join("web");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(nested.join("package.json"), "{}").unwrap();
+
+        assert_eq!(
+            detect_languages([nested.as_path(), root.path()]),
+            vec!["rust", "javascript"]
+        );
+        assert_eq!(detect_languages([root.path()]), vec!["rust"]);
+    }
+}
@@ -0,0 +1,178 @@
+use serde::Deserialize;
+
+use crate::steering::constraints::SteeringConstraints;
+
+/// Optional YAML header at the top of a steering file, delimited by `---`
+/// lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub(crate) struct SteeringFrontmatter {
+    #[serde(default)]
+    pub(crate) when: Option<SteeringCondition>,
+    /// Rules core enforces on patches while this file is loaded.
+    #[serde(default)]
+    pub(crate) constraints: Option<SteeringConstraints>,
+}
+
+/// Conditions that must all hold for a steering file to load.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SteeringCondition {
+    /// Load only when one of these languages is detected in the project.
+    #[serde(default)]
+    pub(crate) language: Option<OneOrMany>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    pub(crate) fn values(&self) -> &[String] {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value),
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+impl SteeringCondition {
+    pub(crate) fn matches(&self, detected_languages: &[&str]) -> bool {
+        self.language.as_ref().is_none_or(|languages| {
+            languages.values().iter().any(|language| {
+                detected_languages
+                    .iter()
+                    .any(|detected| detected.eq_ignore_ascii_case(language.trim()))
+            })
+        })
+    }
+}
+
+/// Splits `contents` into its parsed frontmatter and the remaining body.
+/// Only a `---` block holding a YAML mapping is frontmatter. Files without a
+/// leading `---` line, or where it is a Markdown horizontal rule (never
+/// closed, or followed by prose rather than a mapping), are all body.
+pub(crate) fn parse_steering_file(contents: &str) -> Result<(SteeringFrontmatter, &str), String> {
+    let Some(rest) = strip_delimiter_line(contents) else {
+        return Ok((SteeringFrontmatter::default(), contents));
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim() == "---" {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            if yaml.trim().is_empty() {
+                return Ok((SteeringFrontmatter::default(), body));
+            }
+            let Ok(mapping @ serde_yaml::Value::Mapping(_)) =
+                serde_yaml::from_str::<serde_yaml::Value>(yaml)
+            else {
+                break;
+            };
+            let frontmatter = serde_yaml::from_value(mapping)
+                .map_err(|err| format!("invalid frontmatter: {err}"))?;
+            return Ok((frontmatter, body));
+        }
+        offset += line.len();
+    }
+
+    Ok((SteeringFrontmatter::default(), contents))
+}
+
+fn strip_delimiter_line(contents: &str) -> Option<&str> {
+    let (first, rest) = contents.split_once('\n')?;
+    (first.trim() == "---").then_some(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_language_condition_and_body() {
+        let (frontmatter, body) =
+            parse_steering_file("---\nwhen: { language: \"rust\" }\n---\nUse clippy.\n").unwrap();
+
+        assert_eq!(
+            frontmatter,
+            SteeringFrontmatter {
+                when: Some(SteeringCondition {
+                    language: Some(OneOrMany::One("rust".to_string())),
+                }),
+                constraints: None,
+            }
+        );
+        assert_eq!(body, "Use clippy.\n");
+    }
+
+    #[test]
+    fn parses_constraints() {
+        let (frontmatter, _) = parse_steering_file(
+            "---\nconstraints:\n  forbid_paths: [\"*.pem\"]\n  max_patch_lines: 200\n---\nbody\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            frontmatter.constraints,
+            Some(SteeringConstraints {
+                forbid_paths: vec!["*.pem".to_string()],
+                require_tests_for: Vec::new(),
+                max_patch_lines: Some(200),
+            })
+        );
+        assert!(parse_steering_file("---\nconstraints: { max_lines: 3 }\n---\nbody").is_err());
+    }
+
+    #[test]
+    fn files_without_frontmatter_are_unconditional() {
+        let (frontmatter, body) = parse_steering_file("Keep diffs small.\n").unwrap();
+
+        assert_eq!(frontmatter, SteeringFrontmatter::default());
+        assert_eq!(body, "Keep diffs small.\n");
+    }
+
+    #[test]
+    fn condition_matches_any_listed_language() {
+        let condition = SteeringCondition {
+            language: Some(OneOrMany::Many(vec![
+                "Python".to_string(),
+                "go".to_string(),
+            ])),
+        };
+
+        assert!(condition.matches(&["rust", "python"]));
+        assert!(!condition.matches(&["rust"]));
+        assert!(SteeringCondition::default().matches(&[]));
+    }
+
+    #[test]
+    fn unclosed_delimiter_is_part_of_the_body() {
+        let contents = "---\nKeep diffs small.\n";
+        let (frontmatter, body) = parse_steering_file(contents).unwrap();
+
+        assert_eq!(frontmatter, SteeringFrontmatter::default());
+        assert_eq!(body, contents);
+    }
+
+    #[test]
+    fn horizontal_rules_around_prose_are_part_of_the_body() {
+        for contents in [
+            "---\nKeep diffs small.\n---\nRun the tests.\n",
+            "---\n- a list, not a mapping\n---\nbody\n",
+            "---\nwhen: [\n---\nbody\n",
+        ] {
+            let (frontmatter, body) = parse_steering_file(contents).unwrap();
+            assert_eq!(frontmatter, SteeringFrontmatter::default());
+            assert_eq!(body, contents);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_conditions() {
+        assert!(parse_steering_file("---\nwhen: { os: linux }\n---\nbody").is_err());
+    }
+}
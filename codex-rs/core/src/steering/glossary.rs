@@ -0,0 +1,435 @@
+//! `glossary.md` steering files: project terminology that is both injected as
+//! ordinary steering and enforced as a lint on text the agent writes.
+//!
+//! A glossary has two kinds of bullet lists, under headings that mention
+//! "preferred" or "banned":
+//!
+//! ```markdown
+//! ## Preferred
+//! - GitHub: Github, git hub
+//!
+//! ## Banned
+//! - whitelist: say allowlist
+//! - simply
+//! ```
+//!
+//! Matching is case-insensitive and whole-word. A preferred term written in
+//! exactly its preferred spelling is never reported, so case variants such as
+//! `Github` can be listed next to `GitHub`.
+
+use std::fs;
+use std::path::Path;
+
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::TerminologyViolation;
+
+use crate::config::trust_store::project_files_allowed;
+use crate::steering::frontmatter::parse_steering_file;
+use crate::steering::loader::global_steering_root;
+use crate::steering::loader::project_steering_root;
+
+pub const GLOSSARY_FILENAME: &str = "glossary.md";
+
+/// File extensions whose added lines are linted when the agent edits them.
+const DOC_EXTENSIONS: &[&str] = &["md", "markdown", "mdx", "txt", "rst", "adoc"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GlossaryEntry {
+    /// Lowercased spelling to search for.
+    needle: String,
+    /// For preferred-term entries, the canonical spelling.
+    preferred: Option<String>,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Glossary {
+    entries: Vec<GlossaryEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Other,
+    Preferred,
+    Banned,
+}
+
+impl Glossary {
+    pub fn parse(contents: &str) -> Self {
+        let body = parse_steering_file(contents)
+            .map(|(_, body)| body)
+            .unwrap_or(contents);
+        let mut glossary = Glossary::default();
+        let mut section = Section::Other;
+
+        for line in body.lines() {
+            let line = line.trim();
+            if let Some(heading) = line.strip_prefix('#') {
+                let heading = heading.trim_start_matches('#').trim().to_ascii_lowercase();
+                section = if heading.contains("preferred") {
+                    Section::Preferred
+                } else if heading.contains("banned") {
+                    Section::Banned
+                } else {
+                    Section::Other
+                };
+                continue;
+            }
+            let Some(item) = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .map(str::trim)
+            else {
+                continue;
+            };
+            let (term, rest) = match item.split_once(':') {
+                Some((term, rest)) => (term.trim(), Some(rest.trim())),
+                None => (item, None),
+            };
+            let term = term.trim_matches('`');
+            if term.is_empty() {
+                continue;
+            }
+
+            match section {
+                Section::Preferred => {
+                    let variants = rest.unwrap_or_default();
+                    for variant in variants.split(',') {
+                        let variant = variant.trim().trim_matches('`');
+                        if variant.is_empty() {
+                            continue;
+                        }
+                        glossary.entries.push(GlossaryEntry {
+                            needle: variant.to_ascii_lowercase(),
+                            preferred: Some(term.to_string()),
+                            reason: None,
+                        });
+                    }
+                }
+                Section::Banned => glossary.entries.push(GlossaryEntry {
+                    needle: term.to_ascii_lowercase(),
+                    preferred: None,
+                    reason: rest.filter(|reason| !reason.is_empty()).map(str::to_string),
+                }),
+                Section::Other => {}
+            }
+        }
+        glossary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn extend(&mut self, other: Glossary) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Reports every glossary term in `text`. Fenced code blocks and inline
+    /// code spans are skipped.
+    pub fn lint(&self, text: &str) -> Vec<TerminologyViolation> {
+        self.lint_lines(
+            text.lines()
+                .enumerate()
+                .map(|(index, line)| (index + 1, line)),
+        )
+    }
+
+    /// Like [`Glossary::lint`], for lines that carry their own line numbers.
+    pub fn lint_lines<'a, I>(&self, lines: I) -> Vec<TerminologyViolation>
+    where
+        I: IntoIterator<Item = (usize, &'a str)>,
+    {
+        let mut violations = Vec::new();
+        if self.is_empty() {
+            return violations;
+        }
+
+        let mut in_fence = false;
+        for (line_number, line) in lines {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            let prose = blank_inline_code(line);
+            let lowered = prose.to_ascii_lowercase();
+            for entry in &self.entries {
+                for (start, _) in lowered.match_indices(&entry.needle) {
+                    let end = start + entry.needle.len();
+                    if !is_word_boundary(&lowered, start, end) {
+                        continue;
+                    }
+                    let term = &prose[start..end];
+                    if entry.preferred.as_deref() == Some(term) {
+                        continue;
+                    }
+                    violations.push(TerminologyViolation {
+                        term: term.to_string(),
+                        line: line_number,
+                        preferred: entry.preferred.clone(),
+                        reason: entry.reason.clone(),
+                    });
+                }
+            }
+        }
+        violations.sort_by_key(|violation| violation.line);
+        violations
+    }
+}
+
+/// Loads `glossary.md` from the global steering directory and from the
+/// project's `.codex/steering`, in that order. The project file is skipped
+/// when the user chose not to trust the project's files.
+pub fn load_glossary(codex_home: &Path, cwd: &Path) -> Glossary {
+    let mut glossary = Glossary::default();
+    let project_root = project_files_allowed(codex_home, cwd)
+        .then(|| project_steering_root(cwd))
+        .flatten();
+    let roots = std::iter::once(global_steering_root(codex_home)).chain(project_root);
+    for root in roots {
+        if let Ok(contents) = fs::read_to_string(root.path.join(GLOSSARY_FILENAME)) {
+            glossary.extend(Glossary::parse(&contents));
+        }
+    }
+    glossary
+}
+
+/// Lints the lines an apply_patch change adds to documentation files.
+/// Returns `(path, violations)` for each file with at least one violation.
+pub fn lint_doc_changes<'a, I>(
+    glossary: &Glossary,
+    changes: I,
+) -> Vec<(String, Vec<TerminologyViolation>)>
+where
+    I: IntoIterator<Item = (&'a Path, &'a FileChange)>,
+{
+    let mut results = Vec::new();
+    for (path, change) in changes {
+        let (path, violations) = match change {
+            FileChange::Add { content } if is_doc_file(path) => (path, glossary.lint(content)),
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let path = move_path.as_deref().unwrap_or(path);
+                if !is_doc_file(path) {
+                    continue;
+                }
+                (path, glossary.lint_lines(added_lines(unified_diff)))
+            }
+            FileChange::Add { .. } | FileChange::Delete { .. } => continue,
+        };
+        if !violations.is_empty() {
+            results.push((path.display().to_string(), violations));
+        }
+    }
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    results
+}
+
+/// Extracts the message of a `git commit` invocation given as argv, joining
+/// repeated `-m` paragraphs the way git does.
+pub fn commit_message(argv: &[String]) -> Option<String> {
+    let git_index = argv.iter().position(|word| word == "git")?;
+    let mut words = argv[git_index + 1..].iter();
+    // Skip global options such as `-C dir` before the subcommand.
+    loop {
+        let word = words.next()?;
+        match word.as_str() {
+            "commit" => break,
+            "-C" | "-c" => {
+                words.next()?;
+            }
+            word if word.starts_with('-') => {}
+            _ => return None,
+        }
+    }
+
+    let mut paragraphs = Vec::new();
+    while let Some(word) = words.next() {
+        if let Some(message) = word.strip_prefix("--message=") {
+            paragraphs.push(message.to_string());
+        } else if word == "-m"
+            || word == "--message"
+            || (is_short_flag_group(word) && word.ends_with('m'))
+        {
+            paragraphs.push(words.next()?.clone());
+        } else if let Some(message) = word.strip_prefix("-m")
+            && !message.is_empty()
+        {
+            paragraphs.push(message.to_string());
+        }
+    }
+    (!paragraphs.is_empty()).then(|| paragraphs.join("\n\n"))
+}
+
+fn is_short_flag_group(word: &str) -> bool {
+    word.len() > 2
+        && word.starts_with('-')
+        && !word.starts_with("--")
+        && word[1..].chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_doc_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            DOC_EXTENSIONS
+                .iter()
+                .any(|doc| doc.eq_ignore_ascii_case(extension))
+        })
+}
+
+/// Added lines of a unified diff, numbered by their position in the new file.
+fn added_lines(unified_diff: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut next_line = 0usize;
+    for line in unified_diff.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            next_line = header
+                .split_whitespace()
+                .find_map(|range| range.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(1);
+        } else if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        } else if let Some(added) = line.strip_prefix('+') {
+            lines.push((next_line, added));
+            next_line += 1;
+        } else if line.starts_with(' ') {
+            next_line += 1;
+        }
+    }
+    lines
+}
+
+/// Replaces `inline code` spans with spaces so byte offsets are preserved.
+fn blank_inline_code(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            out.push(' ');
+        } else if in_code {
+            out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word)
+        && !text[end..].chars().next().is_some_and(is_word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    const GLOSSARY: &str = "\
+# Glossary
+
+## Preferred terms
+- GitHub: Github, git hub
+- sign in: login, log-in
+
+## Banned
+- whitelist: say allowlist
+- simply
+";
+
+    fn violation(
+        term: &str,
+        line: usize,
+        preferred: Option<&str>,
+        reason: Option<&str>,
+    ) -> TerminologyViolation {
+        TerminologyViolation {
+            term: term.to_string(),
+            line,
+            preferred: preferred.map(str::to_string),
+            reason: reason.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reports_variants_and_banned_terms() {
+        let glossary = Glossary::parse(GLOSSARY);
+        let text = "Push to GitHub, not Github.\nSimply add it to the Whitelist.\n`whitelist` in code is fine.";
+
+        assert_eq!(
+            glossary.lint(text),
+            vec![
+                violation("Github", 1, Some("GitHub"), None),
+                violation("Whitelist", 2, None, Some("say allowlist")),
+                violation("Simply", 2, None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_whole_words_and_skips_code_fences() {
+        let glossary = Glossary::parse(GLOSSARY);
+        let text = "The login_form and blogin are fine.\n```\nlogin\n```\nPlease log-in.";
+
+        assert_eq!(
+            glossary.lint(text),
+            vec![violation("log-in", 5, Some("sign in"), None)]
+        );
+    }
+
+    #[test]
+    fn lints_only_added_doc_lines_with_new_file_numbers() {
+        let glossary = Glossary::parse(GLOSSARY);
+        let readme = PathBuf::from("README.md");
+        let main = PathBuf::from("main.rs");
+        let readme_change = FileChange::Update {
+            unified_diff: "@@ -4,2 +4,3 @@\n context\n-old whitelist\n+new whitelist\n+more\n"
+                .to_string(),
+            move_path: None,
+        };
+        let main_change = FileChange::Add {
+            content: "// whitelist\n".to_string(),
+        };
+
+        assert_eq!(
+            lint_doc_changes(
+                &glossary,
+                [
+                    (readme.as_path(), &readme_change),
+                    (main.as_path(), &main_change)
+                ]
+            ),
+            vec![(
+                "README.md".to_string(),
+                vec![violation("whitelist", 5, None, Some("say allowlist"))]
+            )]
+        );
+    }
+
+    #[test]
+    fn extracts_commit_messages() {
+        let argv = |words: &[&str]| words.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            commit_message(&argv(&["git", "commit", "-am", "Fix", "-m", "Body"])),
+            Some("Fix\n\nBody".to_string())
+        );
+        assert_eq!(
+            commit_message(&argv(&["git", "-C", "repo", "commit", "--message=Fix"])),
+            Some("Fix".to_string())
+        );
+        assert_eq!(commit_message(&argv(&["git", "commit"])), None);
+        assert_eq!(commit_message(&argv(&["git", "log", "-m", "x"])), None);
+    }
+}
@@ -0,0 +1,661 @@
+use crate::config::Config;
+use crate::config::types::SteeringConfig;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::steering::constraints::SteeringConstraintRule;
+use crate::steering::detect::detect_languages;
+use crate::steering::frontmatter::parse_steering_file;
+use crate::steering::manifest::PlannedFile;
+use crate::steering::manifest::STEERING_MANIFEST_FILENAME;
+use crate::steering::manifest::load_manifest;
+use crate::steering::model::SteeringDiscovery;
+use crate::steering::model::SteeringError;
+use crate::steering::model::SteeringFile;
+use crate::steering::model::SteeringScope;
+use dunce::canonicalize as normalize_path;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+const STEERING_DIR_NAME: &str = "steering";
+const STEERING_FILE_EXTENSION: &str = "md";
+const REPO_ROOT_CONFIG_DIR_NAME: &str = ".codex";
+
+pub(crate) struct SteeringRoot {
+    pub(crate) path: PathBuf,
+    pub(crate) scope: SteeringScope,
+}
+
+/// What is known about the project when deciding which steering files apply.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SteeringContext<'a> {
+    pub(crate) detected_languages: &'a [&'a str],
+    /// The working directory relative to the project root, `/`-separated and
+    /// empty at the root. Matched against manifest `activate` globs.
+    pub(crate) relative_cwd: &'a str,
+}
+
+/// Loads the steering files that apply to `config.cwd`: global files from
+/// `$CODEX_HOME/steering` first, then project files from `.codex/steering`,
+/// then those of each of `config.workspace_roots`.
+/// Files whose `when` frontmatter does not match the project are left out,
+/// and a `manifest.toml` in either directory overrides its file order.
+pub fn discover_steering(config: &Config) -> SteeringDiscovery {
+    let root_dirs: Vec<PathBuf> = config
+        .workspace_roots
+        .iter()
+        .flat_map(|root| project_dirs(root))
+        .collect();
+    let project_dirs = project_dirs(&config.cwd);
+    let languages = detect_languages(project_dirs.iter().chain(&root_dirs).map(PathBuf::as_path));
+    let relative_cwd = relative_cwd(&project_dirs);
+    let context = SteeringContext {
+        detected_languages: &languages,
+        relative_cwd: &relative_cwd,
+    };
+    discover_steering_in_roots(steering_roots(config), &config.steering, context)
+}
+
+/// The directory that steering constraint globs are relative to: the root
+/// of the git repository containing `cwd`, or `cwd` itself outside one.
+pub fn steering_project_root(cwd: &Path) -> PathBuf {
+    project_dirs(cwd).pop().unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// `project_dirs` runs from the working directory up to the project root.
+fn relative_cwd(project_dirs: &[PathBuf]) -> String {
+    let (Some(cwd), Some(root)) = (project_dirs.first(), project_dirs.last()) else {
+        return String::new();
+    };
+    cwd.strip_prefix(root)
+        .map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+fn steering_roots(config: &Config) -> Vec<SteeringRoot> {
+    let mut roots = vec![global_steering_root(&config.codex_home)];
+    if config.load_project_files
+        && let Some(project_root) = project_steering_root(&config.cwd)
+    {
+        roots.push(project_root);
+    }
+    if config.load_project_files {
+        for root in config
+            .workspace_roots
+            .iter()
+            .filter_map(|root| project_steering_root(root))
+        {
+            if !roots.iter().any(|existing| existing.path == root.path) {
+                roots.push(root);
+            }
+        }
+    }
+    roots
+}
+
+pub(crate) fn global_steering_root(codex_home: &Path) -> SteeringRoot {
+    SteeringRoot {
+        path: codex_home.join(STEERING_DIR_NAME),
+        scope: SteeringScope::Global,
+    }
+}
+
+/// Directories from `cwd` up to the root of the enclosing git repository, or
+/// just `cwd` outside a repository.
+fn project_dirs(cwd: &Path) -> Vec<PathBuf> {
+    let base = normalize_path(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+    let Some(repo_root) =
+        resolve_root_git_project_for_trust(&base).map(|root| normalize_path(&root).unwrap_or(root))
+    else {
+        return vec![base];
+    };
+
+    let mut dirs = Vec::new();
+    for dir in base.ancestors() {
+        dirs.push(dir.to_path_buf());
+        if dir == repo_root {
+            break;
+        }
+    }
+    dirs
+}
+
+/// Finds the nearest `.codex/steering` directory between `cwd` and the root of
+/// the enclosing git repository.
+pub(crate) fn project_steering_root(cwd: &Path) -> Option<SteeringRoot> {
+    let base = if cwd.is_dir() { cwd } else { cwd.parent()? };
+    let base = normalize_path(base).unwrap_or_else(|_| base.to_path_buf());
+
+    let repo_root =
+        resolve_root_git_project_for_trust(&base).map(|root| normalize_path(&root).unwrap_or(root));
+
+    let scope = SteeringScope::Project;
+    if let Some(repo_root) = repo_root.as_deref() {
+        for dir in base.ancestors() {
+            let steering_root = dir.join(REPO_ROOT_CONFIG_DIR_NAME).join(STEERING_DIR_NAME);
+            if steering_root.is_dir() {
+                return Some(SteeringRoot {
+                    path: steering_root,
+                    scope,
+                });
+            }
+
+            if dir == repo_root {
+                break;
+            }
+        }
+        return None;
+    }
+
+    let steering_root = base.join(REPO_ROOT_CONFIG_DIR_NAME).join(STEERING_DIR_NAME);
+    steering_root.is_dir().then_some(SteeringRoot {
+        path: steering_root,
+        scope,
+    })
+}
+
+struct Candidate {
+    path: PathBuf,
+    scope: SteeringScope,
+    contents: String,
+}
+
+/// Reads every root in order, drops files whose `when` condition does not match
+/// the detected languages, applies each root's manifest, and fits the rest
+/// into `settings.max_bytes` in two phases. Files larger than the budget their
+/// manifest entry assigns are skipped outright. In the first phase, each scope
+/// admits its files in discovery order until its reservation is used up
+/// (`project_min_fraction` of the budget for project files, the rest for
+/// global files). In the second, anything left over is offered, in discovery
+/// order, to the files that did not fit their reservation. Files that still do
+/// not fit are reported in `skipped`.
+pub(crate) fn discover_steering_in_roots<I>(
+    roots: I,
+    settings: &SteeringConfig,
+    context: SteeringContext<'_>,
+) -> SteeringDiscovery
+where
+    I: IntoIterator<Item = SteeringRoot>,
+{
+    let mut discovery = SteeringDiscovery::default();
+    let mut candidates = Vec::new();
+
+    for root in roots {
+        for PlannedFile { path, max_bytes } in plan_root(&root.path, context, &mut discovery) {
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    discovery.errors.push(SteeringError {
+                        path,
+                        message: format!("failed to read file: {err}"),
+                    });
+                    continue;
+                }
+            };
+
+            let (frontmatter, body) = match parse_steering_file(&contents) {
+                Ok(parsed) => parsed,
+                Err(message) => {
+                    discovery.errors.push(SteeringError { path, message });
+                    continue;
+                }
+            };
+            if frontmatter
+                .when
+                .as_ref()
+                .is_some_and(|condition| !condition.matches(context.detected_languages))
+            {
+                continue;
+            }
+            if let Some(constraints) = frontmatter
+                .constraints
+                .filter(|constraints| !constraints.is_empty())
+            {
+                discovery.constraints.push(SteeringConstraintRule {
+                    source: path.clone(),
+                    constraints,
+                });
+            }
+
+            let contents = body.trim();
+            if contents.is_empty() {
+                continue;
+            }
+            if max_bytes.is_some_and(|max_bytes| contents.len() > max_bytes) {
+                discovery.skipped.push(path);
+                continue;
+            }
+            candidates.push(Candidate {
+                path,
+                scope: root.scope,
+                contents: contents.to_string(),
+            });
+        }
+    }
+
+    let project_reserved = settings.project_reserved_bytes().min(settings.max_bytes);
+    let mut global_remaining = settings.max_bytes - project_reserved;
+    let mut project_remaining = project_reserved;
+    let mut admitted = vec![false; candidates.len()];
+
+    for (idx, candidate) in candidates.iter().enumerate() {
+        let remaining = match candidate.scope {
+            SteeringScope::Global => &mut global_remaining,
+            SteeringScope::Project => &mut project_remaining,
+        };
+        if candidate.contents.len() <= *remaining {
+            *remaining -= candidate.contents.len();
+            admitted[idx] = true;
+        }
+    }
+
+    let used: usize = candidates
+        .iter()
+        .zip(&admitted)
+        .filter(|(_, admitted)| **admitted)
+        .map(|(candidate, _)| candidate.contents.len())
+        .sum();
+    let mut leftover = settings.max_bytes.saturating_sub(used);
+    for (idx, candidate) in candidates.iter().enumerate() {
+        if !admitted[idx] && candidate.contents.len() <= leftover {
+            leftover -= candidate.contents.len();
+            admitted[idx] = true;
+        }
+    }
+
+    for (candidate, admitted) in candidates.into_iter().zip(admitted) {
+        if admitted {
+            discovery.files.push(SteeringFile {
+                path: candidate.path,
+                scope: candidate.scope,
+                contents: candidate.contents,
+            });
+        } else {
+            discovery.skipped.push(candidate.path);
+        }
+    }
+
+    discovery
+}
+
+/// Lists the steering files in `dir` in load order: by file name, or as
+/// arranged by the directory's manifest when it has a valid one. A manifest
+/// that fails to parse is reported and the directory falls back to file-name
+/// order.
+fn plan_root(
+    dir: &Path,
+    context: SteeringContext<'_>,
+    discovery: &mut SteeringDiscovery,
+) -> Vec<PlannedFile> {
+    let files = list_steering_files(dir, discovery);
+    match load_manifest(dir) {
+        Ok(Some(manifest)) => {
+            discovery
+                .manifests
+                .push(dir.join(STEERING_MANIFEST_FILENAME));
+            manifest.plan(dir, files, context.relative_cwd, &mut discovery.errors)
+        }
+        Ok(None) => unplanned(files),
+        Err(err) => {
+            discovery.errors.push(err);
+            unplanned(files)
+        }
+    }
+}
+
+fn unplanned(files: Vec<PathBuf>) -> Vec<PlannedFile> {
+    files
+        .into_iter()
+        .map(|path| PlannedFile {
+            path,
+            max_bytes: None,
+        })
+        .collect()
+}
+
+/// Returns the markdown files directly inside `dir`, sorted by file name.
+fn list_steering_files(dir: &Path, discovery: &mut SteeringDiscovery) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => {
+            discovery.errors.push(SteeringError {
+                path: dir.to_path_buf(),
+                message: format!("failed to read directory: {err}"),
+            });
+            return Vec::new();
+        }
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(STEERING_FILE_EXTENSION))
+        })
+        .collect();
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steering::constraints::SteeringConstraints;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn roots(tmp: &TempDir) -> Vec<SteeringRoot> {
+        vec![
+            SteeringRoot {
+                path: tmp.path().join("global"),
+                scope: SteeringScope::Global,
+            },
+            SteeringRoot {
+                path: tmp.path().join("project"),
+                scope: SteeringScope::Project,
+            },
+        ]
+    }
+
+    #[test]
+    fn loads_global_then_project_files_sorted_by_name() {
+        let tmp = TempDir::new().unwrap();
+        let global_b = write(&tmp.path().join("global"), "b.md", "global b\n");
+        let global_a = write(&tmp.path().join("global"), "a.md", "global a");
+        write(&tmp.path().join("global"), "notes.txt", "ignored");
+        write(&tmp.path().join("global"), "empty.md", "  \n");
+        let project = write(&tmp.path().join("project"), "style.md", "project style");
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig::default(),
+            SteeringContext::default(),
+        );
+
+        assert_eq!(
+            discovery,
+            SteeringDiscovery {
+                files: vec![
+                    SteeringFile {
+                        path: global_a,
+                        scope: SteeringScope::Global,
+                        contents: "global a".to_string(),
+                    },
+                    SteeringFile {
+                        path: global_b,
+                        scope: SteeringScope::Global,
+                        contents: "global b".to_string(),
+                    },
+                    SteeringFile {
+                        path: project,
+                        scope: SteeringScope::Project,
+                        contents: "project style".to_string(),
+                    },
+                ],
+                skipped: Vec::new(),
+                errors: Vec::new(),
+                manifests: Vec::new(),
+                constraints: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn project_reservation_is_not_consumed_by_global_files() {
+        let tmp = TempDir::new().unwrap();
+        let global = write(&tmp.path().join("global"), "big.md", "0123456789");
+        let project = write(&tmp.path().join("project"), "small.md", "abcdef");
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig {
+                max_bytes: 12,
+                project_min_fraction: 0.5,
+                learned_file: None,
+            },
+            SteeringContext::default(),
+        );
+
+        assert_eq!(
+            discovery,
+            SteeringDiscovery {
+                files: vec![SteeringFile {
+                    path: project,
+                    scope: SteeringScope::Project,
+                    contents: "abcdef".to_string(),
+                }],
+                skipped: vec![global],
+                errors: Vec::new(),
+                manifests: Vec::new(),
+                constraints: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn without_reservation_files_are_admitted_first_come() {
+        let tmp = TempDir::new().unwrap();
+        let global = write(&tmp.path().join("global"), "big.md", "0123456789");
+        let project = write(&tmp.path().join("project"), "small.md", "abcdef");
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig {
+                max_bytes: 12,
+                project_min_fraction: 0.0,
+                learned_file: None,
+            },
+            SteeringContext::default(),
+        );
+
+        assert_eq!(
+            discovery,
+            SteeringDiscovery {
+                files: vec![SteeringFile {
+                    path: global,
+                    scope: SteeringScope::Global,
+                    contents: "0123456789".to_string(),
+                }],
+                skipped: vec![project],
+                errors: Vec::new(),
+                manifests: Vec::new(),
+                constraints: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn unused_project_reservation_goes_to_global_files() {
+        let tmp = TempDir::new().unwrap();
+        let first = write(&tmp.path().join("global"), "a.md", "0123456789");
+        let second = write(&tmp.path().join("global"), "b.md", "abcdef");
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig {
+                max_bytes: 16,
+                project_min_fraction: 0.5,
+                learned_file: None,
+            },
+            SteeringContext::default(),
+        );
+
+        assert_eq!(
+            discovery,
+            SteeringDiscovery {
+                files: vec![
+                    SteeringFile {
+                        path: first,
+                        scope: SteeringScope::Global,
+                        contents: "0123456789".to_string(),
+                    },
+                    SteeringFile {
+                        path: second,
+                        scope: SteeringScope::Global,
+                        contents: "abcdef".to_string(),
+                    },
+                ],
+                skipped: Vec::new(),
+                errors: Vec::new(),
+                manifests: Vec::new(),
+                constraints: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn language_conditions_filter_files() {
+        let tmp = TempDir::new().unwrap();
+        let rust = write(
+            &tmp.path().join("global"),
+            "rust.md",
+            "---\nwhen: { language: \"rust\" }\n---\nRun cargo clippy.\n",
+        );
+        write(
+            &tmp.path().join("global"),
+            "go.md",
+            "---\nwhen: { language: [go] }\n---\nRun go vet.\n",
+        );
+        let broken = write(
+            &tmp.path().join("global"),
+            "broken.md",
+            "---\nwhen: { os: linux }\n---\nbody\n",
+        );
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig::default(),
+            SteeringContext {
+                detected_languages: &["rust"],
+                relative_cwd: "",
+            },
+        );
+
+        assert_eq!(
+            discovery.files,
+            vec![SteeringFile {
+                path: rust,
+                scope: SteeringScope::Global,
+                contents: "Run cargo clippy.".to_string(),
+            }]
+        );
+        assert_eq!(
+            discovery
+                .errors
+                .iter()
+                .map(|err| err.path.clone())
+                .collect::<Vec<_>>(),
+            vec![broken]
+        );
+    }
+
+    #[test]
+    fn constraints_are_collected_from_matching_files() {
+        let tmp = TempDir::new().unwrap();
+        let rules = write(
+            &tmp.path().join("project"),
+            "rules.md",
+            "---\nconstraints:\n  forbid_paths: [\"*.pem\"]\n---\n",
+        );
+        write(
+            &tmp.path().join("global"),
+            "go.md",
+            "---\nwhen: { language: go }\nconstraints: { max_patch_lines: 10 }\n---\nRun go vet.\n",
+        );
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig::default(),
+            SteeringContext {
+                detected_languages: &["rust"],
+                relative_cwd: "",
+            },
+        );
+
+        assert_eq!(discovery.files, Vec::new());
+        assert_eq!(
+            discovery.constraints,
+            vec![SteeringConstraintRule {
+                source: rules,
+                constraints: SteeringConstraints {
+                    forbid_paths: vec!["*.pem".to_string()],
+                    ..Default::default()
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn manifest_overrides_order_and_budgets() {
+        let tmp = TempDir::new().unwrap();
+        let project_dir = tmp.path().join("project");
+        let first = write(&project_dir, "a.md", "first by name");
+        let second = write(&project_dir, "b.md", "pinned first");
+        let oversized = write(&project_dir, "c.md", "too long for its budget");
+        write(&project_dir, "d.md", "disabled");
+        write(
+            &project_dir,
+            "manifest.toml",
+            r#"
+[[files]]
+path = "b.md"
+
+[[files]]
+path = "c.md"
+max_bytes = 8
+
+[[files]]
+path = "d.md"
+enabled = false
+"#,
+        );
+        write(&tmp.path().join("global"), "manifest.toml", "files = 1\n");
+
+        let discovery = discover_steering_in_roots(
+            roots(&tmp),
+            &SteeringConfig::default(),
+            SteeringContext::default(),
+        );
+
+        assert_eq!(
+            discovery.files,
+            vec![
+                SteeringFile {
+                    path: second,
+                    scope: SteeringScope::Project,
+                    contents: "pinned first".to_string(),
+                },
+                SteeringFile {
+                    path: first,
+                    scope: SteeringScope::Project,
+                    contents: "first by name".to_string(),
+                },
+            ]
+        );
+        assert_eq!(discovery.skipped, vec![oversized]);
+        assert_eq!(discovery.manifests, vec![project_dir.join("manifest.toml")]);
+        assert_eq!(
+            discovery
+                .errors
+                .iter()
+                .map(|err| err.path.clone())
+                .collect::<Vec<_>>(),
+            vec![tmp.path().join("global").join("manifest.toml")]
+        );
+    }
+}
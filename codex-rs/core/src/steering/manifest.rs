@@ -0,0 +1,281 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use wildmatch::WildMatchPattern;
+
+use crate::steering::model::SteeringError;
+
+pub(crate) const STEERING_MANIFEST_FILENAME: &str = "manifest.toml";
+
+/// Optional `manifest.toml` inside a steering directory that replaces the
+/// file-name ordering for that directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SteeringManifest {
+    #[serde(default)]
+    pub(crate) files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ManifestEntry {
+    /// File name of a `.md` file in the same directory as the manifest.
+    pub(crate) path: String,
+    #[serde(default = "default_enabled")]
+    pub(crate) enabled: bool,
+    /// Largest size this file may have; larger files are skipped.
+    #[serde(default)]
+    pub(crate) max_bytes: Option<usize>,
+    /// Load only when the working directory, relative to the project root,
+    /// matches one of these globs (or is inside a matching directory).
+    #[serde(default)]
+    pub(crate) activate: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A steering file selected for loading, with the budget its manifest entry
+/// assigned, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlannedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) max_bytes: Option<usize>,
+}
+
+/// Reads `dir/manifest.toml`. Returns `Ok(None)` when the directory has no
+/// manifest.
+pub(crate) fn load_manifest(dir: &Path) -> Result<Option<SteeringManifest>, SteeringError> {
+    let path = dir.join(STEERING_MANIFEST_FILENAME);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(SteeringError {
+                path,
+                message: format!("failed to read manifest: {err}"),
+            });
+        }
+    };
+    toml::from_str(&contents)
+        .map(Some)
+        .map_err(|err| SteeringError {
+            path,
+            message: format!("invalid manifest: {err}"),
+        })
+}
+
+impl SteeringManifest {
+    /// Orders `files` (the `.md` files found in `dir`) by the manifest: listed
+    /// files first, in manifest order, then unlisted files in their existing
+    /// order. Disabled entries and entries whose `activate` globs do not match
+    /// `relative_cwd` are dropped. Invalid entries are reported in `errors`
+    /// and ignored.
+    pub(crate) fn plan(
+        &self,
+        dir: &Path,
+        files: Vec<PathBuf>,
+        relative_cwd: &str,
+        errors: &mut Vec<SteeringError>,
+    ) -> Vec<PlannedFile> {
+        let manifest_path = dir.join(STEERING_MANIFEST_FILENAME);
+        let mut error = |idx: usize, message: String| {
+            errors.push(SteeringError {
+                path: manifest_path.clone(),
+                message: format!("files[{idx}]: {message}"),
+            });
+        };
+
+        let mut planned = Vec::new();
+        let mut listed = HashSet::new();
+        for (idx, entry) in self.files.iter().enumerate() {
+            let name = entry.path.trim();
+            if name.is_empty() || Path::new(name).file_name() != Some(OsStr::new(name)) {
+                error(
+                    idx,
+                    format!(
+                        "`{name}` must be the name of a file next to the manifest, without directories"
+                    ),
+                );
+                continue;
+            }
+            let path = dir.join(name);
+            if !files.contains(&path) {
+                error(
+                    idx,
+                    format!("`{name}` is not a .md file in {}", dir.display()),
+                );
+                continue;
+            }
+            if !listed.insert(path.clone()) {
+                error(idx, format!("`{name}` is listed more than once"));
+                continue;
+            }
+            if let Some(pattern) = entry.activate.iter().find(|glob| glob.trim().is_empty()) {
+                error(
+                    idx,
+                    format!("activate pattern `{pattern}` must not be empty"),
+                );
+                continue;
+            }
+
+            if entry.enabled && entry.activates(relative_cwd) {
+                planned.push(PlannedFile {
+                    path,
+                    max_bytes: entry.max_bytes,
+                });
+            }
+        }
+
+        planned.extend(
+            files
+                .into_iter()
+                .filter(|path| !listed.contains(path))
+                .map(|path| PlannedFile {
+                    path,
+                    max_bytes: None,
+                }),
+        );
+        planned
+    }
+}
+
+impl ManifestEntry {
+    /// Entries without globs always activate. `*` matches across `/`, and a
+    /// glob also matches every directory below the one it names.
+    fn activates(&self, relative_cwd: &str) -> bool {
+        if self.activate.is_empty() {
+            return true;
+        }
+        let mut candidates = vec![relative_cwd];
+        let mut rest = relative_cwd;
+        while let Some((parent, _)) = rest.rsplit_once('/') {
+            candidates.push(parent);
+            rest = parent;
+        }
+        self.activate.iter().any(|glob| {
+            let pattern = WildMatchPattern::<'*', '?'>::new(glob.trim().trim_end_matches('/'));
+            candidates
+                .iter()
+                .any(|candidate| pattern.matches(candidate))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(path: &str) -> ManifestEntry {
+        ManifestEntry {
+            path: path.to_string(),
+            enabled: true,
+            max_bytes: None,
+            activate: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_manifest_and_rejects_unknown_keys() {
+        let manifest: SteeringManifest = toml::from_str(
+            r#"
+[[files]]
+path = "style.md"
+max_bytes = 2048
+activate = ["services/api*"]
+
+[[files]]
+path = "legacy.md"
+enabled = false
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.files,
+            vec![
+                ManifestEntry {
+                    max_bytes: Some(2048),
+                    activate: vec!["services/api*".to_string()],
+                    ..entry("style.md")
+                },
+                ManifestEntry {
+                    enabled: false,
+                    ..entry("legacy.md")
+                },
+            ]
+        );
+        assert!(
+            toml::from_str::<SteeringManifest>("[[files]]\npath = \"a.md\"\norder = 1\n").is_err()
+        );
+    }
+
+    #[test]
+    fn plan_orders_listed_files_first_and_reports_bad_entries() {
+        let dir = Path::new("/steering");
+        let files = vec![dir.join("a.md"), dir.join("b.md"), dir.join("c.md")];
+        let manifest = SteeringManifest {
+            files: vec![
+                ManifestEntry {
+                    max_bytes: Some(10),
+                    ..entry("c.md")
+                },
+                ManifestEntry {
+                    enabled: false,
+                    ..entry("a.md")
+                },
+                entry("missing.md"),
+                entry("../escape.md"),
+                entry("c.md"),
+            ],
+        };
+
+        let mut errors = Vec::new();
+        let planned = manifest.plan(dir, files, "", &mut errors);
+
+        assert_eq!(
+            planned,
+            vec![
+                PlannedFile {
+                    path: dir.join("c.md"),
+                    max_bytes: Some(10),
+                },
+                PlannedFile {
+                    path: dir.join("b.md"),
+                    max_bytes: None,
+                },
+            ]
+        );
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| err.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "files[2]: `missing.md` is not a .md file in /steering",
+                "files[3]: `../escape.md` must be the name of a file next to the manifest, without directories",
+                "files[4]: `c.md` is listed more than once",
+            ]
+        );
+    }
+
+    #[test]
+    fn activation_globs_match_working_directory_and_its_parents() {
+        let web = ManifestEntry {
+            activate: vec!["packages/web".to_string(), "apps/*-ui".to_string()],
+            ..entry("web.md")
+        };
+
+        assert!(web.activates("packages/web"));
+        assert!(web.activates("packages/web/src/components"));
+        assert!(web.activates("apps/admin-ui"));
+        assert!(!web.activates("packages/api"));
+        assert!(!web.activates(""));
+        assert!(entry("always.md").activates(""));
+    }
+}
@@ -0,0 +1,25 @@
+pub mod constraints;
+mod detect;
+mod frontmatter;
+pub mod glossary;
+pub mod loader;
+mod manifest;
+pub mod model;
+pub mod remember;
+pub mod render;
+
+pub use constraints::SteeringConstraintSet;
+pub use glossary::Glossary;
+pub use glossary::load_glossary;
+pub use loader::discover_steering;
+pub use loader::steering_project_root;
+pub use model::SteeringDiscovery;
+pub use model::SteeringError;
+pub use model::SteeringFile;
+pub use model::SteeringScope;
+pub use remember::RememberOutcome;
+pub use remember::learned_steering_dirs;
+pub use remember::learned_steering_path;
+pub use remember::remember_prompt;
+pub use render::render_steering;
+pub use render::render_steering_section;
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use crate::steering::constraints::SteeringConstraintRule;
+
+/// Where a steering file was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringScope {
+    /// `$CODEX_HOME/steering`, shared by every project.
+    Global,
+    /// `.codex/steering` inside the current repository.
+    Project,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteeringFile {
+    pub path: PathBuf,
+    pub scope: SteeringScope,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteeringError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SteeringDiscovery {
+    /// Files that will be injected, in injection order.
+    pub files: Vec<SteeringFile>,
+    /// Files that were found but did not fit in the byte budget, either the
+    /// shared one or their own manifest `max_bytes`.
+    pub skipped: Vec<PathBuf>,
+    pub errors: Vec<SteeringError>,
+    /// `manifest.toml` files that set the order of a steering directory.
+    /// Empty when every directory used file-name order.
+    pub manifests: Vec<PathBuf>,
+    /// `constraints` frontmatter from every file whose `when` condition
+    /// matched, including files left out of the byte budget.
+    pub constraints: Vec<SteeringConstraintRule>,
+}
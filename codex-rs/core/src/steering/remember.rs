@@ -0,0 +1,270 @@
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+
+use crate::config::Config;
+use crate::git_info::resolve_root_git_project_for_trust;
+use crate::steering::loader::global_steering_root;
+
+/// Default location of learned rules, relative to the project root.
+pub const DEFAULT_LEARNED_STEERING_FILE: &str = ".codex/steering/learned.md";
+
+const PROJECT_STEERING_DIR: &str = ".codex/steering";
+
+const REMEMBER_PROMPT_TEMPLATE: &str = include_str!("../../templates/steering/remember.md");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RememberOutcome {
+    Added,
+    AlreadyPresent,
+}
+
+/// File that remembered rules are appended to: `steering.learned_file` when
+/// set, otherwise `.codex/steering/learned.md`, both relative to the root of
+/// the repository containing `config.cwd` (or `config.cwd` itself outside a
+/// repository).
+pub fn learned_steering_path(config: &Config) -> PathBuf {
+    let learned_file = config
+        .steering
+        .learned_file
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LEARNED_STEERING_FILE));
+    project_root(config).join(learned_file)
+}
+
+/// Directories learned rules may be saved in: the project's `.codex/steering`
+/// and `$CODEX_HOME/steering`. A `steering.learned_file` elsewhere is refused.
+pub fn learned_steering_dirs(config: &Config) -> Vec<PathBuf> {
+    vec![
+        project_root(config).join(PROJECT_STEERING_DIR),
+        global_steering_root(&config.codex_home).path,
+    ]
+}
+
+fn project_root(config: &Config) -> PathBuf {
+    resolve_root_git_project_for_trust(&config.cwd).unwrap_or_else(|| config.cwd.clone())
+}
+
+/// Builds the message that asks the model to distill `rule` (or, when `None`,
+/// the user's latest correction in the conversation) into an imperative rule
+/// and save it with the `remember_steering_rule` tool.
+pub fn remember_prompt(config: &Config, rule: Option<&str>) -> String {
+    let path = learned_steering_path(config);
+    let existing_rules = fs::read_to_string(&path)
+        .ok()
+        .filter(|contents| !contents.trim().is_empty())
+        .unwrap_or_else(|| "(none yet)".to_string());
+    let request = match rule.map(str::trim).filter(|rule| !rule.is_empty()) {
+        Some(rule) => format!("Rule to remember: \"{rule}\""),
+        None => "Distill the most recent correction or preference the user expressed in this conversation into a rule.".to_string(),
+    };
+
+    REMEMBER_PROMPT_TEMPLATE
+        .replace("{request}", &request)
+        .replace("{path}", &path.display().to_string())
+        .replace("{existing_rules}", existing_rules.trim_end())
+}
+
+/// Appends `rule` to `path` as a Markdown bullet unless an equivalent rule is
+/// already listed. Comparison ignores case, whitespace, bullet markers, and a
+/// trailing period. `path` must be a file inside one of `steering_dirs`.
+pub(crate) fn append_learned_rule(
+    path: &Path,
+    steering_dirs: &[PathBuf],
+    rule: &str,
+) -> io::Result<RememberOutcome> {
+    ensure_in_steering_dir(path, steering_dirs)?;
+    let existing = read_learned_file(path)?;
+    let Some(addition) = rule_addition(&existing, rule)? else {
+        return Ok(RememberOutcome::AlreadyPresent);
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(addition.as_bytes())?;
+    Ok(RememberOutcome::Added)
+}
+
+/// The change [`append_learned_rule`] would make, for an approval request.
+/// `None` when an equivalent rule is already listed.
+pub(crate) fn learned_rule_change(
+    path: &Path,
+    steering_dirs: &[PathBuf],
+    rule: &str,
+) -> io::Result<Option<FileChange>> {
+    ensure_in_steering_dir(path, steering_dirs)?;
+    let existing = read_learned_file(path)?;
+    let Some(addition) = rule_addition(&existing, rule)? else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(Some(FileChange::Add { content: addition }));
+    }
+    let updated = format!("{existing}{addition}");
+    let unified_diff = similar::TextDiff::from_lines(&existing, &updated)
+        .unified_diff()
+        .context_radius(3)
+        .to_string();
+    Ok(Some(FileChange::Update {
+        unified_diff,
+        move_path: None,
+    }))
+}
+
+/// Refuses paths outside `steering_dirs`, including ones that climb out with
+/// `..` or write through a symlink.
+fn ensure_in_steering_dir(path: &Path, steering_dirs: &[PathBuf]) -> io::Result<()> {
+    let inside = !path
+        .components()
+        .any(|component| component == Component::ParentDir)
+        && steering_dirs
+            .iter()
+            .any(|dir| path.starts_with(dir) && path != dir);
+    let symlink = fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink());
+    if inside && !symlink {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!(
+            "{} is not a file inside a steering directory; learned rules are saved under `.codex/steering` or `$CODEX_HOME/steering`",
+            path.display()
+        ),
+    ))
+}
+
+fn read_learned_file(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// The text to append to `existing` for `rule`, or `None` when an equivalent
+/// rule is already listed.
+fn rule_addition(existing: &str, rule: &str) -> io::Result<Option<String>> {
+    let rule = rule.split_whitespace().collect::<Vec<_>>().join(" ");
+    let rule = rule.trim_start_matches(['-', '*', ' ']);
+    if rule.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "rule must not be empty",
+        ));
+    }
+
+    let normalized = normalize_rule(rule);
+    if existing
+        .lines()
+        .any(|line| normalize_rule(line) == normalized)
+    {
+        return Ok(None);
+    }
+    let separator = if !existing.is_empty() && !existing.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    };
+    Ok(Some(format!("{separator}- {rule}\n")))
+}
+
+fn normalize_rule(line: &str) -> String {
+    line.trim()
+        .trim_start_matches(['-', '*'])
+        .trim()
+        .trim_end_matches('.')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn steering_dirs(tmp: &TempDir) -> Vec<PathBuf> {
+        vec![tmp.path().join(".codex/steering")]
+    }
+
+    #[test]
+    fn appends_rules_and_skips_duplicates() {
+        let tmp = TempDir::new().unwrap();
+        let dirs = steering_dirs(&tmp);
+        let path = tmp.path().join(".codex/steering/learned.md");
+
+        assert_eq!(
+            append_learned_rule(&path, &dirs, "Use pnpm instead of npm.").unwrap(),
+            RememberOutcome::Added
+        );
+        assert_eq!(
+            append_learned_rule(&path, &dirs, "  use PNPM instead of npm ").unwrap(),
+            RememberOutcome::AlreadyPresent
+        );
+        assert_eq!(
+            append_learned_rule(&path, &dirs, "- Run tests before committing.").unwrap(),
+            RememberOutcome::Added
+        );
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "- Use pnpm instead of npm.\n- Run tests before committing.\n"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_rules() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(".codex/steering/learned.md");
+        assert!(append_learned_rule(&path, &steering_dirs(&tmp), " - ").is_err());
+    }
+
+    #[test]
+    fn refuses_paths_outside_the_steering_dirs() {
+        let tmp = TempDir::new().unwrap();
+        let dirs = steering_dirs(&tmp);
+        for path in [
+            tmp.path().join("learned.md"),
+            tmp.path().join(".codex/steering/../config.toml"),
+            tmp.path().join(".codex/steering"),
+        ] {
+            let err = append_learned_rule(&path, &dirs, "Use pnpm.").unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::PermissionDenied, "{path:?}");
+        }
+        assert!(!tmp.path().join("learned.md").exists());
+    }
+
+    #[test]
+    fn describes_the_change_for_approval() {
+        let tmp = TempDir::new().unwrap();
+        let dirs = steering_dirs(&tmp);
+        let path = tmp.path().join(".codex/steering/learned.md");
+
+        assert_eq!(
+            learned_rule_change(&path, &dirs, "Use pnpm.").unwrap(),
+            Some(FileChange::Add {
+                content: "- Use pnpm.\n".to_string()
+            })
+        );
+        append_learned_rule(&path, &dirs, "Use pnpm.").unwrap();
+        assert_eq!(learned_rule_change(&path, &dirs, "use pnpm").unwrap(), None);
+        let Some(FileChange::Update { unified_diff, .. }) =
+            learned_rule_change(&path, &dirs, "Run tests.").unwrap()
+        else {
+            panic!("expected an update");
+        };
+        assert!(unified_diff.contains("+- Run tests.\n"), "{unified_diff}");
+    }
+}
@@ -0,0 +1,26 @@
+use crate::config::Config;
+use crate::features::Feature;
+use crate::steering::loader::discover_steering;
+use crate::steering::model::SteeringFile;
+
+/// Concatenates the loaded steering files into the text injected at the start
+/// of a session. Returns `None` when there is nothing to inject.
+pub fn render_steering_section(files: &[SteeringFile]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let sections: Vec<&str> = files.iter().map(|file| file.contents.as_str()).collect();
+    Some(sections.join("\n\n"))
+}
+
+/// Returns the combined steering text a session started with `config` would
+/// inject, or an empty string when the steering feature is disabled or no
+/// steering files apply. Load errors and budget skips are not reported; use
+/// [`discover_steering`] for those.
+pub fn render_steering(config: &Config) -> String {
+    if !config.features.enabled(Feature::Steering) {
+        return String::new();
+    }
+    render_steering_section(&discover_steering(config).files).unwrap_or_default()
+}
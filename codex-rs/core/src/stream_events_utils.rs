@@ -12,13 +12,21 @@ use crate::function_tool::FunctionCallError;
 use crate::parse_turn_item;
 use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::router::ToolRouter;
+use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentMessageEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
 use futures::Future;
 use tracing::debug;
 use tracing::instrument;
 
+/// Ends an assistant reply cut short by an interrupt, so the model knows the
+/// text is incomplete when the conversation continues.
+pub(crate) const INTERRUPTED_REPLY_MARKER: &str = "[The user interrupted this reply]";
+
 /// Handle a completed output item from the model stream, recording it and
 /// queuing any tool execution futures. This records items immediately so
 /// history and rollout stay in sync even if the turn is later cancelled.
@@ -171,7 +179,8 @@ pub(crate) fn last_assistant_message_from_item(item: &ResponseItem) -> Option<St
         && role == "assistant"
     {
         return content.iter().rev().find_map(|ci| match ci {
-            codex_protocol::models::ContentItem::OutputText { text } => Some(text.clone()),
+            codex_protocol::models::ContentItem::OutputText { text }
+            | codex_protocol::models::ContentItem::Refusal { refusal: text } => Some(text.clone()),
             _ => None,
         });
     }
@@ -209,3 +218,34 @@ pub(crate) fn response_input_to_response_item(input: &ResponseInputItem) -> Opti
         _ => None,
     }
 }
+
+/// Keeps the assistant text streamed before an interrupt instead of
+/// discarding it. History gets it with [`INTERRUPTED_REPLY_MARKER`], so the
+/// next turn builds on the partial reply. The rollout also gets it as an agent
+/// message, so a resumed transcript shows it; live clients already rendered
+/// the deltas.
+pub(crate) async fn record_interrupted_reply(
+    sess: &Session,
+    turn_context: &TurnContext,
+    partial: &str,
+) {
+    let partial = partial.trim_end();
+    if partial.trim().is_empty() {
+        return;
+    }
+    let item = ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText {
+            text: format!("{partial}\n\n{INTERRUPTED_REPLY_MARKER}"),
+        }],
+    };
+    sess.record_conversation_items(turn_context, std::slice::from_ref(&item))
+        .await;
+    sess.persist_rollout_items(&[RolloutItem::EventMsg(EventMsg::AgentMessage(
+        AgentMessageEvent {
+            message: partial.to_string(),
+        },
+    ))])
+    .await;
+}
@@ -13,17 +13,21 @@ use tokio::select;
 use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
+use tracing::info;
 use tracing::trace;
 use tracing::warn;
 
 use crate::AuthManager;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::features::Feature;
 use crate::openai_models::models_manager::ModelsManager;
+use crate::protocol::BackgroundEventEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::TaskCompleteEvent;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
+use crate::session_title;
 use crate::state::ActiveTurn;
 use crate::state::RunningTask;
 use crate::state::TaskKind;
@@ -110,6 +114,7 @@ impl Session {
         task: T,
     ) {
         self.abort_all_tasks(TurnAbortReason::Replaced).await;
+        self.services.idle_summarizer.stop();
 
         let task: Arc<dyn SessionTask> = Arc::new(task);
         let task_kind = task.kind();
@@ -179,6 +184,31 @@ impl Session {
         drop(active);
         if should_close_sessions {
             self.close_unified_exec_sessions().await;
+            let provider = turn_context.client.get_provider();
+            if let Some(base_url) = self.prewarm_base_url(&provider) {
+                self.services
+                    .connection_prewarmer
+                    .keep_warm(base_url, provider.http_client());
+            }
+            if self.enabled(Feature::IdleSummarization) {
+                self.services.idle_summarizer.start(Arc::clone(self));
+            }
+            if last_agent_message.is_some() {
+                session_title::maybe_generate(self, Arc::clone(&turn_context)).await;
+            }
+        }
+        if !turn_context.turn_grants.is_empty() {
+            let granted = turn_context
+                .turn_grants
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            info!(%granted, "reverting capabilities granted for this turn");
+            let event = EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: format!("Reverted temporary grant: {granted}."),
+            });
+            self.send_event(turn_context.as_ref(), event).await;
         }
         let event = EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message });
         self.send_event(turn_context.as_ref(), event).await;
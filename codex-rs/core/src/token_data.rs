@@ -87,16 +87,14 @@ pub enum IdTokenInfoError {
     Json(#[from] serde_json::Error),
 }
 
-pub fn parse_id_token(id_token: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
-    // JWT format: header.payload.signature
-    let mut parts = id_token.split('.');
-    let (_header_b64, payload_b64, _sig_b64) = match (parts.next(), parts.next(), parts.next()) {
-        (Some(h), Some(p), Some(s)) if !h.is_empty() && !p.is_empty() && !s.is_empty() => (h, p, s),
-        _ => return Err(IdTokenInfoError::InvalidFormat),
-    };
+#[derive(Deserialize)]
+struct ExpiryClaims {
+    #[serde(default)]
+    exp: Option<i64>,
+}
 
-    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_b64)?;
-    let claims: IdClaims = serde_json::from_slice(&payload_bytes)?;
+pub fn parse_id_token(id_token: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
+    let claims: IdClaims = decode_jwt_claims(id_token)?;
 
     match claims.auth {
         Some(auth) => Ok(IdTokenInfo {
@@ -114,6 +112,24 @@ pub fn parse_id_token(id_token: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
     }
 }
 
+/// The `exp` claim of `jwt`, in seconds since the Unix epoch.
+pub fn jwt_expiry(jwt: &str) -> Result<Option<i64>, IdTokenInfoError> {
+    let claims: ExpiryClaims = decode_jwt_claims(jwt)?;
+    Ok(claims.exp)
+}
+
+fn decode_jwt_claims<T: serde::de::DeserializeOwned>(jwt: &str) -> Result<T, IdTokenInfoError> {
+    // JWT format: header.payload.signature
+    let mut parts = jwt.split('.');
+    let (_header_b64, payload_b64, _sig_b64) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(h), Some(p), Some(s)) if !h.is_empty() && !p.is_empty() && !s.is_empty() => (h, p, s),
+        _ => return Err(IdTokenInfoError::InvalidFormat),
+    };
+
+    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_b64)?;
+    Ok(serde_json::from_slice(&payload_bytes)?)
+}
+
 fn deserialize_id_token<'de, D>(deserializer: D) -> Result<IdTokenInfo, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -192,4 +208,24 @@ mod tests {
         assert!(info.email.is_none());
         assert!(info.get_chatgpt_plan_type().is_none());
     }
+
+    #[test]
+    fn jwt_expiry_reads_the_exp_claim() {
+        fn b64url_no_pad(bytes: &[u8]) -> String {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+        }
+
+        let payload = serde_json::json!({ "sub": "123", "exp": 1_700_000_000 });
+        let payload_b64 = b64url_no_pad(&serde_json::to_vec(&payload).unwrap());
+        let fake_jwt = format!("e30.{payload_b64}.sig");
+
+        assert_eq!(
+            jwt_expiry(&fake_jwt).expect("should parse"),
+            Some(1_700_000_000)
+        );
+        assert!(matches!(
+            jwt_expiry("not-a-jwt"),
+            Err(IdTokenInfoError::InvalidFormat)
+        ));
+    }
 }
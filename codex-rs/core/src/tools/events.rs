@@ -1,8 +1,10 @@
+use crate::bash::parse_shell_lc_plain_commands;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
@@ -12,13 +14,26 @@ use crate::protocol::ExecCommandSource;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
+use crate::protocol::TerminologyViolation;
+use crate::protocol::TerminologyWarningEvent;
 use crate::protocol::TurnDiffEvent;
+use crate::steering::Glossary;
+use crate::steering::glossary::GLOSSARY_FILENAME;
+use crate::steering::glossary::commit_message;
+use crate::steering::glossary::lint_doc_changes;
+use crate::steering::load_glossary;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
+use crate::workspace_events::WorkspaceEvent;
+use crate::workspace_events::WorkspaceEventCtx;
+use crate::workspace_events::WorkspaceSubscriber;
+use async_trait::async_trait;
 use codex_protocol::parse_command::ParsedCommand;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
 use std::time::Duration;
 
 use super::format_exec_output_str;
@@ -190,19 +205,10 @@ impl ToolEmitter {
                     )
                     .await;
             }
-            (Self::ApplyPatch { changes, .. }, ToolEventStage::Success(output)) => {
-                emit_patch_end(
-                    ctx,
-                    changes.clone(),
-                    output.stdout.text.clone(),
-                    output.stderr.text.clone(),
-                    output.exit_code == 0,
-                )
-                .await;
-            }
             (
                 Self::ApplyPatch { changes, .. },
-                ToolEventStage::Failure(ToolEventFailure::Output(output)),
+                ToolEventStage::Success(output)
+                | ToolEventStage::Failure(ToolEventFailure::Output(output)),
             ) => {
                 emit_patch_end(
                     ctx,
@@ -407,6 +413,7 @@ async fn emit_exec_end(
     exec_input: ExecCommandInput<'_>,
     exec_result: ExecCommandResult,
 ) {
+    let exit_code = exec_result.exit_code;
     ctx.session
         .send_event(
             ctx.turn,
@@ -428,6 +435,14 @@ async fn emit_exec_end(
             }),
         )
         .await;
+    publish(
+        ctx,
+        WorkspaceEvent::CommandFinished {
+            command: exec_input.command.to_vec(),
+            exit_code,
+        },
+    )
+    .await;
 }
 
 async fn emit_patch_end(
@@ -446,20 +461,185 @@ async fn emit_patch_end(
                 stdout,
                 stderr,
                 success,
-                changes,
+                changes: changes.clone(),
             }),
         )
         .await;
 
-    if let Some(tracker) = ctx.turn_diff_tracker {
+    // Announce the changed files first so subscribers that cache file
+    // contents see the new ones while handling the patch.
+    if let Some(files_changed) = WorkspaceEvent::files_changed_by(&changes, success) {
+        publish(ctx, files_changed).await;
+    }
+    publish(ctx, WorkspaceEvent::PatchApplied { changes, success }).await;
+}
+
+async fn publish(ctx: ToolEventCtx<'_>, event: WorkspaceEvent) {
+    let event_ctx = WorkspaceEventCtx {
+        session: ctx.session,
+        turn: Some(ctx.turn),
+        turn_diff_tracker: ctx.turn_diff_tracker,
+    };
+    ctx.session
+        .services
+        .workspace_events
+        .publish(event_ctx, event)
+        .await;
+}
+
+/// Sends the updated turn diff after every patch.
+pub(crate) struct TurnDiffPublisher;
+
+#[async_trait]
+impl WorkspaceSubscriber for TurnDiffPublisher {
+    async fn on_event(&self, ctx: WorkspaceEventCtx<'_>, event: &WorkspaceEvent) {
+        let (WorkspaceEvent::PatchApplied { .. }, Some(turn), Some(tracker)) =
+            (event, ctx.turn, ctx.turn_diff_tracker)
+        else {
+            return;
+        };
         let unified_diff = {
             let mut guard = tracker.lock().await;
             guard.get_unified_diff()
         };
         if let Ok(Some(unified_diff)) = unified_diff {
             ctx.session
-                .send_event(ctx.turn, EventMsg::TurnDiff(TurnDiffEvent { unified_diff }))
+                .send_event(turn, EventMsg::TurnDiff(TurnDiffEvent { unified_diff }))
                 .await;
         }
     }
 }
+
+/// Checks documentation edits and commit messages against the steering
+/// glossary. The glossary is read once per turn and read again after
+/// steering reloads, a patch edits a `glossary.md`, or a command runs (it may
+/// have edited one).
+#[derive(Default)]
+pub(crate) struct TerminologyLinter {
+    cached: StdMutex<Option<CachedGlossary>>,
+}
+
+struct CachedGlossary {
+    turn_id: String,
+    cwd: PathBuf,
+    glossary: Arc<Glossary>,
+}
+
+impl TerminologyLinter {
+    /// The steering glossary for this turn, when steering is enabled and the
+    /// glossary has at least one entry.
+    fn glossary(&self, turn: &TurnContext) -> Option<Arc<Glossary>> {
+        let config = turn.client.config();
+        if !config.features.enabled(Feature::Steering) {
+            return None;
+        }
+        let mut cached = self.lock_cached();
+        let glossary = match cached.as_ref() {
+            Some(entry) if entry.turn_id == turn.sub_id && entry.cwd == turn.cwd => {
+                Arc::clone(&entry.glossary)
+            }
+            _ => {
+                let glossary = Arc::new(load_glossary(&config.codex_home, &turn.cwd));
+                *cached = Some(CachedGlossary {
+                    turn_id: turn.sub_id.clone(),
+                    cwd: turn.cwd.clone(),
+                    glossary: Arc::clone(&glossary),
+                });
+                glossary
+            }
+        };
+        (!glossary.is_empty()).then_some(glossary)
+    }
+
+    /// Checks the message of a successful `git commit` against the glossary.
+    async fn lint_commit_message(&self, session: &Session, turn: &TurnContext, command: &[String]) {
+        let commands =
+            parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+        let messages: Vec<String> = commands
+            .iter()
+            .filter_map(|argv| commit_message(argv))
+            .collect();
+        if messages.is_empty() {
+            return;
+        }
+        let Some(glossary) = self.glossary(turn) else {
+            return;
+        };
+        let lints = messages
+            .iter()
+            .map(|message| ("commit message".to_string(), glossary.lint(message)))
+            .filter(|(_, violations)| !violations.is_empty())
+            .collect();
+        emit_terminology_warnings(session, turn, lints).await;
+    }
+
+    fn lock_cached(&self) -> std::sync::MutexGuard<'_, Option<CachedGlossary>> {
+        self.cached
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl WorkspaceSubscriber for TerminologyLinter {
+    async fn on_event(&self, ctx: WorkspaceEventCtx<'_>, event: &WorkspaceEvent) {
+        match event {
+            WorkspaceEvent::SteeringReloaded => {
+                self.lock_cached().take();
+            }
+            WorkspaceEvent::FilesChanged { paths } => {
+                let glossary_changed = paths.iter().any(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name == GLOSSARY_FILENAME)
+                });
+                if glossary_changed {
+                    self.lock_cached().take();
+                }
+            }
+            WorkspaceEvent::PatchApplied {
+                changes,
+                success: true,
+            } => {
+                let Some(turn) = ctx.turn else {
+                    return;
+                };
+                let Some(glossary) = self.glossary(turn) else {
+                    return;
+                };
+                let lints = lint_doc_changes(
+                    &glossary,
+                    changes
+                        .iter()
+                        .map(|(path, change)| (path.as_path(), change)),
+                );
+                emit_terminology_warnings(ctx.session, turn, lints).await;
+            }
+            WorkspaceEvent::CommandFinished {
+                command, exit_code, ..
+            } => {
+                self.lock_cached().take();
+                if *exit_code == 0
+                    && let Some(turn) = ctx.turn
+                {
+                    self.lint_commit_message(ctx.session, turn, command).await;
+                }
+            }
+            WorkspaceEvent::PatchApplied { .. } => {}
+        }
+    }
+}
+
+async fn emit_terminology_warnings(
+    session: &Session,
+    turn: &TurnContext,
+    lints: Vec<(String, Vec<TerminologyViolation>)>,
+) {
+    for (source, violations) in lints {
+        session
+            .send_event(
+                turn,
+                EventMsg::TerminologyWarning(TerminologyWarningEvent { source, violations }),
+            )
+            .await;
+    }
+}
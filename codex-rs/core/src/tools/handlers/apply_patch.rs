@@ -3,6 +3,7 @@ use std::path::Path;
 
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
+use crate::apply_patch::PARTIAL_PATCH_NOTE;
 use crate::apply_patch::convert_apply_patch_to_protocol;
 use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
@@ -130,7 +131,10 @@ impl ToolHandler for ApplyPatchHandler {
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let mut content = emitter.finish(event_ctx, out).await?;
+                        if apply.user_modified_patch {
+                            content = format!("{content}\n{PARTIAL_PATCH_NOTE}");
+                        }
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -217,7 +221,10 @@ pub(crate) async fn intercept_apply_patch(
                         .await;
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    let content = emitter.finish(event_ctx, out).await?;
+                    let mut content = emitter.finish(event_ctx, out).await?;
+                    if apply.user_modified_patch {
+                        content = format!("{content}\n{PARTIAL_PATCH_NOTE}");
+                    }
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
@@ -0,0 +1,306 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::archive::ArchiveEntry;
+use crate::archive::ArchiveEntryKind;
+use crate::archive::ArchiveFormat;
+use crate::archive::detect_format;
+use crate::archive::list_entries;
+use crate::archive::read_member;
+use crate::artifacts::session_artifacts_dir;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ArchiveHandler;
+
+/// Subdirectory of the session artifacts area that receives extracted members.
+const EXTRACTED_ARCHIVES_SUBDIR: &str = "archives";
+const DEFAULT_EXTRACT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_EXTRACT_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+fn default_offset() -> usize {
+    1
+}
+
+fn default_limit() -> usize {
+    200
+}
+
+#[derive(Deserialize)]
+struct ListArchiveArgs {
+    archive_path: String,
+    #[serde(default = "default_offset")]
+    offset: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+struct ExtractArchiveMemberArgs {
+    archive_path: String,
+    member: String,
+    #[serde(default)]
+    max_bytes: Option<u64>,
+}
+
+#[async_trait]
+impl ToolHandler for ArchiveHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "archive handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        match tool_name.as_str() {
+            "list_archive" => {
+                let args: ListArchiveArgs = parse_arguments(&arguments)?;
+                let archive_path = turn.resolve_path(Some(args.archive_path));
+                handle_list_archive(archive_path, args.offset, args.limit).await
+            }
+            "extract_archive_member" => {
+                let args: ExtractArchiveMemberArgs = parse_arguments(&arguments)?;
+                let archive_path = turn.resolve_path(Some(args.archive_path));
+                let destination_root = session_artifacts_dir(
+                    &turn.client.config().codex_home,
+                    session.conversation_id(),
+                )
+                .join(EXTRACTED_ARCHIVES_SUBDIR);
+                let max_bytes = args
+                    .max_bytes
+                    .unwrap_or(DEFAULT_EXTRACT_MAX_BYTES)
+                    .min(MAX_EXTRACT_MAX_BYTES);
+                handle_extract_member(archive_path, args.member, max_bytes, destination_root).await
+            }
+            other => Err(FunctionCallError::RespondToModel(format!(
+                "unsupported archive tool: {other}"
+            ))),
+        }
+    }
+}
+
+fn parse_arguments<T: DeserializeOwned>(arguments: &str) -> Result<T, FunctionCallError> {
+    serde_json::from_str(arguments).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err:?}"))
+    })
+}
+
+async fn handle_list_archive(
+    archive_path: PathBuf,
+    offset: usize,
+    limit: usize,
+) -> Result<ToolOutput, FunctionCallError> {
+    if offset == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "offset must be a 1-indexed entry number".to_string(),
+        ));
+    }
+    if limit == 0 {
+        return Err(FunctionCallError::RespondToModel(
+            "limit must be greater than zero".to_string(),
+        ));
+    }
+
+    let path = archive_path.clone();
+    let (format, entries) = tokio::task::spawn_blocking(move || {
+        let format = detect_format(&path)?;
+        list_entries(&path, format).map(|entries| (format, entries))
+    })
+    .await
+    .map_err(|err| FunctionCallError::RespondToModel(format!("archive listing failed: {err}")))?
+    .map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "failed to read archive `{}`: {err}",
+            archive_path.display()
+        ))
+    })?;
+
+    Ok(ToolOutput::Function {
+        content: format_listing(&archive_path, format, &entries, offset, limit),
+        content_items: None,
+        success: Some(true),
+    })
+}
+
+async fn handle_extract_member(
+    archive_path: PathBuf,
+    member: String,
+    max_bytes: u64,
+    destination_root: PathBuf,
+) -> Result<ToolOutput, FunctionCallError> {
+    let relative_member = sanitize_member_path(&member).ok_or_else(|| {
+        FunctionCallError::RespondToModel(format!(
+            "member `{member}` must be a relative path without `..` components"
+        ))
+    })?;
+    let archive_name = archive_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "archive".to_string());
+    let destination = destination_root.join(archive_name).join(relative_member);
+
+    let path = archive_path.clone();
+    let member_name = member.clone();
+    let data = tokio::task::spawn_blocking(move || {
+        let format = detect_format(&path)?;
+        read_member(&path, format, &member_name, max_bytes)
+    })
+    .await
+    .map_err(|err| FunctionCallError::RespondToModel(format!("archive extraction failed: {err}")))?
+    .map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "failed to extract from `{}`: {err}",
+            archive_path.display()
+        ))
+    })?;
+
+    if let Some(parent) = destination.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to create artifacts directory `{}`: {err}",
+                parent.display()
+            ))
+        })?;
+    }
+    tokio::fs::write(&destination, &data).await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "failed to write `{}`: {err}",
+            destination.display()
+        ))
+    })?;
+
+    Ok(ToolOutput::Function {
+        content: format!(
+            "Extracted `{member}` ({} bytes) to {}",
+            data.len(),
+            destination.display()
+        ),
+        content_items: None,
+        success: Some(true),
+    })
+}
+
+fn format_listing(
+    archive_path: &Path,
+    format: ArchiveFormat,
+    entries: &[ArchiveEntry],
+    offset: usize,
+    limit: usize,
+) -> String {
+    let mut lines = Vec::with_capacity(limit.min(entries.len()) + 2);
+    lines.push(format!(
+        "Archive: {} ({}, {} entries)",
+        archive_path.display(),
+        format.as_str(),
+        entries.len()
+    ));
+
+    let start = offset - 1;
+    let selected = entries.iter().skip(start).take(limit);
+    for entry in selected {
+        let suffix = match entry.kind {
+            ArchiveEntryKind::Directory if !entry.path.ends_with('/') => "/",
+            ArchiveEntryKind::Symlink => "@",
+            ArchiveEntryKind::Other => "?",
+            _ => "",
+        };
+        lines.push(format!("{:>12}  {}{suffix}", entry.size, entry.path));
+    }
+
+    let end = start.saturating_add(limit);
+    if end < entries.len() {
+        lines.push(format!(
+            "More than {limit} entries found; pass offset={} to continue",
+            end + 1
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Keeps extracted members inside the artifacts directory regardless of how
+/// the archive names them.
+fn sanitize_member_path(member: &str) -> Option<PathBuf> {
+    let mut sanitized = PathBuf::new();
+    for component in Path::new(member).components() {
+        match component {
+            Component::Normal(part) => sanitized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    if sanitized.as_os_str().is_empty() {
+        None
+    } else {
+        Some(sanitized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(path: &str, size: u64, kind: ArchiveEntryKind) -> ArchiveEntry {
+        ArchiveEntry {
+            path: path.to_string(),
+            size,
+            kind,
+        }
+    }
+
+    #[test]
+    fn listing_reports_pagination() {
+        let entries = vec![
+            entry("bin", 0, ArchiveEntryKind::Directory),
+            entry("bin/run", 1200, ArchiveEntryKind::File),
+            entry("bin/latest", 0, ArchiveEntryKind::Symlink),
+        ];
+
+        let listing = format_listing(Path::new("/tmp/a.zip"), ArchiveFormat::Zip, &entries, 1, 2);
+        assert_eq!(
+            listing,
+            [
+                "Archive: /tmp/a.zip (zip, 3 entries)",
+                "           0  bin/",
+                "        1200  bin/run",
+                "More than 2 entries found; pass offset=3 to continue",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn member_paths_cannot_escape_destination() {
+        assert_eq!(
+            sanitize_member_path("./pkg/lib.rs"),
+            Some(PathBuf::from("pkg/lib.rs"))
+        );
+        assert_eq!(sanitize_member_path("../etc/passwd"), None);
+        assert_eq!(sanitize_member_path("/etc/passwd"), None);
+        assert_eq!(sanitize_member_path("."), None);
+    }
+}
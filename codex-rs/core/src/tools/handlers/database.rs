@@ -0,0 +1,311 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use rusqlite::Connection;
+use rusqlite::ErrorCode;
+use rusqlite::OpenFlags;
+use rusqlite::hooks::AuthAction;
+use rusqlite::hooks::AuthContext;
+use rusqlite::hooks::Authorization;
+use rusqlite::types::ValueRef;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde_json::Value;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct DatabaseHandler;
+
+const DEFAULT_ROW_LIMIT: usize = 100;
+const MAX_ROW_LIMIT: usize = 1000;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+/// How many SQLite VM instructions run between deadline checks.
+const PROGRESS_INTERVAL: i32 = 1000;
+
+#[derive(Deserialize)]
+struct QuerySqliteArgs {
+    #[serde(default)]
+    database_path: Option<String>,
+    #[serde(default)]
+    connection: Option<String>,
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct QueryResult {
+    row_count: usize,
+    limit: usize,
+    truncated: bool,
+    rows: Vec<QueryRow>,
+}
+
+/// One result row, serialized as a JSON object whose keys keep the column
+/// order of the query.
+#[derive(Debug)]
+struct QueryRow {
+    columns: Arc<[String]>,
+    values: Vec<Value>,
+}
+
+impl Serialize for QueryRow {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for (column, value) in self.columns.iter().zip(&self.values) {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+#[async_trait]
+impl ToolHandler for DatabaseHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { turn, payload, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "query_sqlite handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: QuerySqliteArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let database_path = match (args.database_path, args.connection) {
+            (Some(path), None) => turn.resolve_path(Some(path)),
+            (None, Some(name)) => {
+                let config = turn.client.config();
+                let Some(connection) = config.databases.get(&name) else {
+                    let mut known: Vec<&str> =
+                        config.databases.keys().map(String::as_str).collect();
+                    known.sort_unstable();
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "unknown database connection `{name}`; configured connections: [{}]",
+                        known.join(", ")
+                    )));
+                };
+                turn.resolve_path(Some(connection.path.to_string_lossy().into_owned()))
+            }
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "provide exactly one of `database_path` or `connection`".to_string(),
+                ));
+            }
+        };
+
+        validate_query(&args.query)?;
+        if !tokio::fs::try_exists(&database_path).await.unwrap_or(false) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "database `{}` does not exist",
+                database_path.display()
+            )));
+        }
+
+        let limit = args
+            .limit
+            .unwrap_or(DEFAULT_ROW_LIMIT)
+            .clamp(1, MAX_ROW_LIMIT);
+        let query = args.query;
+        let result =
+            tokio::task::spawn_blocking(move || run_sqlite_query(&database_path, &query, limit))
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("query task failed: {err}"))
+                })??;
+        let content = serde_json::to_string(&result).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to serialize query result: {err}"))
+        })?;
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn validate_query(query: &str) -> Result<(), FunctionCallError> {
+    if query.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "query must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Runs `query` on a connection opened with `SQLITE_OPEN_READ_ONLY`. Only a
+/// single read-only statement is accepted, `ATTACH` is refused so the query
+/// cannot reach other files, and the query is interrupted after
+/// [`QUERY_TIMEOUT`]. Stops stepping once `limit` rows have been collected.
+fn run_sqlite_query(
+    database_path: &Path,
+    query: &str,
+    limit: usize,
+) -> Result<QueryResult, FunctionCallError> {
+    let conn = Connection::open_with_flags(
+        database_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|err| {
+        FunctionCallError::RespondToModel(format!(
+            "failed to open database `{}`: {err}",
+            database_path.display()
+        ))
+    })?;
+    conn.authorizer(Some(|context: AuthContext<'_>| match context.action {
+        AuthAction::Attach { .. } | AuthAction::Detach { .. } => Authorization::Deny,
+        _ => Authorization::Allow,
+    }));
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    conn.progress_handler(PROGRESS_INTERVAL, Some(move || Instant::now() > deadline));
+
+    let mut statement = conn.prepare(query).map_err(query_error)?;
+    if !statement.readonly() {
+        return Err(FunctionCallError::RespondToModel(
+            "only read-only statements are allowed".to_string(),
+        ));
+    }
+    let columns: Arc<[String]> = statement
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut cursor = statement.query([]).map_err(query_error)?;
+    while let Some(row) = cursor.next().map_err(query_error)? {
+        if rows.len() == limit {
+            truncated = true;
+            break;
+        }
+        let values = (0..columns.len())
+            .map(|index| row.get_ref(index).map(json_value))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(query_error)?;
+        rows.push(QueryRow {
+            columns: Arc::clone(&columns),
+            values,
+        });
+    }
+
+    Ok(QueryResult {
+        row_count: rows.len(),
+        limit,
+        truncated,
+        rows,
+    })
+}
+
+fn query_error(err: rusqlite::Error) -> FunctionCallError {
+    if err.sqlite_error_code() == Some(ErrorCode::OperationInterrupted) {
+        return FunctionCallError::RespondToModel(format!(
+            "query did not finish within {} seconds",
+            QUERY_TIMEOUT.as_secs()
+        ));
+    }
+    FunctionCallError::RespondToModel(format!("query failed: {err}"))
+}
+
+/// Blobs have no JSON counterpart and are returned as hex strings.
+fn json_value(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(value) => Value::from(value),
+        ValueRef::Real(value) => {
+            serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number)
+        }
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(bytes) => {
+            Value::String(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn sample_database() -> (TempDir, PathBuf) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.sqlite3");
+        let conn = Connection::open(&path).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE users (name TEXT, id INTEGER, avatar BLOB);
+             INSERT INTO users VALUES ('ada', 1, x'cafe'), ('grace', 2, NULL);",
+        )
+        .expect("seed");
+        (dir, path)
+    }
+
+    fn query(path: &Path, sql: &str, limit: usize) -> Result<Value, String> {
+        run_sqlite_query(path, sql, limit)
+            .map(|result| serde_json::to_value(result).expect("serialize"))
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn returns_rows_in_column_order() {
+        let (_dir, path) = sample_database();
+        let result = run_sqlite_query(&path, "SELECT * FROM users ORDER BY id", 10).expect("query");
+        assert_eq!(
+            serde_json::to_string(&result).expect("serialize"),
+            r#"{"row_count":2,"limit":10,"truncated":false,"rows":[{"name":"ada","id":1,"avatar":"cafe"},{"name":"grace","id":2,"avatar":null}]}"#
+        );
+    }
+
+    #[test]
+    fn stops_at_the_row_limit() {
+        let (_dir, path) = sample_database();
+        assert_eq!(
+            query(&path, "SELECT id FROM users ORDER BY id", 1),
+            Ok(json!({"row_count": 1, "limit": 1, "truncated": true, "rows": [{"id": 1}]}))
+        );
+    }
+
+    #[test]
+    fn rejects_writes_and_attach() {
+        let (dir, path) = sample_database();
+        assert!(query(&path, "DELETE FROM users", 10).is_err());
+        assert!(query(&path, "CREATE TABLE t (x)", 10).is_err());
+        let other = dir.path().join("other.sqlite3");
+        let attach = format!("ATTACH DATABASE '{}' AS other", other.display());
+        assert!(query(&path, &attach, 10).is_err());
+        assert!(!other.exists());
+        assert_eq!(
+            query(&path, "SELECT count(*) AS n FROM users", 10).map(|v| v["rows"].clone()),
+            Ok(json!([{"n": 2}]))
+        );
+    }
+
+    #[test]
+    fn rejects_empty_queries() {
+        assert!(validate_query("  ").is_err());
+        assert!(validate_query("SELECT 1").is_ok());
+    }
+}
@@ -12,6 +12,7 @@ use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_roots::outside_workspace;
 
 pub struct GrepFilesHandler;
 
@@ -73,6 +74,9 @@ impl ToolHandler for GrepFilesHandler {
 
         let limit = args.limit.min(MAX_LIMIT);
         let search_path = turn.resolve_path(args.path.clone());
+        if let Some(reason) = outside_workspace(&turn.client.config(), &search_path) {
+            return Err(FunctionCallError::RespondToModel(reason));
+        }
 
         verify_path_exists(&search_path).await?;
 
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Method;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use serde::Deserialize;
+use serde_json::json;
+use url::Host;
+use url::Url;
+
+use crate::default_client::apply_network_config;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct HttpRequestHandler;
+
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const MAX_TIMEOUT_MS: u64 = 120_000;
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+const MAX_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Deserialize)]
+struct HttpRequestArgs {
+    url: String,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    #[serde(default)]
+    max_body_bytes: Option<usize>,
+}
+
+impl HttpRequestArgs {
+    fn method(&self) -> Result<Method, FunctionCallError> {
+        let method = self.method.as_deref().unwrap_or("GET").to_ascii_uppercase();
+        Method::from_bytes(method.as_bytes()).map_err(|_| {
+            FunctionCallError::RespondToModel(format!("invalid HTTP method `{method}`"))
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for HttpRequestHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        let ToolPayload::Function { arguments } = &invocation.payload else {
+            return true;
+        };
+
+        serde_json::from_str::<HttpRequestArgs>(arguments)
+            .ok()
+            .and_then(|args| args.method().ok())
+            .is_none_or(|method| !method.is_safe())
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { turn, payload, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "http_request handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: HttpRequestArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let url = Url::parse(&args.url).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("invalid url `{}`: {err}", args.url))
+        })?;
+        let config = turn.client.config();
+        ensure_host_allowed(
+            &url,
+            &config.http_request_allowed_hosts,
+            turn.sandbox_policy.has_full_network_access(),
+        )?;
+
+        let method = args.method()?;
+        let headers = build_headers(&args.headers)?;
+        let timeout = Duration::from_millis(
+            args.timeout_ms
+                .unwrap_or(DEFAULT_TIMEOUT_MS)
+                .min(MAX_TIMEOUT_MS),
+        );
+        let max_body_bytes = args
+            .max_body_bytes
+            .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+            .min(MAX_MAX_BODY_BYTES);
+
+        // Redirects are not followed so a response cannot bounce the request
+        // to a host outside the allowlist. Proxies from the environment are
+        // ignored; only `network.proxy` is honored.
+        let builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .timeout(timeout);
+        let client = apply_network_config(builder, &config.network)
+            .build()
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to build HTTP client: {err}"))
+            })?;
+
+        let mut request = client.request(method, url).headers(headers);
+        if let Some(body) = args.body {
+            request = request.body(body);
+        }
+        let mut response = request
+            .send()
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(format!("request failed: {err}")))?;
+
+        let status = response.status();
+        let response_headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.as_str().to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+
+        let mut body = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response.chunk().await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read response body: {err}"))
+        })? {
+            let remaining = max_body_bytes - body.len();
+            if chunk.len() > remaining {
+                body.extend_from_slice(&chunk[..remaining]);
+                truncated = true;
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let content = json!({
+            "status": status.as_u16(),
+            "reason": status.canonical_reason(),
+            "headers": response_headers,
+            "body": String::from_utf8_lossy(&body),
+            "body_truncated": truncated,
+        });
+
+        Ok(ToolOutput::Function {
+            content: content.to_string(),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Loopback hosts are always reachable. Other hosts must be listed in
+/// `http_request.allowed_hosts`, either as a bare host (any port) or as
+/// `host:port`, and are refused outright when the sandbox policy withholds
+/// network access, since the request would bypass it.
+fn ensure_host_allowed(
+    url: &Url,
+    allowed_hosts: &[String],
+    network_access: bool,
+) -> Result<(), FunctionCallError> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "unsupported url scheme `{}`; only http and https are allowed",
+            url.scheme()
+        )));
+    }
+
+    let Some(host) = url.host() else {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "url `{url}` has no host"
+        )));
+    };
+
+    let is_loopback = match &host {
+        Host::Domain(domain) => {
+            let domain = domain.to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Host::Ipv4(ip) => IpAddr::V4(*ip).is_loopback(),
+        Host::Ipv6(ip) => IpAddr::V6(*ip).is_loopback(),
+    };
+    if is_loopback {
+        return Ok(());
+    }
+    if !network_access {
+        return Err(FunctionCallError::RespondToModel(
+            "http_request can only reach localhost: the sandbox policy for this turn does not allow network access".to_string(),
+        ));
+    }
+
+    let host_name = match &host {
+        Host::Domain(domain) => domain.to_ascii_lowercase(),
+        Host::Ipv4(ip) => ip.to_string(),
+        Host::Ipv6(ip) => format!("[{ip}]"),
+    };
+    let host_with_port = url
+        .port_or_known_default()
+        .map(|port| format!("{host_name}:{port}"));
+    let allowed = allowed_hosts.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        entry == host_name || host_with_port.as_deref() == Some(entry.as_str())
+    });
+    if allowed {
+        return Ok(());
+    }
+
+    Err(FunctionCallError::RespondToModel(format!(
+        "host `{host_name}` is not allowed; http_request only reaches localhost and hosts listed in `http_request.allowed_hosts`"
+    )))
+}
+
+fn build_headers(headers: &BTreeMap<String, String>) -> Result<HeaderMap, FunctionCallError> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            FunctionCallError::RespondToModel(format!("invalid header name `{name}`"))
+        })?;
+        let header_value = HeaderValue::from_str(value).map_err(|_| {
+            FunctionCallError::RespondToModel(format!("invalid value for header `{name}`"))
+        })?;
+        map.append(header_name, header_value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(url: &str, allowed_hosts: &[&str]) -> bool {
+        let allowed_hosts: Vec<String> = allowed_hosts.iter().map(ToString::to_string).collect();
+        ensure_host_allowed(&Url::parse(url).unwrap(), &allowed_hosts, true).is_ok()
+    }
+
+    #[test]
+    fn loopback_hosts_are_always_allowed() {
+        assert!(allowed("http://localhost:3000/health", &[]));
+        assert!(allowed("http://api.localhost/", &[]));
+        assert!(allowed("http://127.0.0.1:8080/", &[]));
+        assert!(allowed("http://[::1]:8080/", &[]));
+    }
+
+    #[test]
+    fn other_hosts_require_allowlist_entry() {
+        assert!(!allowed("https://example.com/", &[]));
+        assert!(allowed("https://example.com/", &["example.com"]));
+        assert!(allowed("https://Example.com/", &["example.com:443"]));
+        assert!(!allowed("http://example.com:8080/", &["example.com:443"]));
+        assert!(!allowed("http://10.0.0.5/", &["example.com"]));
+        assert!(allowed("http://10.0.0.5/", &["10.0.0.5"]));
+    }
+
+    #[test]
+    fn sandbox_without_network_only_reaches_loopback() {
+        let url = Url::parse("http://localhost:3000/health").unwrap();
+        assert!(ensure_host_allowed(&url, &[], false).is_ok());
+        let url = Url::parse("http://127.0.0.1:8080/").unwrap();
+        assert!(ensure_host_allowed(&url, &[], false).is_ok());
+        let url = Url::parse("https://example.com/").unwrap();
+        assert!(ensure_host_allowed(&url, &["example.com".to_string()], false).is_err());
+        let url = Url::parse("file:///etc/passwd").unwrap();
+        assert!(ensure_host_allowed(&url, &[], false).is_err());
+    }
+
+    #[test]
+    fn non_http_schemes_are_rejected() {
+        assert!(!allowed("file:///etc/passwd", &[]));
+        assert!(!allowed("ftp://localhost/", &[]));
+    }
+}
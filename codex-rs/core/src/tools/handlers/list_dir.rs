@@ -15,6 +15,7 @@ use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_roots::outside_workspace;
 
 pub struct ListDirHandler;
 
@@ -51,7 +52,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -100,6 +101,10 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
+        if let Some(reason) = outside_workspace(&turn.client.config(), &path) {
+            return Err(FunctionCallError::RespondToModel(reason));
+        }
+
         let entries = list_dir_slice(&path, offset, limit, depth).await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
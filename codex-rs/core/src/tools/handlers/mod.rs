@@ -1,11 +1,16 @@
 pub mod apply_patch;
+mod archive;
+mod database;
 mod grep_files;
+mod http_request;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
 mod plan;
+mod process_inspect;
 mod read_file;
 mod shell;
+mod steering;
 mod test_sync;
 mod unified_exec;
 mod view_image;
@@ -13,14 +18,19 @@ mod view_image;
 pub use plan::PLAN_TOOL;
 
 pub use apply_patch::ApplyPatchHandler;
+pub use archive::ArchiveHandler;
+pub use database::DatabaseHandler;
 pub use grep_files::GrepFilesHandler;
+pub use http_request::HttpRequestHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
+pub use process_inspect::ProcessInspectHandler;
 pub use read_file::ReadFileHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
+pub use steering::SteeringHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
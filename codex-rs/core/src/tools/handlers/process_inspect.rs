@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::function_tool::FunctionCallError;
+use crate::process_inspect::find_processes;
+use crate::process_inspect::list_listening_sockets;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ProcessInspectHandler;
+
+const MAX_PROCESS_RESULTS: usize = 100;
+
+#[derive(Deserialize)]
+struct ListListeningPortsArgs {
+    #[serde(default)]
+    port: Option<u16>,
+}
+
+#[derive(Deserialize)]
+struct FindProcessesArgs {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    port: Option<u16>,
+}
+
+#[async_trait]
+impl ToolHandler for ProcessInspectHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            tool_name, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "process inspection handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        match tool_name.as_str() {
+            "list_listening_ports" => {
+                let args: ListListeningPortsArgs = parse_arguments(&arguments)?;
+                let sockets = run_blocking(move || list_listening_sockets(args.port)).await?;
+                json_output(&sockets)
+            }
+            "find_processes" => {
+                let args: FindProcessesArgs = parse_arguments(&arguments)?;
+                let name = args.name.filter(|name| !name.trim().is_empty());
+                if name.is_none() && args.port.is_none() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "provide `name`, `port`, or both".to_string(),
+                    ));
+                }
+                let mut processes =
+                    run_blocking(move || find_processes(name.as_deref(), args.port)).await?;
+                processes.truncate(MAX_PROCESS_RESULTS);
+                json_output(&processes)
+            }
+            other => Err(FunctionCallError::RespondToModel(format!(
+                "unsupported process inspection tool: {other}"
+            ))),
+        }
+    }
+}
+
+fn parse_arguments<T: DeserializeOwned>(arguments: &str) -> Result<T, FunctionCallError> {
+    serde_json::from_str(arguments).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err:?}"))
+    })
+}
+
+async fn run_blocking<T, F>(f: F) -> Result<T, FunctionCallError>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("process inspection failed: {err}"))
+        })?
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to inspect processes: {err}"))
+        })
+}
+
+fn json_output<T: Serialize>(value: &T) -> Result<ToolOutput, FunctionCallError> {
+    let content = serde_json::to_string(value).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to serialize result: {err}"))
+    })?;
+    Ok(ToolOutput::Function {
+        content,
+        content_items: None,
+        success: Some(true),
+    })
+}
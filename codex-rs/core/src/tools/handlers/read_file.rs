@@ -5,12 +5,14 @@ use async_trait::async_trait;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::workspace_roots::outside_workspace;
 
 pub struct ReadFileHandler;
 
@@ -96,7 +98,12 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -140,15 +147,31 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
-        let collected = match mode {
-            ReadMode::Slice => slice::read(&path, offset, limit).await?,
+        if let Some(reason) = outside_workspace(&turn.client.config(), &path) {
+            return Err(FunctionCallError::RespondToModel(reason));
+        }
+
+        let content = match mode {
+            ReadMode::Slice if session.enabled(Feature::DifferentialReads) => {
+                differential::read(
+                    &session.services.file_versions,
+                    &path,
+                    offset,
+                    limit,
+                    turn.truncation_policy,
+                )
+                .await?
+            }
+            ReadMode::Slice => slice::read(&path, offset, limit).await?.join("\n"),
             ReadMode::Indentation => {
                 let indentation = indentation.unwrap_or_default();
-                indentation::read_block(&path, offset, limit, indentation).await?
+                indentation::read_block(&path, offset, limit, indentation)
+                    .await?
+                    .join("\n")
             }
         };
         Ok(ToolOutput::Function {
-            content: collected.join("\n"),
+            content,
             content_items: None,
             success: Some(true),
         })
@@ -222,6 +245,128 @@ mod slice {
     }
 }
 
+/// Slice reads that leave out what earlier reads of the file already showed.
+mod differential {
+    use crate::file_versions::FileVersions;
+    use crate::file_versions::Reread;
+    use crate::function_tool::FunctionCallError;
+    use crate::tools::handlers::read_file::format_line;
+    use crate::truncate::TruncationPolicy;
+    use crate::truncate::approx_token_count;
+    use std::ops::RangeInclusive;
+    use std::path::Path;
+
+    pub async fn read(
+        versions: &FileVersions,
+        path: &Path,
+        offset: usize,
+        limit: usize,
+        policy: TruncationPolicy,
+    ) -> Result<String, FunctionCallError> {
+        let bytes = tokio::fs::read(path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
+        })?;
+        let lines = split_lines(&bytes);
+        if offset > lines.len() {
+            return Err(FunctionCallError::RespondToModel(
+                "offset exceeds file length".to_string(),
+            ));
+        }
+        let range = offset..=offset.saturating_add(limit - 1).min(lines.len());
+
+        let full = render_lines(&lines, range.clone());
+        let (content, diff_shown) = match versions.reread(path, &lines, range.clone()) {
+            Some(reread) => {
+                let partial = render_reread(&lines, &range, &reread);
+                if partial.len() < full.len() {
+                    (partial, reread.diff.is_some())
+                } else {
+                    (full, false)
+                }
+            }
+            None => (full, false),
+        };
+
+        // Output over the budget is truncated before the model sees it, so
+        // only a reply that fits counts as seen.
+        if approx_token_count(&content) <= policy.token_budget() {
+            versions.record(path, lines, range, diff_shown);
+        } else {
+            versions.forget(path);
+        }
+        Ok(content)
+    }
+
+    fn split_lines(bytes: &[u8]) -> Vec<String> {
+        let mut lines: Vec<String> = bytes
+            .split(|byte| *byte == b'\n')
+            .map(|line| {
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                String::from_utf8_lossy(line).into_owned()
+            })
+            .collect();
+        // A trailing newline ends the last line rather than starting another.
+        if bytes.is_empty() || bytes.ends_with(b"\n") {
+            lines.pop();
+        }
+        lines
+    }
+
+    fn render_lines(lines: &[String], numbers: impl IntoIterator<Item = usize>) -> String {
+        numbers
+            .into_iter()
+            .map(|number| format!("L{number}: {}", format_line(lines[number - 1].as_bytes())))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_reread(lines: &[String], range: &RangeInclusive<usize>, reread: &Reread) -> String {
+        let mut out = match &reread.diff {
+            Some(diff) => format!(
+                "The file changed since you last read it. Diff from the version you read:\n{diff}"
+            ),
+            None => "The file is unchanged since you last read it.".to_string(),
+        };
+        let omitted: Vec<usize> = range
+            .clone()
+            .filter(|number| !reread.unseen.contains(number))
+            .collect();
+        if !omitted.is_empty() {
+            out.push_str(&format!(
+                "\nOmitted lines you already have: {}.",
+                describe_ranges(&omitted)
+            ));
+        }
+        if !reread.unseen.is_empty() {
+            out.push('\n');
+            out.push_str(&render_lines(lines, reread.unseen.iter().copied()));
+        }
+        out
+    }
+
+    /// `[1, 2, 3, 7]` becomes `L1-L3, L7`.
+    fn describe_ranges(numbers: &[usize]) -> String {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for &number in numbers {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == number => *end = number,
+                _ => ranges.push((number, number)),
+            }
+        }
+        ranges
+            .into_iter()
+            .map(|(start, end)| {
+                if start == end {
+                    format!("L{start}")
+                } else {
+                    format!("L{start}-L{end}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 mod indentation {
     use crate::function_tool::FunctionCallError;
     use crate::tools::handlers::read_file::IndentationArgs;
@@ -516,6 +661,48 @@ gamma
         Ok(())
     }
 
+    #[tokio::test]
+    async fn differential_rereads_send_diff_and_unseen_lines() -> anyhow::Result<()> {
+        use crate::file_versions::FileVersions;
+        use crate::truncate::TruncationPolicy;
+
+        let temp = NamedTempFile::new()?;
+        let write_lines = |changed: Option<usize>| {
+            let contents: String = (1..=20)
+                .map(|i| match changed {
+                    Some(line) if line == i => "changed\n".to_string(),
+                    _ => format!("line {i}: {}\n", "x".repeat(50)),
+                })
+                .collect();
+            std::fs::write(temp.path(), contents)
+        };
+        let versions = FileVersions::default();
+        let policy = TruncationPolicy::Tokens(10_000);
+
+        write_lines(None)?;
+        let first = differential::read(&versions, temp.path(), 1, 10, policy).await?;
+        assert!(first.starts_with("L1: line 1: "), "{first}");
+
+        write_lines(Some(3))?;
+        let second = differential::read(&versions, temp.path(), 1, 20, policy).await?;
+        assert!(
+            second.starts_with("The file changed since you last read it."),
+            "{second}"
+        );
+        assert!(second.contains("\n+changed\n"), "{second}");
+        assert!(
+            second.contains("Omitted lines you already have: L1-L10.\nL11: line 11: "),
+            "{second}"
+        );
+
+        let third = differential::read(&versions, temp.path(), 1, 20, policy).await?;
+        assert_eq!(
+            third,
+            "The file is unchanged since you last read it.\nOmitted lines you already have: L1-L20."
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn errors_when_offset_exceeds_length() -> anyhow::Result<()> {
         let mut temp = NamedTempFile::new()?;
@@ -4,6 +4,10 @@ use codex_protocol::models::ShellToolCallParams;
 use std::sync::Arc;
 
 use crate::codex::TurnContext;
+use crate::config::types::ToolTimeouts;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::DEFAULT_EXEC_COMMAND_TIMEOUT_MS;
 use crate::exec::ExecParams;
 use crate::exec_env::create_env;
 use crate::exec_policy::create_exec_approval_requirement_for_command;
@@ -23,6 +27,7 @@ use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
 
 pub struct ShellHandler;
 
@@ -33,7 +38,11 @@ impl ShellHandler {
         ExecParams {
             command: params.command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
-            expiration: params.timeout_ms.into(),
+            expiration: shell_timeout_ms(
+                &turn_context.client.config().tool_timeouts,
+                params.timeout_ms,
+            )
+            .into(),
             env: create_env(&turn_context.shell_environment_policy),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             justification: params.justification,
@@ -42,6 +51,17 @@ impl ShellHandler {
     }
 }
 
+/// The timeout for a shell command whose call asked for `requested`:
+/// `shell_ms` when it asked for none, capped at `shell_max_ms`. `None` leaves
+/// the built-in default.
+fn shell_timeout_ms(timeouts: &ToolTimeouts, requested: Option<u64>) -> Option<u64> {
+    let timeout = requested.or(timeouts.shell_ms);
+    match timeouts.shell_max_ms {
+        Some(max) => Some(timeout.unwrap_or(DEFAULT_EXEC_COMMAND_TIMEOUT_MS).min(max)),
+        None => timeout,
+    }
+}
+
 impl ShellCommandHandler {
     fn base_command(shell: &Shell, command: &str, login: Option<bool>) -> Vec<String> {
         let use_login_shell = login.unwrap_or(true);
@@ -59,7 +79,11 @@ impl ShellCommandHandler {
         ExecParams {
             command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
-            expiration: params.timeout_ms.into(),
+            expiration: shell_timeout_ms(
+                &turn_context.client.config().tool_timeouts,
+                params.timeout_ms,
+            )
+            .into(),
             env: create_env(&turn_context.shell_environment_policy),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             justification: params.justification,
@@ -261,6 +285,14 @@ impl ShellHandler {
             exec_params.sandbox_permissions,
         )
         .await;
+        let exec_approval_requirement = match &turn.client.config().approvals_policy {
+            Some(policy) => policy.apply_to_command(
+                &exec_params.command,
+                turn.approval_policy,
+                exec_approval_requirement,
+            ),
+            None => exec_approval_requirement,
+        };
 
         let req = ShellRequest {
             command: exec_params.command.clone(),
@@ -279,9 +311,35 @@ impl ShellHandler {
             call_id: call_id.clone(),
             tool_name: tool_name.to_string(),
         };
-        let out = orchestrator
+        let mut out = orchestrator
             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
             .await;
+        // A command that hung, such as a stuck test or network call, is run
+        // again up to `tool_timeouts.shell_retries` times before the timeout
+        // is reported to the model.
+        let retries = turn
+            .client
+            .config()
+            .tool_timeouts
+            .shell_retries
+            .unwrap_or(0);
+        for attempt in 1..=retries {
+            if !matches!(
+                out,
+                Err(ToolError::Codex(CodexErr::Sandbox(
+                    SandboxErr::Timeout { .. }
+                )))
+            ) {
+                break;
+            }
+            tracing::info!(
+                "retrying timed-out command (attempt {attempt} of {retries}): {:?}",
+                req.command
+            );
+            out = orchestrator
+                .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
+                .await;
+        }
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         let content = emitter.finish(event_ctx, out).await?;
         Ok(ToolOutput::Function {
@@ -311,6 +369,9 @@ mod tests {
     use crate::shell_snapshot::ShellSnapshot;
     use crate::tools::handlers::ShellCommandHandler;
 
+    use super::shell_timeout_ms;
+    use crate::config::types::ToolTimeouts;
+
     /// The logic for is_known_safe_command() has heuristics for known shells,
     /// so we must ensure the commands generated by [ShellCommandHandler] can be
     /// recognized as safe if the `command` is safe.
@@ -418,4 +479,26 @@ mod tests {
             shell.derive_exec_args("echo non login shell", false)
         );
     }
+
+    #[test]
+    fn shell_timeout_is_capped_by_shell_max_ms() {
+        let timeouts = ToolTimeouts {
+            shell_ms: Some(60_000),
+            ..Default::default()
+        };
+        assert_eq!(shell_timeout_ms(&timeouts, None), Some(60_000));
+        assert_eq!(shell_timeout_ms(&timeouts, Some(600_000)), Some(600_000));
+
+        let capped = ToolTimeouts {
+            shell_max_ms: Some(120_000),
+            ..timeouts
+        };
+        assert_eq!(shell_timeout_ms(&capped, None), Some(60_000));
+        assert_eq!(shell_timeout_ms(&capped, Some(600_000)), Some(120_000));
+        assert_eq!(
+            shell_timeout_ms(&ToolTimeouts::default(), None),
+            None,
+            "no configuration keeps the built-in default"
+        );
+    }
 }
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SandboxPolicy;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::steering::RememberOutcome;
+use crate::steering::learned_steering_dirs;
+use crate::steering::learned_steering_path;
+use crate::steering::remember::append_learned_rule;
+use crate::steering::remember::learned_rule_change;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct SteeringHandler;
+
+#[derive(Deserialize)]
+struct RememberSteeringRuleArgs {
+    rule: String,
+}
+
+#[async_trait]
+impl ToolHandler for SteeringHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "remember_steering_rule handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: RememberSteeringRuleArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let config = turn.client.config();
+        let path = learned_steering_path(&config);
+        let steering_dirs = learned_steering_dirs(&config);
+        let save_error = |err: std::io::Error| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to save rule to {}: {err}",
+                path.display()
+            ))
+        };
+
+        // Outside the sandbox's writable roots the write needs the user's
+        // approval, the same as an apply_patch edit would.
+        if !sandbox_allows_write(&turn.sandbox_policy, &turn.cwd, &path)
+            && let Some(change) =
+                learned_rule_change(&path, &steering_dirs, &args.rule).map_err(save_error)?
+        {
+            if turn.approval_policy == AskForApproval::Never {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "cannot save rule: the sandbox does not allow writing {} and approval_policy is never",
+                    path.display()
+                )));
+            }
+            let decision = session
+                .request_patch_approval(
+                    &turn,
+                    call_id,
+                    HashMap::from([(path.clone(), change)]),
+                    Some("Save a learned steering rule".to_string()),
+                    None,
+                )
+                .await
+                .await
+                .unwrap_or_default();
+            if !matches!(
+                decision,
+                ReviewDecision::Approved
+                    | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedForProject { .. }
+            ) {
+                return Err(FunctionCallError::RespondToModel(
+                    "the user declined saving the rule".to_string(),
+                ));
+            }
+        }
+
+        let target = path.clone();
+        let outcome = tokio::task::spawn_blocking(move || {
+            append_learned_rule(&target, &steering_dirs, &args.rule)
+        })
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to save rule: {err}")))?
+        .map_err(save_error)?;
+
+        let content = match outcome {
+            RememberOutcome::Added => format!("Saved rule to {}", path.display()),
+            RememberOutcome::AlreadyPresent => {
+                format!("An equivalent rule is already in {}", path.display())
+            }
+        };
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn sandbox_allows_write(policy: &SandboxPolicy, cwd: &Path, path: &Path) -> bool {
+    policy.has_full_disk_write_access()
+        || policy
+            .get_writable_roots_with_cwd(cwd)
+            .iter()
+            .any(|root| root.is_path_writable(path))
+}
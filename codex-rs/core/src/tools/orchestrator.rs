@@ -78,19 +78,37 @@ impl ToolOrchestrator {
                     }
                     ReviewDecision::Approved
                     | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                    | ReviewDecision::ApprovedForSession => {}
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedForProject { .. }
+                    | ReviewDecision::ApprovedWithModifiedPatch { .. } => {}
                 }
                 already_approved = true;
             }
         }
 
         // 2) First attempt under the selected sandbox.
+        let container = tool_ctx
+            .session
+            .services
+            .container_sandbox
+            .as_ref()
+            .filter(|_| tool.runs_in_container());
         let initial_sandbox = match tool.sandbox_mode_for_first_attempt(req) {
             SandboxOverride::BypassSandboxFirstAttempt => crate::exec::SandboxType::None,
-            SandboxOverride::NoOverride => self
-                .sandbox
-                .select_initial(&turn_ctx.sandbox_policy, tool.sandbox_preference()),
+            SandboxOverride::NoOverride => self.sandbox.select_initial(
+                &turn_ctx.sandbox_policy,
+                tool.sandbox_preference(),
+                container,
+            ),
         };
+        if initial_sandbox == crate::exec::SandboxType::Container
+            && let Some(container) = container
+            && let Err(err) = container.ensure_image().await
+        {
+            return Err(ToolError::Rejected(format!(
+                "container sandbox unavailable: {err}"
+            )));
+        }
 
         // Platform-specific flag gating is handled by SandboxManager::select_initial
         // via crate::safety::get_platform_sandbox().
@@ -100,6 +118,8 @@ impl ToolOrchestrator {
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
+            network_proxy: tool_ctx.session.services.sandbox_network_proxy.as_ref(),
+            container,
         };
 
         match tool.run(req, &initial_attempt, tool_ctx).await {
@@ -140,7 +160,9 @@ impl ToolOrchestrator {
                         }
                         ReviewDecision::Approved
                         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                        | ReviewDecision::ApprovedForSession => {}
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedForProject { .. }
+                        | ReviewDecision::ApprovedWithModifiedPatch { .. } => {}
                     }
                 }
 
@@ -150,6 +172,8 @@ impl ToolOrchestrator {
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
+                    network_proxy: None,
+                    container: None,
                 };
 
                 // Second attempt.
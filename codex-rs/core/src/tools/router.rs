@@ -28,6 +28,9 @@ pub struct ToolCall {
 pub struct ToolRouter {
     registry: ToolRegistry,
     specs: Vec<ConfiguredToolSpec>,
+    /// Refuse mutating calls with this message, as during the exploration
+    /// phase or in plan mode.
+    read_only: Option<&'static str>,
 }
 
 impl ToolRouter {
@@ -38,7 +41,25 @@ impl ToolRouter {
         let builder = build_specs(config, mcp_tools);
         let (specs, registry) = builder.build();
 
-        Self { registry, specs }
+        Self {
+            registry,
+            specs,
+            read_only: None,
+        }
+    }
+
+    /// Router for the read-only exploration phase and plan mode. MCP tools
+    /// are left out because there is no way to tell which of them write.
+    /// Mutating calls are answered with `rejection`.
+    pub fn read_only(config: &ToolsConfig, rejection: &'static str) -> Self {
+        let builder = build_specs(&config.read_only(), None);
+        let (specs, registry) = builder.build();
+
+        Self {
+            registry,
+            specs,
+            read_only: Some(rejection),
+        }
     }
 
     pub fn specs(&self) -> Vec<ToolSpec> {
@@ -151,6 +172,17 @@ impl ToolRouter {
             payload,
         };
 
+        if let Some(rejection) = self.read_only
+            && let Some(handler) = self.registry.handler(&invocation.tool_name)
+            && handler.is_mutating(&invocation).await
+        {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(rejection.to_string()),
+            ));
+        }
+
         match self.registry.dispatch(invocation).await {
             Ok(response) => Ok(response),
             Err(FunctionCallError::Fatal(message)) => Err(FunctionCallError::Fatal(message)),
@@ -84,6 +84,10 @@ impl Sandboxable for ApplyPatchRuntime {
     fn escalate_on_failure(&self) -> bool {
         true
     }
+    fn runs_in_container(&self) -> bool {
+        // Patches are applied by re-invoking the Codex executable.
+        false
+    }
 }
 
 impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
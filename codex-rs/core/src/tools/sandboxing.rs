@@ -9,7 +9,9 @@ use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
+use crate::sandboxing::ContainerSandbox;
 use crate::sandboxing::SandboxManager;
+use crate::sandboxing::SandboxNetworkProxy;
 use crate::sandboxing::SandboxTransformError;
 use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
@@ -207,6 +209,12 @@ pub(crate) trait Sandboxable {
     fn escalate_on_failure(&self) -> bool {
         true
     }
+    /// Whether the container sandbox (`sandbox.backend`) may run this tool.
+    /// Tools that run Codex's own binary cannot, since the image lacks it;
+    /// they keep the platform sandbox.
+    fn runs_in_container(&self) -> bool {
+        true
+    }
 }
 
 pub(crate) struct ToolCtx<'a> {
@@ -237,6 +245,8 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    pub(crate) network_proxy: Option<&'a SandboxNetworkProxy>,
+    pub(crate) container: Option<&'a ContainerSandbox>,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -250,6 +260,8 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.network_proxy,
+            self.container,
         )
     }
 }
@@ -22,6 +22,11 @@ pub(crate) struct ToolsConfig {
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_request: bool,
     pub include_view_image_tool: bool,
+    pub include_archive_tools: bool,
+    pub include_database_tools: bool,
+    pub include_http_request_tool: bool,
+    pub include_process_tools: bool,
+    pub include_steering_tools: bool,
     pub experimental_supported_tools: Vec<String>,
 }
 
@@ -39,6 +44,11 @@ impl ToolsConfig {
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_view_image_tool = features.enabled(Feature::ViewImageTool);
+        let include_archive_tools = features.enabled(Feature::ArchiveTools);
+        let include_database_tools = features.enabled(Feature::DatabaseTools);
+        let include_http_request_tool = features.enabled(Feature::HttpRequestTool);
+        let include_process_tools = features.enabled(Feature::ProcessTools);
+        let include_steering_tools = features.enabled(Feature::Steering);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -70,9 +80,27 @@ impl ToolsConfig {
             apply_patch_tool_type,
             web_search_request: include_web_search_request,
             include_view_image_tool,
+            include_archive_tools,
+            include_database_tools,
+            include_http_request_tool,
+            include_process_tools,
+            include_steering_tools,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
         }
     }
+
+    /// The tools offered during the read-only exploration phase: file edits,
+    /// HTTP requests, and steering rule writes are left out. Archive and
+    /// database tools stay, since they only read from the workspace (archive
+    /// members are extracted into the session artifacts directory).
+    pub fn read_only(&self) -> Self {
+        Self {
+            apply_patch_tool_type: None,
+            include_http_request_tool: false,
+            include_steering_tools: false,
+            ..self.clone()
+        }
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -671,6 +699,273 @@ fn create_list_dir_tool() -> ToolSpec {
     })
 }
 
+fn create_list_archive_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "archive_path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Path to a .zip, .tar, or .tar.gz file. Relative paths resolve against the turn cwd."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "offset".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "The entry number to start listing from. Must be 1 or greater.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some("The maximum number of entries to return.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "list_archive".to_string(),
+        description: "Lists the members of a zip or tar archive with their uncompressed sizes without extracting anything."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["archive_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_extract_archive_member_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "archive_path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Path to a .zip, .tar, or .tar.gz file. Relative paths resolve against the turn cwd."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "member".to_string(),
+        JsonSchema::String {
+            description: Some("Exact member path as reported by list_archive.".to_string()),
+        },
+    );
+    properties.insert(
+        "max_bytes".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Refuse to extract members larger than this many bytes. Defaults to 10 MiB and is capped at 50 MiB."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "extract_archive_member".to_string(),
+        description: "Extracts a single file from a zip or tar archive into the session artifacts directory and returns the path it was written to."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["archive_path".to_string(), "member".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_query_sqlite_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "database_path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Path to a SQLite database file. Relative paths resolve against the turn cwd. Mutually exclusive with `connection`."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "connection".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Name of a database declared under `[databases]` in the Codex config. Mutually exclusive with `database_path`."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some("The SQL query to run.".to_string()),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "The maximum number of rows to return. Defaults to 100 and is capped at 1000."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "query_sqlite".to_string(),
+        description: "Runs a SQL query against a local SQLite database opened read-only and returns the matching rows as JSON objects."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_http_request_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Absolute http(s) URL. Only localhost and hosts allowlisted in the Codex config are reachable."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "method".to_string(),
+        JsonSchema::String {
+            description: Some("HTTP method. Defaults to GET.".to_string()),
+        },
+    );
+    properties.insert(
+        "headers".to_string(),
+        JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(JsonSchema::String { description: None }.into()),
+        },
+    );
+    properties.insert(
+        "body".to_string(),
+        JsonSchema::String {
+            description: Some("Request body sent verbatim.".to_string()),
+        },
+    );
+    properties.insert(
+        "timeout_ms".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Request timeout in milliseconds. Defaults to 30000 and is capped at 120000."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "max_body_bytes".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Truncate the response body after this many bytes. Defaults to 65536 and is capped at 1048576."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "http_request".to_string(),
+        description: "Sends an HTTP request to a local or allowlisted endpoint and returns the status, response headers, and (possibly truncated) body. Redirects are not followed."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_list_listening_ports_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "port".to_string(),
+        JsonSchema::Number {
+            description: Some("Only report sockets listening on this port.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "list_listening_ports".to_string(),
+        description: "Lists listening TCP sockets with their address, port, and owning process when it can be determined."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_find_processes_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "name".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Case-insensitive substring to match against process names.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "port".to_string(),
+        JsonSchema::Number {
+            description: Some("Only return processes listening on this TCP port.".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "find_processes".to_string(),
+        description: "Finds your own running processes by name and/or listening port and returns their pid, parent pid, command line (shortened, with credential-like arguments masked), and listening ports. Provide at least one filter."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_remember_steering_rule_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "rule".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "One short imperative instruction, e.g. \"Use pnpm instead of npm.\"".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "remember_steering_rule".to_string(),
+        description: "Appends a rule to the project's learned steering file so it is loaded in future sessions. Only call this when the user asks you to remember something."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["rule".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_mcp_resources_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -981,14 +1276,19 @@ pub(crate) fn build_specs(
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::ArchiveHandler;
+    use crate::tools::handlers::DatabaseHandler;
     use crate::tools::handlers::GrepFilesHandler;
+    use crate::tools::handlers::HttpRequestHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::ProcessInspectHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
+    use crate::tools::handlers::SteeringHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
@@ -1102,6 +1402,37 @@ pub(crate) fn build_specs(
         builder.register_handler("view_image", view_image_handler);
     }
 
+    if config.include_archive_tools {
+        let archive_handler = Arc::new(ArchiveHandler);
+        builder.push_spec_with_parallel_support(create_list_archive_tool(), true);
+        builder.push_spec_with_parallel_support(create_extract_archive_member_tool(), true);
+        builder.register_handler("list_archive", archive_handler.clone());
+        builder.register_handler("extract_archive_member", archive_handler);
+    }
+
+    if config.include_database_tools {
+        builder.push_spec_with_parallel_support(create_query_sqlite_tool(), true);
+        builder.register_handler("query_sqlite", Arc::new(DatabaseHandler));
+    }
+
+    if config.include_http_request_tool {
+        builder.push_spec_with_parallel_support(create_http_request_tool(), true);
+        builder.register_handler("http_request", Arc::new(HttpRequestHandler));
+    }
+
+    if config.include_process_tools {
+        let process_inspect_handler = Arc::new(ProcessInspectHandler);
+        builder.push_spec_with_parallel_support(create_list_listening_ports_tool(), true);
+        builder.push_spec_with_parallel_support(create_find_processes_tool(), true);
+        builder.register_handler("list_listening_ports", process_inspect_handler.clone());
+        builder.register_handler("find_processes", process_inspect_handler);
+    }
+
+    if config.include_steering_tools {
+        builder.push_spec(create_remember_steering_rule_tool());
+        builder.register_handler("remember_steering_rule", Arc::new(SteeringHandler));
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1493,6 +1824,130 @@ mod tests {
         assert_contains_tool_names(&tools, &subset);
     }
 
+    #[test]
+    fn test_archive_tools_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ArchiveTools);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        assert_contains_tool_names(&tools, &["list_archive", "extract_archive_member"]);
+        assert!(find_tool(&tools, "list_archive").supports_parallel_tool_calls);
+        assert!(find_tool(&tools, "extract_archive_member").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_database_tools_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "query_sqlite")
+        );
+
+        let mut features = Features::with_defaults();
+        features.enable(Feature::DatabaseTools);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        assert_contains_tool_names(&tools, &["query_sqlite"]);
+        assert!(find_tool(&tools, "query_sqlite").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_http_request_tool_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::HttpRequestTool);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        assert_contains_tool_names(&tools, &["http_request"]);
+        assert!(find_tool(&tools, "http_request").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_process_tools_gated_by_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ProcessTools);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        assert_contains_tool_names(&tools, &["list_listening_ports", "find_processes"]);
+        assert!(find_tool(&tools, "list_listening_ports").supports_parallel_tool_calls);
+        assert!(find_tool(&tools, "find_processes").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_read_only_tools_drop_editing_tools() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::HttpRequestTool);
+        features.enable(Feature::Steering);
+        features.enable(Feature::ArchiveTools);
+        features.enable(Feature::DatabaseTools);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config.read_only(), None).build();
+        let names: Vec<&str> = tools.iter().map(|tool| tool.spec.name()).collect();
+
+        for name in ["apply_patch", "http_request", "remember_steering_rule"] {
+            assert!(!names.contains(&name), "{name} offered while read-only");
+        }
+        for name in ["list_archive", "extract_archive_member", "query_sqlite"] {
+            assert!(names.contains(&name), "{name} missing while read-only");
+        }
+        assert!(
+            names
+                .iter()
+                .any(|name| name.starts_with("shell") || *name == "exec_command"),
+            "read-only toolset should keep a shell: {names:?}"
+        );
+    }
+
+    #[test]
+    fn test_remember_tool_gated_by_steering_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Steering);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        assert_contains_tool_names(&tools, &["remember_steering_rule"]);
+        assert!(!find_tool(&tools, "remember_steering_rule").supports_parallel_tool_calls);
+    }
+
     #[test]
     #[ignore]
     fn test_parallel_support_flags() {
@@ -0,0 +1,196 @@
+//! Capabilities granted for a single turn via `Op::GrantTurnCapability`.
+//!
+//! Grants are queued on the session and layered over the persistent
+//! configuration when the next task starts. They are never written back to
+//! the session configuration, so the following turn sees the original policy.
+
+use std::path::Path;
+
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TurnCapabilityGrant;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::features::Features;
+use crate::features::feature_for_key;
+
+/// Check that `grant` can be honored under `sandbox_policy` and normalize it
+/// (relative writable roots are resolved against `cwd`).
+pub(crate) fn validate_turn_grant(
+    grant: TurnCapabilityGrant,
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Result<TurnCapabilityGrant, String> {
+    match grant {
+        TurnCapabilityGrant::Network | TurnCapabilityGrant::WritableRoot { .. }
+            if matches!(sandbox_policy, SandboxPolicy::ReadOnly) =>
+        {
+            Err(format!(
+                "cannot grant {grant} under the read-only sandbox; switch to workspace-write first"
+            ))
+        }
+        TurnCapabilityGrant::Network => Ok(TurnCapabilityGrant::Network),
+        TurnCapabilityGrant::Tool { name } => match feature_for_key(&name) {
+            Some(feature) if feature.gates_tool() => Ok(TurnCapabilityGrant::Tool { name }),
+            Some(_) => Err(format!("feature `{name}` does not provide a tool")),
+            None => Err(format!("unknown tool feature `{name}`")),
+        },
+        TurnCapabilityGrant::WritableRoot { path } => {
+            let resolved = AbsolutePathBuf::resolve_path_against_base(&path, cwd)
+                .map_err(|err| format!("invalid writable root {}: {err}", path.display()))?;
+            if !resolved.as_path().is_dir() {
+                return Err(format!(
+                    "writable root {} is not a directory",
+                    resolved.display()
+                ));
+            }
+            Ok(TurnCapabilityGrant::WritableRoot {
+                path: resolved.into_path_buf(),
+            })
+        }
+    }
+}
+
+/// Layer `grants` over the sandbox policy and feature set for one turn.
+pub(crate) fn apply_turn_grants(
+    grants: &[TurnCapabilityGrant],
+    sandbox_policy: &mut SandboxPolicy,
+    features: &mut Features,
+) {
+    for grant in grants {
+        match grant {
+            TurnCapabilityGrant::Network => {
+                if let SandboxPolicy::WorkspaceWrite { network_access, .. } = sandbox_policy {
+                    *network_access = true;
+                }
+            }
+            TurnCapabilityGrant::Tool { name } => {
+                if let Some(feature) = feature_for_key(name)
+                    && feature.gates_tool()
+                {
+                    features.enable(feature);
+                }
+            }
+            TurnCapabilityGrant::WritableRoot { path } => {
+                if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = sandbox_policy
+                    && let Ok(root) = AbsolutePathBuf::from_absolute_path(path)
+                    && !writable_roots.contains(&root)
+                {
+                    writable_roots.push(root);
+                }
+            }
+        }
+    }
+}
+
+/// Whether any of `grants` changes the sandbox policy (as opposed to only
+/// enabling tools).
+pub(crate) fn grants_touch_sandbox(grants: &[TurnCapabilityGrant]) -> bool {
+    grants
+        .iter()
+        .any(|grant| !matches!(grant, TurnCapabilityGrant::Tool { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn workspace_write() -> SandboxPolicy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        }
+    }
+
+    #[test]
+    fn network_grant_is_rejected_under_read_only() {
+        let err = validate_turn_grant(
+            TurnCapabilityGrant::Network,
+            &SandboxPolicy::ReadOnly,
+            Path::new("/"),
+        )
+        .unwrap_err();
+        assert!(err.contains("read-only"), "{err}");
+    }
+
+    #[test]
+    fn unknown_tool_is_rejected() {
+        let err = validate_turn_grant(
+            TurnCapabilityGrant::Tool {
+                name: "does_not_exist".to_string(),
+            },
+            &workspace_write(),
+            Path::new("/"),
+        )
+        .unwrap_err();
+        assert_eq!(err, "unknown tool feature `does_not_exist`");
+    }
+
+    #[test]
+    fn non_tool_feature_is_rejected() {
+        let err = validate_turn_grant(
+            TurnCapabilityGrant::Tool {
+                name: "steering".to_string(),
+            },
+            &workspace_write(),
+            Path::new("/"),
+        )
+        .unwrap_err();
+        assert_eq!(err, "feature `steering` does not provide a tool");
+    }
+
+    #[test]
+    fn relative_writable_root_resolves_against_cwd() {
+        let cwd = tempdir().expect("tempdir");
+        std::fs::create_dir(cwd.path().join("out")).expect("mkdir");
+        let grant = validate_turn_grant(
+            TurnCapabilityGrant::WritableRoot { path: "out".into() },
+            &workspace_write(),
+            cwd.path(),
+        )
+        .expect("valid grant");
+        assert_eq!(
+            grant,
+            TurnCapabilityGrant::WritableRoot {
+                path: cwd.path().join("out"),
+            }
+        );
+    }
+
+    #[test]
+    fn apply_widens_sandbox_and_enables_tools() {
+        let root = tempdir().expect("tempdir");
+        let mut policy = workspace_write();
+        let mut features = Features::with_defaults();
+        apply_turn_grants(
+            &[
+                TurnCapabilityGrant::Network,
+                TurnCapabilityGrant::Tool {
+                    name: "web_search_request".to_string(),
+                },
+                TurnCapabilityGrant::WritableRoot {
+                    path: root.path().to_path_buf(),
+                },
+            ],
+            &mut policy,
+            &mut features,
+        );
+
+        assert_eq!(
+            policy,
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path(root.path()).expect("absolute")
+                ],
+                network_access: true,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+            }
+        );
+        assert!(features.enabled(Feature::WebSearchRequest));
+    }
+}
@@ -493,6 +493,14 @@ impl UnifiedExecSessionManager {
             sandbox_permissions,
         )
         .await;
+        let exec_approval_requirement = match &context.turn.client.config().approvals_policy {
+            Some(policy) => policy.apply_to_command(
+                command,
+                context.turn.approval_policy,
+                exec_approval_requirement,
+            ),
+            None => exec_approval_requirement,
+        };
         let req = UnifiedExecToolRequest::new(
             command.to_vec(),
             cwd,
@@ -7,6 +7,7 @@ use codex_protocol::models::ResponseItem;
 pub const USER_INSTRUCTIONS_OPEN_TAG_LEGACY: &str = "<user_instructions>";
 pub const USER_INSTRUCTIONS_PREFIX: &str = "# AGENTS.md instructions for ";
 pub const SKILL_INSTRUCTIONS_PREFIX: &str = "<skill";
+pub const STEERING_INSTRUCTIONS_OPEN_TAG: &str = "<steering_instructions>";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename = "user_instructions", rename_all = "snake_case")]
@@ -75,6 +76,37 @@ impl From<SkillInstructions> for ResponseItem {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename = "steering_instructions", rename_all = "snake_case")]
+pub(crate) struct SteeringInstructions {
+    pub text: String,
+}
+
+impl SteeringInstructions {
+    pub fn is_steering_instructions(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(STEERING_INSTRUCTIONS_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+}
+
+impl From<SteeringInstructions> for ResponseItem {
+    fn from(si: SteeringInstructions) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{STEERING_INSTRUCTIONS_OPEN_TAG}\n{}\n</steering_instructions>",
+                    si.text
+                ),
+            }],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename = "developer_instructions", rename_all = "snake_case")]
 pub(crate) struct DeveloperInstructions {
@@ -176,6 +208,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_steering_instructions() {
+        let response_item: ResponseItem = SteeringInstructions {
+            text: "prefer small commits".to_string(),
+        }
+        .into();
+
+        let ResponseItem::Message { role, content, .. } = response_item else {
+            panic!("expected ResponseItem::Message");
+        };
+
+        assert_eq!(role, "user");
+        assert!(SteeringInstructions::is_steering_instructions(&content));
+        assert!(!UserInstructions::is_user_instructions(&content));
+
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected one InputText content item");
+        };
+
+        assert_eq!(
+            text,
+            "<steering_instructions>\nprefer small commits\n</steering_instructions>",
+        );
+    }
+
     #[test]
     fn test_is_skill_instructions() {
         assert!(SkillInstructions::is_skill_instructions(&[
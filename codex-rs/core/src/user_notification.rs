@@ -1,21 +1,39 @@
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
 use serde::Serialize;
 use tracing::error;
 use tracing::warn;
 
 #[derive(Debug, Default)]
 pub(crate) struct UserNotifier {
-    notify_command: Option<Vec<String>>,
+    /// Replaced when `notify` changes in a reloaded config.
+    notify_command: RwLock<Option<Vec<String>>>,
 }
 
 impl UserNotifier {
     pub(crate) fn notify(&self, notification: &UserNotification) {
-        if let Some(notify_command) = &self.notify_command
+        if let Some(notify_command) = self.notify_command()
             && !notify_command.is_empty()
         {
-            self.invoke_notify(notify_command, notification)
+            self.invoke_notify(&notify_command, notification)
         }
     }
 
+    pub(crate) fn notify_command(&self) -> Option<Vec<String>> {
+        self.notify_command
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn set_notify_command(&self, notify: Option<Vec<String>>) {
+        *self
+            .notify_command
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = notify;
+    }
+
     fn invoke_notify(&self, notify_command: &[String], notification: &UserNotification) {
         let Ok(json) = serde_json::to_string(&notification) else {
             error!("failed to serialise notification payload");
@@ -36,7 +54,7 @@ impl UserNotifier {
 
     pub(crate) fn new(notify: Option<Vec<String>>) -> Self {
         Self {
-            notify_command: notify,
+            notify_command: RwLock::new(notify),
         }
     }
 }
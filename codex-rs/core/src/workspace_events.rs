@@ -0,0 +1,202 @@
+//! Typed events about the workspace, shared between subsystems.
+//!
+//! Code that changes the workspace (apply_patch, shell commands, a steering
+//! reload) publishes a [`WorkspaceEvent`] on the session's
+//! [`WorkspaceEventBus`] instead of calling every interested subsystem
+//! itself. Subsystems implement [`WorkspaceSubscriber`] and are registered
+//! when the session starts, so a new integration needs no changes at the
+//! call sites. Subscribers run in registration order and are awaited before
+//! `publish` returns, which keeps the protocol events they send ordered after
+//! the event that caused them.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::protocol::FileChange;
+use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::events::TerminologyLinter;
+use crate::tools::events::TurnDiffPublisher;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum WorkspaceEvent {
+    /// Codex wrote these files, including the destinations of moves.
+    FilesChanged { paths: Vec<PathBuf> },
+    /// An apply_patch call finished, successfully or not.
+    PatchApplied {
+        changes: HashMap<PathBuf, FileChange>,
+        success: bool,
+    },
+    /// A shell or unified exec command exited.
+    CommandFinished {
+        command: Vec<String>,
+        exit_code: i32,
+    },
+    /// Steering files were discovered again after a config reload.
+    SteeringReloaded,
+}
+
+impl WorkspaceEvent {
+    /// The files a patch touched, or `None` for a failed patch.
+    pub(crate) fn files_changed_by(
+        changes: &HashMap<PathBuf, FileChange>,
+        success: bool,
+    ) -> Option<Self> {
+        if !success || changes.is_empty() {
+            return None;
+        }
+        let mut paths: Vec<PathBuf> = changes
+            .iter()
+            .map(|(path, change)| match change {
+                FileChange::Update {
+                    move_path: Some(dest),
+                    ..
+                } => dest.clone(),
+                _ => path.clone(),
+            })
+            .collect();
+        paths.sort();
+        Some(Self::FilesChanged { paths })
+    }
+}
+
+/// Where an event happened.
+#[derive(Clone, Copy)]
+pub(crate) struct WorkspaceEventCtx<'a> {
+    pub session: &'a Session,
+    /// `None` for events outside a turn, such as a steering reload.
+    pub turn: Option<&'a TurnContext>,
+    pub turn_diff_tracker: Option<&'a SharedTurnDiffTracker>,
+}
+
+impl<'a> WorkspaceEventCtx<'a> {
+    pub(crate) fn outside_turn(session: &'a Session) -> Self {
+        Self {
+            session,
+            turn: None,
+            turn_diff_tracker: None,
+        }
+    }
+}
+
+#[async_trait]
+pub(crate) trait WorkspaceSubscriber: Send + Sync {
+    async fn on_event(&self, ctx: WorkspaceEventCtx<'_>, event: &WorkspaceEvent);
+}
+
+#[derive(Default)]
+pub(crate) struct WorkspaceEventBus {
+    subscribers: RwLock<Vec<Arc<dyn WorkspaceSubscriber>>>,
+}
+
+impl WorkspaceEventBus {
+    /// A bus with the subscribers every session has: turn diff updates
+    /// followed by glossary checks.
+    pub(crate) fn with_default_subscribers() -> Self {
+        let bus = Self::default();
+        bus.subscribe(Arc::new(TurnDiffPublisher));
+        bus.subscribe(Arc::new(TerminologyLinter::default()));
+        bus
+    }
+
+    pub(crate) fn subscribe(&self, subscriber: Arc<dyn WorkspaceSubscriber>) {
+        self.subscribers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(subscriber);
+    }
+
+    pub(crate) async fn publish(&self, ctx: WorkspaceEventCtx<'_>, event: WorkspaceEvent) {
+        let subscribers = self
+            .subscribers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        for subscriber in subscribers {
+            subscriber.on_event(ctx, &event).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        seen: Arc<Mutex<Vec<(&'static str, WorkspaceEvent)>>>,
+    }
+
+    #[async_trait]
+    impl WorkspaceSubscriber for Recorder {
+        async fn on_event(&self, _ctx: WorkspaceEventCtx<'_>, event: &WorkspaceEvent) {
+            self.seen
+                .lock()
+                .expect("lock")
+                .push((self.name, event.clone()));
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_events_to_subscribers_in_registration_order() {
+        let (session, _turn) = make_session_and_context();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let bus = WorkspaceEventBus::default();
+        for name in ["first", "second"] {
+            bus.subscribe(Arc::new(Recorder {
+                name,
+                seen: Arc::clone(&seen),
+            }));
+        }
+
+        bus.publish(
+            WorkspaceEventCtx::outside_turn(&session),
+            WorkspaceEvent::SteeringReloaded,
+        )
+        .await;
+
+        assert_eq!(
+            *seen.lock().expect("lock"),
+            vec![
+                ("first", WorkspaceEvent::SteeringReloaded),
+                ("second", WorkspaceEvent::SteeringReloaded),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_changed_follows_moves_and_skips_failed_patches() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("/repo/a.rs"),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: Some(PathBuf::from("/repo/b.rs")),
+                },
+            ),
+            (
+                PathBuf::from("/repo/new.md"),
+                FileChange::Add {
+                    content: "hi".to_string(),
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            WorkspaceEvent::files_changed_by(&changes, true),
+            Some(WorkspaceEvent::FilesChanged {
+                paths: vec![PathBuf::from("/repo/b.rs"), PathBuf::from("/repo/new.md")],
+            })
+        );
+        assert_eq!(WorkspaceEvent::files_changed_by(&changes, false), None);
+    }
+}
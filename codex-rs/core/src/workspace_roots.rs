@@ -0,0 +1,76 @@
+//! Sessions that span several repositories (`codex exec --repo A --repo B`).
+//!
+//! The workspace is `Config::cwd` plus `Config::workspace_roots`. With no
+//! extra roots nothing changes. With extra roots, AGENTS.md files and
+//! `.codex/steering` are discovered in every root, and file tools and patches
+//! may only touch paths inside the workspace.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::config::Config;
+
+/// Why `path` may not be used, or `None` when it is inside the workspace or
+/// the session declares no extra roots. Paths are compared lexically.
+pub(crate) fn outside_workspace(config: &Config, path: &Path) -> Option<String> {
+    if config.workspace_roots.is_empty() {
+        return None;
+    }
+    let path = normalize(&config.cwd.join(path));
+    let mut roots = std::iter::once(&config.cwd).chain(&config.workspace_roots);
+    if roots.any(|root| path.starts_with(root)) {
+        return None;
+    }
+    let roots = std::iter::once(&config.cwd)
+        .chain(&config.workspace_roots)
+        .map(|root| root.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "{} is outside the workspace; this session may only use paths under {roots}",
+        path.display()
+    ))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn confines_paths_only_when_roots_are_declared() {
+        let mut config = test_config();
+        config.cwd = PathBuf::from("/work/service-a");
+        assert_eq!(outside_workspace(&config, Path::new("/etc/hosts")), None);
+
+        config.workspace_roots = vec![PathBuf::from("/work/service-b")];
+        assert_eq!(outside_workspace(&config, Path::new("src/lib.rs")), None);
+        assert_eq!(
+            outside_workspace(&config, Path::new("/work/service-b/proto/api.proto")),
+            None
+        );
+        assert_eq!(
+            outside_workspace(&config, Path::new("../service-c/Cargo.toml")),
+            Some(
+                "/work/service-c/Cargo.toml is outside the workspace; this session may only use paths under /work/service-a, /work/service-b"
+                    .to_string()
+            )
+        );
+    }
+}
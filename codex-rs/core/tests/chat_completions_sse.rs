@@ -50,6 +50,7 @@ async fn run_stream_with_bytes(sse_body: &[u8]) -> Vec<ResponseEvent> {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Chat,
         query_params: None,
         http_headers: None,
@@ -57,6 +58,8 @@ async fn run_stream_with_bytes(sse_body: &[u8]) -> Vec<ResponseEvent> {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
@@ -76,12 +76,15 @@ pub fn test_tmp_path_buf() -> PathBuf {
 /// temporary directory. Using a per-test directory keeps tests hermetic and
 /// avoids clobbering a developer’s real `~/.codex`.
 pub fn load_default_config_for_test(codex_home: &TempDir) -> Config {
-    Config::load_from_base_config_with_overrides(
+    let mut config = Config::load_from_base_config_with_overrides(
         ConfigToml::default(),
         default_test_overrides(),
         codex_home.path().to_path_buf(),
     )
-    .expect("defaults for test should always succeed")
+    .expect("defaults for test should always succeed");
+    // Title requests would show up as extra requests against the mock server.
+    config.session_titles.enabled = false;
+    config
 }
 
 #[cfg(target_os = "linux")]
@@ -46,6 +46,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -53,6 +54,8 @@ async fn responses_stream_includes_subagent_header_on_review() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
@@ -140,6 +143,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -147,6 +151,8 @@ async fn responses_stream_includes_subagent_header_on_other() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
@@ -230,6 +236,7 @@ async fn responses_respects_model_family_overrides_from_config() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -237,6 +244,8 @@ async fn responses_respects_model_family_overrides_from_config() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
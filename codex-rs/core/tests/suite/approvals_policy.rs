@@ -0,0 +1,178 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use anyhow::Result;
+use codex_core::approvals_policy::ApprovalsPolicy;
+use codex_core::approvals_policy::ApprovalsPolicyRuleToml;
+use codex_core::approvals_policy::ApprovalsPolicyToml;
+use codex_core::approvals_policy::PolicyAction;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use serde_json::json;
+
+fn command_rule(action: PolicyAction, command: &[&str]) -> ApprovalsPolicyRuleToml {
+    ApprovalsPolicyRuleToml {
+        action,
+        command: Some(command.iter().map(ToString::to_string).collect()),
+        write: None,
+        network: None,
+        reason: Some("Security review.".to_string()),
+    }
+}
+
+async fn run_shell_command(
+    rules: Vec<ApprovalsPolicyRuleToml>,
+    command: &str,
+    approval_policy: AskForApproval,
+) -> Result<TestCodex> {
+    let mut builder = test_codex().with_config(move |config| {
+        config.approvals_policy = Some(
+            ApprovalsPolicy::new(config.cwd.clone(), ApprovalsPolicyToml { rules })
+                .expect("valid rules"),
+        );
+    });
+    let server = start_mock_server().await;
+    let test = builder.build(&server).await?;
+
+    let args = json!({
+        "command": command,
+        "timeout_ms": 1_000,
+    });
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_function_call("call-1", "shell_command", &serde_json::to_string(&args)?),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let session_model = test.session_configured.model.clone();
+    test.codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "run shell command".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: test.cwd_path().to_path_buf(),
+            approval_policy,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+    Ok(test)
+}
+
+#[tokio::test]
+async fn denied_commands_are_refused() -> Result<()> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+
+    let test = run_shell_command(
+        vec![command_rule(PolicyAction::Deny, &["echo", "**"])],
+        "echo blocked",
+        AskForApproval::OnRequest,
+    )
+    .await?;
+
+    let EventMsg::ExecCommandEnd(end) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::ExecCommandEnd(_))
+    })
+    .await
+    else {
+        unreachable!()
+    };
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TaskComplete(_))
+    })
+    .await;
+
+    assert!(
+        end.aggregated_output
+            .contains("rule 1 in .codex/approvals.toml denies `echo blocked`: Security review."),
+        "unexpected output: {}",
+        end.aggregated_output
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn ask_rules_prompt_for_commands_that_would_run_unasked() -> Result<()> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+
+    let test = run_shell_command(
+        vec![command_rule(PolicyAction::Ask, &["echo", "**"])],
+        "echo hello",
+        AskForApproval::OnRequest,
+    )
+    .await?;
+
+    let EventMsg::ExecApprovalRequest(request) = wait_for_event(&test.codex, |event| {
+        matches!(
+            event,
+            EventMsg::ExecApprovalRequest(_) | EventMsg::TaskComplete(_)
+        )
+    })
+    .await
+    else {
+        panic!("expected an approval request");
+    };
+    assert_eq!(
+        request.reason.as_deref(),
+        Some(
+            "rule 1 in .codex/approvals.toml requires approval for `echo hello`: Security review."
+        )
+    );
+
+    test.codex
+        .submit(Op::ExecApproval {
+            id: "0".into(),
+            decision: ReviewDecision::Approved,
+        })
+        .await?;
+    let EventMsg::ExecCommandEnd(end) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::ExecCommandEnd(_))
+    })
+    .await
+    else {
+        unreachable!()
+    };
+    assert!(
+        end.aggregated_output.contains("hello"),
+        "unexpected output: {}",
+        end.aggregated_output
+    );
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TaskComplete(_))
+    })
+    .await;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use anyhow::Result;
+use codex_core::features::Feature;
+use codex_core::protocol::SandboxPolicy;
+use core_test_support::responses::mount_function_call_agent_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_request_reaches_loopback_under_workspace_write() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::HttpRequestTool);
+    });
+    let test = builder.build(&server).await?;
+
+    let dev_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .expect(1)
+        .mount(&dev_server)
+        .await;
+    let port = dev_server.address().port();
+
+    let call_id = "http-request-loopback";
+    let arguments = json!({ "url": format!("http://127.0.0.1:{port}/health") }).to_string();
+    let mocks =
+        mount_function_call_agent_response(&server, call_id, &arguments, "http_request").await;
+
+    test.submit_turn_with_policy(
+        "check the dev server",
+        SandboxPolicy::new_workspace_write_policy(),
+    )
+    .await?;
+
+    let req = mocks.completion.single_request();
+    let (content, _) = req
+        .function_call_output_content_and_success(call_id)
+        .expect("function_call_output present");
+    let output: Value = serde_json::from_str(&content.expect("output content"))?;
+    assert_eq!(output["status"], 200);
+    assert_eq!(output["body"], "ok");
+
+    Ok(())
+}
@@ -18,6 +18,7 @@ mod abort_tasks;
 mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
+mod approvals_policy;
 mod auth_refresh;
 mod cli_stream;
 mod client;
@@ -30,6 +31,7 @@ mod exec;
 mod exec_policy;
 mod fork_conversation;
 mod grep_files;
+mod http_request;
 mod items;
 mod json_result;
 mod list_dir;
@@ -38,6 +40,7 @@ mod live_cli;
 mod model_overrides;
 mod model_tools;
 mod otel;
+mod plan_mode;
 mod prompt_caching;
 mod quota_exceeded;
 mod read_file;
@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use codex_core::CodexConversation;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use core_test_support::responses;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use serde_json::Value;
+
+fn tool_names(body: &Value) -> Vec<String> {
+    body["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| {
+            tool.get("name")
+                .or_else(|| tool.get("type"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+async fn set_plan_mode(codex: &CodexConversation, enabled: bool) {
+    codex.submit(Op::SetPlanMode { enabled }).await.unwrap();
+    wait_for_event(codex, |event| matches!(event, EventMsg::BackgroundEvent(_))).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_mode_hides_editing_tools_until_turned_off() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let reply = |id: &str| {
+        sse(vec![
+            ev_response_created(id),
+            ev_assistant_message(&format!("msg-{id}"), "done"),
+            ev_completed(id),
+        ])
+    };
+    let mock = responses::mount_sse_sequence(&server, vec![reply("resp-1"), reply("resp-2")]).await;
+
+    let test = test_codex()
+        .with_model("gpt-5-codex")
+        .build(&server)
+        .await?;
+
+    set_plan_mode(&test.codex, true).await;
+    test.submit_turn("plan the change").await?;
+    set_plan_mode(&test.codex, false).await;
+    test.submit_turn("now make it").await?;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+
+    let planning = &requests[0];
+    assert!(!tool_names(&planning.body_json()).contains(&"apply_patch".to_string()));
+    assert!(
+        planning
+            .message_input_texts("developer")
+            .iter()
+            .any(|text| text.starts_with("Plan mode is on."))
+    );
+
+    let editing = &requests[1];
+    assert!(tool_names(&editing.body_json()).contains(&"apply_patch".to_string()));
+    assert!(
+        editing
+            .message_input_texts("developer")
+            .iter()
+            .any(|text| text.starts_with("Plan mode is off."))
+    );
+
+    Ok(())
+}
@@ -224,3 +224,34 @@ async fn shell_command_times_out_with_timeout_ms() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shell_command_timeout_is_capped_and_retried() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    skip_if_windows!(Ok(()));
+
+    let harness = shell_command_harness_with(|builder| {
+        builder.with_model("gpt-5.1").with_config(|config| {
+            config.tool_timeouts.shell_max_ms = Some(200);
+            config.tool_timeouts.shell_retries = Some(1);
+        })
+    })
+    .await?;
+
+    let attempts = harness.path("attempts.txt");
+    let call_id = "shell-command-capped-timeout";
+    let command = format!("echo attempt >> {}; sleep 5", attempts.display());
+    mount_shell_responses_with_timeout(&harness, call_id, &command, None, 10_000).await;
+    harness
+        .submit("run a long command with a long timeout")
+        .await?;
+
+    let output = harness.function_call_stdout(call_id).await;
+    assert!(
+        output.contains("command timed out after"),
+        "unexpected output: {output}"
+    );
+    assert_eq!(std::fs::read_to_string(&attempts)?, "attempt\nattempt\n");
+
+    Ok(())
+}
@@ -65,6 +65,7 @@ async fn continue_after_stream_error() {
         env_key: Some("PATH".into()),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -72,6 +73,8 @@ async fn continue_after_stream_error() {
         request_max_retries: Some(1),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2_000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
@@ -72,6 +72,7 @@ async fn retries_on_early_close() {
         env_key: Some("PATH".into()),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        api_key_cmd: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -80,6 +81,8 @@ async fn retries_on_early_close() {
         request_max_retries: Some(0),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2000),
+        fallback_base_urls: None,
+        proxy: None,
         requires_openai_auth: false,
     };
 
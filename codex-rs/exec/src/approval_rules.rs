@@ -0,0 +1,271 @@
+//! Headless answers to approval requests, from `--approve` and `--deny`.
+//!
+//! With either flag, `codex exec` asks for approval before running anything
+//! Codex does not know to be safe and answers each request itself. A request
+//! a `--deny` rule matches stops the run, one an `--approve` rule matches
+//! goes ahead, and anything else is declined so the agent can try another
+//! way. Either of the first and last ends the run with
+//! [`APPROVAL_REQUIRED_EXIT_CODE`](crate::exit_codes::APPROVAL_REQUIRED_EXIT_CODE). `network` is not a request: approving it
+//! lets sandboxed commands reach the network for the run.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use codex_core::is_dangerous_command::command_might_be_dangerous;
+use codex_core::protocol::FileChange;
+use codex_protocol::parse_command::ParsedCommand;
+
+/// One entry of `--approve` or `--deny`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalRule {
+    /// `commands:read-only`: commands that only read, list, or search files.
+    ReadOnlyCommands,
+    /// `commands:all`
+    AllCommands,
+    /// `patches:workspace`: file changes inside the working directory.
+    WorkspacePatches,
+    /// `patches:all`
+    AllPatches,
+    /// `network`
+    Network,
+}
+
+impl FromStr for ApprovalRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "commands:read-only" => Ok(Self::ReadOnlyCommands),
+            "commands" | "commands:all" => Ok(Self::AllCommands),
+            "patches:workspace" => Ok(Self::WorkspacePatches),
+            "patches" | "patches:all" => Ok(Self::AllPatches),
+            "network" => Ok(Self::Network),
+            other => Err(format!(
+                "unknown rule `{other}`; expected commands:read-only, commands:all, patches:workspace, patches:all, or network"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApprovalRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ReadOnlyCommands => "commands:read-only",
+            Self::AllCommands => "commands:all",
+            Self::WorkspacePatches => "patches:workspace",
+            Self::AllPatches => "patches:all",
+            Self::Network => "network",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Approve,
+    Decline,
+    Deny(ApprovalRule),
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ApprovalRules {
+    approve: Vec<ApprovalRule>,
+    deny: Vec<ApprovalRule>,
+}
+
+impl ApprovalRules {
+    pub(crate) fn new(approve: Vec<ApprovalRule>, deny: Vec<ApprovalRule>) -> Self {
+        Self { approve, deny }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.approve.is_empty() && self.deny.is_empty()
+    }
+
+    pub(crate) fn grants_network(&self) -> bool {
+        self.approve.contains(&ApprovalRule::Network) && !self.deny.contains(&ApprovalRule::Network)
+    }
+
+    pub(crate) fn command(&self, command: &[String], parsed: &[ParsedCommand]) -> Verdict {
+        let read_only = !parsed.is_empty()
+            && parsed
+                .iter()
+                .all(|part| !matches!(part, ParsedCommand::Unknown { .. }))
+            && !command_might_be_dangerous(command)
+            && !writes_files(command);
+        self.verdict(|rule| match rule {
+            ApprovalRule::AllCommands => true,
+            ApprovalRule::ReadOnlyCommands => read_only,
+            _ => false,
+        })
+    }
+
+    pub(crate) fn patch(&self, changes: &HashMap<PathBuf, FileChange>, cwd: &Path) -> Verdict {
+        let in_workspace = changes.iter().all(|(path, change)| {
+            is_inside(path, cwd)
+                && match change {
+                    FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } => is_inside(dest, cwd),
+                    _ => true,
+                }
+        });
+        self.verdict(|rule| match rule {
+            ApprovalRule::AllPatches => true,
+            ApprovalRule::WorkspacePatches => in_workspace,
+            _ => false,
+        })
+    }
+
+    /// A matching `--deny` rule wins over any `--approve` rule.
+    fn verdict(&self, matches: impl Fn(ApprovalRule) -> bool) -> Verdict {
+        if let Some(rule) = self.deny.iter().copied().find(|rule| matches(*rule)) {
+            return Verdict::Deny(rule);
+        }
+        if self.approve.iter().copied().any(matches) {
+            Verdict::Approve
+        } else {
+            Verdict::Decline
+        }
+    }
+}
+
+/// Options of read-like tools (`find`, `sed`) that write or run other
+/// programs, which the command summary does not tell apart.
+const WRITING_OPTIONS: &[&str] = &[
+    "-delete",
+    "-exec",
+    "-execdir",
+    "-ok",
+    "-okdir",
+    "-fls",
+    "-fprint",
+    "-fprint0",
+    "-fprintf",
+    "-i",
+    "--in-place",
+];
+
+fn writes_files(command: &[String]) -> bool {
+    command
+        .iter()
+        .flat_map(|arg| arg.split_whitespace())
+        .any(|word| WRITING_OPTIONS.contains(&word) || word.contains('>'))
+}
+
+fn is_inside(path: &Path, cwd: &Path) -> bool {
+    let path = cwd.join(path);
+    !path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+        && path.starts_with(cwd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rules(approve: &str, deny: &str) -> ApprovalRules {
+        let parse = |spec: &str| {
+            spec.split(',')
+                .filter(|rule| !rule.is_empty())
+                .map(|rule| rule.parse().expect("rule"))
+                .collect()
+        };
+        ApprovalRules::new(parse(approve), parse(deny))
+    }
+
+    fn read(cmd: &str) -> ParsedCommand {
+        ParsedCommand::Read {
+            cmd: cmd.to_string(),
+            name: "lib.rs".to_string(),
+            path: PathBuf::from("lib.rs"),
+        }
+    }
+
+    fn unknown(cmd: &str) -> ParsedCommand {
+        ParsedCommand::Unknown {
+            cmd: cmd.to_string(),
+        }
+    }
+
+    fn command(cmd: &str) -> Vec<String> {
+        cmd.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_rules_and_rejects_unknown_ones() {
+        assert_eq!(
+            "commands:read-only".parse(),
+            Ok(ApprovalRule::ReadOnlyCommands)
+        );
+        assert_eq!("patches".parse(), Ok(ApprovalRule::AllPatches));
+        assert!("commands:some".parse::<ApprovalRule>().is_err());
+    }
+
+    #[test]
+    fn read_only_rule_approves_only_reads() {
+        let rules = rules("commands:read-only", "");
+        assert_eq!(
+            rules.command(&command("sed -n 1,5p lib.rs"), &[read("sed")]),
+            Verdict::Approve
+        );
+        assert_eq!(
+            rules.command(&command("make install"), &[unknown("make install")]),
+            Verdict::Decline
+        );
+        assert_eq!(
+            rules.command(&command("rm -rf target"), &[]),
+            Verdict::Decline
+        );
+        let search = ParsedCommand::Search {
+            cmd: "find . -delete".to_string(),
+            query: None,
+            path: None,
+        };
+        assert_eq!(
+            rules.command(&command("find . -delete"), &[search]),
+            Verdict::Decline
+        );
+    }
+
+    #[test]
+    fn deny_wins_over_approve() {
+        let rules = rules("commands:all", "commands:read-only");
+        assert_eq!(
+            rules.command(&command("cat lib.rs"), &[read("cat")]),
+            Verdict::Deny(ApprovalRule::ReadOnlyCommands)
+        );
+        assert_eq!(
+            rules.command(&command("cargo build"), &[unknown("cargo build")]),
+            Verdict::Approve
+        );
+    }
+
+    #[test]
+    fn workspace_patches_stay_inside_the_working_directory() {
+        let rules = rules("patches:workspace", "");
+        let cwd = Path::new("/repo");
+        let change = || FileChange::Add {
+            content: String::new(),
+        };
+        let inside = HashMap::from([(PathBuf::from("/repo/src/lib.rs"), change())]);
+        let outside = HashMap::from([(PathBuf::from("/etc/hosts"), change())]);
+        let escaping = HashMap::from([(PathBuf::from("../other/lib.rs"), change())]);
+        assert_eq!(rules.patch(&inside, cwd), Verdict::Approve);
+        assert_eq!(rules.patch(&outside, cwd), Verdict::Decline);
+        assert_eq!(rules.patch(&escaping, cwd), Verdict::Decline);
+    }
+
+    #[test]
+    fn network_is_granted_only_when_approved_and_not_denied() {
+        assert!(rules("network", "").grants_network());
+        assert!(!rules("network", "network").grants_network());
+        assert!(!rules("commands:all", "").grants_network());
+    }
+}
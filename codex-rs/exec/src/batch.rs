@@ -0,0 +1,592 @@
+//! `codex exec --batch FILE`: run every task in a YAML file.
+//!
+//! Each task runs as its own `codex exec --json` process, so tasks share
+//! nothing but the flags given to the batch. Up to `--parallel` tasks run at
+//! once. For every task the output directory gets `<name>.jsonl` (the event
+//! stream), `<name>.log` (stderr), and `<name>.json` (the result); a
+//! `summary.json` collects all results once the batch is done. With
+//! `--output-format junit`, stdout gets a JUnit report with a test case per
+//! task.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::cli::Cli;
+use crate::cli::OutputFormat;
+use crate::exec_events::ItemStartedEvent;
+use crate::exec_events::ItemUpdatedEvent;
+use crate::exec_events::PatchApplyStatus;
+use crate::exec_events::ThreadEvent;
+use crate::exec_events::ThreadItemDetails;
+use crate::exec_events::Usage;
+use crate::exit_codes::APPROVAL_REQUIRED_EXIT_CODE;
+use crate::exit_codes::MAX_COST_EXIT_CODE;
+use crate::exit_codes::MAX_DURATION_EXIT_CODE;
+use crate::exit_codes::MAX_TURNS_EXIT_CODE;
+use crate::exit_codes::MODEL_REFUSAL_EXIT_CODE;
+use crate::exit_codes::PROVIDER_ERROR_EXIT_CODE;
+use crate::exit_codes::SANDBOX_DENIED_EXIT_CODE;
+use crate::junit::Failure;
+use crate::junit::TestCase;
+use crate::junit::render_junit;
+
+/// The tasks file: a list of prompts with optional per-task overrides.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchFile {
+    tasks: Vec<BatchTask>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchTask {
+    /// Names the result files. Defaults to `task-<n>`.
+    name: Option<String>,
+    prompt: String,
+    /// Relative paths resolve against the tasks file.
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    /// Passed to the task as `--max-turns`.
+    max_turns: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskStatus {
+    Succeeded,
+    Failed,
+    /// Needed an approval no rule gave: stopped by a `--deny` rule, or a
+    /// request no `--approve` rule allowed was declined.
+    Denied,
+    MaxTurnsExceeded,
+    MaxDurationExceeded,
+    MaxCostExceeded,
+    ModelRefusal,
+    SandboxDenied,
+    ProviderError,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskResult {
+    name: String,
+    prompt: String,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    status: TaskStatus,
+    exit_code: Option<i32>,
+    thread_id: Option<String>,
+    final_message: Option<String>,
+    usage: Option<Usage>,
+    steps: usize,
+    files_changed: Vec<String>,
+    error: Option<String>,
+    duration_seconds: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchSummary<'a> {
+    tasks: usize,
+    succeeded: usize,
+    failed: usize,
+    duration_seconds: f64,
+    results: &'a [TaskResult],
+}
+
+pub(crate) async fn run_batch(cli: Cli, file: PathBuf) -> anyhow::Result<()> {
+    if cli.output_format == Some(OutputFormat::Sarif) {
+        anyhow::bail!("--output-format sarif needs review findings; use --output-format junit");
+    }
+    let text = std::fs::read_to_string(&file)
+        .with_context(|| format!("failed to read {}", file.display()))?;
+    let base_dir = file
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let tasks = parse_tasks(&text, &base_dir)
+        .with_context(|| format!("invalid batch file {}", file.display()))?;
+    let out_dir = cli.batch_output.clone().unwrap_or_else(|| {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        base_dir.join(format!("{stem}-results"))
+    });
+    std::fs::create_dir_all(&out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+
+    let exe = std::env::current_exe().context("failed to locate the codex executable")?;
+    let shared_args = Arc::new(shared_args(&exe, &cli));
+    let exe = Arc::new(exe);
+    let out_dir = Arc::new(out_dir);
+    let permits = Arc::new(Semaphore::new(cli.parallel.max(1)));
+    let total = tasks.len();
+    let started = Instant::now();
+
+    let mut running = JoinSet::new();
+    for (index, (name, mut task)) in tasks.into_iter().enumerate() {
+        task.max_turns = task.max_turns.or(cli.max_turns);
+        let exe = Arc::clone(&exe);
+        let shared_args = Arc::clone(&shared_args);
+        let out_dir = Arc::clone(&out_dir);
+        let permits = Arc::clone(&permits);
+        running.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let result = run_task(&exe, &shared_args, &out_dir, name, task).await;
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<(usize, TaskResult)> = Vec::with_capacity(total);
+    while let Some(joined) = running.join_next().await {
+        let (index, result) = joined?;
+        eprintln!(
+            "[{}/{total}] {}: {} ({:.0}s)",
+            results.len() + 1,
+            result.name,
+            status_label(result.status),
+            result.duration_seconds
+        );
+        results.push((index, result));
+    }
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<TaskResult> = results.into_iter().map(|(_, result)| result).collect();
+
+    let succeeded = results
+        .iter()
+        .filter(|result| result.status == TaskStatus::Succeeded)
+        .count();
+    let summary = BatchSummary {
+        tasks: total,
+        succeeded,
+        failed: total - succeeded,
+        duration_seconds: started.elapsed().as_secs_f64(),
+        results: &results,
+    };
+    let summary_path = out_dir.join("summary.json");
+    std::fs::write(&summary_path, serde_json::to_string_pretty(&summary)?)
+        .with_context(|| format!("failed to write {}", summary_path.display()))?;
+    eprintln!(
+        "{succeeded} of {total} tasks succeeded. Results are in {}",
+        out_dir.display()
+    );
+    if cli.output_format == Some(OutputFormat::Junit) {
+        let cases: Vec<TestCase> = results.iter().map(test_case).collect();
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", render_junit("codex-batch", &cases));
+        }
+    }
+
+    if succeeded < total {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Parses the tasks and gives each a unique, file-safe name.
+fn parse_tasks(text: &str, base_dir: &Path) -> anyhow::Result<Vec<(String, BatchTask)>> {
+    let BatchFile { tasks } = serde_yaml::from_str(text)?;
+    if tasks.is_empty() {
+        anyhow::bail!("`tasks` is empty");
+    }
+    let mut seen = HashSet::new();
+    let mut named = Vec::with_capacity(tasks.len());
+    for (index, mut task) in tasks.into_iter().enumerate() {
+        if task.prompt.trim().is_empty() {
+            anyhow::bail!("task {} has an empty prompt", index + 1);
+        }
+        let base = task
+            .name
+            .as_deref()
+            .map(file_safe_name)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("task-{}", index + 1));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !seen.insert(name.clone()) {
+            name = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+        task.cwd = task.cwd.map(|cwd| base_dir.join(cwd));
+        named.push((name, task));
+    }
+    Ok(named)
+}
+
+fn file_safe_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches(|c| matches!(c, '-' | '.'))
+        .to_string()
+}
+
+/// Arguments every task's `codex exec` gets: the batch's own flags, minus
+/// the ones that pick the prompt or the output.
+fn shared_args(exe: &Path, cli: &Cli) -> Vec<OsString> {
+    let mut args = child_run_args(exe, cli);
+    args.extend(["--json".into(), "--color".into(), "never".into()]);
+    args
+}
+
+/// The `codex exec` invocation for a child run, with the flags that shape
+/// the run (config, model, sandbox, approval rules, and the per-run limits
+/// other than `--max-turns`) carried over from `cli`.
+pub(crate) fn child_run_args(exe: &Path, cli: &Cli) -> Vec<OsString> {
+    let mut args: Vec<OsString> = Vec::new();
+    // The multitool runs exec as a subcommand; `codex-exec` is exec itself.
+    let standalone = exe
+        .file_stem()
+        .is_some_and(|stem| stem.to_string_lossy().starts_with("codex-exec"));
+    if !standalone {
+        args.push("exec".into());
+    }
+    for raw in &cli.config_overrides.raw_overrides {
+        args.extend(["-c".into(), raw.into()]);
+    }
+    if let Some(model) = &cli.model {
+        args.extend(["--model".into(), model.into()]);
+    }
+    if cli.oss {
+        args.push("--oss".into());
+    }
+    if let Some(provider) = &cli.oss_provider {
+        args.extend(["--local-provider".into(), provider.into()]);
+    }
+    if let Some(profile) = &cli.config_profile {
+        args.extend(["--profile".into(), profile.into()]);
+    }
+    if let Some(sandbox) = cli.sandbox_mode.and_then(|mode| mode.to_possible_value()) {
+        args.extend(["--sandbox".into(), sandbox.get_name().into()]);
+    }
+    if cli.full_auto {
+        args.push("--full-auto".into());
+    }
+    if cli.dangerously_bypass_approvals_and_sandbox {
+        args.push("--dangerously-bypass-approvals-and-sandbox".into());
+    }
+    for rule in &cli.approve {
+        args.extend(["--approve".into(), rule.to_string().into()]);
+    }
+    for rule in &cli.deny {
+        args.extend(["--deny".into(), rule.to_string().into()]);
+    }
+    if cli.skip_git_repo_check {
+        args.push("--skip-git-repo-check".into());
+    }
+    for dir in &cli.add_dir {
+        args.extend(["--add-dir".into(), dir.into()]);
+    }
+    if let Some(duration) = cli.max_duration {
+        args.extend([
+            "--max-duration".into(),
+            format!("{}s", duration.as_secs()).into(),
+        ]);
+    }
+    if let Some(usd) = cli.max_cost_usd {
+        args.extend(["--max-cost-usd".into(), usd.to_string().into()]);
+    }
+    if let Some(timeout) = cli.tool_timeout {
+        args.extend([
+            "--tool-timeout".into(),
+            format!("{}s", timeout.as_secs()).into(),
+        ]);
+    }
+    if let Some(retries) = cli.tool_retries {
+        args.extend(["--tool-retries".into(), retries.to_string().into()]);
+    }
+    if let Some(schema) = &cli.output_schema {
+        args.extend(["--output-schema".into(), schema.into()]);
+    }
+    for server in &cli.mcp_servers {
+        args.extend(["--mcp".into(), server.into()]);
+    }
+    args
+}
+
+async fn run_task(
+    exe: &Path,
+    shared_args: &[OsString],
+    out_dir: &Path,
+    name: String,
+    task: BatchTask,
+) -> TaskResult {
+    let started = Instant::now();
+    let mut result = TaskResult {
+        name,
+        prompt: task.prompt.clone(),
+        cwd: task.cwd.clone(),
+        model: task.model.clone(),
+        status: TaskStatus::Failed,
+        exit_code: None,
+        thread_id: None,
+        final_message: None,
+        usage: None,
+        steps: 0,
+        files_changed: Vec::new(),
+        error: None,
+        duration_seconds: 0.0,
+    };
+    if let Err(err) = drive_task(exe, shared_args, out_dir, &task, &mut result).await {
+        result.status = TaskStatus::Failed;
+        result.error = Some(format!("{err:#}"));
+    }
+    result.duration_seconds = started.elapsed().as_secs_f64();
+
+    let path = out_dir.join(format!("{}.json", result.name));
+    if let Err(err) = serde_json::to_string_pretty(&result)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(&path, json))
+    {
+        eprintln!("Failed to write {}: {err}", path.display());
+    }
+    result
+}
+
+async fn drive_task(
+    exe: &Path,
+    shared_args: &[OsString],
+    out_dir: &Path,
+    task: &BatchTask,
+    result: &mut TaskResult,
+) -> anyhow::Result<()> {
+    let log = std::fs::File::create(out_dir.join(format!("{}.log", result.name)))?;
+    let mut events =
+        tokio::fs::File::create(out_dir.join(format!("{}.jsonl", result.name))).await?;
+
+    let mut command = tokio::process::Command::new(exe);
+    command.args(shared_args);
+    if let Some(cwd) = &task.cwd {
+        command.arg("--cd").arg(cwd);
+    }
+    if let Some(model) = &task.model {
+        command.arg("--model").arg(model);
+    }
+    if let Some(max_turns) = task.max_turns {
+        command.arg("--max-turns").arg(max_turns.to_string());
+    }
+    command
+        .arg("--")
+        .arg(&task.prompt)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::from(log))
+        .kill_on_drop(true);
+    let mut child = command.spawn().context("failed to start codex exec")?;
+    let stdout = child.stdout.take().context("codex exec has no stdout")?;
+
+    let mut steps = HashSet::new();
+    let mut lines = BufReader::new(stdout).lines();
+    while let Some(line) = lines.next_line().await? {
+        events.write_all(line.as_bytes()).await?;
+        events.write_all(b"\n").await?;
+        if let Ok(event) = serde_json::from_str::<ThreadEvent>(&line) {
+            record_event(event, result, &mut steps);
+        }
+    }
+
+    let status = child.wait().await?;
+    result.steps = steps.len();
+    result.exit_code = status.code();
+    result.status = match status.code() {
+        Some(0) => TaskStatus::Succeeded,
+        Some(APPROVAL_REQUIRED_EXIT_CODE) => TaskStatus::Denied,
+        Some(MAX_TURNS_EXIT_CODE) => TaskStatus::MaxTurnsExceeded,
+        Some(MAX_DURATION_EXIT_CODE) => TaskStatus::MaxDurationExceeded,
+        Some(MAX_COST_EXIT_CODE) => TaskStatus::MaxCostExceeded,
+        Some(MODEL_REFUSAL_EXIT_CODE) => TaskStatus::ModelRefusal,
+        Some(SANDBOX_DENIED_EXIT_CODE) => TaskStatus::SandboxDenied,
+        Some(PROVIDER_ERROR_EXIT_CODE) => TaskStatus::ProviderError,
+        _ => TaskStatus::Failed,
+    };
+    Ok(())
+}
+
+fn record_event(event: ThreadEvent, result: &mut TaskResult, steps: &mut HashSet<String>) {
+    match event {
+        ThreadEvent::ThreadStarted(started) => result.thread_id = Some(started.thread_id),
+        ThreadEvent::TurnCompleted(completed) => result.usage = Some(completed.usage),
+        ThreadEvent::TurnFailed(failed) => result.error = Some(failed.error.message),
+        ThreadEvent::Error(error) => result.error = Some(error.message),
+        ThreadEvent::ItemStarted(ItemStartedEvent { item })
+        | ThreadEvent::ItemUpdated(ItemUpdatedEvent { item }) => {
+            if is_step(&item.details) {
+                steps.insert(item.id);
+            }
+        }
+        ThreadEvent::ItemCompleted(item) => {
+            let id = item.item.id;
+            match item.item.details {
+                ThreadItemDetails::AgentMessage(message) => {
+                    result.final_message = Some(message.text);
+                }
+                ThreadItemDetails::FileChange(change) => {
+                    if change.status == PatchApplyStatus::Completed {
+                        result
+                            .files_changed
+                            .extend(change.changes.into_iter().map(|change| change.path));
+                    }
+                    steps.insert(id);
+                }
+                details if is_step(&details) => {
+                    steps.insert(id);
+                }
+                _ => {}
+            }
+        }
+        ThreadEvent::TurnStarted(_) => {}
+    }
+}
+
+fn is_step(details: &ThreadItemDetails) -> bool {
+    matches!(
+        details,
+        ThreadItemDetails::CommandExecution(_)
+            | ThreadItemDetails::FileChange(_)
+            | ThreadItemDetails::McpToolCall(_)
+            | ThreadItemDetails::WebSearch(_)
+    )
+}
+
+fn test_case(result: &TaskResult) -> TestCase {
+    let failure = (result.status != TaskStatus::Succeeded).then(|| Failure {
+        kind: serde_json::to_value(result.status)
+            .ok()
+            .and_then(|kind| kind.as_str().map(str::to_string))
+            .unwrap_or_default(),
+        message: result
+            .error
+            .clone()
+            .unwrap_or_else(|| status_label(result.status).to_string()),
+        body: String::new(),
+    });
+    TestCase {
+        name: result.name.clone(),
+        classname: "codex.batch".to_string(),
+        time_seconds: result.duration_seconds,
+        failure,
+        system_out: result.final_message.clone(),
+    }
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Succeeded => "succeeded",
+        TaskStatus::Failed => "failed",
+        TaskStatus::Denied => "needed approval",
+        TaskStatus::MaxTurnsExceeded => "stopped by --max-turns",
+        TaskStatus::MaxDurationExceeded => "stopped by --max-duration",
+        TaskStatus::MaxCostExceeded => "stopped by --max-cost-usd",
+        TaskStatus::ModelRefusal => "refused by the model",
+        TaskStatus::SandboxDenied => "blocked by the sandbox",
+        TaskStatus::ProviderError => "model provider error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::AgentMessageItem;
+    use crate::exec_events::CommandExecutionItem;
+    use crate::exec_events::CommandExecutionStatus;
+    use crate::exec_events::ItemCompletedEvent;
+    use crate::exec_events::ThreadItem;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_tasks_with_unique_names_and_resolved_cwds() {
+        let yaml = r#"
+tasks:
+  - name: Bump deps
+    prompt: Update dependencies
+    cwd: service-a
+    max_turns: 5
+  - name: Bump deps
+    prompt: Update dependencies again
+  - prompt: Fix lint
+    model: gpt-5.1-codex
+"#;
+        let tasks = parse_tasks(yaml, Path::new("/jobs")).expect("tasks");
+        let names: Vec<&str> = tasks.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["Bump-deps", "Bump-deps-2", "task-3"]);
+        assert_eq!(tasks[0].1.cwd, Some(PathBuf::from("/jobs/service-a")));
+        assert_eq!(tasks[0].1.max_turns, Some(5));
+        assert_eq!(tasks[2].1.model.as_deref(), Some("gpt-5.1-codex"));
+
+        assert!(parse_tasks("tasks: []", Path::new("/jobs")).is_err());
+        assert!(parse_tasks("tasks:\n  - prompt: x\n    timeout: 3\n", Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn records_results_and_counts_steps_once_per_item() {
+        let mut result = TaskResult {
+            name: "t".to_string(),
+            prompt: "p".to_string(),
+            cwd: None,
+            model: None,
+            status: TaskStatus::Failed,
+            exit_code: None,
+            thread_id: None,
+            final_message: None,
+            usage: None,
+            steps: 0,
+            files_changed: Vec::new(),
+            error: None,
+            duration_seconds: 0.0,
+        };
+        let mut steps = HashSet::new();
+        let command = ThreadItem {
+            id: "item_0".to_string(),
+            details: ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                command: "cargo test".to_string(),
+                aggregated_output: String::new(),
+                exit_code: Some(0),
+                status: CommandExecutionStatus::Completed,
+            }),
+        };
+        record_event(
+            ThreadEvent::ItemStarted(ItemStartedEvent {
+                item: command.clone(),
+            }),
+            &mut result,
+            &mut steps,
+        );
+        record_event(
+            ThreadEvent::ItemCompleted(ItemCompletedEvent { item: command }),
+            &mut result,
+            &mut steps,
+        );
+        record_event(
+            ThreadEvent::ItemCompleted(ItemCompletedEvent {
+                item: ThreadItem {
+                    id: "item_1".to_string(),
+                    details: ThreadItemDetails::AgentMessage(AgentMessageItem {
+                        text: "done".to_string(),
+                    }),
+                },
+            }),
+            &mut result,
+            &mut steps,
+        );
+        assert_eq!(steps.len(), 1);
+        assert_eq!(result.final_message.as_deref(), Some("done"));
+    }
+}
@@ -0,0 +1,173 @@
+//! `codex exec --checkpoint-dir DIR` and `codex exec --resume DIR`.
+//!
+//! A checkpoint directory holds a copy of the session's rollout
+//! (`rollout.jsonl`) and a small `checkpoint.json` saying which session and
+//! prompt it belongs to and whether the run finished. The copy is refreshed
+//! after every model response, so a CI job that is killed part-way can pick
+//! the session up again with `--resume` instead of starting over. A resumed
+//! session appends to the checkpoint's own rollout.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub(crate) const CHECKPOINT_FILE: &str = "checkpoint.json";
+pub(crate) const ROLLOUT_FILE: &str = "rollout.jsonl";
+
+/// Where `--resume <id>` looks for a checkpoint named by id, relative to the
+/// working directory.
+const RUNS_DIR: &str = ".codex/runs";
+
+/// Sent when a run is resumed without a new prompt.
+pub(crate) const CONTINUE_PROMPT: &str = "The previous run of this task was interrupted before it finished. Check what was already done, then continue the task from where it stopped.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CheckpointStatus {
+    /// In progress, or killed before it could say otherwise.
+    Running,
+    Completed,
+    /// Ended early: an error, a `--deny` rule, a run limit, or Ctrl-C.
+    Incomplete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CheckpointState {
+    pub session_id: String,
+    /// The run's original prompt.
+    pub prompt: String,
+    pub status: CheckpointStatus,
+}
+
+pub(crate) struct Checkpoint {
+    dir: PathBuf,
+    /// The live rollout the session writes to.
+    rollout: PathBuf,
+    state: CheckpointState,
+}
+
+impl Checkpoint {
+    /// Starts checkpointing to `dir` and writes the first checkpoint.
+    pub(crate) fn start(
+        dir: PathBuf,
+        rollout: PathBuf,
+        state: CheckpointState,
+    ) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        let checkpoint = Self {
+            dir,
+            rollout,
+            state,
+        };
+        checkpoint.save()?;
+        Ok(checkpoint)
+    }
+
+    /// Refreshes the rollout copy and the state file.
+    pub(crate) fn save(&self) -> anyhow::Result<()> {
+        let target = self.dir.join(ROLLOUT_FILE);
+        if !same_file(&self.rollout, &target) {
+            let partial = self.dir.join(format!("{ROLLOUT_FILE}.partial"));
+            match std::fs::copy(&self.rollout, &partial) {
+                Ok(_) => std::fs::rename(&partial, &target)?,
+                // The session has not written its rollout yet.
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let partial = self.dir.join(format!("{CHECKPOINT_FILE}.partial"));
+        std::fs::write(&partial, serde_json::to_string_pretty(&self.state)?)?;
+        std::fs::rename(&partial, self.dir.join(CHECKPOINT_FILE))?;
+        Ok(())
+    }
+
+    pub(crate) fn finish(&mut self, status: CheckpointStatus) -> anyhow::Result<()> {
+        self.state.status = status;
+        self.save()
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+/// `arg` is a checkpoint directory, or the name of one under `.codex/runs/`.
+pub(crate) fn resolve_checkpoint_dir(arg: &str, cwd: &Path) -> anyhow::Result<PathBuf> {
+    let candidates = [cwd.join(arg), cwd.join(RUNS_DIR).join(arg)];
+    candidates
+        .into_iter()
+        .find(|dir| dir.join(CHECKPOINT_FILE).is_file())
+        .with_context(|| {
+            format!(
+                "no checkpoint `{arg}`: expected {CHECKPOINT_FILE} in {arg}/ or {RUNS_DIR}/{arg}/"
+            )
+        })
+}
+
+pub(crate) fn load_checkpoint(dir: &Path) -> anyhow::Result<CheckpointState> {
+    let path = dir.join(CHECKPOINT_FILE);
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("invalid checkpoint {}", path.display()))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn state() -> CheckpointState {
+        CheckpointState {
+            session_id: "0199a213-81c0-7800-8aa1-bbab2a035a53".to_string(),
+            prompt: "fix the flaky test".to_string(),
+            status: CheckpointStatus::Running,
+        }
+    }
+
+    #[test]
+    fn saves_the_rollout_and_state_after_each_update() -> anyhow::Result<()> {
+        let tmp = TempDir::new()?;
+        let rollout = tmp.path().join("session.jsonl");
+        std::fs::write(&rollout, "{\"first\":1}\n")?;
+        let dir = tmp.path().join("runs/nightly");
+
+        let mut checkpoint = Checkpoint::start(dir.clone(), rollout.clone(), state())?;
+        assert_eq!(load_checkpoint(&dir)?, state());
+
+        std::fs::write(&rollout, "{\"first\":1}\n{\"second\":2}\n")?;
+        checkpoint.finish(CheckpointStatus::Completed)?;
+        assert_eq!(
+            std::fs::read_to_string(dir.join(ROLLOUT_FILE))?,
+            "{\"first\":1}\n{\"second\":2}\n"
+        );
+        assert_eq!(load_checkpoint(&dir)?.status, CheckpointStatus::Completed);
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_paths_and_names_under_codex_runs() -> anyhow::Result<()> {
+        let tmp = TempDir::new()?;
+        let named = tmp.path().join(RUNS_DIR).join("build-42");
+        Checkpoint::start(named.clone(), tmp.path().join("missing.jsonl"), state())?;
+
+        assert_eq!(resolve_checkpoint_dir("build-42", tmp.path())?, named);
+        assert_eq!(
+            resolve_checkpoint_dir(".codex/runs/build-42", tmp.path())?,
+            tmp.path().join(".codex/runs/build-42")
+        );
+        assert!(resolve_checkpoint_dir("build-43", tmp.path()).is_err());
+        Ok(())
+    }
+}
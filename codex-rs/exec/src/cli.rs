@@ -2,6 +2,11 @@ use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
 use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::approval_rules::ApprovalRule;
+use crate::prompt_template::parse_template_arg;
+use crate::review_output::ReviewFormat;
 
 #[derive(Parser, Debug)]
 #[command(version)]
@@ -14,6 +19,17 @@ pub struct Cli {
     #[arg(long = "image", short = 'i', value_name = "FILE", value_delimiter = ',', num_args = 1..)]
     pub images: Vec<PathBuf>,
 
+    /// Save the current clipboard (text or image) as a session artifact and
+    /// attach it to the initial prompt.
+    #[arg(long = "from-clipboard", default_value_t = false)]
+    pub from_clipboard: bool,
+
+    /// Attach a GitHub or GitLab issue (title, body, and comments) to the
+    /// prompt, fetched with `GITHUB_TOKEN` or `GITLAB_TOKEN` when set. Without
+    /// PROMPT, the agent is asked to resolve the issue.
+    #[arg(long = "from-issue", value_name = "URL", conflicts_with_all = ["batch", "watch"])]
+    pub from_issue: Option<String>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -33,7 +49,8 @@ pub struct Cli {
     pub sandbox_mode: Option<codex_common::SandboxModeCliArg>,
 
     /// Configuration profile from config.toml to specify default options.
-    #[arg(long = "profile", short = 'p')]
+    /// `--preset` is an alias. Explicit flags still win over the profile.
+    #[arg(long = "profile", short = 'p', visible_alias = "preset")]
     pub config_profile: Option<String>,
 
     /// Convenience alias for low-friction sandboxed automatic execution (-a on-request, --sandbox workspace-write).
@@ -50,6 +67,52 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Answer approval requests without a prompt. RULES is a comma-separated
+    /// list of `commands:read-only`, `commands:all`, `patches:workspace`,
+    /// `patches:all`, and `network`. Requests no rule approves are declined.
+    #[arg(
+        long = "approve",
+        value_name = "RULES",
+        value_delimiter = ',',
+        conflicts_with = "dangerously_bypass_approvals_and_sandbox"
+    )]
+    pub approve: Vec<ApprovalRule>,
+
+    /// Stop the run with exit code 3 when it needs an action these rules
+    /// match. Takes the same rules as `--approve` and wins over it.
+    #[arg(
+        long = "deny",
+        value_name = "RULES",
+        value_delimiter = ',',
+        conflicts_with = "dangerously_bypass_approvals_and_sandbox"
+    )]
+    pub deny: Vec<ApprovalRule>,
+
+    /// End the run after this many model requests (exit code 4).
+    #[arg(long = "max-turns", value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_turns: Option<u32>,
+
+    /// End the run after this much wall-clock time, such as `90s`, `30m`, or
+    /// `1h30m` (exit code 5).
+    #[arg(long = "max-duration", value_name = "DURATION", value_parser = parse_duration)]
+    pub max_duration: Option<Duration>,
+
+    /// End the run once its estimated cost reaches this many US dollars (exit
+    /// code 6). Needs a `[model_prices]` entry for the model.
+    #[arg(long = "max-cost-usd", value_name = "USD", value_parser = parse_usd)]
+    pub max_cost_usd: Option<f64>,
+
+    /// Kill a shell command the agent runs after this long, such as `120s` or
+    /// `10m`, even when it asks for more time, and report the timeout to the
+    /// agent so the run goes on. Also the timeout of commands that set none.
+    #[arg(long = "tool-timeout", value_name = "DURATION", value_parser = parse_duration)]
+    pub tool_timeout: Option<Duration>,
+
+    /// Run a shell command that timed out again up to this many times before
+    /// reporting the timeout to the agent.
+    #[arg(long = "tool-retries", value_name = "N")]
+    pub tool_retries: Option<u32>,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -62,10 +125,28 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Work across several repositories. Repeat for each one: the first is the
+    /// working directory unless `--cd` is given, AGENTS.md and
+    /// `.codex/steering` are read from every one, and file edits are confined
+    /// to them.
+    #[arg(
+        long = "repo",
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        conflicts_with_all = ["batch", "watch"]
+    )]
+    pub repos: Vec<PathBuf>,
+
     /// Path to a JSON Schema file describing the model's final response shape.
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Attach an MCP server for this session only, without saving it to
+    /// config.toml. COMMAND is a shell-quoted command line or an `http(s)://`
+    /// URL. May be repeated.
+    #[arg(long = "mcp", value_name = "NAME=COMMAND")]
+    pub mcp_servers: Vec<String>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -73,16 +154,181 @@ pub struct Cli {
     #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
     pub color: Color,
 
-    /// Print events to stdout as JSONL.
+    /// Print events to stdout as JSONL, one object per line. The schema is
+    /// documented in docs/exec.md.
     #[arg(long = "json", alias = "experimental-json", default_value_t = false)]
     pub json: bool,
 
+    /// Print the results on stdout as SARIF or JUnit XML instead of the final
+    /// message, for code scanning and CI test reporters. SARIF needs review
+    /// findings.
+    #[arg(long = "output-format", value_enum, conflicts_with = "json")]
+    pub output_format: Option<OutputFormat>,
+
+    /// Format progress for a CI log. `github` turns errors, warnings, and
+    /// review findings into annotations, folds each command and tool call
+    /// into a collapsible group, and sets the step outputs `final-message`,
+    /// `diff-path`, and `session-id`.
+    #[arg(
+        long = "ci",
+        value_enum,
+        value_name = "PROVIDER",
+        conflicts_with = "json"
+    )]
+    pub ci: Option<CiProvider>,
+
+    /// Print only the final message (and errors).
+    #[arg(long = "quiet", short = 'q', conflicts_with_all = ["json", "verbose"])]
+    pub quiet: bool,
+
+    /// Print full tool output; repeat (`-vv`) to add per-request token usage.
+    #[arg(long = "verbose", short = 'v', action = clap::ArgAction::Count, conflicts_with = "json")]
+    pub verbose: u8,
+
+    /// Print the exit statuses a run can end with, as JSON with `--json`,
+    /// and exit.
+    #[arg(long = "print-exit-codes", default_value_t = false)]
+    pub print_exit_codes: bool,
+
     /// Specifies file where the last message from the agent should be written.
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Run every task in a YAML file instead of a single prompt. Each task
+    /// runs as its own `codex exec --json`; see docs/exec.md for the format.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "json", "last_message_file", "cwd", "images", "from_clipboard"]
+    )]
+    pub batch: Option<PathBuf>,
+
+    /// Number of batch tasks to run at once.
+    #[arg(
+        long = "parallel",
+        value_name = "N",
+        default_value_t = 1,
+        requires = "batch"
+    )]
+    pub parallel: usize,
+
+    /// Directory for per-task results and `summary.json`. Defaults to
+    /// `<FILE stem>-results` next to the batch file.
+    #[arg(long = "batch-output", value_name = "DIR", requires = "batch")]
+    pub batch_output: Option<PathBuf>,
+
+    /// When the run ends, collect its diff, transcript, usage report, and
+    /// any deliverables the agent names into DIR, with a `manifest.json`.
+    #[arg(
+        long = "artifacts-dir",
+        value_name = "DIR",
+        conflicts_with_all = ["batch", "watch"]
+    )]
+    pub artifacts_dir: Option<PathBuf>,
+
+    /// Save the session to DIR after every model response, so an interrupted
+    /// run can continue with `--resume DIR`.
+    #[arg(long = "checkpoint-dir", value_name = "DIR", conflicts_with = "batch")]
+    pub checkpoint_dir: Option<PathBuf>,
+
+    /// Continue the run checkpointed in DIR, or in `.codex/runs/ID`. PROMPT,
+    /// if given, is sent instead of a request to carry on.
+    #[arg(
+        long = "resume",
+        value_name = "ID|DIR",
+        conflicts_with_all = ["batch", "images", "from_clipboard"]
+    )]
+    pub resume_checkpoint: Option<String>,
+
+    /// Start a new session that already holds the conversation recorded in
+    /// a rollout file, or in the session with this id, and send PROMPT as
+    /// its next message. The recorded session is left as it was.
+    #[arg(
+        long = "seed-session",
+        value_name = "PATH|ID",
+        conflicts_with_all = ["batch", "watch", "resume_checkpoint"]
+    )]
+    pub seed_session: Option<String>,
+
+    /// Compact the seeded conversation before sending PROMPT, so a long
+    /// investigation does not crowd out the new work.
+    #[arg(long = "seed-compact", requires = "seed_session")]
+    pub seed_compact: bool,
+
+    /// Start a fresh run of PROMPT each time files matching GLOB change under
+    /// the working root, until interrupted. GLOB is relative to the root; `*`
+    /// also matches `/`. May be repeated.
+    #[arg(
+        long = "watch",
+        value_name = "GLOB",
+        conflicts_with_all = ["batch", "checkpoint_dir", "resume_checkpoint", "prompt_file", "images", "from_clipboard", "last_message_file", "output_format"]
+    )]
+    pub watch: Vec<String>,
+
+    /// How long `--watch` waits after a run ends before changes can start the
+    /// next one. Changes made during a run or its cooldown are ignored.
+    #[arg(
+        long = "watch-cooldown",
+        value_name = "DURATION",
+        default_value = "30s",
+        value_parser = parse_duration,
+        requires = "watch"
+    )]
+    pub watch_cooldown: Duration,
+
+    /// Read the prompt from FILE. `{{NAME}}` in it is filled in from `--arg`,
+    /// and `{{env.NAME}}` from the environment variable `NAME`.
+    #[arg(
+        long = "prompt-file",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "batch"]
+    )]
+    pub prompt_file: Option<PathBuf>,
+
+    /// Value for `{{NAME}}` in the prompt. May be repeated.
+    #[arg(
+        long = "arg",
+        value_name = "NAME=VALUE",
+        value_parser = parse_template_arg,
+        conflicts_with = "batch"
+    )]
+    pub template_args: Vec<(String, String)>,
+
+    /// Investigate with read-only tools and reply with a plan (steps,
+    /// affected files, and risks) instead of making changes. With `-o`, the
+    /// plan is saved as JSON for `--from-plan`.
+    #[arg(
+        long = "plan-only",
+        default_value_t = false,
+        conflicts_with_all = ["output_schema", "output_format", "from_plan", "batch", "watch", "full_auto", "dangerously_bypass_approvals_and_sandbox", "sandbox_mode"]
+    )]
+    pub plan_only: bool,
+
+    /// How `--plan-only` prints the plan on stdout.
+    #[arg(
+        long = "plan-format",
+        value_enum,
+        default_value_t = PlanFormat::Markdown,
+        requires = "plan_only"
+    )]
+    pub plan_format: PlanFormat,
+
+    /// Carry out a plan saved by `--plan-only -o FILE`. PROMPT, if given, is
+    /// added to the plan as extra instructions.
+    #[arg(
+        long = "from-plan",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt_file", "resume_checkpoint", "batch", "watch"]
+    )]
+    pub from_plan: Option<PathBuf>,
+
+    /// Do not attach piped stdin as context when PROMPT is given.
+    #[arg(long = "no-stdin", default_value_t = false)]
+    pub no_stdin: bool,
+
     /// Initial instructions for the agent. If not provided as an argument (or
-    /// if `-` is used), instructions are read from stdin.
+    /// if `-` is used), instructions are read from stdin. Otherwise, piped
+    /// stdin is attached to the prompt as context.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
 }
@@ -145,6 +391,58 @@ pub struct ReviewArgs {
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Print the findings on stdout as Markdown, SARIF, GitHub review
+    /// comments, or JUnit XML.
+    #[arg(
+        long = "format",
+        visible_alias = "output-format",
+        value_enum,
+        default_value_t = ReviewFormat::Text
+    )]
+    pub format: ReviewFormat,
+}
+
+/// Parses `90`, `90s`, `30m`, `2h`, or a combination such as `1h30m`. A bare
+/// number is seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{value}`; expected e.g. 90s, 30m, or 1h30m");
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return (seconds > 0)
+            .then(|| Duration::from_secs(seconds))
+            .ok_or_else(invalid);
+    }
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            _ => return Err(invalid()),
+        };
+        let amount: u64 = digits.parse().map_err(|_| invalid())?;
+        total += amount * unit;
+        digits.clear();
+    }
+    if !digits.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+fn parse_usd(value: &str) -> Result<f64, String> {
+    match value.trim().trim_start_matches('$').parse::<f64>() {
+        Ok(usd) if usd.is_finite() && usd > 0.0 => Ok(usd),
+        _ => Err(format!(
+            "invalid amount `{value}`; expected a positive number of US dollars"
+        )),
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -155,3 +453,88 @@ pub enum Color {
     #[default]
     Auto,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    Sarif,
+    Junit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum PlanFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum CiProvider {
+    /// GitHub Actions workflow commands and step outputs.
+    Github,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_durations_with_units() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(1800)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("5d").is_err());
+    }
+
+    #[test]
+    fn parses_positive_dollar_amounts() {
+        assert_eq!(parse_usd("$2.50"), Ok(2.5));
+        assert!(parse_usd("0").is_err());
+        assert!(parse_usd("-1").is_err());
+    }
+
+    #[test]
+    fn review_format_accepts_kebab_case_names() {
+        let cli = Cli::try_parse_from([
+            "codex-exec",
+            "review",
+            "--base",
+            "main",
+            "--format",
+            "gh-comments",
+        ])
+        .expect("parse");
+        let Some(Command::Review(args)) = cli.command else {
+            panic!("expected review command");
+        };
+        assert_eq!(args.base.as_deref(), Some("main"));
+        assert_eq!(args.format, ReviewFormat::GhComments);
+    }
+
+    #[test]
+    fn plan_only_forces_its_own_output_schema_and_sandbox() {
+        let cli = Cli::try_parse_from(["codex-exec", "--plan-only", "--plan-format", "json", "x"])
+            .expect("parse");
+        assert!(cli.plan_only);
+        assert_eq!(cli.plan_format, PlanFormat::Json);
+
+        for conflicting in [
+            ["--output-schema", "schema.json"].as_slice(),
+            ["--from-plan", "plan.json"].as_slice(),
+            ["--sandbox", "workspace-write"].as_slice(),
+            ["--full-auto"].as_slice(),
+        ] {
+            let args = ["codex-exec", "--plan-only"]
+                .into_iter()
+                .chain(conflicting.iter().copied())
+                .chain(["x"]);
+            assert!(Cli::try_parse_from(args).is_err(), "{conflicting:?}");
+        }
+        assert!(Cli::try_parse_from(["codex-exec", "--plan-format", "json", "x"]).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+//! Prompt wiring for `codex exec --from-clipboard`; the capture itself lives in
+//! `codex_common::clipboard_capture`.
+
+use std::path::Path;
+
+/// Text appended to the prompt so the agent knows where the captured clipboard
+/// text lives.
+pub(crate) fn clipboard_text_note(path: &Path) -> String {
+    format!(
+        "The clipboard contents were saved to {}; read that file for context.",
+        path.display()
+    )
+}
@@ -1,7 +1,11 @@
 use std::path::Path;
+use std::time::Duration;
 
+use codex_common::elapsed::format_duration;
 use codex_core::config::Config;
 use codex_core::protocol::Event;
+use codex_core::protocol::RunLimit;
+use codex_core::protocol::RunLimitReachedEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 
 pub(crate) enum CodexStatus {
@@ -36,6 +40,24 @@ pub(crate) fn handle_last_message(last_agent_message: Option<&str>, output_file:
     }
 }
 
+/// Which limit ended the run, and how far it got.
+pub(crate) fn run_limit_summary(event: &RunLimitReachedEvent) -> String {
+    let flag = match event.limit {
+        RunLimit::Turns => "--max-turns",
+        RunLimit::Duration => "--max-duration",
+        RunLimit::Cost => "--max-cost-usd",
+    };
+    let elapsed = format_duration(Duration::from_secs(event.elapsed_seconds));
+    let cost = event
+        .cost_usd
+        .map(|cost| format!(", about ${cost:.2}"))
+        .unwrap_or_default();
+    format!(
+        "Stopped by {flag} after {} model requests ({elapsed}{cost}).",
+        event.turns
+    )
+}
+
 fn write_last_message_file(contents: &str, last_message_path: Option<&Path>) {
     if let Some(path) = last_message_path
         && let Err(e) = std::fs::write(path, contents)
@@ -34,12 +34,12 @@ use std::time::Instant;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::event_processor::run_limit_summary;
 use codex_common::create_config_summary_entries;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 
-/// This should be configurable. When used in CI, users may not want to impose
-/// a limit so they can see the full transcript.
+/// Lines of command and tool output shown by default; `-v` shows all of it.
 const MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL: usize = 20;
 pub(crate) struct EventProcessorWithHumanOutput {
     call_id_to_patch: HashMap<String, PatchApplyBegin>,
@@ -63,6 +63,15 @@ pub(crate) struct EventProcessorWithHumanOutput {
     last_message_path: Option<PathBuf>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsageInfo>,
     final_message: Option<String>,
+    /// Whether `print_final_output` writes the final message to stdout.
+    /// Off when stdout carries a report instead.
+    print_final_message: bool,
+    verbosity: Verbosity,
+    /// Total tokens at the last request printed with `-vv`, so repeated token
+    /// counts for the same request are printed once.
+    last_request_tokens: Option<i64>,
+    /// Why the run stopped early, repeated after the token count.
+    run_limit_summary: Option<String>,
 }
 
 impl EventProcessorWithHumanOutput {
@@ -70,6 +79,8 @@ impl EventProcessorWithHumanOutput {
         with_ansi: bool,
         config: &Config,
         last_message_path: Option<PathBuf>,
+        print_final_message: bool,
+        verbosity: Verbosity,
     ) -> Self {
         let call_id_to_patch = HashMap::new();
 
@@ -89,6 +100,10 @@ impl EventProcessorWithHumanOutput {
                 last_message_path,
                 last_total_token_usage: None,
                 final_message: None,
+                print_final_message,
+                verbosity,
+                last_request_tokens: None,
+                run_limit_summary: None,
             }
         } else {
             Self {
@@ -106,9 +121,46 @@ impl EventProcessorWithHumanOutput {
                 last_message_path,
                 last_total_token_usage: None,
                 final_message: None,
+                print_final_message,
+                verbosity,
+                last_request_tokens: None,
+                run_limit_summary: None,
             }
         }
     }
+
+    fn output_line_limit(&self) -> usize {
+        if self.verbosity >= Verbosity::Verbose {
+            usize::MAX
+        } else {
+            MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL
+        }
+    }
+}
+
+/// How much of the run goes to stderr: `-q`, the default, `-v`, or `-vv`.
+/// The final message goes to stdout at every level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verbosity {
+    /// Errors only.
+    Quiet,
+    /// Progress: messages, commands with the start of their output, patches.
+    Normal,
+    /// Full command and tool output.
+    Verbose,
+    /// Also token usage and context window for every model request.
+    Debug,
+}
+
+impl Verbosity {
+    pub(crate) fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            (false, _) => Self::Debug,
+        }
+    }
 }
 
 struct PatchApplyBegin {
@@ -133,6 +185,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         prompt: &str,
         session_configured_event: &SessionConfiguredEvent,
     ) {
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
         const VERSION: &str = env!("CARGO_PKG_VERSION");
         ts_msg!(
             self,
@@ -161,11 +216,32 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let Event { id: _, msg } = event;
+        if self.verbosity == Verbosity::Quiet
+            && !matches!(
+                msg,
+                EventMsg::Error(_)
+                    | EventMsg::RunLimitReached(_)
+                    | EventMsg::TaskComplete(_)
+                    | EventMsg::TokenCount(_)
+                    | EventMsg::ShutdownComplete
+            )
+        {
+            return CodexStatus::Running;
+        }
         match msg {
             EventMsg::Error(ErrorEvent { message, .. }) => {
                 let prefix = "ERROR:".style(self.red);
                 ts_msg!(self, "{prefix} {message}");
             }
+            EventMsg::RunLimitReached(ev) => {
+                let summary = run_limit_summary(&ev);
+                ts_msg!(
+                    self,
+                    "{} {summary}",
+                    "limit reached:".style(self.red).style(self.bold)
+                );
+                self.run_limit_summary = Some(summary);
+            }
             EventMsg::Warning(WarningEvent { message }) => {
                 ts_msg!(
                     self,
@@ -173,6 +249,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::TerminologyWarning(ev) => {
+                ts_msg!(
+                    self,
+                    "{} {ev}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -183,6 +266,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     ts_msg!(self, "  {}", details.style(self.dimmed));
                 }
             }
+            EventMsg::ConfigReloaded(ev) => {
+                ts_msg!(self, "{}", ev.to_string().style(self.dimmed));
+            }
             EventMsg::McpStartupUpdate(update) => {
                 let status_text = match update.status {
                     codex_core::protocol::McpStartupStatus::Starting => "starting".to_string(),
@@ -252,6 +338,26 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 return CodexStatus::InitiateShutdown;
             }
             EventMsg::TokenCount(ev) => {
+                if self.verbosity == Verbosity::Debug
+                    && let Some(info) = &ev.info
+                    && self.last_request_tokens != Some(info.total_token_usage.total_tokens)
+                {
+                    self.last_request_tokens = Some(info.total_token_usage.total_tokens);
+                    let last = &info.last_token_usage;
+                    let window = info
+                        .model_context_window
+                        .map(|window| format!(" of a {} window", format_with_separators(window)))
+                        .unwrap_or_default();
+                    ts_msg!(
+                        self,
+                        "{} input {} ({} cached){window}, output {} ({} reasoning)",
+                        "request".style(self.dimmed),
+                        format_with_separators(last.input_tokens),
+                        format_with_separators(last.cached_input_tokens),
+                        format_with_separators(last.output_tokens),
+                        format_with_separators(last.reasoning_output_tokens)
+                    );
+                }
                 self.last_total_token_usage = ev.info;
             }
 
@@ -297,7 +403,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
 
                 let truncated_output = aggregated_output
                     .lines()
-                    .take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL)
+                    .take(self.output_line_limit())
                     .collect::<Vec<_>>()
                     .join("\n");
                 match exit_code {
@@ -348,7 +454,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     let pretty =
                         serde_json::to_string_pretty(&val).unwrap_or_else(|_| val.to_string());
 
-                    for line in pretty.lines().take(MAX_OUTPUT_LINES_FOR_EXEC_TOOL_CALL) {
+                    for line in pretty.lines().take(self.output_line_limit()) {
                         eprintln!("{}", line.style(self.dimmed));
                     }
                 }
@@ -570,6 +676,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
+            | EventMsg::SearchHistoryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
@@ -586,6 +693,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
+            | EventMsg::SessionTitle(_)
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_) => {}
         }
@@ -593,20 +701,27 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     }
 
     fn print_final_output(&mut self) {
-        if let Some(usage_info) = &self.last_total_token_usage {
+        if let Some(usage_info) = &self.last_total_token_usage
+            && self.verbosity > Verbosity::Quiet
+        {
             eprintln!(
                 "{}\n{}",
                 "tokens used".style(self.magenta).style(self.italic),
                 format_with_separators(usage_info.total_token_usage.blended_total())
             );
         }
+        if let Some(summary) = &self.run_limit_summary {
+            eprintln!("{}", summary.style(self.red));
+        }
 
         // If the user has not piped the final message to a file, they will see
         // it twice: once written to stderr as part of the normal event
         // processing, and once here on stdout. We print the token summary above
         // to help break up the output visually in that case.
         #[allow(clippy::print_stdout)]
-        if let Some(message) = &self.final_message {
+        if let Some(message) = &self.final_message
+            && self.print_final_message
+        {
             if message.ends_with('\n') {
                 print!("{message}");
             } else {
@@ -616,7 +731,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     }
 }
 
-fn escape_command(command: &[String]) -> String {
+pub(crate) fn escape_command(command: &[String]) -> String {
     try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
 }
 
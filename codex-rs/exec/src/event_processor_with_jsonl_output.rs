@@ -5,6 +5,7 @@ use std::sync::atomic::AtomicU64;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
+use crate::event_processor::run_limit_summary;
 use crate::exec_events::AgentMessageItem;
 use crate::exec_events::CommandExecutionItem;
 use crate::exec_events::CommandExecutionStatus;
@@ -20,6 +21,7 @@ use crate::exec_events::McpToolCallItemResult;
 use crate::exec_events::McpToolCallStatus;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
+use crate::exec_events::PromptBreakdown;
 use crate::exec_events::ReasoningItem;
 use crate::exec_events::ThreadErrorEvent;
 use crate::exec_events::ThreadEvent;
@@ -65,6 +67,7 @@ pub struct EventProcessorWithJsonOutput {
     // Tracks the todo list for the current turn (at most one per turn).
     running_todo_list: Option<RunningTodoList>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
+    last_prompt_breakdown: Option<codex_core::protocol::PromptTokenBreakdown>,
     running_mcp_tool_calls: HashMap<String, RunningMcpToolCall>,
     last_critical_error: Option<ThreadErrorEvent>,
 }
@@ -99,6 +102,7 @@ impl EventProcessorWithJsonOutput {
             running_patch_applies: HashMap::new(),
             running_todo_list: None,
             last_total_token_usage: None,
+            last_prompt_breakdown: None,
             running_mcp_tool_calls: HashMap::new(),
             last_critical_error: None,
         }
@@ -125,6 +129,9 @@ impl EventProcessorWithJsonOutput {
                 if let Some(info) = &ev.info {
                     self.last_total_token_usage = Some(info.total_token_usage.clone());
                 }
+                if let Some(breakdown) = ev.prompt_breakdown {
+                    self.last_prompt_breakdown = Some(breakdown);
+                }
                 Vec::new()
             }
             EventMsg::TaskStarted(ev) => self.handle_task_started(ev),
@@ -136,6 +143,13 @@ impl EventProcessorWithJsonOutput {
                 self.last_critical_error = Some(error.clone());
                 vec![ThreadEvent::Error(error)]
             }
+            EventMsg::RunLimitReached(ev) => {
+                let error = ThreadErrorEvent {
+                    message: run_limit_summary(ev),
+                };
+                self.last_critical_error = Some(error.clone());
+                vec![ThreadEvent::Error(error)]
+            }
             EventMsg::Warning(ev) => {
                 let item = ThreadItem {
                     id: self.get_next_item_id(),
@@ -145,6 +159,15 @@ impl EventProcessorWithJsonOutput {
                 };
                 vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
             }
+            EventMsg::TerminologyWarning(ev) => {
+                let item = ThreadItem {
+                    id: self.get_next_item_id(),
+                    details: ThreadItemDetails::Error(ErrorItem {
+                        message: ev.to_string(),
+                    }),
+                };
+                vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
+            }
             EventMsg::StreamError(ev) => vec![ThreadEvent::Error(ThreadErrorEvent {
                 message: ev.message.clone(),
             })],
@@ -459,14 +482,23 @@ impl EventProcessorWithJsonOutput {
     }
 
     fn handle_task_complete(&mut self) -> Vec<ThreadEvent> {
+        let prompt_breakdown = self.last_prompt_breakdown.map(|b| PromptBreakdown {
+            steering_tokens: b.steering_tokens,
+            agents_md_tokens: b.agents_md_tokens,
+            history_tokens: b.history_tokens,
+        });
         let usage = if let Some(u) = &self.last_total_token_usage {
             Usage {
                 input_tokens: u.input_tokens,
                 cached_input_tokens: u.cached_input_tokens,
                 output_tokens: u.output_tokens,
+                prompt_breakdown,
             }
         } else {
-            Usage::default()
+            Usage {
+                prompt_breakdown,
+                ..Usage::default()
+            }
         };
 
         let mut items = Vec::new();
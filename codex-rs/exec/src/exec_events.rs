@@ -64,6 +64,21 @@ pub struct Usage {
     pub cached_input_tokens: i64,
     /// The number of output tokens used during the turn.
     pub output_tokens: i64,
+    /// Estimated split of the latest prompt between steering files, AGENTS.md, and history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub prompt_breakdown: Option<PromptBreakdown>,
+}
+
+/// Approximate prompt tokens attributable to each source of context.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Default)]
+pub struct PromptBreakdown {
+    /// Tokens spent on files loaded from the steering directories.
+    pub steering_tokens: i64,
+    /// Tokens spent on AGENTS.md instructions.
+    pub agents_md_tokens: i64,
+    /// Tokens spent on the rest of the conversation history.
+    pub history_tokens: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -0,0 +1,338 @@
+//! The exit statuses of `codex exec`, one per way a run can end, so wrapper
+//! scripts can branch on why a run failed. `--print-exit-codes` prints the
+//! table. Codes are never renumbered; new ones are added at the end.
+
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ErrorEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::RunLimit;
+use codex_core::protocol::RunLimitReachedEvent;
+use codex_protocol::items::AgentMessageContent;
+use codex_protocol::items::TurnItem;
+use serde::Serialize;
+
+use crate::approval_rules::ApprovalRule;
+use crate::approval_rules::Verdict;
+use crate::event_processor::run_limit_summary;
+use crate::junit::Failure;
+
+pub(crate) const SUCCESS_EXIT_CODE: i32 = 0;
+pub(crate) const INTERNAL_ERROR_EXIT_CODE: i32 = 1;
+/// What clap exits with for invalid arguments.
+pub(crate) const USAGE_ERROR_EXIT_CODE: i32 = 2;
+pub(crate) const APPROVAL_REQUIRED_EXIT_CODE: i32 = 3;
+pub(crate) const MAX_TURNS_EXIT_CODE: i32 = 4;
+pub(crate) const MAX_DURATION_EXIT_CODE: i32 = 5;
+pub(crate) const MAX_COST_EXIT_CODE: i32 = 6;
+pub(crate) const MODEL_REFUSAL_EXIT_CODE: i32 = 7;
+pub(crate) const SANDBOX_DENIED_EXIT_CODE: i32 = 8;
+pub(crate) const PROVIDER_ERROR_EXIT_CODE: i32 = 9;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ExitCode {
+    pub code: i32,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub(crate) const EXIT_CODES: &[ExitCode] = &[
+    ExitCode {
+        code: SUCCESS_EXIT_CODE,
+        name: "success",
+        description: "The run finished.",
+    },
+    ExitCode {
+        code: INTERNAL_ERROR_EXIT_CODE,
+        name: "internal_error",
+        description: "Any other failure, such as invalid configuration or an unexpected error in Codex.",
+    },
+    ExitCode {
+        code: USAGE_ERROR_EXIT_CODE,
+        name: "usage_error",
+        description: "Invalid command-line arguments.",
+    },
+    ExitCode {
+        code: APPROVAL_REQUIRED_EXIT_CODE,
+        name: "approval_required",
+        description: "An action needed approval nobody could give: a --deny rule stopped the run, or no --approve rule allowed a request.",
+    },
+    ExitCode {
+        code: MAX_TURNS_EXIT_CODE,
+        name: "max_turns_exceeded",
+        description: "Stopped by --max-turns.",
+    },
+    ExitCode {
+        code: MAX_DURATION_EXIT_CODE,
+        name: "max_duration_exceeded",
+        description: "Stopped by --max-duration.",
+    },
+    ExitCode {
+        code: MAX_COST_EXIT_CODE,
+        name: "max_cost_exceeded",
+        description: "Stopped by --max-cost-usd.",
+    },
+    ExitCode {
+        code: MODEL_REFUSAL_EXIT_CODE,
+        name: "model_refusal",
+        description: "The model refused the request.",
+    },
+    ExitCode {
+        code: SANDBOX_DENIED_EXIT_CODE,
+        name: "sandbox_denied",
+        description: "The run failed because the sandbox blocked it.",
+    },
+    ExitCode {
+        code: PROVIDER_ERROR_EXIT_CODE,
+        name: "provider_error",
+        description: "The model provider failed or rejected the credentials: authentication, usage limits, context window, or connection errors.",
+    },
+];
+
+/// The table for `--print-exit-codes`, as aligned text or as a JSON array.
+pub(crate) fn render_exit_codes(json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(EXIT_CODES).unwrap_or_default();
+    }
+    let width = EXIT_CODES
+        .iter()
+        .map(|exit| exit.name.len())
+        .max()
+        .unwrap_or_default();
+    EXIT_CODES
+        .iter()
+        .map(|exit| {
+            format!(
+                "{:>3}  {:width$}  {}",
+                exit.code, exit.name, exit.description
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The name of an exit status, such as `max_turns_exceeded`.
+pub(crate) fn exit_code_name(code: i32) -> &'static str {
+    EXIT_CODES
+        .iter()
+        .find(|exit| exit.code == code)
+        .map_or("internal_error", |exit| exit.name)
+}
+
+/// What the event stream said about how a run ended.
+#[derive(Debug, Default)]
+pub(crate) struct RunOutcome {
+    /// The `--deny` rule that stopped the run.
+    pub denied: Option<ApprovalRule>,
+    /// Requests declined because no `--approve` rule allowed them.
+    pub declined: usize,
+    pub limit_reached: Option<RunLimitReachedEvent>,
+    pub error: Option<ErrorEvent>,
+    /// Set while the latest agent message is a refusal.
+    pub refusal: Option<String>,
+}
+
+impl RunOutcome {
+    pub(crate) fn record_event(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::Error(ev) => self.error = Some(ev.clone()),
+            EventMsg::RunLimitReached(ev) => self.limit_reached = Some(ev.clone()),
+            EventMsg::ItemCompleted(ev) => {
+                if let TurnItem::AgentMessage(message) = &ev.item {
+                    self.refusal = message.content.iter().find_map(|content| match content {
+                        AgentMessageContent::Refusal { refusal } => Some(refusal.clone()),
+                        AgentMessageContent::Text { .. } => None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_verdict(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Approve => {}
+            Verdict::Decline => self.declined += 1,
+            Verdict::Deny(rule) => self.denied = Some(*rule),
+        }
+    }
+
+    /// Whether the run stopped before the agent was done with it.
+    pub(crate) fn stopped_early(&self) -> bool {
+        self.denied.is_some() || self.limit_reached.is_some() || self.error.is_some()
+    }
+
+    /// The exit status, picking what ended the run over what happened along
+    /// the way.
+    pub(crate) fn exit_code(&self) -> i32 {
+        if self.denied.is_some() {
+            APPROVAL_REQUIRED_EXIT_CODE
+        } else if let Some(event) = &self.limit_reached {
+            run_limit_exit_code(event.limit)
+        } else if let Some(error) = &self.error {
+            error_exit_code(error.codex_error_info.as_ref())
+        } else if self.refusal.is_some() {
+            MODEL_REFUSAL_EXIT_CODE
+        } else if self.declined > 0 {
+            APPROVAL_REQUIRED_EXIT_CODE
+        } else {
+            SUCCESS_EXIT_CODE
+        }
+    }
+
+    /// Why the run failed, for its JUnit test case.
+    pub(crate) fn failure(&self) -> Option<Failure> {
+        let code = self.exit_code();
+        let message = if let Some(rule) = self.denied {
+            format!("Stopped by --deny {rule}.")
+        } else if let Some(event) = &self.limit_reached {
+            run_limit_summary(event)
+        } else if let Some(error) = &self.error {
+            error.message.clone()
+        } else if let Some(refusal) = &self.refusal {
+            refusal.clone()
+        } else if self.declined > 0 {
+            format!(
+                "Declined {} approval request(s): no --approve rule allowed them.",
+                self.declined
+            )
+        } else {
+            return None;
+        };
+        Some(Failure {
+            kind: exit_code_name(code).to_string(),
+            message,
+            body: String::new(),
+        })
+    }
+}
+
+fn run_limit_exit_code(limit: RunLimit) -> i32 {
+    match limit {
+        RunLimit::Turns => MAX_TURNS_EXIT_CODE,
+        RunLimit::Duration => MAX_DURATION_EXIT_CODE,
+        RunLimit::Cost => MAX_COST_EXIT_CODE,
+    }
+}
+
+fn error_exit_code(info: Option<&CodexErrorInfo>) -> i32 {
+    match info {
+        Some(CodexErrorInfo::SandboxError) => SANDBOX_DENIED_EXIT_CODE,
+        Some(
+            CodexErrorInfo::Unauthorized
+            | CodexErrorInfo::UsageLimitExceeded
+            | CodexErrorInfo::ContextWindowExceeded
+            | CodexErrorInfo::InternalServerError
+            | CodexErrorInfo::HttpConnectionFailed { .. }
+            | CodexErrorInfo::ResponseStreamConnectionFailed { .. }
+            | CodexErrorInfo::ResponseStreamDisconnected { .. }
+            | CodexErrorInfo::ResponseTooManyFailedAttempts { .. },
+        ) => PROVIDER_ERROR_EXIT_CODE,
+        Some(CodexErrorInfo::BadRequest | CodexErrorInfo::Other) | None => INTERNAL_ERROR_EXIT_CODE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ConversationId;
+    use codex_protocol::items::AgentMessageItem;
+    use codex_protocol::protocol::ItemCompletedEvent;
+    use pretty_assertions::assert_eq;
+
+    fn error(info: Option<CodexErrorInfo>) -> EventMsg {
+        EventMsg::Error(ErrorEvent {
+            message: "failed".to_string(),
+            codex_error_info: info,
+        })
+    }
+
+    fn agent_message(content: AgentMessageContent) -> EventMsg {
+        EventMsg::ItemCompleted(ItemCompletedEvent {
+            thread_id: ConversationId::new(),
+            turn_id: "turn-1".to_string(),
+            item: TurnItem::AgentMessage(AgentMessageItem::new(&[content])),
+        })
+    }
+
+    fn exit_code(events: &[EventMsg], verdicts: &[Verdict]) -> i32 {
+        let mut outcome = RunOutcome::default();
+        for verdict in verdicts {
+            outcome.record_verdict(verdict);
+        }
+        for event in events {
+            outcome.record_event(event);
+        }
+        outcome.exit_code()
+    }
+
+    #[test]
+    fn codes_are_unique_and_in_order() {
+        let codes: Vec<i32> = EXIT_CODES.iter().map(|exit| exit.code).collect();
+        assert_eq!(codes, (0..=9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn errors_map_to_their_failure_class() {
+        assert_eq!(exit_code(&[], &[]), SUCCESS_EXIT_CODE);
+        assert_eq!(
+            exit_code(&[error(Some(CodexErrorInfo::Unauthorized))], &[]),
+            PROVIDER_ERROR_EXIT_CODE
+        );
+        assert_eq!(
+            exit_code(&[error(Some(CodexErrorInfo::SandboxError))], &[]),
+            SANDBOX_DENIED_EXIT_CODE
+        );
+        assert_eq!(
+            exit_code(&[error(Some(CodexErrorInfo::Other))], &[]),
+            INTERNAL_ERROR_EXIT_CODE
+        );
+        assert_eq!(exit_code(&[error(None)], &[]), INTERNAL_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn refusal_counts_only_when_it_is_the_last_message() {
+        let refusal = agent_message(AgentMessageContent::Refusal {
+            refusal: "I can't help with that.".to_string(),
+        });
+        let text = agent_message(AgentMessageContent::Text {
+            text: "Done.".to_string(),
+        });
+        assert_eq!(exit_code(&[refusal.clone()], &[]), MODEL_REFUSAL_EXIT_CODE);
+        assert_eq!(exit_code(&[refusal, text], &[]), SUCCESS_EXIT_CODE);
+    }
+
+    #[test]
+    fn what_stopped_the_run_wins_over_declined_requests() {
+        assert_eq!(
+            exit_code(&[], &[Verdict::Decline]),
+            APPROVAL_REQUIRED_EXIT_CODE
+        );
+        assert_eq!(
+            exit_code(
+                &[error(Some(CodexErrorInfo::UsageLimitExceeded))],
+                &[Verdict::Decline]
+            ),
+            PROVIDER_ERROR_EXIT_CODE
+        );
+        let limit = EventMsg::RunLimitReached(RunLimitReachedEvent {
+            limit: RunLimit::Cost,
+            turns: 3,
+            elapsed_seconds: 10,
+            cost_usd: Some(1.5),
+        });
+        assert_eq!(
+            exit_code(&[limit], &[Verdict::Deny(ApprovalRule::AllCommands)]),
+            APPROVAL_REQUIRED_EXIT_CODE
+        );
+    }
+
+    #[test]
+    fn failure_kind_is_the_exit_code_name() {
+        let mut outcome = RunOutcome::default();
+        assert!(outcome.failure().is_none());
+        outcome.record_event(&error(Some(CodexErrorInfo::Unauthorized)));
+        let failure = outcome.failure().expect("failure");
+        assert_eq!(failure.kind, "provider_error");
+        assert_eq!(failure.message, "failed");
+    }
+}
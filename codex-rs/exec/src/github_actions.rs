@@ -0,0 +1,348 @@
+//! `codex exec --ci github`: progress that reads well in a GitHub Actions log.
+//!
+//! The human-readable output is kept and decorated with workflow commands:
+//! errors and warnings are repeated as `::error::`/`::warning::` so they
+//! become annotations, review findings are annotated on their file and lines,
+//! and each command, tool call, or patch is folded into a `::group::`. When
+//! the run ends, the final message, the path of the run's diff, and the
+//! session id are written to `$GITHUB_OUTPUT` as step outputs.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::Config;
+use codex_core::protocol::ErrorEvent;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::WarningEvent;
+
+use crate::event_processor::CodexStatus;
+use crate::event_processor::EventProcessor;
+use crate::event_processor::run_limit_summary;
+use crate::event_processor_with_human_output::escape_command;
+use crate::review_output::review_annotations;
+
+/// Delimiter for multiline step outputs; extended until the value does not
+/// contain it.
+const OUTPUT_DELIMITER: &str = "CODEX_OUTPUT_EOF";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+/// A `::error::`, `::warning::`, or `::notice::` workflow command.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Annotation {
+    pub level: AnnotationLevel,
+    /// Relative to the repository root.
+    pub file: Option<String>,
+    /// First and last line in `file`.
+    pub lines: Option<(u32, u32)>,
+    pub title: Option<String>,
+    pub message: String,
+}
+
+impl Annotation {
+    fn new(level: AnnotationLevel, message: &str) -> Self {
+        Self {
+            level,
+            file: None,
+            lines: None,
+            title: None,
+            message: message.to_string(),
+        }
+    }
+
+    fn render(&self) -> String {
+        let command = match self.level {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        };
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", escape_property(file)));
+        }
+        if let Some((line, end_line)) = self.lines {
+            properties.push(format!("line={line},endLine={end_line}"));
+        }
+        if let Some(title) = &self.title {
+            properties.push(format!("title={}", escape_property(title)));
+        }
+        let properties = if properties.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", properties.join(","))
+        };
+        format!("::{command}{properties}::{}", escape_data(&self.message))
+    }
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+pub(crate) struct GitHubActionsOutput {
+    inner: Box<dyn EventProcessor>,
+    repo_root: PathBuf,
+    /// `$GITHUB_OUTPUT`; without it no step outputs are written.
+    output_file: Option<PathBuf>,
+    /// Where the diff named by the `diff-path` output is written.
+    diff_dir: PathBuf,
+    group_open: bool,
+    session_id: Option<String>,
+    diff: Option<String>,
+    final_message: Option<String>,
+}
+
+impl GitHubActionsOutput {
+    pub(crate) fn new(
+        inner: Box<dyn EventProcessor>,
+        repo_root: PathBuf,
+        output_file: Option<PathBuf>,
+        diff_dir: PathBuf,
+    ) -> Self {
+        Self {
+            inner,
+            repo_root,
+            output_file,
+            diff_dir,
+            group_open: false,
+            session_id: None,
+            diff: None,
+            final_message: None,
+        }
+    }
+
+    /// Groups cannot nest, so a new group closes the open one.
+    fn open_group(&mut self, title: &str) {
+        self.close_group();
+        eprintln!("::group::{}", escape_data(title));
+        self.group_open = true;
+    }
+
+    fn close_group(&mut self) {
+        if self.group_open {
+            eprintln!("::endgroup::");
+            self.group_open = false;
+        }
+    }
+
+    fn annotations(&self, msg: &EventMsg) -> Vec<Annotation> {
+        match msg {
+            EventMsg::Error(ErrorEvent { message, .. }) => {
+                vec![Annotation::new(AnnotationLevel::Error, message)]
+            }
+            EventMsg::Warning(WarningEvent { message })
+            | EventMsg::StreamError(StreamErrorEvent { message, .. }) => {
+                vec![Annotation::new(AnnotationLevel::Warning, message)]
+            }
+            EventMsg::RunLimitReached(ev) => {
+                vec![Annotation::new(
+                    AnnotationLevel::Error,
+                    &run_limit_summary(ev),
+                )]
+            }
+            EventMsg::ExitedReviewMode(ev) => ev
+                .review_output
+                .as_ref()
+                .map(|output| review_annotations(output, &self.repo_root))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn write_step_outputs(&self, output_file: &Path) -> std::io::Result<()> {
+        let mut outputs = Vec::new();
+        if let Some(session_id) = &self.session_id {
+            outputs.push(("session-id", session_id.clone()));
+        }
+        outputs.push((
+            "final-message",
+            self.final_message.clone().unwrap_or_default(),
+        ));
+        if let Some(diff) = self.diff.as_deref().filter(|diff| !diff.is_empty()) {
+            let name = self.session_id.as_deref().unwrap_or("run");
+            let diff_path = self.diff_dir.join(format!("codex-{name}.patch"));
+            std::fs::write(&diff_path, diff)?;
+            outputs.push(("diff-path", diff_path.display().to_string()));
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output_file)?;
+        file.write_all(step_outputs(&outputs).as_bytes())
+    }
+}
+
+/// `$GITHUB_OUTPUT` entries in the multiline `name<<DELIMITER` form.
+fn step_outputs(outputs: &[(&str, String)]) -> String {
+    let mut text = String::new();
+    for (name, value) in outputs {
+        let mut delimiter = OUTPUT_DELIMITER.to_string();
+        while value.contains(&delimiter) {
+            delimiter.push('_');
+        }
+        text.push_str(&format!("{name}<<{delimiter}\n{value}\n{delimiter}\n"));
+    }
+    text
+}
+
+impl EventProcessor for GitHubActionsOutput {
+    fn print_config_summary(
+        &mut self,
+        config: &Config,
+        prompt: &str,
+        session_configured: &SessionConfiguredEvent,
+    ) {
+        self.session_id = Some(session_configured.session_id.to_string());
+        self.inner
+            .print_config_summary(config, prompt, session_configured);
+    }
+
+    fn process_event(&mut self, event: Event) -> CodexStatus {
+        match &event.msg {
+            EventMsg::ExecCommandBegin(ev) => {
+                self.open_group(&format!("$ {}", escape_command(&ev.command)));
+            }
+            EventMsg::McpToolCallBegin(ev) => self.open_group(&format!(
+                "tool {}.{}",
+                ev.invocation.server, ev.invocation.tool
+            )),
+            EventMsg::PatchApplyBegin(ev) => {
+                self.open_group(&format!("apply_patch: {} file(s)", ev.changes.len()));
+            }
+            EventMsg::TurnDiff(ev) => self.diff = Some(ev.unified_diff.clone()),
+            EventMsg::TaskComplete(ev) => self.final_message = ev.last_agent_message.clone(),
+            _ => {}
+        }
+        let ends_group = matches!(
+            event.msg,
+            EventMsg::ExecCommandEnd(_) | EventMsg::McpToolCallEnd(_) | EventMsg::PatchApplyEnd(_)
+        );
+        let annotations = self.annotations(&event.msg);
+
+        let status = self.inner.process_event(event);
+        if ends_group || !annotations.is_empty() {
+            self.close_group();
+        }
+        for annotation in annotations {
+            eprintln!("{}", annotation.render());
+        }
+        status
+    }
+
+    fn print_final_output(&mut self) {
+        self.close_group();
+        self.inner.print_final_output();
+        if let Some(output_file) = &self.output_file
+            && let Err(err) = self.write_step_outputs(output_file)
+        {
+            eprintln!(
+                "::warning::Failed to write step outputs to {}: {err}",
+                output_file.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::TaskCompleteEvent;
+    use codex_core::protocol::TurnDiffEvent;
+    use pretty_assertions::assert_eq;
+
+    struct Silent;
+
+    impl EventProcessor for Silent {
+        fn print_config_summary(&mut self, _: &Config, _: &str, _: &SessionConfiguredEvent) {}
+
+        fn process_event(&mut self, _: Event) -> CodexStatus {
+            CodexStatus::Running
+        }
+    }
+
+    #[test]
+    fn renders_annotations_as_workflow_commands() {
+        assert_eq!(
+            Annotation::new(AnnotationLevel::Error, "stream failed: 100%\nretrying").render(),
+            "::error::stream failed: 100%25%0Aretrying"
+        );
+        assert_eq!(
+            Annotation {
+                level: AnnotationLevel::Warning,
+                file: Some("src/lib.rs".to_string()),
+                lines: Some((10, 12)),
+                title: Some("[P1] Unchecked index, panics: on empty input".to_string()),
+                message: "Use `get`.".to_string(),
+            }
+            .render(),
+            "::warning file=src/lib.rs,line=10,endLine=12,title=[P1] Unchecked index%2C panics%3A on empty input::Use `get`."
+        );
+    }
+
+    #[test]
+    fn writes_multiline_step_outputs() {
+        assert_eq!(
+            step_outputs(&[
+                ("final-message", "Done.\nCODEX_OUTPUT_EOF".to_string()),
+                ("session-id", "abc".to_string()),
+            ]),
+            "final-message<<CODEX_OUTPUT_EOF_\nDone.\nCODEX_OUTPUT_EOF\nCODEX_OUTPUT_EOF_\nsession-id<<CODEX_OUTPUT_EOF\nabc\nCODEX_OUTPUT_EOF\n"
+        );
+    }
+
+    #[test]
+    fn sets_final_message_and_diff_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_file = dir.path().join("github_output");
+        let mut output = GitHubActionsOutput::new(
+            Box::new(Silent),
+            dir.path().to_path_buf(),
+            Some(output_file.clone()),
+            dir.path().to_path_buf(),
+        );
+        for msg in [
+            EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff: "--- a/x\n+++ b/x\n".to_string(),
+            }),
+            EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: Some("Bumped the version.".to_string()),
+            }),
+        ] {
+            output.process_event(Event {
+                id: "1".to_string(),
+                msg,
+            });
+        }
+        output.print_final_output();
+
+        let diff_path = dir.path().join("codex-run.patch");
+        assert_eq!(
+            std::fs::read_to_string(&output_file).unwrap(),
+            format!(
+                "final-message<<CODEX_OUTPUT_EOF\nBumped the version.\nCODEX_OUTPUT_EOF\ndiff-path<<CODEX_OUTPUT_EOF\n{}\nCODEX_OUTPUT_EOF\n",
+                diff_path.display()
+            )
+        );
+        assert_eq!(
+            std::fs::read_to_string(diff_path).unwrap(),
+            "--- a/x\n+++ b/x\n"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! `codex exec --from-issue URL`: attach a GitHub or GitLab issue to the
+//! prompt.
+//!
+//! The issue's title, body, and comments come from the forge's REST API,
+//! authenticated with `GITHUB_TOKEN` (or `GH_TOKEN`) and `GITLAB_TOKEN` when
+//! set, so public issues work without a token. `GITHUB_API_URL` points GitHub
+//! issues at another API, as it does in GitHub Actions; GitHub Enterprise and
+//! self-hosted GitLab issues use the API of the host in the URL.
+
+use anyhow::Context;
+use codex_core::default_client::create_client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+/// Comments fetched per issue; forges return at most this many per page.
+const MAX_COMMENTS: usize = 100;
+
+/// The prompt when `--from-issue` is given without one.
+pub(crate) const ISSUE_PROMPT: &str = "Resolve the issue below.";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Forge {
+    GitHub,
+    GitLab,
+}
+
+/// Where an issue lives, parsed from its web URL.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IssueLocation {
+    forge: Forge,
+    /// `https://host`, without a trailing slash.
+    origin: String,
+    /// `org/repo` on GitHub, `group/subgroup/project` on GitLab.
+    project: String,
+    number: u64,
+}
+
+impl IssueLocation {
+    /// The short reference forges link in commit messages, like `org/repo#123`.
+    pub(crate) fn reference(&self) -> String {
+        format!("{}#{}", self.project, self.number)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Issue {
+    pub reference: String,
+    pub url: String,
+    pub title: String,
+    pub body: String,
+    pub comments: Vec<IssueComment>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct IssueComment {
+    pub author: String,
+    pub body: String,
+}
+
+/// Parses `https://github.com/org/repo/issues/123` or
+/// `https://gitlab.com/group/project/-/issues/123`.
+pub(crate) fn parse_issue_url(url: &str) -> anyhow::Result<IssueLocation> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "{url} is not an issue URL; expected https://github.com/ORG/REPO/issues/N or https://gitlab.com/GROUP/PROJECT/-/issues/N"
+        )
+    };
+    let (scheme, rest) = url.split_once("://").ok_or_else(invalid)?;
+    if scheme != "https" && scheme != "http" {
+        return Err(invalid());
+    }
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_once('/').ok_or_else(invalid)?;
+    let path = path.trim_end_matches('/');
+    let origin = format!("{scheme}://{host}");
+
+    let (forge, project, number) = if let Some((project, number)) = path.split_once("/-/issues/") {
+        (Forge::GitLab, project, number)
+    } else {
+        let (project, number) = path.rsplit_once("/issues/").ok_or_else(invalid)?;
+        if project.split('/').count() != 2 {
+            return Err(invalid());
+        }
+        (Forge::GitHub, project, number)
+    };
+    let number = number.parse().map_err(|_| invalid())?;
+    if host.is_empty() || project.split('/').any(str::is_empty) {
+        return Err(invalid());
+    }
+    Ok(IssueLocation {
+        forge,
+        origin,
+        project: project.to_string(),
+        number,
+    })
+}
+
+pub(crate) async fn fetch_issue(location: &IssueLocation) -> anyhow::Result<Issue> {
+    let reference = location.reference();
+    let issue = match location.forge {
+        Forge::GitHub => fetch_github_issue(location).await,
+        Forge::GitLab => fetch_gitlab_issue(location).await,
+    };
+    issue.with_context(|| format!("failed to fetch issue {reference}"))
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubIssue {
+    title: String,
+    body: Option<String>,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubComment {
+    user: Option<GitHubUser>,
+    body: Option<String>,
+}
+
+async fn fetch_github_issue(location: &IssueLocation) -> anyhow::Result<Issue> {
+    let api = match std::env::var("GITHUB_API_URL") {
+        Ok(api) if !api.is_empty() => api.trim_end_matches('/').to_string(),
+        _ if location.origin.ends_with("://github.com") => "https://api.github.com".to_string(),
+        _ => format!("{}/api/v3", location.origin),
+    };
+    let token = ["GITHUB_TOKEN", "GH_TOKEN"]
+        .into_iter()
+        .find_map(|name| std::env::var(name).ok().filter(|token| !token.is_empty()));
+    let auth = token.map(|token| ("Authorization", format!("Bearer {token}")));
+    let base = format!(
+        "{api}/repos/{}/issues/{}",
+        location.project, location.number
+    );
+
+    let issue: GitHubIssue = get_json(&base, auth.clone()).await?;
+    let comments: Vec<GitHubComment> =
+        get_json(&format!("{base}/comments?per_page={MAX_COMMENTS}"), auth).await?;
+    Ok(Issue {
+        reference: location.reference(),
+        url: issue.html_url,
+        title: issue.title,
+        body: issue.body.unwrap_or_default(),
+        comments: comments
+            .into_iter()
+            .map(|comment| IssueComment {
+                author: comment
+                    .user
+                    .map(|user| user.login)
+                    .unwrap_or_else(|| "ghost".to_string()),
+                body: comment.body.unwrap_or_default(),
+            })
+            .collect(),
+    })
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabIssue {
+    title: String,
+    description: Option<String>,
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabNote {
+    author: GitLabUser,
+    body: String,
+    /// Notes GitLab writes itself, such as label changes.
+    #[serde(default)]
+    system: bool,
+}
+
+async fn fetch_gitlab_issue(location: &IssueLocation) -> anyhow::Result<Issue> {
+    let auth = std::env::var("GITLAB_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+        .map(|token| ("PRIVATE-TOKEN", token));
+    let base = format!(
+        "{}/api/v4/projects/{}/issues/{}",
+        location.origin,
+        location.project.replace('/', "%2F"),
+        location.number
+    );
+
+    let issue: GitLabIssue = get_json(&base, auth.clone()).await?;
+    let notes: Vec<GitLabNote> = get_json(
+        &format!("{base}/notes?sort=asc&per_page={MAX_COMMENTS}"),
+        auth,
+    )
+    .await?;
+    Ok(Issue {
+        reference: location.reference(),
+        url: issue.web_url,
+        title: issue.title,
+        body: issue.description.unwrap_or_default(),
+        comments: notes
+            .into_iter()
+            .filter(|note| !note.system)
+            .map(|note| IssueComment {
+                author: note.author.username,
+                body: note.body,
+            })
+            .collect(),
+    })
+}
+
+async fn get_json<T: DeserializeOwned>(
+    url: &str,
+    auth: Option<(&'static str, String)>,
+) -> anyhow::Result<T> {
+    let mut request = create_client()
+        .get(url)
+        .header("Accept", "application/json");
+    if let Some((header, value)) = auth {
+        request = request.header(header, value);
+    }
+    let response = request.send().await.context("request failed")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("{url} returned {status}: {}", body.trim());
+    }
+    response
+        .json()
+        .await
+        .with_context(|| format!("{url} returned an unexpected response"))
+}
+
+impl Issue {
+    /// The session title: the reference and the issue's title.
+    pub(crate) fn session_title(&self) -> String {
+        format!("{}: {}", self.reference, self.title)
+    }
+
+    /// The issue as a context block for the prompt, ending with a request to
+    /// reference it in commit messages.
+    pub(crate) fn context(&self) -> String {
+        let mut text = format!(
+            "<issue reference=\"{}\" url=\"{}\">\n# {}\n",
+            self.reference, self.url, self.title
+        );
+        let body = self.body.trim();
+        if !body.is_empty() {
+            text.push_str(&format!("\n{body}\n"));
+        }
+        for comment in &self.comments {
+            text.push_str(&format!(
+                "\n## Comment by @{}\n\n{}\n",
+                comment.author,
+                comment.body.trim()
+            ));
+        }
+        text.push_str(&format!(
+            "</issue>\n\nIf you commit changes for this issue, reference {} in each commit message, for example with a `Refs: {}` trailer.",
+            self.reference, self.reference
+        ));
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_github_and_gitlab_issue_urls() {
+        assert_eq!(
+            parse_issue_url("https://github.com/org/repo/issues/123#issuecomment-1").unwrap(),
+            IssueLocation {
+                forge: Forge::GitHub,
+                origin: "https://github.com".to_string(),
+                project: "org/repo".to_string(),
+                number: 123,
+            }
+        );
+        assert_eq!(
+            parse_issue_url("https://gitlab.example.com/group/sub/project/-/issues/7/").unwrap(),
+            IssueLocation {
+                forge: Forge::GitLab,
+                origin: "https://gitlab.example.com".to_string(),
+                project: "group/sub/project".to_string(),
+                number: 7,
+            }
+        );
+        assert!(parse_issue_url("https://github.com/org/repo/pull/5").is_err());
+        assert!(parse_issue_url("https://github.com/org/repo/issues/new").is_err());
+        assert!(parse_issue_url("github.com/org/repo/issues/5").is_err());
+    }
+
+    #[test]
+    fn renders_the_issue_as_context() {
+        let issue = Issue {
+            reference: "org/repo#123".to_string(),
+            url: "https://github.com/org/repo/issues/123".to_string(),
+            title: "Login fails on Safari".to_string(),
+            body: "Steps to reproduce...\n".to_string(),
+            comments: vec![IssueComment {
+                author: "alice".to_string(),
+                body: "Also on iOS.".to_string(),
+            }],
+        };
+
+        assert_eq!(issue.session_title(), "org/repo#123: Login fails on Safari");
+        assert_eq!(
+            issue.context(),
+            "<issue reference=\"org/repo#123\" url=\"https://github.com/org/repo/issues/123\">\n# Login fails on Safari\n\nSteps to reproduce...\n\n## Comment by @alice\n\nAlso on iOS.\n</issue>\n\nIf you commit changes for this issue, reference org/repo#123 in each commit message, for example with a `Refs: org/repo#123` trailer."
+        );
+    }
+}
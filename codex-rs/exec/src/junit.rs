@@ -0,0 +1,123 @@
+//! JUnit XML for `--output-format junit`, the report format CI test reporters
+//! read. A run, a batch task, or a review finding becomes a test case that
+//! fails when the run did not succeed or the finding stands.
+
+pub(crate) struct TestCase {
+    pub name: String,
+    pub classname: String,
+    pub time_seconds: f64,
+    pub failure: Option<Failure>,
+    /// Shown by most reporters next to the case, e.g. the final message.
+    pub system_out: Option<String>,
+}
+
+pub(crate) struct Failure {
+    /// Short machine-readable kind, such as `error` or `max_turns_exceeded`.
+    pub kind: String,
+    pub message: String,
+    pub body: String,
+}
+
+pub(crate) fn render_junit(suite: &str, cases: &[TestCase]) -> String {
+    let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+    let time: f64 = cases.iter().map(|case| case.time_seconds).sum();
+    let counts = format!(
+        "tests=\"{}\" failures=\"{failures}\" errors=\"0\" time=\"{time:.3}\"",
+        cases.len()
+    );
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<testsuites name=\"codex\" {counts}>\n"));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" {counts}>\n",
+        escape(suite)
+    ));
+    for case in cases {
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            escape(&case.name),
+            escape(&case.classname),
+            case.time_seconds
+        ));
+        if case.failure.is_none() && case.system_out.is_none() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+        if let Some(failure) = &case.failure {
+            xml.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                escape(&failure.kind),
+                escape(&failure.message),
+                escape(&failure.body)
+            ));
+        }
+        if let Some(out) = &case.system_out {
+            xml.push_str(&format!("      <system-out>{}</system-out>\n", escape(out)));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>");
+    xml
+}
+
+/// Escapes text for an attribute or element, dropping characters XML 1.0
+/// cannot carry.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch.is_control() => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_passing_and_failing_cases() {
+        let cases = [
+            TestCase {
+                name: "bump-deps".to_string(),
+                classname: "codex.batch".to_string(),
+                time_seconds: 1.5,
+                failure: None,
+                system_out: None,
+            },
+            TestCase {
+                name: "fix <lint>".to_string(),
+                classname: "codex.batch".to_string(),
+                time_seconds: 2.25,
+                failure: Some(Failure {
+                    kind: "error".to_string(),
+                    message: "stream \"disconnected\"".to_string(),
+                    body: "a & b\u{1b}".to_string(),
+                }),
+                system_out: Some("partial".to_string()),
+            },
+        ];
+        assert_eq!(
+            render_junit("codex-batch", &cases),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="codex" tests="2" failures="1" errors="0" time="3.750">
+  <testsuite name="codex-batch" tests="2" failures="1" errors="0" time="3.750">
+    <testcase name="bump-deps" classname="codex.batch" time="1.500"/>
+    <testcase name="fix &lt;lint&gt;" classname="codex.batch" time="2.250">
+      <failure type="error" message="stream &quot;disconnected&quot;">a &amp; b</failure>
+      <system-out>partial</system-out>
+    </testcase>
+  </testsuite>
+</testsuites>"#
+        );
+    }
+}
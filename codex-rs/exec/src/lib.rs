@@ -4,15 +4,34 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod approval_rules;
+mod batch;
+mod checkpoint;
 mod cli;
+mod clipboard;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod exit_codes;
+mod github_actions;
+mod issue;
+mod junit;
+mod plan;
+mod prompt_template;
+mod review_output;
+mod run_artifacts;
+mod seed_session;
+mod stdin_context;
+mod watch;
 
+pub use approval_rules::ApprovalRule;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+use codex_common::clipboard_capture::CLIPBOARD_ARTIFACTS_SUBDIR;
+use codex_common::clipboard_capture::ClipboardCapture;
+use codex_common::clipboard_capture::capture_clipboard;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
 use codex_core::AuthManager;
@@ -20,29 +39,38 @@ use codex_core::ConversationManager;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
 use codex_core::NewConversation;
 use codex_core::OLLAMA_OSS_PROVIDER_ID;
+use codex_core::artifacts::session_artifacts_dir;
 use codex_core::auth::enforce_login_restrictions;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::types::RunLimits;
 use codex_core::git_info::get_git_repo_root;
+use codex_core::plan_mode::plan_output_schema;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::TurnCapabilityGrant;
+use codex_core::secrets::RedactingWriter;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
+use event_processor_with_human_output::Verbosity;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+pub use review_output::ReviewFormat;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
 use std::path::PathBuf;
+use std::time::Instant;
 use supports_color::Stream;
 use tracing::debug;
 use tracing::error;
@@ -50,9 +78,44 @@ use tracing::info;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
+use crate::approval_rules::ApprovalRules;
+use crate::approval_rules::Verdict;
+use crate::checkpoint::CONTINUE_PROMPT;
+use crate::checkpoint::Checkpoint;
+use crate::checkpoint::CheckpointState;
+use crate::checkpoint::CheckpointStatus;
+use crate::checkpoint::ROLLOUT_FILE;
+use crate::checkpoint::load_checkpoint;
+use crate::checkpoint::resolve_checkpoint_dir;
+use crate::cli::CiProvider;
 use crate::cli::Command as ExecCommand;
+use crate::cli::OutputFormat;
+use crate::cli::PlanFormat;
+use crate::clipboard::clipboard_text_note;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::exit_codes::PROVIDER_ERROR_EXIT_CODE;
+use crate::exit_codes::RunOutcome;
+use crate::exit_codes::SUCCESS_EXIT_CODE;
+use crate::exit_codes::render_exit_codes;
+use crate::github_actions::GitHubActionsOutput;
+use crate::issue::ISSUE_PROMPT;
+use crate::issue::fetch_issue;
+use crate::issue::parse_issue_url;
+use crate::junit::TestCase;
+use crate::junit::render_junit;
+use crate::plan::load_plan;
+use crate::plan::render_plan;
+use crate::prompt_template::render_prompt_template;
+use crate::review_output::render_review;
+use crate::run_artifacts::DELIVERABLES_NOTE;
+use crate::run_artifacts::RunArtifacts;
+use crate::run_artifacts::RunReport;
+use crate::seed_session::compact_seed;
+use crate::seed_session::load_seed_history;
+use crate::seed_session::resolve_seed_path;
+use crate::stdin_context::MAX_STDIN_CONTEXT_BYTES;
+use crate::stdin_context::read_stdin_context;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_conversation_path_by_id_str;
 
@@ -71,27 +134,130 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
     }
 
+    if cli.print_exit_codes {
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", render_exit_codes(cli.json));
+        }
+        return Ok(());
+    }
+
+    if let Some(file) = cli.batch.clone() {
+        if cli.command.is_some() {
+            anyhow::bail!("--batch cannot be combined with a subcommand");
+        }
+        return batch::run_batch(cli, file).await;
+    }
+
+    if !cli.watch.is_empty() {
+        if cli.command.is_some() {
+            anyhow::bail!("--watch cannot be combined with a subcommand");
+        }
+        return watch::run_watch(cli).await;
+    }
+
     let Cli {
         command,
         images,
+        from_clipboard,
+        from_issue,
         model: model_cli_arg,
         oss,
         oss_provider,
         config_profile,
         full_auto,
         dangerously_bypass_approvals_and_sandbox,
+        approve,
+        deny,
+        max_turns,
+        max_duration,
+        max_cost_usd,
+        tool_timeout,
+        tool_retries,
         cwd,
         skip_git_repo_check,
         add_dir,
+        repos,
         color,
         last_message_file,
         json: json_mode,
+        quiet,
+        verbose,
+        print_exit_codes: _,
+        output_format,
+        ci,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
-        config_overrides,
+        mcp_servers,
+        mut config_overrides,
+        no_stdin,
+        batch: _,
+        parallel: _,
+        batch_output: _,
+        checkpoint_dir,
+        artifacts_dir,
+        resume_checkpoint,
+        seed_session,
+        seed_compact,
+        prompt_file,
+        template_args,
+        plan_only,
+        plan_format,
+        from_plan,
+        watch: _,
+        watch_cooldown: _,
     } = cli;
 
+    let review_format = match &command {
+        Some(ExecCommand::Review(args)) => match output_format {
+            Some(format) if args.format == ReviewFormat::Text => format.into(),
+            _ => args.format,
+        },
+        _ => ReviewFormat::Text,
+    };
+    if json_mode && review_format != ReviewFormat::Text {
+        anyhow::bail!("--format cannot be combined with --json");
+    }
+    if resume_checkpoint.is_some() && command.is_some() {
+        anyhow::bail!("--resume cannot be combined with a subcommand");
+    }
+    if seed_session.is_some() && command.is_some() {
+        anyhow::bail!("--seed-session cannot be combined with a subcommand");
+    }
+    if from_issue.is_some() && matches!(command, Some(ExecCommand::Review(_))) {
+        anyhow::bail!("--from-issue cannot be combined with `codex exec review`");
+    }
+    let issue_location = from_issue.as_deref().map(parse_issue_url).transpose()?;
+    let templated = prompt_file.is_some() || !template_args.is_empty();
+    if templated && matches!(command, Some(ExecCommand::Review(_))) {
+        anyhow::bail!("--prompt-file and --arg cannot be combined with `codex exec review`");
+    }
+    if (plan_only || from_plan.is_some()) && matches!(command, Some(ExecCommand::Review(_))) {
+        anyhow::bail!("--plan-only and --from-plan cannot be combined with `codex exec review`");
+    }
+    let prompt = match prompt_file {
+        Some(path) => Some(std::fs::read_to_string(&path).map_err(|err| {
+            anyhow::anyhow!("failed to read prompt file {}: {err}", path.display())
+        })?),
+        None => prompt,
+    };
+    // With `--from-plan`, PROMPT becomes extra instructions after the plan.
+    let prompt = match from_plan {
+        Some(path) => Some(load_plan(&path)?.execution_prompt(prompt.as_deref())),
+        None => prompt,
+    };
+    // Outside a review, `--output-format junit` reports the run itself.
+    let report_run = match (&command, output_format) {
+        (Some(ExecCommand::Review(_)), _) | (_, None) => false,
+        (_, Some(OutputFormat::Junit)) => true,
+        (_, Some(OutputFormat::Sarif)) => {
+            anyhow::bail!(
+                "--output-format sarif needs review findings; use `codex review --format sarif`"
+            );
+        }
+    };
+
     let (stdout_with_ansi, stderr_with_ansi) = match color {
         cli::Color::Always => (true, true),
         cli::Color::Never => (false, false),
@@ -111,10 +277,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     let fmt_layer = tracing_subscriber::fmt::layer()
         .with_ansi(stderr_with_ansi)
-        .with_writer(std::io::stderr)
+        .with_writer(|| RedactingWriter::new(std::io::stderr()))
         .with_filter(env_filter);
 
-    let sandbox_mode = if full_auto {
+    let sandbox_mode = if plan_only {
+        Some(SandboxMode::ReadOnly)
+    } else if full_auto {
         Some(SandboxMode::WorkspaceWrite)
     } else if dangerously_bypass_approvals_and_sandbox {
         Some(SandboxMode::DangerFullAccess)
@@ -122,8 +290,23 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         sandbox_mode_cli_arg.map(Into::<SandboxMode>::into)
     };
 
+    // `--tool-timeout` and `--tool-retries` are shorthands for
+    // `[tool_timeouts]` keys and win over `-c`.
+    if let Some(timeout) = tool_timeout {
+        let ms = timeout.as_millis();
+        config_overrides.raw_overrides.extend([
+            format!("tool_timeouts.shell_ms={ms}"),
+            format!("tool_timeouts.shell_max_ms={ms}"),
+        ]);
+    }
+    if let Some(retries) = tool_retries {
+        config_overrides
+            .raw_overrides
+            .push(format!("tool_timeouts.shell_retries={retries}"));
+    }
+
     // Parse `-c` overrides from the CLI.
-    let cli_kv_overrides = match config_overrides.parse_overrides() {
+    let mut cli_kv_overrides = match config_overrides.parse_overrides() {
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
         Err(e) => {
@@ -132,6 +315,19 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
+    // Servers from `--mcp` join `mcp_servers` like `-c` overrides, so they
+    // last for this run only.
+    for spec in &mcp_servers {
+        match codex_core::mcp::session_mcp_server_overrides(spec) {
+            Ok(overrides) => cli_kv_overrides.extend(overrides),
+            #[allow(clippy::print_stderr)]
+            Err(e) => {
+                eprintln!("Error parsing --mcp: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     // we load config.toml here to determine project state.
     #[allow(clippy::print_stderr)]
     let config_toml = {
@@ -182,13 +378,27 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         None // No model specified, will use the default.
     };
 
+    // With `--repo`, the first repository is the working directory unless
+    // `--cd` names one; the others become extra workspace roots.
+    let current_dir = std::env::current_dir()?;
+    let mut repos = repos.into_iter().map(|repo| current_dir.join(repo));
+    let cwd = cwd.or_else(|| repos.next());
+    let workspace_roots: Vec<PathBuf> = repos.collect();
+
     // Load configuration and determine approval policy
+    let approval_rules = ApprovalRules::new(approve, deny);
     let overrides = ConfigOverrides {
         model,
         review_model: None,
         config_profile,
-        // Default to never ask for approvals in headless mode. Feature flags can override.
-        approval_policy: Some(AskForApproval::Never),
+        // Default to never ask for approvals in headless mode; with
+        // `--approve`/`--deny`, ask and let the rules answer. Feature flags
+        // can override.
+        approval_policy: Some(if approval_rules.is_empty() {
+            AskForApproval::Never
+        } else {
+            AskForApproval::UnlessTrusted
+        }),
         sandbox_mode,
         cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
         model_provider: model_provider.clone(),
@@ -200,14 +410,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         show_raw_agent_reasoning: oss.then_some(true),
         tools_web_search_request: None,
         additional_writable_roots: add_dir,
+        workspace_roots,
+        load_patch_policy: true,
+        run_limits: RunLimits {
+            max_turns,
+            max_duration,
+            max_cost_usd,
+        },
     };
 
     let config =
         Config::load_with_cli_overrides_and_harness_overrides(cli_kv_overrides, overrides).await?;
+    codex_core::default_client::set_network_config(config.network.clone());
 
     if let Err(err) = enforce_login_restrictions(&config).await {
         eprintln!("{err}");
-        std::process::exit(1);
+        std::process::exit(PROVIDER_ERROR_EXIT_CODE);
     }
 
     let otel = codex_core::otel_init::build_provider(&config, env!("CARGO_PKG_VERSION"));
@@ -231,14 +449,28 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .with(otel_logger_layer)
         .try_init();
 
+    let verbosity = Verbosity::from_flags(quiet, verbose);
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
         true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
         _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stdout_with_ansi,
             &config,
             last_message_file.clone(),
+            review_format == ReviewFormat::Text && !report_run && !plan_only,
+            verbosity,
         )),
     };
+    if let Some(CiProvider::Github) = ci {
+        let repo_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.to_path_buf());
+        event_processor = Box::new(GitHubActionsOutput::new(
+            event_processor,
+            repo_root,
+            std::env::var_os("GITHUB_OUTPUT").map(PathBuf::from),
+            std::env::var_os("RUNNER_TEMP")
+                .map(PathBuf::from)
+                .unwrap_or_else(std::env::temp_dir),
+        ));
+    }
 
     if oss {
         // We're in the oss section, so provider_id should be Some
@@ -263,7 +495,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     let default_effort = config.model_reasoning_effort;
     let default_summary = config.model_reasoning_summary;
 
-    if !skip_git_repo_check && get_git_repo_root(&default_cwd).is_none() {
+    if !skip_git_repo_check
+        && std::iter::once(&default_cwd)
+            .chain(&config.workspace_roots)
+            .any(|dir| get_git_repo_root(dir).is_none())
+    {
         eprintln!("Not inside a trusted directory and --skip-git-repo-check was not specified.");
         std::process::exit(1);
     }
@@ -278,10 +514,42 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .get_models_manager()
         .get_model(&config.model, &config)
         .await;
+    if max_cost_usd.is_some() && !config.model_prices.contains_key(&default_model) {
+        eprintln!(
+            "--max-cost-usd needs a price for {default_model}: add [model_prices.\"{default_model}\"] to config.toml."
+        );
+        std::process::exit(1);
+    }
+
+    let issue = match &issue_location {
+        Some(location) => Some(fetch_issue(location).await?),
+        None => None,
+    };
+
+    let resumed_checkpoint = match resume_checkpoint.as_deref() {
+        Some(arg) => {
+            let dir = resolve_checkpoint_dir(arg, &config.cwd)?;
+            let state = load_checkpoint(&dir)?;
+            if state.status == CheckpointStatus::Completed && prompt.is_none() {
+                eprintln!(
+                    "The run in {} already completed; pass a prompt to continue the session.",
+                    dir.display()
+                );
+                return Ok(());
+            }
+            Some((dir, state))
+        }
+        None => None,
+    };
+
+    let seed_path = match seed_session.as_deref() {
+        Some(arg) => Some(resolve_seed_path(arg, &config.cwd, &config.codex_home).await?),
+        None => None,
+    };
 
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewConversation {
-        conversation_id: _,
+        conversation_id,
         conversation,
         session_configured,
     } = if let Some(ExecCommand::Resume(args)) = command.as_ref() {
@@ -296,12 +564,65 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 .new_conversation(config.clone())
                 .await?
         }
+    } else if let Some((dir, _)) = &resumed_checkpoint {
+        conversation_manager
+            .resume_conversation_from_rollout(
+                config.clone(),
+                dir.join(ROLLOUT_FILE),
+                auth_manager.clone(),
+            )
+            .await?
+    } else if let Some(seed) = &seed_path {
+        conversation_manager
+            .resume_conversation_with_history(
+                config.clone(),
+                load_seed_history(seed).await?,
+                auth_manager.clone(),
+            )
+            .await?
     } else {
         conversation_manager
             .new_conversation(config.clone())
             .await?
     };
-    let (initial_operation, prompt_summary) = match (command, prompt, images) {
+    let mut images = images;
+    let mut clipboard_note = None;
+    if from_clipboard {
+        if matches!(command, Some(ExecCommand::Review(_))) {
+            anyhow::bail!("--from-clipboard cannot be combined with `codex exec review`");
+        }
+        let dir = session_artifacts_dir(&config.codex_home, conversation_id)
+            .join(CLIPBOARD_ARTIFACTS_SUBDIR);
+        match capture_clipboard(&dir)? {
+            ClipboardCapture::Image { path, .. } => images.push(path),
+            ClipboardCapture::Text { path, .. } => {
+                clipboard_note = Some(clipboard_text_note(&path));
+            }
+        }
+    }
+
+    let prompt = match (&resumed_checkpoint, &issue) {
+        (Some(_), _) => prompt.or_else(|| Some(CONTINUE_PROMPT.to_string())),
+        (None, Some(_)) => prompt.or_else(|| Some(ISSUE_PROMPT.to_string())),
+        (None, None) => prompt,
+    };
+    let render_prompt = |text: String| -> anyhow::Result<String> {
+        if !templated {
+            return Ok(text);
+        }
+        let rendered =
+            render_prompt_template(&text, &template_args, |name| std::env::var(name).ok())?;
+        for name in rendered.unused {
+            eprintln!("--arg {name} is not used by the prompt.");
+        }
+        Ok(rendered.text)
+    };
+    let output_schema = if plan_only {
+        Some(plan_output_schema())
+    } else {
+        load_output_schema(output_schema_path)
+    };
+    let (mut initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
@@ -319,7 +640,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                     }
                 })
                 .or(root_prompt);
-            let prompt_text = resolve_prompt(prompt_arg);
+            let stdin_context = piped_stdin_context(prompt_arg.as_deref(), no_stdin);
+            let prompt_text = render_prompt(resolve_prompt(prompt_arg))?;
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
@@ -327,7 +649,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             items.push(UserInput::Text {
                 text: prompt_text.clone(),
             });
-            let output_schema = load_output_schema(output_schema_path.clone());
+            items.extend(stdin_context.map(|text| UserInput::Text { text }));
             (
                 InitialOperation::UserTurn {
                     items,
@@ -337,7 +659,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             )
         }
         (None, root_prompt, imgs) => {
-            let prompt_text = resolve_prompt(root_prompt);
+            let stdin_context = piped_stdin_context(root_prompt.as_deref(), no_stdin);
+            let prompt_text = render_prompt(resolve_prompt(root_prompt))?;
             let mut items: Vec<UserInput> = imgs
                 .into_iter()
                 .map(|path| UserInput::LocalImage { path })
@@ -345,7 +668,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             items.push(UserInput::Text {
                 text: prompt_text.clone(),
             });
-            let output_schema = load_output_schema(output_schema_path);
+            items.extend(stdin_context.map(|text| UserInput::Text { text }));
             (
                 InitialOperation::UserTurn {
                     items,
@@ -356,10 +679,62 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
+    if let InitialOperation::UserTurn { items, .. } = &mut initial_operation {
+        items.extend(clipboard_note.map(|text| UserInput::Text { text }));
+        items.extend(issue.as_ref().map(|issue| UserInput::Text {
+            text: issue.context(),
+        }));
+        if artifacts_dir.is_some() {
+            items.push(UserInput::Text {
+                text: DELIVERABLES_NOTE.to_string(),
+            });
+        }
+    }
+
     // Print the effective configuration and initial request so users can see what Codex
     // is using.
     event_processor.print_config_summary(&config, &prompt_summary, &session_configured);
 
+    let checkpoint_dir = checkpoint_dir.map(|dir| config.cwd.join(dir));
+    let checkpoint = match (checkpoint_dir, resumed_checkpoint) {
+        (dir, Some((resumed_dir, state))) => Some(Checkpoint::start(
+            dir.unwrap_or(resumed_dir),
+            conversation.rollout_path(),
+            CheckpointState {
+                status: CheckpointStatus::Running,
+                ..state
+            },
+        )?),
+        (Some(dir), None) => Some(Checkpoint::start(
+            dir,
+            conversation.rollout_path(),
+            CheckpointState {
+                session_id: conversation_id.to_string(),
+                prompt: prompt_summary.clone(),
+                status: CheckpointStatus::Running,
+            },
+        )?),
+        (None, None) => None,
+    };
+    if let Some(checkpoint) = &checkpoint
+        && verbosity > Verbosity::Quiet
+    {
+        eprintln!(
+            "Checkpointing to {}; continue an interrupted run with `codex exec --resume {}`.",
+            checkpoint.dir().display(),
+            checkpoint.dir().display()
+        );
+    }
+
+    if let Some(seed) = &seed_path {
+        if verbosity > Verbosity::Quiet {
+            eprintln!("Seeded the session with {}.", seed.display());
+        }
+        if seed_compact {
+            compact_seed(&conversation).await?;
+        }
+    }
+
     info!("Codex initialized with event: {session_configured:?}");
 
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Event>();
@@ -401,6 +776,35 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
+    if approval_rules.grants_network() {
+        conversation
+            .submit(Op::GrantTurnCapability {
+                grant: TurnCapabilityGrant::Network,
+            })
+            .await?;
+    }
+
+    if plan_only {
+        conversation
+            .submit(Op::SetPlanMode { enabled: true })
+            .await?;
+    }
+
+    if let Some(issue) = &issue {
+        conversation
+            .submit(Op::SetSessionTitle {
+                title: issue.session_title(),
+            })
+            .await?;
+        if verbosity > Verbosity::Quiet {
+            eprintln!(
+                "Attached {} with {} comment(s).",
+                issue.reference,
+                issue.comments.len()
+            );
+        }
+    }
+
     match initial_operation {
         InitialOperation::UserTurn {
             items,
@@ -429,10 +833,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     };
 
     // Run the loop until the task is complete.
-    // Track whether a fatal error was reported by the server so we can
-    // exit with a non-zero status for automation-friendly signaling.
-    let mut error_seen = false;
+    // Track how the run ends so we can exit with a status that says why
+    // for automation-friendly signaling.
+    let mut outcome = RunOutcome::default();
+    let mut artifacts = RunArtifacts::default();
+    let mut review_output = None;
+    let mut final_message = None;
+    let mut aborted = false;
+    let started = Instant::now();
     while let Some(event) = rx.recv().await {
+        if let Some((op, verdict)) = answer_approval(&approval_rules, &event, &config.cwd) {
+            conversation.submit(op).await?;
+            outcome.record_verdict(&verdict);
+            if matches!(verdict, Verdict::Deny(_)) {
+                conversation.submit(Op::Shutdown).await?;
+            }
+        }
         if let EventMsg::ElicitationRequest(ev) = &event.msg {
             // Automatically cancel elicitation requests in exec mode.
             conversation
@@ -443,8 +859,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
+        outcome.record_event(&event.msg);
+        artifacts.record_event(&event.msg);
+        if let EventMsg::TaskComplete(ev) = &event.msg {
+            final_message = ev.last_agent_message.clone();
+        }
+        if matches!(event.msg, EventMsg::TurnAborted(_)) {
+            aborted = true;
+        }
+        if matches!(event.msg, EventMsg::TokenCount(_))
+            && let Some(checkpoint) = &checkpoint
+            && let Err(err) = checkpoint.save()
+        {
+            error!("Failed to save checkpoint: {err:#}");
+        }
+        if let EventMsg::ExitedReviewMode(ev) = &event.msg {
+            review_output = ev.review_output.clone();
         }
         let shutdown: CodexStatus = event_processor.process_event(event);
         match shutdown {
@@ -458,13 +888,121 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     }
     event_processor.print_final_output();
-    if error_seen {
-        std::process::exit(1);
+    if let Some(mut checkpoint) = checkpoint {
+        let status = if !outcome.stopped_early() && !aborted {
+            CheckpointStatus::Completed
+        } else {
+            CheckpointStatus::Incomplete
+        };
+        if let Err(err) = checkpoint.finish(status) {
+            eprintln!(
+                "Failed to save the checkpoint in {}: {err:#}",
+                checkpoint.dir().display()
+            );
+        }
+    }
+    if let Some(output) = review_output {
+        let repo_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.to_path_buf());
+        if let Some(rendered) = render_review(review_format, &output, &repo_root) {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{rendered}");
+            }
+        }
+    }
+    // The JSONL stream already carries the plan in its final agent message.
+    if plan_only
+        && !json_mode
+        && let Some(message) = &final_message
+    {
+        let rendered = render_plan(plan_format, message).unwrap_or_else(|err| {
+            eprintln!("{err:#}");
+            message.clone()
+        });
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", rendered.trim_end());
+        }
+    }
+    if let Some(dir) = artifacts_dir {
+        let dir = config.cwd.join(dir);
+        let report = RunReport {
+            session_id: conversation_id.to_string(),
+            model: &session_configured.model,
+            price: config.model_prices.get(&session_configured.model),
+            exit_code: outcome.exit_code(),
+            duration: started.elapsed(),
+            final_message: final_message.as_deref(),
+            rollout: &conversation.rollout_path(),
+            cwd: &config.cwd,
+        };
+        match artifacts.write(&dir, &report).await {
+            Ok(()) if verbosity > Verbosity::Quiet => {
+                eprintln!("Saved the run's artifacts to {}.", dir.display());
+            }
+            Ok(()) => {}
+            Err(err) => eprintln!("Failed to save artifacts to {}: {err:#}", dir.display()),
+        }
+    }
+    if report_run {
+        let case = TestCase {
+            name: prompt_summary,
+            classname: "codex.exec".to_string(),
+            time_seconds: started.elapsed().as_secs_f64(),
+            failure: outcome.failure(),
+            system_out: final_message,
+        };
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", render_junit("codex-exec", &[case]));
+        }
+    }
+    let exit_code = outcome.exit_code();
+    if exit_code != SUCCESS_EXIT_CODE {
+        std::process::exit(exit_code);
     }
 
     Ok(())
 }
 
+/// The reply to an approval request under `--approve`/`--deny`, noted on
+/// stderr unless it was approved.
+fn answer_approval(
+    rules: &ApprovalRules,
+    event: &Event,
+    cwd: &std::path::Path,
+) -> Option<(Op, Verdict)> {
+    let (verdict, action) = match &event.msg {
+        EventMsg::ExecApprovalRequest(request) => (
+            rules.command(&request.command, &request.parsed_cmd),
+            format!("run `{}`", request.command.join(" ")),
+        ),
+        EventMsg::ApplyPatchApprovalRequest(request) => (
+            rules.patch(&request.changes, cwd),
+            format!("edit {} file(s)", request.changes.len()),
+        ),
+        _ => return None,
+    };
+    let decision = match verdict {
+        Verdict::Approve => ReviewDecision::Approved,
+        Verdict::Decline => {
+            eprintln!("Declined to {action}: no --approve rule allows it.");
+            ReviewDecision::Denied
+        }
+        Verdict::Deny(rule) => {
+            eprintln!("Stopping: the run needs to {action}, which --deny {rule} forbids.");
+            ReviewDecision::Abort
+        }
+    };
+    let id = event.id.clone();
+    let op = if matches!(event.msg, EventMsg::ExecApprovalRequest(_)) {
+        Op::ExecApproval { id, decision }
+    } else {
+        Op::PatchApproval { id, decision }
+    };
+    Some((op, verdict))
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
@@ -521,6 +1059,22 @@ fn load_output_schema(path: Option<PathBuf>) -> Option<Value> {
     }
 }
 
+/// Piped stdin as a context block, when the prompt itself came from the
+/// command line.
+fn piped_stdin_context(prompt_arg: Option<&str>, no_stdin: bool) -> Option<String> {
+    let prompt_on_command_line = prompt_arg.is_some_and(|prompt| prompt != "-");
+    if no_stdin || !prompt_on_command_line || std::io::stdin().is_terminal() {
+        return None;
+    }
+    match read_stdin_context(std::io::stdin().lock(), MAX_STDIN_CONTEXT_BYTES) {
+        Ok(context) => context,
+        Err(err) => {
+            eprintln!("Failed to read piped stdin: {err}");
+            None
+        }
+    }
+}
+
 fn resolve_prompt(prompt_arg: Option<String>) -> String {
     match prompt_arg {
         Some(p) if p != "-" => p,
@@ -593,6 +1147,7 @@ mod tests {
             commit: None,
             commit_title: None,
             prompt: None,
+            format: ReviewFormat::Text,
         })
         .expect("builds uncommitted review request");
 
@@ -612,6 +1167,7 @@ mod tests {
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
             prompt: None,
+            format: ReviewFormat::Text,
         })
         .expect("builds commit review request");
 
@@ -634,6 +1190,7 @@ mod tests {
             commit: None,
             commit_title: None,
             prompt: Some("  custom review instructions  ".to_string()),
+            format: ReviewFormat::Text,
         })
         .expect("builds custom review request");
 
@@ -0,0 +1,62 @@
+//! `--plan-only` output and `--from-plan` input.
+
+use std::path::Path;
+
+use anyhow::Context;
+use codex_core::plan_mode::PlanDocument;
+
+use crate::cli::PlanFormat;
+
+/// Reads a plan saved by `codex exec --plan-only -o FILE`.
+pub(crate) fn load_plan(path: &Path) -> anyhow::Result<PlanDocument> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read plan file {}", path.display()))?;
+    serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a plan saved by --plan-only", path.display()))
+}
+
+/// Renders the final message of a `--plan-only` run, which the output schema
+/// constrains to a [`PlanDocument`].
+pub(crate) fn render_plan(format: PlanFormat, message: &str) -> anyhow::Result<String> {
+    let plan: PlanDocument =
+        serde_json::from_str(message).context("the model's reply is not a valid plan")?;
+    Ok(match format {
+        PlanFormat::Markdown => plan.to_markdown(),
+        PlanFormat::Json => serde_json::to_string_pretty(&plan)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const PLAN: &str = r#"{"summary":"Bump the MSRV.","steps":[{"title":"Edit the manifest","details":"","files":["Cargo.toml"]}],"affected_files":["Cargo.toml"],"risks":["CI images may lag."]}"#;
+
+    #[test]
+    fn renders_plan_as_markdown() {
+        let rendered = render_plan(PlanFormat::Markdown, PLAN).expect("render");
+        assert_eq!(
+            rendered,
+            "# Plan\n\nBump the MSRV.\n\n## Steps\n\n\
+             1. **Edit the manifest**\n   Files: `Cargo.toml`\n\n\
+             ## Affected files\n\n- `Cargo.toml`\n\n\
+             ## Risks\n\n- CI images may lag.\n"
+        );
+    }
+
+    #[test]
+    fn rejects_replies_that_are_not_plans() {
+        assert!(render_plan(PlanFormat::Json, "Here is my plan: ...").is_err());
+    }
+
+    #[test]
+    fn loads_saved_plan() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("plan.json");
+        std::fs::write(&path, PLAN).expect("write");
+        let plan = load_plan(&path).expect("load");
+        assert_eq!(plan.affected_files, vec!["Cargo.toml".to_string()]);
+        assert!(load_plan(&dir.path().join("missing.json")).is_err());
+    }
+}
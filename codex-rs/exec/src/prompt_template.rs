@@ -0,0 +1,162 @@
+//! Prompt templates for `codex exec --prompt-file FILE --arg NAME=VALUE`.
+//!
+//! `{{NAME}}` is replaced with the value of `--arg NAME=...` and
+//! `{{env.NAME}}` with the environment variable `NAME`, so one prompt file can
+//! drive many parameterized runs. Whitespace inside the braces is ignored, and
+//! `\{{` stands for a literal `{{`. Braces around anything that is not a name
+//! are left as they are.
+
+use std::collections::HashSet;
+
+const ENV_PREFIX: &str = "env.";
+
+/// Parses `NAME=VALUE` for `--arg`.
+pub(crate) fn parse_template_arg(arg: &str) -> Result<(String, String), String> {
+    let Some((name, value)) = arg.split_once('=') else {
+        return Err(format!("expected NAME=VALUE, got `{arg}`"));
+    };
+    if !is_name(name) || name.contains('.') {
+        return Err(format!(
+            "`{name}` is not a valid name: use letters, digits, `_`, and `-`"
+        ));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RenderedPrompt {
+    pub text: String,
+    /// `--arg` names the template never mentions.
+    pub unused: Vec<String>,
+}
+
+/// Fills in the placeholders in `template`. Fails, naming every missing
+/// value, when a placeholder has no `--arg` or its variable is not set. A
+/// repeated `--arg` takes its last value.
+pub(crate) fn render_prompt_template(
+    template: &str,
+    args: &[(String, String)],
+    env: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<RenderedPrompt> {
+    let mut text = String::with_capacity(template.len());
+    let mut used = HashSet::new();
+    let mut missing: Vec<&str> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        if rest[..start].ends_with('\\') {
+            text.push_str(&rest[..start - 1]);
+            text.push_str("{{");
+            rest = after;
+            continue;
+        }
+        text.push_str(&rest[..start]);
+        let placeholder = after
+            .find("}}")
+            .map(|end| (after[..end].trim(), &after[end + 2..]))
+            .filter(|(name, _)| is_name(name));
+        let Some((name, remainder)) = placeholder else {
+            text.push_str("{{");
+            rest = after;
+            continue;
+        };
+        let value = match name.strip_prefix(ENV_PREFIX) {
+            Some(variable) => env(variable),
+            None => {
+                used.insert(name);
+                args.iter()
+                    .rev()
+                    .find(|(arg, _)| arg == name)
+                    .map(|(_, value)| value.clone())
+            }
+        };
+        match value {
+            Some(value) => text.push_str(&value),
+            None if !missing.contains(&name) => missing.push(name),
+            None => {}
+        }
+        rest = remainder;
+    }
+    text.push_str(rest);
+
+    if !missing.is_empty() {
+        let names = missing
+            .iter()
+            .map(|name| format!("{{{{{name}}}}}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!(
+            "the prompt needs values for {names}: pass `--arg NAME=VALUE`, or set the variable for `{{{{env.NAME}}}}`"
+        );
+    }
+    let mut unused: Vec<String> = Vec::new();
+    for (name, _) in args {
+        if !used.contains(name.as_str()) && !unused.contains(name) {
+            unused.push(name.clone());
+        }
+    }
+    Ok(RenderedPrompt { text, unused })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn args(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn env(name: &str) -> Option<String> {
+        (name == "DEPLOY_ENV").then(|| "staging".to_string())
+    }
+
+    #[test]
+    fn fills_args_and_environment_variables() {
+        let rendered = render_prompt_template(
+            "Check {{service}} in {{ env.DEPLOY_ENV }}; then {{service}} again. \\{{literal}} {{ not a name }}",
+            &args(&[("service", "auth"), ("service", "billing"), ("team", "core")]),
+            env,
+        )
+        .expect("rendered");
+        assert_eq!(
+            rendered,
+            RenderedPrompt {
+                text: "Check billing in staging; then billing again. {{literal}} {{ not a name }}"
+                    .to_string(),
+                unused: vec!["team".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn names_every_missing_value() {
+        let err = render_prompt_template("{{service}} {{env.REGION}} {{service}}", &[], env)
+            .expect_err("missing values");
+        assert_eq!(
+            err.to_string(),
+            "the prompt needs values for {{service}}, {{env.REGION}}: pass `--arg NAME=VALUE`, or set the variable for `{{env.NAME}}`"
+        );
+    }
+
+    #[test]
+    fn parses_name_value_pairs() {
+        assert_eq!(
+            parse_template_arg("env=prod=1"),
+            Ok(("env".to_string(), "prod=1".to_string()))
+        );
+        assert!(parse_template_arg("service").is_err());
+        assert!(parse_template_arg("env.HOME=/tmp").is_err());
+        assert!(parse_template_arg("two words=x").is_err());
+    }
+}
@@ -0,0 +1,384 @@
+//! Machine-readable review findings for `codex review --format`.
+//!
+//! Each finding carries its file (relative to the repository root), line
+//! range, severity, and the reviewer's suggestion, in a shape other tools can
+//! post: a Markdown report, a SARIF 2.1.0 log for code-scanning uploads, the
+//! `comments` array of GitHub's "create a review" API, or a JUnit report with
+//! one failing test case per finding.
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewOutputEvent;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::cli::OutputFormat;
+use crate::github_actions::Annotation;
+use crate::github_actions::AnnotationLevel;
+use crate::junit::Failure;
+use crate::junit::TestCase;
+use crate::junit::render_junit;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// The reviewer's own summary on stderr, as in an interactive review.
+    #[default]
+    Text,
+    Markdown,
+    Sarif,
+    GhComments,
+    Junit,
+}
+
+impl From<OutputFormat> for ReviewFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Sarif => Self::Sarif,
+            OutputFormat::Junit => Self::Junit,
+        }
+    }
+}
+
+/// Severity derived from the finding's `[P0]`–`[P3]` priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    fn from_priority(priority: i32) -> Self {
+        match priority {
+            0 => Self::Critical,
+            1 => Self::High,
+            2 => Self::Medium,
+            _ => Self::Low,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+
+    fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Critical | Self::High => "error",
+            Self::Medium => "warning",
+            Self::Low => "note",
+        }
+    }
+
+    fn annotation_level(self) -> AnnotationLevel {
+        match self {
+            Self::Critical | Self::High => AnnotationLevel::Error,
+            Self::Medium => AnnotationLevel::Warning,
+            Self::Low => AnnotationLevel::Notice,
+        }
+    }
+}
+
+/// Renders `output` for stdout. `None` for [`ReviewFormat::Text`], which
+/// leaves the output as it is.
+pub(crate) fn render_review(
+    format: ReviewFormat,
+    output: &ReviewOutputEvent,
+    repo_root: &Path,
+) -> Option<String> {
+    let rendered = match format {
+        ReviewFormat::Text => return None,
+        ReviewFormat::Markdown => markdown(output, repo_root),
+        ReviewFormat::Sarif => pretty(&sarif(output, repo_root)),
+        ReviewFormat::GhComments => pretty(&gh_comments(output, repo_root)),
+        ReviewFormat::Junit => junit(output, repo_root),
+    };
+    Some(rendered)
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// The finding's path relative to `repo_root`, with `/` separators. Paths
+/// outside the repository stay absolute.
+fn relative_path(finding: &ReviewFinding, repo_root: &Path) -> String {
+    let path = &finding.code_location.absolute_file_path;
+    match path.strip_prefix(repo_root) {
+        Ok(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+fn line_range(finding: &ReviewFinding) -> (u32, u32) {
+    let range = &finding.code_location.line_range;
+    let start = range.start.max(1);
+    (start, range.end.max(start))
+}
+
+fn markdown(output: &ReviewOutputEvent, repo_root: &Path) -> String {
+    let mut text = String::from("# Code review\n");
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        text.push_str(&format!("\n{explanation}\n"));
+    }
+    if output.findings.is_empty() {
+        text.push_str("\nNo findings.\n");
+        return text;
+    }
+    for finding in &output.findings {
+        let (start, end) = line_range(finding);
+        let lines = if start == end {
+            format!("{start}")
+        } else {
+            format!("{start}-{end}")
+        };
+        text.push_str(&format!(
+            "\n## {}\n\n`{}:{lines}` · severity: {}\n\n{}\n",
+            finding.title.trim(),
+            relative_path(finding, repo_root),
+            Severity::from_priority(finding.priority).as_str(),
+            finding.body.trim()
+        ));
+    }
+    text
+}
+
+fn sarif(output: &ReviewOutputEvent, repo_root: &Path) -> Value {
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let severity = Severity::from_priority(finding.priority);
+            json!({
+                "ruleId": "codex-review",
+                "level": severity.sarif_level(),
+                "message": {
+                    "text": format!("{}\n\n{}", finding.title.trim(), finding.body.trim()),
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_path(finding, repo_root) },
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "properties": {
+                    "severity": severity.as_str(),
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex-review",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": [{
+                        "id": "codex-review",
+                        "shortDescription": { "text": "Issue found by Codex code review" },
+                    }],
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Entries for the `comments` field of GitHub's "create a review for a pull
+/// request" API.
+fn gh_comments(output: &ReviewOutputEvent, repo_root: &Path) -> Value {
+    let comments: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let mut comment = json!({
+                "path": relative_path(finding, repo_root),
+                "line": end,
+                "side": "RIGHT",
+                "body": format!(
+                    "**{}**\n\nSeverity: {}\n\n{}",
+                    finding.title.trim(),
+                    Severity::from_priority(finding.priority).as_str(),
+                    finding.body.trim()
+                ),
+            });
+            // GitHub rejects a multi-line comment whose start is its end.
+            if start < end {
+                comment["start_line"] = json!(start);
+                comment["start_side"] = json!("RIGHT");
+            }
+            comment
+        })
+        .collect();
+    Value::Array(comments)
+}
+
+/// A GitHub Actions annotation per finding, for `--ci github`.
+pub(crate) fn review_annotations(output: &ReviewOutputEvent, repo_root: &Path) -> Vec<Annotation> {
+    output
+        .findings
+        .iter()
+        .map(|finding| Annotation {
+            level: Severity::from_priority(finding.priority).annotation_level(),
+            file: Some(relative_path(finding, repo_root)),
+            lines: Some(line_range(finding)),
+            title: Some(finding.title.trim().to_string()),
+            message: finding.body.trim().to_string(),
+        })
+        .collect()
+}
+
+/// One failing case per finding, named after it and grouped by file. A review
+/// without findings is a single passing case.
+fn junit(output: &ReviewOutputEvent, repo_root: &Path) -> String {
+    let mut cases: Vec<TestCase> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let (start, end) = line_range(finding);
+            let path = relative_path(finding, repo_root);
+            TestCase {
+                name: finding.title.trim().to_string(),
+                classname: path.clone(),
+                time_seconds: 0.0,
+                failure: Some(Failure {
+                    kind: Severity::from_priority(finding.priority)
+                        .as_str()
+                        .to_string(),
+                    message: format!("{path}:{start}-{end}"),
+                    body: finding.body.trim().to_string(),
+                }),
+                system_out: None,
+            }
+        })
+        .collect();
+    if cases.is_empty() {
+        cases.push(TestCase {
+            name: "review".to_string(),
+            classname: "codex-review".to_string(),
+            time_seconds: 0.0,
+            failure: None,
+            system_out: Some(output.overall_explanation.trim().to_string())
+                .filter(|explanation| !explanation.is_empty()),
+        });
+    }
+    render_junit("codex-review", &cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn output() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                ReviewFinding {
+                    title: "[P1] Missing bounds check".to_string(),
+                    body: "Index can exceed the slice length.".to_string(),
+                    confidence_score: 0.8,
+                    priority: 1,
+                    code_location: ReviewCodeLocation {
+                        absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                        line_range: ReviewLineRange { start: 10, end: 12 },
+                    },
+                },
+                ReviewFinding {
+                    title: "[P3] Typo".to_string(),
+                    body: "`recieve` should be `receive`.".to_string(),
+                    confidence_score: 0.5,
+                    priority: 3,
+                    code_location: ReviewCodeLocation {
+                        absolute_file_path: PathBuf::from("/repo/README.md"),
+                        line_range: ReviewLineRange { start: 4, end: 4 },
+                    },
+                },
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One real bug.".to_string(),
+            overall_confidence_score: 0.7,
+        }
+    }
+
+    #[test]
+    fn text_format_leaves_output_alone() {
+        assert_eq!(
+            render_review(ReviewFormat::Text, &output(), Path::new("/repo")),
+            None
+        );
+    }
+
+    #[test]
+    fn markdown_lists_findings_with_locations_and_severity() {
+        let markdown =
+            render_review(ReviewFormat::Markdown, &output(), Path::new("/repo")).expect("markdown");
+        assert_eq!(
+            markdown,
+            "# Code review\n\nOne real bug.\n\n## [P1] Missing bounds check\n\n`src/lib.rs:10-12` · severity: high\n\nIndex can exceed the slice length.\n\n## [P3] Typo\n\n`README.md:4` · severity: low\n\n`recieve` should be `receive`.\n"
+        );
+    }
+
+    #[test]
+    fn sarif_results_carry_level_and_region() {
+        let sarif = sarif(&output(), Path::new("/repo"));
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs" },
+                "region": { "startLine": 10, "endLine": 12 },
+            })
+        );
+        assert_eq!(sarif["runs"][0]["results"][1]["level"], "note");
+    }
+
+    #[test]
+    fn gh_comments_use_start_line_only_for_ranges() {
+        let comments = gh_comments(&output(), Path::new("/repo"));
+        assert_eq!(comments[0]["path"], "src/lib.rs");
+        assert_eq!(comments[0]["start_line"], 10);
+        assert_eq!(comments[0]["line"], 12);
+        assert_eq!(comments[1]["line"], 4);
+        assert!(comments[1].get("start_line").is_none());
+    }
+
+    #[test]
+    fn junit_fails_one_case_per_finding() {
+        let junit = junit(&output(), Path::new("/repo"));
+        assert!(junit.contains(r#"<testsuite name="codex-review" tests="2" failures="2""#));
+        assert!(junit.contains(
+            r#"<testcase name="[P1] Missing bounds check" classname="src/lib.rs" time="0.000">"#
+        ));
+        assert!(junit.contains(r#"<failure type="high" message="src/lib.rs:10-12">"#));
+
+        let clean = ReviewOutputEvent {
+            findings: Vec::new(),
+            ..output()
+        };
+        assert!(junit(&clean, Path::new("/repo")).contains(r#"tests="1" failures="0""#));
+    }
+}
@@ -0,0 +1,270 @@
+//! `codex exec --artifacts-dir DIR`: gather what a run produced into one
+//! directory, so CI can upload it as a single artifact.
+//!
+//! When the run ends, DIR gets the run's final diff (`diff.patch`), its
+//! transcript in the `codex export` JSON format (`transcript.json`), its token
+//! usage and estimated cost (`usage.json`), and a copy of every file the agent
+//! named as a deliverable under `deliverables/`. `manifest.json` lists them
+//! all with the session id and exit status.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use codex_core::config::types::ModelPrice;
+use codex_core::export::JsonExporter;
+use codex_core::export::TranscriptExporter;
+use codex_core::export::load_transcript;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::TokenUsage;
+use serde::Serialize;
+
+use crate::exit_codes::exit_code_name;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DIFF_FILE: &str = "diff.patch";
+const TRANSCRIPT_FILE: &str = "transcript.json";
+const USAGE_FILE: &str = "usage.json";
+const DELIVERABLES_DIR: &str = "deliverables";
+
+/// Marks a deliverable in the agent's final message.
+const DELIVERABLE_PREFIX: &str = "DELIVERABLE:";
+
+/// Sent with the prompt so the agent knows how to hand files over.
+pub(crate) const DELIVERABLES_NOTE: &str = "This run collects its results for upload. If you produce files meant to be handed over, such as a report or a build output, end your final message with one line per file: `DELIVERABLE: <path relative to the working directory>`.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ArtifactKind {
+    Diff,
+    Transcript,
+    Usage,
+    Deliverable,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ManifestFile {
+    /// Relative to the artifacts directory, with `/` separators.
+    path: String,
+    kind: ArtifactKind,
+    bytes: u64,
+    /// For a deliverable, the path the agent gave.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest {
+    session_id: String,
+    exit_code: i32,
+    status: &'static str,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Serialize)]
+struct UsageReport<'a> {
+    model: &'a str,
+    duration_seconds: f64,
+    usage: Option<&'a TokenUsage>,
+    /// Priced with `[model_prices]`; absent when the model has no price.
+    estimated_cost_usd: Option<f64>,
+}
+
+/// What the run was, for the manifest and the usage report.
+pub(crate) struct RunReport<'a> {
+    pub session_id: String,
+    pub model: &'a str,
+    pub price: Option<&'a ModelPrice>,
+    pub exit_code: i32,
+    pub duration: Duration,
+    pub final_message: Option<&'a str>,
+    pub rollout: &'a Path,
+    pub cwd: &'a Path,
+}
+
+/// Collects the parts of the event stream that end up in the artifacts.
+#[derive(Debug, Default)]
+pub(crate) struct RunArtifacts {
+    diff: Option<String>,
+    usage: Option<TokenUsage>,
+}
+
+impl RunArtifacts {
+    pub(crate) fn record_event(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::TurnDiff(ev) => self.diff = Some(ev.unified_diff.clone()),
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = &ev.info {
+                    self.usage = Some(info.total_token_usage.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Writes the artifacts and the manifest into `dir`. A transcript or
+    /// deliverable that cannot be read is reported on stderr and left out.
+    pub(crate) async fn write(self, dir: &Path, report: &RunReport<'_>) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        let mut files = Vec::new();
+
+        if let Some(diff) = self.diff.filter(|diff| !diff.is_empty()) {
+            files.push(write_file(
+                dir,
+                DIFF_FILE,
+                diff.as_bytes(),
+                ArtifactKind::Diff,
+            )?);
+        }
+
+        match load_transcript(report.rollout).await {
+            Ok(transcript) => {
+                let rendered = JsonExporter.export(&transcript)?;
+                files.push(write_file(
+                    dir,
+                    TRANSCRIPT_FILE,
+                    &rendered,
+                    ArtifactKind::Transcript,
+                )?);
+            }
+            Err(err) => eprintln!(
+                "Left the transcript out of the artifacts: failed to read {}: {err}",
+                report.rollout.display()
+            ),
+        }
+
+        let usage = UsageReport {
+            model: report.model,
+            duration_seconds: report.duration.as_secs_f64(),
+            usage: self.usage.as_ref(),
+            estimated_cost_usd: report
+                .price
+                .zip(self.usage.as_ref())
+                .map(|(price, usage)| price.cost_usd(usage)),
+        };
+        files.push(write_file(
+            dir,
+            USAGE_FILE,
+            &serde_json::to_vec_pretty(&usage)?,
+            ArtifactKind::Usage,
+        )?);
+
+        for source in report.final_message.map(deliverables).unwrap_or_default() {
+            match copy_deliverable(dir, report.cwd, source) {
+                Ok(file) => files.push(file),
+                Err(err) => eprintln!("Left deliverable {source} out of the artifacts: {err:#}"),
+            }
+        }
+
+        let manifest = Manifest {
+            session_id: report.session_id.clone(),
+            exit_code: report.exit_code,
+            status: exit_code_name(report.exit_code),
+            files,
+        };
+        let manifest_path = dir.join(MANIFEST_FILE);
+        std::fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?)
+            .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+        Ok(())
+    }
+}
+
+fn write_file(
+    dir: &Path,
+    name: &str,
+    contents: &[u8],
+    kind: ArtifactKind,
+) -> anyhow::Result<ManifestFile> {
+    let path = dir.join(name);
+    std::fs::write(&path, contents)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(ManifestFile {
+        path: name.to_string(),
+        kind,
+        bytes: contents.len() as u64,
+        source: None,
+    })
+}
+
+/// The paths on `DELIVERABLE:` lines of the final message.
+fn deliverables(message: &str) -> Vec<&str> {
+    message
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(DELIVERABLE_PREFIX))
+        .map(|path| path.trim().trim_matches('`'))
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// Copies a deliverable into `deliverables/`, keeping its path under the
+/// working directory. Files outside the working directory are refused.
+fn copy_deliverable(dir: &Path, cwd: &Path, source: &str) -> anyhow::Result<ManifestFile> {
+    let cwd = cwd.canonicalize()?;
+    let path = cwd
+        .join(source)
+        .canonicalize()
+        .with_context(|| format!("{source} does not exist"))?;
+    let relative: PathBuf = match path.strip_prefix(&cwd) {
+        Ok(relative) if path.is_file() => relative.to_path_buf(),
+        Ok(_) => anyhow::bail!("{source} is not a file"),
+        Err(_) => anyhow::bail!("{source} is outside the working directory"),
+    };
+    let dest = dir.join(DELIVERABLES_DIR).join(&relative);
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let bytes = std::fs::copy(&path, &dest)
+        .with_context(|| format!("failed to copy {source} to {}", dest.display()))?;
+    let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    Ok(ManifestFile {
+        path: format!("{DELIVERABLES_DIR}/{relative}"),
+        kind: ArtifactKind::Deliverable,
+        bytes,
+        source: Some(source.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_deliverables_from_the_final_message() {
+        let message = "Wrote the report.\n\nDELIVERABLE: reports/summary.md\n  DELIVERABLE: `dist/app.tar.gz`\nDELIVERABLE:\n";
+        assert_eq!(
+            deliverables(message),
+            vec!["reports/summary.md", "dist/app.tar.gz"]
+        );
+    }
+
+    #[test]
+    fn copies_deliverables_inside_the_working_directory_only() {
+        let cwd = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        std::fs::create_dir(cwd.path().join("reports")).unwrap();
+        std::fs::write(cwd.path().join("reports/summary.md"), "# Summary\n").unwrap();
+
+        assert_eq!(
+            copy_deliverable(out.path(), cwd.path(), "reports/summary.md").unwrap(),
+            ManifestFile {
+                path: "deliverables/reports/summary.md".to_string(),
+                kind: ArtifactKind::Deliverable,
+                bytes: 10,
+                source: Some("reports/summary.md".to_string()),
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(out.path().join("deliverables/reports/summary.md")).unwrap(),
+            "# Summary\n"
+        );
+        assert!(copy_deliverable(out.path(), cwd.path(), "../outside.txt").is_err());
+        assert!(copy_deliverable(out.path(), cwd.path(), "reports").is_err());
+    }
+}
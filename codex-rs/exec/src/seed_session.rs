@@ -0,0 +1,60 @@
+//! `codex exec --seed-session PATH|ID`: start a new session from an earlier
+//! conversation.
+//!
+//! The recorded conversation is copied into the new session, as a fork is, so
+//! the model sees it as context for the prompt while the original rollout
+//! stays untouched. With `--seed-compact`, the copy is compacted before the
+//! prompt is sent.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_core::CodexConversation;
+use codex_core::InitialHistory;
+use codex_core::RolloutRecorder;
+use codex_core::find_conversation_path_by_id_str;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+
+/// The rollout file for `--seed-session`: a path to a `.jsonl` file, or the id
+/// of a session recorded under `codex_home`.
+pub(crate) async fn resolve_seed_path(
+    arg: &str,
+    cwd: &Path,
+    codex_home: &Path,
+) -> anyhow::Result<PathBuf> {
+    let path = cwd.join(arg);
+    if path.is_file() {
+        return Ok(path);
+    }
+    find_conversation_path_by_id_str(codex_home, arg)
+        .await?
+        .with_context(|| format!("--seed-session {arg} is neither a rollout file nor a session id"))
+}
+
+/// The conversation in `path`, as history for a new session.
+pub(crate) async fn load_seed_history(path: &Path) -> anyhow::Result<InitialHistory> {
+    let history = RolloutRecorder::get_rollout_history(path)
+        .await
+        .with_context(|| format!("failed to read session {}", path.display()))?;
+    Ok(InitialHistory::Forked(history.get_rollout_items()))
+}
+
+/// Compacts the seeded conversation and waits until it is done, before any
+/// event reaches the event processor.
+pub(crate) async fn compact_seed(conversation: &CodexConversation) -> anyhow::Result<()> {
+    conversation.submit(Op::Compact).await?;
+    loop {
+        match conversation.next_event().await?.msg {
+            EventMsg::TaskComplete(_) => return Ok(()),
+            EventMsg::Error(ev) => {
+                anyhow::bail!("failed to compact the seeded session: {}", ev.message)
+            }
+            EventMsg::TurnAborted(_) => {
+                anyhow::bail!("compacting the seeded session was interrupted")
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Piped stdin as context for a prompt given on the command line, as in
+//! `git diff | codex exec "review this diff"`.
+//!
+//! The input is attached as a `<stdin>` block after the prompt. Only the first
+//! [`MAX_STDIN_CONTEXT_BYTES`] are kept, and input that is not UTF-8 text is
+//! left out with a note on stderr.
+
+use std::io::Read;
+
+/// Piped input beyond this many bytes is cut off.
+pub(crate) const MAX_STDIN_CONTEXT_BYTES: usize = 256 * 1024;
+
+/// Reads `input` to the end and formats it as a context block. `None` when
+/// the input is empty or binary.
+pub(crate) fn read_stdin_context(
+    mut input: impl Read,
+    max_bytes: usize,
+) -> std::io::Result<Option<String>> {
+    let mut bytes = Vec::new();
+    (&mut input)
+        .take(max_bytes as u64)
+        .read_to_end(&mut bytes)?;
+    // Drain the rest so the writer is not cut off with a broken pipe, and to
+    // report the full size.
+    let rest = std::io::copy(&mut input, &mut std::io::sink())?;
+    let total = bytes.len() as u64 + rest;
+
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    let Some(contents) = text_prefix(bytes) else {
+        eprintln!("Piped stdin looks binary ({total} bytes); it was not attached.");
+        return Ok(None);
+    };
+    if (contents.len() as u64) < total {
+        eprintln!(
+            "Piped stdin is {total} bytes; attaching the first {}.",
+            contents.len()
+        );
+    }
+    Ok(Some(format_stdin_context(&contents, total)))
+}
+
+/// `bytes` as text, minus a character the size cap cut in half. `None` for
+/// binary data.
+fn text_prefix(bytes: Vec<u8>) -> Option<String> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    match String::from_utf8(bytes) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.utf8_error().error_len().is_none() => {
+            let valid = err.utf8_error().valid_up_to();
+            let mut bytes = err.into_bytes();
+            bytes.truncate(valid);
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        Err(_) => None,
+    }
+}
+
+fn format_stdin_context(contents: &str, total: u64) -> String {
+    let mut text = format!("<stdin>\n{contents}");
+    if !contents.ends_with('\n') {
+        text.push('\n');
+    }
+    if (contents.len() as u64) < total {
+        text.push_str(&format!(
+            "[truncated: first {} of {total} bytes]\n",
+            contents.len()
+        ));
+    }
+    text.push_str("</stdin>");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn read(input: &[u8], max_bytes: usize) -> Option<String> {
+        read_stdin_context(input, max_bytes).expect("read")
+    }
+
+    #[test]
+    fn wraps_piped_text_in_a_stdin_block() {
+        assert_eq!(
+            read(b"diff --git a/x b/x\n+added\n", 1024),
+            Some("<stdin>\ndiff --git a/x b/x\n+added\n</stdin>".to_string())
+        );
+        assert_eq!(read(b"", 1024), None);
+        assert_eq!(read(b" \n\n", 1024), None);
+    }
+
+    #[test]
+    fn truncates_at_the_cap_without_splitting_characters() {
+        assert_eq!(
+            read("ééé".as_bytes(), 3),
+            Some("<stdin>\né\n[truncated: first 2 of 6 bytes]\n</stdin>".to_string())
+        );
+    }
+
+    #[test]
+    fn leaves_out_binary_input() {
+        assert_eq!(read(b"\x89PNG\r\n\x1a\n\0\0", 1024), None);
+        assert_eq!(read(&[0xff, 0xfe, b'a'], 1024), None);
+    }
+}
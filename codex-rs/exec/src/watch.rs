@@ -0,0 +1,300 @@
+//! `codex exec --watch GLOB PROMPT`: start a fresh run whenever matching files
+//! change.
+//!
+//! Each run is its own `codex exec` process with the flags given to the watch,
+//! so limits such as `--max-turns` bound every run rather than the watch as a
+//! whole. Filesystem events are debounced into one run per burst of changes.
+//! Changes made while a run is going or during the cooldown after it,
+//! including the run's own edits, are dropped, so a run cannot start the next.
+
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use clap::ValueEnum;
+use notify::Event;
+use notify::EventKind;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::sync::mpsc;
+use wildmatch::WildMatchPattern;
+
+use crate::batch::child_run_args;
+use crate::cli::Cli;
+use crate::cli::Color;
+use crate::exit_codes::exit_code_name;
+
+/// How long matching files must stay untouched before a run starts.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Changed files listed in a run's prompt; the rest are only counted.
+const MAX_LISTED_CHANGES: usize = 20;
+
+pub(crate) async fn run_watch(cli: Cli) -> anyhow::Result<()> {
+    let prompt = match cli.prompt.as_deref() {
+        Some(prompt) if prompt != "-" => prompt.to_string(),
+        _ => anyhow::bail!("--watch needs a PROMPT argument"),
+    };
+    let root = match &cli.cwd {
+        Some(dir) => std::env::current_dir()?.join(dir),
+        None => std::env::current_dir()?,
+    };
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", root.display()))?;
+    let patterns = WatchPatterns::new(&cli.watch);
+    let exe = std::env::current_exe().context("failed to locate the codex executable")?;
+    let args = watch_run_args(&exe, &cli);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("failed to watch {}", root.display()))?;
+    eprintln!(
+        "Watching {} in {}. Press Ctrl-C to stop.",
+        cli.watch.join(", "),
+        root.display()
+    );
+
+    let mut runs = 0;
+    loop {
+        let changed = tokio::select! {
+            changed = next_changes(&mut rx, &root, &patterns) => changed,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        let Some(changed) = changed else {
+            break;
+        };
+        runs += 1;
+        eprintln!(
+            "[run {runs}] {} changed: {}",
+            changed.len(),
+            list_changes(&changed, ", ")
+        );
+
+        let started = Instant::now();
+        let mut child = tokio::process::Command::new(&exe)
+            .args(&args)
+            .arg(run_prompt(&prompt, &changed))
+            .current_dir(&root)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .context("failed to start a run")?;
+        let status = tokio::select! {
+            status = child.wait() => status?,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        let outcome = match status.code() {
+            Some(code) => format!("{} (exit code {code})", exit_code_name(code)),
+            None => "killed by a signal".to_string(),
+        };
+        eprintln!(
+            "[run {runs}] {outcome} after {:.0}s. Watching again in {}s.",
+            started.elapsed().as_secs_f64(),
+            cli.watch_cooldown.as_secs()
+        );
+
+        tokio::select! {
+            _ = tokio::time::sleep(cli.watch_cooldown) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        while rx.try_recv().is_ok() {}
+    }
+    eprintln!("Stopped watching after {runs} run(s).");
+    Ok(())
+}
+
+/// The globs from `--watch`. As in steering manifests, `*` matches any run of
+/// characters including `/`; `**/` is accepted and means the same.
+struct WatchPatterns(Vec<WildMatchPattern<'*', '?'>>);
+
+impl WatchPatterns {
+    fn new(globs: &[String]) -> Self {
+        Self(
+            globs
+                .iter()
+                .map(|glob| {
+                    let glob = glob.trim().trim_start_matches("./").replace("**/", "*");
+                    WildMatchPattern::new(&glob)
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether `relative`, a path under the watched root, matches a glob.
+    fn matches(&self, relative: &Path) -> bool {
+        let path = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.0.iter().any(|pattern| pattern.matches(&path))
+    }
+}
+
+/// Waits for a watched file to change, then until watched files have been
+/// quiet for [`DEBOUNCE`], and returns every file that changed, relative to
+/// `root`. `None` once the watcher has stopped.
+async fn next_changes(
+    rx: &mut mpsc::UnboundedReceiver<Event>,
+    root: &Path,
+    patterns: &WatchPatterns,
+) -> Option<BTreeSet<PathBuf>> {
+    let mut changed = BTreeSet::new();
+    while changed.is_empty() {
+        let event = rx.recv().await?;
+        changed.extend(matching_paths(&event, root, patterns));
+    }
+    let mut quiet_at = tokio::time::Instant::now() + DEBOUNCE;
+    loop {
+        match tokio::time::timeout_at(quiet_at, rx.recv()).await {
+            Ok(Some(event)) => {
+                let paths = matching_paths(&event, root, patterns);
+                if !paths.is_empty() {
+                    changed.extend(paths);
+                    quiet_at = tokio::time::Instant::now() + DEBOUNCE;
+                }
+            }
+            Ok(None) | Err(_) => return Some(changed),
+        }
+    }
+}
+
+fn matching_paths(event: &Event, root: &Path, patterns: &WatchPatterns) -> Vec<PathBuf> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return Vec::new();
+    }
+    event
+        .paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(root).ok())
+        .filter(|relative| patterns.matches(relative))
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// The flags every run gets. Each run prints its own progress to the
+/// terminal; `--json` streams every run's events to stdout.
+fn watch_run_args(exe: &Path, cli: &Cli) -> Vec<OsString> {
+    let mut args = child_run_args(exe, cli);
+    args.push("--no-stdin".into());
+    if let Some(max_turns) = cli.max_turns {
+        args.extend(["--max-turns".into(), max_turns.to_string().into()]);
+    }
+    if cli.color != Color::Auto
+        && let Some(color) = cli.color.to_possible_value()
+    {
+        args.extend(["--color".into(), color.get_name().into()]);
+    }
+    if cli.json {
+        args.push("--json".into());
+    }
+    if let Some(ci) = cli.ci.and_then(|ci| ci.to_possible_value()) {
+        args.extend(["--ci".into(), ci.get_name().into()]);
+    }
+    if cli.quiet {
+        args.push("--quiet".into());
+    }
+    for _ in 0..cli.verbose {
+        args.push("--verbose".into());
+    }
+    args
+}
+
+/// The watch's prompt, followed by the files that changed since the last run.
+fn run_prompt(prompt: &str, changed: &BTreeSet<PathBuf>) -> String {
+    format!(
+        "{prompt}\n\nFiles changed since the last run:\n{}",
+        list_changes(changed, "\n")
+    )
+}
+
+fn list_changes(changed: &BTreeSet<PathBuf>, separator: &str) -> String {
+    let mut listed: Vec<String> = changed
+        .iter()
+        .take(MAX_LISTED_CHANGES)
+        .map(|path| path.display().to_string())
+        .collect();
+    if changed.len() > MAX_LISTED_CHANGES {
+        listed.push(format!("and {} more", changed.len() - MAX_LISTED_CHANGES));
+    }
+    listed.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::CreateKind;
+    use notify::event::ModifyKind;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn matches_globs_against_paths_under_the_root() {
+        let patterns = WatchPatterns::new(&["src/**/*.rs".to_string(), "./Cargo.toml".to_string()]);
+
+        assert!(patterns.matches(Path::new("src/lib.rs")));
+        assert!(patterns.matches(Path::new("src/watch/mod.rs")));
+        assert!(patterns.matches(Path::new("Cargo.toml")));
+        assert!(!patterns.matches(Path::new("tests/suite.rs")));
+        assert!(!patterns.matches(Path::new("src/lib.rs.orig")));
+    }
+
+    #[test]
+    fn keeps_changes_to_watched_files_only() {
+        let root = Path::new("/repo");
+        let patterns = WatchPatterns::new(&["src/*.rs".to_string()]);
+        let created = Event::new(EventKind::Create(CreateKind::File))
+            .add_path(PathBuf::from("/repo/src/new.rs"))
+            .add_path(PathBuf::from("/repo/target/debug/build.log"))
+            .add_path(PathBuf::from("/elsewhere/src/lib.rs"));
+        let accessed = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/repo/src/lib.rs"));
+        let modified = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/repo/src/lib.rs"));
+
+        assert_eq!(
+            matching_paths(&created, root, &patterns),
+            vec![PathBuf::from("src/new.rs")]
+        );
+        assert_eq!(
+            matching_paths(&accessed, root, &patterns),
+            Vec::<PathBuf>::new()
+        );
+        assert_eq!(
+            matching_paths(&modified, root, &patterns),
+            vec![PathBuf::from("src/lib.rs")]
+        );
+    }
+
+    #[test]
+    fn prompt_lists_changed_files() {
+        let mut changed: BTreeSet<PathBuf> = (0..MAX_LISTED_CHANGES + 2)
+            .map(|n| PathBuf::from(format!("src/m{n:02}.rs")))
+            .collect();
+        let prompt = run_prompt("fix any new compiler errors", &changed);
+        assert!(prompt.starts_with(
+            "fix any new compiler errors\n\nFiles changed since the last run:\nsrc/m00.rs\nsrc/m01.rs\n"
+        ));
+        assert!(prompt.ends_with("src/m19.rs\nand 2 more"));
+
+        changed.retain(|path| path == Path::new("src/m00.rs"));
+        assert_eq!(
+            run_prompt("check", &changed),
+            "check\n\nFiles changed since the last run:\nsrc/m00.rs"
+        );
+    }
+}
@@ -13,6 +13,8 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::RunLimit;
+use codex_core::protocol::RunLimitReachedEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::WarningEvent;
@@ -22,6 +24,8 @@ use codex_exec::exec_events::AgentMessageItem;
 use codex_exec::exec_events::CommandExecutionItem;
 use codex_exec::exec_events::CommandExecutionStatus;
 use codex_exec::exec_events::ErrorItem;
+use codex_exec::exec_events::FileChangeItem;
+use codex_exec::exec_events::FileUpdateChange;
 use codex_exec::exec_events::ItemCompletedEvent;
 use codex_exec::exec_events::ItemStartedEvent;
 use codex_exec::exec_events::ItemUpdatedEvent;
@@ -31,6 +35,7 @@ use codex_exec::exec_events::McpToolCallItemResult;
 use codex_exec::exec_events::McpToolCallStatus;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
+use codex_exec::exec_events::PromptBreakdown;
 use codex_exec::exec_events::ReasoningItem;
 use codex_exec::exec_events::ThreadErrorEvent;
 use codex_exec::exec_events::ThreadEvent;
@@ -627,6 +632,43 @@ fn error_followed_by_task_complete_produces_turn_failed() {
     );
 }
 
+#[test]
+fn run_limit_reached_produces_error_and_turn_failed() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let message = "Stopped by --max-cost-usd after 7 model requests (2m 05s, about $1.02).";
+
+    let limit_event = event(
+        "e1",
+        EventMsg::RunLimitReached(RunLimitReachedEvent {
+            limit: RunLimit::Cost,
+            turns: 7,
+            elapsed_seconds: 125,
+            cost_usd: Some(1.02),
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&limit_event),
+        vec![ThreadEvent::Error(ThreadErrorEvent {
+            message: message.to_string(),
+        })]
+    );
+
+    let complete_event = event(
+        "e2",
+        EventMsg::TaskComplete(codex_core::protocol::TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    );
+    assert_eq!(
+        ep.collect_thread_events(&complete_event),
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: message.to_string(),
+            },
+        })]
+    );
+}
+
 #[test]
 fn exec_command_end_success_produces_completed_command_item() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -1053,6 +1095,11 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            prompt_breakdown: Some(codex_core::protocol::PromptTokenBreakdown {
+                steering_tokens: 300,
+                agents_md_tokens: 150,
+                history_tokens: 750,
+            }),
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -1072,7 +1119,113 @@ fn task_complete_produces_turn_completed_with_usage() {
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 345,
+                prompt_breakdown: Some(PromptBreakdown {
+                    steering_tokens: 300,
+                    agents_md_tokens: 150,
+                    history_tokens: 750,
+                }),
             },
         })]
     );
 }
+
+/// The JSONL wire format is documented in docs/exec.md and scripts depend on
+/// it. Changing any of these shapes is a breaking change.
+#[test]
+fn thread_events_serialize_to_the_documented_schema() {
+    let line = |event: ThreadEvent| serde_json::to_value(event).expect("serialize");
+
+    assert_eq!(
+        line(ThreadEvent::ThreadStarted(ThreadStartedEvent {
+            thread_id: "thread-1".to_string(),
+        })),
+        json!({"type": "thread.started", "thread_id": "thread-1"})
+    );
+    assert_eq!(
+        line(ThreadEvent::TurnStarted(TurnStartedEvent {})),
+        json!({"type": "turn.started"})
+    );
+    assert_eq!(
+        line(ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::CommandExecution(CommandExecutionItem {
+                    command: "cargo test".to_string(),
+                    aggregated_output: "ok\n".to_string(),
+                    exit_code: Some(1),
+                    status: CommandExecutionStatus::Failed,
+                }),
+            },
+        })),
+        json!({
+            "type": "item.completed",
+            "item": {
+                "id": "item_0",
+                "type": "command_execution",
+                "command": "cargo test",
+                "aggregated_output": "ok\n",
+                "exit_code": 1,
+                "status": "failed",
+            },
+        })
+    );
+    assert_eq!(
+        line(ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_1".to_string(),
+                details: ThreadItemDetails::FileChange(FileChangeItem {
+                    changes: vec![FileUpdateChange {
+                        path: "src/lib.rs".to_string(),
+                        kind: PatchChangeKind::Update,
+                    }],
+                    status: PatchApplyStatus::Completed,
+                }),
+            },
+        })),
+        json!({
+            "type": "item.completed",
+            "item": {
+                "id": "item_1",
+                "type": "file_change",
+                "changes": [{"path": "src/lib.rs", "kind": "update"}],
+                "status": "completed",
+            },
+        })
+    );
+    assert_eq!(
+        line(ThreadEvent::ItemCompleted(ItemCompletedEvent {
+            item: ThreadItem {
+                id: "item_2".to_string(),
+                details: ThreadItemDetails::AgentMessage(AgentMessageItem {
+                    text: "done".to_string(),
+                }),
+            },
+        })),
+        json!({
+            "type": "item.completed",
+            "item": {"id": "item_2", "type": "agent_message", "text": "done"},
+        })
+    );
+    assert_eq!(
+        line(ThreadEvent::TurnCompleted(TurnCompletedEvent {
+            usage: Usage {
+                input_tokens: 10,
+                cached_input_tokens: 4,
+                output_tokens: 2,
+                prompt_breakdown: None,
+            },
+        })),
+        json!({
+            "type": "turn.completed",
+            "usage": {"input_tokens": 10, "cached_input_tokens": 4, "output_tokens": 2},
+        })
+    );
+    assert_eq!(
+        line(ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: "boom".to_string(),
+            },
+        })),
+        json!({"type": "turn.failed", "error": {"message": "boom"}})
+    );
+}
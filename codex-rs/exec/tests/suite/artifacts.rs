@@ -0,0 +1,72 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// `--artifacts-dir` collects the transcript, the usage report, and the
+/// deliverables the agent names, and lists them in the manifest.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn collects_run_artifacts_with_a_manifest() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::write(test.cwd_path().join("report.md"), "# Findings\n")?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Wrote the report.\n\nDELIVERABLE: report.md"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--artifacts-dir")
+        .arg("out")
+        .arg("summarize the findings")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(texts.iter().any(|text| text.contains("DELIVERABLE: <path")));
+
+    let out = test.cwd_path().join("out");
+    let manifest: Value =
+        serde_json::from_str(&std::fs::read_to_string(out.join("manifest.json"))?)?;
+    assert_eq!(manifest["exit_code"], 0);
+    assert_eq!(manifest["status"], "success");
+    let files: Vec<(&str, &str)> = manifest["files"]
+        .as_array()
+        .expect("files")
+        .iter()
+        .map(|file| {
+            (
+                file["path"].as_str().unwrap(),
+                file["kind"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            ("transcript.json", "transcript"),
+            ("usage.json", "usage"),
+            ("deliverables/report.md", "deliverable"),
+        ]
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("deliverables/report.md"))?,
+        "# Findings\n"
+    );
+    let transcript: Value =
+        serde_json::from_str(&std::fs::read_to_string(out.join("transcript.json"))?)?;
+    assert!(
+        transcript["entries"]
+            .as_array()
+            .is_some_and(|entries| !entries.is_empty())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+
+/// A checkpointed run can be resumed from its directory with the earlier
+/// conversation intact.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resumes_a_checkpointed_run() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let dir = test.cwd_path().join(".codex/runs/nightly");
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Half done"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--checkpoint-dir")
+        .arg(&dir)
+        .arg("migrate the config loader")
+        .assert()
+        .success();
+
+    let state: Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("checkpoint.json"))?)?;
+    assert_eq!(state["status"], "completed");
+    assert_eq!(state["prompt"], "migrate the config loader");
+    let rollout = std::fs::read_to_string(dir.join("rollout.jsonl"))?;
+    assert!(rollout.contains("migrate the config loader"));
+
+    // Pretend the job was killed before it finished.
+    let mut state = state;
+    state["status"] = Value::from("running");
+    std::fs::write(dir.join("checkpoint.json"), state.to_string())?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp2"),
+        responses::ev_assistant_message("m2", "All done"),
+        responses::ev_completed("resp2"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--resume")
+        .arg("nightly")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(texts.iter().any(|text| text == "migrate the config loader"));
+    assert!(
+        texts
+            .iter()
+            .any(|text| text.starts_with("The previous run of this task was interrupted"))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+/// `--ci github` writes the final message and session id to `$GITHUB_OUTPUT`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sets_step_outputs() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let runner = tempfile::tempdir()?;
+    let output_file = runner.path().join("github_output");
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "All tests pass."),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .env("GITHUB_OUTPUT", &output_file)
+        .env("RUNNER_TEMP", runner.path())
+        .arg("--skip-git-repo-check")
+        .arg("--ci")
+        .arg("github")
+        .arg("run the tests")
+        .assert()
+        .success()
+        .stdout("All tests pass.\n");
+
+    let outputs = std::fs::read_to_string(&output_file)?;
+    assert!(outputs.starts_with("session-id<<CODEX_OUTPUT_EOF\n"));
+    assert!(
+        outputs.ends_with("final-message<<CODEX_OUTPUT_EOF\nAll tests pass.\nCODEX_OUTPUT_EOF\n")
+    );
+
+    Ok(())
+}
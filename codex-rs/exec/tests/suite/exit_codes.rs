@@ -0,0 +1,80 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+/// `--print-exit-codes --json` lists the contract without starting a run.
+#[test]
+fn print_exit_codes_lists_every_failure_class() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let output = test
+        .cmd()
+        .arg("--print-exit-codes")
+        .arg("--json")
+        .output()?;
+    assert!(output.status.success());
+    let codes: Value = serde_json::from_slice(&output.stdout)?;
+    let names: Vec<(i64, &str)> = codes
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|exit| {
+            (
+                exit["code"].as_i64().unwrap(),
+                exit["name"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (0, "success"),
+            (1, "internal_error"),
+            (2, "usage_error"),
+            (3, "approval_required"),
+            (4, "max_turns_exceeded"),
+            (5, "max_duration_exceeded"),
+            (6, "max_cost_exceeded"),
+            (7, "model_refusal"),
+            (8, "sandbox_denied"),
+            (9, "provider_error"),
+        ]
+    );
+
+    Ok(())
+}
+
+/// A run whose answer is a refusal exits with the refusal status.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refusal_exits_with_its_own_status() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        json!({
+            "type": "response.output_item.done",
+            "item": {
+                "type": "message",
+                "role": "assistant",
+                "id": "m1",
+                "content": [{"type": "refusal", "refusal": "I can't help with that."}]
+            }
+        }),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("write malware")
+        .assert()
+        .code(7);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+/// `--from-issue` sends the issue and its comments after the prompt.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn attaches_the_issue_to_the_prompt() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/org/repo/issues/123"))
+        .and(header("authorization", "Bearer gh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "title": "Login fails on Safari",
+            "body": "Clicking *Sign in* does nothing.",
+            "html_url": "https://github.com/org/repo/issues/123",
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/org/repo/issues/123/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"user": {"login": "alice"}, "body": "Also on iOS."},
+        ])))
+        .mount(&server)
+        .await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Fixed"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .env("GITHUB_API_URL", server.uri())
+        .env("GITHUB_TOKEN", "gh-token")
+        .arg("--skip-git-repo-check")
+        .arg("--from-issue")
+        .arg("https://github.com/org/repo/issues/123")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(texts.iter().any(|text| text == "Resolve the issue below."));
+    let issue = texts
+        .iter()
+        .find(|text| text.starts_with("<issue reference=\"org/repo#123\""))
+        .expect("issue context");
+    assert!(issue.contains("# Login fails on Safari\n\nClicking *Sign in* does nothing.\n"));
+    assert!(issue.contains("## Comment by @alice\n\nAlso on iOS.\n"));
+    assert!(issue.contains("`Refs: org/repo#123`"));
+
+    Ok(())
+}
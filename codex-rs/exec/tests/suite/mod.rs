@@ -1,9 +1,22 @@
 // Aggregates all former standalone integration tests as modules.
 mod add_dir;
 mod apply_patch;
+mod artifacts;
 mod auth_env;
+mod ci_github;
+mod checkpoint;
+mod exit_codes;
+mod from_issue;
+mod multi_repo;
 mod originator;
+mod output_format;
 mod output_schema;
+mod patch_policy;
+mod plan_only;
+mod prompt_template;
 mod resume;
 mod sandbox;
+mod seed_session;
 mod server_error_exit;
+mod stdin_context;
+mod verbosity;
@@ -0,0 +1,66 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+/// `--repo` reads AGENTS.md from every repository, labelling the extra ones.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reads_agents_md_from_every_repo() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let service_a = tempfile::tempdir()?;
+    let service_b = tempfile::tempdir()?;
+    std::fs::write(service_a.path().join("AGENTS.md"), "Run make test.")?;
+    std::fs::write(service_b.path().join("AGENTS.md"), "Run cargo test.")?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Bumped"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--repo")
+        .arg(service_a.path())
+        .arg("--repo")
+        .arg(service_b.path())
+        .arg("bump the shared proto version")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    let instructions = texts
+        .iter()
+        .find(|text| text.starts_with("# AGENTS.md instructions for "))
+        .expect("project docs");
+    let service_b = service_b.path().canonicalize()?;
+    assert!(instructions.contains(&format!(
+        "Run make test.\n\nInstructions for the repository at {}\n\nRun cargo test.",
+        service_b.display()
+    )));
+
+    Ok(())
+}
+
+/// Every `--repo` must exist.
+#[test]
+fn rejects_a_missing_repo() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let service_a = tempfile::tempdir()?;
+
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--repo")
+        .arg(service_a.path())
+        .arg("--repo")
+        .arg(service_a.path().join("missing"))
+        .arg("bump the shared proto version")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("is not a directory"));
+
+    Ok(())
+}
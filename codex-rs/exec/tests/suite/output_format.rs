@@ -0,0 +1,56 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+/// `--output-format junit` replaces the final message on stdout with a
+/// passing test case that carries it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn junit_reports_the_run_as_a_test_case() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "All tests pass"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--output-format")
+        .arg("junit")
+        .arg("run the tests")
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("<?xml"), "stdout: {stdout}");
+    assert!(stdout.contains(r#"tests="1" failures="0""#));
+    assert!(stdout.contains(r#"<testcase name="run the tests" classname="codex.exec""#));
+    assert!(stdout.contains("<system-out>All tests pass</system-out>"));
+
+    Ok(())
+}
+
+/// SARIF needs review findings, so a plain run is refused.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sarif_is_refused_outside_a_review() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--output-format")
+        .arg("sarif")
+        .arg("run the tests")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "use `codex review --format sarif`",
+        ));
+
+    Ok(())
+}
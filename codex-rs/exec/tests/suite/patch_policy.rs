@@ -0,0 +1,72 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn patches_outside_the_policy_are_sent_back_to_the_model() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let test = test_codex_exec();
+    let cwd = test.cwd_path().to_path_buf();
+    std::fs::create_dir(cwd.join(".codex"))?;
+    std::fs::write(
+        cwd.join(".codex/patch-policy.toml"),
+        "allow = [\"src/**\"]\ndeny = [\".github/**\"]\n",
+    )?;
+
+    let rejected_patch = r#"*** Begin Patch
+*** Add File: .github/workflows/ci.yml
++on: push
+*** End Patch"#;
+    let allowed_patch = r#"*** Begin Patch
+*** Add File: src/lib.rs
++pub fn answer() -> u32 { 42 }
+*** End Patch"#;
+    let server = start_mock_server().await;
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_apply_patch_function_call("request_0", rejected_patch),
+                ev_completed("request_0"),
+            ]),
+            sse(vec![
+                ev_apply_patch_function_call("request_1", allowed_patch),
+                ev_completed("request_1"),
+            ]),
+            sse(vec![ev_completed("request_2")]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-s")
+        .arg("danger-full-access")
+        .arg("add the answer")
+        .assert()
+        .success();
+
+    assert!(!cwd.join(".github/workflows/ci.yml").exists());
+    assert_eq!(
+        std::fs::read_to_string(cwd.join("src/lib.rs"))?,
+        "pub fn answer() -> u32 { 42 }\n"
+    );
+    let rejection = mock
+        .function_call_output_text("request_0")
+        .expect("the rejected patch should get an output");
+    assert!(
+        rejection.contains(
+            "patch rejected by the patch policy: `.github/workflows/ci.yml` matches deny `.github/**`"
+        ),
+        "unexpected output: {rejection}"
+    );
+    Ok(())
+}
@@ -0,0 +1,119 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use codex_core::plan_mode::plan_output_schema;
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use predicates::str::contains;
+use serde_json::Value;
+
+const PLAN: &str = r#"{"summary":"Add a retry.","steps":[{"title":"Wrap the request","details":"Retry twice on 5xx.","files":["src/fetch.rs"]}],"affected_files":["src/fetch.rs"],"risks":["Retries may hide outages."]}"#;
+
+fn tool_names(body: &Value) -> Vec<String> {
+    body["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| {
+            tool.get("name")
+                .or_else(|| tool.get("type"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// `--plan-only` asks for the plan schema, hides editing tools, prints the
+/// plan as Markdown, and saves the JSON with `-o`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn plan_only_prints_and_saves_the_plan() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", PLAN),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--plan-only")
+        .arg("-o")
+        .arg("plan.json")
+        .arg("-m")
+        .arg("gpt-5.1")
+        .arg("make fetching resilient")
+        .assert()
+        .success()
+        .stdout(contains("1. **Wrap the request**"))
+        .stdout(contains("- Retries may hide outages."));
+
+    let request = response_mock.single_request();
+    let payload = request.body_json();
+    assert_eq!(payload["text"]["format"]["schema"], plan_output_schema());
+    assert!(!tool_names(&payload).contains(&"apply_patch".to_string()));
+    assert!(
+        request
+            .message_input_texts("developer")
+            .iter()
+            .any(|text| text.starts_with("Plan mode is on."))
+    );
+    assert_eq!(
+        std::fs::read_to_string(test.cwd_path().join("plan.json"))?.trim(),
+        PLAN
+    );
+
+    Ok(())
+}
+
+/// `--from-plan` sends the saved plan, with PROMPT as extra instructions.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn from_plan_sends_the_plan_as_the_prompt() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::write(test.cwd_path().join("plan.json"), PLAN)?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Done"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--from-plan")
+        .arg("plan.json")
+        .arg("Use three retries.")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    let prompt = texts
+        .iter()
+        .find(|text| text.starts_with("Carry out the following plan."))
+        .expect("plan prompt");
+    assert!(prompt.contains("1. **Wrap the request**"));
+    assert!(prompt.ends_with("## Additional instructions\n\nUse three retries.\n"));
+
+    Ok(())
+}
+
+/// A file that is not a saved plan fails before any request is sent.
+#[test]
+fn from_plan_rejects_other_files() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::write(test.cwd_path().join("plan.json"), "# not json")?;
+
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--from-plan")
+        .arg("plan.json")
+        .assert()
+        .failure()
+        .stderr(contains("is not a plan saved by --plan-only"));
+
+    Ok(())
+}
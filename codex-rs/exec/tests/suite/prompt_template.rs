@@ -0,0 +1,64 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use predicates::str::contains;
+
+/// `--prompt-file` fills `{{NAME}}` from `--arg` and `{{env.NAME}}` from the
+/// environment before sending the prompt.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prompt_file_fills_args_and_environment() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::write(
+        test.cwd_path().join("task.md"),
+        "Deploy {{service}} to {{env.DEPLOY_TARGET}}.\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Deployed"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .env("DEPLOY_TARGET", "staging")
+        .arg("--skip-git-repo-check")
+        .arg("--prompt-file")
+        .arg("task.md")
+        .arg("--arg")
+        .arg("service=billing")
+        .arg("--arg")
+        .arg("team=core")
+        .assert()
+        .success()
+        .stderr(contains("--arg team is not used by the prompt."));
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(
+        texts
+            .iter()
+            .any(|text| text == "Deploy billing to staging.\n")
+    );
+
+    Ok(())
+}
+
+/// A placeholder without a value fails before any request is sent.
+#[test]
+fn missing_values_fail_the_run() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::write(test.cwd_path().join("task.md"), "Deploy {{service}}.")?;
+
+    test.cmd()
+        .arg("--skip-git-repo-check")
+        .arg("--prompt-file")
+        .arg("task.md")
+        .assert()
+        .failure()
+        .stderr(contains("the prompt needs values for {{service}}"));
+
+    Ok(())
+}
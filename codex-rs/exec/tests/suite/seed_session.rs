@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+use core_test_support::test_codex_exec::test_codex_exec;
+use std::path::Path;
+use std::path::PathBuf;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// The rollout files under `sessions_dir` whose contents include `marker`.
+fn session_files_containing(sessions_dir: &Path, marker: &str) -> Vec<PathBuf> {
+    WalkDir::new(sessions_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.path().to_path_buf())
+        .filter(|path| std::fs::read_to_string(path).is_ok_and(|content| content.contains(marker)))
+        .collect()
+}
+
+#[test]
+fn seed_session_starts_a_new_session_with_the_old_conversation() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cli_responses_fixture.sse");
+
+    let marker = format!("seed-original-{}", Uuid::new_v4());
+    test.cmd()
+        .env("CODEX_RS_SSE_FIXTURE", &fixture)
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(env!("CARGO_MANIFEST_DIR"))
+        .arg(format!("echo {marker}"))
+        .assert()
+        .success();
+
+    let sessions_dir = test.home_path().join("sessions");
+    let original = session_files_containing(&sessions_dir, &marker);
+    assert_eq!(original.len(), 1, "expected one session with {marker}");
+    let original = &original[0];
+    let original_content = std::fs::read_to_string(original)?;
+
+    let marker2 = format!("seed-followup-{}", Uuid::new_v4());
+    test.cmd()
+        .env("CODEX_RS_SSE_FIXTURE", &fixture)
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--skip-git-repo-check")
+        .arg("-C")
+        .arg(env!("CARGO_MANIFEST_DIR"))
+        .arg("--seed-session")
+        .arg(original)
+        .arg(format!("echo {marker2}"))
+        .assert()
+        .success();
+
+    let seeded = session_files_containing(&sessions_dir, &marker2);
+    assert_eq!(seeded.len(), 1, "expected one session with {marker2}");
+    assert_ne!(
+        &seeded[0], original,
+        "seeding must not append to the source"
+    );
+    assert!(std::fs::read_to_string(&seeded[0])?.contains(&marker));
+    assert_eq!(std::fs::read_to_string(original)?, original_content);
+    Ok(())
+}
+
+#[test]
+fn seed_session_rejects_an_unknown_session() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    test.cmd()
+        .env("OPENAI_BASE_URL", "http://unused.local")
+        .arg("--skip-git-repo-check")
+        .arg("--seed-session")
+        .arg("missing/rollout.jsonl")
+        .arg("continue")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "--seed-session missing/rollout.jsonl is neither a rollout file nor a session id",
+        ));
+    Ok(())
+}
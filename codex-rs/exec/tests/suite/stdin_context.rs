@@ -0,0 +1,63 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+
+/// Piped stdin is sent after the prompt as a `<stdin>` block.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn attaches_piped_stdin_to_the_prompt() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Looks good"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("review this diff")
+        .write_stdin("diff --git a/lib.rs b/lib.rs\n+fn added() {}\n")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(texts.iter().any(|text| text == "review this diff"));
+    assert!(
+        texts.contains(
+            &"<stdin>\ndiff --git a/lib.rs b/lib.rs\n+fn added() {}\n</stdin>".to_string()
+        )
+    );
+
+    Ok(())
+}
+
+/// `--no-stdin` leaves piped input alone.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_stdin_skips_piped_input() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "Done"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_once(&server, body).await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--no-stdin")
+        .arg("say hi")
+        .write_stdin("ignored input\n")
+        .assert()
+        .success();
+
+    let texts = response_mock.single_request().message_input_texts("user");
+    assert!(!texts.iter().any(|text| text.contains("<stdin>")));
+
+    Ok(())
+}
@@ -238,3 +238,59 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Runs `bash -c "echo hi > /dev/tcp/<address>/<port>"` under a read-only
+/// policy and reports whether it connected.
+#[expect(clippy::expect_used)]
+async fn sandboxed_connect(address: std::net::IpAddr, port: u16) -> bool {
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .args([
+            "--sandbox-policy-cwd",
+            std::env::temp_dir().to_str().expect("temp dir is UTF-8"),
+            "--sandbox-policy",
+            r#"{"type":"read-only"}"#,
+            "--",
+            "bash",
+            "-c",
+            &format!("echo hi > /dev/tcp/{address}/{port}"),
+        ])
+        .status()
+        .await
+        .expect("run codex-linux-sandbox")
+        .success()
+}
+
+/// The address of the interface that would carry traffic to the internet,
+/// found without sending anything. `None` on hosts without such a route.
+fn non_loopback_address() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    let address = socket.local_addr().ok()?.ip();
+    (!address.is_loopback() && !address.is_unspecified()).then_some(address)
+}
+
+/// Landlock can only limit TCP by port, which would let a command reach any
+/// host on the proxy's port, so the Linux sandbox blocks TCP entirely and a
+/// listener is unreachable on loopback and non-loopback addresses alike.
+#[tokio::test]
+#[expect(clippy::expect_used)]
+async fn sandbox_blocks_tcp_to_loopback_and_other_addresses() {
+    let listener = std::net::TcpListener::bind("0.0.0.0:0").expect("bind listener");
+    let port = listener.local_addr().expect("listener addr").port();
+
+    assert!(
+        !sandboxed_connect(std::net::Ipv4Addr::LOCALHOST.into(), port).await,
+        "connecting to the loopback listener should fail"
+    );
+    let Some(address) = non_loopback_address() else {
+        return;
+    };
+    assert!(
+        std::net::TcpStream::connect((address, port)).is_ok(),
+        "the listener should be reachable on {address} outside the sandbox"
+    );
+    assert!(
+        !sandboxed_connect(address, port).await,
+        "connecting to {address}:{port} should fail"
+    );
+}
@@ -204,7 +204,7 @@ async fn run_codex_tool_session_inner(
                         outgoing.send_response(request_id.clone(), result).await;
                         break;
                     }
-                    EventMsg::Warning(_) => {
+                    EventMsg::Warning(_) | EventMsg::TerminologyWarning(_) => {
                         continue;
                     }
                     EventMsg::ElicitationRequest(_) => {
@@ -292,6 +292,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
+                    | EventMsg::SearchHistoryResponse(_)
                     | EventMsg::PlanUpdate(_)
                     | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
@@ -309,6 +310,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::ContextCompacted(_)
+                    | EventMsg::ConfigReloaded(_)
+                    | EventMsg::SessionTitle(_)
+                    | EventMsg::RunLimitReached(_)
                     | EventMsg::DeprecationNotice(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
@@ -94,6 +94,7 @@ pub async fn run_main(
         .map_err(|e| {
             std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
         })?;
+    codex_core::default_client::set_network_config(config.network.clone());
 
     // Task: process incoming messages.
     let processor_handle = tokio::spawn({
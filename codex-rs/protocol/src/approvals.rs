@@ -37,6 +37,99 @@ impl From<Vec<String>> for ExecPolicyAmendment {
     }
 }
 
+/// A standing approval for commands run in one project, kept in the project
+/// trust store so Codex stops asking about them.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "match", rename_all = "snake_case")]
+#[ts(tag = "match")]
+pub enum CommandApprovalRule {
+    /// The command with exactly these arguments.
+    Exact { command: Vec<String> },
+    /// Commands starting with these arguments, so `cargo test` also covers
+    /// `cargo test -p core`.
+    Prefix { command: Vec<String> },
+    /// One glob per argument, where `*` matches any run of characters and `?`
+    /// any one character. A final `**` matches any remaining arguments.
+    Pattern { command: Vec<String> },
+}
+
+impl CommandApprovalRule {
+    pub fn command(&self) -> &[String] {
+        match self {
+            Self::Exact { command } | Self::Prefix { command } | Self::Pattern { command } => {
+                command
+            }
+        }
+    }
+
+    /// Whether the rule covers `argv`. A rule with no arguments, or a pattern
+    /// that is only `**`, covers nothing.
+    pub fn matches(&self, argv: &[String]) -> bool {
+        match self {
+            _ if self.command().is_empty() => false,
+            Self::Exact { command } => command == argv,
+            Self::Prefix { command } => argv.starts_with(command),
+            Self::Pattern { command } => {
+                let (patterns, rest_ok) = match command.split_last() {
+                    Some((last, patterns)) if last == "**" => (patterns, true),
+                    _ => (command.as_slice(), false),
+                };
+                if patterns.is_empty()
+                    || argv.len() < patterns.len()
+                    || (!rest_ok && argv.len() != patterns.len())
+                {
+                    return false;
+                }
+                patterns
+                    .iter()
+                    .zip(argv)
+                    .all(|(pattern, arg)| glob_matches(pattern, arg))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CommandApprovalRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Self::Exact { .. } => "exact",
+            Self::Prefix { .. } => "prefix",
+            Self::Pattern { .. } => "pattern",
+        };
+        write!(f, "{kind}: {}", self.command().join(" "))
+    }
+}
+
+/// Matches `text` against a glob of `*` and `?` wildcards.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was, and how much of `text` it has swallowed.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
@@ -93,3 +186,66 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn argv(command: &str) -> Vec<String> {
+        command.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn exact_and_prefix_rules() {
+        let exact = CommandApprovalRule::Exact {
+            command: argv("cargo test"),
+        };
+        assert!(exact.matches(&argv("cargo test")));
+        assert!(!exact.matches(&argv("cargo test -p core")));
+
+        let prefix = CommandApprovalRule::Prefix {
+            command: argv("cargo test"),
+        };
+        assert!(prefix.matches(&argv("cargo test")));
+        assert!(prefix.matches(&argv("cargo test -p core")));
+        assert!(!prefix.matches(&argv("cargo")));
+        assert!(!prefix.matches(&argv("cargo testing")));
+
+        assert!(!CommandApprovalRule::Prefix { command: vec![] }.matches(&argv("rm -rf /")));
+    }
+
+    #[test]
+    fn pattern_rules_glob_each_argument() {
+        let rule = CommandApprovalRule::Pattern {
+            command: argv("cargo test -p codex-*"),
+        };
+        assert!(rule.matches(&argv("cargo test -p codex-core")));
+        assert!(!rule.matches(&argv("cargo test -p other")));
+        assert!(!rule.matches(&argv("cargo test -p codex-core --release")));
+
+        let rule = CommandApprovalRule::Pattern {
+            command: argv("npm run ?est **"),
+        };
+        assert!(rule.matches(&argv("npm run test")));
+        assert!(rule.matches(&argv("npm run test -- --watch")));
+        assert!(!rule.matches(&argv("npm run build")));
+
+        let rule = CommandApprovalRule::Pattern {
+            command: argv("**"),
+        };
+        assert!(!rule.matches(&argv("rm -rf /")));
+    }
+
+    #[test]
+    fn rules_serialize_with_a_match_tag() {
+        let rule = CommandApprovalRule::Prefix {
+            command: argv("cargo test"),
+        };
+        assert_eq!(
+            serde_json::to_value(&rule).expect("serialize"),
+            serde_json::json!({ "match": "prefix", "command": ["cargo", "test"] })
+        );
+        assert_eq!(rule.to_string(), "prefix: cargo test");
+    }
+}
@@ -31,6 +31,7 @@ pub struct UserMessageItem {
 #[ts(tag = "type")]
 pub enum AgentMessageContent {
     Text { text: String },
+    Refusal { refusal: String },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema)]
@@ -102,9 +103,12 @@ impl AgentMessageItem {
         self.content
             .iter()
             .map(|c| match c {
-                AgentMessageContent::Text { text } => EventMsg::AgentMessage(AgentMessageEvent {
-                    message: text.clone(),
-                }),
+                AgentMessageContent::Text { text }
+                | AgentMessageContent::Refusal { refusal: text } => {
+                    EventMsg::AgentMessage(AgentMessageEvent {
+                        message: text.clone(),
+                    })
+                }
             })
             .collect()
     }
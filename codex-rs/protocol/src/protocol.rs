@@ -39,6 +39,7 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::CommandApprovalRule;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -141,6 +142,19 @@ pub enum Op {
         summary: Option<ReasoningSummaryConfig>,
     },
 
+    /// Grant a capability for the next turn only.
+    ///
+    /// The grant is applied on top of the persistent turn context when the
+    /// next `UserInput`/`UserTurn` starts a task and is dropped once that turn
+    /// completes, so the session reverts to its configured policy without any
+    /// further action. Grants must be issued while no task is running.
+    GrantTurnCapability { grant: TurnCapabilityGrant },
+
+    /// Turn plan mode on or off. While it is on, turns only get read-only
+    /// tools and the model is asked to reply with a plan instead of making
+    /// changes. Must be sent while no task is running.
+    SetPlanMode { enabled: bool },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -179,10 +193,21 @@ pub enum Op {
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
 
+    /// Search the history for entries containing `query`, ignoring case.
+    /// Reply is delivered via `EventMsg::SearchHistoryResponse`.
+    SearchHistoryRequest { query: String },
+
     /// Request the list of MCP tools available across all configured servers.
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Start an MCP server for the rest of this session only; nothing is
+    /// written to config. `command` is a program and its arguments, or a
+    /// single `http(s)://` URL for a streamable HTTP server. Progress is
+    /// reported with `EventMsg::McpStartupUpdate` and
+    /// `EventMsg::McpStartupComplete`.
+    AddMcpServer { name: String, command: Vec<String> },
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
@@ -225,6 +250,37 @@ pub enum Op {
 
     /// Request the list of available models.
     ListModels,
+
+    /// Rename the session. The server replies with
+    /// [`EventMsg::SessionTitle`], which is also recorded in the rollout.
+    SetSessionTitle { title: String },
+}
+
+/// A capability that can be granted for exactly one turn via
+/// [`Op::GrantTurnCapability`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TurnCapabilityGrant {
+    /// Allow outbound network access from sandboxed commands.
+    Network,
+    /// Enable a feature-gated tool, identified by its feature key (for
+    /// example `web_search_request`).
+    Tool { name: String },
+    /// Make an additional directory writable from within the sandbox.
+    /// Relative paths are resolved against the session `cwd`.
+    WritableRoot { path: PathBuf },
+}
+
+impl fmt::Display for TurnCapabilityGrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TurnCapabilityGrant::Network => f.write_str("network access"),
+            TurnCapabilityGrant::Tool { name } => write!(f, "tool `{name}`"),
+            TurnCapabilityGrant::WritableRoot { path } => {
+                write!(f, "write access to {}", path.display())
+            }
+        }
+    }
 }
 
 /// Determines the conditions under which the user is consulted to approve
@@ -505,6 +561,10 @@ pub enum EventMsg {
     /// indicates the task continued but the user should still be notified.
     Warning(WarningEvent),
 
+    /// Agent-authored text (a doc edit or commit message) uses terms that the
+    /// steering glossary bans or replaces with a preferred name.
+    TerminologyWarning(TerminologyWarningEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
@@ -514,6 +574,10 @@ pub enum EventMsg {
     /// Agent has completed all actions
     TaskComplete(TaskCompleteEvent),
 
+    /// A run limit from `codex exec --max-turns`, `--max-duration`, or
+    /// `--max-cost-usd` was reached and the task ended early.
+    RunLimitReached(RunLimitReachedEvent),
+
     /// Usage update for the current session, including totals and last turn.
     /// Optional means unknown — UIs should not display when `None`.
     TokenCount(TokenCountEvent),
@@ -544,6 +608,13 @@ pub enum EventMsg {
     /// Ack the client's configure message.
     SessionConfigured(SessionConfiguredEvent),
 
+    /// The config files changed on disk while the session was running.
+    ConfigReloaded(ConfigReloadedEvent),
+
+    /// The session's title was generated from the first exchange or set with
+    /// [`Op::SetSessionTitle`].
+    SessionTitle(SessionTitleEvent),
+
     /// Incremental MCP startup progress updates.
     McpStartupUpdate(McpStartupUpdateEvent),
 
@@ -604,6 +675,9 @@ pub enum EventMsg {
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
+    /// Response to SearchHistoryRequest.
+    SearchHistoryResponse(SearchHistoryResponseEvent),
+
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
@@ -801,6 +875,90 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TerminologyWarningEvent {
+    /// What was linted: a file path for doc edits, or `commit message`.
+    pub source: String,
+    pub violations: Vec<TerminologyViolation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TerminologyViolation {
+    /// The offending term exactly as it appears in the text.
+    pub term: String,
+    /// 1-based line within the linted text.
+    pub line: usize,
+    /// The glossary's preferred name, when the term is a known variant.
+    pub preferred: Option<String>,
+    /// Why the term is banned, when the glossary gives a reason.
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for TerminologyWarningEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "glossary terms in {}:", self.source)?;
+        for violation in &self.violations {
+            write!(f, "\n  {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TerminologyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: `{}`", self.line, self.term)?;
+        match (&self.preferred, &self.reason) {
+            (Some(preferred), _) => write!(f, " → use `{preferred}`"),
+            (None, Some(reason)) => write!(f, " is banned ({reason})"),
+            (None, None) => write!(f, " is banned"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SessionTitleEvent {
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ConfigReloadedEvent {
+    /// Changed keys (dotted paths such as `steering.max_bytes`) that now
+    /// apply to this session.
+    pub applied: Vec<String>,
+    /// Changed keys that only take effect in a new session.
+    pub requires_restart: Vec<String>,
+    /// Changed keys whose setting this session takes from a command-line
+    /// flag, a profile, or an in-session command instead, so the edit is
+    /// ignored.
+    pub overridden: Vec<String>,
+    /// The session's model after the reload.
+    pub model: String,
+    /// The session's reasoning effort after the reload.
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+}
+
+impl fmt::Display for ConfigReloadedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.applied.is_empty() {
+            parts.push(format!("applied {}", self.applied.join(", ")));
+        }
+        if !self.requires_restart.is_empty() {
+            parts.push(format!(
+                "restart to apply {}",
+                self.requires_restart.join(", ")
+            ));
+        }
+        if !self.overridden.is_empty() {
+            parts.push(format!(
+                "overridden for this session: {}",
+                self.overridden.join(", ")
+            ));
+        }
+        write!(f, "config reloaded: {}", parts.join("; "))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -809,6 +967,24 @@ pub struct TaskCompleteEvent {
     pub last_agent_message: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct RunLimitReachedEvent {
+    pub limit: RunLimit,
+    /// Model requests made in the session.
+    pub turns: u32,
+    pub elapsed_seconds: u64,
+    /// Estimated from `model_prices`; `None` when the model has no price.
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum RunLimit {
+    Turns,
+    Duration,
+    Cost,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskStartedEvent {
     pub model_context_window: Option<i64>,
@@ -895,6 +1071,25 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated split of the current prompt between injected guidance and
+    /// conversation history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_breakdown: Option<PromptTokenBreakdown>,
+}
+
+/// Approximate number of prompt tokens attributable to each source of
+/// context. Values are byte-based estimates, not tokenizer-accurate counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PromptTokenBreakdown {
+    /// Tokens spent on files loaded from the steering directories.
+    #[ts(type = "number")]
+    pub steering_tokens: i64,
+    /// Tokens spent on `AGENTS.md` and other user instructions.
+    #[ts(type = "number")]
+    pub agents_md_tokens: i64,
+    /// Tokens spent on everything else in the conversation history.
+    #[ts(type = "number")]
+    pub history_tokens: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1607,6 +1802,14 @@ pub struct GetHistoryEntryResponseEvent {
     pub entry: Option<HistoryEntry>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SearchHistoryResponseEvent {
+    /// The query this answers.
+    pub query: String,
+    /// Matching entries, newest first, one per distinct text.
+    pub entries: Vec<HistoryEntry>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
@@ -1676,6 +1879,10 @@ impl fmt::Display for McpAuthStatus {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
+    /// Custom slash commands from `.codex/commands` and
+    /// `$CODEX_HOME/commands`, invoked as `/name`.
+    #[serde(default)]
+    pub custom_commands: Vec<CustomPrompt>,
 }
 
 /// Response payload for `Op::ListSkills`.
@@ -1770,6 +1977,16 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved this command and wants `rule` saved in the project
+    /// trust store, so commands it matches in this project run without
+    /// asking, in this session and later ones.
+    ApprovedForProject { rule: CommandApprovalRule },
+
+    /// User has reviewed a proposed patch and approved only some of its hunks.
+    /// `patch` is the `apply_patch` input to apply instead, holding just the
+    /// accepted changes.
+    ApprovedWithModifiedPatch { patch: String },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -155,6 +155,8 @@ impl RmcpClient {
         })
     }
 
+    /// `http_client` carries the caller's proxy and TLS settings; default
+    /// headers are added on top of it.
     #[allow(clippy::too_many_arguments)]
     pub async fn new_streamable_http_client(
         server_name: &str,
@@ -163,6 +165,7 @@ impl RmcpClient {
         http_headers: Option<HashMap<String, String>>,
         env_http_headers: Option<HashMap<String, String>>,
         store_mode: OAuthCredentialsStoreMode,
+        http_client: reqwest::ClientBuilder,
     ) -> Result<Self> {
         let default_headers = build_default_headers(http_headers, env_http_headers)?;
 
@@ -184,6 +187,7 @@ impl RmcpClient {
                 initial_tokens,
                 store_mode,
                 default_headers.clone(),
+                http_client,
             )
             .await?;
             PendingTransport::StreamableHttpWithOAuth {
@@ -196,8 +200,7 @@ impl RmcpClient {
                 http_config = http_config.auth_header(bearer_token);
             }
 
-            let http_client =
-                apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+            let http_client = apply_default_headers(http_client, &default_headers).build()?;
 
             let transport = StreamableHttpClientTransport::with_client(http_client, http_config);
             PendingTransport::StreamableHttp { transport }
@@ -427,12 +430,12 @@ async fn create_oauth_transport_and_runtime(
     initial_tokens: StoredOAuthTokens,
     credentials_store: OAuthCredentialsStoreMode,
     default_headers: HeaderMap,
+    http_client: reqwest::ClientBuilder,
 ) -> Result<(
     StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
     OAuthPersistor,
 )> {
-    let http_client =
-        apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+    let http_client = apply_default_headers(http_client, &default_headers).build()?;
     let mut oauth_state = OAuthState::new(url.to_string(), Some(http_client.clone())).await?;
 
     oauth_state
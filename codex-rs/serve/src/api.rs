@@ -0,0 +1,347 @@
+//! The routes of `codex serve`. Request and response bodies are JSON; events
+//! are streamed as server-sent events whose data is the same `Event` JSON the
+//! app server sends.
+
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::Path;
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::HeaderValue;
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::header::CONTENT_TYPE;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::sse::Event as SseEvent;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
+use axum::routing::get;
+use axum::routing::post;
+use codex_core::config::ConfigOverrides;
+use codex_core::protocol::Op;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::user_input::UserInput;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::ServeState;
+use crate::session::ServedSession;
+
+type ApiResult = Result<Response, ApiError>;
+
+pub(crate) fn router(state: Arc<ServeState>) -> Router {
+    Router::new()
+        .route("/sessions", get(list_sessions).post(create_session))
+        .route("/sessions/{id}", get(get_session).delete(delete_session))
+        .route("/sessions/{id}/messages", post(send_message))
+        .route("/sessions/{id}/interrupt", post(interrupt))
+        .route("/sessions/{id}/events", get(stream_events))
+        .route("/sessions/{id}/approvals", get(list_approvals))
+        .route(
+            "/sessions/{id}/approvals/{approval_id}",
+            post(answer_approval),
+        )
+        .route("/sessions/{id}/diff", get(get_diff))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CreateSessionRequest {
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    sandbox: Option<SandboxMode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendMessageRequest {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ApprovalRequest {
+    decision: ReviewDecision,
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({ "error": self.message }).to_string();
+        (
+            self.status,
+            [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            body,
+        )
+            .into_response()
+    }
+}
+
+fn json_response(status: StatusCode, body: &impl Serialize) -> ApiResult {
+    let body = serde_json::to_string(body)
+        .map_err(|err| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok((
+        status,
+        [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+        body,
+    )
+        .into_response())
+}
+
+/// Parses a JSON request body; an empty body counts as `{}`.
+fn parse_body<T: DeserializeOwned>(body: &Bytes) -> Result<T, ApiError> {
+    let body: &[u8] = if body.is_empty() { b"{}" } else { body };
+    serde_json::from_slice(body).map_err(|err| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("invalid request body: {err}"),
+        )
+    })
+}
+
+fn find_session(state: &ServeState, id: &str) -> Result<Arc<ServedSession>, ApiError> {
+    ConversationId::from_string(id)
+        .ok()
+        .and_then(|id| state.sessions().get(&id).cloned())
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("no session {id}")))
+}
+
+async fn submit(session: &ServedSession, op: Op) -> Result<String, ApiError> {
+    session.conversation.submit(op).await.map_err(|err| {
+        ApiError::new(
+            StatusCode::CONFLICT,
+            format!("session {} is not accepting input: {err}", session.id),
+        )
+    })
+}
+
+async fn require_token(
+    State(state): State<Arc<ServeState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), state.token.as_bytes()));
+    if !authorized {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "missing or wrong bearer token")
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// Compares two byte strings without stopping at the first difference, so the
+/// response time does not reveal how much of the token was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn create_session(State(state): State<Arc<ServeState>>, body: Bytes) -> ApiResult {
+    let CreateSessionRequest {
+        cwd,
+        model,
+        approval_policy,
+        sandbox,
+    } = parse_body(&body)?;
+    let config = state
+        .session_config(ConfigOverrides {
+            model,
+            cwd,
+            approval_policy,
+            sandbox_mode: sandbox,
+            ..Default::default()
+        })
+        .await
+        .map_err(|err| ApiError::new(StatusCode::BAD_REQUEST, err.to_string()))?;
+    let new_conversation = state
+        .conversation_manager
+        .new_conversation(config)
+        .await
+        .map_err(|err| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to start a session: {err}"),
+            )
+        })?;
+    let session = ServedSession::spawn(
+        new_conversation.conversation,
+        new_conversation.session_configured,
+    );
+    state.sessions().insert(session.id, session.clone());
+    json_response(StatusCode::CREATED, &session.summary())
+}
+
+async fn list_sessions(State(state): State<Arc<ServeState>>) -> ApiResult {
+    let mut sessions: Vec<_> = state
+        .sessions()
+        .values()
+        .map(|session| session.summary())
+        .collect();
+    sessions.sort_by_key(|session| session.id.to_string());
+    json_response(StatusCode::OK, &json!({ "sessions": sessions }))
+}
+
+async fn get_session(State(state): State<Arc<ServeState>>, Path(id): Path<String>) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    json_response(StatusCode::OK, &session.summary())
+}
+
+async fn delete_session(State(state): State<Arc<ServeState>>, Path(id): Path<String>) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    state.sessions().remove(&session.id);
+    session.shutdown().await;
+    state
+        .conversation_manager
+        .remove_conversation(&session.id)
+        .await;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn send_message(
+    State(state): State<Arc<ServeState>>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    let SendMessageRequest { text } = parse_body(&body)?;
+    if text.trim().is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "text is empty"));
+    }
+    let turn_id = submit(
+        &session,
+        Op::UserInput {
+            items: vec![UserInput::Text { text }],
+        },
+    )
+    .await?;
+    json_response(StatusCode::ACCEPTED, &json!({ "turn_id": turn_id }))
+}
+
+async fn interrupt(State(state): State<Arc<ServeState>>, Path(id): Path<String>) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    submit(&session, Op::Interrupt).await?;
+    Ok(StatusCode::ACCEPTED.into_response())
+}
+
+/// Streams the session's events from the oldest one kept, or from after the
+/// one named by `Last-Event-ID` when a client reconnects. A `Last-Event-ID`
+/// older than the kept events gets 410 Gone, so the client can start over.
+async fn stream_events(
+    State(state): State<Arc<ServeState>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    let first = match headers.get("last-event-id") {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .map(|last| last + 1)
+            .ok_or_else(|| {
+                ApiError::new(StatusCode::BAD_REQUEST, "Last-Event-ID must be a number")
+            })?,
+        None => session.first_buffered_event(),
+    };
+    if first < session.first_buffered_event() {
+        return Err(ApiError::new(
+            StatusCode::GONE,
+            format!("events before {first} are no longer kept; reconnect without Last-Event-ID"),
+        ));
+    }
+    let events = ServedSession::events_from(session, first).map(|(number, event)| {
+        let data = serde_json::to_string(&*event).unwrap_or_default();
+        Ok::<_, Infallible>(
+            SseEvent::default()
+                .id(number.to_string())
+                .event(event.msg.to_string())
+                .data(data),
+        )
+    });
+    Ok(Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+async fn list_approvals(State(state): State<Arc<ServeState>>, Path(id): Path<String>) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    json_response(
+        StatusCode::OK,
+        &json!({ "approvals": session.pending_approvals() }),
+    )
+}
+
+async fn answer_approval(
+    State(state): State<Arc<ServeState>>,
+    Path((id, approval_id)): Path<(String, String)>,
+    body: Bytes,
+) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    let ApprovalRequest { decision } = parse_body(&body)?;
+    let op = session
+        .take_approval(&approval_id, decision)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                format!("session {id} is not waiting on approval {approval_id}"),
+            )
+        })?;
+    submit(&session, op).await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn get_diff(State(state): State<Arc<ServeState>>, Path(id): Path<String>) -> ApiResult {
+    let session = find_session(&state, &id)?;
+    Ok((
+        [(CONTENT_TYPE, HeaderValue::from_static("text/x-diff"))],
+        session.diff(),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_time_eq_compares_whole_tokens() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+}
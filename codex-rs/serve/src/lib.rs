@@ -0,0 +1,159 @@
+//! `codex serve`: drive Codex sessions over a local HTTP API.
+//!
+//! Editors and internal tools create sessions, send messages, stream events
+//! over server-sent events, answer approvals, and fetch the diff with plain
+//! HTTP instead of embedding this crate or speaking MCP. Sessions run on the
+//! same `ConversationManager` as the TUI and `codex exec`, and every request
+//! must carry the server's bearer token. The API is documented in
+//! `docs/serve.md`.
+
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::SessionSource;
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+mod api;
+mod session;
+
+use crate::session::ServedSession;
+
+/// Serve a local HTTP API for driving Codex sessions.
+#[derive(Debug, clap::Parser)]
+pub struct ServeCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Address to listen on. Anything that can reach it and knows the token
+    /// can run commands as you, so keep it on loopback.
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub host: IpAddr,
+
+    /// Port to listen on; 0 picks a free one.
+    #[arg(long, default_value_t = 4000)]
+    pub port: u16,
+
+    /// Bearer token clients must send. A random one is generated and printed
+    /// when unset.
+    #[arg(long, env = "CODEX_SERVE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+}
+
+pub(crate) struct ServeState {
+    conversation_manager: ConversationManager,
+    cli_kv_overrides: Vec<(String, toml::Value)>,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    token: String,
+    sessions: Mutex<HashMap<ConversationId, Arc<ServedSession>>>,
+}
+
+impl ServeState {
+    /// Loads the configuration for a new session: the server's `-c`
+    /// overrides, then the settings the client asked for.
+    async fn session_config(&self, overrides: ConfigOverrides) -> std::io::Result<Config> {
+        Config::load_with_cli_overrides_and_harness_overrides(
+            self.cli_kv_overrides.clone(),
+            ConfigOverrides {
+                codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+                ..overrides
+            },
+        )
+        .await
+    }
+
+    fn sessions(&self) -> std::sync::MutexGuard<'_, HashMap<ConversationId, Arc<ServedSession>>> {
+        match self.sessions.lock() {
+            Ok(sessions) => sessions,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+pub async fn run_main(
+    cli: ServeCli,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let ServeCli {
+        config_overrides,
+        host,
+        port,
+        token,
+    } = cli;
+    let cli_kv_overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        cli_kv_overrides.clone(),
+        ConfigOverrides {
+            codex_linux_sandbox_exe: codex_linux_sandbox_exe.clone(),
+            ..Default::default()
+        },
+    )
+    .await
+    .context("failed to load configuration")?;
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    // Like the app server's, these sessions are interactive: `codex resume`
+    // lists them.
+    let conversation_manager = ConversationManager::new(auth_manager, SessionSource::VSCode);
+
+    let print_token = token.is_none();
+    let token = token.unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    let listener = TcpListener::bind((host, port))
+        .await
+        .with_context(|| format!("failed to listen on {host}:{port}"))?;
+    let addr = listener.local_addr()?;
+    #[allow(clippy::print_stderr)]
+    {
+        eprintln!("codex serve listening on http://{addr}");
+        if print_token {
+            eprintln!("Authorization: Bearer {token}");
+        }
+    }
+
+    let state = Arc::new(ServeState {
+        conversation_manager,
+        cli_kv_overrides,
+        codex_linux_sandbox_exe,
+        token,
+        sessions: Mutex::new(HashMap::new()),
+    });
+    tokio::select! {
+        result = axum::serve(listener, api::router(state.clone())).into_future() => result?,
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    // Event streams never end on their own, so rather than wait for
+    // connections to drain, shut the sessions down so their rollouts are
+    // complete.
+    let sessions: Vec<_> = state
+        .sessions()
+        .drain()
+        .map(|(_, session)| session)
+        .collect();
+    for session in sessions {
+        session.shutdown().await;
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use clap::Parser;
+use codex_arg0::arg0_dispatch_or_else;
+use codex_serve::ServeCli;
+use codex_serve::run_main;
+
+fn main() -> anyhow::Result<()> {
+    arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
+        run_main(ServeCli::parse(), codex_linux_sandbox_exe).await
+    })
+}
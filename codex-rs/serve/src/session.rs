@@ -0,0 +1,321 @@
+//! A conversation served over HTTP: its latest events are kept so clients
+//! can stream from the start or pick up where they left off, along with the
+//! approvals it is waiting on and the diffs of its turns.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use codex_core::CodexConversation;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionConfiguredEvent;
+use futures::Stream;
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::warn;
+
+/// What a session is doing, as reported by `GET /sessions/{id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SessionStatus {
+    Idle,
+    Running,
+    WaitingForApproval,
+    Closed,
+}
+
+/// How many events a session keeps for replay. Older ones are dropped, and a
+/// client that asks to resume before them is told to start over.
+const MAX_BUFFERED_EVENTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SessionSummary {
+    pub id: ConversationId,
+    pub model: String,
+    pub cwd: PathBuf,
+    pub rollout_path: PathBuf,
+    pub status: SessionStatus,
+    /// Events emitted so far; the next one has this `id` on the event stream.
+    pub event_count: usize,
+}
+
+pub(crate) struct ServedSession {
+    pub(crate) id: ConversationId,
+    pub(crate) conversation: Arc<CodexConversation>,
+    model: String,
+    cwd: PathBuf,
+    state: Mutex<SessionState>,
+    /// Bumped after each recorded event so event streams wake up.
+    events_changed: watch::Sender<usize>,
+}
+
+/// The most recent events of a session, numbered from the first one it
+/// emitted.
+#[derive(Default)]
+struct EventBuffer {
+    events: VecDeque<Arc<Event>>,
+    /// The number of the oldest kept event.
+    first: usize,
+}
+
+impl EventBuffer {
+    fn push(&mut self, event: Arc<Event>) {
+        if self.events.len() == MAX_BUFFERED_EVENTS {
+            self.events.pop_front();
+            self.first += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// The number the next event will get.
+    fn end(&self) -> usize {
+        self.first + self.events.len()
+    }
+
+    fn get(&self, number: usize) -> Option<&Arc<Event>> {
+        self.events.get(number.checked_sub(self.first)?)
+    }
+}
+
+#[derive(Default)]
+struct SessionState {
+    events: EventBuffer,
+    /// Keyed by the id of the request event, which is also the id its answer
+    /// is submitted with.
+    pending_approvals: BTreeMap<String, (ApprovalKind, Arc<Event>)>,
+    running: bool,
+    closed: bool,
+    /// The diff of each finished turn that changed files.
+    turn_diffs: Vec<String>,
+    /// The latest diff of the running turn.
+    current_diff: Option<String>,
+}
+
+impl ServedSession {
+    /// Starts recording the events of `conversation`, beginning with the
+    /// `session_configured` event it was created with.
+    pub(crate) fn spawn(
+        conversation: Arc<CodexConversation>,
+        session_configured: SessionConfiguredEvent,
+    ) -> Arc<Self> {
+        let (events_changed, _) = watch::channel(0);
+        let session = Arc::new(Self {
+            id: session_configured.session_id,
+            conversation,
+            model: session_configured.model.clone(),
+            cwd: session_configured.cwd.clone(),
+            state: Mutex::new(SessionState::default()),
+            events_changed,
+        });
+        session.record(Event {
+            id: String::new(),
+            msg: EventMsg::SessionConfigured(session_configured),
+        });
+
+        let pump = session.clone();
+        tokio::spawn(async move {
+            loop {
+                match pump.conversation.next_event().await {
+                    Ok(event) => {
+                        let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+                        pump.record(event);
+                        if shutdown {
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        warn!("session {} stopped emitting events: {err}", pump.id);
+                        break;
+                    }
+                }
+            }
+            pump.state().closed = true;
+            pump.events_changed.send_modify(|count| *count += 1);
+        });
+        session
+    }
+
+    fn state(&self) -> MutexGuard<'_, SessionState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn record(&self, event: Event) {
+        let event = Arc::new(event);
+        let count = {
+            let mut state = self.state();
+            match &event.msg {
+                EventMsg::TaskStarted(_) => state.running = true,
+                EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                    state.running = false;
+                    state.pending_approvals.clear();
+                    if let Some(diff) = state.current_diff.take() {
+                        state.turn_diffs.push(diff);
+                    }
+                }
+                EventMsg::ExecApprovalRequest(_) => {
+                    state
+                        .pending_approvals
+                        .insert(event.id.clone(), (ApprovalKind::Exec, event.clone()));
+                }
+                EventMsg::ApplyPatchApprovalRequest(_) => {
+                    state
+                        .pending_approvals
+                        .insert(event.id.clone(), (ApprovalKind::Patch, event.clone()));
+                }
+                EventMsg::TurnDiff(diff) => {
+                    state.current_diff = Some(diff.unified_diff.clone());
+                }
+                _ => {}
+            }
+            state.events.push(event);
+            state.events.end()
+        };
+        self.events_changed.send_replace(count);
+    }
+
+    pub(crate) fn summary(&self) -> SessionSummary {
+        let state = self.state();
+        let status = if state.closed {
+            SessionStatus::Closed
+        } else if !state.pending_approvals.is_empty() {
+            SessionStatus::WaitingForApproval
+        } else if state.running {
+            SessionStatus::Running
+        } else {
+            SessionStatus::Idle
+        };
+        SessionSummary {
+            id: self.id,
+            model: self.model.clone(),
+            cwd: self.cwd.clone(),
+            rollout_path: self.conversation.rollout_path(),
+            status,
+            event_count: state.events.end(),
+        }
+    }
+
+    /// The number of the oldest event still kept for replay.
+    pub(crate) fn first_buffered_event(&self) -> usize {
+        self.state().events.first
+    }
+
+    pub(crate) fn pending_approvals(&self) -> Vec<Event> {
+        self.state()
+            .pending_approvals
+            .values()
+            .map(|(_, event)| Event::clone(event))
+            .collect()
+    }
+
+    /// The op that answers approval request `approval_id`, or `None` when
+    /// the session is not waiting on it.
+    pub(crate) fn take_approval(&self, approval_id: &str, decision: ReviewDecision) -> Option<Op> {
+        let (kind, _) = self.state().pending_approvals.remove(approval_id)?;
+        let id = approval_id.to_string();
+        Some(match kind {
+            ApprovalKind::Exec => Op::ExecApproval { id, decision },
+            ApprovalKind::Patch => Op::PatchApproval { id, decision },
+        })
+    }
+
+    /// Every file change of the session so far as a unified diff, one turn
+    /// after another.
+    pub(crate) fn diff(&self) -> String {
+        let state = self.state();
+        state
+            .turn_diffs
+            .iter()
+            .chain(state.current_diff.as_ref())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Asks the session to shut down and waits until it has.
+    pub(crate) async fn shutdown(&self) {
+        let mut changed = self.events_changed.subscribe();
+        if let Err(err) = self.conversation.submit(Op::Shutdown).await {
+            warn!("failed to shut down session {}: {err}", self.id);
+            return;
+        }
+        loop {
+            let closed = self.state().closed;
+            if closed || changed.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// The events from number `first` on, then the live ones until the
+    /// session shuts down. The stream ends early if a client falls so far
+    /// behind that the events it needs next have been dropped.
+    pub(crate) fn events_from(
+        session: Arc<Self>,
+        first: usize,
+    ) -> impl Stream<Item = (usize, Arc<Event>)> {
+        let changed = session.events_changed.subscribe();
+        futures::stream::unfold(
+            (first, changed, session),
+            |(next, mut changed, session)| async move {
+                loop {
+                    changed.borrow_and_update();
+                    let (event, closed) = {
+                        let state = session.state();
+                        if next < state.events.first {
+                            return None;
+                        }
+                        (state.events.get(next).cloned(), state.closed)
+                    };
+                    if let Some(event) = event {
+                        return Some(((next, event), (next + 1, changed, session)));
+                    }
+                    if closed || changed.changed().await.is_err() {
+                        return None;
+                    }
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn event(id: usize) -> Arc<Event> {
+        Arc::new(Event {
+            id: id.to_string(),
+            msg: EventMsg::ShutdownComplete,
+        })
+    }
+
+    #[test]
+    fn event_buffer_drops_the_oldest_events() {
+        let mut buffer = EventBuffer::default();
+        for id in 0..MAX_BUFFERED_EVENTS + 2 {
+            buffer.push(event(id));
+        }
+
+        assert_eq!(buffer.first, 2);
+        assert_eq!(buffer.end(), MAX_BUFFERED_EVENTS + 2);
+        assert!(buffer.get(1).is_none());
+        assert_eq!(buffer.get(2).map(|event| event.id.as_str()), Some("2"));
+        assert!(buffer.get(MAX_BUFFERED_EVENTS + 2).is_none());
+    }
+}
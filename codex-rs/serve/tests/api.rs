@@ -0,0 +1,216 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::path::Path;
+use std::process::Child;
+use std::process::ChildStderr;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+
+const TOKEN: &str = "test-token";
+
+/// A running `codex-serve`, killed when dropped.
+struct Server {
+    child: Child,
+    /// Kept open so the server can keep logging to stderr.
+    _stderr: BufReader<ChildStderr>,
+    base_url: String,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn start_server(codex_home: &Path, model_server_uri: &str) -> Result<Server> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{model_server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )?;
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("codex-serve"))
+        .args(["--port", "0", "--token", TOKEN])
+        .env("CODEX_HOME", codex_home)
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stderr = child.stderr.take().context("stderr is piped")?;
+    let mut stderr = BufReader::new(stderr);
+    let mut line = String::new();
+    stderr.read_line(&mut line)?;
+    let base_url = line
+        .trim()
+        .strip_prefix("codex serve listening on ")
+        .with_context(|| format!("unexpected first line: {line}"))?
+        .to_string();
+    Ok(Server {
+        child,
+        _stderr: stderr,
+        base_url,
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn runs_a_turn_and_streams_its_events() -> Result<()> {
+    let model_server = start_mock_server().await;
+    mount_sse_once(
+        &model_server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "Hello from the API"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let codex_home = TempDir::new()?;
+    let cwd = TempDir::new()?;
+    let server = start_server(codex_home.path(), &model_server.uri())?;
+    let client = reqwest::Client::new();
+
+    let unauthorized = client
+        .get(format!("{}/sessions", server.base_url))
+        .send()
+        .await?;
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+    let created: Value = client
+        .post(format!("{}/sessions", server.base_url))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "cwd": cwd.path() }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    assert_eq!(created["model"], "mock-model");
+    let session_url = format!(
+        "{}/sessions/{}",
+        server.base_url,
+        created["id"].as_str().context("session id")?
+    );
+
+    let sent = client
+        .post(format!("{session_url}/messages"))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "text": "hello" }))
+        .send()
+        .await?;
+    assert_eq!(sent.status(), StatusCode::ACCEPTED);
+
+    let mut events = client
+        .get(format!("{session_url}/events"))
+        .bearer_auth(TOKEN)
+        .send()
+        .await?
+        .error_for_status()?;
+    let mut stream = String::new();
+    tokio::time::timeout(Duration::from_secs(20), async {
+        while !stream.contains("event: task_complete") {
+            let Some(chunk) = events.chunk().await? else {
+                break;
+            };
+            stream.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        anyhow::Ok(())
+    })
+    .await??;
+    assert!(
+        stream.starts_with("id: 0\nevent: session_configured\n"),
+        "{stream}"
+    );
+    assert!(stream.contains("Hello from the API"), "{stream}");
+
+    let session: Value = client
+        .get(&session_url)
+        .bearer_auth(TOKEN)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(session["status"], "idle");
+
+    let deleted = client
+        .delete(&session_url)
+        .bearer_auth(TOKEN)
+        .send()
+        .await?;
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    let missing = client.get(&session_url).bearer_auth(TOKEN).send().await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejects_unknown_approvals() -> Result<()> {
+    let model_server = start_mock_server().await;
+    let codex_home = TempDir::new()?;
+    let server = start_server(codex_home.path(), &model_server.uri())?;
+    let client = reqwest::Client::new();
+
+    let created: Value = client
+        .post(format!("{}/sessions", server.base_url))
+        .bearer_auth(TOKEN)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let session_url = format!(
+        "{}/sessions/{}",
+        server.base_url,
+        created["id"].as_str().context("session id")?
+    );
+
+    let answered = client
+        .post(format!("{session_url}/approvals/7"))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "decision": "approved" }))
+        .send()
+        .await?;
+    assert_eq!(answered.status(), StatusCode::NOT_FOUND);
+    let body: Value = answered.json().await?;
+    assert_eq!(
+        body["error"],
+        format!(
+            "session {} is not waiting on approval 7",
+            created["id"].as_str().context("session id")?
+        )
+    );
+
+    let bad_decision = client
+        .post(format!("{session_url}/approvals/7"))
+        .bearer_auth(TOKEN)
+        .json(&json!({ "decision": "maybe" }))
+        .send()
+        .await?;
+    assert_eq!(bad_decision.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
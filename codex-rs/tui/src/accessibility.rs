@@ -0,0 +1,20 @@
+//! Screen-reader friendly output (`tui.accessible`).
+//!
+//! Screen readers read the terminal as text, so in this mode the TUI leaves
+//! out what only makes sense visually: animations (off through
+//! `Config::animations`), card borders, and the plan side pane. Selected rows
+//! say so in words rather than only in color, and the chat widget announces
+//! state changes as plain lines in the transcript.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
@@ -1,9 +1,10 @@
+use crate::accessibility;
 use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
 use crate::chatwidget::ChatWidget;
-use crate::diff_render::DiffSummary;
+use crate::composer_draft::ComposerDraft;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
@@ -14,6 +15,11 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
+use crate::session_tabs::ParkedTab;
+use crate::session_tabs::SessionTabs;
+use crate::session_tabs::TabId;
+use crate::terminal_palette::default_bg;
+use crate::theme;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -23,8 +29,10 @@ use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::types::HistoryPersistence;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
+use codex_core::git_info::git_status_summary;
 use codex_core::openai_models::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::openai_models::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
 use codex_core::openai_models::models_manager::ModelsManager;
@@ -44,9 +52,14 @@ use color_eyre::eyre::WrapErr;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
+use ratatui::layout::Rect;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
 use ratatui::widgets::Wrap;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -116,6 +129,15 @@ fn emit_skill_load_warnings(app_event_tx: &AppEventSender, errors: &[SkillErrorI
     }
 }
 
+/// Recomputes the git line for a tab in the background, sending the result
+/// through `app_event_tx`, which is tagged with that tab.
+fn spawn_git_status_refresh(cwd: PathBuf, app_event_tx: AppEventSender) {
+    tokio::spawn(async move {
+        let summary = git_status_summary(&cwd).await;
+        app_event_tx.send(AppEvent::GitStatusUpdated(summary));
+    });
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct SessionSummary {
     usage_line: String,
@@ -316,9 +338,121 @@ pub(crate) struct App {
 
     // One-shot suppression of the next world-writable scan after user confirmation.
     skip_world_writable_scan_once: bool,
+
+    /// Session tabs other than the one on screen, whose state is in the
+    /// fields above.
+    pub(crate) tabs: SessionTabs,
+
+    /// Where the unsent composer text is saved; `None` when history
+    /// persistence is off.
+    composer_draft: Option<ComposerDraft>,
 }
 
 impl App {
+    /// Switches to `theme` and repaints the whole viewport: the terminal only
+    /// rewrites cells whose buffer contents changed, and a theme changes none.
+    fn redraw_with_theme(tui: &mut tui::Tui, theme: theme::Theme) -> Result<()> {
+        theme::set_current(theme);
+        tui.terminal.clear()?;
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    /// Puts `tab` on screen and parks the tab that was showing. The screen
+    /// and scrollback are replaced with the new tab's transcript, and events
+    /// it held back while parked are handled now.
+    fn show_tab(&mut self, tui: &mut tui::Tui, tab: ParkedTab) -> Result<()> {
+        let ParkedTab {
+            id,
+            chat_widget,
+            config,
+            current_model,
+            file_search,
+            transcript_cells,
+            has_emitted_history_lines,
+            suppress_shutdown_complete,
+            pending_events,
+            needs_attention: _,
+        } = tab;
+        let outgoing = ParkedTab {
+            id: self.tabs.active(),
+            chat_widget: std::mem::replace(&mut self.chat_widget, chat_widget),
+            config: std::mem::replace(&mut self.config, config),
+            current_model: std::mem::replace(&mut self.current_model, current_model),
+            file_search: std::mem::replace(&mut self.file_search, file_search),
+            transcript_cells: std::mem::replace(&mut self.transcript_cells, transcript_cells),
+            has_emitted_history_lines: std::mem::replace(
+                &mut self.has_emitted_history_lines,
+                has_emitted_history_lines,
+            ),
+            suppress_shutdown_complete: std::mem::replace(
+                &mut self.suppress_shutdown_complete,
+                suppress_shutdown_complete,
+            ),
+            pending_events: Vec::new(),
+            needs_attention: false,
+        };
+        self.tabs.park(outgoing, id);
+        self.backtrack = BacktrackState::default();
+        self.deferred_history_lines.clear();
+
+        tui.clear_history()?;
+        self.render_transcript_once(tui);
+        for event in pending_events {
+            self.app_event_tx.send(event);
+        }
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    /// Handles an event from a tab that is not on screen. Its conversation
+    /// keeps running: history goes to its transcript, and anything that needs
+    /// the screen waits until the tab is shown.
+    fn handle_parked_tab_event(&mut self, id: TabId, event: AppEvent) {
+        match event {
+            AppEvent::StartCommitAnimation | AppEvent::StopCommitAnimation => {
+                // The animation thread ticks every tab.
+                self.app_event_tx.send(event);
+                return;
+            }
+            AppEvent::FromTab { .. } => return,
+            _ => {}
+        }
+        // Events from a tab that was closed are dropped.
+        let Some(tab) = self.tabs.get_mut(id) else {
+            return;
+        };
+        match event {
+            AppEvent::InsertHistoryCell(cell) => {
+                tab.transcript_cells.push(cell.into());
+            }
+            AppEvent::CodexEvent(event) => {
+                if tab.suppress_shutdown_complete && matches!(event.msg, EventMsg::ShutdownComplete)
+                {
+                    tab.suppress_shutdown_complete = false;
+                    return;
+                }
+                if matches!(
+                    event.msg,
+                    EventMsg::ExecApprovalRequest(_)
+                        | EventMsg::ApplyPatchApprovalRequest(_)
+                        | EventMsg::ElicitationRequest(_)
+                        | EventMsg::TaskComplete(_)
+                        | EventMsg::Error(_)
+                ) {
+                    tab.needs_attention = true;
+                }
+                tab.chat_widget.handle_codex_event(event);
+            }
+            AppEvent::CodexOp(op) => tab.chat_widget.submit_op(op),
+            AppEvent::RefreshGitStatus => {
+                spawn_git_status_refresh(tab.config.cwd.clone(), self.app_event_tx.for_tab(id));
+            }
+            AppEvent::GitStatusUpdated(summary) => tab.chat_widget.set_git_status(summary),
+            event => tab.pending_events.push(event),
+        }
+    }
+
     async fn shutdown_current_conversation(&mut self) {
         if let Some(conversation_id) = self.chat_widget.conversation_id() {
             self.suppress_shutdown_complete = true;
@@ -342,6 +476,18 @@ impl App {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
         let app_event_tx = AppEventSender::new(app_event_tx);
+        crate::render::code_highlight::set_syntax_highlighting(config.syntax_highlighting);
+        if config.tui_mouse
+            && let Err(err) = tui.set_mouse_capture(true)
+        {
+            tracing::warn!("failed to enable mouse capture: {err}");
+        }
+        crate::file_citation::set_link_target(config.tui_file_hyperlinks.then(|| {
+            crate::file_citation::LinkTarget {
+                cwd: config.cwd.clone(),
+                opener: config.file_opener,
+            }
+        }));
 
         let conversation_manager = Arc::new(ConversationManager::new(
             auth_manager.clone(),
@@ -367,6 +513,7 @@ impl App {
         }
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let tabs = SessionTabs::default();
         let model_family = conversation_manager
             .get_models_manager()
             .construct_model_family(model.as_str(), &config)
@@ -376,7 +523,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: app_event_tx.for_tab(tabs.active()),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -402,7 +549,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: app_event_tx.for_tab(tabs.active()),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -418,9 +565,30 @@ impl App {
                     resumed.session_configured,
                 )
             }
+            ResumeSelection::Attach(socket_path) => {
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: app_event_tx.for_tab(tabs.active()),
+                    initial_prompt: None,
+                    initial_images: Vec::new(),
+                    enhanced_keys_supported,
+                    auth_manager: auth_manager.clone(),
+                    models_manager: conversation_manager.get_models_manager(),
+                    feedback: feedback.clone(),
+                    is_first_run,
+                    model_family: model_family.clone(),
+                };
+                ChatWidget::new_attached(init, socket_path)
+            }
         };
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
+        accessibility::set_enabled(config.tui_accessible);
+        match theme::resolve(&config.tui_theme, &config.tui_themes, default_bg()) {
+            Ok(theme) => theme::set_current(theme),
+            Err(err) => chat_widget.add_error_message(format!("Invalid tui.theme: {err}")),
+        }
 
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         #[cfg(not(debug_assertions))]
@@ -446,8 +614,20 @@ impl App {
             pending_update_action: None,
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
+            tabs,
+            composer_draft: None,
         };
 
+        if matches!(app.config.history.persistence, HistoryPersistence::SaveAll) {
+            let mut draft = ComposerDraft::new(&app.config.codex_home);
+            if initial_prompt.is_none()
+                && let Some(text) = draft.take(&app.config.cwd)
+            {
+                app.chat_widget.set_composer_text(text);
+            }
+            app.composer_draft = Some(draft);
+        }
+
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
         #[cfg(target_os = "windows")]
         {
@@ -497,6 +677,7 @@ impl App {
                 app.handle_tui_event(tui, event).await?
             }
         } {}
+        app.save_composer_draft();
         tui.terminal.clear()?;
         Ok(AppExitInfo {
             token_usage: app.token_usage(),
@@ -516,6 +697,7 @@ impl App {
             match event {
                 TuiEvent::Key(key_event) => {
                     self.handle_key_event(tui, key_event).await;
+                    self.save_composer_draft();
                 }
                 TuiEvent::Paste(pasted) => {
                     // Many terminals convert newlines to \r when pasting (e.g., iTerm2),
@@ -524,6 +706,10 @@ impl App {
                     // [iTerm2]: https://github.com/gnachman/iTerm2/blob/5d0c0d9f68523cbd0494dad5422998964a2ecd8d/sources/iTermPasteHelper.m#L206-L216
                     let pasted = pasted.replace("\r", "\n");
                     self.chat_widget.handle_paste(pasted);
+                    self.save_composer_draft();
+                }
+                TuiEvent::Mouse(mouse_event) => {
+                    self.handle_mouse_event(tui, mouse_event)?;
                 }
                 TuiEvent::Draw => {
                     self.chat_widget.maybe_post_pending_notification(tui);
@@ -533,11 +719,24 @@ impl App {
                     {
                         return Ok(true);
                     }
+                    // With more than one tab, a row above the chat lists them.
+                    let tab_bar =
+                        (self.tabs.len() > 1).then(|| self.tabs.tab_bar(&self.config.cwd));
+                    let tab_bar_height = u16::from(tab_bar.is_some());
                     tui.draw(
-                        self.chat_widget.desired_height(tui.terminal.size()?.width),
+                        self.chat_widget
+                            .desired_height(tui.terminal.size()?.width)
+                            .saturating_add(tab_bar_height),
                         |frame| {
-                            self.chat_widget.render(frame.area(), frame.buffer);
-                            if let Some((x, y)) = self.chat_widget.cursor_pos(frame.area()) {
+                            let mut area = frame.area();
+                            if let Some(tab_bar) = tab_bar {
+                                let bar = Rect::new(area.x, area.y, area.width, 1);
+                                tab_bar.render(bar, frame.buffer);
+                                area.y += tab_bar_height;
+                                area.height = area.height.saturating_sub(tab_bar_height);
+                            }
+                            self.chat_widget.render(area, frame.buffer);
+                            if let Some((x, y)) = self.chat_widget.cursor_pos(area) {
                                 frame.set_cursor_position((x, y));
                             }
                         },
@@ -549,6 +748,14 @@ impl App {
     }
 
     async fn handle_event(&mut self, tui: &mut tui::Tui, event: AppEvent) -> Result<bool> {
+        let event = match event {
+            AppEvent::FromTab { tab, event } if tab == self.tabs.active() => *event,
+            AppEvent::FromTab { tab, event } => {
+                self.handle_parked_tab_event(tab, *event);
+                return Ok(true);
+            }
+            event => event,
+        };
         let model_family = self
             .server
             .get_models_manager()
@@ -564,7 +771,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: self.config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: self.app_event_tx.clone(),
+                    app_event_tx: self.app_event_tx.for_tab(self.tabs.active()),
                     initial_prompt: None,
                     initial_images: Vec::new(),
                     enhanced_keys_supported: self.enhanced_keys_supported,
@@ -614,7 +821,7 @@ impl App {
                                 let init = crate::chatwidget::ChatWidgetInit {
                                     config: self.config.clone(),
                                     frame_requester: tui.frame_requester(),
-                                    app_event_tx: self.app_event_tx.clone(),
+                                    app_event_tx: self.app_event_tx.for_tab(self.tabs.active()),
                                     initial_prompt: None,
                                     initial_images: Vec::new(),
                                     enhanced_keys_supported: self.enhanced_keys_supported,
@@ -651,7 +858,9 @@ impl App {
                             }
                         }
                     }
-                    ResumeSelection::Exit | ResumeSelection::StartFresh => {}
+                    ResumeSelection::Exit
+                    | ResumeSelection::StartFresh
+                    | ResumeSelection::Attach(_) => {}
                 }
 
                 // Leaving alt-screen may blank the inline viewport; force a redraw either way.
@@ -704,6 +913,9 @@ impl App {
             }
             AppEvent::CommitTick => {
                 self.chat_widget.on_commit_tick();
+                for tab in self.tabs.parked_mut() {
+                    tab.chat_widget.on_commit_tick();
+                }
             }
             AppEvent::CodexEvent(event) => {
                 if self.suppress_shutdown_complete
@@ -726,6 +938,24 @@ impl App {
                 return Ok(false);
             }
             AppEvent::CodexOp(op) => self.chat_widget.submit_op(op),
+            AppEvent::OpenInEditor { path, line } => {
+                match crate::file_citation::editor_command(&path, line) {
+                    Some(mut command) => match tui.run_external(&mut command) {
+                        Ok(status) if !status.success() => {
+                            self.chat_widget
+                                .add_error_message(format!("Editor exited with {status}"));
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            self.chat_widget
+                                .add_error_message(format!("Failed to open editor: {err}"));
+                        }
+                    },
+                    None => self.chat_widget.add_error_message(
+                        "Set $VISUAL or $EDITOR to open files in an editor.".to_string(),
+                    ),
+                }
+            }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
@@ -742,6 +972,23 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::RefreshGitStatus => {
+                spawn_git_status_refresh(
+                    self.config.cwd.clone(),
+                    self.app_event_tx.for_tab(self.tabs.active()),
+                );
+            }
+            AppEvent::GitStatusUpdated(summary) => self.chat_widget.set_git_status(summary),
+            AppEvent::GitStatusResult(text) => {
+                let _ = tui.enter_alt_screen();
+                let pager_lines: Vec<ratatui::text::Line<'static>> =
+                    text.lines().map(ansi_escape_line).collect();
+                self.overlay = Some(Overlay::new_static_with_lines(
+                    pager_lines,
+                    "G I T".to_string(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -983,6 +1230,107 @@ impl App {
                     ));
                 }
             }
+            AppEvent::FromTab { .. } => {}
+            AppEvent::OpenTab { cwd } => {
+                let mut config = self.config.clone();
+                if let Some(cwd) = cwd {
+                    if !cwd.is_dir() {
+                        self.chat_widget.add_error_message(format!(
+                            "Cannot open a tab in {}: not a directory",
+                            cwd.display()
+                        ));
+                        return Ok(true);
+                    }
+                    config.cwd = cwd;
+                }
+                let id = self.tabs.allocate_id();
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: self.app_event_tx.for_tab(id),
+                    initial_prompt: None,
+                    initial_images: Vec::new(),
+                    enhanced_keys_supported: self.enhanced_keys_supported,
+                    auth_manager: self.auth_manager.clone(),
+                    models_manager: self.server.get_models_manager(),
+                    feedback: self.feedback.clone(),
+                    is_first_run: false,
+                    model_family: model_family.clone(),
+                };
+                let tab = ParkedTab {
+                    id,
+                    chat_widget: ChatWidget::new(init, self.server.clone()),
+                    file_search: FileSearchManager::new(
+                        config.cwd.clone(),
+                        self.app_event_tx.clone(),
+                    ),
+                    config,
+                    current_model: model_family.get_model_slug().to_string(),
+                    transcript_cells: Vec::new(),
+                    has_emitted_history_lines: false,
+                    suppress_shutdown_complete: false,
+                    pending_events: Vec::new(),
+                    needs_attention: false,
+                };
+                self.show_tab(tui, tab)?;
+            }
+            AppEvent::ToggleMouseCapture => self.toggle_mouse_capture(tui),
+            AppEvent::Copy(target) => self.chat_widget.copy(target),
+            AppEvent::OpenTabPicker => {
+                let labels = self.tabs.labels(&self.config.cwd);
+                self.chat_widget.open_tab_picker(labels);
+            }
+            AppEvent::SwitchTab(id) => {
+                if let Some(tab) = self.tabs.take(id) {
+                    self.show_tab(tui, tab)?;
+                }
+            }
+            AppEvent::CloseTab => {
+                let Some(next) = self.tabs.neighbor(true).and_then(|id| self.tabs.take(id)) else {
+                    self.chat_widget
+                        .add_info_message("This is the only tab.".to_string(), None);
+                    return Ok(true);
+                };
+                let closing = self.tabs.active();
+                self.show_tab(tui, next)?;
+                if let Some(tab) = self.tabs.take(closing) {
+                    tab.chat_widget.submit_op(Op::Shutdown);
+                    if let Some(conversation_id) = tab.chat_widget.conversation_id() {
+                        self.server.remove_conversation(&conversation_id).await;
+                    }
+                }
+            }
+            AppEvent::PreviewTheme(name) => {
+                if let Ok(theme) = theme::resolve(&name, &self.config.tui_themes, default_bg()) {
+                    Self::redraw_with_theme(tui, theme)?;
+                }
+            }
+            AppEvent::PersistTheme(name) => {
+                let theme = match theme::resolve(&name, &self.config.tui_themes, default_bg()) {
+                    Ok(theme) => theme,
+                    Err(err) => {
+                        self.chat_widget.add_error_message(err);
+                        return Ok(true);
+                    }
+                };
+                Self::redraw_with_theme(tui, theme)?;
+                self.config.tui_theme = name.clone();
+                self.chat_widget.set_tui_theme(name.clone());
+                match ConfigEditsBuilder::new(&self.config.codex_home)
+                    .set_tui_theme(&name)
+                    .apply()
+                    .await
+                {
+                    Ok(()) => self
+                        .chat_widget
+                        .add_info_message(format!("Theme changed to {name}"), None),
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to persist theme");
+                        self.chat_widget
+                            .add_error_message(format!("Failed to save theme: {err}"));
+                    }
+                }
+            }
             AppEvent::SkipNextWorldWritableScan => {
                 self.skip_world_writable_scan_once = true;
             }
@@ -1071,13 +1419,19 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::PatchReviewed { id, decision } => {
+                self.chat_widget.resolve_patch_approval(&id, decision);
+            }
             AppEvent::FullScreenApprovalRequest(request) => match request {
-                ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
+                ApprovalRequest::ApplyPatch {
+                    id, cwd, changes, ..
+                } => {
                     let _ = tui.enter_alt_screen();
-                    let diff_summary = DiffSummary::new(changes, cwd);
-                    self.overlay = Some(Overlay::new_static_with_renderables(
-                        vec![diff_summary.into()],
-                        "P A T C H".to_string(),
+                    self.overlay = Some(Overlay::new_diff_review(
+                        id,
+                        cwd,
+                        &changes,
+                        self.app_event_tx.clone(),
                     ));
                 }
                 ApprovalRequest::Exec { command, .. } => {
@@ -1138,6 +1492,72 @@ impl App {
         self.config.model_reasoning_effort = effort;
     }
 
+    /// Saves the composer text of the tab on screen so it survives the
+    /// terminal closing.
+    fn save_composer_draft(&mut self) {
+        if let Some(draft) = self.composer_draft.as_mut() {
+            draft.save(
+                &self.chat_widget.config_ref().cwd,
+                &self.chat_widget.composer_text(),
+            );
+        }
+    }
+
+    fn open_transcript_overlay(&mut self, tui: &mut tui::Tui) {
+        // Enter alternate screen and set viewport to full size.
+        let _ = tui.enter_alt_screen();
+        self.overlay = Some(Overlay::new_transcript(
+            self.transcript_cells.clone(),
+            self.config.collapse_tool_output_lines,
+        ));
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// With mouse capture on the wheel no longer reaches the terminal's
+    /// scrollback, so scrolling up opens the transcript instead. A click on
+    /// the tab bar switches to that tab.
+    fn handle_mouse_event(&mut self, tui: &mut tui::Tui, mouse_event: MouseEvent) -> Result<()> {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => self.open_transcript_overlay(tui),
+            MouseEventKind::Down(MouseButton::Left)
+                if self.tabs.len() > 1 && mouse_event.row == tui.terminal.viewport_area.y =>
+            {
+                if let Some(tab) = self
+                    .tabs
+                    .tab_at(&self.config.cwd, mouse_event.column)
+                    .filter(|id| *id != self.tabs.active())
+                    .and_then(|id| self.tabs.take(id))
+                {
+                    self.show_tab(tui, tab)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn toggle_mouse_capture(&mut self, tui: &mut tui::Tui) {
+        let enabled = !tui.mouse_capture();
+        if let Err(err) = tui.set_mouse_capture(enabled) {
+            self.chat_widget
+                .add_error_message(format!("Failed to change mouse capture: {err}"));
+            return;
+        }
+        let (message, hint) = if enabled {
+            (
+                "Mouse capture on: scroll up to open the transcript, click a tool call there to expand it.",
+                "Hold Shift (Option in some terminals) to select text; /mouse turns capture off.",
+            )
+        } else {
+            (
+                "Mouse capture off: the terminal handles scrolling and text selection.",
+                "/mouse turns capture back on.",
+            )
+        };
+        self.chat_widget
+            .add_info_message(message.to_string(), Some(hint.to_string()));
+    }
+
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
         match key_event {
             KeyEvent {
@@ -1145,11 +1565,26 @@ impl App {
                 modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } => {
-                // Enter alternate screen and set viewport to full size.
-                let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
-                tui.frame_requester().schedule_frame();
+            } => self.open_transcript_overlay(tui),
+            // Ctrl+Tab / Ctrl+PageDown and Ctrl+Shift+Tab / Ctrl+PageUp cycle
+            // through session tabs.
+            KeyEvent {
+                code: code @ (KeyCode::Tab | KeyCode::BackTab | KeyCode::PageDown | KeyCode::PageUp),
+                modifiers,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if modifiers.contains(crossterm::event::KeyModifiers::CONTROL)
+                && self.tabs.len() > 1 =>
+            {
+                let forward = matches!(code, KeyCode::Tab | KeyCode::PageDown);
+                if let Some(tab) = self
+                    .tabs
+                    .neighbor(forward)
+                    .and_then(|id| self.tabs.take(id))
+                    && let Err(err) = self.show_tab(tui, tab)
+                {
+                    tracing::error!("failed to switch tabs: {err}");
+                }
             }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with the composer focused and empty. In any other state, forward
@@ -1284,6 +1719,8 @@ mod tests {
             pending_update_action: None,
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
+            tabs: SessionTabs::default(),
+            composer_draft: None,
         }
     }
 
@@ -1324,6 +1761,8 @@ mod tests {
                 pending_update_action: None,
                 suppress_shutdown_complete: false,
                 skip_world_writable_scan_once: false,
+                tabs: SessionTabs::default(),
+                composer_draft: None,
             },
             rx,
             op_rx,
@@ -1441,6 +1880,7 @@ mod tests {
         let user_cell = |text: &str| -> Arc<dyn HistoryCell> {
             Arc::new(UserHistoryCell {
                 message: text.to_string(),
+                thumbnails: Vec::new(),
             }) as Arc<dyn HistoryCell>
         };
         let agent_cell = |text: &str| -> Arc<dyn HistoryCell> {
@@ -1,14 +1,18 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::git_info::GitStatusSummary;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewDecision;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::clipboard_copy::CopyTarget;
 use crate::history_cell::HistoryCell;
+use crate::session_tabs::TabId;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
@@ -52,6 +56,22 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Recompute the git line for the tab that sent this.
+    RefreshGitStatus,
+
+    /// Result of [`AppEvent::RefreshGitStatus`]; `None` outside a git
+    /// repository.
+    GitStatusUpdated(Option<GitStatusSummary>),
+
+    /// Result of computing a `/git` command.
+    GitStatusResult(String),
+
+    /// Open `path` at `line` in `$VISUAL`/`$EDITOR`.
+    OpenInEditor {
+        path: PathBuf,
+        line: usize,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -123,6 +143,41 @@ pub(crate) enum AppEvent {
         updates: Vec<(Feature, bool)>,
     },
 
+    /// An event sent by the session in tab `tab`, which may not be the one
+    /// on screen.
+    FromTab {
+        tab: TabId,
+        event: Box<AppEvent>,
+    },
+
+    /// Open a new session tab, in `cwd` or else the current tab's directory.
+    OpenTab {
+        cwd: Option<PathBuf>,
+    },
+
+    /// List the open tabs to pick one to switch to.
+    OpenTabPicker,
+
+    /// Show the session tab `id`.
+    SwitchTab(TabId),
+
+    /// End the session in the current tab and close it.
+    CloseTab,
+
+    /// Copy the text the target names to the clipboard, from the `/copy`
+    /// popup.
+    Copy(CopyTarget),
+
+    /// Turn mouse capture on or off for the rest of the session (`/mouse`).
+    ToggleMouseCapture,
+
+    /// Draw the UI with the named theme without saving it, while `/theme`
+    /// moves through the list.
+    PreviewTheme(String),
+
+    /// Switch to the named theme and persist it as `tui.theme`.
+    PersistTheme(String),
+
     /// Update whether the full access warning prompt has been acknowledged.
     UpdateFullAccessWarningAcknowledged(bool),
 
@@ -171,6 +226,12 @@ pub(crate) enum AppEvent {
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
+    /// The user finished reviewing a proposed patch hunk by hunk.
+    PatchReviewed {
+        id: String,
+        decision: ReviewDecision,
+    },
+
     /// Open the feedback note entry overlay after the user selects a category.
     OpenFeedbackNote {
         category: FeedbackCategory,
@@ -5,6 +5,7 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::BottomPaneView;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::approval_queue::ApprovalQueue;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
@@ -16,8 +17,10 @@ use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use codex_core::config::trust_store::suggested_approval_rule;
 use codex_core::features::Feature;
 use codex_core::features::Features;
+use codex_core::protocol::CommandApprovalRule;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
@@ -42,6 +45,7 @@ pub(crate) enum ApprovalRequest {
     Exec {
         id: String,
         command: Vec<String>,
+        cwd: PathBuf,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     },
@@ -59,10 +63,14 @@ pub(crate) enum ApprovalRequest {
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
+///
+/// While more than one request waits, the overlay lists them all in an
+/// [`ApprovalQueue`] instead of prompting for one at a time.
 pub(crate) struct ApprovalOverlay {
     current_request: Option<ApprovalRequest>,
     current_variant: Option<ApprovalVariant>,
     queue: Vec<ApprovalRequest>,
+    batch: Option<ApprovalQueue>,
     app_event_tx: AppEventSender,
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
@@ -77,6 +85,7 @@ impl ApprovalOverlay {
             current_request: None,
             current_variant: None,
             queue: Vec::new(),
+            batch: None,
             app_event_tx: app_event_tx.clone(),
             list: ListSelectionView::new(Default::default(), app_event_tx),
             options: Vec::new(),
@@ -89,7 +98,21 @@ impl ApprovalOverlay {
     }
 
     pub fn enqueue_request(&mut self, req: ApprovalRequest) {
-        self.queue.push(req);
+        if let Some(batch) = self.batch.as_mut() {
+            batch.push(req);
+            return;
+        }
+        if self.current_complete {
+            self.queue.push(req);
+            return;
+        }
+        // A second request is waiting: list everything pending together.
+        let mut requests: Vec<ApprovalRequest> = self.current_request.take().into_iter().collect();
+        requests.append(&mut self.queue);
+        requests.push(req);
+        self.current_variant = None;
+        self.options.clear();
+        self.batch = Some(ApprovalQueue::new(requests));
     }
 
     fn set_current(&mut self, request: ApprovalRequest) {
@@ -109,10 +132,11 @@ impl ApprovalOverlay {
     ) -> (Vec<ApprovalOption>, SelectionViewParams) {
         let (options, title) = match &variant {
             ApprovalVariant::Exec {
+                command,
                 proposed_execpolicy_amendment,
                 ..
             } => (
-                exec_options(proposed_execpolicy_amendment.clone(), features),
+                exec_options(command, proposed_execpolicy_amendment.clone(), features),
                 "Would you like to run the following command?".to_string(),
             ),
             ApprovalVariant::ApplyPatch { .. } => (
@@ -166,6 +190,14 @@ impl ApprovalOverlay {
         let Some(option) = self.options.get(actual_idx) else {
             return;
         };
+        if matches!(option.decision, ApprovalDecision::ReviewHunks) {
+            // The review overlay answers the request when it is done.
+            if let Some(request) = self.current_request.as_ref() {
+                self.app_event_tx
+                    .send(AppEvent::FullScreenApprovalRequest(request.clone()));
+            }
+            return;
+        }
         if let Some(variant) = self.current_variant.as_ref() {
             match (variant, &option.decision) {
                 (ApprovalVariant::Exec { id, command, .. }, ApprovalDecision::Review(decision)) => {
@@ -221,6 +253,110 @@ impl ApprovalOverlay {
             }));
     }
 
+    /// Sends the plain yes or no answer for `request`.
+    fn answer(&self, request: &ApprovalRequest, approve: bool) {
+        match request {
+            ApprovalRequest::Exec { id, command, .. } => {
+                let decision = if approve {
+                    ReviewDecision::Approved
+                } else {
+                    ReviewDecision::Denied
+                };
+                self.handle_exec_decision(id, command, decision);
+            }
+            ApprovalRequest::ApplyPatch { id, .. } => {
+                let decision = if approve {
+                    ReviewDecision::Approved
+                } else {
+                    ReviewDecision::Denied
+                };
+                self.handle_patch_decision(id, decision);
+            }
+            ApprovalRequest::McpElicitation {
+                server_name,
+                request_id,
+                ..
+            } => {
+                let decision = if approve {
+                    ElicitationAction::Accept
+                } else {
+                    ElicitationAction::Decline
+                };
+                self.handle_elicitation_decision(server_name, request_id, decision);
+            }
+        }
+    }
+
+    fn cancel(&self, request: &ApprovalRequest) {
+        match request {
+            ApprovalRequest::Exec { id, command, .. } => {
+                self.handle_exec_decision(id, command, ReviewDecision::Abort);
+            }
+            ApprovalRequest::ApplyPatch { id, .. } => {
+                self.handle_patch_decision(id, ReviewDecision::Abort);
+            }
+            ApprovalRequest::McpElicitation {
+                server_name,
+                request_id,
+                ..
+            } => {
+                self.handle_elicitation_decision(
+                    server_name,
+                    request_id,
+                    ElicitationAction::Cancel,
+                );
+            }
+        }
+    }
+
+    fn handle_batch_key(&mut self, key_event: KeyEvent) {
+        let Some(batch) = self.batch.as_mut() else {
+            return;
+        };
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => batch.move_selection(false),
+            KeyCode::Down | KeyCode::Char('j') => batch.move_selection(true),
+            KeyCode::Char('y') | KeyCode::Char('n') => {
+                if let Some(request) = batch.take_selected() {
+                    self.answer(&request, key_event.code == KeyCode::Char('y'));
+                }
+            }
+            KeyCode::Char('a') => {
+                for request in batch.take_matching_prefix() {
+                    self.answer(&request, true);
+                }
+            }
+            KeyCode::Char('r') => {
+                // The review overlay answers the patch when it is done.
+                if let Some(request @ ApprovalRequest::ApplyPatch { .. }) = batch.selected() {
+                    self.app_event_tx
+                        .send(AppEvent::FullScreenApprovalRequest(request.clone()));
+                }
+            }
+            _ => {}
+        }
+        self.settle_batch();
+    }
+
+    /// Goes back to the single prompt once one request is left.
+    fn settle_batch(&mut self) {
+        let Some(batch) = self.batch.as_mut() else {
+            return;
+        };
+        if batch.len() > 1 {
+            return;
+        }
+        let last = batch.drain().pop();
+        self.batch = None;
+        match last {
+            Some(request) => self.set_current(request),
+            None => self.done = true,
+        }
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -263,6 +399,10 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.batch.is_some() {
+            self.handle_batch_key(key_event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -276,6 +416,11 @@ impl BottomPaneView for ApprovalOverlay {
         if self.done {
             return CancellationEvent::Handled;
         }
+        if let Some(mut batch) = self.batch.take() {
+            for request in batch.drain() {
+                self.cancel(&request);
+            }
+        }
         if !self.current_complete
             && let Some(variant) = self.current_variant.as_ref()
         {
@@ -314,19 +459,50 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn resolve_patch_approval(&mut self, id: &str, decision: ReviewDecision) -> bool {
+        if let Some(batch) = self.batch.as_mut() {
+            if batch.take_patch(id).is_none() {
+                return false;
+            }
+            self.handle_patch_decision(id, decision);
+            self.settle_batch();
+            return true;
+        }
+        match self.current_variant.as_ref() {
+            Some(ApprovalVariant::ApplyPatch { id: current }) if current == id => {
+                if !self.current_complete {
+                    self.handle_patch_decision(id, decision);
+                    self.current_complete = true;
+                    self.advance_queue();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.batch {
+            Some(batch) => batch.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.batch {
+            Some(batch) => batch.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.batch {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -341,6 +517,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
             ApprovalRequest::Exec {
                 id,
                 command,
+                cwd: _,
                 reason,
                 proposed_execpolicy_amendment,
             } => {
@@ -429,6 +606,8 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Open the patch in the hunk-by-hunk review overlay.
+    ReviewHunks,
 }
 
 #[derive(Clone)]
@@ -448,9 +627,20 @@ impl ApprovalOption {
 }
 
 fn exec_options(
+    command: &[String],
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     features: &Features,
 ) -> Vec<ApprovalOption> {
+    let project_rule = suggested_approval_rule(command);
+    let project_label = match &project_rule {
+        CommandApprovalRule::Prefix { command } => format!(
+            "Yes, and always allow `{}` in this project",
+            strip_bash_lc_and_escape(command)
+        ),
+        CommandApprovalRule::Exact { .. } | CommandApprovalRule::Pattern { .. } => {
+            "Yes, and always allow this exact command in this project".to_string()
+        }
+    };
     vec![ApprovalOption {
         label: "Yes, proceed".to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Approved),
@@ -477,12 +667,22 @@ fn exec_options(
                 }
             }),
     )
-    .chain([ApprovalOption {
-        label: "No, and tell Codex what to do differently".to_string(),
-        decision: ApprovalDecision::Review(ReviewDecision::Abort),
-        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-    }])
+    .chain([
+        ApprovalOption {
+            label: project_label,
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForProject {
+                rule: project_rule,
+            }),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ])
     .collect()
 }
 
@@ -494,6 +694,12 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
         },
+        ApprovalOption {
+            label: "Review hunk by hunk".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -537,6 +743,7 @@ mod tests {
         ApprovalRequest::Exec {
             id: "test".to_string(),
             command: vec!["echo".to_string(), "hi".to_string()],
+            cwd: PathBuf::from("/tmp"),
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
         }
@@ -579,6 +786,7 @@ mod tests {
             ApprovalRequest::Exec {
                 id: "test".to_string(),
                 command: vec!["echo".to_string()],
+                cwd: PathBuf::from("/tmp"),
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -609,6 +817,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exec_project_option_emits_prefix_rule() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::Exec {
+                id: "test".to_string(),
+                command: vec![
+                    "bash".to_string(),
+                    "-lc".to_string(),
+                    "cargo test -p codex-core".to_string(),
+                ],
+                cwd: PathBuf::from("/tmp"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        assert!(view.options.iter().any(|option| option.label
+            == "Yes, and always allow `cargo test -p codex-core` in this project"));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        let decision = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|event| match event {
+            AppEvent::CodexOp(Op::ExecApproval { decision, .. }) => Some(decision),
+            _ => None,
+        });
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedForProject {
+                rule: CommandApprovalRule::Prefix {
+                    command: vec![
+                        "cargo".to_string(),
+                        "test".to_string(),
+                        "-p".to_string(),
+                        "codex-core".to_string(),
+                    ],
+                },
+            })
+        );
+    }
+
     #[test]
     fn exec_prefix_option_hidden_when_execpolicy_disabled() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -617,6 +867,7 @@ mod tests {
             ApprovalRequest::Exec {
                 id: "test".to_string(),
                 command: vec!["echo".to_string()],
+                cwd: PathBuf::from("/tmp"),
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -643,6 +894,7 @@ mod tests {
         let exec_request = ApprovalRequest::Exec {
             id: "test".into(),
             command,
+            cwd: PathBuf::from("/tmp"),
             reason: None,
             proposed_execpolicy_amendment: None,
         };
@@ -714,4 +966,91 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::Approved));
     }
+
+    #[test]
+    fn review_option_opens_the_review_and_waits_for_its_decision() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let request = ApprovalRequest::ApplyPatch {
+            id: "patch".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/repo"),
+            changes: HashMap::from([(
+                PathBuf::from("/repo/a.txt"),
+                FileChange::Add {
+                    content: "a\n".to_string(),
+                },
+            )]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::FullScreenApprovalRequest(
+                ApprovalRequest::ApplyPatch { .. }
+            ))
+        ));
+        assert!(!view.is_complete());
+
+        assert!(!view.resolve_patch_approval("other", ReviewDecision::Approved));
+        assert!(view.resolve_patch_approval("patch", ReviewDecision::Abort));
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(Op::PatchApproval { id, decision })) = rx.try_recv() else {
+            panic!("expected a patch approval op");
+        };
+        assert_eq!(id, "patch");
+        assert_eq!(decision, ReviewDecision::Abort);
+    }
+
+    #[test]
+    fn stacked_requests_are_listed_and_answered_by_prefix() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let exec = |id: &str, program: &str| ApprovalRequest::Exec {
+            id: id.to_string(),
+            command: vec![program.to_string(), "--version".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            reason: Some("needs network".to_string()),
+            proposed_execpolicy_amendment: None,
+        };
+        let mut view = ApprovalOverlay::new(exec("1", "cargo"), tx, Features::with_defaults());
+        view.enqueue_request(exec("2", "npm"));
+        view.enqueue_request(exec("3", "cargo"));
+
+        let width = 60;
+        let area = Rect::new(0, 0, width, view.desired_height(width));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        let text: String = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect();
+        assert!(text.contains("3 approvals waiting"), "{text}");
+        assert!(text.contains("$ npm --version"), "{text}");
+        assert!(text.contains("in /tmp · needs network"), "{text}");
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        let mut approved = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { id, decision }) = event {
+                approved.push((id, decision));
+            }
+        }
+        assert_eq!(
+            approved,
+            vec![
+                ("1".to_string(), ReviewDecision::Approved),
+                ("3".to_string(), ReviewDecision::Approved),
+            ]
+        );
+
+        // One request is left, so the usual prompt is back.
+        assert!(view.batch.is_none());
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
+use codex_core::protocol::ReviewDecision;
 use crossterm::event::KeyEvent;
 
 use super::CancellationEvent;
@@ -34,4 +35,10 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<ApprovalRequest> {
         Some(request)
     }
+
+    /// Answer the patch approval request `id` with a decision made outside
+    /// the view. Return `false` if the view is not showing that request.
+    fn resolve_patch_approval(&mut self, _id: &str, _decision: ReviewDecision) -> bool {
+        false
+    }
 }
@@ -17,11 +17,14 @@ use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
 
 use super::chat_composer_history::ChatComposerHistory;
+use super::chat_composer_history::HistorySearch;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::FooterMode;
 use super::footer::FooterProps;
+use super::footer::TaskBadge;
+use super::footer::TokenMeter;
 use super::footer::esc_hint_mode;
 use super::footer::footer_height;
 use super::footer::render_footer;
@@ -30,7 +33,11 @@ use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::SkillPopup;
+use super::vim::Vim;
+use super::vim::VimMode;
+use super::vim::VimOutcome;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::expand_custom_command;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
 use crate::bottom_pane::prompt_args::parse_slash_name;
@@ -54,10 +61,14 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::key_hint;
 use crate::ui_consts::LIVE_PREFIX_COLS;
+use codex_core::config::types::Keymap;
+use codex_core::protocol::Op;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -73,6 +84,8 @@ const LARGE_PASTE_CHAR_THRESHOLD: usize = 1000;
 pub enum InputResult {
     Submitted(String),
     Command(SlashCommand),
+    /// A built-in command that accepts inline arguments, e.g. `/remember <rule>`.
+    CommandWithArgs(SlashCommand, String),
     None,
 }
 
@@ -107,6 +120,9 @@ pub(crate) struct ChatComposer {
     large_paste_counters: HashMap<usize, usize>,
     has_focus: bool,
     attached_images: Vec<AttachedImage>,
+    /// Paths picked from the `@` file popup, attached to the message when it
+    /// still mentions them on submit.
+    mentioned_files: Vec<PathBuf>,
     placeholder_text: String,
     is_task_running: bool,
     // Non-bracketed paste burst tracker.
@@ -114,12 +130,19 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    custom_commands: Vec<CustomPrompt>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
+    token_meter: Option<TokenMeter>,
+    task_badge: Option<TaskBadge>,
     skills: Option<Vec<SkillMetadata>>,
     dismissed_skill_popup_token: Option<String>,
+    /// Modal editing state when `tui.keymap = "vim"`.
+    vim: Option<Vim>,
+    /// The Ctrl+R history search, while it is open.
+    history_search: Option<HistorySearch>,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -157,23 +180,46 @@ impl ChatComposer {
             large_paste_counters: HashMap::new(),
             has_focus: has_input_focus,
             attached_images: Vec::new(),
+            mentioned_files: Vec::new(),
             placeholder_text,
             is_task_running: false,
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            custom_commands: Vec::new(),
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            token_meter: None,
+            task_badge: None,
             skills: None,
             dismissed_skill_popup_token: None,
+            vim: None,
+            history_search: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
         this
     }
 
+    pub(crate) fn set_keymap(&mut self, keymap: Keymap, vim_keys: &BTreeMap<String, String>) {
+        self.vim = match keymap {
+            Keymap::Default => None,
+            Keymap::Vim => Some(Vim::new(vim_keys)),
+        };
+    }
+
+    /// Whether Esc edits the composer (leaving vim's insert or visual mode)
+    /// rather than interrupting a task or starting a backtrack.
+    pub(crate) fn captures_esc(&self) -> bool {
+        self.history_search.is_some()
+            || self
+                .vim
+                .as_ref()
+                .is_some_and(|vim| vim.mode() != VimMode::Normal)
+    }
+
     pub fn set_skill_mentions(&mut self, skills: Option<Vec<SkillMetadata>>) {
         self.skills = skills;
     }
@@ -237,6 +283,7 @@ impl ChatComposer {
     }
 
     pub fn handle_paste(&mut self, pasted: String) -> bool {
+        self.accept_history_match();
         let char_count = pasted.chars().count();
         if char_count > LARGE_PASTE_CHAR_THRESHOLD {
             let placeholder = self.next_large_paste_placeholder(char_count);
@@ -294,12 +341,14 @@ impl ChatComposer {
         self.textarea.set_text("");
         self.pending_pastes.clear();
         self.attached_images.clear();
+        self.mentioned_files.clear();
         self.textarea.set_text(&text);
         self.textarea.set_cursor(0);
         self.sync_popups();
     }
 
     pub(crate) fn clear_for_ctrl_c(&mut self) -> Option<String> {
+        self.history_search = None;
         if self.is_empty() {
             return None;
         }
@@ -334,6 +383,10 @@ impl ChatComposer {
         images.into_iter().map(|img| img.path).collect()
     }
 
+    pub(crate) fn take_recent_submission_files(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.mentioned_files)
+    }
+
     pub(crate) fn flush_paste_burst_if_due(&mut self) -> bool {
         self.handle_paste_burst_flush(Instant::now())
     }
@@ -389,13 +442,48 @@ impl ChatComposer {
         self.sync_popups();
     }
 
+    /// Integrate the history file's matches for a Ctrl+R search.
+    pub(crate) fn on_history_search_response(&mut self, query: &str, entries: Vec<String>) -> bool {
+        let Some(search) = self.history_search.as_mut() else {
+            return false;
+        };
+        if !search.on_response(query, entries) {
+            return false;
+        }
+        self.show_history_match();
+        true
+    }
+
     /// Handle a key event coming from the main UI.
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        if self.history_search.is_some() {
+            if let Some(result) = self.handle_history_search_key(key_event) {
+                return result;
+            }
+        } else if matches!(self.active_popup, ActivePopup::None)
+            && key_event.kind == KeyEventKind::Press
+            && key_event.code == KeyCode::Char('r')
+            && key_event.modifiers == KeyModifiers::CONTROL
+        {
+            self.history_search = Some(HistorySearch::new(self.current_text()));
+            self.update_history_search(String::new());
+            return (InputResult::None, true);
+        }
+
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
             ActivePopup::Skill(_) => self.handle_key_event_with_skill_popup(key_event),
-            ActivePopup::None => self.handle_key_event_without_popup(key_event),
+            ActivePopup::None => match self
+                .vim
+                .as_mut()
+                .map(|vim| vim.handle_key(key_event, &mut self.textarea))
+            {
+                Some(VimOutcome::Handled) => (InputResult::None, true),
+                Some(VimOutcome::Forward(key_event)) | None => {
+                    self.handle_key_event_without_popup(key_event)
+                }
+            },
         };
 
         // Update (or hide/show) popup after processing the key.
@@ -404,6 +492,133 @@ impl ChatComposer {
         result
     }
 
+    /// Handles a key while the history search is open. Returns `None` when
+    /// the key ends the search by accepting the match and should then be
+    /// handled as usual.
+    fn handle_history_search_key(&mut self, key_event: KeyEvent) -> Option<(InputResult, bool)> {
+        if key_event.kind == KeyEventKind::Release {
+            return Some((InputResult::None, false));
+        }
+        let search = self.history_search.as_mut()?;
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Up, ..
+            } => {
+                search.select_older();
+                self.show_history_match();
+            }
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            } => {
+                search.select_newer();
+                self.show_history_match();
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                if let Some(search) = self.history_search.take() {
+                    let original = search.into_original();
+                    self.textarea.set_text(&original);
+                    self.textarea.set_cursor(original.len());
+                    self.sync_popups();
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Enter | KeyCode::Tab,
+                ..
+            } => self.accept_history_match(),
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                let mut query = search.query().to_string();
+                query.pop();
+                self.update_history_search(query);
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            } if !has_ctrl_or_alt(modifiers) => {
+                let query = format!("{}{c}", search.query());
+                self.update_history_search(query);
+            }
+            _ => {
+                self.accept_history_match();
+                return None;
+            }
+        }
+        Some((InputResult::None, true))
+    }
+
+    /// Searches again for `query`: this session's prompts right away, the
+    /// history file's through `Op::SearchHistoryRequest`.
+    fn update_history_search(&mut self, query: String) {
+        let local = self.history.local_matches(&query);
+        let Some(search) = self.history_search.as_mut() else {
+            return;
+        };
+        search.set_query(query.clone(), local);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::SearchHistoryRequest { query }));
+        self.show_history_match();
+    }
+
+    /// Shows the selected match in the textarea; with no match the text
+    /// stays as it was.
+    fn show_history_match(&mut self) {
+        if let Some(text) = self
+            .history_search
+            .as_ref()
+            .and_then(HistorySearch::selected_match)
+        {
+            self.textarea.set_text(&text);
+            self.textarea.set_cursor(text.len());
+        }
+    }
+
+    /// Closes the search, keeping the match as the composer text.
+    fn accept_history_match(&mut self) {
+        if self.history_search.take().is_none() {
+            return;
+        }
+        let text = self.current_text();
+        self.set_text_content(text.clone());
+        self.textarea.set_cursor(text.len());
+        self.history.reset_navigation();
+    }
+
+    fn history_search_line(search: &HistorySearch) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> =
+            vec!["search history: ".dim(), search.query().to_string().cyan()];
+        match search.position() {
+            Some((selected, count)) => spans.push(format!("  {selected}/{count}").dim()),
+            None if !search.query().is_empty() => spans.push("  no match".red()),
+            None => {}
+        }
+        spans.extend([
+            "   ".into(),
+            key_hint::ctrl(KeyCode::Char('r')).into(),
+            " older  ".dim(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " accept  ".dim(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " cancel".dim(),
+        ]);
+        Line::from(spans)
+    }
+
     /// Return true if either the slash-command popup or the file-search popup is active.
     pub(crate) fn popup_active(&self) -> bool {
         !matches!(self.active_popup, ActivePopup::None)
@@ -500,6 +715,16 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::UserCommand(idx) => {
+                            if let Some(command) = popup.command(idx) {
+                                let starts_with_cmd = parse_slash_name(first_line)
+                                    .is_some_and(|(name, _)| name == command.name);
+                                if !starts_with_cmd {
+                                    self.textarea.set_text(&format!("/{} ", command.name));
+                                }
+                                cursor_target = Some(self.textarea.text().len());
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -553,6 +778,26 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::UserCommand(idx) => {
+                            let Some(command) = popup.command(idx) else {
+                                return (InputResult::None, true);
+                            };
+                            let rest = parse_slash_name(self.textarea.text())
+                                .filter(|(name, _)| *name == command.name)
+                                .map(|(_, rest)| rest)
+                                .unwrap_or_default();
+                            if rest.is_empty() && prompt_has_numeric_placeholders(&command.content)
+                            {
+                                // Wait for the arguments.
+                                let text = format!("/{} ", command.name);
+                                self.textarea.set_text(&text);
+                                self.textarea.set_cursor(text.len());
+                                return (InputResult::None, true);
+                            }
+                            let expanded = expand_custom_command(command, rest);
+                            self.textarea.set_text("");
+                            return (InputResult::Submitted(expanded), true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -714,6 +959,10 @@ impl ChatComposer {
                 } else {
                     // Non-image: inserting file path.
                     self.insert_selected_path(&sel_path);
+                    let path = PathBuf::from(&sel_path);
+                    if !self.mentioned_files.contains(&path) {
+                        self.mentioned_files.push(path);
+                    }
                 }
                 // No selection: treat Enter as closing the popup/session.
                 self.active_popup = ActivePopup::None;
@@ -1084,13 +1333,23 @@ impl ChatComposer {
                 // literal text.
                 let first_line = self.textarea.text().lines().next().unwrap_or("");
                 if let Some((name, rest)) = parse_slash_name(first_line)
-                    && rest.is_empty()
                     && let Some((_n, cmd)) = built_in_slash_commands()
                         .into_iter()
                         .find(|(n, _)| *n == name)
                 {
-                    self.textarea.set_text("");
-                    return (InputResult::Command(cmd), true);
+                    if rest.is_empty() {
+                        self.textarea.set_text("");
+                        return (InputResult::Command(cmd), true);
+                    }
+                    if cmd.accepts_inline_args() {
+                        let text = self.textarea.text().to_string();
+                        let args = parse_slash_name(&text)
+                            .map(|(_, rest)| rest.trim().to_string())
+                            .unwrap_or_default();
+                        self.history.record_local_submission(&text);
+                        self.textarea.set_text("");
+                        return (InputResult::CommandWithArgs(cmd, args), true);
+                    }
                 }
                 // If we're in a paste-like burst capture, treat Enter as part of the burst
                 // and accumulate it rather than submitting or inserting immediately.
@@ -1164,7 +1423,11 @@ impl ChatComposer {
                                     .any(|prompt| prompt.name == prompt_name)
                             })
                             .unwrap_or(false);
-                        if !is_builtin && !is_known_prompt {
+                        let is_custom_command = self
+                            .custom_commands
+                            .iter()
+                            .any(|command| command.name == name);
+                        if !is_builtin && !is_known_prompt && !is_custom_command {
                             let message = format!(
                                 r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
                             );
@@ -1191,6 +1454,11 @@ impl ChatComposer {
                 };
                 if let Some(expanded) = expanded_prompt {
                     text = expanded;
+                } else if !input_starts_with_space
+                    && let Some((name, rest)) = parse_slash_name(&text)
+                    && let Some(command) = self.custom_commands.iter().find(|c| c.name == name)
+                {
+                    text = expand_custom_command(command, rest);
                 }
                 if text.is_empty() && !has_attachments {
                     return (InputResult::None, true);
@@ -1532,6 +1800,9 @@ impl ChatComposer {
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            token_meter: self.token_meter,
+            vim_mode: self.vim.as_ref().map(Vim::mode),
+            task_badge: self.task_badge,
         }
     }
 
@@ -1547,6 +1818,9 @@ impl ChatComposer {
     }
 
     fn custom_footer_height(&self) -> Option<u16> {
+        if self.history_search.is_some() {
+            return Some(1);
+        }
         self.footer_hint_override
             .as_ref()
             .map(|items| if items.is_empty() { 0 } else { 1 })
@@ -1629,6 +1903,14 @@ impl ChatComposer {
             return true;
         }
 
+        if self
+            .custom_commands
+            .iter()
+            .any(|c| fuzzy_match(&c.name, name).is_some())
+        {
+            return true;
+        }
+
         let prompt_prefix = format!("{PROMPTS_CMD_PREFIX}:");
         self.custom_prompts
             .iter()
@@ -1678,6 +1960,7 @@ impl ChatComposer {
                     let skills_enabled = self.skills_enabled();
                     let mut command_popup =
                         CommandPopup::new(self.custom_prompts.clone(), skills_enabled);
+                    command_popup.set_commands(self.custom_commands.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1692,6 +1975,17 @@ impl ChatComposer {
         }
     }
 
+    /// Custom slash commands from `.codex/commands` and
+    /// `$CODEX_HOME/commands`. Built-in commands keep their names.
+    pub(crate) fn set_custom_commands(&mut self, mut commands: Vec<CustomPrompt>) {
+        let builtins = built_in_slash_commands();
+        commands.retain(|c| !builtins.iter().any(|(name, _)| *name == c.name));
+        self.custom_commands = commands.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_commands(commands);
+        }
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -1771,6 +2065,23 @@ impl ChatComposer {
         self.context_window_used_tokens = used_tokens;
     }
 
+    /// Returns whether the meter changed.
+    pub(crate) fn set_token_meter(&mut self, meter: Option<TokenMeter>) -> bool {
+        if self.token_meter == meter {
+            return false;
+        }
+        self.token_meter = meter;
+        true
+    }
+
+    pub(crate) fn set_task_badge(&mut self, badge: Option<TaskBadge>) {
+        self.task_badge = badge;
+    }
+
+    pub(crate) fn has_task_badge(&self) -> bool {
+        self.task_badge.is_some()
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -1835,7 +2146,14 @@ impl Renderable for ChatComposer {
                 } else {
                     popup_rect
                 };
-                if let Some(items) = self.footer_hint_override.as_ref() {
+                if let Some(search) = self.history_search.as_ref() {
+                    let mut search_rect = hint_rect;
+                    if search_rect.width > 2 {
+                        search_rect.x += 2;
+                        search_rect.width = search_rect.width.saturating_sub(2);
+                    }
+                    Self::history_search_line(search).render_ref(search_rect, buf);
+                } else if let Some(items) = self.footer_hint_override.as_ref() {
                     if !items.is_empty() {
                         let mut spans = Vec::with_capacity(items.len() * 4);
                         for (idx, (key, label)) in items.iter().enumerate() {
@@ -2131,6 +2449,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ctrl_r_searches_past_prompts() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.history.record_local_submission("run the tests");
+        composer.history.record_local_submission("fix lint");
+        composer.set_text_content("draft".to_string());
+
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert!(composer.captures_esc());
+        for c in "test".chars() {
+            composer.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(composer.current_text(), "run the tests");
+        let mut last_query = None;
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::SearchHistoryRequest { query }) = event {
+                last_query = Some(query);
+            }
+        }
+        assert_eq!(last_query.as_deref(), Some("test"));
+
+        // Older matches from the history file come after this session's.
+        assert!(composer.on_history_search_response("test", vec!["test the parser".to_string()]));
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        assert_eq!(composer.current_text(), "test the parser");
+
+        // Enter keeps the match in the composer without sending it.
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::None);
+        assert_eq!(composer.current_text(), "test the parser");
+        assert!(!composer.captures_esc());
+
+        // Esc puts back what was there before the search.
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+        assert_eq!(composer.current_text(), "fix lint");
+        composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(composer.current_text(), "test the parser");
+    }
+
     #[test]
     fn question_mark_only_toggles_on_first_char() {
         use crossterm::event::KeyCode;
@@ -2629,7 +2996,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::UserCommand(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -2685,7 +3052,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::UserCommand(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -2738,11 +3105,41 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch, but composer submitted literal text: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected bare command for '/init', got {cmd:?} with args {args}")
+            }
             InputResult::None => panic!("expected Command result for '/init'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
     }
 
+    #[test]
+    fn slash_remember_with_text_dispatches_command_with_args() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer
+            .textarea
+            .set_text("/remember always run tests before committing ");
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::CommandWithArgs(cmd, args) => {
+                assert_eq!(cmd, SlashCommand::Remember);
+                assert_eq!(args, "always run tests before committing");
+            }
+            other => panic!("expected CommandWithArgs for '/remember <rule>', got {other:?}"),
+        }
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn extract_args_supports_quoted_paths_single_arg() {
         let args = extract_positional_args_for_prompt_line(
@@ -2811,6 +3208,9 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch after Tab completion, got literal submit: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected bare command for '/diff', got {cmd:?} with args {args}")
+            }
             InputResult::None => panic!("expected Command result for '/diff'"),
         }
         assert!(composer.textarea.is_empty());
@@ -2844,6 +3244,9 @@ mod tests {
             InputResult::Submitted(text) => {
                 panic!("expected command dispatch, but composer submitted literal text: {text}")
             }
+            InputResult::CommandWithArgs(cmd, args) => {
+                panic!("expected bare command for '/mention', got {cmd:?} with args {args}")
+            }
             InputResult::None => panic!("expected Command result for '/mention'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -3180,6 +3583,39 @@ mod tests {
         assert!(composer.attached_images.is_empty());
     }
 
+    #[test]
+    fn picking_a_file_inserts_its_path_and_records_it() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        type_chars_humanlike(&mut composer, &['@', 'l', 'i', 'b']);
+        composer.on_file_search_result(
+            "lib".to_string(),
+            vec![FileMatch {
+                score: 1,
+                path: "src/lib.rs".to_string(),
+                indices: None,
+            }],
+        );
+        composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "src/lib.rs ");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::Submitted(text) if text == "src/lib.rs"));
+        assert_eq!(
+            composer.take_recent_submission_files(),
+            vec![PathBuf::from("src/lib.rs")]
+        );
+        assert!(composer.mentioned_files.is_empty());
+    }
+
     #[test]
     fn image_placeholder_backspace_behaves_like_text_placeholder() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -3406,6 +3842,40 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn custom_command_submission_expands_positional_args() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_custom_commands(vec![CustomPrompt {
+            name: "release-notes".to_string(),
+            path: "/repo/.codex/commands/release-notes.md".to_string().into(),
+            content: "Draft release notes for $1 ($ARGUMENTS)".to_string(),
+            description: None,
+            argument_hint: None,
+        }]);
+
+        composer
+            .textarea
+            .set_text("/release-notes v1.2 \"since May\"");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            InputResult::Submitted("Draft release notes for v1.2 (v1.2 since May)".to_string()),
+            result
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
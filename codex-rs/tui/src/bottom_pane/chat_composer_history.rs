@@ -70,6 +70,16 @@ impl ChatComposerHistory {
         self.local_history.push(text.to_string());
     }
 
+    /// Messages from this session that contain `query`, newest first.
+    pub fn local_matches(&self, query: &str) -> Vec<String> {
+        self.local_history
+            .iter()
+            .rev()
+            .filter(|text| matches_query(text, query))
+            .cloned()
+            .collect()
+    }
+
     /// Reset navigation tracking so the next Up key resumes from the latest entry.
     pub fn reset_navigation(&mut self) {
         self.history_cursor = None;
@@ -194,6 +204,98 @@ impl ChatComposerHistory {
     }
 }
 
+/// Ctrl+R search over past prompts. The composer shows the selected match
+/// while the query is typed; matches from this session come first, then the
+/// history file's as `SearchHistoryResponse` delivers them.
+pub(crate) struct HistorySearch {
+    query: String,
+    /// Composer text before the search, put back when it is cancelled.
+    original: String,
+    /// Matches from this session, newest first.
+    local: Vec<String>,
+    /// Matches from the history file, newest first.
+    persistent: Vec<String>,
+    /// Index into [`Self::matches`]; higher is older.
+    selected: usize,
+}
+
+impl HistorySearch {
+    pub fn new(original: String) -> Self {
+        Self {
+            query: String::new(),
+            original,
+            local: Vec::new(),
+            persistent: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn into_original(self) -> String {
+        self.original
+    }
+
+    /// Starts over with a new query. Matches from the file for the old
+    /// query stay until the new ones arrive, if they still match.
+    pub fn set_query(&mut self, query: String, local: Vec<String>) {
+        self.persistent.retain(|text| matches_query(text, &query));
+        self.query = query;
+        self.local = local;
+        self.selected = 0;
+    }
+
+    /// Takes the file's matches for `query`; returns false when they are for
+    /// an older query.
+    pub fn on_response(&mut self, query: &str, entries: Vec<String>) -> bool {
+        if query != self.query {
+            return false;
+        }
+        self.persistent = entries;
+        self.selected = self.selected.min(self.matches().len().saturating_sub(1));
+        true
+    }
+
+    /// Matches newest first, without repeats.
+    pub fn matches(&self) -> Vec<&str> {
+        let mut matches: Vec<&str> = Vec::new();
+        for text in self.local.iter().chain(self.persistent.iter()) {
+            if !matches.contains(&text.as_str()) {
+                matches.push(text);
+            }
+        }
+        matches
+    }
+
+    pub fn selected_match(&self) -> Option<String> {
+        self.matches()
+            .get(self.selected)
+            .map(|text| text.to_string())
+    }
+
+    /// The selected match's position, 1-based, and the number of matches.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        let count = self.matches().len();
+        (count > 0).then_some((self.selected + 1, count))
+    }
+
+    pub fn select_older(&mut self) {
+        if self.selected + 1 < self.matches().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_newer(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+fn matches_query(text: &str, query: &str) -> bool {
+    text.to_lowercase().contains(&query.to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,6 +303,37 @@ mod tests {
     use codex_core::protocol::Op;
     use tokio::sync::mpsc::unbounded_channel;
 
+    #[test]
+    fn history_search_puts_session_matches_first_and_skips_repeats() {
+        let mut history = ChatComposerHistory::new();
+        history.record_local_submission("run the tests");
+        history.record_local_submission("fix lint");
+
+        let mut search = HistorySearch::new("draft".to_string());
+        search.set_query("TEST".to_string(), history.local_matches("TEST"));
+        assert_eq!(search.selected_match(), Some("run the tests".to_string()));
+
+        // A response for an older query is ignored.
+        assert!(!search.on_response("TES", vec!["stale".to_string()]));
+        assert!(search.on_response(
+            "TEST",
+            vec!["run the tests".to_string(), "test the parser".to_string()],
+        ));
+        assert_eq!(search.matches(), vec!["run the tests", "test the parser"]);
+
+        search.select_older();
+        search.select_older();
+        assert_eq!(search.position(), Some((2, 2)));
+        assert_eq!(search.selected_match(), Some("test the parser".to_string()));
+        search.select_newer();
+        assert_eq!(search.position(), Some((1, 2)));
+
+        // Narrowing the query keeps the file's matches that still apply.
+        search.set_query("TEST THE".to_string(), history.local_matches("TEST THE"));
+        assert_eq!(search.matches(), vec!["test the parser"]);
+        assert_eq!(search.into_original(), "draft");
+    }
+
     #[test]
     fn duplicate_submissions_are_not_recorded() {
         let mut history = ChatComposerHistory::new();
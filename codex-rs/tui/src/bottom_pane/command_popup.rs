@@ -15,18 +15,22 @@ use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use std::collections::HashSet;
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt, or a
+/// custom slash command.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `commands`
+    UserCommand(usize),
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    commands: Vec<CustomPrompt>,
     state: ScrollState,
 }
 
@@ -44,6 +48,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            commands: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -63,6 +68,18 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    /// Custom slash commands, invoked as `/name`. Names taken by built-ins
+    /// are left out.
+    pub(crate) fn set_commands(&mut self, mut commands: Vec<CustomPrompt>) {
+        commands.retain(|c| !self.builtins.iter().any(|(name, _)| *name == c.name));
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        self.commands = commands;
+    }
+
+    pub(crate) fn command(&self, idx: usize) -> Option<&CustomPrompt> {
+        self.commands.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/" on the *first* line becomes the active filter that is used
@@ -114,7 +131,10 @@ impl CommandPopup {
             for (_, cmd) in self.builtins.iter() {
                 out.push((CommandItem::Builtin(*cmd), None, 0));
             }
-            // Then prompts, already sorted by name.
+            // Then custom commands and prompts, already sorted by name.
+            for idx in 0..self.commands.len() {
+                out.push((CommandItem::UserCommand(idx), None, 0));
+            }
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None, 0));
             }
@@ -126,6 +146,11 @@ impl CommandPopup {
                 out.push((CommandItem::Builtin(*cmd), Some(indices), score));
             }
         }
+        for (idx, c) in self.commands.iter().enumerate() {
+            if let Some((indices, score)) = fuzzy_match(&c.name, filter) {
+                out.push((CommandItem::UserCommand(idx), Some(indices), score));
+            }
+        }
         // Support both search styles:
         // - Typing "name" should surface "/prompts:name" results.
         // - Typing "prompts:name" should also work.
@@ -141,10 +166,12 @@ impl CommandPopup {
                 let an = match a.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::UserCommand(i) => &self.commands[i].name,
                 };
                 let bn = match b.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::UserCommand(i) => &self.commands[i].name,
                 };
                 an.cmp(bn)
             })
@@ -178,6 +205,14 @@ impl CommandPopup {
                             description,
                         )
                     }
+                    CommandItem::UserCommand(i) => {
+                        let command = &self.commands[i];
+                        let description = command
+                            .description
+                            .clone()
+                            .unwrap_or_else(|| "run custom command".to_string());
+                        (format!("/{}", command.name), description)
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -246,7 +281,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::UserCommand(_) => false,
         });
         assert!(
             has_init,
@@ -264,7 +299,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::UserCommand(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -276,7 +313,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::UserCommand(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -338,6 +375,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_commands_are_listed_by_bare_name() {
+        let command = |name: &str| CustomPrompt {
+            name: name.to_string(),
+            path: format!("/repo/.codex/commands/{name}.md").into(),
+            content: "Summarize $ARGUMENTS".to_string(),
+            description: None,
+            argument_hint: None,
+        };
+        let mut popup = CommandPopup::new(Vec::new(), false);
+        popup.set_commands(vec![command("release-notes"), command("init")]);
+        popup.on_composer_text_change("/release".to_string());
+
+        let selected = popup.selected_item();
+        assert_eq!(selected, Some(CommandItem::UserCommand(0)));
+        let rows = popup.rows_from_matches(vec![(CommandItem::UserCommand(0), None, 0)]);
+        assert_eq!(rows[0].name, "/release-notes");
+        assert_eq!(rows[0].description.as_deref(), Some("run custom command"));
+        // A command named like a built-in is left out.
+        assert!(popup.command(1).is_none());
+    }
+
     #[test]
     fn prompt_description_uses_frontmatter_metadata() {
         let popup = CommandPopup::new(
@@ -385,7 +444,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::UserCommand(_) => None,
             })
             .collect();
         assert!(
@@ -1,9 +1,13 @@
 #[cfg(target_os = "linux")]
+use crate::bottom_pane::vim::VimMode;
 use crate::clipboard_paste::is_probably_wsl;
+use crate::exec_cell::spinner;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
+use crate::status::format_cost;
 use crate::status::format_tokens_compact;
+use crate::theme;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
@@ -13,6 +17,7 @@ use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
+use std::time::Instant;
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct FooterProps {
@@ -22,8 +27,36 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    pub(crate) token_meter: Option<TokenMeter>,
+    /// The composer's mode when `tui.keymap = "vim"`.
+    pub(crate) vim_mode: Option<VimMode>,
+    pub(crate) task_badge: Option<TaskBadge>,
 }
 
+/// Shown at the start of the status bar while a turn runs without the status
+/// indicator above the composer (e.g. while the answer streams), so there is
+/// still a sign that Codex is busy and how many messages wait behind it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TaskBadge {
+    pub(crate) since: Instant,
+    pub(crate) queued: usize,
+    pub(crate) animations_enabled: bool,
+}
+
+/// Tokens used so far and their estimated cost, shown after the context
+/// indicator.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct TokenMeter {
+    pub(crate) session_tokens: i64,
+    /// Tokens used by the running turn, while one is running.
+    pub(crate) turn_tokens: Option<i64>,
+    /// `None` when the model has no entry in `model_prices`.
+    pub(crate) session_cost: Option<f64>,
+}
+
+/// At or below this much context left, the indicator turns red.
+const CONTEXT_WARNING_PERCENT: i64 = 15;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum FooterMode {
     CtrlCReminder,
@@ -72,6 +105,7 @@ pub(crate) fn render_footer(area: Rect, buf: &mut Buffer, props: FooterProps) {
         " ".repeat(FOOTER_INDENT_COLS).into(),
         " ".repeat(FOOTER_INDENT_COLS).into(),
     ))
+    .style(theme::status_bar_style())
     .render(area, buf);
 }
 
@@ -85,10 +119,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             is_task_running: props.is_task_running,
         })],
         FooterMode::ShortcutSummary => {
-            let mut line = context_window_line(
-                props.context_window_percent,
-                props.context_window_used_tokens,
-            );
+            let mut line = context_window_line(props);
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
@@ -110,10 +141,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             shortcut_overlay_lines(state)
         }
         FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint)],
-        FooterMode::ContextOnly => vec![context_window_line(
-            props.context_window_percent,
-            props.context_window_used_tokens,
-        )],
+        FooterMode::ContextOnly => vec![context_window_line(props)],
     }
 }
 
@@ -241,18 +269,49 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>) -> Line<'static> {
-    if let Some(percent) = percent {
-        let percent = percent.clamp(0, 100);
-        return Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
+fn context_window_line(props: FooterProps) -> Line<'static> {
+    let mut line = Line::default();
+    if let Some(mode) = props.vim_mode {
+        line.push_span(mode.label().bold());
+        line.push_span(" ".into());
     }
-
-    if let Some(tokens) = used_tokens {
-        let used_fmt = format_tokens_compact(tokens);
-        return Line::from(vec![Span::from(format!("{used_fmt} used")).dim()]);
+    if let Some(badge) = props.task_badge {
+        line.push_span(spinner(Some(badge.since), badge.animations_enabled));
+        if badge.queued > 0 {
+            line.push_span(format!(" {} queued", badge.queued).dim());
+        } else {
+            line.push_span(" working".dim());
+        }
+        line.push_span(" · ".dim());
     }
+    let context = if let Some(percent) = props.context_window_percent {
+        let percent = percent.clamp(0, 100);
+        let span = Span::from(format!("{percent}% context left"));
+        if percent <= CONTEXT_WARNING_PERCENT {
+            span.red()
+        } else {
+            span.dim()
+        }
+    } else if let Some(tokens) = props.context_window_used_tokens {
+        let used_fmt = format_tokens_compact(tokens);
+        Span::from(format!("{used_fmt} used")).dim()
+    } else {
+        Span::from("100% context left").dim()
+    };
+    line.push_span(context);
 
-    Line::from(vec![Span::from("100% context left").dim()])
+    if let Some(meter) = props.token_meter.filter(|meter| meter.session_tokens > 0) {
+        line.push_span(" · ".dim());
+        let mut meter_text = format!("{} tokens", format_tokens_compact(meter.session_tokens));
+        if let Some(cost) = meter.session_cost {
+            meter_text.push_str(&format!(" ({})", format_cost(cost)));
+        }
+        if let Some(turn) = meter.turn_tokens.filter(|turn| *turn > 0) {
+            meter_text.push_str(&format!(", {} this turn", format_tokens_compact(turn)));
+        }
+        line.push_span(meter_text.dim());
+    }
+    line
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -414,8 +473,10 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
 mod tests {
     use super::*;
     use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use ratatui::style::Style;
 
     fn snapshot_footer(name: &str, props: FooterProps) {
         let height = footer_height(props).max(1);
@@ -429,6 +490,94 @@ mod tests {
         assert_snapshot!(name, terminal.backend());
     }
 
+    fn footer_text(props: FooterProps) -> String {
+        footer_lines(props)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn context_line_shows_the_token_meter_and_warns_when_nearly_full() {
+        let props = FooterProps {
+            mode: FooterMode::ContextOnly,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: true,
+            context_window_percent: Some(12),
+            context_window_used_tokens: None,
+            token_meter: Some(TokenMeter {
+                session_tokens: 48_200,
+                turn_tokens: Some(3_150),
+                session_cost: Some(0.4213),
+            }),
+            vim_mode: None,
+            task_badge: None,
+        };
+        assert_eq!(
+            footer_text(props),
+            "12% context left · 48.2K tokens ($0.42), 3.15K this turn"
+        );
+        let line = footer_lines(props).remove(0);
+        assert_eq!(line.spans[0].style, Style::default().red());
+
+        let unpriced = FooterProps {
+            context_window_percent: Some(72),
+            token_meter: Some(TokenMeter {
+                session_tokens: 900,
+                turn_tokens: None,
+                session_cost: None,
+            }),
+            ..props
+        };
+        assert_eq!(footer_text(unpriced), "72% context left · 900 tokens");
+        let line = footer_lines(unpriced).remove(0);
+        assert_eq!(line.spans[0].style, Style::default().dim());
+
+        let vim = FooterProps {
+            vim_mode: Some(VimMode::Normal),
+            ..unpriced
+        };
+        assert_eq!(footer_text(vim), "NORMAL 72% context left · 900 tokens");
+    }
+
+    #[test]
+    fn task_badge_leads_the_context_line() {
+        let badge = TaskBadge {
+            since: Instant::now(),
+            queued: 0,
+            animations_enabled: false,
+        };
+        let props = FooterProps {
+            mode: FooterMode::ShortcutSummary,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: true,
+            context_window_percent: None,
+            context_window_used_tokens: None,
+            token_meter: None,
+            vim_mode: None,
+            task_badge: Some(badge),
+        };
+        assert_eq!(
+            footer_text(props),
+            "• working · 100% context left · ? for shortcuts"
+        );
+
+        let queued = FooterProps {
+            mode: FooterMode::ContextOnly,
+            task_badge: Some(TaskBadge { queued: 2, ..badge }),
+            ..props
+        };
+        assert_eq!(footer_text(queued), "• 2 queued · 100% context left");
+    }
+
     #[test]
     fn footer_snapshots() {
         snapshot_footer(
@@ -440,6 +589,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -452,6 +604,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -464,6 +619,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -476,6 +634,9 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -488,6 +649,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -500,6 +664,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -512,6 +679,9 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
 
@@ -524,6 +694,9 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                token_meter: None,
+                vim_mode: None,
+                task_badge: None,
             },
         );
     }
@@ -33,6 +33,9 @@ use unicode_width::UnicodeWidthStr;
 /// One selectable item in the generic selection list.
 pub(crate) type SelectionAction = Box<dyn Fn(&AppEventSender) + Send + Sync>;
 
+/// Called with the index of the item the selection moved to.
+pub(crate) type SelectionChangedAction = Box<dyn Fn(usize, &AppEventSender) + Send + Sync>;
+
 #[derive(Default)]
 pub(crate) struct SelectionItem {
     pub name: String,
@@ -56,6 +59,10 @@ pub(crate) struct SelectionViewParams {
     pub search_placeholder: Option<String>,
     pub header: Box<dyn Renderable>,
     pub initial_selected_idx: Option<usize>,
+    /// Runs whenever the selection moves to another item, e.g. to preview it.
+    pub on_selection_changed: Option<SelectionChangedAction>,
+    /// Runs when the view is dismissed without accepting an item.
+    pub on_cancel: Option<SelectionAction>,
 }
 
 impl Default for SelectionViewParams {
@@ -69,6 +76,8 @@ impl Default for SelectionViewParams {
             search_placeholder: None,
             header: Box::new(()),
             initial_selected_idx: None,
+            on_selection_changed: None,
+            on_cancel: None,
         }
     }
 }
@@ -86,6 +95,10 @@ pub(crate) struct ListSelectionView {
     last_selected_actual_idx: Option<usize>,
     header: Box<dyn Renderable>,
     initial_selected_idx: Option<usize>,
+    on_selection_changed: Option<SelectionChangedAction>,
+    on_cancel: Option<SelectionAction>,
+    /// The item `on_selection_changed` last ran for.
+    notified_actual_idx: Option<usize>,
 }
 
 impl ListSelectionView {
@@ -117,11 +130,34 @@ impl ListSelectionView {
             last_selected_actual_idx: None,
             header,
             initial_selected_idx: params.initial_selected_idx,
+            on_selection_changed: params.on_selection_changed,
+            on_cancel: params.on_cancel,
+            notified_actual_idx: None,
         };
         s.apply_filter();
+        s.notified_actual_idx = s.selected_actual_idx();
         s
     }
 
+    fn selected_actual_idx(&self) -> Option<usize> {
+        self.state
+            .selected_idx
+            .and_then(|visible_idx| self.filtered_indices.get(visible_idx).copied())
+    }
+
+    fn notify_selection_changed(&mut self) {
+        let selected = self.selected_actual_idx();
+        if selected == self.notified_actual_idx {
+            return;
+        }
+        self.notified_actual_idx = selected;
+        if let (Some(actual_idx), Some(on_selection_changed)) =
+            (selected, &self.on_selection_changed)
+        {
+            on_selection_changed(actual_idx, &self.app_event_tx);
+        }
+    }
+
     fn visible_len(&self) -> usize {
         self.filtered_indices.len()
     }
@@ -231,6 +267,7 @@ impl ListSelectionView {
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
         self.skip_disabled_up();
+        self.notify_selection_changed();
     }
 
     fn move_down(&mut self) {
@@ -239,6 +276,7 @@ impl ListSelectionView {
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
         self.skip_disabled_down();
+        self.notify_selection_changed();
     }
 
     fn accept(&mut self) {
@@ -357,6 +395,7 @@ impl BottomPaneView for ListSelectionView {
             } if self.is_searchable => {
                 self.search_query.pop();
                 self.apply_filter();
+                self.notify_selection_changed();
             }
             KeyEvent {
                 code: KeyCode::Esc, ..
@@ -373,6 +412,7 @@ impl BottomPaneView for ListSelectionView {
             {
                 self.search_query.push(c);
                 self.apply_filter();
+                self.notify_selection_changed();
             }
             KeyEvent {
                 code: KeyCode::Char(c),
@@ -411,6 +451,9 @@ impl BottomPaneView for ListSelectionView {
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
         self.complete = true;
+        if let Some(on_cancel) = &self.on_cancel {
+            on_cancel(&self.app_event_tx);
+        }
         CancellationEvent::Handled
     }
 }
@@ -838,4 +881,43 @@ mod tests {
             render_lines_with_width(&view, 24)
         );
     }
+
+    #[test]
+    fn moving_the_selection_notifies_and_esc_cancels() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let items: Vec<SelectionItem> = ["dark", "light", "high-contrast"]
+            .into_iter()
+            .map(|name| SelectionItem {
+                name: name.to_string(),
+                is_current: name == "dark",
+                dismiss_on_select: true,
+                ..Default::default()
+            })
+            .collect();
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                items,
+                on_selection_changed: Some(Box::new(|idx, tx| {
+                    tx.send(AppEvent::PreviewTheme(idx.to_string()));
+                })),
+                on_cancel: Some(Box::new(|tx| {
+                    tx.send(AppEvent::PreviewTheme("cancelled".to_string()));
+                })),
+                ..Default::default()
+            },
+            tx,
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Esc));
+
+        let mut notified = Vec::new();
+        while let Ok(AppEvent::PreviewTheme(name)) = rx.try_recv() {
+            notified.push(name);
+        }
+        assert_eq!(notified, vec!["1", "2", "cancelled"]);
+        assert!(view.is_complete());
+    }
 }
@@ -1,6 +1,8 @@
 //! Bottom pane: shows the ChatComposer or a BottomPaneView, if one is active.
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::queued_user_messages::QueuedUserMessages;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
@@ -9,7 +11,10 @@ use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::config::types::Keymap;
 use codex_core::features::Features;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use crossterm::event::KeyCode;
@@ -17,8 +22,10 @@ use crossterm::event::KeyEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use std::time::Duration;
+use std::time::Instant;
 
 mod approval_overlay;
+mod approval_queue;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
 mod bottom_pane_view;
@@ -29,6 +36,8 @@ pub mod custom_prompt_view;
 mod experimental_features_view;
 mod file_search_popup;
 mod footer;
+use footer::TaskBadge;
+pub(crate) use footer::TokenMeter;
 mod list_selection_view;
 mod prompt_args;
 mod skill_popup;
@@ -43,6 +52,7 @@ mod scroll_state;
 mod selection_popup_common;
 mod textarea;
 mod unified_exec_footer;
+mod vim;
 pub(crate) use feedback_view::FeedbackNoteView;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -85,6 +95,9 @@ pub(crate) struct BottomPane {
     unified_exec_footer: UnifiedExecFooter,
     /// Queued user messages to show above the composer while a turn is running.
     queued_user_messages: QueuedUserMessages,
+    /// When the status bar started showing the task badge, which stands in
+    /// for the status indicator while that is hidden.
+    task_badge_since: Option<Instant>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 }
@@ -132,6 +145,7 @@ impl BottomPane {
             status: None,
             unified_exec_footer: UnifiedExecFooter::new(),
             queued_user_messages: QueuedUserMessages::new(),
+            task_badge_since: None,
             esc_backtrack_hint: false,
             animations_enabled,
             context_window_percent: None,
@@ -195,6 +209,7 @@ impl BottomPane {
             // send an interrupt even while the composer has focus.
             if matches!(key_event.code, crossterm::event::KeyCode::Esc)
                 && self.is_task_running
+                && !self.composer.captures_esc()
                 && let Some(status) = &self.status
             {
                 // Send Op::Interrupt
@@ -349,6 +364,7 @@ impl BottomPane {
             // Hide the status indicator when a task completes, but keep other modal views.
             self.hide_status_indicator();
         }
+        self.sync_task_badge();
     }
 
     /// Hide the status indicator while leaving task-running state untouched.
@@ -356,6 +372,23 @@ impl BottomPane {
         if self.status.take().is_some() {
             self.request_redraw();
         }
+        self.sync_task_badge();
+    }
+
+    /// Show the task badge in the status bar while a task runs without the
+    /// status indicator.
+    fn sync_task_badge(&mut self) {
+        if !self.is_task_running || self.status.is_some() {
+            self.task_badge_since = None;
+            self.composer.set_task_badge(None);
+            return;
+        }
+        let since = *self.task_badge_since.get_or_insert_with(Instant::now);
+        self.composer.set_task_badge(Some(TaskBadge {
+            since,
+            queued: self.queued_user_messages.messages.len(),
+            animations_enabled: self.animations_enabled,
+        }));
     }
 
     pub(crate) fn ensure_status_indicator(&mut self) {
@@ -367,6 +400,7 @@ impl BottomPane {
             ));
             self.request_redraw();
         }
+        self.sync_task_badge();
     }
 
     pub(crate) fn set_interrupt_hint_visible(&mut self, visible: bool) {
@@ -389,6 +423,17 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_token_meter(&mut self, meter: Option<TokenMeter>) {
+        if self.composer.set_token_meter(meter) {
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_keymap(&mut self, keymap: Keymap, vim_keys: &BTreeMap<String, String>) {
+        self.composer.set_keymap(keymap, vim_keys);
+        self.request_redraw();
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -398,6 +443,7 @@ impl BottomPane {
     /// Update the queued messages preview shown above the composer.
     pub(crate) fn set_queued_user_messages(&mut self, queued: Vec<String>) {
         self.queued_user_messages.messages = queued;
+        self.sync_task_badge();
         self.request_redraw();
     }
 
@@ -413,6 +459,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_custom_commands(&mut self, commands: Vec<CustomPrompt>) {
+        self.composer.set_custom_commands(commands);
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -421,11 +472,19 @@ impl BottomPane {
         self.is_task_running
     }
 
+    /// Whether a view (popup, approval prompt) covers the composer.
+    pub(crate) fn has_active_view(&self) -> bool {
+        !self.view_stack.is_empty()
+    }
+
     /// Return true when the pane is in the regular composer state without any
     /// overlays or popups and not running a task. This is the safe context to
     /// use Esc-Esc for backtracking from the main view.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        !self.is_task_running && self.view_stack.is_empty() && !self.composer.popup_active()
+        !self.is_task_running
+            && self.view_stack.is_empty()
+            && !self.composer.popup_active()
+            && !self.composer.captures_esc()
     }
 
     pub(crate) fn show_view(&mut self, view: Box<dyn BottomPaneView>) {
@@ -452,6 +511,26 @@ impl BottomPane {
         self.push_view(Box::new(modal));
     }
 
+    /// Answers the patch approval request `id` with a decision made outside
+    /// the approval modal, such as in the hunk review overlay.
+    pub(crate) fn resolve_patch_approval(&mut self, id: &str, decision: ReviewDecision) {
+        if let Some(view) = self.view_stack.last_mut()
+            && view.resolve_patch_approval(id, decision.clone())
+        {
+            if view.is_complete() {
+                self.view_stack.pop();
+                self.on_active_view_complete();
+            }
+        } else {
+            // The modal is gone; answer the request directly.
+            self.app_event_tx.send(AppEvent::CodexOp(Op::PatchApproval {
+                id: id.to_string(),
+                decision,
+            }));
+        }
+        self.request_redraw();
+    }
+
     fn on_active_view_complete(&mut self) {
         self.resume_status_timer_after_modal();
     }
@@ -506,6 +585,12 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn on_history_search_response(&mut self, query: &str, entries: Vec<String>) {
+        if self.composer.on_history_search_response(query, entries) {
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn on_file_search_result(&mut self, query: String, matches: Vec<FileMatch>) {
         self.composer.on_file_search_result(query, matches);
         self.request_redraw();
@@ -529,6 +614,10 @@ impl BottomPane {
         self.composer.take_recent_submission_images()
     }
 
+    pub(crate) fn take_recent_submission_files(&mut self) -> Vec<PathBuf> {
+        self.composer.take_recent_submission_files()
+    }
+
     fn as_renderable(&'_ self) -> RenderableItem<'_> {
         if let Some(view) = self.active_view() {
             RenderableItem::Borrowed(view)
@@ -557,6 +646,11 @@ impl BottomPane {
 
 impl Renderable for BottomPane {
     fn render(&self, area: Rect, buf: &mut Buffer) {
+        // Keep the badge's spinner moving, as the status indicator does.
+        if self.animations_enabled && self.view_stack.is_empty() && self.composer.has_task_badge() {
+            self.frame_requester
+                .schedule_frame_in(Duration::from_millis(32));
+        }
         self.as_renderable().render(area, buf);
     }
     fn desired_height(&self, width: u16) -> u16 {
@@ -598,6 +692,7 @@ mod tests {
         ApprovalRequest::Exec {
             id: "1".to_string(),
             command: vec!["echo".into(), "ok".into()],
+            cwd: PathBuf::from("/tmp"),
             reason: None,
             proposed_execpolicy_amendment: None,
         }
@@ -192,6 +192,13 @@ pub fn expand_custom_prompt(
     Ok(Some(expanded))
 }
 
+/// Expands a custom slash command (`/name [value] …`): `$1..$9` and
+/// `$ARGUMENTS` in its body take the values in `rest`, the text after the
+/// name.
+pub fn expand_custom_command(command: &CustomPrompt, rest: &str) -> String {
+    expand_numeric_placeholders(&command.content, &parse_positional_args(rest))
+}
+
 /// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
 pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     if content.contains("$ARGUMENTS") {
@@ -314,6 +321,26 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
 mod tests {
     use super::*;
 
+    #[test]
+    fn expand_custom_command_substitutes_positional_args() {
+        let command = CustomPrompt {
+            name: "release-notes".to_string(),
+            path: "/repo/.codex/commands/release-notes.md".to_string().into(),
+            content: "Write release notes for $1 since $2.\nContext: $ARGUMENTS".to_string(),
+            description: None,
+            argument_hint: None,
+        };
+
+        assert_eq!(
+            expand_custom_command(&command, "v1.2 \"last tuesday\""),
+            "Write release notes for v1.2 since last tuesday.\nContext: v1.2 last tuesday"
+        );
+        assert_eq!(
+            expand_custom_command(&command, ""),
+            "Write release notes for  since .\nContext: "
+        );
+    }
+
     #[test]
     fn expand_arguments_basic() {
         let prompts = vec![CustomPrompt {
@@ -10,6 +10,7 @@ use ratatui::text::Span;
 use ratatui::widgets::Widget;
 use unicode_width::UnicodeWidthChar;
 
+use crate::accessibility;
 use crate::key_hint::KeyBinding;
 
 use super::scroll_state::ScrollState;
@@ -198,8 +199,10 @@ pub(crate) fn render_rows(
             break;
         }
 
+        let selected = Some(i) == state.selected_idx;
         let mut full_line = build_full_line(row, desc_col);
-        if Some(i) == state.selected_idx {
+        mark_selected(&mut full_line, selected);
+        if selected {
             // Match previous behavior: cyan + bold for the selected row.
             // Reset the style first to avoid inheriting dim from keyboard shortcuts.
             full_line.spans.iter_mut().for_each(|span| {
@@ -235,6 +238,14 @@ pub(crate) fn render_rows(
     }
 }
 
+/// Says which row is selected in words as well, for screen readers that
+/// don't convey color.
+fn mark_selected(line: &mut Line<'static>, selected: bool) {
+    if selected && accessibility::enabled() {
+        line.push_span(" (selected)".dim());
+    }
+}
+
 /// Compute the number of terminal rows required to render up to `max_results`
 /// items from `rows_all` given the current scroll/selection state and the
 /// available `width`. Accounts for description wrapping and alignment so the
@@ -269,14 +280,14 @@ pub(crate) fn measure_rows_height(
     use crate::wrapping::RtOptions;
     use crate::wrapping::word_wrap_line;
     let mut total: u16 = 0;
-    for row in rows_all
+    for (i, row) in rows_all
         .iter()
         .enumerate()
         .skip(start_idx)
         .take(visible_items)
-        .map(|(_, r)| r)
     {
-        let full_line = build_full_line(row, desc_col);
+        let mut full_line = build_full_line(row, desc_col);
+        mark_selected(&mut full_line, Some(i) == state.selected_idx);
         let continuation_indent = wrap_indent(row, desc_col, content_width);
         let opts = RtOptions::new(content_width as usize)
             .initial_indent(Line::from(""))
@@ -5,6 +5,7 @@ use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
@@ -34,6 +35,8 @@ pub(crate) struct TextArea {
     preferred_col: Option<usize>,
     elements: Vec<TextElement>,
     kill_buffer: String,
+    /// Range drawn highlighted, e.g. a vim visual selection.
+    selection: Option<Range<usize>>,
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +60,7 @@ impl TextArea {
             preferred_col: None,
             elements: Vec::new(),
             kill_buffer: String::new(),
+            selection: None,
         }
     }
 
@@ -212,6 +216,11 @@ impl TextArea {
         self.end_of_line(self.cursor_pos)
     }
 
+    /// The line the cursor is on, without its newline.
+    pub(crate) fn current_line_range(&self) -> Range<usize> {
+        self.beginning_of_current_line()..self.end_of_current_line()
+    }
+
     pub fn input(&mut self, event: KeyEvent) {
         match event {
             // Some terminals (or configurations) send Control key chords as
@@ -526,6 +535,24 @@ impl TextArea {
         }
     }
 
+    /// Removes `range` into the kill buffer, so that [`Self::yank`] puts it
+    /// back.
+    pub(crate) fn kill(&mut self, range: Range<usize>) {
+        self.kill_range(range);
+    }
+
+    /// Copies `range` into the kill buffer without removing it.
+    pub(crate) fn copy(&mut self, range: Range<usize>) {
+        let range = self.expand_range_to_element_boundaries(range);
+        if range.start < range.end {
+            self.kill_buffer = self.text[range].to_string();
+        }
+    }
+
+    pub(crate) fn set_selection(&mut self, selection: Option<Range<usize>>) {
+        self.selection = selection;
+    }
+
     pub fn yank(&mut self) {
         if self.kill_buffer.is_empty() {
             return;
@@ -847,7 +874,7 @@ impl TextArea {
         }
     }
 
-    fn next_atomic_boundary(&self, pos: usize) -> usize {
+    pub(crate) fn next_atomic_boundary(&self, pos: usize) -> usize {
         if pos >= self.text.len() {
             return self.text.len();
         }
@@ -1033,6 +1060,19 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            if let Some(selection) = &self.selection {
+                let overlap_start = selection.start.max(line_range.start);
+                let overlap_end = selection.end.min(line_range.end);
+                if overlap_start < overlap_end {
+                    let x_off = self.text[line_range.start..overlap_start].width() as u16;
+                    let width = self.text[overlap_start..overlap_end].width() as u16;
+                    buf.set_style(
+                        Rect::new(area.x + x_off, y, width, 1).intersection(area),
+                        Style::default().add_modifier(Modifier::REVERSED),
+                    );
+                }
+            }
         }
     }
 }
@@ -0,0 +1,508 @@
+//! Modal editing for the composer when `tui.keymap = "vim"`.
+//!
+//! Insert mode is the regular composer. Normal and visual mode translate
+//! keys into edits on the [`TextArea`]; keys that mean the same as they do
+//! in the regular composer (arrows, Enter, Ctrl chords, and `j`/`k`, which
+//! browse history at the first and last line) are handed back as
+//! [`VimOutcome::Forward`] so that the composer's own handling runs.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use strum_macros::EnumString;
+
+use super::textarea::TextArea;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VimMode {
+    Normal,
+    Insert,
+    Visual,
+}
+
+impl VimMode {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            VimMode::Normal => "NORMAL",
+            VimMode::Insert => "INSERT",
+            VimMode::Visual => "VISUAL",
+        }
+    }
+}
+
+pub(crate) enum VimOutcome {
+    /// Vim handled (or ignored) the key.
+    Handled,
+    /// The composer should handle this key as usual.
+    Forward(KeyEvent),
+}
+
+/// Normal-mode actions, named in `tui.vim_keys` by their kebab-case names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum VimAction {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordBackward,
+    LineStart,
+    LineEnd,
+    TextStart,
+    TextEnd,
+    Insert,
+    Append,
+    InsertLineStart,
+    AppendLineEnd,
+    OpenBelow,
+    OpenAbove,
+    DeleteChar,
+    DeleteCharBefore,
+    DeleteLine,
+    DeleteToLineEnd,
+    ChangeLine,
+    ChangeToLineEnd,
+    YankLine,
+    Paste,
+    Visual,
+    Submit,
+}
+
+type Key = (KeyCode, KeyModifiers);
+
+#[derive(Debug)]
+pub(crate) struct Vim {
+    mode: VimMode,
+    /// First key of a two-key command (`dd`, `cw`, `gg`, ...).
+    pending: Option<char>,
+    /// Where the visual selection started.
+    anchor: usize,
+    custom: HashMap<Key, VimAction>,
+}
+
+impl Vim {
+    /// Starts in insert mode so the composer works as usual until Esc.
+    /// Entries of `custom` that do not parse are logged and skipped.
+    pub(crate) fn new<'a>(custom: impl IntoIterator<Item = (&'a String, &'a String)>) -> Self {
+        let custom = custom
+            .into_iter()
+            .filter_map(|(key, action)| {
+                let parsed = parse_key(key).zip(VimAction::from_str(action).ok());
+                if parsed.is_none() {
+                    tracing::warn!("ignoring tui.vim_keys entry `{key}` = `{action}`");
+                }
+                parsed
+            })
+            .collect();
+        Self {
+            mode: VimMode::Insert,
+            pending: None,
+            anchor: 0,
+            custom,
+        }
+    }
+
+    pub(crate) fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    pub(crate) fn handle_key(&mut self, key: KeyEvent, textarea: &mut TextArea) -> VimOutcome {
+        if key.kind == KeyEventKind::Release {
+            return VimOutcome::Handled;
+        }
+        let outcome = match self.mode {
+            VimMode::Insert if key.code == KeyCode::Esc => {
+                self.mode = VimMode::Normal;
+                if textarea.cursor() > textarea.current_line_range().start {
+                    textarea.move_cursor_left();
+                }
+                VimOutcome::Handled
+            }
+            VimMode::Insert => VimOutcome::Forward(key),
+            VimMode::Normal => self.handle_normal_key(key, textarea),
+            VimMode::Visual => self.handle_visual_key(key, textarea),
+        };
+        let selection = (self.mode == VimMode::Visual).then(|| self.selection(textarea));
+        textarea.set_selection(selection);
+        outcome
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent, textarea: &mut TextArea) -> VimOutcome {
+        let modifiers = normalize_modifiers(key);
+        if let Some(operator) = self.pending.take() {
+            if let KeyCode::Char(c) = key.code
+                && modifiers == KeyModifiers::NONE
+            {
+                self.apply_operator(operator, c, textarea);
+            }
+            return VimOutcome::Handled;
+        }
+        if let Some(action) = self.custom.get(&(key.code, modifiers)).copied() {
+            return self.apply(action, textarea);
+        }
+        let KeyCode::Char(c) = key.code else {
+            return VimOutcome::Forward(key);
+        };
+        if modifiers != KeyModifiers::NONE {
+            return VimOutcome::Forward(key);
+        }
+        let action = match c {
+            'd' | 'c' | 'y' | 'g' => {
+                self.pending = Some(c);
+                return VimOutcome::Handled;
+            }
+            // Start a slash command from an empty composer.
+            ':' | '/' if textarea.is_empty() => {
+                self.mode = VimMode::Insert;
+                return VimOutcome::Forward(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+            }
+            'h' => VimAction::Left,
+            'l' => VimAction::Right,
+            'j' => VimAction::Down,
+            'k' => VimAction::Up,
+            'w' => VimAction::WordForward,
+            'b' => VimAction::WordBackward,
+            '0' | '^' => VimAction::LineStart,
+            '$' => VimAction::LineEnd,
+            'G' => VimAction::TextEnd,
+            'i' => VimAction::Insert,
+            'a' => VimAction::Append,
+            'I' => VimAction::InsertLineStart,
+            'A' => VimAction::AppendLineEnd,
+            'o' => VimAction::OpenBelow,
+            'O' => VimAction::OpenAbove,
+            'x' => VimAction::DeleteChar,
+            'X' => VimAction::DeleteCharBefore,
+            'D' => VimAction::DeleteToLineEnd,
+            'C' => VimAction::ChangeToLineEnd,
+            'Y' => VimAction::YankLine,
+            'p' => VimAction::Paste,
+            'v' => VimAction::Visual,
+            _ => return VimOutcome::Handled,
+        };
+        self.apply(action, textarea)
+    }
+
+    fn apply(&mut self, action: VimAction, textarea: &mut TextArea) -> VimOutcome {
+        let line = textarea.current_line_range();
+        match action {
+            VimAction::Left => {
+                if textarea.cursor() > line.start {
+                    textarea.move_cursor_left();
+                }
+            }
+            VimAction::Right => {
+                if textarea.next_atomic_boundary(textarea.cursor()) < line.end {
+                    textarea.move_cursor_right();
+                }
+            }
+            VimAction::Up => {
+                return VimOutcome::Forward(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+            }
+            VimAction::Down => {
+                return VimOutcome::Forward(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+            }
+            VimAction::Submit => {
+                return VimOutcome::Forward(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+            }
+            VimAction::WordForward => {
+                textarea.set_cursor(next_word_start(textarea.text(), textarea.cursor()));
+            }
+            VimAction::WordBackward => textarea.set_cursor(textarea.beginning_of_previous_word()),
+            VimAction::LineStart => textarea.set_cursor(line.start),
+            VimAction::LineEnd => {
+                textarea.set_cursor(line.end);
+                if line.end > line.start {
+                    textarea.move_cursor_left();
+                }
+            }
+            VimAction::TextStart => textarea.set_cursor(0),
+            VimAction::TextEnd => textarea.set_cursor(textarea.text().len()),
+            VimAction::Insert => self.mode = VimMode::Insert,
+            VimAction::Append => {
+                if textarea.cursor() < line.end {
+                    textarea.move_cursor_right();
+                }
+                self.mode = VimMode::Insert;
+            }
+            VimAction::InsertLineStart => {
+                textarea.set_cursor(line.start);
+                self.mode = VimMode::Insert;
+            }
+            VimAction::AppendLineEnd => {
+                textarea.set_cursor(line.end);
+                self.mode = VimMode::Insert;
+            }
+            VimAction::OpenBelow => {
+                textarea.set_cursor(line.end);
+                textarea.insert_str("\n");
+                self.mode = VimMode::Insert;
+            }
+            VimAction::OpenAbove => {
+                textarea.set_cursor(line.start);
+                textarea.insert_str("\n");
+                textarea.set_cursor(line.start);
+                self.mode = VimMode::Insert;
+            }
+            VimAction::DeleteChar => {
+                let cursor = textarea.cursor();
+                if cursor < line.end {
+                    textarea.kill(cursor..textarea.next_atomic_boundary(cursor));
+                }
+            }
+            VimAction::DeleteCharBefore => {
+                if textarea.cursor() > line.start {
+                    textarea.delete_backward(1);
+                }
+            }
+            VimAction::DeleteLine => self.apply_operator('d', 'd', textarea),
+            VimAction::DeleteToLineEnd => self.apply_operator('d', '$', textarea),
+            VimAction::ChangeLine => self.apply_operator('c', 'c', textarea),
+            VimAction::ChangeToLineEnd => self.apply_operator('c', '$', textarea),
+            VimAction::YankLine => self.apply_operator('y', 'y', textarea),
+            VimAction::Paste => {
+                if textarea.cursor() < line.end {
+                    textarea.move_cursor_right();
+                }
+                textarea.yank();
+            }
+            VimAction::Visual => {
+                self.anchor = textarea.cursor();
+                self.mode = VimMode::Visual;
+            }
+        }
+        VimOutcome::Handled
+    }
+
+    /// Runs a two-key command: `gg`, or an operator (`d`, `c`, `y`) followed
+    /// by a motion (`w`, `b`, `0`, `$`) or by itself for the whole line.
+    fn apply_operator(&mut self, operator: char, motion: char, textarea: &mut TextArea) {
+        if operator == 'g' {
+            if motion == 'g' {
+                textarea.set_cursor(0);
+            }
+            return;
+        }
+        let cursor = textarea.cursor();
+        let line = textarea.current_line_range();
+        let range = match motion {
+            m if m == operator => {
+                // The whole line, with one of its newlines unless it is the
+                // only line; `cc` keeps the line itself.
+                if operator == 'c' {
+                    line
+                } else if line.end < textarea.text().len() {
+                    line.start..line.end + 1
+                } else {
+                    line.start.saturating_sub(1)..line.end
+                }
+            }
+            'w' => cursor..next_word_start(textarea.text(), cursor),
+            'b' => textarea.beginning_of_previous_word()..cursor,
+            '0' => line.start..cursor,
+            '$' => cursor..line.end,
+            _ => return,
+        };
+        match operator {
+            'y' => textarea.copy(range),
+            'd' => textarea.kill(range),
+            'c' => {
+                textarea.kill(range);
+                self.mode = VimMode::Insert;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_visual_key(&mut self, key: KeyEvent, textarea: &mut TextArea) -> VimOutcome {
+        let modifiers = normalize_modifiers(key);
+        if key.code == KeyCode::Esc {
+            self.mode = VimMode::Normal;
+            return VimOutcome::Handled;
+        }
+        let KeyCode::Char(c) = key.code else {
+            return VimOutcome::Handled;
+        };
+        if modifiers != KeyModifiers::NONE {
+            return VimOutcome::Handled;
+        }
+        if self.pending.take() == Some('g') {
+            if c == 'g' {
+                textarea.set_cursor(0);
+            }
+            return VimOutcome::Handled;
+        }
+        match c {
+            'h' => self.move_within_line(textarea, false),
+            'l' => self.move_within_line(textarea, true),
+            'j' => textarea.move_cursor_down(),
+            'k' => textarea.move_cursor_up(),
+            'w' => textarea.set_cursor(next_word_start(textarea.text(), textarea.cursor())),
+            'b' => textarea.set_cursor(textarea.beginning_of_previous_word()),
+            '0' | '^' => textarea.set_cursor(textarea.current_line_range().start),
+            '$' => textarea.set_cursor(textarea.current_line_range().end),
+            'G' => textarea.set_cursor(textarea.text().len()),
+            'g' => self.pending = Some('g'),
+            'v' => self.mode = VimMode::Normal,
+            'd' | 'x' | 'c' | 'y' => {
+                let selection = self.selection(textarea);
+                let start = selection.start;
+                match c {
+                    'y' => textarea.copy(selection),
+                    _ => textarea.kill(selection),
+                }
+                textarea.set_cursor(start);
+                self.mode = if c == 'c' {
+                    VimMode::Insert
+                } else {
+                    VimMode::Normal
+                };
+            }
+            _ => {}
+        }
+        VimOutcome::Handled
+    }
+
+    fn move_within_line(&self, textarea: &mut TextArea, forward: bool) {
+        let line = textarea.current_line_range();
+        if forward && textarea.cursor() < line.end {
+            textarea.move_cursor_right();
+        } else if !forward && textarea.cursor() > line.start {
+            textarea.move_cursor_left();
+        }
+    }
+
+    /// The selection from the anchor to the cursor, both included.
+    fn selection(&self, textarea: &TextArea) -> Range<usize> {
+        let len = textarea.text().len();
+        let anchor = self.anchor.min(len);
+        let start = anchor.min(textarea.cursor());
+        let last = anchor.max(textarea.cursor());
+        let end = if last < len {
+            textarea.next_atomic_boundary(last)
+        } else {
+            len
+        };
+        start..end
+    }
+}
+
+/// Shift is part of the character (`G`, `$`), so only Ctrl and Alt count.
+fn normalize_modifiers(key: KeyEvent) -> KeyModifiers {
+    match key.code {
+        KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+        _ => key.modifiers,
+    }
+}
+
+/// Parses a `tui.vim_keys` key: one character, `ctrl-<char>`, or `enter`,
+/// `esc`, `tab`, `backspace`, or `space`.
+fn parse_key(key: &str) -> Option<Key> {
+    if let Some(rest) = key.strip_prefix("ctrl-") {
+        let mut chars = rest.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Some((KeyCode::Char(c.to_ascii_lowercase()), KeyModifiers::CONTROL)),
+            _ => None,
+        };
+    }
+    let code = match key {
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some((code, KeyModifiers::NONE))
+}
+
+/// Start of the next whitespace-separated word after `pos`.
+fn next_word_start(text: &str, pos: usize) -> usize {
+    let rest = &text[pos..];
+    let after_word = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    rest[after_word..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(text.len(), |i| pos + after_word + i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    fn press(vim: &mut Vim, textarea: &mut TextArea, keys: &str) {
+        for c in keys.chars() {
+            let key = match c {
+                '\u{1b}' => KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+                c => KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            };
+            if let VimOutcome::Forward(key) = vim.handle_key(key, textarea) {
+                textarea.input(key);
+            }
+        }
+    }
+
+    #[test]
+    fn normal_mode_edits_and_operators() {
+        let mut vim = Vim::new(&BTreeMap::<String, String>::new());
+        let mut textarea = TextArea::new();
+        press(&mut vim, &mut textarea, "one two three\u{1b}");
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        press(&mut vim, &mut textarea, "0dw");
+        assert_eq!(textarea.text(), "two three");
+        press(&mut vim, &mut textarea, "$x");
+        assert_eq!(textarea.text(), "two thre");
+        press(&mut vim, &mut textarea, "0cwsix\u{1b}");
+        assert_eq!(textarea.text(), "sixthre");
+        press(&mut vim, &mut textarea, "Ayy\u{1b}ddp");
+        assert_eq!(textarea.text(), "sixthreyy");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn visual_mode_selects_and_deletes() {
+        let mut vim = Vim::new(&BTreeMap::<String, String>::new());
+        let mut textarea = TextArea::new();
+        press(&mut vim, &mut textarea, "hello world\u{1b}0vllll");
+        assert_eq!(vim.mode(), VimMode::Visual);
+        assert_eq!(vim.selection(&textarea), 0..5);
+        press(&mut vim, &mut textarea, "d");
+        assert_eq!(textarea.text(), " world");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn custom_keys_override_the_defaults() {
+        let custom = BTreeMap::from([
+            ("H".to_string(), "line-start".to_string()),
+            ("ctrl-e".to_string(), "append-line-end".to_string()),
+            ("Q".to_string(), "no-such-action".to_string()),
+        ]);
+        let mut vim = Vim::new(&custom);
+        assert_eq!(vim.custom.len(), 2);
+
+        let mut textarea = TextArea::new();
+        press(&mut vim, &mut textarea, "abc\u{1b}H");
+        assert_eq!(textarea.cursor(), 0);
+        vim.handle_key(
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
+            &mut textarea,
+        );
+        assert_eq!(vim.mode(), VimMode::Insert);
+        assert_eq!(textarea.cursor(), 3);
+    }
+}
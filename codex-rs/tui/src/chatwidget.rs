@@ -4,14 +4,23 @@ use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use codex_app_server_protocol::AuthMode;
 use codex_backend_client::Client as BackendClient;
+use codex_core::artifacts::session_artifacts_dir;
 use codex_core::config::Config;
+use codex_core::config::types::DEFAULT_TUI_THEME;
+use codex_core::config::types::ModelPrice;
 use codex_core::config::types::Notifications;
+use codex_core::config::types::ShowReasoning;
+use codex_core::export::exporter_for;
+use codex_core::export::load_transcript;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
+use codex_core::git_info::GitStatusSummary;
 use codex_core::git_info::current_branch_name;
+use codex_core::git_info::git_status_short;
 use codex_core::git_info::local_git_branches;
 use codex_core::openai_models::model_family::ModelFamily;
 use codex_core::openai_models::models_manager::ModelsManager;
@@ -24,6 +33,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -44,7 +54,9 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PromptTokenBreakdown;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillsListEntry;
@@ -54,6 +66,7 @@ use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnCapabilityGrant;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
@@ -63,6 +76,7 @@ use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_core::skills::model::SkillMetadata;
+use codex_core::steering::remember_prompt;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
@@ -78,6 +92,7 @@ use ratatui::layout::Rect;
 use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
+use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 use tokio::sync::mpsc::UnboundedSender;
@@ -96,36 +111,56 @@ use crate::bottom_pane::InputResult;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::TokenMeter;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_copy::CopyTarget;
+use crate::clipboard_copy::copy_to_clipboard;
+use crate::clipboard_copy::last_code_block;
+use crate::clipboard_paste::EncodedImageFormat;
+use crate::clipboard_paste::PasteImageError;
 use crate::clipboard_paste::paste_image_to_temp_png;
+use crate::diff_render::create_turn_changes_summary;
+use crate::diff_render::diff_stat;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::file_attachment::file_attachments;
+use crate::file_citation::FileCitation;
+use crate::file_citation::find_citations;
 use crate::get_git_diff::get_git_diff;
+use crate::git_status_line::git_status_line;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
+use crate::image_thumbnail::ImageThumbnail;
+use crate::key_hint;
+use crate::live_reasoning::LiveReasoning;
 use crate::markdown::append_markdown;
+use crate::plan_pane::PlanPane;
+use crate::plan_pane::WithPlanPane;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::session_tabs::TabId;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
+use crate::theme::BUILTIN_THEMES;
 use crate::tui::FrameRequester;
 mod interrupts;
 use self::interrupts::InterruptManager;
 mod agent;
 use self::agent::spawn_agent;
 use self::agent::spawn_agent_from_existing;
+use self::agent::spawn_attached_agent;
 mod session_header;
 use self::session_header::SessionHeader;
 use crate::streaming::controller::StreamController;
@@ -134,6 +169,9 @@ use std::path::Path;
 use chrono::Local;
 use codex_common::approval_presets::ApprovalPreset;
 use codex_common::approval_presets::builtin_approval_presets;
+use codex_common::clipboard_capture::CLIPBOARD_ARTIFACTS_SUBDIR;
+use codex_common::clipboard_capture::ClipboardCapture;
+use codex_common::clipboard_capture::capture_clipboard;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
@@ -313,6 +351,25 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    /// Session usage when the running turn started; `None` between turns.
+    turn_start_usage: Option<TokenUsage>,
+    /// Usage of the last finished turn, for `/cost`.
+    last_turn_usage: Option<TokenUsage>,
+    /// When the running turn started, to skip notifications for short turns.
+    turn_started_at: Option<Instant>,
+    /// File references (`src/lib.rs:42`) in the last completed reply.
+    last_citations: Vec<FileCitation>,
+    /// What the copy keys and `/copy` copy: the last completed reply, the
+    /// last command Codex ran, and the last turn's diff.
+    last_reply: Option<String>,
+    last_command: Option<String>,
+    last_diff: Option<String>,
+    /// The running turn's file changes, summarized when it completes.
+    turn_diff: Option<String>,
+    /// Each finished turn's diff, the session diff Alt+G shows outside git.
+    session_diffs: Vec<String>,
+    /// Latest estimate of prompt tokens by source (steering, AGENTS.md, history).
+    prompt_breakdown: Option<PromptTokenBreakdown>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
@@ -332,6 +389,8 @@ pub(crate) struct ChatWidget {
     reasoning_buffer: String,
     // Accumulates full reasoning content for transcript-only recording
     full_reasoning_buffer: String,
+    // Whether the live reasoning above the composer is expanded (Alt+T).
+    reasoning_expanded: bool,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -359,11 +418,26 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // The latest plan from `update_plan`, shown beside the conversation
+    plan_pane: Option<PlanPane>,
+    // Toggled with Ctrl+L
+    plan_pane_visible: bool,
+    // Branch and worktree state shown above the conversation; `None` until
+    // known or outside a git repository
+    git_status: Option<GitStatusSummary>,
+    // Generated after the first exchange or set with `/title`
+    session_title: Option<String>,
+    // Toggled with `/plan`; turns get read-only tools and end with a plan
+    plan_mode: bool,
+    // Watching another TUI's session with `codex attach`; input is not sent
+    read_only: bool,
 }
 
 struct UserMessage {
     text: String,
     image_paths: Vec<PathBuf>,
+    /// Files picked from the `@` popup, relative to the working directory.
+    file_paths: Vec<PathBuf>,
 }
 
 impl From<String> for UserMessage {
@@ -371,6 +445,7 @@ impl From<String> for UserMessage {
         Self {
             text,
             image_paths: Vec::new(),
+            file_paths: Vec::new(),
         }
     }
 }
@@ -380,6 +455,7 @@ impl From<&str> for UserMessage {
         Self {
             text: text.to_string(),
             image_paths: Vec::new(),
+            file_paths: Vec::new(),
         }
     }
 }
@@ -388,7 +464,11 @@ fn create_initial_user_message(text: String, image_paths: Vec<PathBuf>) -> Optio
     if text.is_empty() && image_paths.is_empty() {
         None
     } else {
-        Some(UserMessage { text, image_paths })
+        Some(UserMessage {
+            text,
+            image_paths,
+            file_paths: Vec::new(),
+        })
     }
 }
 
@@ -433,6 +513,7 @@ impl ChatWidget {
         if let Some(messages) = initial_messages {
             self.replay_initial_messages(messages);
         }
+        self.refresh_git_status();
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
         self.submit_op(Op::ListSkills {
@@ -527,6 +608,7 @@ impl ChatWidget {
             let cell = history_cell::new_reasoning_summary_block(
                 self.full_reasoning_buffer.clone(),
                 reasoning_summary_format,
+                self.config.tui_show_reasoning,
             );
             self.add_boxed_history(cell);
         }
@@ -540,6 +622,7 @@ impl ChatWidget {
         self.full_reasoning_buffer.push_str(&self.reasoning_buffer);
         self.full_reasoning_buffer.push_str("\n\n");
         self.reasoning_buffer.clear();
+        self.request_redraw();
     }
 
     // Raw reasoning uses the same flow as summarized reasoning
@@ -550,27 +633,55 @@ impl ChatWidget {
         self.retry_status_header = None;
         self.bottom_pane.set_interrupt_hint_visible(true);
         self.set_status_header(String::from("Working"));
+        self.announce("Codex is working.");
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.turn_start_usage = Some(self.token_usage());
+        self.turn_started_at = Some(Instant::now());
+        self.turn_diff = None;
+        // A finished plan stays up until the next turn starts.
+        if self.plan_pane.as_ref().is_some_and(PlanPane::is_complete) {
+            self.plan_pane = None;
+        }
+        self.refresh_token_meter();
         self.request_redraw();
     }
 
     fn on_task_complete(&mut self, last_agent_message: Option<String>) {
         // If a stream is currently active, finalize it.
         self.flush_answer_stream_with_separator();
+        self.finish_turn_diff(true);
+        if let Some(start) = self.turn_start_usage.take() {
+            self.last_turn_usage = Some(crate::status::usage_since(&self.token_usage(), &start));
+        }
+        self.refresh_token_meter();
         // Mark task stopped and request redraw now that all content is in history.
         self.bottom_pane.set_task_running(false);
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
+        if let Some(message) = &last_agent_message {
+            self.last_citations = find_citations(message);
+            self.last_reply = Some(message.clone());
+        }
         self.request_redraw();
 
+        self.announce("Codex is done.");
+
         // If there is a queued user message, send exactly one now to begin the next turn.
         self.maybe_send_next_queued_input();
-        // Emit a notification when the turn completes (suppressed if focused).
-        self.notify(Notification::AgentTurnComplete {
-            response: last_agent_message.unwrap_or_default(),
-        });
+        // Emit a notification when the turn completes (suppressed if focused),
+        // unless it was shorter than `tui.turn_notification_min_seconds`.
+        let min_turn = Duration::from_secs(self.config.tui_turn_notification_min_seconds);
+        let long_enough = self
+            .turn_started_at
+            .take()
+            .is_none_or(|started| started.elapsed() >= min_turn);
+        if long_enough {
+            self.notify(Notification::AgentTurnComplete {
+                response: last_agent_message.unwrap_or_default(),
+            });
+        }
 
         self.maybe_show_pending_rate_limit_prompt();
     }
@@ -581,6 +692,7 @@ impl ChatWidget {
             None => {
                 self.bottom_pane.set_context_window(None, None);
                 self.token_info = None;
+                self.refresh_token_meter();
             }
         }
     }
@@ -590,6 +702,43 @@ impl ChatWidget {
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
         self.bottom_pane.set_context_window(percent, used_tokens);
         self.token_info = Some(info);
+        self.refresh_token_meter();
+    }
+
+    fn model_price(&self) -> Option<&ModelPrice> {
+        self.config
+            .model_prices
+            .get(self.model_family.get_model_slug())
+    }
+
+    /// Updates the session and turn token counts in the footer.
+    fn refresh_token_meter(&mut self) {
+        let Some(info) = &self.token_info else {
+            self.bottom_pane.set_token_meter(None);
+            return;
+        };
+        let session = &info.total_token_usage;
+        let meter = TokenMeter {
+            session_tokens: session.blended_total(),
+            turn_tokens: self
+                .turn_start_usage
+                .as_ref()
+                .map(|start| crate::status::usage_since(session, start).blended_total()),
+            session_cost: self
+                .model_price()
+                .map(|price| crate::status::estimate_cost(session, price)),
+        };
+        self.bottom_pane.set_token_meter(Some(meter));
+    }
+
+    pub(crate) fn add_cost_output(&mut self) {
+        let session = self.token_usage();
+        self.add_to_history(crate::status::new_cost_output(
+            self.model_family.get_model_slug(),
+            self.model_price(),
+            &session,
+            self.last_turn_usage.as_ref(),
+        ));
     }
 
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
@@ -698,6 +847,8 @@ impl ChatWidget {
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
         self.stream_controller = None;
+        self.reasoning_buffer.clear();
+        self.full_reasoning_buffer.clear();
         self.maybe_show_pending_rate_limit_prompt();
     }
     pub(crate) fn get_model_family(&self) -> ModelFamily {
@@ -713,6 +864,18 @@ impl ChatWidget {
         self.maybe_send_next_queued_input();
     }
 
+    fn on_config_reloaded(&mut self, ev: ConfigReloadedEvent) {
+        if ev.applied.iter().any(|key| key == "model") {
+            self.app_event_tx
+                .send(AppEvent::UpdateModel(ev.model.clone()));
+        }
+        if ev.applied.iter().any(|key| key == "model_reasoning_effort") {
+            self.app_event_tx
+                .send(AppEvent::UpdateReasoningEffort(ev.reasoning_effort));
+        }
+        self.add_info_message(ev.to_string(), None);
+    }
+
     fn on_warning(&mut self, message: impl Into<String>) {
         self.add_to_history(history_cell::new_warning_event(message.into()));
         self.request_redraw();
@@ -790,6 +953,16 @@ impl ChatWidget {
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
     fn on_interrupted_turn(&mut self, reason: TurnAbortReason) {
+        // Keep the part of the reply that already streamed. Core keeps it in
+        // the conversation too, so the next turn builds on it.
+        let reply_interrupted = self.stream_controller.is_some();
+        self.flush_answer_stream_with_separator();
+        if reply_interrupted {
+            self.add_plain_history_lines(vec!["(reply interrupted)".dim().italic().into()]);
+        }
+
+        self.finish_turn_diff(false);
+
         // Finalize, log a gentle prompt, and clear running state.
         self.finalize_turn();
 
@@ -825,9 +998,51 @@ impl ChatWidget {
     }
 
     fn on_plan_update(&mut self, update: UpdatePlanArgs) {
+        self.plan_pane = Some(PlanPane::new(update.clone()));
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn toggle_plan_pane(&mut self) {
+        self.plan_pane_visible = !self.plan_pane_visible;
+        if self.plan_pane.is_none() {
+            let state = if self.plan_pane_visible {
+                "shown"
+            } else {
+                "hidden"
+            };
+            self.add_info_message(
+                format!("The plan pane will be {state} once Codex makes a plan."),
+                Some("Press Ctrl+L to toggle it.".to_string()),
+            );
+        }
+        self.request_redraw();
+    }
+
+    fn toggle_live_reasoning(&mut self) {
+        self.reasoning_expanded = !self.reasoning_expanded;
+        if self.live_reasoning().is_none() {
+            let state = if self.reasoning_expanded {
+                "expanded"
+            } else {
+                "collapsed"
+            };
+            self.add_info_message(
+                format!("Reasoning will stream {state} while Codex thinks."),
+                Some("Press Alt+T to toggle it.".to_string()),
+            );
+        }
+        self.request_redraw();
+    }
+
+    /// The reasoning streamed so far in this block, if it should be shown.
+    fn live_reasoning(&self) -> Option<LiveReasoning> {
+        LiveReasoning::new(
+            format!("{}{}", self.full_reasoning_buffer, self.reasoning_buffer),
+            self.config.tui_show_reasoning,
+            self.reasoning_expanded,
+        )
+    }
+
     fn on_exec_approval_request(&mut self, id: String, ev: ExecApprovalRequestEvent) {
         let id2 = id.clone();
         let ev2 = ev.clone();
@@ -903,6 +1118,7 @@ impl ChatWidget {
     }
 
     fn on_patch_apply_end(&mut self, event: codex_core::protocol::PatchApplyEndEvent) {
+        self.refresh_git_status();
         let ev2 = event.clone();
         self.defer_or_handle(
             |q| q.push_patch_end(event),
@@ -911,6 +1127,10 @@ impl ChatWidget {
     }
 
     fn on_exec_command_end(&mut self, ev: ExecCommandEndEvent) {
+        // Reads, listings, and searches leave the worktree as it was.
+        if !is_standard_tool_call(&ev.parsed_cmd) {
+            self.refresh_git_status();
+        }
         if is_unified_exec_source(ev.source) {
             self.track_unified_exec_session_end(&ev);
             if !self.bottom_pane.is_task_running() {
@@ -999,6 +1219,39 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        if !unified_diff.trim().is_empty() {
+            self.last_diff = Some(unified_diff.clone());
+        }
+        // Each event carries the whole turn's diff so far.
+        self.turn_diff = Some(unified_diff).filter(|diff| !diff.trim().is_empty());
+    }
+
+    /// Keeps the finished turn's diff for the session diff and, when asked,
+    /// adds the "changes this turn" summary.
+    fn finish_turn_diff(&mut self, summarize: bool) {
+        let Some(diff) = self.turn_diff.take() else {
+            return;
+        };
+        let stats = diff_stat(&diff);
+        if summarize && !stats.is_empty() {
+            self.add_plain_history_lines(create_turn_changes_summary(&stats));
+        }
+        self.session_diffs.push(diff);
+    }
+
+    /// Alt+G: everything changed this session, from git when the session is
+    /// in a repository and from the diffs of finished turns otherwise.
+    fn open_session_diff(&mut self) {
+        self.add_diff_in_progress();
+        let tx = self.app_event_tx.clone();
+        let tracked = self.session_diffs.join("");
+        tokio::spawn(async move {
+            let text = match get_git_diff().await {
+                Ok((true, diff_text)) => diff_text,
+                _ => tracked,
+            };
+            tx.send(AppEvent::DiffResult(text));
+        });
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -1130,6 +1383,9 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if !is_unified_exec_interaction {
+            self.last_command = Some(strip_bash_lc_and_escape(&command));
+        }
 
         let needs_new = self
             .active_cell
@@ -1188,11 +1444,13 @@ impl ChatWidget {
         self.flush_answer_stream_with_separator();
         let command = shlex::try_join(ev.command.iter().map(String::as_str))
             .unwrap_or_else(|_| ev.command.join(" "));
+        self.announce(&format!("Approval needed to run {command}."));
         self.notify(Notification::ExecApprovalRequested { command });
 
         let request = ApprovalRequest::Exec {
             id,
             command: ev.command,
+            cwd: ev.cwd,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
@@ -1214,6 +1472,11 @@ impl ChatWidget {
             changes: ev.changes.clone(),
             cwd: self.config.cwd.clone(),
         };
+        let files = ev.changes.len();
+        self.announce(&format!(
+            "Approval needed to edit {files} {}.",
+            if files == 1 { "file" } else { "files" }
+        ));
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
         self.request_redraw();
@@ -1223,6 +1486,11 @@ impl ChatWidget {
         });
     }
 
+    /// Answers a patch approval request from the hunk review overlay.
+    pub(crate) fn resolve_patch_approval(&mut self, id: &str, decision: ReviewDecision) {
+        self.bottom_pane.resolve_patch_approval(id, decision);
+    }
+
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
 
@@ -1370,6 +1638,9 @@ impl ChatWidget {
         let placeholder = EXAMPLE_PROMPTS[rng.random_range(0..EXAMPLE_PROMPTS.len())].to_string();
         let codex_op_tx = spawn_agent(config.clone(), app_event_tx.clone(), conversation_manager);
 
+        let reasoning_expanded = config.tui_show_reasoning == ShowReasoning::Always;
+        // The side pane reads as interleaved lines to a screen reader.
+        let plan_pane_visible = !config.tui_accessible;
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -1395,6 +1666,16 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            turn_start_usage: None,
+            turn_started_at: None,
+            last_citations: Vec::new(),
+            last_reply: None,
+            last_command: None,
+            last_diff: None,
+            turn_diff: None,
+            session_diffs: Vec::new(),
+            last_turn_usage: None,
+            prompt_breakdown: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1410,6 +1691,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_expanded,
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -1423,8 +1705,17 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            plan_pane: None,
+            plan_pane_visible,
+            git_status: None,
+            session_title: None,
+            plan_mode: false,
+            read_only: false,
         };
 
+        widget
+            .bottom_pane
+            .set_keymap(widget.config.tui_keymap, &widget.config.tui_vim_keys);
         widget.prefetch_rate_limits();
 
         widget
@@ -1435,6 +1726,31 @@ impl ChatWidget {
         common: ChatWidgetInit,
         conversation: std::sync::Arc<codex_core::CodexConversation>,
         session_configured: codex_core::protocol::SessionConfiguredEvent,
+    ) -> Self {
+        let attach_home = common
+            .config
+            .tui_allow_attach
+            .then(|| common.config.codex_home.clone());
+        let codex_op_tx = spawn_agent_from_existing(
+            conversation,
+            session_configured,
+            common.app_event_tx.clone(),
+            attach_home,
+        );
+        Self::with_agent(common, codex_op_tx, false)
+    }
+
+    /// A read-only view of a session running in another TUI, reached through
+    /// its attach socket (`codex attach`).
+    pub(crate) fn new_attached(common: ChatWidgetInit, socket_path: PathBuf) -> Self {
+        let codex_op_tx = spawn_attached_agent(socket_path, common.app_event_tx.clone());
+        Self::with_agent(common, codex_op_tx, true)
+    }
+
+    fn with_agent(
+        common: ChatWidgetInit,
+        codex_op_tx: UnboundedSender<Op>,
+        read_only: bool,
     ) -> Self {
         let ChatWidgetInit {
             config,
@@ -1453,9 +1769,9 @@ impl ChatWidget {
         let mut rng = rand::rng();
         let placeholder = EXAMPLE_PROMPTS[rng.random_range(0..EXAMPLE_PROMPTS.len())].to_string();
 
-        let codex_op_tx =
-            spawn_agent_from_existing(conversation, session_configured, app_event_tx.clone());
-
+        let reasoning_expanded = config.tui_show_reasoning == ShowReasoning::Always;
+        // The side pane reads as interleaved lines to a screen reader.
+        let plan_pane_visible = !config.tui_accessible;
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -1481,6 +1797,16 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            turn_start_usage: None,
+            turn_started_at: None,
+            last_citations: Vec::new(),
+            last_reply: None,
+            last_command: None,
+            last_diff: None,
+            turn_diff: None,
+            session_diffs: Vec::new(),
+            last_turn_usage: None,
+            prompt_breakdown: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1496,6 +1822,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            reasoning_expanded,
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
@@ -1509,8 +1836,17 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            plan_pane: None,
+            plan_pane_visible,
+            git_status: None,
+            session_title: None,
+            plan_mode: false,
+            read_only,
         };
 
+        widget
+            .bottom_pane
+            .set_keymap(widget.config.tui_keymap, &widget.config.tui_vim_keys);
         widget.prefetch_rate_limits();
 
         widget
@@ -1535,21 +1871,64 @@ impl ChatWidget {
             } if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
                 && c.eq_ignore_ascii_case(&'v') =>
             {
-                match paste_image_to_temp_png() {
-                    Ok((path, info)) => {
-                        self.attach_image(
-                            path,
-                            info.width,
-                            info.height,
-                            info.encoded_format.label(),
-                        );
-                    }
-                    Err(err) => {
-                        tracing::warn!("failed to paste image: {err}");
-                        self.add_to_history(history_cell::new_error_event(format!(
-                            "Failed to paste image: {err}",
-                        )));
-                    }
+                if let Err(err) = self.paste_clipboard_image() {
+                    self.add_to_history(history_cell::new_error_event(format!(
+                        "Failed to paste image: {err}",
+                    )));
+                }
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if (c.eq_ignore_ascii_case(&'o') || c.eq_ignore_ascii_case(&'g'))
+                && !self.bottom_pane.has_active_view() =>
+            {
+                self.bottom_pane.clear_ctrl_c_quit_hint();
+                self.open_citations_popup(c.eq_ignore_ascii_case(&'g'));
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'l') => {
+                self.bottom_pane.clear_ctrl_c_quit_hint();
+                self.toggle_plan_pane();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'t') && !self.bottom_pane.has_active_view() => {
+                self.bottom_pane.clear_ctrl_c_quit_hint();
+                self.toggle_live_reasoning();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if c.eq_ignore_ascii_case(&'g') && !self.bottom_pane.has_active_view() => {
+                self.bottom_pane.clear_ctrl_c_quit_hint();
+                self.open_session_diff();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if CopyTarget::for_key(c).is_some() && !self.bottom_pane.has_active_view() => {
+                self.bottom_pane.clear_ctrl_c_quit_hint();
+                if let Some(target) = CopyTarget::for_key(c) {
+                    self.copy(target);
                 }
                 return;
             }
@@ -1580,12 +1959,16 @@ impl ChatWidget {
                         let user_message = UserMessage {
                             text,
                             image_paths: self.bottom_pane.take_recent_submission_images(),
+                            file_paths: self.bottom_pane.take_recent_submission_files(),
                         };
                         self.queue_user_message(user_message);
                     }
                     InputResult::Command(cmd) => {
                         self.dispatch_command(cmd);
                     }
+                    InputResult::CommandWithArgs(cmd, args) => {
+                        self.dispatch_command_with_args(cmd, args);
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1607,6 +1990,226 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Save the clipboard under the session artifacts directory and attach it to
+    /// the composer: images as image attachments, text as a path reference.
+    fn paste_clipboard_as_file(&mut self) {
+        let Some(conversation_id) = self.conversation_id else {
+            self.add_error_message(
+                "Session has not started yet; try again in a moment.".to_string(),
+            );
+            return;
+        };
+        let dir = session_artifacts_dir(&self.config.codex_home, conversation_id)
+            .join(CLIPBOARD_ARTIFACTS_SUBDIR);
+        match capture_clipboard(&dir) {
+            Ok(ClipboardCapture::Image {
+                path,
+                width,
+                height,
+            }) => {
+                self.attach_image(path, width, height, EncodedImageFormat::Png.label());
+            }
+            Ok(ClipboardCapture::Text { path, bytes }) => {
+                self.insert_str(&format!("{} ", path.display()));
+                self.add_info_message(
+                    format!(
+                        "Saved {bytes} bytes of clipboard text to {}",
+                        path.display()
+                    ),
+                    None,
+                );
+            }
+            Err(err) => {
+                tracing::warn!("failed to save clipboard: {err}");
+                self.add_error_message(format!("Failed to paste clipboard as file: {err}"));
+            }
+        }
+    }
+
+    fn dispatch_command_with_args(&mut self, cmd: SlashCommand, args: String) {
+        if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
+            let message = format!(
+                "'/{}' is disabled while a task is in progress.",
+                cmd.command()
+            );
+            self.add_to_history(history_cell::new_error_event(message));
+            self.request_redraw();
+            return;
+        }
+        match cmd {
+            SlashCommand::Remember => {
+                self.remember_steering_rule(Some(args.as_str()));
+            }
+            SlashCommand::AllowOnce => {
+                self.grant_turn_capability(&args);
+            }
+            SlashCommand::Mcp => {
+                self.add_session_mcp_server(&args);
+            }
+            SlashCommand::Tab => {
+                self.run_tab_command(&args);
+            }
+            SlashCommand::Export => {
+                self.export_transcript(&args);
+            }
+            SlashCommand::Title => self.rename_session(&args),
+            SlashCommand::Plan => self.plan(&args),
+            SlashCommand::Copy => match CopyTarget::from_arg(args.trim()) {
+                Some(target) => self.copy(target),
+                None => self.add_info_message(
+                    "Usage: /copy [code|command|diff|reply]".to_string(),
+                    Some("Without an argument, /copy lists what there is to copy.".to_string()),
+                ),
+            },
+            _ => self.dispatch_command(cmd),
+        }
+    }
+
+    /// Ask the model to rewrite `rule` (or the lesson from the current
+    /// session) as an imperative and append it to the learned steering file.
+    fn remember_steering_rule(&mut self, rule: Option<&str>) {
+        if !self.config.features.enabled(Feature::Steering) {
+            self.add_error_message(
+                "/remember requires the `steering` feature; restart with `--enable steering` or set `features.steering = true`.".to_string(),
+            );
+            return;
+        }
+        let prompt = remember_prompt(&self.config, rule);
+        self.submit_user_message(prompt.into());
+    }
+
+    /// Parse `/export` arguments and write the session's transcript in the
+    /// background, reporting the result in the transcript.
+    fn export_transcript(&mut self, args: &str) {
+        let Some(request) = parse_export_args(args) else {
+            self.add_info_message(
+                "Usage: /export [markdown|html|json] <path> [--redact]".to_string(),
+                Some(
+                    "The format defaults to the file extension; --redact hides paths and environment details."
+                        .to_string(),
+                ),
+            );
+            return;
+        };
+        if exporter_for(&request.format, &self.config.exporters).is_none() {
+            self.add_error_message(format!("Unknown export format `{}`.", request.format));
+            return;
+        }
+        let Some(rollout_path) = self.rollout_path() else {
+            self.add_error_message("Nothing to export yet.".to_string());
+            return;
+        };
+
+        let output = self.config.cwd.join(&request.path);
+        let exporters = self.config.exporters.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result: Result<(), String> = async {
+                let transcript = load_transcript(&rollout_path)
+                    .await
+                    .map_err(|err| format!("failed to read the session: {err}"))?;
+                let transcript = if request.redact {
+                    transcript.redacted()
+                } else {
+                    transcript
+                };
+                let rendered = exporter_for(&request.format, &exporters)
+                    .ok_or_else(|| format!("unknown export format `{}`", request.format))?
+                    .export(&transcript)
+                    .map_err(|err| err.to_string())?;
+                tokio::fs::write(&output, rendered)
+                    .await
+                    .map_err(|err| format!("failed to write {}: {err}", output.display()))
+            }
+            .await;
+            let cell = match result {
+                Ok(()) => history_cell::new_info_event(
+                    format!("Exported the conversation to {}", output.display()),
+                    request.redact.then(|| "(redacted)".to_string()),
+                ),
+                Err(err) => history_cell::new_error_event(format!("Export failed: {err}")),
+            };
+            tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+        });
+    }
+
+    /// `/plan` toggles plan mode; `/plan <task>` turns it on and asks for a
+    /// plan for the task.
+    fn plan(&mut self, task: &str) {
+        let task = task.trim();
+        let enabled = !task.is_empty() || !self.plan_mode;
+        if enabled != self.plan_mode {
+            self.plan_mode = enabled;
+            self.submit_op(Op::SetPlanMode { enabled });
+            if enabled {
+                self.add_info_message(
+                    "Plan mode on: Codex will investigate and reply with a plan without editing."
+                        .to_string(),
+                    Some("Run /plan again to turn it off and carry out the plan.".to_string()),
+                );
+            } else {
+                self.add_info_message(
+                    "Plan mode off: editing tools are enabled.".to_string(),
+                    None,
+                );
+            }
+        }
+        if !task.is_empty() {
+            self.submit_user_message(task.to_string().into());
+        }
+    }
+
+    /// Parse `/allow-once` arguments and ask core to grant the capability for
+    /// the next turn only.
+    fn grant_turn_capability(&mut self, args: &str) {
+        match parse_turn_capability_grant(args) {
+            Some(grant) => self.submit_op(Op::GrantTurnCapability { grant }),
+            None => self.add_info_message(
+                "Usage: /allow-once network | tool <feature> | write <dir>".to_string(),
+                Some(
+                    "The grant applies to your next message and is reverted afterwards."
+                        .to_string(),
+                ),
+            ),
+        }
+    }
+
+    /// Parse `/mcp add <name> <command>` and attach the server to this
+    /// session only; it is not written to config.toml.
+    fn add_session_mcp_server(&mut self, args: &str) {
+        let usage = || "Usage: /mcp add <name> <command | url>".to_string();
+        let Some(rest) = args
+            .strip_prefix("add")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+        else {
+            self.add_info_message(usage(), None);
+            return;
+        };
+        let rest = rest.trim_start();
+        let (name, command) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let parsed = codex_core::mcp::split_mcp_command(command).and_then(|command| {
+            codex_core::mcp::session_mcp_server_config(name, command.clone())
+                .map(|server| (command, server))
+        });
+        let (command, server) = match parsed {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                self.add_error_message(format!("{message}. {}", usage()));
+                return;
+            }
+        };
+        if self.config.mcp_servers.contains_key(name) {
+            self.add_error_message(format!("MCP server '{name}' is already configured."));
+            return;
+        }
+
+        self.config.mcp_servers.insert(name.to_string(), server);
+        self.submit_op(Op::AddMcpServer {
+            name: name.to_string(),
+            command,
+        });
+    }
+
     fn dispatch_command(&mut self, cmd: SlashCommand) {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
@@ -1631,6 +2234,12 @@ impl ChatWidget {
             SlashCommand::Resume => {
                 self.app_event_tx.send(AppEvent::OpenResumePicker);
             }
+            SlashCommand::Title => {
+                self.rename_session("");
+            }
+            SlashCommand::Plan => {
+                self.plan("");
+            }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
                 if init_target.exists() {
@@ -1656,9 +2265,21 @@ impl ChatWidget {
             SlashCommand::Approvals => {
                 self.open_approvals_popup();
             }
+            SlashCommand::AllowOnce => {
+                self.grant_turn_capability("");
+            }
             SlashCommand::Experimental => {
                 self.open_experimental_popup();
             }
+            SlashCommand::Theme => {
+                self.open_theme_popup();
+            }
+            SlashCommand::Mouse => {
+                self.app_event_tx.send(AppEvent::ToggleMouseCapture);
+            }
+            SlashCommand::Tab => {
+                self.app_event_tx.send(AppEvent::OpenTabPicker);
+            }
             SlashCommand::Quit | SlashCommand::Exit => {
                 self.request_exit();
             }
@@ -1691,15 +2312,40 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Git => {
+                let tx = self.app_event_tx.clone();
+                let cwd = self.config.cwd.clone();
+                tokio::spawn(async move {
+                    let text = git_status_short(&cwd)
+                        .await
+                        .unwrap_or_else(|| "`/git` — _not inside a git repository_".to_string());
+                    tx.send(AppEvent::GitStatusResult(text));
+                });
+            }
+            SlashCommand::Export => {
+                self.export_transcript("");
+            }
+            SlashCommand::Copy => {
+                self.open_copy_popup();
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
+            SlashCommand::PasteAsFile => {
+                self.paste_clipboard_as_file();
+            }
+            SlashCommand::Remember => {
+                self.remember_steering_rule(None);
+            }
             SlashCommand::Skills => {
                 self.insert_str("$");
             }
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Cost => {
+                self.add_cost_output();
+            }
             SlashCommand::Ps => {
                 self.add_ps_output();
             }
@@ -1758,9 +2404,24 @@ impl ChatWidget {
     }
 
     pub(crate) fn handle_paste(&mut self, text: String) {
+        // Terminals such as kitty and iTerm2 send an empty paste when the
+        // clipboard holds only an image; attach the image instead.
+        if text.is_empty() {
+            let _ = self.paste_clipboard_image();
+            return;
+        }
         self.bottom_pane.handle_paste(text);
     }
 
+    /// Attach the clipboard's image, saved to a temporary PNG.
+    fn paste_clipboard_image(&mut self) -> Result<(), PasteImageError> {
+        let (path, info) = paste_image_to_temp_png().inspect_err(|err| {
+            tracing::warn!("failed to paste image: {err}");
+        })?;
+        self.attach_image(path, info.width, info.height, info.encoded_format.label());
+        Ok(())
+    }
+
     // Returns true if caller should skip rendering this frame (a future frame is scheduled).
     pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
         if self.bottom_pane.flush_paste_burst_if_due() {
@@ -1809,10 +2470,20 @@ impl ChatWidget {
     }
 
     fn submit_user_message(&mut self, user_message: UserMessage) {
-        let UserMessage { text, image_paths } = user_message;
+        let UserMessage {
+            text,
+            image_paths,
+            file_paths,
+        } = user_message;
         if text.is_empty() && image_paths.is_empty() {
             return;
         }
+        if self.read_only {
+            self.add_error_message(
+                "This session is attached read-only; the message was not sent.".to_string(),
+            );
+            return;
+        }
 
         let mut items: Vec<UserInput> = Vec::new();
 
@@ -1838,10 +2509,21 @@ impl ChatWidget {
             items.push(UserInput::Text { text: text.clone() });
         }
 
+        let thumbnails: Vec<ImageThumbnail> = image_paths
+            .iter()
+            .filter_map(|path| ImageThumbnail::load(path))
+            .collect();
         for path in image_paths {
             items.push(UserInput::LocalImage { path });
         }
 
+        items.extend(file_attachments(
+            &text,
+            &file_paths,
+            &self.config.cwd,
+            self.config.file_attachment_max_bytes,
+        ));
+
         if let Some(skills) = self.bottom_pane.skills() {
             let skill_mentions = find_skill_mentions(&text, skills);
             for skill in skill_mentions {
@@ -1867,9 +2549,9 @@ impl ChatWidget {
                 });
         }
 
-        // Only show the text portion in conversation history.
-        if !text.is_empty() {
-            self.add_to_history(history_cell::new_user_prompt(text));
+        // Show the text and a preview of each image in conversation history.
+        if !text.is_empty() || !thumbnails.is_empty() {
+            self.add_to_history(history_cell::new_user_prompt(text, thumbnails));
         }
         self.needs_final_message_separator = false;
     }
@@ -1917,6 +2599,8 @@ impl ChatWidget {
 
         match msg {
             EventMsg::SessionConfigured(e) => self.on_session_configured(e),
+            EventMsg::ConfigReloaded(ev) => self.on_config_reloaded(ev),
+            EventMsg::SessionTitle(ev) => self.on_session_title(ev.title),
             EventMsg::AgentMessage(AgentMessageEvent { message }) => self.on_agent_message(message),
             EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { delta }) => {
                 self.on_agent_message_delta(delta)
@@ -1936,10 +2620,14 @@ impl ChatWidget {
                 self.on_task_complete(last_agent_message)
             }
             EventMsg::TokenCount(ev) => {
+                if ev.prompt_breakdown.is_some() {
+                    self.prompt_breakdown = ev.prompt_breakdown;
+                }
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            EventMsg::TerminologyWarning(ev) => self.on_warning(ev.to_string()),
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
@@ -1977,6 +2665,10 @@ impl ChatWidget {
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
+            EventMsg::SearchHistoryResponse(ev) => self.bottom_pane.on_history_search_response(
+                &ev.query,
+                ev.entries.into_iter().map(|entry| entry.text).collect(),
+            ),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
@@ -2008,6 +2700,7 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::RunLimitReached(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -2068,7 +2761,10 @@ impl ChatWidget {
     fn on_user_message_event(&mut self, event: UserMessageEvent) {
         let message = event.message.trim();
         if !message.is_empty() {
-            self.add_to_history(history_cell::new_user_prompt(message.to_string()));
+            self.add_to_history(history_cell::new_user_prompt(
+                message.to_string(),
+                Vec::new(),
+            ));
         }
     }
 
@@ -2129,6 +2825,55 @@ impl ChatWidget {
         self.bottom_pane.set_queued_user_messages(messages);
     }
 
+    fn refresh_git_status(&self) {
+        self.app_event_tx.send(AppEvent::RefreshGitStatus);
+    }
+
+    pub(crate) fn set_git_status(&mut self, summary: Option<GitStatusSummary>) {
+        if self.git_status != summary {
+            self.git_status = summary;
+            self.request_redraw();
+        }
+    }
+
+    fn on_session_title(&mut self, title: String) {
+        self.announce(&format!("Session title: {title}"));
+        self.session_title = Some(title);
+        self.request_redraw();
+    }
+
+    /// `/title <name>` renames the session; a bare `/title` shows the
+    /// current title.
+    fn rename_session(&mut self, args: &str) {
+        let title = args.trim();
+        if title.is_empty() {
+            let message = match &self.session_title {
+                Some(title) => format!("Session title: {title}"),
+                None => "This session has no title yet.".to_string(),
+            };
+            self.add_info_message(message, Some("Rename it with /title <name>.".to_string()));
+            return;
+        }
+        self.submit_op(Op::SetSessionTitle {
+            title: title.to_string(),
+        });
+    }
+
+    /// The line above the conversation: the session title, then git state.
+    fn header_line(&self) -> Option<Line<'static>> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        if let Some(title) = &self.session_title {
+            spans.push(title.clone().bold());
+        }
+        if let Some(summary) = &self.git_status {
+            if !spans.is_empty() {
+                spans.push("  ".into());
+            }
+            spans.extend(git_status_line(summary).spans);
+        }
+        (!spans.is_empty()).then(|| spans.into())
+    }
+
     pub(crate) fn add_diff_in_progress(&mut self) {
         self.request_redraw();
     }
@@ -2150,6 +2895,7 @@ impl ChatWidget {
             &self.model_family,
             total_usage,
             context_usage,
+            self.prompt_breakdown.as_ref(),
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
@@ -2736,6 +3482,140 @@ impl ChatWidget {
         });
     }
 
+    /// The text `target` copies, if there is any yet.
+    fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::CodeBlock => self.last_reply.as_deref().and_then(last_code_block),
+            CopyTarget::Command => self.last_command.clone(),
+            CopyTarget::Diff => self.last_diff.clone(),
+            CopyTarget::Reply => self.last_reply.clone(),
+        }
+    }
+
+    /// Puts the text `target` names on the clipboard and says where it went.
+    pub(crate) fn copy(&mut self, target: CopyTarget) {
+        let Some(text) = self.copy_text(target) else {
+            self.add_info_message(format!("Nothing to copy: no {} yet.", target.label()), None);
+            return;
+        };
+        match copy_to_clipboard(&text) {
+            Ok(destination) => self.add_info_message(
+                format!(
+                    "Copied the {} to the {}.",
+                    target.label(),
+                    destination.describe()
+                ),
+                None,
+            ),
+            Err(err) => {
+                self.add_error_message(format!("Failed to copy the {}: {err}", target.label()));
+            }
+        }
+    }
+
+    /// Lists what there is to copy, each with its Alt shortcut.
+    fn open_copy_popup(&mut self) {
+        let items: Vec<SelectionItem> = CopyTarget::ALL
+            .into_iter()
+            .filter_map(|target| {
+                let text = self.copy_text(target)?;
+                let preview = text.lines().find(|line| !line.trim().is_empty())?;
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::Copy(target));
+                })];
+                let mut name = target.label().to_string();
+                if let Some(first) = name.get_mut(..1) {
+                    first.make_ascii_uppercase();
+                }
+                Some(SelectionItem {
+                    name,
+                    description: Some(truncate_text(preview.trim(), 60)),
+                    display_shortcut: Some(key_hint::alt(KeyCode::Char(target.key()))),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        if items.is_empty() {
+            self.add_info_message(
+                "Nothing to copy yet.".to_string(),
+                Some("Replies, commands, and diffs from this session can be copied.".to_string()),
+            );
+            return;
+        }
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Copy to clipboard".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Lists the file references in the last reply. Choosing one shows its
+    /// lines in the transcript, or with `open_in_editor` opens it in
+    /// `$VISUAL`/`$EDITOR`.
+    fn open_citations_popup(&mut self, open_in_editor: bool) {
+        let cwd = self.config.cwd.clone();
+        let citations: Vec<FileCitation> = self
+            .last_citations
+            .iter()
+            .filter(|citation| citation.resolve(&cwd).is_file())
+            .cloned()
+            .collect();
+        if citations.is_empty() {
+            self.add_info_message("No file references in the last reply.".to_string(), None);
+            return;
+        }
+        if open_in_editor && let [citation] = citations.as_slice() {
+            self.app_event_tx.send(AppEvent::OpenInEditor {
+                path: citation.resolve(&cwd),
+                line: citation.start,
+            });
+            return;
+        }
+
+        let items = citations
+            .into_iter()
+            .map(|citation| {
+                let name = citation.label();
+                let cwd = cwd.clone();
+                let actions: Vec<SelectionAction> = if open_in_editor {
+                    vec![Box::new(move |tx| {
+                        tx.send(AppEvent::OpenInEditor {
+                            path: citation.resolve(&cwd),
+                            line: citation.start,
+                        });
+                    })]
+                } else {
+                    vec![Box::new(move |tx| {
+                        tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            citation.snippet_cell(&cwd),
+                        )));
+                    })]
+                };
+                SelectionItem {
+                    name,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(if open_in_editor {
+                "Open in editor".to_string()
+            } else {
+                "Show referenced lines".to_string()
+            }),
+            subtitle: Some("File references in the last reply".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_experimental_popup(&mut self) {
         let features: Vec<BetaFeatureItem> = FEATURES
             .iter()
@@ -2755,6 +3635,121 @@ impl ChatWidget {
         self.bottom_pane.show_view(Box::new(view));
     }
 
+    /// Lists the built-in and user-defined themes. Moving through the list
+    /// previews each one; Esc goes back to the current theme.
+    /// `/tab` lists the open tabs, `/tab new [dir]` opens one (in `dir`,
+    /// relative to this session's working directory), and `/tab close`
+    /// closes this one.
+    fn run_tab_command(&mut self, args: &str) {
+        let args = args.trim();
+        let (subcommand, rest) = args.split_once(' ').unwrap_or((args, ""));
+        match subcommand {
+            "" => self.app_event_tx.send(AppEvent::OpenTabPicker),
+            "new" => {
+                let rest = rest.trim();
+                let cwd = (!rest.is_empty()).then(|| self.config.cwd.join(rest));
+                self.app_event_tx.send(AppEvent::OpenTab { cwd });
+            }
+            "close" if rest.trim().is_empty() => self.app_event_tx.send(AppEvent::CloseTab),
+            _ => self.add_info_message(
+                "Usage: /tab, /tab new [dir], or /tab close".to_string(),
+                None,
+            ),
+        }
+    }
+
+    pub(crate) fn open_tab_picker(&mut self, tabs: Vec<(TabId, String, bool)>) {
+        let mut items: Vec<SelectionItem> = tabs
+            .into_iter()
+            .map(|(id, label, is_current)| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SwitchTab(id));
+                })];
+                SelectionItem {
+                    name: label,
+                    is_current,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let actions: Vec<SelectionAction> = vec![Box::new(|tx| {
+            tx.send(AppEvent::OpenTab { cwd: None });
+        })];
+        items.push(SelectionItem {
+            name: "New tab".to_string(),
+            description: Some(format!("in {}", self.config.cwd.display())),
+            actions,
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Tabs".to_string()),
+            subtitle: Some("Ctrl+Tab and Ctrl+Shift+Tab switch between tabs.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_theme_popup(&mut self) {
+        let names: Vec<String> = BUILTIN_THEMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain(
+                self.config
+                    .tui_themes
+                    .keys()
+                    .filter(|name| !BUILTIN_THEMES.contains(&name.as_str()))
+                    .cloned(),
+            )
+            .collect();
+        let items = names
+            .iter()
+            .map(|name| {
+                let description = match name.as_str() {
+                    "auto" => "dark or light, following the terminal background",
+                    "dark" => "the terminal's own ANSI colors",
+                    "light" => "darker colors for light backgrounds",
+                    "high-contrast" => "bright colors and solid borders",
+                    _ => "from [tui.themes] in config.toml",
+                };
+                let theme = name.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::PersistTheme(theme.clone()));
+                })];
+                SelectionItem {
+                    name: name.clone(),
+                    description: Some(description.to_string()),
+                    is_current: *name == self.config.tui_theme,
+                    is_default: name == DEFAULT_TUI_THEME,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let current = self.config.tui_theme.clone();
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Select Theme".to_string()),
+            subtitle: Some("Colors change as you move; Esc keeps the current theme.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            on_selection_changed: Some(Box::new(move |idx, tx| {
+                if let Some(name) = names.get(idx) {
+                    tx.send(AppEvent::PreviewTheme(name.clone()));
+                }
+            })),
+            on_cancel: Some(Box::new(move |tx| {
+                tx.send(AppEvent::PreviewTheme(current.clone()));
+            })),
+            ..Default::default()
+        });
+    }
+
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
@@ -3105,6 +4100,10 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn set_tui_theme(&mut self, name: String) {
+        self.config.tui_theme = name;
+    }
+
     pub(crate) fn set_full_access_warning_acknowledged(&mut self, acknowledged: bool) {
         self.config.notices.hide_full_access_warning = Some(acknowledged);
     }
@@ -3144,6 +4143,14 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// With `tui.accessible`, adds `message` to the transcript as a plain
+    /// line, where a screen reader reads it out.
+    fn announce(&mut self, message: &str) {
+        if self.config.tui_accessible {
+            self.add_plain_history_lines(vec![Line::from(message.to_string())]);
+        }
+    }
+
     pub(crate) fn add_plain_history_lines(&mut self, lines: Vec<Line<'static>>) {
         self.add_boxed_history(Box::new(PlainHistoryCell::new(lines)));
         self.request_redraw();
@@ -3186,6 +4193,10 @@ impl ChatWidget {
         self.bottom_pane.composer_is_empty()
     }
 
+    pub(crate) fn composer_text(&self) -> String {
+        self.bottom_pane.composer_text()
+    }
+
     /// True when the UI is in the regular composer state with no running task,
     /// no modal overlay (e.g. approvals or status indicator), and no composer popups.
     /// In this state Esc-Esc backtracking is enabled.
@@ -3230,9 +4241,11 @@ impl ChatWidget {
 
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
-        debug!("received {len} custom prompts");
+        let commands = ev.custom_commands.len();
+        debug!("received {len} custom prompts and {commands} custom commands");
         // Forward to bottom pane so the slash popup can show them now.
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
+        self.bottom_pane.set_custom_commands(ev.custom_commands);
     }
 
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
@@ -3421,6 +4434,9 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.turn_start_usage = None;
+        self.last_turn_usage = None;
+        self.refresh_token_meter();
     }
 
     fn as_renderable(&self) -> RenderableItem<'_> {
@@ -3429,12 +4445,25 @@ impl ChatWidget {
             None => RenderableItem::Owned(Box::new(())),
         };
         let mut flex = FlexRenderable::new();
+        if let Some(header) = self.header_line() {
+            flex.push(0, RenderableItem::Owned(header.into()));
+        }
         flex.push(1, active_cell_renderable);
+        if let Some(reasoning) = self.live_reasoning() {
+            flex.push(
+                0,
+                RenderableItem::Owned(Box::new(reasoning)).inset(Insets::tlbr(1, 0, 0, 0)),
+            );
+        }
         flex.push(
             0,
             RenderableItem::Borrowed(&self.bottom_pane).inset(Insets::tlbr(1, 0, 0, 0)),
         );
-        RenderableItem::Owned(Box::new(flex))
+        let flex = RenderableItem::Owned(Box::new(flex));
+        match self.plan_pane.as_ref().filter(|_| self.plan_pane_visible) {
+            Some(pane) => RenderableItem::Owned(Box::new(WithPlanPane::new(flex, pane))),
+            None => flex,
+        }
     }
 }
 
@@ -3623,6 +4652,59 @@ pub(crate) fn show_review_commit_picker_with_entries(
     });
 }
 
+/// Parse the argument of `/allow-once`: `network`, `tool <feature>`, or
+/// `write <dir>`.
+fn parse_turn_capability_grant(args: &str) -> Option<TurnCapabilityGrant> {
+    let (kind, rest) = match args.trim().split_once(char::is_whitespace) {
+        Some((kind, rest)) => (kind, rest.trim()),
+        None => (args.trim(), ""),
+    };
+    match (kind, rest) {
+        ("network", "") => Some(TurnCapabilityGrant::Network),
+        ("tool", name) if !name.is_empty() => Some(TurnCapabilityGrant::Tool {
+            name: name.to_string(),
+        }),
+        ("write", path) if !path.is_empty() => Some(TurnCapabilityGrant::WritableRoot {
+            path: PathBuf::from(path),
+        }),
+        _ => None,
+    }
+}
+
+/// A parsed `/export [format] <path> [--redact]`.
+#[derive(Debug, PartialEq, Eq)]
+struct ExportRequest {
+    format: String,
+    path: PathBuf,
+    redact: bool,
+}
+
+/// Parse the arguments of `/export`. Without a format, the file extension
+/// picks one (`.html`, `.json`, else Markdown).
+fn parse_export_args(args: &str) -> Option<ExportRequest> {
+    let mut words = shlex::split(args)?;
+    let redact = words.iter().any(|word| word == "--redact");
+    words.retain(|word| word != "--redact");
+    let (format, path) = match words.as_slice() {
+        [path] => {
+            let path = PathBuf::from(path);
+            let format = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("html" | "htm") => "html",
+                Some("json") => "json",
+                _ => "markdown",
+            };
+            (format.to_string(), path)
+        }
+        [format, path] => (format.clone(), PathBuf::from(path)),
+        _ => return None,
+    };
+    Some(ExportRequest {
+        format,
+        path,
+        redact,
+    })
+}
+
 fn skills_for_cwd(cwd: &Path, skills_entries: &[SkillsListEntry]) -> Vec<SkillMetadata> {
     skills_entries
         .iter()
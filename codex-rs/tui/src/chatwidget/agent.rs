@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use codex_core::CodexConversation;
@@ -7,11 +8,14 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::WarningEvent;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::session_share;
+use crate::session_share::SessionShare;
 
 /// Spawn the agent bootstrapper and op forwarding loop, returning the
 /// `UnboundedSender<Op>` used by the UI to submit operations.
@@ -22,10 +26,11 @@ pub(crate) fn spawn_agent(
 ) -> UnboundedSender<Op> {
     let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
 
+    let attach_home = config.tui_allow_attach.then(|| config.codex_home.clone());
     let app_event_tx_clone = app_event_tx;
     tokio::spawn(async move {
         let NewConversation {
-            conversation_id: _,
+            conversation_id,
             conversation,
             session_configured,
         } = match server.new_conversation(config).await {
@@ -44,12 +49,16 @@ pub(crate) fn spawn_agent(
             }
         };
 
+        let share =
+            attach_home.and_then(|home| SessionShare::start(&home, &conversation_id.to_string()));
+
         // Forward the captured `SessionConfigured` event so it can be rendered in the UI.
         let ev = codex_core::protocol::Event {
             // The `id` does not matter for rendering, so we can use a fake value.
             id: "".to_string(),
             msg: codex_core::protocol::EventMsg::SessionConfigured(session_configured),
         };
+        publish(share.as_ref(), &ev);
         app_event_tx_clone.send(AppEvent::CodexEvent(ev));
 
         let conversation_clone = conversation.clone();
@@ -63,6 +72,7 @@ pub(crate) fn spawn_agent(
         });
 
         while let Ok(event) = conversation.next_event().await {
+            publish(share.as_ref(), &event);
             app_event_tx_clone.send(AppEvent::CodexEvent(event));
         }
     });
@@ -72,21 +82,27 @@ pub(crate) fn spawn_agent(
 
 /// Spawn agent loops for an existing conversation (e.g., a forked conversation).
 /// Sends the provided `SessionConfiguredEvent` immediately, then forwards subsequent
-/// events and accepts Ops for submission.
+/// events and accepts Ops for submission. With `attach_home`, the session is
+/// also served to `codex attach` viewers.
 pub(crate) fn spawn_agent_from_existing(
     conversation: std::sync::Arc<CodexConversation>,
     session_configured: codex_core::protocol::SessionConfiguredEvent,
     app_event_tx: AppEventSender,
+    attach_home: Option<PathBuf>,
 ) -> UnboundedSender<Op> {
     let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
 
     let app_event_tx_clone = app_event_tx;
     tokio::spawn(async move {
+        let session_id = session_configured.session_id.to_string();
+        let share = attach_home.and_then(|home| SessionShare::start(&home, &session_id));
+
         // Forward the captured `SessionConfigured` event so it can be rendered in the UI.
         let ev = codex_core::protocol::Event {
             id: "".to_string(),
             msg: codex_core::protocol::EventMsg::SessionConfigured(session_configured),
         };
+        publish(share.as_ref(), &ev);
         app_event_tx_clone.send(AppEvent::CodexEvent(ev));
 
         let conversation_clone = conversation.clone();
@@ -100,9 +116,40 @@ pub(crate) fn spawn_agent_from_existing(
         });
 
         while let Ok(event) = conversation.next_event().await {
+            publish(share.as_ref(), &event);
             app_event_tx_clone.send(AppEvent::CodexEvent(event));
         }
     });
 
     codex_op_tx
 }
+
+/// Spawn a read-only view of a session running in another TUI
+/// (`codex attach`). Events come from the session's attach socket; ops are
+/// dropped because a viewer cannot act on the session.
+pub(crate) fn spawn_attached_agent(
+    socket_path: PathBuf,
+    app_event_tx: AppEventSender,
+) -> UnboundedSender<Op> {
+    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+
+    tokio::spawn(async move { while codex_op_rx.recv().await.is_some() {} });
+    tokio::spawn(async move {
+        let message = match session_share::watch(&socket_path, &app_event_tx).await {
+            Ok(()) => "The attached session ended.".to_string(),
+            Err(err) => format!("Lost the attached session: {err}"),
+        };
+        app_event_tx.send(AppEvent::CodexEvent(Event {
+            id: "".to_string(),
+            msg: EventMsg::Warning(WarningEvent { message }),
+        }));
+    });
+
+    codex_op_tx
+}
+
+fn publish(share: Option<&SessionShare>, event: &Event) {
+    if let Some(share) = share {
+        share.publish(event);
+    }
+}
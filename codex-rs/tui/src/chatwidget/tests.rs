@@ -12,6 +12,8 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::ConfigToml;
 use codex_core::config::Constrained;
 use codex_core::config::ConstraintError;
+use codex_core::config::types::ModelPrice;
+use codex_core::config::types::ThemeToml;
 use codex_core::openai_models::models_manager::ModelsManager;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -37,6 +39,7 @@ use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionTitleEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
@@ -207,6 +210,7 @@ fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_review_tokens, context_window)),
             rate_limits: None,
+            prompt_breakdown: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -226,6 +230,7 @@ fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(review_tokens, context_window)),
             rate_limits: None,
+            prompt_breakdown: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(97));
@@ -255,6 +260,7 @@ fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_compact_tokens, context_window)),
             rate_limits: None,
+            prompt_breakdown: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -264,6 +270,7 @@ fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: None,
             rate_limits: None,
+            prompt_breakdown: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), None);
@@ -294,6 +301,7 @@ fn context_indicator_shows_used_tokens_when_window_unknown() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(token_info),
             rate_limits: None,
+            prompt_breakdown: None,
         }),
     });
 
@@ -379,6 +387,16 @@ fn make_chatwidget_manual(
         session_header: SessionHeader::new(resolved_model.clone()),
         initial_user_message: None,
         token_info: None,
+        turn_start_usage: None,
+        turn_started_at: None,
+        last_citations: Vec::new(),
+        last_reply: None,
+        last_command: None,
+        last_diff: None,
+        turn_diff: None,
+        session_diffs: Vec::new(),
+        last_turn_usage: None,
+        prompt_breakdown: None,
         rate_limit_snapshot: None,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
@@ -394,6 +412,7 @@ fn make_chatwidget_manual(
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
         full_reasoning_buffer: String::new(),
+        reasoning_expanded: false,
         current_status_header: String::from("Working"),
         retry_status_header: None,
         conversation_id: None,
@@ -408,6 +427,12 @@ fn make_chatwidget_manual(
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        plan_pane: None,
+        plan_pane_visible: true,
+        git_status: None,
+        session_title: None,
+        plan_mode: false,
+        read_only: false,
     };
     (widget, rx, op_rx)
 }
@@ -1276,6 +1301,118 @@ fn review_popup_custom_prompt_action_sends_event() {
     assert!(found, "expected OpenReviewCustomPrompt event to be sent");
 }
 
+#[test]
+fn slash_allow_once_submits_turn_capability_grant() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command_with_args(SlashCommand::AllowOnce, "write  target/out ".to_string());
+
+    match op_rx.try_recv() {
+        Ok(Op::GrantTurnCapability { grant }) => assert_eq!(
+            grant,
+            TurnCapabilityGrant::WritableRoot {
+                path: PathBuf::from("target/out"),
+            }
+        ),
+        other => panic!("expected GrantTurnCapability op, got {other:?}"),
+    }
+}
+
+#[test]
+fn slash_allow_once_without_args_shows_usage() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command(SlashCommand::AllowOnce);
+
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("usage message"));
+    assert!(rendered.contains("Usage: /allow-once"), "{rendered:?}");
+}
+
+#[test]
+fn slash_plan_toggles_plan_mode() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command(SlashCommand::Plan);
+    assert_matches!(op_rx.try_recv(), Ok(Op::SetPlanMode { enabled: true }));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("plan mode message"));
+    assert!(rendered.contains("Plan mode on"), "{rendered:?}");
+
+    chat.dispatch_command(SlashCommand::Plan);
+    assert_matches!(op_rx.try_recv(), Ok(Op::SetPlanMode { enabled: false }));
+}
+
+#[test]
+fn slash_plan_with_task_enables_plan_mode_and_submits_it() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command_with_args(SlashCommand::Plan, " migrate to tokio 2 ".to_string());
+    let ops: Vec<Op> = std::iter::from_fn(|| op_rx.try_recv().ok()).collect();
+    assert_matches!(ops.first(), Some(Op::SetPlanMode { enabled: true }));
+    assert!(ops.iter().any(|op| matches!(
+        op,
+        Op::UserInput { items } if items == &vec![UserInput::Text {
+            text: "migrate to tokio 2".to_string(),
+        }]
+    )));
+
+    // Already in plan mode: the next task is sent without toggling.
+    chat.dispatch_command_with_args(SlashCommand::Plan, "and the tests".to_string());
+    let ops: Vec<Op> = std::iter::from_fn(|| op_rx.try_recv().ok()).collect();
+    assert!(!ops.iter().any(|op| matches!(op, Op::SetPlanMode { .. })));
+    assert!(ops.iter().any(|op| matches!(op, Op::UserInput { .. })));
+}
+
+#[test]
+fn slash_title_renames_the_session() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command_with_args(SlashCommand::Title, "  Fix flaky login test ".to_string());
+    match op_rx.try_recv() {
+        Ok(Op::SetSessionTitle { title }) => assert_eq!(title, "Fix flaky login test"),
+        other => panic!("expected SetSessionTitle op, got {other:?}"),
+    }
+
+    chat.dispatch_command(SlashCommand::Title);
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("title message"));
+    assert!(rendered.contains("no title yet"), "{rendered:?}");
+}
+
+#[test]
+fn slash_mcp_add_attaches_a_session_only_server() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None);
+
+    chat.dispatch_command_with_args(
+        SlashCommand::Mcp,
+        "add docs npx -y '@acme/docs mcp'".to_string(),
+    );
+
+    match op_rx.try_recv() {
+        Ok(Op::AddMcpServer { name, command }) => {
+            assert_eq!(name, "docs");
+            assert_eq!(command, vec!["npx", "-y", "@acme/docs mcp"]);
+        }
+        other => panic!("expected AddMcpServer op, got {other:?}"),
+    }
+    assert!(chat.config.mcp_servers.contains_key("docs"));
+
+    chat.dispatch_command_with_args(SlashCommand::Mcp, "add docs other-server".to_string());
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("error message"));
+    assert!(rendered.contains("already configured"), "{rendered:?}");
+
+    chat.dispatch_command_with_args(SlashCommand::Mcp, "remove docs".to_string());
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("usage message"));
+    assert!(rendered.contains("Usage: /mcp add"), "{rendered:?}");
+}
+
 #[test]
 fn slash_init_skips_when_project_doc_exists() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None);
@@ -1656,6 +1793,43 @@ fn interrupted_turn_error_message_snapshot() {
     assert_snapshot!("interrupted_turn_error_message", last);
 }
 
+#[test]
+fn interrupted_stream_keeps_the_partial_reply() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "The flake comes from the session".into(),
+        }),
+    });
+
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TurnAborted(codex_core::protocol::TurnAbortedEvent {
+            reason: TurnAbortReason::Interrupted,
+        }),
+    });
+
+    let combined = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    let reply = combined
+        .find("The flake comes from the session")
+        .expect("partial reply kept");
+    let marker = combined.find("(reply interrupted)").expect("marker");
+    let prompt = combined
+        .find("Conversation interrupted")
+        .expect("interrupt message");
+    assert!(reply < marker && marker < prompt, "{combined:?}");
+}
+
 /// Opening custom prompt from the review popup, pressing Esc returns to the
 /// parent popup, pressing Esc again dismisses all panels (back to normal mode).
 #[test]
@@ -1845,6 +2019,105 @@ fn experimental_features_toggle_saves_on_exit() {
     assert_eq!(updates, vec![(expected_feature, true)]);
 }
 
+#[test]
+fn theme_popup_previews_and_saves_the_selected_theme() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    chat.config
+        .tui_themes
+        .insert("solarized".to_string(), ThemeToml::default());
+    chat.open_theme_popup();
+
+    // The list starts on the current theme, `auto`, and wraps to the last.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::PreviewTheme(name) => events.push(format!("preview {name}")),
+            AppEvent::PersistTheme(name) => events.push(format!("persist {name}")),
+            _ => {}
+        }
+    }
+    assert_eq!(events, vec!["preview solarized", "persist solarized"]);
+}
+
+#[test]
+fn tab_command_opens_closes_and_lists_tabs() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    let cwd = chat.config.cwd.clone();
+    chat.dispatch_command_with_args(SlashCommand::Tab, "new  sub/dir ".to_string());
+    chat.dispatch_command_with_args(SlashCommand::Tab, "new".to_string());
+    chat.dispatch_command_with_args(SlashCommand::Tab, "close".to_string());
+    chat.dispatch_command_with_args(SlashCommand::Tab, String::new());
+
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::OpenTab { cwd } => events.push(format!("open {cwd:?}")),
+            AppEvent::CloseTab => events.push("close".to_string()),
+            AppEvent::OpenTabPicker => events.push("picker".to_string()),
+            _ => {}
+        }
+    }
+    assert_eq!(
+        events,
+        vec![
+            format!("open {:?}", Some(cwd.join("sub/dir"))),
+            "open None".to_string(),
+            "close".to_string(),
+            "picker".to_string(),
+        ]
+    );
+
+    chat.open_tab_picker(vec![
+        (0, "1 repo".to_string(), true),
+        (2, "2 docs".to_string(), false),
+    ]);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let switched = std::iter::from_fn(|| rx.try_recv().ok())
+        .any(|event| matches!(event, AppEvent::SwitchTab(2)));
+    assert!(switched, "expected a switch to tab 2");
+}
+
+#[test]
+fn cost_command_breaks_down_session_and_turn_usage() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    let model = chat.model_family.get_model_slug().to_string();
+    chat.config.model_prices.insert(
+        model.clone(),
+        ModelPrice {
+            input: 1.25,
+            cached_input: Some(0.125),
+            output: 10.0,
+        },
+    );
+    let usage = TokenUsage {
+        input_tokens: 1_000_000,
+        cached_input_tokens: 400_000,
+        output_tokens: 100_000,
+        reasoning_output_tokens: 50_000,
+        total_tokens: 1_100_000,
+    };
+    chat.on_task_started();
+    chat.set_token_info(Some(TokenUsageInfo {
+        total_token_usage: usage.clone(),
+        last_token_usage: usage,
+        model_context_window: None,
+    }));
+    chat.on_task_complete(None);
+    drain_insert_history(&mut rx);
+
+    chat.dispatch_command(SlashCommand::Cost);
+    let output = lines_to_single_string(&drain_insert_history(&mut rx).concat());
+    assert!(output.contains(&format!("Session · {model}")), "{output}");
+    assert!(
+        output.contains("  Input   1M (400K cached)  $0.80\n  Output  100K (50K reasoning)  $1.00\n  Total   700K  $1.80\n\nLast turn\n"),
+        "{output}"
+    );
+}
+
 #[test]
 fn model_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5-codex"));
@@ -3110,6 +3383,156 @@ fn plan_update_renders_history_cell() {
     assert!(blob.contains("Write tests"));
 }
 
+#[test]
+fn copy_targets_track_the_last_reply_command_and_diff() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None);
+    assert_eq!(chat.copy_text(CopyTarget::Reply), None);
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TurnDiff(TurnDiffEvent {
+            unified_diff: "--- a/x\n+++ b/x\n".into(),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: Some("Run:\n\n```sh\ncargo test -p codex-tui\n```\n".into()),
+        }),
+    });
+
+    assert_eq!(
+        chat.copy_text(CopyTarget::CodeBlock),
+        Some("cargo test -p codex-tui".to_string())
+    );
+    assert_eq!(
+        chat.copy_text(CopyTarget::Diff),
+        Some("--- a/x\n+++ b/x\n".to_string())
+    );
+    assert!(
+        chat.copy_text(CopyTarget::Reply)
+            .is_some_and(|reply| reply.starts_with("Run:"))
+    );
+    assert_eq!(chat.copy_text(CopyTarget::Command), None);
+}
+
+#[test]
+fn turn_with_file_changes_ends_with_a_changes_summary() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n-old\n+new\n+more\n";
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TurnDiff(TurnDiffEvent {
+            unified_diff: diff.into(),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    let summary = lines_to_single_string(cells.last().expect("changes summary"));
+    assert!(
+        summary.contains("Changes this turn 1 file (+2 -1)"),
+        "{summary:?}"
+    );
+    assert!(summary.contains("src/lib.rs (+2 -1)"), "{summary:?}");
+    assert_eq!(chat.session_diffs, vec![diff.to_string()]);
+
+    // A turn without edits adds nothing.
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    });
+    assert!(
+        drain_insert_history(&mut rx)
+            .iter()
+            .all(|cell| !lines_to_single_string(cell).contains("Changes this turn"))
+    );
+    assert_eq!(chat.session_diffs.len(), 1);
+}
+
+#[test]
+fn plan_pane_shows_latest_plan_and_toggles_with_ctrl_l() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None);
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::PlanUpdate(UpdatePlanArgs {
+            explanation: None,
+            plan: vec![PlanItemArg {
+                step: "Write tests".into(),
+                status: StepStatus::InProgress,
+            }],
+        }),
+    });
+
+    let rendered = |chat: &ChatWidget| {
+        let area = Rect::new(0, 0, 120, chat.desired_height(120));
+        let mut buf = ratatui::buffer::Buffer::empty(area);
+        chat.render(area, &mut buf);
+        format!("{buf:?}")
+    };
+    assert!(rendered(&chat).contains("Plan 0/1"));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+    assert!(!rendered(&chat).contains("Plan 0/1"));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+    assert!(rendered(&chat).contains("Plan 0/1"));
+}
+
+#[test]
+fn live_reasoning_streams_collapsed_and_expands_with_alt_t() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None);
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::AgentReasoningDelta(AgentReasoningDeltaEvent {
+            delta: "**Scanning**\n\nLooking at the parser".into(),
+        }),
+    });
+
+    let rendered = |chat: &ChatWidget| {
+        let area = Rect::new(0, 0, 80, chat.desired_height(80));
+        let mut buf = ratatui::buffer::Buffer::empty(area);
+        chat.render(area, &mut buf);
+        format!("{buf:?}")
+    };
+    let collapsed = rendered(&chat);
+    assert!(collapsed.contains("(alt+t to expand) Looking at the parser"));
+    assert!(!collapsed.contains("Scanning"));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::ALT));
+    let expanded = rendered(&chat);
+    assert!(expanded.contains("Scanning"));
+    assert!(expanded.contains("Looking at the parser"));
+    assert!(!expanded.contains("alt+t to expand"));
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::AgentReasoning(AgentReasoningEvent {
+            text: String::new(),
+        }),
+    });
+    assert!(!rendered(&chat).contains("Looking at the parser"));
+}
+
 #[test]
 fn stream_error_updates_status_indicator() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
@@ -3539,3 +3962,196 @@ fn chatwidget_tall() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[test]
+fn turn_complete_notification_skips_turns_shorter_than_the_minimum() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None);
+    let run_turn = |chat: &mut ChatWidget| {
+        chat.handle_codex_event(Event {
+            id: "t1".into(),
+            msg: EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+            }),
+        });
+        chat.handle_codex_event(Event {
+            id: "t1".into(),
+            msg: EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: Some("Done".into()),
+            }),
+        });
+    };
+
+    chat.config.tui_turn_notification_min_seconds = 60;
+    run_turn(&mut chat);
+    assert!(chat.pending_notification.is_none());
+
+    chat.config.tui_turn_notification_min_seconds = 0;
+    run_turn(&mut chat);
+    assert_matches!(
+        chat.pending_notification,
+        Some(Notification::AgentTurnComplete { ref response }) if response == "Done"
+    );
+}
+
+#[test]
+fn file_references_in_the_last_reply_can_be_shown_and_opened() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    let dir = tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").expect("write");
+    chat.config.cwd = dir.path().to_path_buf();
+
+    chat.handle_codex_event(Event {
+        id: "t1".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: Some("See `lib.rs:2` (missing.rs:3 is gone).".into()),
+        }),
+    });
+    while rx.try_recv().is_ok() {}
+
+    // Only one reference resolves, so Ctrl+G opens it without a popup.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::OpenInEditor { ref path, line: 2 }) if path == &dir.path().join("lib.rs")
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(&cells.concat());
+    assert_eq!(rendered, "• lib.rs:2\n  2 │ fn b() {}\n");
+}
+
+#[test]
+fn export_args_take_an_optional_format_and_redact_flag() {
+    assert_eq!(
+        parse_export_args("notes/session.html"),
+        Some(ExportRequest {
+            format: "html".to_string(),
+            path: PathBuf::from("notes/session.html"),
+            redact: false,
+        })
+    );
+    assert_eq!(
+        parse_export_args("json --redact 'my session.txt'"),
+        Some(ExportRequest {
+            format: "json".to_string(),
+            path: PathBuf::from("my session.txt"),
+            redact: true,
+        })
+    );
+    assert_eq!(
+        parse_export_args("out"),
+        Some(ExportRequest {
+            format: "markdown".to_string(),
+            path: PathBuf::from("out"),
+            redact: false,
+        })
+    );
+    assert_eq!(parse_export_args(""), None);
+    assert_eq!(parse_export_args("a b c"), None);
+}
+
+#[test]
+fn accessible_mode_announces_state_changes() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    chat.config.tui_accessible = true;
+
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+            call_id: "call-1".into(),
+            turn_id: "turn-1".into(),
+            command: vec!["cargo".into(), "test".into()],
+            cwd: PathBuf::from("/tmp"),
+            reason: None,
+            proposed_execpolicy_amendment: None,
+            parsed_cmd: vec![],
+        }),
+    });
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    });
+
+    let rendered = lines_to_single_string(&drain_insert_history(&mut rx).concat());
+    let announcements: Vec<&str> = rendered
+        .lines()
+        .filter(|line| line.starts_with("Codex is") || line.starts_with("Approval needed"))
+        .collect();
+    assert_eq!(
+        announcements,
+        vec![
+            "Codex is working.",
+            "Approval needed to run cargo test.",
+            "Codex is done.",
+        ]
+    );
+}
+
+#[test]
+fn git_line_shows_above_the_conversation_and_refreshes_after_edits() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None);
+    chat.set_git_status(Some(GitStatusSummary {
+        branch: Some("main".to_string()),
+        changed: 2,
+        ahead_behind: Some((1, 0)),
+    }));
+
+    let area = Rect::new(0, 0, 80, chat.desired_height(80));
+    let mut buf = ratatui::buffer::Buffer::empty(area);
+    chat.render(area, &mut buf);
+    let first_row: String = (0..area.width)
+        .map(|x| buf[(x, 0)].symbol().to_string())
+        .collect();
+    assert_eq!(first_row.trim_end(), "⎇ main · 2 changed · ↑1");
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: "call-1".into(),
+            turn_id: "turn-1".into(),
+            stdout: String::new(),
+            stderr: String::new(),
+            success: true,
+            changes: HashMap::new(),
+        }),
+    });
+    assert!(
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .any(|event| matches!(event, AppEvent::RefreshGitStatus))
+    );
+}
+
+#[test]
+fn session_title_leads_the_header_line() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None);
+    chat.set_git_status(Some(GitStatusSummary {
+        branch: Some("main".to_string()),
+        changed: 0,
+        ahead_behind: None,
+    }));
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SessionTitle(SessionTitleEvent {
+            title: "Fix flaky login test".to_string(),
+        }),
+    });
+
+    let area = Rect::new(0, 0, 80, chat.desired_height(80));
+    let mut buf = ratatui::buffer::Buffer::empty(area);
+    chat.render(area, &mut buf);
+    let first_row: String = (0..area.width)
+        .map(|x| buf[(x, 0)].symbol().to_string())
+        .collect();
+    assert_eq!(first_row.trim_end(), "Fix flaky login test  ⎇ main · clean");
+}
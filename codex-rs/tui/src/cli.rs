@@ -32,6 +32,11 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_show_all: bool,
 
+    /// Internal: watch a running session read-only. Set by the top-level
+    /// `codex attach <SESSION_ID> --read-only` wrapper.
+    #[clap(skip)]
+    pub attach_session_id: Option<String>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -47,7 +52,8 @@ pub struct Cli {
     pub oss_provider: Option<String>,
 
     /// Configuration profile from config.toml to specify default options.
-    #[arg(long = "profile", short = 'p')]
+    /// `--preset` is an alias. Explicit flags still win over the profile.
+    #[arg(long = "profile", short = 'p', visible_alias = "preset")]
     pub config_profile: Option<String>,
 
     /// Select the sandbox policy to use when executing model-generated shell
@@ -85,6 +91,12 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Attach an MCP server for this session only, without saving it to
+    /// config.toml. COMMAND is a shell-quoted command line or an `http(s)://`
+    /// URL. May be repeated.
+    #[arg(long = "mcp", value_name = "NAME=COMMAND")]
+    pub mcp_servers: Vec<String>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -0,0 +1,188 @@
+//! Copying text out of the TUI. Selecting transcript text with the mouse
+//! picks up wrapping, prefixes, and padding, so the copy commands put the
+//! original text on the clipboard instead: through the system clipboard when
+//! there is one, or else with an OSC 52 escape that asks the terminal to set
+//! it (which also reaches the local clipboard over SSH).
+
+use std::io::Write;
+
+use base64::Engine;
+use pulldown_cmark::Event;
+use pulldown_cmark::Parser;
+use pulldown_cmark::Tag;
+use pulldown_cmark::TagEnd;
+
+/// What the copy keys and `/copy` put on the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CopyTarget {
+    /// The last code block of the last reply.
+    CodeBlock,
+    /// The last shell command Codex ran.
+    Command,
+    /// The changes made during the last turn, as a unified diff.
+    Diff,
+    /// The whole last reply, as Markdown.
+    Reply,
+}
+
+impl CopyTarget {
+    pub(crate) const ALL: [CopyTarget; 4] = [
+        CopyTarget::CodeBlock,
+        CopyTarget::Command,
+        CopyTarget::Diff,
+        CopyTarget::Reply,
+    ];
+
+    /// The `/copy` argument naming this target.
+    pub(crate) fn arg(self) -> &'static str {
+        match self {
+            CopyTarget::CodeBlock => "code",
+            CopyTarget::Command => "command",
+            CopyTarget::Diff => "diff",
+            CopyTarget::Reply => "reply",
+        }
+    }
+
+    pub(crate) fn from_arg(arg: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|target| target.arg() == arg)
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            CopyTarget::CodeBlock => "last code block",
+            CopyTarget::Command => "last command",
+            CopyTarget::Diff => "last diff",
+            CopyTarget::Reply => "last reply",
+        }
+    }
+
+    /// The letter that copies this target with Alt.
+    pub(crate) fn key(self) -> char {
+        match self {
+            CopyTarget::CodeBlock => 'c',
+            CopyTarget::Command => 'x',
+            CopyTarget::Diff => 'd',
+            CopyTarget::Reply => 'r',
+        }
+    }
+
+    pub(crate) fn for_key(c: char) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|target| target.key().eq_ignore_ascii_case(&c))
+    }
+}
+
+/// Where copied text went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CopyDestination {
+    /// The system clipboard.
+    Clipboard,
+    /// An OSC 52 request to the terminal, which may ignore it.
+    Terminal,
+}
+
+impl CopyDestination {
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            CopyDestination::Clipboard => "clipboard",
+            CopyDestination::Terminal => "terminal clipboard (OSC 52)",
+        }
+    }
+}
+
+pub(crate) fn copy_to_clipboard(text: &str) -> std::io::Result<CopyDestination> {
+    // Over SSH the system clipboard is the remote machine's.
+    if std::env::var_os("SSH_CONNECTION").is_none() {
+        match system_clipboard::set_text(text) {
+            Ok(()) => return Ok(CopyDestination::Clipboard),
+            Err(err) => tracing::debug!("system clipboard unavailable, using OSC 52: {err}"),
+        }
+    }
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+    stdout.flush()?;
+    Ok(CopyDestination::Terminal)
+}
+
+/// The escape that sets the clipboard to `text`, wrapped for tmux to pass
+/// through when `tmux` is set.
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    if tmux {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{encoded}\x07\x1b\\")
+    } else {
+        format!("\x1b]52;c;{encoded}\x07")
+    }
+}
+
+/// The contents of the last code block in a Markdown reply.
+pub(crate) fn last_code_block(markdown: &str) -> Option<String> {
+    let mut last = None;
+    let mut current: Option<String> = None;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => current = Some(String::new()),
+            Event::Text(text) => {
+                if let Some(block) = current.as_mut() {
+                    block.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => last = current.take(),
+            _ => {}
+        }
+    }
+    last.map(|block| block.trim_end_matches('\n').to_string())
+}
+
+#[cfg(not(target_os = "android"))]
+mod system_clipboard {
+    use std::sync::Mutex;
+
+    /// Kept open because on X11 and Wayland the copied text is served by
+    /// whoever owns the clipboard; dropping it would take the text along.
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+    pub(super) fn set_text(text: &str) -> Result<(), String> {
+        let mut clipboard = CLIPBOARD.lock().map_err(|err| err.to_string())?;
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(|err| err.to_string())?);
+        }
+        match clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text).map_err(|err| err.to_string()),
+            None => Err("clipboard unavailable".to_string()),
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+mod system_clipboard {
+    pub(super) fn set_text(_text: &str) -> Result<(), String> {
+        Err("clipboard is unsupported on Android".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_the_last_code_block() {
+        let reply = "Run this:\n\n```sh\ncargo test\n```\n\nthen:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n";
+        assert_eq!(
+            last_code_block(reply),
+            Some("fn main() {\n    println!(\"hi\");\n}".to_string())
+        );
+        assert_eq!(last_code_block("no code here"), None);
+    }
+
+    #[test]
+    fn osc52_encodes_and_wraps_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! The composer text that has not been sent yet, saved as it changes so it
+//! survives the terminal closing, and put back the next time Codex starts in
+//! the same directory.
+//!
+//! Drafts live in `composer_drafts.json` next to the message history, one
+//! per working directory. Nothing is saved when `history.persistence` is
+//! `none`.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::xdg::codex_state_home;
+
+const DRAFTS_FILENAME: &str = "composer_drafts.json";
+
+pub(crate) struct ComposerDraft {
+    path: PathBuf,
+    /// The directory and text last written, to skip unchanged saves.
+    saved: Option<(String, String)>,
+}
+
+impl ComposerDraft {
+    pub(crate) fn new(codex_home: &Path) -> Self {
+        Self {
+            path: codex_state_home(codex_home).join(DRAFTS_FILENAME),
+            saved: None,
+        }
+    }
+
+    /// Removes and returns the draft saved for `cwd`.
+    pub(crate) fn take(&mut self, cwd: &Path) -> Option<String> {
+        let mut drafts = read_drafts(&self.path);
+        let draft = drafts.remove(&draft_key(cwd))?;
+        if let Err(err) = write_drafts(&self.path, &drafts) {
+            tracing::warn!("failed to update {}: {err}", self.path.display());
+        }
+        Some(draft)
+    }
+
+    /// Saves `text` as the draft for `cwd` unless it is what was saved last.
+    /// Blank text removes the draft.
+    pub(crate) fn save(&mut self, cwd: &Path, text: &str) {
+        let key = draft_key(cwd);
+        match &self.saved {
+            Some((saved_key, saved_text)) if *saved_key == key && saved_text == text => return,
+            None if text.trim().is_empty() => return,
+            _ => {}
+        }
+        let mut drafts = read_drafts(&self.path);
+        if text.trim().is_empty() {
+            drafts.remove(&key);
+        } else {
+            drafts.insert(key.clone(), text.to_string());
+        }
+        match write_drafts(&self.path, &drafts) {
+            Ok(()) => self.saved = Some((key, text.to_string())),
+            Err(err) => tracing::warn!("failed to save draft to {}: {err}", self.path.display()),
+        }
+    }
+}
+
+fn draft_key(cwd: &Path) -> String {
+    cwd.display().to_string()
+}
+
+fn read_drafts(path: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write_drafts(path: &Path, drafts: &BTreeMap<String, String>) -> io::Result<()> {
+    if drafts.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(drafts).map_err(io::Error::other)?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    io::Write::write_all(&mut options.open(path)?, contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn drafts_are_kept_per_directory_until_taken() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let app = Path::new("/work/app");
+        let lib = Path::new("/work/lib");
+
+        let mut draft = ComposerDraft::new(home.path());
+        draft.save(app, "refactor the parser");
+        draft.save(lib, "bump the version");
+
+        let mut next = ComposerDraft::new(home.path());
+        assert_eq!(next.take(app), Some("refactor the parser".to_string()));
+        assert_eq!(next.take(app), None);
+
+        // Sending (or clearing) the draft removes it; with no drafts left
+        // the file goes away.
+        next.save(lib, "");
+        next.save(lib, "bump it");
+        next.save(lib, "  ");
+        assert_eq!(ComposerDraft::new(home.path()).take(lib), None);
+        assert!(!home.path().join(DRAFTS_FILENAME).exists());
+    }
+}
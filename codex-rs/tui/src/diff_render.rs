@@ -1,7 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -13,13 +12,16 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::exec_command::relativize_to_home;
+use crate::key_hint;
 use crate::render::Insets;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
+use crossterm::event::KeyCode;
 
 // Internal representation for diff line rendering
 enum DiffLineType {
@@ -85,6 +87,76 @@ pub(crate) fn create_diff_summary(
     render_changes_block(rows, wrap_cols, cwd)
 }
 
+/// One file of a multi-file unified diff with its line counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffStat {
+    pub(crate) path: String,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+}
+
+/// Per-file line counts of a `diff --git` style diff, such as the one core
+/// sends at the end of each turn.
+pub(crate) fn diff_stat(unified_diff: &str) -> Vec<DiffStat> {
+    let mut stats: Vec<DiffStat> = Vec::new();
+    let mut in_hunk = false;
+    for line in unified_diff.lines() {
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            let path = paths
+                .rsplit_once(" b/")
+                .map_or(paths, |(_, right)| right)
+                .to_string();
+            stats.push(DiffStat {
+                path,
+                added: 0,
+                removed: 0,
+            });
+            in_hunk = false;
+            continue;
+        }
+        if line.starts_with("@@") {
+            in_hunk = true;
+            continue;
+        }
+        let Some(stat) = stats.last_mut().filter(|_| in_hunk) else {
+            continue;
+        };
+        if line.starts_with('+') {
+            stat.added += 1;
+        } else if line.starts_with('-') {
+            stat.removed += 1;
+        }
+    }
+    stats
+}
+
+/// The "changes this turn" summary: totals with a hint for the session diff,
+/// then one line per file.
+pub(crate) fn create_turn_changes_summary(stats: &[DiffStat]) -> Vec<RtLine<'static>> {
+    let total_added: usize = stats.iter().map(|s| s.added).sum();
+    let total_removed: usize = stats.iter().map(|s| s.removed).sum();
+    let noun = if stats.len() == 1 { "file" } else { "files" };
+    let mut header: Vec<RtSpan<'static>> = vec![
+        "• ".dim(),
+        "Changes this turn".bold(),
+        format!(" {} {noun} ", stats.len()).into(),
+    ];
+    header.extend(render_line_count_summary(total_added, total_removed));
+    header.push(" · ".dim());
+    header.push(key_hint::alt(KeyCode::Char('g')).into());
+    header.push(" session diff".dim());
+
+    let mut out = vec![RtLine::from(header)];
+    for (idx, stat) in stats.iter().enumerate() {
+        let prefix = if idx == 0 { "  └ " } else { "    " };
+        let mut spans: Vec<RtSpan<'static>> = vec![prefix.dim(), stat.path.clone().into()];
+        spans.push(" ".into());
+        spans.extend(render_line_count_summary(stat.added, stat.removed));
+        out.push(RtLine::from(spans));
+    }
+    out
+}
+
 // Shared row for per-file presentation
 #[derive(Clone)]
 struct Row {
@@ -126,9 +198,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(RtSpan::styled(format!("+{added}"), style_add()));
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(RtSpan::styled(format!("-{removed}"), style_del()));
     spans.push(")".into());
     spans
 }
@@ -409,11 +481,11 @@ fn style_context() -> Style {
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    Style::default().fg(theme::current().diff_add)
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    Style::default().fg(theme::current().diff_del)
 }
 
 #[cfg(test)]
@@ -670,4 +742,56 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    #[test]
+    fn turn_changes_summary_counts_lines_per_file() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    old();
++    new();
++    more();
+ }
+diff --git a/README.md b/README.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1,2 @@
++# Title
++--- not a header
+";
+        let stats = diff_stat(diff);
+        assert_eq!(
+            stats,
+            vec![
+                DiffStat {
+                    path: "src/lib.rs".to_string(),
+                    added: 2,
+                    removed: 1,
+                },
+                DiffStat {
+                    path: "README.md".to_string(),
+                    added: 2,
+                    removed: 0,
+                },
+            ]
+        );
+
+        let text: Vec<String> = create_turn_changes_summary(&stats)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "• Changes this turn 2 files (+4 -1) · ⌥ + g session diff",
+                "  └ src/lib.rs (+2 -1)",
+                "    README.md (+2 -0)",
+            ]
+        );
+    }
 }
@@ -0,0 +1,852 @@
+//! Hunk-by-hunk review of a patch the agent proposes.
+//!
+//! Each added or deleted file is one hunk, and an updated file has one hunk
+//! per `@@` section of its diff. The user accepts or rejects hunks one at a
+//! time; [`DiffReview::decision`] turns the result into the answer for the
+//! approval request, rebuilding an `apply_patch` patch from the accepted
+//! hunks when only some of them were.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+use codex_core::protocol::ReviewDecision;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::text::Text;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthChar;
+
+use crate::diff_render::display_path_for;
+use crate::render::code_highlight::CodeLanguage;
+use crate::render::code_highlight::detect_language;
+use crate::render::code_highlight::highlight_code_to_lines;
+use crate::render::code_highlight::syntax_highlighting_enabled;
+use crate::render::renderable::Renderable;
+use crate::transcript_search::highlight_line;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HunkLine {
+    Context(String),
+    Delete(String),
+    Insert(String),
+}
+
+#[derive(Debug, Clone)]
+struct ReviewHunk {
+    old_start: usize,
+    new_start: usize,
+    lines: Vec<HunkLine>,
+    accepted: bool,
+}
+
+impl ReviewHunk {
+    fn old_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| !matches!(line, HunkLine::Insert(_)))
+            .count()
+    }
+
+    fn new_len(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| !matches!(line, HunkLine::Delete(_)))
+            .count()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FileKind {
+    Add,
+    Delete,
+    Update { move_path: Option<PathBuf> },
+}
+
+#[derive(Debug, Clone)]
+struct ReviewFile {
+    path: PathBuf,
+    kind: FileKind,
+    hunks: Vec<ReviewHunk>,
+}
+
+impl ReviewFile {
+    fn new(path: PathBuf, change: &FileChange) -> Self {
+        let whole_file = |content: &str, line: fn(String) -> HunkLine| ReviewHunk {
+            old_start: 1,
+            new_start: 1,
+            lines: content.lines().map(|l| line(l.to_string())).collect(),
+            accepted: true,
+        };
+        let (kind, hunks) = match change {
+            FileChange::Add { content } => {
+                (FileKind::Add, vec![whole_file(content, HunkLine::Insert)])
+            }
+            FileChange::Delete { content } => (
+                FileKind::Delete,
+                vec![whole_file(content, HunkLine::Delete)],
+            ),
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let mut hunks: Vec<ReviewHunk> = diffy::Patch::from_str(unified_diff)
+                    .map(|patch| {
+                        patch
+                            .hunks()
+                            .iter()
+                            .map(|hunk| ReviewHunk {
+                                old_start: hunk.old_range().start(),
+                                new_start: hunk.new_range().start(),
+                                lines: hunk
+                                    .lines()
+                                    .iter()
+                                    .map(|line| match line {
+                                        diffy::Line::Context(text) => HunkLine::Context(
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                        diffy::Line::Delete(text) => HunkLine::Delete(
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                        diffy::Line::Insert(text) => HunkLine::Insert(
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                    })
+                                    .collect(),
+                                accepted: true,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // A pure rename has no hunks; review it as one empty hunk.
+                if hunks.is_empty() {
+                    hunks.push(ReviewHunk {
+                        old_start: 1,
+                        new_start: 1,
+                        lines: Vec::new(),
+                        accepted: true,
+                    });
+                }
+                (
+                    FileKind::Update {
+                        move_path: move_path.clone(),
+                    },
+                    hunks,
+                )
+            }
+        };
+        Self { path, kind, hunks }
+    }
+
+    fn language(&self) -> Option<CodeLanguage> {
+        if !syntax_highlighting_enabled() {
+            return None;
+        }
+        let path = match &self.kind {
+            FileKind::Update {
+                move_path: Some(move_path),
+            } => move_path,
+            _ => &self.path,
+        };
+        detect_language(path.extension()?.to_str()?)
+    }
+
+    /// Digits of the largest line number any hunk shows.
+    fn number_width(&self) -> usize {
+        let max = self
+            .hunks
+            .iter()
+            .map(|hunk| (hunk.old_start + hunk.old_len()).max(hunk.new_start + hunk.new_len()))
+            .max()
+            .unwrap_or(1);
+        max.to_string().len()
+    }
+
+    /// This file's part of a patch holding only the accepted hunks, or
+    /// `None` when none of them are.
+    fn patch_section(&self) -> Option<String> {
+        if !self.hunks.iter().any(|hunk| hunk.accepted) {
+            return None;
+        }
+        let path = self.path.display();
+        let section = match &self.kind {
+            FileKind::Add => {
+                let mut section = format!("*** Add File: {path}\n");
+                for line in self.hunks.iter().flat_map(|hunk| &hunk.lines) {
+                    if let HunkLine::Insert(text) = line {
+                        section.push_str(&format!("+{text}\n"));
+                    }
+                }
+                section
+            }
+            FileKind::Delete => format!("*** Delete File: {path}\n"),
+            FileKind::Update { move_path } => {
+                let mut section = format!("*** Update File: {path}\n");
+                if let Some(move_path) = move_path {
+                    section.push_str(&format!("*** Move to: {}\n", move_path.display()));
+                }
+                for hunk in self.hunks.iter().filter(|hunk| hunk.accepted) {
+                    if hunk.lines.is_empty() {
+                        continue;
+                    }
+                    section.push_str("@@\n");
+                    for line in &hunk.lines {
+                        let (sign, text) = match line {
+                            HunkLine::Context(text) => (' ', text),
+                            HunkLine::Delete(text) => ('-', text),
+                            HunkLine::Insert(text) => ('+', text),
+                        };
+                        section.push_str(&format!("{sign}{text}\n"));
+                    }
+                }
+                section
+            }
+        };
+        Some(section)
+    }
+}
+
+/// The state of a review: which hunks are accepted, which one the keys act
+/// on, and how the diff is laid out.
+#[derive(Debug)]
+pub(crate) struct DiffReview {
+    files: Vec<ReviewFile>,
+    /// Index of the selected hunk, counting across files.
+    selected: usize,
+    side_by_side: bool,
+}
+
+impl DiffReview {
+    pub(crate) fn new(changes: &HashMap<PathBuf, FileChange>) -> Self {
+        let mut files: Vec<ReviewFile> = changes
+            .iter()
+            .map(|(path, change)| ReviewFile::new(path.clone(), change))
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            files,
+            selected: 0,
+            side_by_side: false,
+        }
+    }
+
+    fn hunks(&self) -> impl Iterator<Item = &ReviewHunk> {
+        self.files.iter().flat_map(|file| &file.hunks)
+    }
+
+    fn hunks_mut(&mut self) -> impl Iterator<Item = &mut ReviewHunk> {
+        self.files.iter_mut().flat_map(|file| &mut file.hunks)
+    }
+
+    pub(crate) fn hunk_count(&self) -> usize {
+        self.hunks().count()
+    }
+
+    pub(crate) fn accepted_count(&self) -> usize {
+        self.hunks().filter(|hunk| hunk.accepted).count()
+    }
+
+    pub(crate) fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.hunk_count() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Accepts or rejects the selected hunk and moves on to the next one.
+    pub(crate) fn set_selected_accepted(&mut self, accepted: bool) {
+        let selected = self.selected;
+        if let Some(hunk) = self.hunks_mut().nth(selected) {
+            hunk.accepted = accepted;
+        }
+        self.select_next();
+    }
+
+    pub(crate) fn set_all_accepted(&mut self, accepted: bool) {
+        for hunk in self.hunks_mut() {
+            hunk.accepted = accepted;
+        }
+    }
+
+    pub(crate) fn is_side_by_side(&self) -> bool {
+        self.side_by_side
+    }
+
+    pub(crate) fn toggle_side_by_side(&mut self) {
+        self.side_by_side = !self.side_by_side;
+    }
+
+    /// The answer to the approval request: the whole patch when every hunk
+    /// is accepted, none of it when none is, and otherwise a patch holding
+    /// only the accepted hunks.
+    pub(crate) fn decision(&self) -> ReviewDecision {
+        let accepted = self.accepted_count();
+        if accepted == self.hunk_count() {
+            ReviewDecision::Approved
+        } else if accepted == 0 {
+            ReviewDecision::Abort
+        } else {
+            ReviewDecision::ApprovedWithModifiedPatch {
+                patch: self.modified_patch(),
+            }
+        }
+    }
+
+    /// An `apply_patch` patch with only the accepted hunks.
+    pub(crate) fn modified_patch(&self) -> String {
+        let mut patch = String::from("*** Begin Patch\n");
+        for section in self.files.iter().filter_map(ReviewFile::patch_section) {
+            patch.push_str(&section);
+        }
+        patch.push_str("*** End Patch");
+        patch
+    }
+
+    /// One renderable per file header and per hunk, and the index of each
+    /// hunk's renderable so the selected one can be scrolled into view.
+    pub(crate) fn renderables(&self, cwd: &Path) -> (Vec<Box<dyn Renderable>>, Vec<usize>) {
+        let mut renderables: Vec<Box<dyn Renderable>> = Vec::new();
+        let mut hunk_chunks = Vec::new();
+        let mut index = 0;
+        for (file_index, file) in self.files.iter().enumerate() {
+            if file_index > 0 {
+                renderables.push(Box::new(Line::from("")));
+            }
+            renderables.push(Box::new(file_header(file, cwd)));
+            let language = file.language();
+            let number_width = file.number_width();
+            for hunk in &file.hunks {
+                hunk_chunks.push(renderables.len());
+                renderables.push(Box::new(HunkView {
+                    header: hunk_header(hunk, index == self.selected),
+                    lines: styled_lines(hunk, language),
+                    number_width,
+                    side_by_side: self.side_by_side,
+                    rejected: !hunk.accepted,
+                }));
+                index += 1;
+            }
+        }
+        (renderables, hunk_chunks)
+    }
+}
+
+fn file_header(file: &ReviewFile, cwd: &Path) -> Line<'static> {
+    let (added, removed) =
+        file.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .fold((0, 0), |(added, removed), line| match line {
+                HunkLine::Insert(_) => (added + 1, removed),
+                HunkLine::Delete(_) => (added, removed + 1),
+                HunkLine::Context(_) => (added, removed),
+            });
+    let mut spans: Vec<Span<'static>> = vec!["• ".dim(), display_path_for(&file.path, cwd).bold()];
+    match &file.kind {
+        FileKind::Add => spans.push(" (new file)".dim()),
+        FileKind::Delete => spans.push(" (deleted)".dim()),
+        FileKind::Update {
+            move_path: Some(move_path),
+        } => spans.push(format!(" → {}", display_path_for(move_path, cwd)).into()),
+        FileKind::Update { move_path: None } => {}
+    }
+    spans.extend([
+        " (".into(),
+        format!("+{added}").green(),
+        " ".into(),
+        format!("-{removed}").red(),
+        ")".into(),
+    ]);
+    Line::from(spans)
+}
+
+fn hunk_header(hunk: &ReviewHunk, selected: bool) -> Line<'static> {
+    let marker = if selected { "› ".cyan() } else { "  ".into() };
+    let range = format!(
+        "@@ -{},{} +{},{} @@ ",
+        hunk.old_start,
+        hunk.old_len(),
+        hunk.new_start,
+        hunk.new_len()
+    );
+    let range = if selected { range.cyan() } else { range.dim() };
+    let status = if hunk.accepted {
+        "accepted".green()
+    } else {
+        "rejected".red()
+    };
+    Line::from(vec![marker, range, status])
+}
+
+/// A line of a hunk with its content styled and its line numbers.
+struct StyledLine {
+    sign: char,
+    old: Option<usize>,
+    new: Option<usize>,
+    content: Line<'static>,
+}
+
+/// Styles every line of `hunk`: syntax colors when `language` is known,
+/// diff colors otherwise, and the changed part of each deleted line that an
+/// added line replaces stands out in both.
+fn styled_lines(hunk: &ReviewHunk, language: Option<CodeLanguage>) -> Vec<StyledLine> {
+    let side = |keep: fn(&HunkLine) -> Option<&String>| {
+        let text: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter_map(keep)
+            .map(String::as_str)
+            .collect();
+        language.map(|language| highlight_code_to_lines(language, &text.join("\n")))
+    };
+    let old_side = side(|line| match line {
+        HunkLine::Context(text) | HunkLine::Delete(text) => Some(text),
+        HunkLine::Insert(_) => None,
+    });
+    let new_side = side(|line| match line {
+        HunkLine::Context(text) | HunkLine::Insert(text) => Some(text),
+        HunkLine::Delete(_) => None,
+    });
+    let emphasis = change_emphasis(&hunk.lines);
+
+    let mut old_index = 0;
+    let mut new_index = 0;
+    let mut lines = Vec::with_capacity(hunk.lines.len());
+    for (line_index, line) in hunk.lines.iter().enumerate() {
+        let (sign, old, new, content) = match line {
+            HunkLine::Context(text) => (
+                ' ',
+                Some(hunk.old_start + old_index),
+                Some(hunk.new_start + new_index),
+                side_line(new_side.as_deref(), new_index, text, Style::default()),
+            ),
+            HunkLine::Delete(text) => (
+                '-',
+                Some(hunk.old_start + old_index),
+                None,
+                side_line(old_side.as_deref(), old_index, text, Style::default().red()),
+            ),
+            HunkLine::Insert(text) => (
+                '+',
+                None,
+                Some(hunk.new_start + new_index),
+                side_line(
+                    new_side.as_deref(),
+                    new_index,
+                    text,
+                    Style::default().green(),
+                ),
+            ),
+        };
+        if old.is_some() {
+            old_index += 1;
+        }
+        if new.is_some() {
+            new_index += 1;
+        }
+        let content = match emphasis.get(&line_index) {
+            Some(&(start, end)) => {
+                let style = if sign == '-' {
+                    Style::default().red().reversed()
+                } else {
+                    Style::default().green().reversed()
+                };
+                highlight_line(content, &[(start, end, style)])
+            }
+            None => content,
+        };
+        lines.push(StyledLine {
+            sign,
+            old,
+            new,
+            content,
+        });
+    }
+    lines
+}
+
+/// Line `index` of one side of a hunk: its syntax-highlighted form when there
+/// is one, else `text` in `style`.
+fn side_line(
+    highlighted: Option<&[Line<'static>]>,
+    index: usize,
+    text: &str,
+    style: Style,
+) -> Line<'static> {
+    highlighted
+        .and_then(|lines| lines.get(index).cloned())
+        .unwrap_or_else(|| Line::from(Span::styled(text.to_string(), style)))
+}
+
+/// For each deleted line that an added line replaces, and for that added
+/// line, the byte range that differs between the two. A run of deleted lines
+/// followed by a run of added ones pairs them up in order. Lines that share
+/// nothing at either end get no range, since all of them changed.
+fn change_emphasis(lines: &[HunkLine]) -> HashMap<usize, (usize, usize)> {
+    let mut emphasis = HashMap::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let deletes: Vec<usize> = (index..lines.len())
+            .take_while(|&i| matches!(lines[i], HunkLine::Delete(_)))
+            .collect();
+        let after = index + deletes.len();
+        let inserts: Vec<usize> = (after..lines.len())
+            .take_while(|&i| matches!(lines[i], HunkLine::Insert(_)))
+            .collect();
+        if deletes.is_empty() {
+            index += 1;
+            continue;
+        }
+        for (&old, &new) in deletes.iter().zip(&inserts) {
+            let (HunkLine::Delete(old_text), HunkLine::Insert(new_text)) =
+                (&lines[old], &lines[new])
+            else {
+                continue;
+            };
+            let prefix = old_text
+                .char_indices()
+                .zip(new_text.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(old_text.len().min(new_text.len()), |((i, _), _)| i);
+            let suffix = old_text[prefix..]
+                .chars()
+                .rev()
+                .zip(new_text[prefix..].chars().rev())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum::<usize>();
+            if prefix == 0 && suffix == 0 {
+                continue;
+            }
+            emphasis.insert(old, (prefix, old_text.len() - suffix));
+            emphasis.insert(new, (prefix, new_text.len() - suffix));
+        }
+        index = after + inserts.len();
+    }
+    emphasis
+}
+
+/// One hunk: its header and its lines, unified or side by side.
+struct HunkView {
+    header: Line<'static>,
+    lines: Vec<StyledLine>,
+    number_width: usize,
+    side_by_side: bool,
+    rejected: bool,
+}
+
+impl HunkView {
+    fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut out = vec![self.header.clone()];
+        if self.side_by_side {
+            // Each side gets half of what is left of the width after the
+            // separator.
+            let column = (width as usize).saturating_sub(3) / 2;
+            for (old, new) in self.split_rows() {
+                let mut spans = fit_to_width(self.cell(old, |line| line.old), column);
+                spans.push(" │ ".dim());
+                spans.extend(fit_to_width(self.cell(new, |line| line.new), column));
+                out.push(Line::from(spans));
+            }
+        } else {
+            for line in &self.lines {
+                let mut spans = vec![
+                    Span::from(format!(
+                        "{} {} ",
+                        line_number(line.old, self.number_width),
+                        line_number(line.new, self.number_width)
+                    ))
+                    .dim(),
+                    sign_span(line.sign),
+                ];
+                spans.extend(line.content.spans.iter().cloned());
+                out.push(Line::from(spans));
+            }
+        }
+        if self.rejected {
+            for line in out.iter_mut().skip(1) {
+                *line = line.clone().dim();
+            }
+        }
+        out
+    }
+
+    /// Pairs old lines with new ones: context on both sides, and each run of
+    /// deleted lines next to the run of added lines that follows it.
+    fn split_rows(&self) -> Vec<(Option<&StyledLine>, Option<&StyledLine>)> {
+        let mut rows = Vec::new();
+        let mut index = 0;
+        while index < self.lines.len() {
+            let line = &self.lines[index];
+            if line.sign == ' ' {
+                rows.push((Some(line), Some(line)));
+                index += 1;
+                continue;
+            }
+            let deletes: Vec<&StyledLine> = self.lines[index..]
+                .iter()
+                .take_while(|line| line.sign == '-')
+                .collect();
+            let inserts: Vec<&StyledLine> = self.lines[index + deletes.len()..]
+                .iter()
+                .take_while(|line| line.sign == '+')
+                .collect();
+            for row in 0..deletes.len().max(inserts.len()) {
+                rows.push((deletes.get(row).copied(), inserts.get(row).copied()));
+            }
+            index += deletes.len() + inserts.len();
+        }
+        rows
+    }
+
+    fn cell(
+        &self,
+        line: Option<&StyledLine>,
+        number: fn(&StyledLine) -> Option<usize>,
+    ) -> Vec<Span<'static>> {
+        let Some(line) = line else {
+            return Vec::new();
+        };
+        let mut spans = vec![
+            Span::from(format!("{} ", line_number(number(line), self.number_width))).dim(),
+            sign_span(line.sign),
+        ];
+        spans.extend(line.content.spans.iter().cloned());
+        spans
+    }
+}
+
+impl Renderable for HunkView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(Text::from(self.render_lines(area.width))).render(area, buf);
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        let rows = if self.side_by_side {
+            self.split_rows().len()
+        } else {
+            self.lines.len()
+        };
+        (rows + 1).try_into().unwrap_or(u16::MAX)
+    }
+}
+
+fn line_number(number: Option<usize>, width: usize) -> String {
+    match number {
+        Some(number) => format!("{number:>width$}"),
+        None => " ".repeat(width),
+    }
+}
+
+fn sign_span(sign: char) -> Span<'static> {
+    match sign {
+        '-' => "-".red(),
+        '+' => "+".green(),
+        _ => " ".into(),
+    }
+}
+
+/// Cuts `spans` to `width` columns, padding with spaces when shorter.
+fn fit_to_width(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let mut used = 0;
+    let mut out = Vec::new();
+    for span in spans {
+        let mut text = String::new();
+        for ch in span.content.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if used + ch_width > width {
+                break;
+            }
+            text.push(ch);
+            used += ch_width;
+        }
+        let truncated = text.len() < span.content.len();
+        out.push(Span::styled(text, span.style));
+        if truncated {
+            break;
+        }
+    }
+    if used < width {
+        out.push(" ".repeat(width - used).into());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    fn update(path: &str, old: &str, new: &str) -> (PathBuf, FileChange) {
+        (
+            PathBuf::from(path),
+            FileChange::Update {
+                unified_diff: diffy::create_patch(old, new).to_string(),
+                move_path: None,
+            },
+        )
+    }
+
+    fn numbered(lines: std::ops::Range<usize>) -> String {
+        lines.map(|i| format!("line {i}\n")).collect()
+    }
+
+    /// A file of 20 lines where lines 2 and 18 changed, far enough apart to
+    /// give two hunks, plus a new file.
+    fn changes() -> HashMap<PathBuf, FileChange> {
+        let old = numbered(1..21);
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line eighteen\n");
+        HashMap::from([
+            update("/repo/a.txt", &old, &new),
+            (
+                PathBuf::from("/repo/b.txt"),
+                FileChange::Add {
+                    content: "hello\n".to_string(),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn splits_files_into_hunks() {
+        let review = DiffReview::new(&changes());
+        assert_eq!(review.hunk_count(), 3);
+        assert_eq!(review.accepted_count(), 3);
+        assert_eq!(review.decision(), ReviewDecision::Approved);
+        assert_eq!(review.files[0].hunks[0].old_start, 1);
+        assert_eq!(
+            review.files[0].hunks[1].lines[3],
+            HunkLine::Delete("line 18".to_string())
+        );
+    }
+
+    #[test]
+    fn partial_acceptance_rebuilds_the_patch() {
+        let mut review = DiffReview::new(&changes());
+        // Reject the first hunk of a.txt; keep the second and b.txt.
+        review.set_selected_accepted(false);
+        assert_eq!(review.selected(), 1);
+
+        let ReviewDecision::ApprovedWithModifiedPatch { patch } = review.decision() else {
+            panic!("expected a modified patch, got {:?}", review.decision());
+        };
+        assert_eq!(
+            patch,
+            "*** Begin Patch\n\
+             *** Update File: /repo/a.txt\n\
+             @@\n line 15\n line 16\n line 17\n-line 18\n+line eighteen\n line 19\n line 20\n\
+             *** Add File: /repo/b.txt\n\
+             +hello\n\
+             *** End Patch"
+        );
+
+        review.set_all_accepted(false);
+        assert_eq!(review.decision(), ReviewDecision::Abort);
+    }
+
+    #[test]
+    fn modified_patch_applies_only_accepted_hunks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("a.txt");
+        let old = numbered(1..21);
+        std::fs::write(&path, &old).expect("write");
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line eighteen\n");
+        let mut review = DiffReview::new(&HashMap::from([(
+            path.clone(),
+            FileChange::Update {
+                unified_diff: diffy::create_patch(&old, &new).to_string(),
+                move_path: None,
+            },
+        )]));
+        review.set_selected_accepted(true);
+        review.set_selected_accepted(false);
+
+        let args = codex_apply_patch::parse_patch(&review.modified_patch()).expect("valid patch");
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        codex_apply_patch::apply_hunks(&args.hunks, &mut stdout, &mut stderr).expect("applies");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            old.replace("line 2\n", "line two\n")
+        );
+    }
+
+    #[test]
+    fn emphasizes_the_changed_part_of_replaced_lines() {
+        let lines = vec![
+            HunkLine::Context("fn main() {".to_string()),
+            HunkLine::Delete("    let x = 1;".to_string()),
+            HunkLine::Insert("    let x = 42;".to_string()),
+            HunkLine::Insert("    println!(\"{x}\");".to_string()),
+        ];
+        assert_eq!(
+            change_emphasis(&lines),
+            HashMap::from([(1, (12, 13)), (2, (12, 14))])
+        );
+    }
+
+    #[test]
+    fn renders_unified_and_side_by_side() {
+        let (path, change) = update("/repo/a.txt", "one\ntwo\n", "one\n2\n");
+        let mut review = DiffReview::new(&HashMap::from([(path, change)]));
+        let render = |review: &DiffReview| {
+            let (renderables, hunks) = review.renderables(Path::new("/repo"));
+            let view = &renderables[hunks[0]];
+            let area = Rect::new(0, 0, 23, view.desired_height(23));
+            let mut buf = Buffer::empty(area);
+            view.render(area, &mut buf);
+            (0..area.height)
+                .map(|y| {
+                    (0..area.width)
+                        .map(|x| buf[(x, y)].symbol())
+                        .collect::<String>()
+                        .trim_end()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            render(&review),
+            vec!["› @@ -1,2 +1,2 @@ accep", "1 1  one", "2   -two", "  2 +2",]
+        );
+
+        review.toggle_side_by_side();
+        assert_eq!(
+            render(&review),
+            vec![
+                "› @@ -1,2 +1,2 @@ accep",
+                "1  one     │ 1  one",
+                "2 -two     │ 2 +2",
+            ]
+        );
+        assert_eq!(
+            line_text(&file_header(&review.files[0], Path::new("/repo"))),
+            "• /repo/a.txt (+1 -1)"
+        );
+    }
+}
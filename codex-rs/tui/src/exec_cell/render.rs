@@ -9,6 +9,7 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::text_formatting::truncate_text;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -27,6 +28,8 @@ use unicode_width::UnicodeWidthStr;
 pub(crate) const TOOL_CALL_MAX_LINES: usize = 5;
 const USER_SHELL_TOOL_CALL_MAX_LINES: usize = 50;
 const MAX_INTERACTION_PREVIEW_CHARS: usize = 80;
+/// Columns a collapsed summary keeps for everything after the command.
+const SUMMARY_STATUS_WIDTH: usize = 40;
 
 pub(crate) struct OutputLinesParams {
     pub(crate) line_limit: usize,
@@ -249,6 +252,40 @@ impl HistoryCell for ExecCell {
         }
         lines
     }
+
+    fn collapsed_summary(&self, width: u16) -> Option<Line<'static>> {
+        if self.is_exploring_cell() {
+            return None;
+        }
+        let call = self.calls.last()?;
+        let output = call.output.as_ref()?;
+        if call.is_unified_exec_interaction() {
+            return None;
+        }
+        // Leave room for the exit code, duration, and line count.
+        let max_command = usize::from(width)
+            .saturating_sub(SUMMARY_STATUS_WIDTH)
+            .max(20);
+        let script = strip_bash_lc_and_escape(&call.command);
+        let mut command = truncate_text(script.lines().next().unwrap_or_default(), max_command);
+        if script.lines().nth(1).is_some() {
+            command.push_str(" ...");
+        }
+        let mut summary = Line::from(vec!["$ ".magenta(), command.into(), " ".into()]);
+        if output.exit_code == 0 {
+            summary.push_span("✓".green().bold());
+        } else {
+            summary.push_span("✗".red().bold());
+            summary.push_span(format!(" ({})", output.exit_code));
+        }
+        if let Some(duration) = call.duration {
+            summary.push_span(format!(" • {}", format_duration(duration)).dim());
+        }
+        let output_lines = output.formatted_output.lines().count();
+        let noun = if output_lines == 1 { "line" } else { "lines" };
+        summary.push_span(format!(" • {output_lines} {noun} of output").dim());
+        Some(summary)
+    }
 }
 
 impl ExecCell {
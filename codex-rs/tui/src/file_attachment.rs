@@ -0,0 +1,136 @@
+//! Contents of the files picked from the `@` popup, sent along with the
+//! message that mentions them.
+
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::user_input::UserInput;
+
+/// One text item per file in `paths` that `text` still mentions, holding
+/// at most `max_bytes` of the file. Files that cannot be read or are not
+/// text are left out. `max_bytes` of `0` attaches nothing.
+pub(crate) fn file_attachments(
+    text: &str,
+    paths: &[PathBuf],
+    cwd: &Path,
+    max_bytes: usize,
+) -> Vec<UserInput> {
+    if max_bytes == 0 {
+        return Vec::new();
+    }
+    paths
+        .iter()
+        .filter(|path| text.contains(path.to_string_lossy().as_ref()))
+        .filter_map(|path| match read_attachment(&cwd.join(path), max_bytes) {
+            Ok(Some((contents, total))) => Some(UserInput::Text {
+                text: format_attachment(path, &contents, total),
+            }),
+            Ok(None) => None,
+            Err(err) => {
+                tracing::warn!("failed to attach {}: {err}", path.display());
+                None
+            }
+        })
+        .collect()
+}
+
+/// The first `max_bytes` of the file at `path` and its full size, or `None`
+/// when it is not a UTF-8 text file.
+fn read_attachment(path: &Path, max_bytes: usize) -> std::io::Result<Option<(String, u64)>> {
+    let file = std::fs::File::open(path)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Ok(None);
+    }
+    let mut bytes = Vec::new();
+    file.take(max_bytes as u64).read_to_end(&mut bytes)?;
+    if bytes.contains(&0) {
+        return Ok(None);
+    }
+    let contents = match String::from_utf8(bytes) {
+        Ok(contents) => contents,
+        // The cap cut a character in half; drop the partial one.
+        Err(err) if err.utf8_error().error_len().is_none() => {
+            let valid = err.utf8_error().valid_up_to();
+            let mut bytes = err.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        Err(_) => return Ok(None),
+    };
+    Ok(Some((contents, metadata.len())))
+}
+
+fn format_attachment(path: &Path, contents: &str, total: u64) -> String {
+    let mut text = format!("<file path=\"{}\">\n{contents}", path.display());
+    if !contents.ends_with('\n') {
+        text.push('\n');
+    }
+    if (contents.len() as u64) < total {
+        text.push_str(&format!(
+            "[truncated: first {} of {total} bytes]\n",
+            contents.len()
+        ));
+    }
+    text.push_str("</file>");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn texts(items: Vec<UserInput>) -> Vec<String> {
+        items
+            .into_iter()
+            .map(|item| match item {
+                UserInput::Text { text } => text,
+                other => panic!("expected text, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attaches_mentioned_files_up_to_the_cap() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("src")).expect("mkdir");
+        std::fs::write(dir.path().join("src/lib.rs"), "fn main() {}\n").expect("write");
+        std::fs::write(dir.path().join("big.txt"), "é".repeat(10)).expect("write");
+        std::fs::write(dir.path().join("blob.bin"), [0u8, 1, 2]).expect("write");
+        let paths = [
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("big.txt"),
+            PathBuf::from("blob.bin"),
+            PathBuf::from("removed.txt"),
+        ];
+
+        let attached = file_attachments(
+            "look at src/lib.rs, big.txt and blob.bin",
+            &paths,
+            dir.path(),
+            5,
+        );
+        assert_eq!(
+            texts(attached),
+            vec![
+                "<file path=\"src/lib.rs\">\nfn ma\n[truncated: first 5 of 13 bytes]\n</file>"
+                    .to_string(),
+                // Five bytes would split the third `é`.
+                "<file path=\"big.txt\">\néé\n[truncated: first 4 of 20 bytes]\n</file>"
+                    .to_string(),
+            ]
+        );
+
+        let attached = file_attachments("src/lib.rs", &paths, dir.path(), 1024);
+        assert_eq!(
+            texts(attached),
+            vec!["<file path=\"src/lib.rs\">\nfn main() {}\n</file>".to_string()]
+        );
+        assert_eq!(
+            file_attachments("src/lib.rs", &paths, dir.path(), 0),
+            Vec::new()
+        );
+    }
+}
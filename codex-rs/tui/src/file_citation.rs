@@ -0,0 +1,303 @@
+//! File references such as `src/lib.rs:42-60` in assistant replies.
+//!
+//! The transcript links them with OSC 8 hyperlinks as history lines are
+//! written (see [`linkify`]); Ctrl+O shows the cited lines of the last
+//! reply and Ctrl+G opens one in `$VISUAL`/`$EDITOR`.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::LazyLock;
+use std::sync::RwLock;
+
+use codex_core::config::types::UriBasedFileOpener;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use regex_lite::Regex;
+
+use crate::history_cell::PlainHistoryCell;
+use crate::render::code_highlight::detect_language;
+use crate::render::code_highlight::highlight_code_to_lines;
+use crate::render::code_highlight::syntax_highlighting_enabled;
+
+/// Most lines a snippet shows; longer ranges are cut off.
+const MAX_SNIPPET_LINES: usize = 40;
+
+/// `path/to/file.ext:LINE` or `path/to/file.ext:START-END`. The leading
+/// group keeps the match from starting in the middle of a word or URL.
+static CITATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:^|[^\w./~:-])((?:~/|/)?[\w.-]+(?:/[\w.-]+)*\.[A-Za-z][A-Za-z0-9]*):(\d+)(?:-(\d+))?",
+    )
+    .unwrap_or_else(|_| std::process::abort())
+});
+
+static LINK_TARGET: RwLock<Option<LinkTarget>> = RwLock::new(None);
+
+/// Where transcript hyperlinks point: paths resolve against `cwd` and open
+/// through `opener` when it has a URI scheme.
+#[derive(Clone, Debug)]
+pub(crate) struct LinkTarget {
+    pub cwd: PathBuf,
+    pub opener: UriBasedFileOpener,
+}
+
+/// Turns transcript hyperlinks on (`Some`) or off (`tui.file_hyperlinks`).
+pub(crate) fn set_link_target(target: Option<LinkTarget>) {
+    if let Ok(mut current) = LINK_TARGET.write() {
+        *current = target;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FileCitation {
+    /// The path as written in the reply.
+    pub path: String,
+    /// First cited line, 1-based.
+    pub start: usize,
+    /// Last cited line; equal to `start` for a single line.
+    pub end: usize,
+}
+
+impl FileCitation {
+    pub(crate) fn label(&self) -> String {
+        if self.end > self.start {
+            format!("{}:{}-{}", self.path, self.start, self.end)
+        } else {
+            format!("{}:{}", self.path, self.start)
+        }
+    }
+
+    pub(crate) fn resolve(&self, cwd: &Path) -> PathBuf {
+        if let Some(rest) = self.path.strip_prefix("~/")
+            && let Some(home) = dirs::home_dir()
+        {
+            return home.join(rest);
+        }
+        cwd.join(&self.path)
+    }
+
+    /// The cited lines with line numbers, highlighted by file extension.
+    pub(crate) fn snippet_cell(&self, cwd: &Path) -> PlainHistoryCell {
+        let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), self.label().bold()].into()];
+        match self.snippet_lines(cwd) {
+            Ok(snippet) => lines.extend(snippet),
+            Err(err) => lines.push(Line::from(format!("  └ {err}").red())),
+        }
+        PlainHistoryCell::new(lines)
+    }
+
+    fn snippet_lines(&self, cwd: &Path) -> Result<Vec<Line<'static>>, String> {
+        let path = self.resolve(cwd);
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+        let source: Vec<&str> = contents.lines().collect();
+        let start = self.start.max(1);
+        if start > source.len() {
+            return Err(format!("{} has only {} lines", self.path, source.len()));
+        }
+        let end = self
+            .end
+            .clamp(start, source.len())
+            .min(start + MAX_SNIPPET_LINES - 1);
+        let text = source[start - 1..end].join("\n");
+
+        let language = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .filter(|_| syntax_highlighting_enabled())
+            .and_then(detect_language);
+        let body: Vec<Line<'static>> = match language {
+            Some(language) => highlight_code_to_lines(language, &text),
+            None => text
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect(),
+        };
+
+        let width = end.to_string().len();
+        let mut lines: Vec<Line<'static>> = body
+            .into_iter()
+            .zip(start..=end)
+            .map(|(line, number)| {
+                let mut spans: Vec<Span<'static>> = vec![format!("  {number:>width$} │ ").dim()];
+                spans.extend(line.spans);
+                Line::from(spans)
+            })
+            .collect();
+        if end < self.end.min(source.len()) {
+            lines.push(Line::from(
+                format!("  … {} more lines", self.end.min(source.len()) - end).dim(),
+            ));
+        }
+        Ok(lines)
+    }
+
+    /// A URL that opens the cited file: the `file_opener` scheme with the
+    /// line number, or a plain `file://` URL.
+    fn url(&self, target: &LinkTarget) -> Option<String> {
+        let path = self.resolve(&target.cwd);
+        match target.opener.get_scheme() {
+            Some(scheme) => Some(format!("{scheme}://file{}:{}", path.display(), self.start)),
+            None => url::Url::from_file_path(&path).ok().map(String::from),
+        }
+    }
+}
+
+/// Every distinct file reference in `text`, in order of appearance.
+pub(crate) fn find_citations(text: &str) -> Vec<FileCitation> {
+    let mut citations: Vec<FileCitation> = Vec::new();
+    for (_, citation) in citation_matches(text) {
+        if !citations.contains(&citation) {
+            citations.push(citation);
+        }
+    }
+    citations
+}
+
+fn citation_matches(text: &str) -> impl Iterator<Item = (std::ops::Range<usize>, FileCitation)> {
+    CITATION_REGEX.captures_iter(text).filter_map(|caps| {
+        let path = caps.get(1)?;
+        let start: usize = caps.get(2)?.as_str().parse().ok()?;
+        let end = match caps.get(3) {
+            Some(end) => end.as_str().parse().ok()?,
+            None => start,
+        };
+        let range = path.start()..caps.get(0)?.end();
+        Some((
+            range,
+            FileCitation {
+                path: path.as_str().to_string(),
+                start,
+                end: end.max(start),
+            },
+        ))
+    })
+}
+
+/// `text` with each reference to an existing file wrapped in an OSC 8
+/// hyperlink, or `None` when hyperlinks are off or nothing matched.
+pub(crate) fn linkify(text: &str) -> Option<String> {
+    let target = LINK_TARGET.read().ok()?.clone()?;
+    let mut linked = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, citation) in citation_matches(text) {
+        if !citation.resolve(&target.cwd).is_file() {
+            continue;
+        }
+        let Some(url) = citation.url(&target) else {
+            continue;
+        };
+        linked.push_str(&text[last..range.start]);
+        linked.push_str(&format!(
+            "\x1b]8;;{url}\x1b\\{}\x1b]8;;\x1b\\",
+            &text[range.clone()]
+        ));
+        last = range.end;
+    }
+    if last == 0 {
+        return None;
+    }
+    linked.push_str(&text[last..]);
+    Some(linked)
+}
+
+/// A command that opens `path` at `line` in `$VISUAL` or `$EDITOR`.
+pub(crate) fn editor_command(path: &Path, line: usize) -> Option<Command> {
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok())?;
+    let mut words = shlex::split(&editor)?.into_iter();
+    let program = words.next()?;
+    let mut command = Command::new(&program);
+    command.args(words);
+    command.args(editor_args(&program, path, line));
+    Some(command)
+}
+
+/// VS Code-style editors take `--goto file:line`; the others (vi, nano,
+/// emacs, ...) take `+line file`.
+fn editor_args(program: &str, path: &Path, line: usize) -> Vec<String> {
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    match name {
+        "code" | "code-insiders" | "cursor" | "windsurf" => {
+            vec!["--goto".to_string(), format!("{}:{line}", path.display())]
+        }
+        _ => vec![format!("+{line}"), path.display().to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn citation(path: &str, start: usize, end: usize) -> FileCitation {
+        FileCitation {
+            path: path.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn finds_single_lines_and_ranges() {
+        let text = "See `src/foo.rs:42-60` and lib.rs:7, again src/foo.rs:42-60. \
+                    Not http://host.com:80 or v1.2:3";
+        assert_eq!(
+            find_citations(text),
+            vec![citation("src/foo.rs", 42, 60), citation("lib.rs", 7, 7)]
+        );
+    }
+
+    #[test]
+    fn snippet_shows_numbered_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("notes.txt"), "one\ntwo\nthree\nfour\n").expect("write");
+
+        let cell = citation("notes.txt", 2, 3).snippet_cell(dir.path());
+        let rendered: Vec<String> = crate::history_cell::HistoryCell::display_lines(&cell, 80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec!["• notes.txt:2-3", "  2 │ two", "  3 │ three"]
+        );
+
+        let missing = citation("notes.txt", 9, 9).snippet_cell(dir.path());
+        let rendered: Vec<String> = crate::history_cell::HistoryCell::display_lines(&missing, 80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rendered[1], "  └ notes.txt has only 4 lines");
+    }
+
+    #[test]
+    fn editor_args_follow_the_editor() {
+        let path = Path::new("/work/src/lib.rs");
+        assert_eq!(
+            editor_args("nvim", path, 12),
+            vec!["+12".to_string(), "/work/src/lib.rs".to_string()]
+        );
+        assert_eq!(
+            editor_args("/usr/local/bin/code", path, 12),
+            vec!["--goto".to_string(), "/work/src/lib.rs:12".to_string()]
+        );
+    }
+}
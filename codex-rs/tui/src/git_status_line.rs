@@ -0,0 +1,70 @@
+//! The git line at the top of the chat view: the current branch, how many
+//! paths have changed, and how far the branch is ahead of or behind its
+//! upstream. `/git` shows the full `git status --short`.
+
+use codex_core::git_info::GitStatusSummary;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+pub(crate) fn git_status_line(summary: &GitStatusSummary) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = vec!["⎇ ".dim()];
+    match &summary.branch {
+        Some(branch) => spans.push(branch.clone().cyan()),
+        None => spans.push("detached HEAD".cyan()),
+    }
+    spans.push(" · ".dim());
+    if summary.changed == 0 {
+        spans.push("clean".dim());
+    } else {
+        spans.push(format!("{} changed", summary.changed).dim());
+    }
+    if let Some((ahead, behind)) = summary.ahead_behind.filter(|counts| *counts != (0, 0)) {
+        spans.push(" · ".dim());
+        let counts = match (ahead, behind) {
+            (ahead, 0) => format!("↑{ahead}"),
+            (0, behind) => format!("↓{behind}"),
+            (ahead, behind) => format!("↑{ahead} ↓{behind}"),
+        };
+        spans.push(counts.dim());
+    }
+    spans.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(summary: &GitStatusSummary) -> String {
+        git_status_line(summary)
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn shows_branch_changes_and_upstream_counts() {
+        let summary = GitStatusSummary {
+            branch: Some("main".to_string()),
+            changed: 3,
+            ahead_behind: Some((2, 1)),
+        };
+        assert_eq!(text(&summary), "⎇ main · 3 changed · ↑2 ↓1");
+
+        let in_sync = GitStatusSummary {
+            changed: 0,
+            ahead_behind: Some((0, 0)),
+            ..summary
+        };
+        assert_eq!(text(&in_sync), "⎇ main · clean");
+
+        let detached = GitStatusSummary {
+            branch: None,
+            changed: 1,
+            ahead_behind: None,
+        };
+        assert_eq!(text(&detached), "⎇ detached HEAD · 1 changed");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::accessibility;
 use crate::diff_render::create_diff_summary;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
@@ -7,6 +8,7 @@ use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::image_thumbnail::ImageThumbnail;
 use crate::live_wrap::take_prefix_by_width;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
@@ -16,6 +18,7 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme;
 use crate::tooltips;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::update_action::UpdateAction;
@@ -27,6 +30,7 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::config::types::ShowReasoning;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
@@ -100,6 +104,12 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn is_stream_continuation(&self) -> bool {
         false
     }
+
+    /// One line the transcript overlay shows in place of `transcript_lines`
+    /// while the cell is collapsed. Cells without a summary never collapse.
+    fn collapsed_summary(&self, _width: u16) -> Option<Line<'static>> {
+        None
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -133,6 +143,8 @@ impl dyn HistoryCell {
 #[derive(Debug)]
 pub(crate) struct UserHistoryCell {
     pub message: String,
+    /// Previews of the images sent with the message.
+    pub thumbnails: Vec<ImageThumbnail>,
 }
 
 impl HistoryCell for UserHistoryCell {
@@ -156,6 +168,15 @@ impl HistoryCell for UserHistoryCell {
 
         lines.push(Line::from("").style(style));
         lines.extend(prefix_lines(wrapped, "› ".bold().dim(), "  ".into()));
+        for thumbnail in &self.thumbnails {
+            lines.push(Line::from("").style(style));
+            let thumbnail = thumbnail
+                .lines()
+                .iter()
+                .map(|line| line.clone().style(style))
+                .collect();
+            lines.extend(prefix_lines(thumbnail, "  ".into(), "  ".into()));
+        }
         lines.push(Line::from("").style(style));
         lines
     }
@@ -163,7 +184,7 @@ impl HistoryCell for UserHistoryCell {
 
 #[derive(Debug)]
 pub(crate) struct ReasoningSummaryCell {
-    _header: String,
+    header: String,
     content: String,
     transcript_only: bool,
 }
@@ -171,7 +192,7 @@ pub(crate) struct ReasoningSummaryCell {
 impl ReasoningSummaryCell {
     pub(crate) fn new(header: String, content: String, transcript_only: bool) -> Self {
         Self {
-            _header: header,
+            header,
             content,
             transcript_only,
         }
@@ -230,6 +251,23 @@ impl HistoryCell for ReasoningSummaryCell {
     fn desired_transcript_height(&self, width: u16) -> u16 {
         self.lines(width).len() as u16
     }
+
+    fn collapsed_summary(&self, width: u16) -> Option<Line<'static>> {
+        let title = [self.header.as_str(), self.content.as_str()]
+            .into_iter()
+            .flat_map(str::lines)
+            .map(|line| line.trim().trim_matches('*').trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or("Reasoning")
+            .to_string();
+        let count = self.lines(width).len();
+        let noun = if count == 1 { "line" } else { "lines" };
+        Some(Line::from(vec![
+            "• ".dim(),
+            title.dim().italic(),
+            format!(" • {count} {noun} of reasoning").dim(),
+        ]))
+    }
 }
 
 #[derive(Debug)]
@@ -603,6 +641,27 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedForProject { .. } => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to always run ".into(),
+                    snippet,
+                    " in this project".bold(),
+                ],
+            )
+        }
+        ApprovedWithModifiedPatch { .. } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                " part of the patch".into(),
+            ],
+        ),
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -653,6 +712,16 @@ impl HistoryCell for PatchHistoryCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         create_diff_summary(&self.changes, &self.cwd, width as usize)
     }
+
+    fn collapsed_summary(&self, width: u16) -> Option<Line<'static>> {
+        let mut lines = self.display_lines(width).into_iter();
+        // The first line is the "Edited <file> (+N -M)" header.
+        let mut summary = lines.next()?;
+        let diff_lines = lines.count();
+        let noun = if diff_lines == 1 { "line" } else { "lines" };
+        summary.push_span(format!(" • {diff_lines} {noun} of diff").dim());
+        Some(summary)
+    }
 }
 
 #[derive(Debug)]
@@ -696,6 +765,10 @@ fn with_border_internal(
     lines: Vec<Line<'static>>,
     forced_inner_width: Option<usize>,
 ) -> Vec<Line<'static>> {
+    // Box-drawing characters are noise to a screen reader.
+    if accessibility::enabled() {
+        return lines;
+    }
     let max_line_width = lines
         .iter()
         .map(|line| {
@@ -711,7 +784,14 @@ fn with_border_internal(
 
     let mut out = Vec::with_capacity(lines.len() + 2);
     let border_inner_width = content_width + 2;
-    out.push(vec![format!("╭{}╮", "─".repeat(border_inner_width)).dim()].into());
+    let border = theme::border_style();
+    out.push(
+        vec![Span::styled(
+            format!("╭{}╮", "─".repeat(border_inner_width)),
+            border,
+        )]
+        .into(),
+    );
 
     for line in lines.into_iter() {
         let used_width: usize = line
@@ -720,16 +800,22 @@ fn with_border_internal(
             .sum();
         let span_count = line.spans.len();
         let mut spans: Vec<Span<'static>> = Vec::with_capacity(span_count + 4);
-        spans.push(Span::from("│ ").dim());
+        spans.push(Span::styled("│ ", border));
         spans.extend(line.into_iter());
         if used_width < content_width {
             spans.push(Span::from(" ".repeat(content_width - used_width)).dim());
         }
-        spans.push(Span::from(" │").dim());
+        spans.push(Span::styled(" │", border));
         out.push(Line::from(spans));
     }
 
-    out.push(vec![format!("╰{}╯", "─".repeat(border_inner_width)).dim()].into());
+    out.push(
+        vec![Span::styled(
+            format!("╰{}╯", "─".repeat(border_inner_width)),
+            border,
+        )]
+        .into(),
+    );
 
     out
 }
@@ -861,8 +947,11 @@ pub(crate) fn new_session_info(
     SessionInfoCell(CompositeHistoryCell { parts })
 }
 
-pub(crate) fn new_user_prompt(message: String) -> UserHistoryCell {
-    UserHistoryCell { message }
+pub(crate) fn new_user_prompt(message: String, thumbnails: Vec<ImageThumbnail>) -> UserHistoryCell {
+    UserHistoryCell {
+        message,
+        thumbnails,
+    }
 }
 
 #[derive(Debug)]
@@ -1493,24 +1582,6 @@ impl HistoryCell for PlanUpdateCell {
                 .collect()
         };
 
-        let render_step = |status: &StepStatus, text: &str| -> Vec<Line<'static>> {
-            let (box_str, step_style) = match status {
-                StepStatus::Completed => ("✔ ", Style::default().crossed_out().dim()),
-                StepStatus::InProgress => ("□ ", Style::default().cyan().bold()),
-                StepStatus::Pending => ("□ ", Style::default().dim()),
-            };
-            let wrap_width = (width as usize)
-                .saturating_sub(4)
-                .saturating_sub(box_str.width())
-                .max(1);
-            let parts = textwrap::wrap(text, wrap_width);
-            let step_text = parts
-                .into_iter()
-                .map(|s| s.to_string().set_style(step_style).into())
-                .collect();
-            prefix_lines(step_text, box_str.into(), "  ".into())
-        };
-
         let mut lines: Vec<Line<'static>> = vec![];
         lines.push(vec!["• ".dim(), "Updated Plan".bold()].into());
 
@@ -1528,7 +1599,7 @@ impl HistoryCell for PlanUpdateCell {
             indented_lines.push(Line::from("(no steps provided)".dim().italic()));
         } else {
             for PlanItemArg { step, status } in self.plan.iter() {
-                indented_lines.extend(render_step(status, step));
+                indented_lines.extend(plan_step_lines(status, step, width.saturating_sub(4)));
             }
         }
         lines.extend(prefix_lines(indented_lines, "  └ ".dim(), "    ".into()));
@@ -1537,6 +1608,22 @@ impl HistoryCell for PlanUpdateCell {
     }
 }
 
+/// One plan step as a checkbox line wrapped to `width`: ✔ when completed,
+/// highlighted while in progress.
+pub(crate) fn plan_step_lines(status: &StepStatus, text: &str, width: u16) -> Vec<Line<'static>> {
+    let (box_str, step_style) = match status {
+        StepStatus::Completed => ("✔ ", Style::default().crossed_out().dim()),
+        StepStatus::InProgress => ("□ ", Style::default().cyan().bold()),
+        StepStatus::Pending => ("□ ", Style::default().dim()),
+    };
+    let wrap_width = (width as usize).saturating_sub(box_str.width()).max(1);
+    let step_text = textwrap::wrap(text, wrap_width)
+        .into_iter()
+        .map(|s| s.to_string().set_style(step_style).into())
+        .collect();
+    prefix_lines(step_text, box_str.into(), "  ".into())
+}
+
 /// Create a new `PendingPatch` cell that lists the file‑level summary of
 /// a proposed patch. The summary lines should already be formatted (e.g.
 /// "A path/to/file.rs").
@@ -1587,10 +1674,21 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
     PlainHistoryCell { lines }
 }
 
+/// The finished reasoning as a history cell. It stays out of the scrollback
+/// (transcript only) with `never`, and with `auto` unless the model gave it a
+/// header.
 pub(crate) fn new_reasoning_summary_block(
     full_reasoning_buffer: String,
     reasoning_summary_format: ReasoningSummaryFormat,
+    show_reasoning: ShowReasoning,
 ) -> Box<dyn HistoryCell> {
+    if show_reasoning != ShowReasoning::Auto {
+        return Box::new(ReasoningSummaryCell::new(
+            "".to_string(),
+            full_reasoning_buffer.trim().to_string(),
+            show_reasoning == ShowReasoning::Never,
+        ));
+    }
     if reasoning_summary_format == ReasoningSummaryFormat::Experimental {
         // Experimental format is following:
         // ** header **
@@ -2529,6 +2627,7 @@ mod tests {
         let msg = "one two three four five six seven";
         let cell = UserHistoryCell {
             message: msg.to_string(),
+            thumbnails: Vec::new(),
         };
 
         // Small width to force wrapping more clearly. Effective wrap width is width-2 due to the ▌ prefix and trailing space.
@@ -2597,6 +2696,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
             reasoning_format,
+            ShowReasoning::Auto,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2612,6 +2712,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "Detailed reasoning goes here.".to_string(),
             reasoning_format,
+            ShowReasoning::Auto,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2634,6 +2735,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level reasoning**\n\nDetailed reasoning goes here.".to_string(),
             model_family.reasoning_summary_format,
+            ShowReasoning::Auto,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2646,6 +2748,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level reasoning without closing".to_string(),
             reasoning_format,
+            ShowReasoning::Auto,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2658,6 +2761,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level reasoning without closing**".to_string(),
             reasoning_format.clone(),
+            ShowReasoning::Auto,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2666,6 +2770,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level reasoning without closing**\n\n  ".to_string(),
             reasoning_format,
+            ShowReasoning::Auto,
         );
 
         let rendered = render_transcript(cell.as_ref());
@@ -2678,6 +2783,7 @@ mod tests {
         let cell = new_reasoning_summary_block(
             "**High level plan**\n\nWe should fix the bug next.".to_string(),
             reasoning_format,
+            ShowReasoning::Auto,
         );
 
         let rendered_display = render_lines(&cell.display_lines(80));
@@ -2687,6 +2793,32 @@ mod tests {
         assert_eq!(rendered_transcript, vec!["• We should fix the bug next."]);
     }
 
+    #[test]
+    fn reasoning_summary_block_follows_show_reasoning() {
+        let reasoning = "Detailed reasoning goes here.".to_string();
+
+        let always = new_reasoning_summary_block(
+            reasoning.clone(),
+            ReasoningSummaryFormat::None,
+            ShowReasoning::Always,
+        );
+        assert_eq!(
+            render_lines(&always.display_lines(80)),
+            vec!["• Detailed reasoning goes here."]
+        );
+
+        let never = new_reasoning_summary_block(
+            "**High level plan**\n\nWe should fix the bug next.".to_string(),
+            ReasoningSummaryFormat::Experimental,
+            ShowReasoning::Never,
+        );
+        assert!(never.display_lines(80).is_empty());
+        assert_eq!(
+            render_lines(&[never.collapsed_summary(80).expect("reasoning collapses")]),
+            vec!["• High level plan • 3 lines of reasoning"]
+        );
+    }
+
     #[test]
     fn deprecation_notice_renders_summary_with_details() {
         let cell = new_deprecation_notice(
@@ -0,0 +1,112 @@
+//! Small previews of attached images, drawn with half blocks under the user
+//! message in the transcript.
+//!
+//! Each cell shows two pixels: the upper half block's foreground is the top
+//! pixel and its background the bottom one, so square pixels come out
+//! roughly square in a terminal cell.
+
+use std::path::Path;
+
+use image::RgbaImage;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::terminal_palette::best_color;
+
+const MAX_COLUMNS: u32 = 24;
+const MAX_ROWS: u32 = 6;
+
+/// Pixels more transparent than this show the terminal background.
+const MIN_ALPHA: u8 = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImageThumbnail {
+    lines: Vec<Line<'static>>,
+}
+
+impl ImageThumbnail {
+    /// A thumbnail of the image at `path`, or `None` when it cannot be
+    /// decoded or the terminal shows no colors.
+    pub(crate) fn load(path: &Path) -> Option<Self> {
+        supports_color::on_cached(supports_color::Stream::Stdout)?;
+        let image = match image::open(path) {
+            Ok(image) => image,
+            Err(err) => {
+                tracing::warn!("failed to decode {} for a thumbnail: {err}", path.display());
+                return None;
+            }
+        };
+        let small = image.thumbnail(MAX_COLUMNS, MAX_ROWS * 2).to_rgba8();
+        Some(Self::from_pixels(&small, best_color))
+    }
+
+    fn from_pixels(image: &RgbaImage, color: impl Fn((u8, u8, u8)) -> Color) -> Self {
+        let pixel = |x: u32, y: u32| {
+            image
+                .get_pixel_checked(x, y)
+                .filter(|p| p.0[3] >= MIN_ALPHA)
+                .map(|p| color((p.0[0], p.0[1], p.0[2])))
+        };
+        let lines = (0..image.height())
+            .step_by(2)
+            .map(|y| {
+                let spans: Vec<Span<'static>> = (0..image.width())
+                    .map(|x| match (pixel(x, y), pixel(x, y + 1)) {
+                        (Some(top), Some(bottom)) => {
+                            Span::styled("▀", Style::default().fg(top).bg(bottom))
+                        }
+                        (Some(top), None) => Span::styled("▀", Style::default().fg(top)),
+                        (None, Some(bottom)) => Span::styled("▄", Style::default().fg(bottom)),
+                        (None, None) => Span::from(" "),
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub(crate) fn lines(&self) -> &[Line<'static>] {
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[allow(clippy::disallowed_methods)]
+    fn two_pixels_per_cell_with_transparency() {
+        let mut image = RgbaImage::new(2, 3);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 1, Rgba([0, 0, 255, 255]));
+        image.put_pixel(1, 1, Rgba([0, 255, 0, 255]));
+        image.put_pixel(0, 2, Rgba([9, 9, 9, 255]));
+        let rgb = |(r, g, b)| Color::Rgb(r, g, b);
+
+        let thumbnail = ImageThumbnail::from_pixels(&image, rgb);
+        assert_eq!(
+            thumbnail.lines(),
+            &[
+                Line::from(vec![
+                    Span::styled(
+                        "▀",
+                        Style::default()
+                            .fg(Color::Rgb(255, 0, 0))
+                            .bg(Color::Rgb(0, 0, 255)),
+                    ),
+                    Span::styled("▄", Style::default().fg(Color::Rgb(0, 255, 0))),
+                ]),
+                Line::from(vec![
+                    Span::styled("▀", Style::default().fg(Color::Rgb(9, 9, 9))),
+                    Span::from(" "),
+                ]),
+            ]
+        );
+    }
+}
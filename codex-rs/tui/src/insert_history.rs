@@ -41,6 +41,7 @@ where
     // Pre-wrap lines using word-aware wrapping so terminal scrollback sees the same
     // formatting as the TUI. This avoids character-level hard wrapping by the terminal.
     let wrapped = word_wrap_lines_borrowed(&lines, area.width.max(1) as usize);
+    let theme = crate::theme::current();
     let wrapped_lines = wrapped.len() as u16;
     let cursor_top = if area.bottom() < screen_size.height {
         // If the viewport is not at the bottom of the screen, scroll it down to make room.
@@ -99,11 +100,11 @@ where
             SetColors(Colors::new(
                 line.style
                     .fg
-                    .map(std::convert::Into::into)
+                    .map(|color| theme.remap(color).into())
                     .unwrap_or(CColor::Reset),
                 line.style
                     .bg
-                    .map(std::convert::Into::into)
+                    .map(|color| theme.remap(color).into())
                     .unwrap_or(CColor::Reset)
             ))
         )?;
@@ -245,6 +246,7 @@ fn write_spans<'a, I>(mut writer: &mut impl Write, content: I) -> io::Result<()>
 where
     I: IntoIterator<Item = &'a Span<'a>>,
 {
+    let theme = crate::theme::current();
     let mut fg = Color::Reset;
     let mut bg = Color::Reset;
     let mut last_modifier = Modifier::empty();
@@ -265,13 +267,19 @@ where
         if next_fg != fg || next_bg != bg {
             queue!(
                 writer,
-                SetColors(Colors::new(next_fg.into(), next_bg.into()))
+                SetColors(Colors::new(
+                    theme.remap(next_fg).into(),
+                    theme.remap(next_bg).into()
+                ))
             )?;
             fg = next_fg;
             bg = next_bg;
         }
 
-        queue!(writer, Print(span.content.clone()))?;
+        match crate::file_citation::linkify(&span.content) {
+            Some(linked) => queue!(writer, Print(linked))?,
+            None => queue!(writer, Print(span.content.clone()))?,
+        }
     }
 
     queue!(
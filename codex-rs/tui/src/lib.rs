@@ -19,9 +19,11 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::trust_store::has_project_files;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
 use codex_core::protocol::AskForApproval;
+use codex_core::secrets::RedactingWriter;
 use codex_protocol::config_types::SandboxMode;
 use std::fs::OpenOptions;
 use std::path::PathBuf;
@@ -29,8 +31,10 @@ use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
 
+mod accessibility;
 mod additional_dirs;
 mod app;
 mod app_backtrack;
@@ -40,18 +44,26 @@ mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
 mod cli;
+mod clipboard_copy;
 mod clipboard_paste;
 mod color;
+mod composer_draft;
 pub mod custom_terminal;
 mod diff_render;
+mod diff_review;
 mod exec_cell;
 mod exec_command;
+mod file_attachment;
+mod file_citation;
 mod file_search;
 mod frames;
 mod get_git_diff;
+mod git_status_line;
 mod history_cell;
+mod image_thumbnail;
 pub mod insert_history;
 mod key_hint;
+mod live_reasoning;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -61,11 +73,14 @@ mod notifications;
 pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
+mod plan_pane;
 pub mod public_widgets;
 mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod session_share;
+mod session_tabs;
 mod shimmer;
 mod slash_command;
 mod status;
@@ -74,7 +89,9 @@ mod streaming;
 mod style;
 mod terminal_palette;
 mod text_formatting;
+mod theme;
 mod tooltips;
+mod transcript_search;
 mod tui;
 mod ui_consts;
 pub mod update_action;
@@ -87,7 +104,6 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
-use crate::onboarding::TrustDirectorySelection;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
@@ -95,6 +111,8 @@ pub use cli::Cli;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use slash_command::SlashCommand;
+pub use slash_command::built_in_slash_commands;
 use std::io::Write as _;
 
 // (tests access modules directly within the crate)
@@ -132,7 +150,7 @@ pub async fn run_main(
     let raw_overrides = cli.config_overrides.raw_overrides.clone();
     // `oss` model provider.
     let overrides_cli = codex_common::CliConfigOverrides { raw_overrides };
-    let cli_kv_overrides = match overrides_cli.parse_overrides() {
+    let mut cli_kv_overrides = match overrides_cli.parse_overrides() {
         // Parse `-c` overrides from the CLI.
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
@@ -142,6 +160,19 @@ pub async fn run_main(
         }
     };
 
+    // Servers from `--mcp` join `mcp_servers` like `-c` overrides, so they
+    // last for this run only.
+    for spec in &cli.mcp_servers {
+        match codex_core::mcp::session_mcp_server_overrides(spec) {
+            Ok(overrides) => cli_kv_overrides.extend(overrides),
+            #[allow(clippy::print_stderr)]
+            Err(e) => {
+                eprintln!("Error parsing --mcp: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     // we load config.toml here to determine project state.
     #[allow(clippy::print_stderr)]
     let codex_home = match find_codex_home() {
@@ -216,6 +247,7 @@ pub async fn run_main(
     };
 
     let config = load_config_or_exit(cli_kv_overrides.clone(), overrides.clone()).await;
+    codex_core::default_client::set_network_config(config.network.clone());
 
     if let Some(warning) = add_dir_warning_message(&cli.add_dir, &config.sandbox_policy) {
         #[allow(clippy::print_stderr)]
@@ -261,17 +293,18 @@ pub async fn run_main(
     };
 
     let file_layer = tracing_subscriber::fmt::layer()
-        .with_writer(non_blocking)
+        .with_writer(move || RedactingWriter::new(non_blocking.clone()))
         .with_target(false)
         .with_ansi(false)
         .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
         .with_filter(env_filter());
 
     let feedback = codex_feedback::CodexFeedback::new();
+    let feedback_writer = feedback.make_writer();
     let targets = Targets::new().with_default(tracing::Level::TRACE);
 
     let feedback_layer = tracing_subscriber::fmt::layer()
-        .with_writer(feedback.make_writer())
+        .with_writer(move || RedactingWriter::new(feedback_writer.make_writer()))
         .with_ansi(false)
         .with_target(false)
         .with_filter(targets);
@@ -348,6 +381,7 @@ async fn run_ratatui_app(
     terminal.clear()?;
 
     let mut tui = Tui::new(terminal);
+    tui.set_notification_method(initial_config.tui_notification_method);
 
     #[cfg(not(debug_assertions))]
     {
@@ -404,12 +438,9 @@ async fn run_ratatui_app(
                 update_action: None,
             });
         }
-        // if the user acknowledged windows or made an explicit decision ato trust the directory, reload the config accordingly
-        if onboarding_result
-            .directory_trust_decision
-            .map(|d| d == TrustDirectorySelection::Trust)
-            .unwrap_or(false)
-        {
+        // Reload after a trust decision: it changes the default policies and
+        // whether the project's .codex/ files are loaded.
+        if onboarding_result.directory_trust_decision.is_some() {
             load_config_or_exit(cli_kv_overrides, overrides).await
         } else {
             initial_config
@@ -418,8 +449,28 @@ async fn run_ratatui_app(
         initial_config
     };
 
-    // Determine resume behavior: explicit id, then resume last, then picker.
-    let resume_selection = if let Some(id_str) = cli.resume_session_id.as_deref() {
+    // Determine resume behavior: a session to attach to, explicit id, then
+    // resume last, then picker.
+    let resume_selection = if let Some(id_str) = cli.attach_session_id.as_deref() {
+        let socket_path = session_share::socket_path(&config.codex_home, id_str);
+        if !socket_path.exists() {
+            restore();
+            session_log::log_session_end();
+            let _ = tui.terminal.clear();
+            if let Err(err) = writeln!(
+                std::io::stdout(),
+                "No running session found with ID {id_str}. Only sessions open in a Codex TUI with tui.allow_attach on can be watched."
+            ) {
+                error!("Failed to write attach error message: {err}");
+            }
+            return Ok(AppExitInfo {
+                token_usage: codex_core::protocol::TokenUsage::default(),
+                conversation_id: None,
+                update_action: None,
+            });
+        }
+        resume_picker::ResumeSelection::Attach(socket_path)
+    } else if let Some(id_str) = cli.resume_session_id.as_deref() {
         match find_conversation_path_by_id_str(&config.codex_home, id_str).await? {
             Some(path) => resume_picker::ResumeSelection::Resume(path),
             None => {
@@ -563,12 +614,14 @@ fn should_show_trust_screen(config: &Config) -> bool {
         // If the experimental sandbox is not enabled, Native Windows cannot enforce sandboxed write access; skip the trust prompt entirely.
         return false;
     }
-    if config.did_user_set_custom_approval_policy_or_sandbox_mode {
-        // Respect explicit approval/sandbox overrides made by the user.
+    if config.active_project.trust_level.is_some() {
+        // A trust decision has already been made.
         return false;
     }
-    // otherwise, show only if no trust decision has been made
-    config.active_project.trust_level.is_none()
+    // Explicit approval/sandbox overrides make the prompt unnecessary, unless
+    // the project has .codex/ files that should not load without a decision.
+    !config.did_user_set_custom_approval_policy_or_sandbox_mode
+        || has_project_files(&config.codex_home, &config.cwd)
 }
 
 fn should_show_onboarding(
@@ -677,4 +730,33 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    #[serial]
+    fn project_files_prompt_for_trust_despite_custom_policy() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        std::fs::create_dir(project.path().join(".codex"))?;
+        std::fs::write(
+            project.path().join(".codex/config.toml"),
+            "model = \"o3\"\n",
+        )?;
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides {
+                cwd: Some(project.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        config.did_user_set_custom_approval_policy_or_sandbox_mode = true;
+        config.active_project = ProjectConfig { trust_level: None };
+        config.set_windows_sandbox_globally(true);
+
+        assert!(should_show_trust_screen(&config));
+
+        std::fs::remove_dir_all(project.path().join(".codex"))?;
+        assert!(!should_show_trust_screen(&config));
+        Ok(())
+    }
 }
@@ -0,0 +1,124 @@
+//! The model's reasoning while it streams, shown dim above the composer.
+//! Collapsed it is a single line with the latest thought; expanded it shows
+//! the tail of the reasoning so far. Alt+T switches between the two, and
+//! `tui.show_reasoning` picks the starting state.
+
+use codex_core::config::types::ShowReasoning;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::history_cell::HistoryCell;
+use crate::history_cell::ReasoningSummaryCell;
+use crate::render::renderable::Renderable;
+
+/// Most lines the expanded view shows; older reasoning scrolls off the top.
+const LIVE_REASONING_LINES: usize = 6;
+
+pub(crate) struct LiveReasoning {
+    text: String,
+    expanded: bool,
+}
+
+impl LiveReasoning {
+    /// The view for `text`, or `None` when there is nothing to show: no
+    /// reasoning yet, or `never` with the view collapsed. Collapsed, bold
+    /// section headers don't count since the status indicator already shows
+    /// them.
+    pub(crate) fn new(text: String, mode: ShowReasoning, expanded: bool) -> Option<Self> {
+        if text.trim().is_empty() || (mode == ShowReasoning::Never && !expanded) {
+            return None;
+        }
+        if !expanded && latest_thought(&text).is_none() {
+            return None;
+        }
+        Some(Self { text, expanded })
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        if !self.expanded {
+            let latest = latest_thought(&self.text).unwrap_or_default().to_string();
+            return vec![
+                vec![
+                    "• ".dim(),
+                    "Thinking ".dim(),
+                    "(alt+t to expand) ".dim(),
+                    latest.dim().italic(),
+                ]
+                .into(),
+            ];
+        }
+        let cell = ReasoningSummaryCell::new(String::new(), self.text.clone(), false);
+        let lines = cell.display_lines(width);
+        let skip = lines.len().saturating_sub(LIVE_REASONING_LINES);
+        lines.into_iter().skip(skip).collect()
+    }
+}
+
+/// The last line of reasoning that isn't blank or a bold section header.
+fn latest_thought(text: &str) -> Option<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !(line.len() > 4 && line.starts_with("**") && line.ends_with("**")))
+        .map(|line| line.trim_matches('*').trim())
+        .rfind(|line| !line.is_empty())
+}
+
+impl Renderable for LiveReasoning {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        u16::try_from(self.lines(width).len()).unwrap_or(u16::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rendered(view: &LiveReasoning, width: u16) -> Vec<String> {
+        view.lines(width)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collapsed_shows_the_latest_thought_and_expanded_the_tail() {
+        let text = (1..=8)
+            .map(|n| format!("thought {n}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let collapsed = LiveReasoning::new(text.clone(), ShowReasoning::Auto, false)
+            .expect("auto shows a line");
+        assert_eq!(
+            rendered(&collapsed, 80),
+            vec!["• Thinking (alt+t to expand) thought 8"]
+        );
+
+        let expanded = LiveReasoning::new(text.clone(), ShowReasoning::Never, true)
+            .expect("expanding overrides never");
+        let lines = rendered(&expanded, 80);
+        assert_eq!(lines.len(), LIVE_REASONING_LINES);
+        assert!(lines.iter().any(|line| line.trim() == "thought 8"));
+        assert!(!lines.iter().any(|line| line.contains("thought 1")));
+
+        assert!(LiveReasoning::new(text, ShowReasoning::Never, false).is_none());
+        assert!(LiveReasoning::new("  ".to_string(), ShowReasoning::Always, true).is_none());
+        assert!(
+            LiveReasoning::new("**Planning**".to_string(), ShowReasoning::Auto, false).is_none()
+        );
+    }
+}
@@ -1,3 +1,7 @@
+use crate::render::code_highlight::CodeLanguage;
+use crate::render::code_highlight::detect_language;
+use crate::render::code_highlight::highlight_code_to_lines;
+use crate::render::code_highlight::syntax_highlighting_enabled;
 use crate::render::line_utils::line_to_static;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
@@ -76,10 +80,14 @@ pub fn render_markdown_text(input: &str) -> Text<'static> {
 }
 
 pub(crate) fn render_markdown_text_with_width(input: &str, width: Option<usize>) -> Text<'static> {
+    render_markdown(input, width, syntax_highlighting_enabled())
+}
+
+fn render_markdown(input: &str, width: Option<usize>, highlight_code: bool) -> Text<'static> {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_STRIKETHROUGH);
     let parser = Parser::new_ext(input, options);
-    let mut w = Writer::new(parser, width);
+    let mut w = Writer::new(parser, width, highlight_code);
     w.run();
     w.text
 }
@@ -99,6 +107,11 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    highlight_code: bool,
+    /// Language of the fenced block being read, when it is highlighted. Its
+    /// text is buffered in `code_block_source` until the fence closes.
+    code_block_language: Option<CodeLanguage>,
+    code_block_source: String,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -111,7 +124,7 @@ impl<'a, I> Writer<'a, I>
 where
     I: Iterator<Item = Event<'a>>,
 {
-    fn new(iter: I, wrap_width: Option<usize>) -> Self {
+    fn new(iter: I, wrap_width: Option<usize>, highlight_code: bool) -> Self {
         Self {
             iter,
             text: Text::default(),
@@ -124,6 +137,9 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            highlight_code,
+            code_block_language: None,
+            code_block_source: String::new(),
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -274,6 +290,19 @@ where
     }
 
     fn text(&mut self, text: CowStr<'a>) {
+        if self.code_block_language.is_some() {
+            self.code_block_source.push_str(&text);
+            return;
+        }
+        let style = self.inline_styles.last().copied().unwrap_or_default();
+        let lines = text
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), style)))
+            .collect();
+        self.push_text_lines(lines);
+    }
+
+    fn push_text_lines(&mut self, lines: Vec<Line<'static>>) {
         if self.pending_marker_line {
             self.push_line(Line::default());
         }
@@ -294,7 +323,7 @@ where
                 self.push_line(Line::default());
             }
         }
-        for (i, line) in text.lines().enumerate() {
+        for (i, line) in lines.into_iter().enumerate() {
             if self.needs_newline {
                 self.push_line(Line::default());
                 self.needs_newline = false;
@@ -302,12 +331,12 @@ where
             if i > 0 {
                 self.push_line(Line::default());
             }
-            let content = line.to_string();
-            let span = Span::styled(
-                content,
-                self.inline_styles.last().copied().unwrap_or_default(),
-            );
-            self.push_span(span);
+            if line.spans.is_empty() {
+                self.push_span(Span::raw(""));
+            }
+            for span in line.spans {
+                self.push_span(span);
+            }
         }
         self.needs_newline = false;
     }
@@ -394,8 +423,11 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
+        self.code_block_language = lang
+            .filter(|_| self.highlight_code)
+            .and_then(|lang| detect_language(&lang));
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
@@ -409,6 +441,13 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some(language) = self.code_block_language.take() {
+            let source = std::mem::take(&mut self.code_block_source);
+            let code = source.strip_suffix('\n').unwrap_or(&source);
+            if !code.is_empty() {
+                self.push_text_lines(highlight_code_to_lines(language, code));
+            }
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
@@ -653,7 +653,25 @@ fn link() {
 
 #[test]
 fn code_block_unhighlighted() {
-    let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+    let text = super::render_markdown("```rust\nfn main() {}\n```\n", None, false);
+    let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn code_block_highlighted_by_fence_language() {
+    let text = render_markdown_text("```rust\nfn main() {}\n\n// done\n```\n");
+    let expected = Text::from_iter([
+        Line::from_iter(["".into(), "fn".magenta(), " main() {}".into()]),
+        Line::from_iter(["", ""]),
+        Line::from_iter(["".into(), "// done".dim()]),
+    ]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn code_block_with_unknown_language_is_plain() {
+    let text = render_markdown_text("```text\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
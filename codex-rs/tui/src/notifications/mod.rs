@@ -1,10 +1,13 @@
+mod native;
 mod osc9;
 mod windows_toast;
 
 use std::env;
 use std::io;
 
+use codex_core::config::types::NotificationMethod;
 use codex_core::env::is_wsl;
+use native::NativeBackend;
 use osc9::Osc9Backend;
 use windows_toast::WindowsToastBackend;
 
@@ -12,12 +15,14 @@ use windows_toast::WindowsToastBackend;
 pub enum NotificationBackendKind {
     Osc9,
     WindowsToast,
+    Native,
 }
 
 #[derive(Debug)]
 pub enum DesktopNotificationBackend {
     Osc9(Osc9Backend),
     WindowsToast(WindowsToastBackend),
+    Native(NativeBackend),
 }
 
 impl DesktopNotificationBackend {
@@ -29,10 +34,20 @@ impl DesktopNotificationBackend {
         Self::WindowsToast(WindowsToastBackend::default())
     }
 
+    /// The system's notifier; toasts on Windows and under WSL.
+    pub fn native() -> Self {
+        if cfg!(windows) || is_wsl() {
+            Self::windows_toast()
+        } else {
+            Self::Native(NativeBackend)
+        }
+    }
+
     pub fn kind(&self) -> NotificationBackendKind {
         match self {
             DesktopNotificationBackend::Osc9(_) => NotificationBackendKind::Osc9,
             DesktopNotificationBackend::WindowsToast(_) => NotificationBackendKind::WindowsToast,
+            DesktopNotificationBackend::Native(_) => NotificationBackendKind::Native,
         }
     }
 
@@ -40,21 +55,39 @@ impl DesktopNotificationBackend {
         match self {
             DesktopNotificationBackend::Osc9(backend) => backend.notify(message),
             DesktopNotificationBackend::WindowsToast(backend) => backend.notify(message),
+            DesktopNotificationBackend::Native(backend) => backend.notify(message),
         }
     }
 }
 
+pub fn backend_for(method: NotificationMethod) -> DesktopNotificationBackend {
+    match method {
+        NotificationMethod::Auto => detect_backend(),
+        NotificationMethod::Osc9 => DesktopNotificationBackend::osc9(),
+        NotificationMethod::Native => DesktopNotificationBackend::native(),
+    }
+}
+
 pub fn detect_backend() -> DesktopNotificationBackend {
     if should_use_windows_toasts() {
         tracing::info!(
             "Windows Terminal session detected under WSL; using Windows toast notifications"
         );
         DesktopNotificationBackend::windows_toast()
+    } else if terminal_ignores_osc9() {
+        tracing::info!("terminal does not show OSC 9 notifications; using the system notifier");
+        DesktopNotificationBackend::native()
     } else {
         DesktopNotificationBackend::osc9()
     }
 }
 
+/// Terminals that drop OSC 9 notifications without showing anything.
+fn terminal_ignores_osc9() -> bool {
+    env::var("TERM_PROGRAM")
+        .is_ok_and(|program| matches!(program.as_str(), "Apple_Terminal" | "vscode"))
+}
+
 fn should_use_windows_toasts() -> bool {
     is_wsl() && env::var_os("WT_SESSION").is_some()
 }
@@ -62,7 +95,9 @@ fn should_use_windows_toasts() -> bool {
 #[cfg(test)]
 mod tests {
     use super::NotificationBackendKind;
+    use super::backend_for;
     use super::detect_backend;
+    use codex_core::config::types::NotificationMethod;
     use serial_test::serial;
     use std::ffi::OsString;
 
@@ -105,14 +140,30 @@ mod tests {
     fn defaults_to_osc9_outside_wsl() {
         let _wsl_guard = EnvVarGuard::remove("WSL_DISTRO_NAME");
         let _wt_guard = EnvVarGuard::remove("WT_SESSION");
+        let _term_guard = EnvVarGuard::remove("TERM_PROGRAM");
         assert_eq!(detect_backend().kind(), NotificationBackendKind::Osc9);
     }
 
+    #[cfg(not(windows))]
+    #[test]
+    #[serial]
+    fn uses_the_system_notifier_in_terminals_without_osc9() {
+        let _wsl_guard = EnvVarGuard::remove("WSL_DISTRO_NAME");
+        let _wt_guard = EnvVarGuard::remove("WT_SESSION");
+        let _term_guard = EnvVarGuard::set("TERM_PROGRAM", "Apple_Terminal");
+        assert_eq!(detect_backend().kind(), NotificationBackendKind::Native);
+        assert_eq!(
+            backend_for(NotificationMethod::Osc9).kind(),
+            NotificationBackendKind::Osc9
+        );
+    }
+
     #[test]
     #[serial]
     fn waits_for_windows_terminal() {
         let _wsl_guard = EnvVarGuard::set("WSL_DISTRO_NAME", "Ubuntu");
         let _wt_guard = EnvVarGuard::remove("WT_SESSION");
+        let _term_guard = EnvVarGuard::remove("TERM_PROGRAM");
         assert_eq!(detect_backend().kind(), NotificationBackendKind::Osc9);
     }
 
@@ -0,0 +1,83 @@
+use std::io;
+use std::process::Command;
+use std::process::Stdio;
+
+const APP_NAME: &str = "Codex";
+
+/// Posts notifications through the system's notifier: `osascript` on macOS
+/// and `notify-send` elsewhere.
+#[derive(Debug, Default)]
+pub struct NativeBackend;
+
+impl NativeBackend {
+    pub fn notify(&mut self, message: &str) -> io::Result<()> {
+        let mut command = notifier_command(message);
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        let status = command.status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "{} exited with status {status}",
+                command.get_program().to_string_lossy()
+            )))
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn notifier_command(message: &str) -> Command {
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(format!(
+        "display notification {} with title {}",
+        applescript_string(message),
+        applescript_string(APP_NAME)
+    ));
+    command
+}
+
+#[cfg(not(target_os = "macos"))]
+fn notifier_command(message: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command
+        .arg("--app-name")
+        .arg(APP_NAME)
+        .arg(APP_NAME)
+        .arg(message);
+    command
+}
+
+/// Quotes `value` as an AppleScript string literal.
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn applescript_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' | '\r' => quoted.push(' '),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::applescript_string;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn quotes_applescript_strings() {
+        assert_eq!(applescript_string("done"), "\"done\"");
+        assert_eq!(
+            applescript_string("say \"hi\"\\now\nplease"),
+            "\"say \\\"hi\\\"\\\\now please\""
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
-use codex_core::config::set_project_trust_level;
-use codex_core::git_info::resolve_root_git_project_for_trust;
+use codex_core::config::trust_store::ProjectTrust;
+use codex_core::config::trust_store::record_project_trust;
+use codex_core::config::trust_store::trust_key;
 use codex_protocol::config_types::TrustLevel;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -28,6 +29,9 @@ pub(crate) struct TrustDirectoryWidget {
     pub codex_home: PathBuf,
     pub cwd: PathBuf,
     pub is_git_repo: bool,
+    /// The project has `.codex/config.toml` or `.codex/steering`, which load
+    /// only if the folder is trusted.
+    pub has_project_files: bool,
     pub selection: Option<TrustDirectorySelection>,
     pub highlighted: TrustDirectorySelection,
     pub error: Option<String>,
@@ -63,6 +67,17 @@ impl WidgetRef for &TrustDirectoryWidget {
         );
         column.push("");
 
+        if self.has_project_files {
+            column.push(
+                Paragraph::new(
+                    "This folder has project settings in .codex/. Codex loads them only if you trust this folder.",
+                )
+                .wrap(Wrap { trim: true })
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.push("");
+        }
+
         let mut options: Vec<(&str, TrustDirectorySelection)> = Vec::new();
         if self.is_git_repo {
             options.push((
@@ -152,9 +167,9 @@ impl StepStateProvider for TrustDirectoryWidget {
 
 impl TrustDirectoryWidget {
     fn handle_trust(&mut self) {
-        let target =
-            resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
-        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Trusted) {
+        let target = trust_key(&self.cwd);
+        let trust = ProjectTrust::new(TrustLevel::Trusted);
+        if let Err(e) = record_project_trust(&self.codex_home, &self.cwd, trust) {
             tracing::error!("Failed to set project trusted: {e:?}");
             self.error = Some(format!("Failed to set trust for {}: {e}", target.display()));
         }
@@ -164,9 +179,9 @@ impl TrustDirectoryWidget {
 
     fn handle_dont_trust(&mut self) {
         self.highlighted = TrustDirectorySelection::DontTrust;
-        let target =
-            resolve_root_git_project_for_trust(&self.cwd).unwrap_or_else(|| self.cwd.clone());
-        if let Err(e) = set_project_trust_level(&self.codex_home, &target, TrustLevel::Untrusted) {
+        let target = trust_key(&self.cwd);
+        let trust = ProjectTrust::new(TrustLevel::Untrusted);
+        if let Err(e) = record_project_trust(&self.codex_home, &self.cwd, trust) {
             tracing::error!("Failed to set project untrusted: {e:?}");
             self.error = Some(format!(
                 "Failed to set untrusted for {}: {e}",
@@ -199,6 +214,7 @@ mod tests {
             codex_home: codex_home.path().to_path_buf(),
             cwd: PathBuf::from("."),
             is_git_repo: false,
+            has_project_files: false,
             selection: None,
             highlighted: TrustDirectorySelection::DontTrust,
             error: None,
@@ -223,6 +239,7 @@ mod tests {
             codex_home: codex_home.path().to_path_buf(),
             cwd: PathBuf::from("/workspace/project"),
             is_git_repo: true,
+            has_project_files: false,
             selection: None,
             highlighted: TrustDirectorySelection::Trust,
             error: None,
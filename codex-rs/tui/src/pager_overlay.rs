@@ -1,7 +1,15 @@
+use std::cell::Ref;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::Result;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::diff_review::DiffReview;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -10,12 +18,22 @@ use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::transcript_search::TranscriptSearch;
+use crate::transcript_search::current_match_style;
+use crate::transcript_search::highlight_line;
+use crate::transcript_search::match_style;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_core::protocol::FileChange;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
+use ratatui::layout::Position;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -31,11 +49,12 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    DiffReview(DiffReviewOverlay),
 }
 
 impl Overlay {
-    pub(crate) fn new_transcript(cells: Vec<Arc<dyn HistoryCell>>) -> Self {
-        Self::Transcript(TranscriptOverlay::new(cells))
+    pub(crate) fn new_transcript(cells: Vec<Arc<dyn HistoryCell>>, collapse_after: usize) -> Self {
+        Self::Transcript(TranscriptOverlay::new(cells, collapse_after))
     }
 
     pub(crate) fn new_static_with_lines(lines: Vec<Line<'static>>, title: String) -> Self {
@@ -49,10 +68,20 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_diff_review(
+        id: String,
+        cwd: PathBuf,
+        changes: &HashMap<PathBuf, FileChange>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        Self::DiffReview(DiffReviewOverlay::new(id, cwd, changes, app_event_tx))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::DiffReview(o) => o.handle_event(tui, event),
         }
     }
 
@@ -60,6 +89,18 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::DiffReview(o) => o.is_done(),
+        }
+    }
+
+    /// Whether the overlay itself handles Esc and Enter rather than leaving
+    /// them to backtracking: the transcript while it is searching, and the
+    /// patch review always.
+    pub(crate) fn owns_esc_and_enter(&self) -> bool {
+        match self {
+            Overlay::Transcript(o) => o.is_searching(),
+            Overlay::Static(_) => false,
+            Overlay::DiffReview(_) => true,
         }
     }
 }
@@ -74,6 +115,8 @@ const KEY_SPACE: KeyBinding = key_hint::plain(KeyCode::Char(' '));
 const KEY_SHIFT_SPACE: KeyBinding = key_hint::shift(KeyCode::Char(' '));
 const KEY_HOME: KeyBinding = key_hint::plain(KeyCode::Home);
 const KEY_END: KeyBinding = key_hint::plain(KeyCode::End);
+const KEY_G: KeyBinding = key_hint::plain(KeyCode::Char('g'));
+const KEY_SHIFT_G: KeyBinding = key_hint::shift(KeyCode::Char('G'));
 const KEY_CTRL_F: KeyBinding = key_hint::ctrl(KeyCode::Char('f'));
 const KEY_CTRL_D: KeyBinding = key_hint::ctrl(KeyCode::Char('d'));
 const KEY_CTRL_B: KeyBinding = key_hint::ctrl(KeyCode::Char('b'));
@@ -83,6 +126,20 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('N'));
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
+const KEY_ALT_C: KeyBinding = key_hint::alt(KeyCode::Char('c'));
+const KEY_ALT_R: KeyBinding = key_hint::alt(KeyCode::Char('r'));
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_SHIFT_TAB: KeyBinding = key_hint::shift(KeyCode::Tab);
+const KEY_Y: KeyBinding = key_hint::plain(KeyCode::Char('y'));
+const KEY_SHIFT_Y: KeyBinding = key_hint::shift(KeyCode::Char('Y'));
+const KEY_S: KeyBinding = key_hint::plain(KeyCode::Char('s'));
+
+/// Rows one notch of the mouse wheel scrolls.
+const WHEEL_SCROLL_LINES: usize = 3;
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -113,33 +170,86 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
 }
 
 /// Generic widget for rendering a pager view.
+///
+/// Only the renderables in view are drawn: the top row of each one is kept
+/// in a [`RowIndex`], so finding the first visible renderable is a binary
+/// search however long the content is.
 struct PagerView {
     renderables: Vec<Box<dyn Renderable>>,
+    row_index: RefCell<RowIndex>,
     scroll_offset: usize,
     title: String,
     last_content_height: Option<usize>,
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// `g` was pressed; a second `g` jumps to the top.
+    pending_g: bool,
 }
 
 impl PagerView {
     fn new(renderables: Vec<Box<dyn Renderable>>, title: String, scroll_offset: usize) -> Self {
         Self {
             renderables,
+            row_index: RefCell::new(RowIndex::default()),
             scroll_offset,
             title,
             last_content_height: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            pending_g: false,
+        }
+    }
+
+    /// Replaces the content; row positions are measured again on the next
+    /// render.
+    fn set_renderables(&mut self, renderables: Vec<Box<dyn Renderable>>) {
+        self.renderables = renderables;
+        *self.row_index.get_mut() = RowIndex::default();
+    }
+
+    /// Adds a renderable below the others, measuring only the new one.
+    fn push_renderable(&mut self, renderable: Box<dyn Renderable>) {
+        self.renderables.push(renderable);
+    }
+
+    /// Top row of each renderable at `width`, followed by the content
+    /// height.
+    fn row_tops(&self, width: u16) -> Ref<'_, [usize]> {
+        {
+            let mut index = self.row_index.borrow_mut();
+            if index.width != Some(width) || index.tops.len() > self.renderables.len() + 1 {
+                *index = RowIndex {
+                    width: Some(width),
+                    tops: vec![0],
+                };
+            }
+            while index.tops.len() <= self.renderables.len() {
+                let last = index.tops.len() - 1;
+                let bottom =
+                    index.tops[last] + self.renderables[last].desired_height(width) as usize;
+                index.tops.push(bottom);
+            }
         }
+        Ref::map(self.row_index.borrow(), |index| index.tops.as_slice())
     }
 
     fn content_height(&self, width: u16) -> usize {
-        self.renderables
-            .iter()
-            .map(|c| c.desired_height(width) as usize)
-            .sum()
+        self.row_tops(width).last().copied().unwrap_or(0)
+    }
+
+    /// Index of the renderable drawn at `row`.
+    fn chunk_at_row(&self, row: usize, width: u16) -> usize {
+        let tops = self.row_tops(width);
+        tops[..tops.len() - 1]
+            .partition_point(|&top| top <= row)
+            .saturating_sub(1)
+    }
+
+    /// Row where renderable `idx` starts.
+    fn chunk_top(&self, idx: usize, width: u16) -> usize {
+        let tops = self.row_tops(width);
+        tops[idx.min(tops.len() - 1)]
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
@@ -172,9 +282,10 @@ impl PagerView {
     }
 
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
-        let mut y = -(self.scroll_offset as isize);
+        let first = self.chunk_at_row(self.scroll_offset, area.width);
+        let mut y = self.chunk_top(first, area.width) as isize - self.scroll_offset as isize;
         let mut drawn_bottom = area.y;
-        for renderable in &self.renderables {
+        for renderable in self.renderables.iter().skip(first) {
             let top = y;
             let height = renderable.desired_height(area.width) as isize;
             y += height;
@@ -240,6 +351,7 @@ impl PagerView {
     }
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        let pending_g = std::mem::take(&mut self.pending_g);
         match key_event {
             e if KEY_UP.is_press(e) || KEY_K.is_press(e) => {
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
@@ -268,10 +380,17 @@ impl PagerView {
                 let half_page = (area.height as usize).saturating_add(1) / 2;
                 self.scroll_offset = self.scroll_offset.saturating_sub(half_page);
             }
-            e if KEY_HOME.is_press(e) => {
+            e if KEY_HOME.is_press(e) || (pending_g && KEY_G.is_press(e)) => {
                 self.scroll_offset = 0;
             }
-            e if KEY_END.is_press(e) => {
+            e if KEY_G.is_press(e) => {
+                self.pending_g = true;
+                return Ok(());
+            }
+            e if KEY_END.is_press(e)
+                || KEY_SHIFT_G.is_press(e)
+                || key_hint::plain(KeyCode::Char('G')).is_press(e) =>
+            {
                 self.scroll_offset = usize::MAX;
             }
             _ => {
@@ -283,6 +402,20 @@ impl PagerView {
         Ok(())
     }
 
+    /// Scrolls on the mouse wheel. Returns false for other mouse events.
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> bool {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(WHEEL_SCROLL_LINES);
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll_offset = self.scroll_offset.saturating_add(WHEEL_SCROLL_LINES);
+            }
+            _ => return false,
+        }
+        true
+    }
+
     /// Returns the height of one page in content rows.
     ///
     /// Prefers the last rendered content height (excluding header/footer chrome);
@@ -335,13 +468,8 @@ impl PagerView {
         if area.height == 0 || idx >= self.renderables.len() {
             return;
         }
-        let first = self
-            .renderables
-            .iter()
-            .take(idx)
-            .map(|r| r.desired_height(area.width) as usize)
-            .sum();
-        let last = first + self.renderables[idx].desired_height(area.width) as usize;
+        let first = self.chunk_top(idx, area.width);
+        let last = self.chunk_top(idx + 1, area.width);
         let current_top = self.scroll_offset;
         let current_bottom = current_top.saturating_add(area.height.saturating_sub(1) as usize);
         if first < current_top {
@@ -352,6 +480,14 @@ impl PagerView {
     }
 }
 
+/// Top rows of a [`PagerView`]'s renderables at one width. Appending
+/// renderables extends it; anything else resets it.
+#[derive(Debug, Default)]
+struct RowIndex {
+    width: Option<u16>,
+    tops: Vec<usize>,
+}
+
 /// A renderable that caches its desired height.
 struct CachedRenderable {
     renderable: Box<dyn Renderable>,
@@ -383,84 +519,254 @@ impl Renderable for CachedRenderable {
     }
 }
 
+/// Which tool-call cells of the transcript show only their summary.
+#[derive(Debug, Default)]
+struct CollapseState {
+    /// Cells longer than this many lines start collapsed; `0` never
+    /// collapses.
+    after: usize,
+    /// Cells Enter flipped from how they start.
+    toggled: HashSet<usize>,
+    /// The tool-call cell Enter toggles.
+    selected: Option<usize>,
+}
+
+impl CollapseState {
+    fn toggle(&mut self, index: usize) {
+        if !self.toggled.remove(&index) {
+            self.toggled.insert(index);
+        }
+    }
+}
+
+/// The summary `cell` shows in place of its lines, or `None` when it is
+/// expanded. Cells longer than `collapse_after` lines start collapsed and
+/// `toggled` flips that.
+fn collapsed_summary(
+    cell: &dyn HistoryCell,
+    collapse_after: usize,
+    toggled: bool,
+    width: u16,
+) -> Option<Line<'static>> {
+    let summary = cell.collapsed_summary(width)?;
+    let long = collapse_after > 0 && cell.transcript_lines(width).len() > collapse_after;
+    (long != toggled).then_some(summary)
+}
+
+fn is_tool_cell(cell: &dyn HistoryCell) -> bool {
+    cell.collapsed_summary(u16::MAX).is_some()
+}
+
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    /// Search matches to highlight: line index, byte range, and style.
+    highlights: Vec<(usize, usize, usize, Style)>,
+    collapse_after: usize,
+    toggled: bool,
+    /// Mark the first line as the cell Enter toggles.
+    selected: bool,
+}
+
+impl CellRenderable {
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        if let Some(summary) =
+            collapsed_summary(self.cell.as_ref(), self.collapse_after, self.toggled, width)
+        {
+            return vec![summary];
+        }
+        let lines = self.cell.transcript_lines(width);
+        if self.highlights.is_empty() {
+            return lines;
+        }
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let ranges: Vec<_> = self
+                    .highlights
+                    .iter()
+                    .filter(|(line_index, ..)| *line_index == index)
+                    .map(|&(_, start, end, style)| (start, end, style))
+                    .collect();
+                highlight_line(line, &ranges)
+            })
+            .collect()
+    }
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.lines(area.width);
+        if self.selected
+            && let Some(first) = lines.first_mut()
+        {
+            *first = first.clone().reversed();
+        }
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        self.cell.desired_transcript_height(width)
+        match collapsed_summary(self.cell.as_ref(), self.collapse_after, self.toggled, width) {
+            Some(summary) => Paragraph::new(summary)
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .try_into()
+                .unwrap_or(0),
+            None => self.cell.desired_transcript_height(width),
+        }
     }
 }
 
+/// How many of the latest cells the transcript lays out when it opens, and
+/// how many older ones it adds each time the view nears the top of those.
+const TRANSCRIPT_LOAD_CELLS: usize = 500;
+
 pub(crate) struct TranscriptOverlay {
     view: PagerView,
     cells: Vec<Arc<dyn HistoryCell>>,
+    /// The first cell with a renderable; `view.renderables[i]` draws
+    /// `cells[loaded_from + i]`. Older cells are not laid out until the
+    /// view scrolls up to them.
+    loaded_from: usize,
+    /// Whether any cell is a shell or patch cell Tab can select.
+    has_tool_cells: bool,
     highlight_cell: Option<usize>,
+    search: TranscriptSearch,
+    /// Width the search matches were found at; `None` when they need to be
+    /// found again.
+    search_width: Option<u16>,
+    /// Start the next search from the top of the view rather than from the
+    /// current match.
+    search_from_view: bool,
+    /// Scroll the current match into view on the next render.
+    scroll_to_match: bool,
+    collapse: CollapseState,
+    /// Where the last render drew the cells.
+    content_area: Rect,
     is_done: bool,
 }
 
 impl TranscriptOverlay {
-    pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
+    /// `collapse_after` is the line count past which shell and patch cells
+    /// start collapsed to their summary; `0` never collapses them.
+    pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>, collapse_after: usize) -> Self {
+        let search = TranscriptSearch::default();
+        let collapse = CollapseState {
+            after: collapse_after,
+            ..CollapseState::default()
+        };
+        let loaded_from = transcript_cells.len().saturating_sub(TRANSCRIPT_LOAD_CELLS);
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
+                Self::render_cells(&transcript_cells, loaded_from, None, &search, &collapse),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
             ),
+            has_tool_cells: transcript_cells
+                .iter()
+                .any(|cell| is_tool_cell(cell.as_ref())),
             cells: transcript_cells,
+            loaded_from,
             highlight_cell: None,
+            search,
+            search_width: None,
+            search_from_view: false,
+            scroll_to_match: false,
+            collapse,
+            content_area: Rect::default(),
             is_done: false,
         }
     }
 
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
+        from: usize,
         highlight_cell: Option<usize>,
+        search: &TranscriptSearch,
+        collapse: &CollapseState,
     ) -> Vec<Box<dyn Renderable>> {
         cells
             .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let mut v: Vec<Box<dyn Renderable>> = Vec::new();
-                let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: if highlight_cell == Some(i) {
-                            user_message_style().reversed()
-                        } else {
-                            user_message_style()
-                        },
-                    })) as Box<dyn Renderable>
+            .skip(from)
+            .map(|(i, c)| Self::cell_renderable(c, i, highlight_cell, search, collapse))
+            .collect()
+    }
+
+    fn cell_renderable(
+        c: &Arc<dyn HistoryCell>,
+        i: usize,
+        highlight_cell: Option<usize>,
+        search: &TranscriptSearch,
+        collapse: &CollapseState,
+    ) -> Box<dyn Renderable> {
+        let current = search.current();
+        let highlights = search
+            .matches()
+            .iter()
+            .filter(|m| m.cell == i)
+            .map(|m| {
+                let style = if Some(*m) == current {
+                    current_match_style()
                 } else {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: Style::default(),
-                    })) as Box<dyn Renderable>
+                    match_style()
                 };
-                if !c.is_stream_continuation() && i > 0 {
-                    cell_renderable = Box::new(InsetRenderable::new(
-                        cell_renderable,
-                        Insets::tlbr(1, 0, 0, 0),
-                    ));
-                }
-                v.push(cell_renderable);
-                v
+                (m.line, m.start, m.end, style)
             })
-            .collect()
+            .collect();
+        let cell_renderable = if c.as_any().is::<UserHistoryCell>() {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: if highlight_cell == Some(i) {
+                    user_message_style().reversed()
+                } else {
+                    user_message_style()
+                },
+                highlights,
+                collapse_after: 0,
+                toggled: false,
+                selected: false,
+            })) as Box<dyn Renderable>
+        } else {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: Style::default(),
+                highlights,
+                collapse_after: collapse.after,
+                toggled: collapse.toggled.contains(&i),
+                selected: collapse.selected == Some(i),
+            })) as Box<dyn Renderable>
+        };
+        if !c.is_stream_continuation() && i > 0 {
+            Box::new(InsetRenderable::new(
+                cell_renderable,
+                Insets::tlbr(1, 0, 0, 0),
+            ))
+        } else {
+            cell_renderable
+        }
     }
 
     pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
         let follow_bottom = self.view.is_scrolled_to_bottom();
+        self.has_tool_cells |= is_tool_cell(cell.as_ref());
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        if self.search.is_active() {
+            // The new cell may contain matches; find them on the next render.
+            self.search_width = None;
+            self.rebuild_renderables();
+        } else {
+            let index = self.cells.len() - 1;
+            self.view.push_renderable(Self::cell_renderable(
+                &self.cells[index],
+                index,
+                self.highlight_cell,
+                &self.search,
+                &self.collapse,
+            ));
+        }
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
         }
@@ -468,21 +774,331 @@ impl TranscriptOverlay {
 
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
         self.highlight_cell = cell;
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        if cell.is_some() {
+            self.collapse.selected = None;
+        }
+        self.rebuild_renderables();
         if let Some(idx) = self.highlight_cell {
-            self.view.scroll_chunk_into_view(idx);
+            self.scroll_cell_into_view(idx);
         }
     }
 
+    /// Whether the search prompt or a finished search is showing, in which
+    /// case Esc and Enter belong to the search rather than to backtracking.
+    pub(crate) fn is_searching(&self) -> bool {
+        self.search.is_active()
+    }
+
+    fn rebuild_renderables(&mut self) {
+        self.view.set_renderables(Self::render_cells(
+            &self.cells,
+            self.loaded_from,
+            self.highlight_cell,
+            &self.search,
+            &self.collapse,
+        ));
+    }
+
+    /// Lays out the cells from `cell` on, keeping the view on the rows it
+    /// showed.
+    fn load_cells_from(&mut self, cell: usize) {
+        if cell >= self.loaded_from {
+            return;
+        }
+        let added = self.loaded_from - cell;
+        self.loaded_from = cell;
+        self.rebuild_renderables();
+        if let Some(chunk) = self.view.pending_scroll_chunk.as_mut() {
+            *chunk += added;
+        }
+        if self.view.scroll_offset != usize::MAX && self.content_area.width > 0 {
+            let above = self.view.chunk_top(added, self.content_area.width);
+            self.view.scroll_offset = self.view.scroll_offset.saturating_add(above);
+        }
+    }
+
+    /// Loads older cells once the view is within two pages of the top of
+    /// the loaded ones. Jumping to the very top (Home, `gg`) loads them all.
+    fn load_older_cells(&mut self, content_area: Rect) {
+        if self.loaded_from == 0 {
+            return;
+        }
+        let page = content_area.height as usize;
+        let max_scroll = self
+            .view
+            .content_height(content_area.width)
+            .saturating_sub(page);
+        if self.view.scroll_offset == 0 {
+            self.load_cells_from(0);
+            self.view.scroll_offset = 0;
+        } else if self.view.scroll_offset.min(max_scroll) < 2 * page {
+            self.load_cells_from(self.loaded_from.saturating_sub(TRANSCRIPT_LOAD_CELLS));
+        }
+    }
+
+    fn scroll_cell_into_view(&mut self, cell: usize) {
+        self.load_cells_from(cell);
+        self.view
+            .scroll_chunk_into_view(cell.saturating_sub(self.loaded_from));
+    }
+
+    /// Handles Tab and Shift+Tab, which move between shell and patch cells,
+    /// and Enter, which expands or collapses the selected one. Returns false
+    /// for keys it leaves to the pager.
+    fn handle_collapse_key(&mut self, key_event: KeyEvent) -> bool {
+        match key_event {
+            e if KEY_TAB.is_press(e) => self.select_tool_cell(true),
+            e if KEY_SHIFT_TAB.is_press(e)
+                || key_hint::plain(KeyCode::BackTab).is_press(e)
+                || key_hint::shift(KeyCode::BackTab).is_press(e) =>
+            {
+                self.select_tool_cell(false)
+            }
+            e if KEY_ENTER.is_press(e) => match self.collapse.selected {
+                Some(selected) => self.collapse.toggle(selected),
+                None => return false,
+            },
+            _ => return false,
+        }
+        // Match rows move when a cell above them changes height.
+        self.rebuild_renderables();
+        true
+    }
+
+    /// Selects the next (or previous) shell or patch cell. With none
+    /// selected, selects the first one from the top of the view, or the last
+    /// one above it.
+    fn select_tool_cell(&mut self, forward: bool) {
+        let cells = &self.cells;
+        let is_tool = |i: &usize| is_tool_cell(cells[*i].as_ref());
+        let next = match self.collapse.selected {
+            Some(selected) if forward => (selected + 1..cells.len()).find(is_tool),
+            Some(selected) => (0..selected).rev().find(is_tool),
+            None => {
+                let top = self.cell_at_row(self.view.scroll_offset, self.content_area.width);
+                (top..cells.len())
+                    .find(is_tool)
+                    .or_else(|| (0..top).rev().find(is_tool))
+            }
+        };
+        if let Some(next) = next {
+            self.collapse.selected = Some(next);
+            self.scroll_cell_into_view(next);
+        }
+    }
+
+    /// A left click on a shell or patch cell selects it and expands or
+    /// collapses it. Returns false for anything else.
+    fn handle_click(&mut self, mouse_event: MouseEvent) -> bool {
+        if !matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left)) {
+            return false;
+        }
+        let area = self.content_area;
+        if !area.contains(Position::new(mouse_event.column, mouse_event.row)) {
+            return false;
+        }
+        let row = self.view.scroll_offset + usize::from(mouse_event.row - area.y);
+        if row >= self.view.content_height(area.width) {
+            return false;
+        }
+        let cell = self.cell_at_row(row, area.width);
+        if !self
+            .cells
+            .get(cell)
+            .is_some_and(|cell| is_tool_cell(cell.as_ref()))
+        {
+            return false;
+        }
+        self.collapse.selected = Some(cell);
+        self.collapse.toggle(cell);
+        self.rebuild_renderables();
+        true
+    }
+
+    /// Handles a key while the search is active. Returns false for keys it
+    /// leaves to the pager.
+    fn handle_search_key(&mut self, key_event: KeyEvent) -> bool {
+        if self.search.is_editing() {
+            match key_event {
+                e if KEY_ENTER.is_press(e) => {
+                    if self.search.query().is_empty() {
+                        self.search.clear();
+                    } else {
+                        self.search.confirm();
+                    }
+                }
+                e if KEY_ESC.is_press(e) => self.search.clear(),
+                e if KEY_BACKSPACE.is_press(e) => {
+                    self.search.pop_char();
+                    self.search_changed();
+                }
+                e if KEY_ALT_C.is_press(e) => {
+                    self.search.toggle_case_sensitive();
+                    self.search_changed();
+                }
+                e if KEY_ALT_R.is_press(e) => {
+                    self.search.toggle_regex();
+                    self.search_changed();
+                }
+                KeyEvent {
+                    code: KeyCode::Char(c),
+                    modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                    ..
+                } => {
+                    self.search.push_char(c);
+                    self.search_changed();
+                }
+                _ => {}
+            }
+            self.rebuild_renderables();
+            return true;
+        }
+        match key_event {
+            e if KEY_SLASH.is_press(e) => {
+                self.search.start();
+                self.search_changed();
+            }
+            _ if !self.search.is_active() => return false,
+            e if KEY_N.is_press(e) => {
+                self.search.next_match();
+                self.scroll_to_match = true;
+            }
+            e if KEY_SHIFT_N.is_press(e) || key_hint::plain(KeyCode::Char('N')).is_press(e) => {
+                self.search.previous_match();
+                self.scroll_to_match = true;
+            }
+            e if KEY_ESC.is_press(e) => self.search.clear(),
+            _ => return false,
+        }
+        self.rebuild_renderables();
+        true
+    }
+
+    /// The query or a mode changed: search again from the top of the view.
+    fn search_changed(&mut self) {
+        self.search_width = None;
+        self.search_from_view = true;
+        self.scroll_to_match = true;
+    }
+
+    /// Finds the matches again if the query or the width changed, and
+    /// scrolls to the current match when asked to.
+    fn refresh_search(&mut self, content_area: Rect) {
+        let width = content_area.width;
+        if self.search.is_active() && self.search_width != Some(width) {
+            let lines: Vec<Vec<Line<'static>>> = self
+                .cells
+                .iter()
+                .map(|cell| cell.transcript_lines(width))
+                .collect();
+            // From the bottom of the transcript, start at the latest match.
+            let from = if !self.search_from_view {
+                self.search
+                    .current()
+                    .map(|current| (current.cell, current.line))
+            } else if self.view.is_scrolled_to_bottom() {
+                None
+            } else {
+                Some((self.cell_at_row(self.view.scroll_offset, width), 0))
+            };
+            self.search.update(&lines, from);
+            self.search_width = Some(width);
+            self.search_from_view = false;
+            self.rebuild_renderables();
+        }
+        if std::mem::take(&mut self.scroll_to_match)
+            && let Some(current) = self.search.current()
+        {
+            self.load_cells_from(current.cell);
+            // A match inside a collapsed cell needs the cell expanded.
+            if let Some(cell) = self.cells.get(current.cell)
+                && collapsed_summary(
+                    cell.as_ref(),
+                    self.collapse.after,
+                    self.collapse.toggled.contains(&current.cell),
+                    width,
+                )
+                .is_some()
+            {
+                self.collapse.toggle(current.cell);
+                self.rebuild_renderables();
+            }
+            let row = self.match_row(current.cell, current.line, width);
+            // Keep a third of a page of context above the match.
+            let context = (content_area.height as usize) / 3;
+            self.view.scroll_offset = row.saturating_sub(context);
+        }
+    }
+
+    /// Index of the cell drawn at `row` of the transcript.
+    fn cell_at_row(&self, row: usize, width: u16) -> usize {
+        self.loaded_from + self.view.chunk_at_row(row, width)
+    }
+
+    /// Row of the transcript where `line` of `cell` starts; `cell` must be
+    /// loaded.
+    fn match_row(&self, cell: usize, line: usize, width: u16) -> usize {
+        let above = self
+            .view
+            .chunk_top(cell.saturating_sub(self.loaded_from), width);
+        let Some(history_cell) = self.cells.get(cell) else {
+            return above;
+        };
+        let inset = usize::from(cell > 0 && !history_cell.is_stream_continuation());
+        if line == 0 {
+            return above + inset;
+        }
+        let lines = history_cell.transcript_lines(width);
+        let lines_above = Paragraph::new(Text::from(lines[..line.min(lines.len())].to_vec()))
+            .wrap(Wrap { trim: false })
+            .line_count(width);
+        above + inset + lines_above
+    }
+
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        if self.search.is_editing() {
+            self.search.status_line().render_ref(line1, buf);
+            render_key_hints(
+                line2,
+                buf,
+                &[
+                    (&[KEY_ENTER], "to search"),
+                    (&[KEY_ESC], "to cancel"),
+                    (&[KEY_ALT_C], "for case-sensitive"),
+                    (&[KEY_ALT_R], "for regex"),
+                ],
+            );
+            return;
+        }
+        if self.search.is_active() {
+            self.search.status_line().render_ref(line1, buf);
+            render_key_hints(
+                line2,
+                buf,
+                &[
+                    (&[KEY_N, KEY_SHIFT_N], "for next/prev match"),
+                    (&[KEY_SLASH], "to search again"),
+                    (&[KEY_ESC], "to clear"),
+                    (&[KEY_Q], "to quit"),
+                ],
+            );
+            return;
+        }
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
 
-        let mut pairs: Vec<(&[KeyBinding], &str)> =
-            vec![(&[KEY_Q], "to quit"), (&[KEY_ESC], "to edit prev")];
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_Q], "to quit"),
+            (&[KEY_ESC], "to edit prev"),
+            (&[KEY_SLASH], "to search"),
+        ];
         if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ENTER], "to edit message"));
+        } else if self.collapse.selected.is_some() {
+            pairs.push((&[KEY_ENTER], "to expand/collapse"));
+        } else if self.has_tool_cells {
+            pairs.push((&[KEY_TAB], "to select tool call"));
         }
         render_key_hints(line2, buf, &pairs);
     }
@@ -491,6 +1107,10 @@ impl TranscriptOverlay {
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        let content_area = self.view.content_area(top);
+        self.content_area = content_area;
+        self.refresh_search(content_area);
+        self.load_older_cells(content_area);
         self.view.render(top, buf);
         self.render_hints(bottom, buf);
     }
@@ -500,12 +1120,26 @@ impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                e if KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if self.handle_search_key(e) || self.handle_collapse_key(e) => {
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                e if KEY_Q.is_press(e) => {
                     self.is_done = true;
                     Ok(())
                 }
                 other => self.view.handle_key_event(tui, other),
             },
+            TuiEvent::Mouse(mouse_event) => {
+                if self.view.handle_mouse_event(mouse_event) || self.handle_click(mouse_event) {
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -565,6 +1199,147 @@ impl StaticOverlay {
                 }
                 other => self.view.handle_key_event(tui, other),
             },
+            TuiEvent::Mouse(mouse_event) => {
+                if self.view.handle_mouse_event(mouse_event) {
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// Full-screen review of a proposed patch, one hunk at a time. Enter
+/// answers the approval request with the accepted hunks; leaving without
+/// Enter keeps the request open.
+pub(crate) struct DiffReviewOverlay {
+    view: PagerView,
+    review: DiffReview,
+    /// Index in `view` of each hunk's renderable.
+    hunk_chunks: Vec<usize>,
+    id: String,
+    cwd: PathBuf,
+    app_event_tx: AppEventSender,
+    is_done: bool,
+}
+
+impl DiffReviewOverlay {
+    pub(crate) fn new(
+        id: String,
+        cwd: PathBuf,
+        changes: &HashMap<PathBuf, FileChange>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let review = DiffReview::new(changes);
+        let (renderables, hunk_chunks) = review.renderables(&cwd);
+        Self {
+            view: PagerView::new(renderables, "P A T C H   R E V I E W".to_string(), 0),
+            review,
+            hunk_chunks,
+            id,
+            cwd,
+            app_event_tx,
+            is_done: false,
+        }
+    }
+
+    fn rebuild_renderables(&mut self) {
+        let (renderables, hunk_chunks) = self.review.renderables(&self.cwd);
+        self.view.set_renderables(renderables);
+        self.hunk_chunks = hunk_chunks;
+        if let Some(&chunk) = self.hunk_chunks.get(self.review.selected()) {
+            self.view.scroll_chunk_into_view(chunk);
+        }
+    }
+
+    /// Handles the review keys. Returns false for the keys that scroll.
+    fn handle_review_key(&mut self, key_event: KeyEvent) -> bool {
+        match key_event {
+            e if KEY_TAB.is_press(e) => self.review.select_next(),
+            e if KEY_SHIFT_TAB.is_press(e) || e.code == KeyCode::BackTab => {
+                self.review.select_previous();
+            }
+            e if KEY_Y.is_press(e) => self.review.set_selected_accepted(true),
+            e if KEY_N.is_press(e) => self.review.set_selected_accepted(false),
+            e if KEY_SHIFT_Y.is_press(e) => self.review.set_all_accepted(true),
+            e if KEY_SHIFT_N.is_press(e) => self.review.set_all_accepted(false),
+            e if KEY_S.is_press(e) => self.review.toggle_side_by_side(),
+            e if KEY_ENTER.is_press(e) => {
+                self.app_event_tx.send(AppEvent::PatchReviewed {
+                    id: self.id.clone(),
+                    decision: self.review.decision(),
+                });
+                self.is_done = true;
+                return true;
+            }
+            _ => return false,
+        }
+        self.rebuild_renderables();
+        true
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let layout = if self.review.is_side_by_side() {
+            "for unified"
+        } else {
+            "for side-by-side"
+        };
+        let apply = format!(
+            "to apply {}/{} hunks",
+            self.review.accepted_count(),
+            self.review.hunk_count()
+        );
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_TAB, KEY_SHIFT_TAB], "to move"),
+            (&[KEY_Y, KEY_N], "to accept/reject"),
+            (&[KEY_SHIFT_Y, KEY_SHIFT_N], "for all"),
+            (&[KEY_S], layout),
+            (&[KEY_ENTER], &apply),
+            (&[KEY_Q], "to go back"),
+        ];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => match key_event {
+                e if KEY_Q.is_press(e) || KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if self.handle_review_key(e) => {
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                other => self.view.handle_key_event(tui, other),
+            },
+            TuiEvent::Mouse(mouse_event) => {
+                if self.view.handle_mouse_event(mouse_event) {
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -574,6 +1349,7 @@ impl StaticOverlay {
             _ => Ok(()),
         }
     }
+
     pub(crate) fn is_done(&self) -> bool {
         self.is_done
     }
@@ -609,11 +1385,13 @@ fn render_offset_content(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_core::config::types::DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES;
     use codex_core::protocol::ExecCommandSource;
     use codex_core::protocol::ReviewDecision;
     use insta::assert_snapshot;
     use std::collections::HashMap;
     use std::path::PathBuf;
+    use std::rc::Rc;
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -653,9 +1431,12 @@ mod tests {
 
     #[test]
     fn edit_prev_hint_is_visible() {
-        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
-            lines: vec![Line::from("hello")],
-        })]);
+        let mut overlay = TranscriptOverlay::new(
+            vec![Arc::new(TestCell {
+                lines: vec![Line::from("hello")],
+            })],
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+        );
 
         // Render into a small buffer and assert the backtrack hint is present
         let area = Rect::new(0, 0, 40, 10);
@@ -679,17 +1460,20 @@ mod tests {
     #[test]
     fn transcript_overlay_snapshot_basic() {
         // Prepare a transcript overlay with a few lines
-        let mut overlay = TranscriptOverlay::new(vec![
-            Arc::new(TestCell {
-                lines: vec![Line::from("alpha")],
-            }),
-            Arc::new(TestCell {
-                lines: vec![Line::from("beta")],
-            }),
-            Arc::new(TestCell {
-                lines: vec![Line::from("gamma")],
-            }),
-        ]);
+        let mut overlay = TranscriptOverlay::new(
+            vec![
+                Arc::new(TestCell {
+                    lines: vec![Line::from("alpha")],
+                }),
+                Arc::new(TestCell {
+                    lines: vec![Line::from("beta")],
+                }),
+                Arc::new(TestCell {
+                    lines: vec![Line::from("gamma")],
+                }),
+            ],
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+        );
         let mut term = Terminal::new(TestBackend::new(40, 10)).expect("term");
         term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
             .expect("draw");
@@ -766,7 +1550,7 @@ mod tests {
         let exec_cell: Arc<dyn HistoryCell> = Arc::new(exec_cell);
         cells.push(exec_cell);
 
-        let mut overlay = TranscriptOverlay::new(cells);
+        let mut overlay = TranscriptOverlay::new(cells, DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES);
         let area = Rect::new(0, 0, 80, 12);
         let mut buf = Buffer::empty(area);
 
@@ -778,6 +1562,151 @@ mod tests {
         assert_snapshot!("transcript_overlay_apply_patch_scroll_vt100", snapshot);
     }
 
+    #[test]
+    fn transcript_search_scrolls_to_and_highlights_the_match() {
+        let cells: Vec<Arc<dyn HistoryCell>> = (0..30)
+            .map(|i| {
+                Arc::new(TestCell {
+                    lines: vec![Line::from(format!("line {i}"))],
+                }) as Arc<dyn HistoryCell>
+            })
+            .collect();
+        let mut overlay = TranscriptOverlay::new(cells, DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES);
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        for c in "/line 12".chars() {
+            assert!(overlay.handle_search_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+        }
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("/line 12▏  1/1"), "{text}");
+        let row = (area.y..area.bottom())
+            .find(|&y| buf[(area.x, y)].symbol() == "l")
+            .expect("match row");
+        let cell = &buf[(area.x, row)];
+        assert!(
+            cell.modifier.contains(ratatui::style::Modifier::REVERSED),
+            "{text}"
+        );
+        assert_eq!(cell.fg, ratatui::style::Color::Cyan);
+
+        // Enter keeps the matches for n/N; Esc clears them.
+        assert!(overlay.handle_search_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(overlay.is_searching());
+        assert!(overlay.handle_search_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+        assert!(!overlay.is_searching());
+        assert!(!overlay.handle_search_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE)));
+    }
+
+    /// A line of text followed by a failed `cargo test` with 30 lines of
+    /// output.
+    fn text_and_long_exec_cells() -> Vec<Arc<dyn HistoryCell>> {
+        let mut exec_cell = crate::exec_cell::new_active_exec_command(
+            "exec-1".into(),
+            vec!["bash".into(), "-lc".into(), "cargo test".into()],
+            vec![ParsedCommand::Unknown {
+                cmd: "cargo test".into(),
+            }],
+            ExecCommandSource::Agent,
+            None,
+            true,
+        );
+        let output: String = (0..30).map(|i| format!("out {i}\n")).collect();
+        exec_cell.complete_call(
+            "exec-1",
+            CommandOutput {
+                exit_code: 1,
+                aggregated_output: output.clone(),
+                formatted_output: output,
+            },
+            Duration::from_millis(1500),
+        );
+        vec![
+            Arc::new(TestCell {
+                lines: vec![Line::from("hello")],
+            }),
+            Arc::new(exec_cell),
+        ]
+    }
+
+    #[test]
+    fn long_exec_cells_collapse_to_a_summary_and_expand_on_enter() {
+        let cells = text_and_long_exec_cells();
+        let mut overlay = TranscriptOverlay::new(cells, DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES);
+        let area = Rect::new(0, 0, 80, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("$ cargo test ✗ (1)"), "{text}");
+        assert!(text.contains("30 lines of output"), "{text}");
+        assert!(!text.contains("out 29"), "{text}");
+        assert!(text.contains("tab to select tool call"), "{text}");
+
+        // Enter does nothing until Tab selects the cell.
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(!overlay.handle_collapse_key(key(KeyCode::Enter)));
+        assert!(overlay.handle_collapse_key(key(KeyCode::Tab)));
+        assert!(overlay.handle_collapse_key(key(KeyCode::Enter)));
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("out 29"), "{text}");
+        assert!(text.contains("enter to expand/collapse"), "{text}");
+
+        assert!(overlay.handle_collapse_key(key(KeyCode::Enter)));
+        overlay.render(area, &mut buf);
+        assert!(!buffer_to_text(&buf, area).contains("out 29"));
+
+        // A search match inside a collapsed cell expands it.
+        for c in "/out 7".chars() {
+            assert!(overlay.handle_search_key(key(KeyCode::Char(c))));
+        }
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("out 7"), "{text}");
+    }
+
+    #[test]
+    fn clicking_a_tool_cell_expands_it_and_the_wheel_scrolls() {
+        let mut overlay = TranscriptOverlay::new(
+            text_and_long_exec_cells(),
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+        );
+        let area = Rect::new(0, 0, 80, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let mouse = |kind, row| MouseEvent {
+            kind,
+            column: 4,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+
+        // The header takes the first row; "hello" is not a tool cell.
+        assert!(!overlay.handle_click(click(1)));
+        assert!(overlay.handle_click(click(3)));
+        assert_eq!(overlay.collapse.selected, Some(1));
+        overlay.render(area, &mut buf);
+        assert!(buffer_to_text(&buf, area).contains("out 0"));
+
+        overlay.view.scroll_offset = 0;
+        assert!(
+            overlay
+                .view
+                .handle_mouse_event(mouse(MouseEventKind::ScrollDown, 3))
+        );
+        assert_eq!(overlay.view.scroll_offset, WHEEL_SCROLL_LINES);
+        assert!(
+            overlay
+                .view
+                .handle_mouse_event(mouse(MouseEventKind::ScrollUp, 3))
+        );
+        assert_eq!(overlay.view.scroll_offset, 0);
+        assert!(!overlay.view.handle_mouse_event(click(3)));
+    }
+
     #[test]
     fn transcript_overlay_keeps_scroll_pinned_at_bottom() {
         let mut overlay = TranscriptOverlay::new(
@@ -788,6 +1717,7 @@ mod tests {
                     }) as Arc<dyn HistoryCell>
                 })
                 .collect(),
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
         );
         let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
         term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
@@ -815,6 +1745,7 @@ mod tests {
                     }) as Arc<dyn HistoryCell>
                 })
                 .collect(),
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
         );
         let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
         term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
@@ -878,6 +1809,7 @@ mod tests {
                     }) as Arc<dyn HistoryCell>
                 })
                 .collect(),
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
         );
         let area = Rect::new(0, 0, 40, 15);
 
@@ -1013,6 +1945,95 @@ mod tests {
         assert_eq!(pv.scroll_offset, 0);
     }
 
+    /// A one-line renderable that counts how often it is drawn.
+    struct CountingRenderable {
+        label: String,
+        renders: Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Renderable for CountingRenderable {
+        fn render(&self, area: Rect, buf: &mut Buffer) {
+            self.renders.set(self.renders.get() + 1);
+            Line::from(self.label.clone()).render(area, buf);
+        }
+
+        fn desired_height(&self, _width: u16) -> u16 {
+            1
+        }
+    }
+
+    #[test]
+    fn pager_view_draws_only_the_renderables_in_view() {
+        let renders = Rc::new(std::cell::Cell::new(0));
+        let mut pv = PagerView::new(
+            (0..20_000)
+                .map(|i| {
+                    Box::new(CountingRenderable {
+                        label: format!("row-{i}"),
+                        renders: renders.clone(),
+                    }) as Box<dyn Renderable>
+                })
+                .collect(),
+            "T".to_string(),
+            12_345,
+        );
+        let area = Rect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(area);
+
+        pv.render(area, &mut buf);
+
+        let rendered = buffer_to_text(&buf, area);
+        assert!(rendered.contains("row-12345"), "{rendered:?}");
+        assert!(rendered.contains("row-12352"), "{rendered:?}");
+        assert!(renders.get() <= 10, "drew {} renderables", renders.get());
+        assert_eq!(pv.chunk_at_row(12_345, 20), 12_345);
+        assert_eq!(pv.content_height(20), 20_000);
+
+        pv.push_renderable(paragraph_block("tail", 3));
+        assert_eq!(pv.content_height(20), 20_003);
+        assert_eq!(pv.chunk_top(20_000, 20), 20_000);
+    }
+
+    #[test]
+    fn transcript_overlay_loads_older_cells_when_scrolled_up() {
+        let total = TRANSCRIPT_LOAD_CELLS * 2 + 100;
+        let mut overlay = TranscriptOverlay::new(
+            (0..total)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line-{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+            DEFAULT_COLLAPSE_TOOL_OUTPUT_LINES,
+        );
+        let area = Rect::new(0, 0, 40, 15);
+        assert_eq!(overlay.view.renderables.len(), TRANSCRIPT_LOAD_CELLS);
+
+        let bottom = transcript_line_numbers(&mut overlay, area);
+        assert_eq!(bottom.last(), Some(&(total - 1)));
+        assert_eq!(overlay.view.renderables.len(), TRANSCRIPT_LOAD_CELLS);
+
+        // Near the top of the loaded cells, the next batch loads without
+        // moving what is on screen.
+        overlay.view.scroll_offset = 4;
+        let before = transcript_line_numbers(&mut overlay, area);
+        assert_eq!(overlay.view.renderables.len(), TRANSCRIPT_LOAD_CELLS * 2);
+        assert_eq!(before, transcript_line_numbers(&mut overlay, area));
+        assert_eq!(before.first(), Some(&(total - TRANSCRIPT_LOAD_CELLS + 2)));
+
+        // Jumping to the top loads everything.
+        overlay.view.scroll_offset = 0;
+        let top = transcript_line_numbers(&mut overlay, area);
+        assert_eq!(overlay.view.renderables.len(), total);
+        assert_eq!(top.first(), Some(&0));
+
+        overlay.insert_cell(Arc::new(TestCell {
+            lines: vec![Line::from(format!("line-{total}"))],
+        }));
+        assert_eq!(overlay.view.renderables.len(), total + 1);
+    }
+
     #[test]
     fn pager_view_is_scrolled_to_bottom_accounts_for_wrapped_height() {
         let mut pv = PagerView::new(vec![paragraph_block("a", 10)], "T".to_string(), 0);
@@ -1034,4 +2055,48 @@ mod tests {
             "expected view to report at bottom after scrolling to end"
         );
     }
+
+    #[test]
+    fn diff_review_enter_answers_with_the_accepted_hunks() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<AppEvent>();
+        let changes = HashMap::from([
+            (
+                PathBuf::from("/repo/a.txt"),
+                FileChange::Add {
+                    content: "a\n".to_string(),
+                },
+            ),
+            (
+                PathBuf::from("/repo/b.txt"),
+                FileChange::Add {
+                    content: "b\n".to_string(),
+                },
+            ),
+        ]);
+        let mut overlay = DiffReviewOverlay::new(
+            "call-1".to_string(),
+            PathBuf::from("/repo"),
+            &changes,
+            AppEventSender::new(tx),
+        );
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        // Scrolling keys are left to the pager.
+        assert!(!overlay.handle_review_key(key(KeyCode::Down)));
+        assert!(overlay.handle_review_key(key(KeyCode::Char('n'))));
+        assert!(rx.try_recv().is_err());
+        assert!(overlay.handle_review_key(key(KeyCode::Enter)));
+        assert!(overlay.is_done());
+
+        let Ok(AppEvent::PatchReviewed { id, decision }) = rx.try_recv() else {
+            panic!("expected a PatchReviewed event");
+        };
+        assert_eq!(id, "call-1");
+        assert_eq!(
+            decision,
+            ReviewDecision::ApprovedWithModifiedPatch {
+                patch: "*** Begin Patch\n*** Add File: /repo/b.txt\n+b\n*** End Patch".to_string()
+            }
+        );
+    }
 }
@@ -0,0 +1,235 @@
+//! The side pane that keeps the agent's current plan (the `update_plan`
+//! tool) next to the conversation, so step progress stays in view instead of
+//! scrolling away with the transcript. Ctrl+L shows or hides it.
+
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::history_cell::plan_step_lines;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableItem;
+
+/// Columns the pane takes, including its border.
+const PLAN_PANE_WIDTH: u16 = 40;
+
+/// Narrowest terminal that gets the pane; below this the conversation keeps
+/// the full width and plan updates only show in the transcript.
+pub(crate) const MIN_WIDTH_FOR_PLAN_PANE: u16 = 100;
+
+pub(crate) struct PlanPane {
+    plan: UpdatePlanArgs,
+}
+
+impl PlanPane {
+    pub(crate) fn new(plan: UpdatePlanArgs) -> Self {
+        Self { plan }
+    }
+
+    /// Whether every step is done (or there are none).
+    pub(crate) fn is_complete(&self) -> bool {
+        self.plan
+            .plan
+            .iter()
+            .all(|item| matches!(item.status, StepStatus::Completed))
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let done = self
+            .plan
+            .plan
+            .iter()
+            .filter(|item| matches!(item.status, StepStatus::Completed))
+            .count();
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                "Plan".bold(),
+                format!(" {done}/{}", self.plan.plan.len()).dim(),
+            ]
+            .into(),
+        ];
+        let note = self
+            .plan
+            .explanation
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty());
+        if let Some(note) = note {
+            lines.extend(
+                textwrap::wrap(note, width.max(1) as usize)
+                    .into_iter()
+                    .map(|line| Line::from(line.to_string().dim().italic())),
+            );
+        }
+        lines.push(Line::from(""));
+        for PlanItemArg { step, status } in &self.plan.plan {
+            lines.extend(plan_step_lines(status, step, width));
+        }
+        lines
+    }
+}
+
+/// Text starts two columns in: the border and a space.
+const BORDER_WIDTH: u16 = 2;
+
+impl Renderable for PlanPane {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            buf[(area.x, y)]
+                .set_symbol("│")
+                .set_style(Style::default().dim());
+        }
+        let inner = Rect {
+            x: area.x.saturating_add(BORDER_WIDTH),
+            y: area.y.saturating_add(1),
+            width: area.width.saturating_sub(BORDER_WIDTH),
+            height: area.height.saturating_sub(1),
+        };
+        Paragraph::new(self.lines(inner.width)).render(inner, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let lines = self.lines(width.saturating_sub(BORDER_WIDTH)).len();
+        u16::try_from(lines).unwrap_or(u16::MAX).saturating_add(1)
+    }
+}
+
+/// `main` with the plan pane on its right. `main` keeps to the bottom of
+/// the area so the composer stays put when the plan is the taller of the
+/// two.
+pub(crate) struct WithPlanPane<'a> {
+    main: RenderableItem<'a>,
+    pane: &'a PlanPane,
+}
+
+impl<'a> WithPlanPane<'a> {
+    pub(crate) fn new(main: RenderableItem<'a>, pane: &'a PlanPane) -> Self {
+        Self { main, pane }
+    }
+
+    fn split(&self, area: Rect) -> Option<(Rect, Rect)> {
+        if area.width < MIN_WIDTH_FOR_PLAN_PANE {
+            return None;
+        }
+        let main_width = area.width - PLAN_PANE_WIDTH;
+        let main_height = self.main.desired_height(main_width).min(area.height);
+        let main = Rect {
+            x: area.x,
+            y: area.bottom() - main_height,
+            width: main_width,
+            height: main_height,
+        };
+        let pane = Rect {
+            x: area.x + main_width,
+            width: PLAN_PANE_WIDTH,
+            ..area
+        };
+        Some((main, pane))
+    }
+}
+
+impl Renderable for WithPlanPane<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        match self.split(area) {
+            Some((main, pane)) => {
+                self.main.render(main, buf);
+                self.pane.render(pane, buf);
+            }
+            None => self.main.render(area, buf),
+        }
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        if width < MIN_WIDTH_FOR_PLAN_PANE {
+            return self.main.desired_height(width);
+        }
+        let main_width = width - PLAN_PANE_WIDTH;
+        self.main
+            .desired_height(main_width)
+            .max(self.pane.desired_height(PLAN_PANE_WIDTH))
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        match self.split(area) {
+            Some((main, _)) => self.main.cursor_pos(main),
+            None => self.main.cursor_pos(area),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn step(step: &str, status: StepStatus) -> PlanItemArg {
+        PlanItemArg {
+            step: step.to_string(),
+            status,
+        }
+    }
+
+    fn rendered(area: Rect, renderable: &dyn Renderable) -> Vec<String> {
+        let mut buf = Buffer::empty(area);
+        renderable.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pane_sits_beside_the_conversation_on_wide_terminals() {
+        let pane = PlanPane::new(UpdatePlanArgs {
+            explanation: None,
+            plan: vec![
+                step("Read the parser", StepStatus::Completed),
+                step("Fix the bug", StepStatus::InProgress),
+                step("Add a test", StepStatus::Pending),
+            ],
+        });
+        assert!(!pane.is_complete());
+        let layout = WithPlanPane::new(RenderableItem::Owned(Box::new("› composer")), &pane);
+
+        assert_eq!(layout.desired_height(120), 6);
+        let lines = rendered(Rect::new(0, 0, 120, 6), &layout);
+        let pane_column: Vec<String> = lines
+            .iter()
+            .map(|line| line.chars().skip(80).collect())
+            .collect();
+        assert_eq!(
+            pane_column,
+            vec![
+                "│",
+                "│ Plan 1/3",
+                "│",
+                "│ ✔ Read the parser",
+                "│ □ Fix the bug",
+                "│ □ Add a test",
+            ]
+        );
+        assert_eq!(lines[5].chars().take(10).collect::<String>(), "› composer");
+
+        // Too narrow: the conversation keeps the whole width.
+        assert_eq!(layout.desired_height(60), 1);
+        assert_eq!(
+            rendered(Rect::new(0, 0, 60, 1), &layout),
+            vec!["› composer"]
+        );
+    }
+}
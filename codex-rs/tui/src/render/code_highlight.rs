@@ -0,0 +1,675 @@
+//! Syntax highlighting for fenced code blocks in assistant messages.
+//!
+//! The language comes from the fence tag (```` ```rust ````, ```` ```py ````).
+//! Shell blocks go through the tree-sitter bash highlighter; the other
+//! languages use a small lexer that only knows keywords, strings, and
+//! comments. Both scan forward, so a line keeps its styling once the rest of
+//! a streaming block arrives.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::highlight::highlight_bash_to_lines;
+use super::highlight::push_segment;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns code block highlighting on or off (`tui.syntax_highlighting`).
+pub(crate) fn set_syntax_highlighting(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn syntax_highlighting_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// How a fence tag is highlighted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CodeLanguage {
+    Shell,
+    Diff,
+    Lexed(&'static Language),
+}
+
+/// The language named by a fence info string such as `rust`,
+/// `rust,ignore`, `python title="x.py"`, or `{.js}`. `None` when no
+/// highlighter knows it, and the block renders plain.
+pub(crate) fn detect_language(info: &str) -> Option<CodeLanguage> {
+    let tag = info
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .next()?
+        .trim_start_matches('{')
+        .trim_start_matches('.')
+        .trim_end_matches('}')
+        .to_ascii_lowercase();
+    let language = match tag.as_str() {
+        "sh" | "bash" | "shell" | "zsh" => return Some(CodeLanguage::Shell),
+        "diff" | "patch" => return Some(CodeLanguage::Diff),
+        "rust" | "rs" => &RUST,
+        "python" | "py" => &PYTHON,
+        "javascript" | "js" | "jsx" | "mjs" | "cjs" | "typescript" | "ts" | "tsx" => &JAVASCRIPT,
+        "go" | "golang" => &GO,
+        "c" | "h" | "cpp" | "c++" | "cc" | "hpp" => &C,
+        "java" => &JAVA,
+        "json" | "jsonc" => &JSON,
+        "toml" => &TOML,
+        "yaml" | "yml" => &YAML,
+        "sql" => &SQL,
+        _ => return None,
+    };
+    Some(CodeLanguage::Lexed(language))
+}
+
+/// Highlights `code` as `language`, one `Line` per source line.
+pub(crate) fn highlight_code_to_lines(language: CodeLanguage, code: &str) -> Vec<Line<'static>> {
+    match language {
+        CodeLanguage::Shell => highlight_bash_to_lines(code),
+        CodeLanguage::Diff => highlight_diff(code),
+        CodeLanguage::Lexed(language) => language.highlight(code),
+    }
+}
+
+fn keyword_style() -> Style {
+    Style::default().magenta()
+}
+
+fn string_style() -> Style {
+    Style::default().green()
+}
+
+fn comment_style() -> Style {
+    Style::default().dim()
+}
+
+fn highlight_diff(code: &str) -> Vec<Line<'static>> {
+    code.split('\n')
+        .map(|line| {
+            let style = if line.starts_with("+++") || line.starts_with("---") {
+                Style::default().bold()
+            } else if line.starts_with('+') {
+                Style::default().green()
+            } else if line.starts_with('-') {
+                Style::default().red()
+            } else if line.starts_with("@@") {
+                Style::default().dim()
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(line.to_string(), style))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Language {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// String delimiters, longest first, and whether the string may span
+    /// lines. A single-line string left open at the end of its line is not a
+    /// string; the delimiter renders plain.
+    strings: &'static [(&'static str, bool)],
+    /// `'x'` is a char literal, but a lone `'` (a Rust lifetime) is not.
+    char_literals: bool,
+    /// Keywords match in any case (SQL).
+    ignore_case: bool,
+}
+
+const C_COMMENT: Option<(&str, &str)> = Some(("/*", "*/"));
+
+static RUST: Language = Language {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("\"", true)],
+    char_literals: true,
+    ignore_case: false,
+};
+
+static PYTHON: Language = Language {
+    keywords: &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "try", "while", "with", "yield",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+    char_literals: false,
+    ignore_case: false,
+};
+
+static JAVASCRIPT: Language = Language {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("`", true), ("\"", false), ("'", false)],
+    char_literals: false,
+    ignore_case: false,
+};
+
+static GO: Language = Language {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "false",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("`", true), ("\"", false)],
+    char_literals: true,
+    ignore_case: false,
+};
+
+static C: Language = Language {
+    keywords: &[
+        "auto",
+        "bool",
+        "break",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "const",
+        "constexpr",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extern",
+        "false",
+        "float",
+        "for",
+        "goto",
+        "if",
+        "inline",
+        "int",
+        "long",
+        "namespace",
+        "new",
+        "nullptr",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "short",
+        "signed",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typedef",
+        "typename",
+        "union",
+        "unsigned",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "while",
+    ],
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("\"", false)],
+    char_literals: true,
+    ignore_case: false,
+};
+
+static JAVA: Language = Language {
+    keywords: &[
+        "abstract",
+        "boolean",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "continue",
+        "default",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extends",
+        "false",
+        "final",
+        "finally",
+        "float",
+        "for",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "int",
+        "interface",
+        "long",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "record",
+        "return",
+        "short",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "var",
+        "void",
+        "while",
+    ],
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("\"\"\"", true), ("\"", false)],
+    char_literals: true,
+    ignore_case: false,
+};
+
+static JSON: Language = Language {
+    keywords: &["false", "null", "true"],
+    // JSONC comments; plain JSON never contains them.
+    line_comments: &["//"],
+    block_comment: C_COMMENT,
+    strings: &[("\"", false)],
+    char_literals: false,
+    ignore_case: false,
+};
+
+static TOML: Language = Language {
+    keywords: &["false", "true"],
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+    char_literals: false,
+    ignore_case: false,
+};
+
+static YAML: Language = Language {
+    keywords: &["false", "null", "true"],
+    line_comments: &["#"],
+    block_comment: None,
+    strings: &[("\"", false), ("'", false)],
+    char_literals: false,
+    ignore_case: false,
+};
+
+static SQL: Language = Language {
+    keywords: &[
+        "alter",
+        "and",
+        "as",
+        "asc",
+        "begin",
+        "by",
+        "case",
+        "commit",
+        "create",
+        "delete",
+        "desc",
+        "distinct",
+        "drop",
+        "else",
+        "end",
+        "exists",
+        "from",
+        "group",
+        "having",
+        "in",
+        "index",
+        "inner",
+        "insert",
+        "into",
+        "is",
+        "join",
+        "key",
+        "left",
+        "like",
+        "limit",
+        "not",
+        "null",
+        "on",
+        "or",
+        "order",
+        "outer",
+        "primary",
+        "references",
+        "right",
+        "select",
+        "set",
+        "table",
+        "then",
+        "union",
+        "update",
+        "values",
+        "when",
+        "where",
+        "with",
+    ],
+    line_comments: &["--"],
+    block_comment: C_COMMENT,
+    strings: &[("'", false)],
+    char_literals: false,
+    ignore_case: true,
+};
+
+impl Language {
+    fn highlight(&self, code: &str) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::default()];
+        let mut plain_start = 0;
+        let mut pos = 0;
+        while pos < code.len() {
+            let prev_is_word = code[..pos].chars().next_back().is_some_and(is_word_char);
+            match self.token_at(&code[pos..], prev_is_word) {
+                Token::Styled(len, style) => {
+                    push_segment(&mut lines, &code[plain_start..pos], None);
+                    push_segment(&mut lines, &code[pos..pos + len], Some(style));
+                    pos += len;
+                    plain_start = pos;
+                }
+                Token::Plain(len) => pos += len,
+            }
+        }
+        push_segment(&mut lines, &code[plain_start..], None);
+        lines
+    }
+
+    fn token_at(&self, rest: &str, prev_is_word: bool) -> Token {
+        if self.line_comments.iter().any(|c| rest.starts_with(c)) {
+            return Token::Styled(rest.find('\n').unwrap_or(rest.len()), comment_style());
+        }
+        if let Some((open, close)) = self.block_comment
+            && rest.starts_with(open)
+        {
+            let len = rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |end| open.len() + end + close.len());
+            return Token::Styled(len, comment_style());
+        }
+        for &(delimiter, multiline) in self.strings {
+            if rest.starts_with(delimiter) {
+                return match string_len(rest, delimiter, multiline) {
+                    Some(len) => Token::Styled(len, string_style()),
+                    None => Token::Plain(delimiter.len()),
+                };
+            }
+        }
+        if self.char_literals
+            && rest.starts_with('\'')
+            && let Some(len) = char_literal_len(rest)
+        {
+            return Token::Styled(len, string_style());
+        }
+        let Some(first) = rest.chars().next() else {
+            return Token::Plain(0);
+        };
+        if !is_word_char(first) || prev_is_word {
+            return Token::Plain(first.len_utf8());
+        }
+        let len = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+        let word = &rest[..len];
+        let is_keyword = if self.ignore_case {
+            self.keywords
+                .iter()
+                .any(|keyword| keyword.eq_ignore_ascii_case(word))
+        } else {
+            self.keywords.contains(&word)
+        };
+        if is_keyword {
+            Token::Styled(len, keyword_style())
+        } else {
+            Token::Plain(len)
+        }
+    }
+}
+
+enum Token {
+    Styled(usize, Style),
+    Plain(usize),
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length of the string opening `rest`, or `None` when a single-line string
+/// is not closed on its line. A multi-line string left open runs to the end,
+/// as it does while a block is still streaming.
+fn string_len(rest: &str, delimiter: &str, multiline: bool) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(delimiter.chars().count());
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == '\n' && !multiline {
+            return None;
+        } else if rest[i..].starts_with(delimiter) {
+            return Some(i + delimiter.len());
+        }
+    }
+    multiline.then_some(rest.len())
+}
+
+/// Length of a char literal such as `'a'` or `'\n'` opening `rest`.
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        return chars
+            .take(10)
+            .take_while(|(_, c)| *c != '\n')
+            .find(|(_, c)| *c == '\'')
+            .map(|(i, _)| i + 1);
+    }
+    match chars.next()? {
+        (i, '\'') if first != '\n' => Some(i + 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Each line as `(text, style)` runs, merging nothing.
+    fn runs(lines: &[Line<'static>]) -> Vec<Vec<(String, Style)>> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| (span.content.to_string(), span.style))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn styled(lines: &[Line<'static>], style: Style) -> Vec<String> {
+        lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .filter(|span| span.style == style)
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn detects_languages_from_fence_tags() {
+        assert_eq!(
+            detect_language("rust,ignore"),
+            Some(CodeLanguage::Lexed(&RUST))
+        );
+        assert_eq!(
+            detect_language("Py title=\"x.py\""),
+            Some(CodeLanguage::Lexed(&PYTHON))
+        );
+        assert_eq!(
+            detect_language("{.ts}"),
+            Some(CodeLanguage::Lexed(&JAVASCRIPT))
+        );
+        assert_eq!(detect_language("zsh"), Some(CodeLanguage::Shell));
+        assert_eq!(detect_language("text"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn highlights_keywords_strings_and_comments() {
+        let code = "fn main() {\n    let s = \"fn\"; // print it\n    println!(\"{s}\");\n}";
+        let lines = highlight_code_to_lines(CodeLanguage::Lexed(&RUST), code);
+        assert_eq!(
+            runs(&lines)[1],
+            vec![
+                ("    ".to_string(), Style::default()),
+                ("let".to_string(), keyword_style()),
+                (" s = ".to_string(), Style::default()),
+                ("\"fn\"".to_string(), string_style()),
+                ("; ".to_string(), Style::default()),
+                ("// print it".to_string(), comment_style()),
+            ]
+        );
+        assert_eq!(styled(&lines, keyword_style()), vec!["fn", "let"]);
+    }
+
+    #[test]
+    fn words_containing_keywords_stay_plain() {
+        let lines = highlight_code_to_lines(CodeLanguage::Lexed(&RUST), "let format_if = iffy;");
+        assert_eq!(styled(&lines, keyword_style()), vec!["let"]);
+    }
+
+    #[test]
+    fn lifetimes_are_not_char_literals() {
+        let code = "fn f<'a>(s: &'a str) -> char { 'x' }";
+        let lines = highlight_code_to_lines(CodeLanguage::Lexed(&RUST), code);
+        assert_eq!(styled(&lines, string_style()), vec!["'x'"]);
+    }
+
+    #[test]
+    fn unclosed_single_line_strings_render_plain() {
+        let code = "greeting: it's late\nname: 'codex'";
+        let lines = highlight_code_to_lines(CodeLanguage::Lexed(&YAML), code);
+        assert_eq!(styled(&lines, string_style()), vec!["'codex'"]);
+    }
+
+    #[test]
+    fn streamed_prefixes_keep_their_styling() {
+        let code = "def f():\n    \"\"\"Docs\n    more\"\"\"\n    return None\n";
+        let full = highlight_code_to_lines(CodeLanguage::Lexed(&PYTHON), code);
+        // Cut the block after the second line, as a stream would.
+        let partial = highlight_code_to_lines(CodeLanguage::Lexed(&PYTHON), &code[..22]);
+        assert_eq!(runs(&partial)[..2], runs(&full)[..2]);
+        assert_eq!(
+            runs(&full)[2],
+            vec![("    more\"\"\"".to_string(), string_style())]
+        );
+    }
+
+    #[test]
+    fn sql_keywords_ignore_case() {
+        let lines =
+            highlight_code_to_lines(CodeLanguage::Lexed(&SQL), "SELECT id from users -- all");
+        assert_eq!(styled(&lines, keyword_style()), vec!["SELECT", "from"]);
+        assert_eq!(styled(&lines, comment_style()), vec!["-- all"]);
+    }
+}
@@ -87,7 +87,7 @@ fn highlight_for(highlight: Highlight) -> BashHighlight {
     BashHighlight::ALL[highlight.0]
 }
 
-fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Style>) {
+pub(super) fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Style>) {
     for (i, part) in segment.split('\n').enumerate() {
         if i > 0 {
             lines.push(Line::from(""));
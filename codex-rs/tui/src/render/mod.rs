@@ -1,5 +1,6 @@
 use ratatui::layout::Rect;
 
+pub(crate) mod code_highlight;
 pub mod highlight;
 pub mod line_utils;
 pub mod renderable;
@@ -29,6 +29,7 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::diff_render::display_path_for;
 use crate::key_hint;
+use crate::status::format_tokens_compact;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
@@ -43,6 +44,9 @@ const LOAD_NEAR_THRESHOLD: usize = 5;
 pub enum ResumeSelection {
     StartFresh,
     Resume(PathBuf),
+    /// Watch a session running in another TUI through its attach socket
+    /// (`codex attach`).
+    Attach(PathBuf),
     Exit,
 }
 
@@ -190,6 +194,9 @@ struct PickerState {
     default_provider: String,
     show_all: bool,
     filter_cwd: Option<PathBuf>,
+    /// Session armed for deletion by a first press of Delete; a second
+    /// press on the same row removes its rollout file.
+    pending_delete: Option<PathBuf>,
 }
 
 struct PaginationState {
@@ -249,6 +256,7 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    total_tokens: Option<i64>,
 }
 
 impl PickerState {
@@ -283,6 +291,7 @@ impl PickerState {
             default_provider,
             show_all,
             filter_cwd,
+            pending_delete: None,
         }
     }
 
@@ -291,6 +300,7 @@ impl PickerState {
     }
 
     async fn handle_key(&mut self, key: KeyEvent) -> Result<Option<ResumeSelection>> {
+        let pending_delete = self.pending_delete.take();
         match key.code {
             KeyCode::Esc => return Ok(Some(ResumeSelection::StartFresh)),
             KeyCode::Char('c')
@@ -338,6 +348,17 @@ impl PickerState {
                     self.request_frame();
                 }
             }
+            KeyCode::Delete => {
+                if let Some(row) = self.filtered_rows.get(self.selected) {
+                    let path = row.path.clone();
+                    if pending_delete.as_ref() == Some(&path) {
+                        self.delete_session(&path).await?;
+                    } else {
+                        self.pending_delete = Some(path);
+                    }
+                    self.request_frame();
+                }
+            }
             KeyCode::Backspace => {
                 let mut new_query = self.query.clone();
                 new_query.pop();
@@ -357,9 +378,28 @@ impl PickerState {
             }
             _ => {}
         }
+        if pending_delete.is_some() && self.pending_delete.is_none() {
+            self.request_frame();
+        }
         Ok(None)
     }
 
+    /// Removes the session's rollout file and drops it from the list.
+    async fn delete_session(&mut self, path: &Path) -> Result<()> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        self.all_rows.retain(|row| row.path != path);
+        self.filtered_rows.retain(|row| row.path != path);
+        self.selected = self
+            .selected
+            .min(self.filtered_rows.len().saturating_sub(1));
+        self.ensure_selected_visible();
+        Ok(())
+    }
+
     fn start_initial_load(&mut self) {
         self.reset_pagination();
         self.all_rows.clear();
@@ -644,7 +684,12 @@ fn head_to_row(item: &ConversationItem) -> Row {
         .or(created_at);
 
     let (cwd, git_branch) = extract_session_meta_from_head(&item.head);
-    let preview = preview_from_head(&item.head)
+    // A title names the session better than its first message, and searching
+    // the preview then finds sessions by title.
+    let preview = item
+        .title
+        .clone()
+        .or_else(|| preview_from_head(&item.head))
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .unwrap_or_else(|| String::from("(no message yet)"));
@@ -656,6 +701,7 @@ fn head_to_row(item: &ConversationItem) -> Row {
         updated_at,
         cwd,
         git_branch,
+        total_tokens: item.total_tokens,
     }
 }
 
@@ -752,6 +798,13 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
             "/".dim(),
             key_hint::plain(KeyCode::Down).into(),
             " to browse".dim(),
+            "    ".dim(),
+            key_hint::plain(KeyCode::Delete).into(),
+            if state.pending_delete.is_some() {
+                " again to delete".magenta()
+            } else {
+                " to delete".dim()
+            },
         ]
         .into();
         frame.render_widget_ref(hint_line, hint);
@@ -784,8 +837,9 @@ fn render_list(
     let max_updated_width = metrics.max_updated_width;
     let max_branch_width = metrics.max_branch_width;
     let max_cwd_width = metrics.max_cwd_width;
+    let max_tokens_width = metrics.max_tokens_width;
 
-    for (idx, (row, (updated_label, branch_label, cwd_label))) in rows[start..end]
+    for (idx, (row, (updated_label, branch_label, cwd_label, tokens_label))) in rows[start..end]
         .iter()
         .zip(labels[start..end].iter())
         .enumerate()
@@ -826,6 +880,20 @@ fn render_list(
         } else {
             Some(Span::from(format!("{cwd_label:<max_cwd_width$}")).dim())
         };
+        let tokens_span = if max_tokens_width == 0 {
+            None
+        } else if tokens_label.is_empty() {
+            Some(
+                Span::from(format!(
+                    "{empty:<width$}",
+                    empty = "-",
+                    width = max_tokens_width
+                ))
+                .dim(),
+            )
+        } else {
+            Some(Span::from(format!("{tokens_label:>max_tokens_width$}")).dim())
+        };
 
         let mut preview_width = area.width as usize;
         preview_width = preview_width.saturating_sub(marker_width);
@@ -838,7 +906,13 @@ fn render_list(
         if max_cwd_width > 0 {
             preview_width = preview_width.saturating_sub(max_cwd_width + 2);
         }
-        let add_leading_gap = max_updated_width == 0 && max_branch_width == 0 && max_cwd_width == 0;
+        if max_tokens_width > 0 {
+            preview_width = preview_width.saturating_sub(max_tokens_width + 2);
+        }
+        let add_leading_gap = max_updated_width == 0
+            && max_branch_width == 0
+            && max_cwd_width == 0
+            && max_tokens_width == 0;
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
@@ -856,6 +930,10 @@ fn render_list(
             spans.push(cwd);
             spans.push("  ".into());
         }
+        if let Some(tokens) = tokens_span {
+            spans.push(tokens);
+            spans.push("  ".into());
+        }
         if add_leading_gap {
             spans.push("  ".into());
         }
@@ -982,6 +1060,15 @@ fn render_column_headers(
         spans.push(Span::from(label).bold());
         spans.push("  ".into());
     }
+    if metrics.max_tokens_width > 0 {
+        let label = format!(
+            "{text:<width$}",
+            text = "Tokens",
+            width = metrics.max_tokens_width
+        );
+        spans.push(Span::from(label).bold());
+        spans.push("  ".into());
+    }
     spans.push("Conversation".bold());
     frame.render_widget_ref(Line::from(spans), area);
 }
@@ -990,7 +1077,9 @@ struct ColumnMetrics {
     max_updated_width: usize,
     max_branch_width: usize,
     max_cwd_width: usize,
-    labels: Vec<(String, String, String)>,
+    /// Zero when no listed session has a recorded token count.
+    max_tokens_width: usize,
+    labels: Vec<(String, String, String, String)>,
 }
 
 fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
@@ -1013,7 +1102,7 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
         format!("…{tail}")
     }
 
-    let mut labels: Vec<(String, String, String)> = Vec::with_capacity(rows.len());
+    let mut labels: Vec<(String, String, String, String)> = Vec::with_capacity(rows.len());
     let mut max_updated_width = UnicodeWidthStr::width("Updated");
     let mut max_branch_width = UnicodeWidthStr::width("Branch");
     let mut max_cwd_width = if include_cwd {
@@ -1021,6 +1110,11 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
     } else {
         0
     };
+    let mut max_tokens_width = if rows.iter().any(|row| row.total_tokens.is_some()) {
+        UnicodeWidthStr::width("Tokens")
+    } else {
+        0
+    };
 
     for row in rows {
         let updated = format_updated_label(row);
@@ -1038,14 +1132,22 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
         };
         max_updated_width = max_updated_width.max(UnicodeWidthStr::width(updated.as_str()));
         max_branch_width = max_branch_width.max(UnicodeWidthStr::width(branch.as_str()));
+        let tokens = row
+            .total_tokens
+            .map(format_tokens_compact)
+            .unwrap_or_default();
         max_cwd_width = max_cwd_width.max(UnicodeWidthStr::width(cwd.as_str()));
-        labels.push((updated, branch, cwd));
+        if max_tokens_width > 0 {
+            max_tokens_width = max_tokens_width.max(UnicodeWidthStr::width(tokens.as_str()));
+        }
+        labels.push((updated, branch, cwd, tokens));
     }
 
     ColumnMetrics {
         max_updated_width,
         max_branch_width,
         max_cwd_width,
+        max_tokens_width,
         labels,
     }
 }
@@ -1084,6 +1186,8 @@ mod tests {
             head: head_with_ts_and_user_text(ts, &[preview]),
             created_at: Some(ts.to_string()),
             updated_at: Some(ts.to_string()),
+            total_tokens: None,
+            title: None,
         }
     }
 
@@ -1158,12 +1262,16 @@ mod tests {
             head: head_with_ts_and_user_text("2025-01-01T00:00:00Z", &["A"]),
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T00:00:00Z".into()),
+            total_tokens: None,
+            title: None,
         };
         let b = ConversationItem {
             path: PathBuf::from("/tmp/b.jsonl"),
             head: head_with_ts_and_user_text("2025-01-02T00:00:00Z", &["B"]),
             created_at: Some("2025-01-02T00:00:00Z".into()),
             updated_at: Some("2025-01-02T00:00:00Z".into()),
+            total_tokens: None,
+            title: None,
         };
         let rows = rows_from_items(vec![a, b]);
         assert_eq!(rows.len(), 2);
@@ -1180,6 +1288,8 @@ mod tests {
             head,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T01:00:00Z".into()),
+            total_tokens: None,
+            title: None,
         };
 
         let row = head_to_row(&item);
@@ -1220,6 +1330,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                total_tokens: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1228,6 +1339,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                total_tokens: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1236,6 +1348,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                total_tokens: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1729,4 +1842,88 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    #[test]
+    fn delete_needs_a_second_press_on_the_same_row() {
+        let tempdir = tempfile::tempdir().expect("tempdir");
+        let first = tempdir.path().join("first.jsonl");
+        let second = tempdir.path().join("second.jsonl");
+        std::fs::write(&first, "").expect("write first");
+        std::fs::write(&second, "").expect("write second");
+
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+        );
+        state.reset_pagination();
+        state.ingest_page(page(
+            vec![
+                make_item(
+                    first.to_str().expect("utf8 path"),
+                    "2025-03-02T00:00:00Z",
+                    "first",
+                ),
+                make_item(
+                    second.to_str().expect("utf8 path"),
+                    "2025-03-01T00:00:00Z",
+                    "second",
+                ),
+            ],
+            None,
+            2,
+            false,
+        ));
+
+        let press = |state: &mut PickerState, code: KeyCode| {
+            block_on_future(async {
+                state
+                    .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                    .await
+                    .unwrap();
+            });
+        };
+
+        // Moving away disarms the pending delete.
+        press(&mut state, KeyCode::Delete);
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Up);
+        press(&mut state, KeyCode::Delete);
+        assert!(first.exists());
+        assert_eq!(state.filtered_rows.len(), 2);
+
+        press(&mut state, KeyCode::Delete);
+        assert!(!first.exists());
+        assert!(second.exists());
+        assert_eq!(state.all_rows.len(), 1);
+        assert_eq!(state.filtered_rows[0].preview, "second");
+        assert_eq!(state.selected, 0);
+        assert!(state.pending_delete.is_none());
+    }
+
+    #[test]
+    fn tokens_column_appears_only_with_recorded_usage() {
+        let mut item = make_item("/tmp/a.jsonl", "2025-03-01T00:00:00Z", "hello");
+        let rows = vec![head_to_row(&item)];
+        assert_eq!(calculate_column_metrics(&rows, false).max_tokens_width, 0);
+
+        item.total_tokens = Some(12_345);
+        let rows = vec![head_to_row(&item)];
+        let metrics = calculate_column_metrics(&rows, false);
+        assert_eq!(metrics.max_tokens_width, "Tokens".len());
+        assert_eq!(metrics.labels[0].3, format_tokens_compact(12_345));
+    }
+
+    #[test]
+    fn title_replaces_the_first_message_as_preview() {
+        let mut item = make_item("/tmp/a.jsonl", "2025-03-01T00:00:00Z", "why does it flake?");
+        assert_eq!(head_to_row(&item).preview, "why does it flake?");
+
+        item.title = Some("Fix flaky login test".to_string());
+        assert_eq!(head_to_row(&item).preview, "Fix flaky login test");
+    }
 }
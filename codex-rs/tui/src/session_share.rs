@@ -0,0 +1,342 @@
+//! Watching a running session from another terminal (`codex attach`).
+//!
+//! While a TUI session runs, it listens on a Unix socket at
+//! `$CODEX_HOME/attach/<session-id>.sock` and writes every event to each
+//! connected viewer as one JSON line, shaped like the app server's
+//! `codex/event/<type>` notifications. A viewer that joins late first gets the
+//! session so far, starting from `SessionConfigured`, then the live stream.
+//! Streaming deltas are kept for that replay only until their turn ends,
+//! since the completed items carry the same text.
+//! Nothing is read back from viewers, so they cannot act on the session.
+//! The socket is only opened when `tui.allow_attach = true`.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::WarningEvent;
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::exec_command::strip_bash_lc_and_escape;
+
+const ATTACH_DIR: &str = "attach";
+const EVENT_METHOD_PREFIX: &str = "codex/event/";
+
+pub(crate) fn socket_path(codex_home: &Path, session_id: &str) -> PathBuf {
+    codex_home
+        .join(ATTACH_DIR)
+        .join(format!("{session_id}.sock"))
+}
+
+/// Serves one session to `codex attach` viewers. Dropping it disconnects
+/// them and removes the socket.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(crate) struct SessionShare {
+    session_id: String,
+    path: PathBuf,
+    viewers: Arc<Mutex<Viewers>>,
+    accept_task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct Viewers {
+    /// The lines replayed to viewers that join late.
+    backlog: Vec<BacklogLine>,
+    senders: Vec<UnboundedSender<Arc<str>>>,
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+struct BacklogLine {
+    line: Arc<str>,
+    /// Dropped once the turn that streamed it ends.
+    is_delta: bool,
+}
+
+impl Viewers {
+    fn record(&mut self, msg: &EventMsg, line: Arc<str>) {
+        match msg {
+            // The finished command carries its output, so late viewers do not
+            // need every chunk of it.
+            EventMsg::ExecCommandOutputDelta(_) => {}
+            EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                self.backlog.retain(|entry| !entry.is_delta);
+                self.backlog.push(BacklogLine {
+                    line,
+                    is_delta: false,
+                });
+            }
+            msg => self.backlog.push(BacklogLine {
+                line,
+                is_delta: is_streaming_delta(msg),
+            }),
+        }
+    }
+}
+
+fn is_streaming_delta(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::AgentMessageDelta(_)
+            | EventMsg::AgentReasoningDelta(_)
+            | EventMsg::AgentReasoningRawContentDelta(_)
+            | EventMsg::AgentMessageContentDelta(_)
+            | EventMsg::ReasoningContentDelta(_)
+            | EventMsg::ReasoningRawContentDelta(_)
+    )
+}
+
+impl SessionShare {
+    /// Starts listening for viewers of `session_id`. `None` when the socket
+    /// cannot be bound; the session runs unshared then.
+    #[cfg(unix)]
+    pub(crate) fn start(codex_home: &Path, session_id: &str) -> Option<Self> {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::net::UnixListener;
+
+        let path = socket_path(codex_home, session_id);
+        let dir = path.parent()?;
+        if let Err(err) = std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700)))
+        {
+            tracing::warn!("failed to create {}: {err}", dir.display());
+            return None;
+        }
+        // A session that crashed leaves its socket behind.
+        let _ = std::fs::remove_file(&path);
+        let listener = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::warn!("failed to listen on {}: {err}", path.display());
+                return None;
+            }
+        };
+
+        let viewers = Arc::new(Mutex::new(Viewers::default()));
+        let accept_viewers = Arc::clone(&viewers);
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                if let Ok(mut viewers) = accept_viewers.lock() {
+                    for entry in &viewers.backlog {
+                        let _ = tx.send(Arc::clone(&entry.line));
+                    }
+                    viewers.senders.push(tx);
+                }
+                tokio::spawn(write_lines(stream, rx));
+            }
+        });
+
+        Some(Self {
+            session_id: session_id.to_string(),
+            path,
+            viewers,
+            accept_task,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn start(_codex_home: &Path, _session_id: &str) -> Option<Self> {
+        None
+    }
+
+    pub(crate) fn publish(&self, event: &Event) {
+        if matches!(event.msg, EventMsg::RawResponseItem(_)) {
+            return;
+        }
+        let Some(line) = encode(event, &self.session_id) else {
+            return;
+        };
+        let line: Arc<str> = line.into();
+        let Ok(mut viewers) = self.viewers.lock() else {
+            return;
+        };
+        viewers
+            .senders
+            .retain(|tx| tx.send(Arc::clone(&line)).is_ok());
+        viewers.record(&event.msg, line);
+    }
+}
+
+impl Drop for SessionShare {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(unix)]
+async fn write_lines(
+    mut stream: tokio::net::UnixStream,
+    mut lines: tokio::sync::mpsc::UnboundedReceiver<Arc<str>>,
+) {
+    use tokio::io::AsyncWriteExt;
+
+    while let Some(line) = lines.recv().await {
+        if stream.write_all(line.as_bytes()).await.is_err()
+            || stream.write_all(b"\n").await.is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Forwards the events of the session behind `path` to the UI until its host
+/// closes the socket.
+#[cfg(unix)]
+pub(crate) async fn watch(path: &Path, app_event_tx: &AppEventSender) -> std::io::Result<()> {
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::BufReader;
+
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    let mut lines = BufReader::new(stream).lines();
+    while let Some(line) = lines.next_line().await? {
+        if let Some(event) = decode(&line) {
+            app_event_tx.send(AppEvent::CodexEvent(for_viewer(event)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn watch(_path: &Path, _app_event_tx: &AppEventSender) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "attaching to a session needs Unix domain sockets",
+    ))
+}
+
+fn encode(event: &Event, session_id: &str) -> Option<String> {
+    let Ok(Value::Object(mut params)) = serde_json::to_value(event) else {
+        return None;
+    };
+    params.insert(
+        "conversationId".to_string(),
+        Value::String(session_id.to_string()),
+    );
+    serde_json::to_string(&JSONRPCNotification {
+        method: format!("{EVENT_METHOD_PREFIX}{}", event.msg),
+        params: Some(Value::Object(params)),
+    })
+    .ok()
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+fn decode(line: &str) -> Option<Event> {
+    let notification: JSONRPCNotification = serde_json::from_str(line).ok()?;
+    if !notification.method.starts_with(EVENT_METHOD_PREFIX) {
+        return None;
+    }
+    serde_json::from_value(notification.params?).ok()
+}
+
+/// Approvals belong to the host, so a viewer sees that one is pending instead
+/// of a prompt it could not answer.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn for_viewer(event: Event) -> Event {
+    let message = match &event.msg {
+        EventMsg::ExecApprovalRequest(request) => format!(
+            "Waiting for the host to approve `{}`",
+            strip_bash_lc_and_escape(&request.command)
+        ),
+        EventMsg::ApplyPatchApprovalRequest(_) => {
+            "Waiting for the host to approve file changes".to_string()
+        }
+        EventMsg::ElicitationRequest(request) => {
+            format!("Waiting for the host to answer {}", request.server_name)
+        }
+        _ => return event,
+    };
+    Event {
+        id: event.id,
+        msg: EventMsg::Warning(WarningEvent { message }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageDeltaEvent;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::TaskCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn events_round_trip_as_app_server_notifications() {
+        let event = Event {
+            id: "1".to_string(),
+            msg: EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: "hello".to_string(),
+            }),
+        };
+        let line = encode(&event, "abc").expect("encode");
+        let notification: JSONRPCNotification = serde_json::from_str(&line).expect("json");
+        assert_eq!(notification.method, "codex/event/agent_message_delta");
+        assert_eq!(
+            notification.params.expect("params")["conversationId"],
+            "abc"
+        );
+
+        let decoded = decode(&line).expect("decode");
+        assert_eq!(decoded.id, "1");
+        assert!(matches!(
+            decoded.msg,
+            EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { delta }) if delta == "hello"
+        ));
+        assert!(decode(r#"{"method":"codex/other","params":{}}"#).is_none());
+    }
+
+    #[test]
+    fn backlog_drops_deltas_once_their_turn_ends() {
+        let mut viewers = Viewers::default();
+        let delta = EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "hel".to_string(),
+        });
+        let message = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "hello".to_string(),
+        });
+        let complete = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        });
+        viewers.record(&delta, "delta".into());
+        viewers.record(&message, "message".into());
+        assert_eq!(viewers.backlog.len(), 2);
+
+        viewers.record(&complete, "complete".into());
+        let lines: Vec<&str> = viewers
+            .backlog
+            .iter()
+            .map(|entry| entry.line.as_ref())
+            .collect();
+        assert_eq!(lines, vec!["message", "complete"]);
+    }
+
+    #[test]
+    fn viewers_see_approvals_as_waiting_notes() {
+        let event = Event {
+            id: "1".to_string(),
+            msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: "call".to_string(),
+                turn_id: String::new(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+                cwd: PathBuf::from("/repo"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        };
+        let EventMsg::Warning(WarningEvent { message }) = for_viewer(event).msg else {
+            panic!("expected a warning");
+        };
+        assert_eq!(message, "Waiting for the host to approve `cargo test`");
+    }
+}
@@ -0,0 +1,217 @@
+//! Several conversations in one TUI process, one per tab.
+//!
+//! `App` keeps the state of the tab on screen in its own fields, just as it
+//! does with a single session; every other tab is parked here with its
+//! widget, transcript, and config. Each tab's widget sends its events through
+//! an [`AppEventSender`](crate::app_event_sender::AppEventSender) tagged with
+//! the tab's id, so a parked tab keeps receiving its conversation's events
+//! while another one is showing.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use codex_core::config::Config;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::app_event::AppEvent;
+use crate::chatwidget::ChatWidget;
+use crate::file_search::FileSearchManager;
+use crate::history_cell::HistoryCell;
+
+pub(crate) type TabId = u64;
+
+/// A tab that is not on screen.
+pub(crate) struct ParkedTab {
+    pub(crate) id: TabId,
+    pub(crate) chat_widget: ChatWidget,
+    pub(crate) config: Config,
+    pub(crate) current_model: String,
+    pub(crate) file_search: FileSearchManager,
+    pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    pub(crate) has_emitted_history_lines: bool,
+    pub(crate) suppress_shutdown_complete: bool,
+    /// Events that need the screen, such as popups and overlays, replayed
+    /// when the tab is shown again.
+    pub(crate) pending_events: Vec<AppEvent>,
+    /// The conversation asked for approval or finished a turn while parked.
+    pub(crate) needs_attention: bool,
+}
+
+pub(crate) struct SessionTabs {
+    active: TabId,
+    next_id: TabId,
+    parked: Vec<ParkedTab>,
+}
+
+impl Default for SessionTabs {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            next_id: 1,
+            parked: Vec::new(),
+        }
+    }
+}
+
+impl SessionTabs {
+    pub(crate) fn active(&self) -> TabId {
+        self.active
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.parked.len() + 1
+    }
+
+    pub(crate) fn allocate_id(&mut self) -> TabId {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    pub(crate) fn get_mut(&mut self, id: TabId) -> Option<&mut ParkedTab> {
+        self.parked.iter_mut().find(|tab| tab.id == id)
+    }
+
+    pub(crate) fn parked_mut(&mut self) -> impl Iterator<Item = &mut ParkedTab> {
+        self.parked.iter_mut()
+    }
+
+    /// Removes the parked tab `id`, e.g. to show or close it.
+    pub(crate) fn take(&mut self, id: TabId) -> Option<ParkedTab> {
+        let index = self.parked.iter().position(|tab| tab.id == id)?;
+        Some(self.parked.remove(index))
+    }
+
+    /// Parks the tab that was on screen and records `active` as the one
+    /// showing now.
+    pub(crate) fn park(&mut self, tab: ParkedTab, active: TabId) {
+        self.parked.push(tab);
+        self.active = active;
+    }
+
+    /// The tab after (or before) the active one, wrapping around. `None`
+    /// when there is only one tab.
+    pub(crate) fn neighbor(&self, forward: bool) -> Option<TabId> {
+        cycle(&self.ids(), self.active, forward)
+    }
+
+    /// Every tab id in the order tabs were opened.
+    pub(crate) fn ids(&self) -> Vec<TabId> {
+        let mut ids: Vec<TabId> = self.parked.iter().map(|tab| tab.id).collect();
+        ids.push(self.active);
+        ids.sort_unstable();
+        ids
+    }
+
+    /// A label per tab, in order: its number and the name of its working
+    /// directory, with whether it is the active one.
+    pub(crate) fn labels(&self, active_cwd: &Path) -> Vec<(TabId, String, bool)> {
+        self.ids()
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let cwd = if id == self.active {
+                    active_cwd
+                } else {
+                    self.parked
+                        .iter()
+                        .find(|tab| tab.id == id)
+                        .map_or(active_cwd, |tab| tab.config.cwd.as_path())
+                };
+                let name = cwd.file_name().map_or_else(
+                    || cwd.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                (id, format!("{} {name}", index + 1), id == self.active)
+            })
+            .collect()
+    }
+
+    /// The row above the chat listing the tabs. Parked tabs that need the
+    /// user are marked with a dot.
+    pub(crate) fn tab_bar(&self, active_cwd: &Path) -> Line<'static> {
+        Line::from(
+            self.tab_bar_segments(active_cwd)
+                .into_iter()
+                .flat_map(|(_, spans)| spans)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The tab drawn at `column` of the tab bar, for mouse clicks.
+    pub(crate) fn tab_at(&self, active_cwd: &Path, column: u16) -> Option<TabId> {
+        let mut right = 0;
+        for (id, spans) in self.tab_bar_segments(active_cwd) {
+            right += spans.iter().map(Span::width).sum::<usize>();
+            if usize::from(column) < right {
+                return Some(id);
+            }
+        }
+        None
+    }
+
+    /// The spans of the tab bar, grouped by the tab they belong to.
+    fn tab_bar_segments(&self, active_cwd: &Path) -> Vec<(TabId, Vec<Span<'static>>)> {
+        self.labels(active_cwd)
+            .into_iter()
+            .map(|(id, label, is_active)| {
+                let mut spans: Vec<Span<'static>> = vec![" ".into()];
+                let needs_attention = self
+                    .parked
+                    .iter()
+                    .any(|tab| tab.id == id && tab.needs_attention);
+                if is_active {
+                    spans.push(format!(" {label} ").cyan().bold().reversed());
+                } else if needs_attention {
+                    spans.push(format!(" {label} ").bold());
+                    spans.push("•".magenta());
+                } else {
+                    spans.push(format!(" {label} ").dim());
+                }
+                (id, spans)
+            })
+            .collect()
+    }
+}
+
+/// The id after (or before) `current` in `ids`, wrapping around.
+fn cycle(ids: &[TabId], current: TabId, forward: bool) -> Option<TabId> {
+    let position = ids.iter().position(|id| *id == current)?;
+    let len = ids.len();
+    if len < 2 {
+        return None;
+    }
+    let next = if forward {
+        (position + 1) % len
+    } else {
+        (position + len - 1) % len
+    };
+    ids.get(next).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cycling_wraps_in_opening_order() {
+        assert_eq!(cycle(&[0], 0, true), None);
+        assert_eq!(cycle(&[0, 1, 3], 3, true), Some(0));
+        assert_eq!(cycle(&[0, 1, 3], 3, false), Some(1));
+        assert_eq!(cycle(&[0, 1, 3], 0, false), Some(3));
+        assert_eq!(SessionTabs::default().neighbor(true), None);
+    }
+
+    #[test]
+    fn clicks_map_to_the_tab_under_them() {
+        let tabs = SessionTabs::default();
+        let cwd = Path::new("/work/app");
+        // " " then " 1 app ".
+        assert_eq!(tabs.tab_at(cwd, 0), Some(0));
+        assert_eq!(tabs.tab_at(cwd, 7), Some(0));
+        assert_eq!(tabs.tab_at(cwd, 8), None);
+    }
+}
@@ -14,17 +14,29 @@ pub enum SlashCommand {
     // more frequently used commands should be listed first.
     Model,
     Approvals,
+    AllowOnce,
     Experimental,
+    Theme,
+    Mouse,
     Skills,
     Review,
+    Plan,
     New,
+    Tab,
     Resume,
+    Title,
     Init,
     Compact,
     Undo,
     Diff,
+    Git,
+    Export,
+    Copy,
     Mention,
+    PasteAsFile,
+    Remember,
     Status,
+    Cost,
     Mcp,
     Logout,
     Quit,
@@ -44,18 +56,42 @@ impl SlashCommand {
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Plan => {
+                "toggle plan mode, where Codex plans without editing (`/plan <task>`)"
+            }
             SlashCommand::Resume => "resume a saved chat",
+            SlashCommand::Title => "show or rename this session (`/title <name>`)",
             SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Git => "show the branch and changed files (`git status --short`)",
+            SlashCommand::Export => {
+                "write the conversation to a file (`/export [markdown|html|json] <path>`)"
+            }
+            SlashCommand::Copy => {
+                "copy the last code block, command, diff, or reply (`/copy [code|command|diff|reply]`)"
+            }
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Remember => "save a rule to the project's learned steering file",
+            SlashCommand::PasteAsFile => {
+                "save the clipboard (text or image) as a file for the next message"
+            }
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Ps => "list background terminals",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what Codex can do without approval",
+            SlashCommand::AllowOnce => {
+                "grant network, a tool, or a writable dir for the next turn only"
+            }
             SlashCommand::Experimental => "toggle beta features",
-            SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Theme => "choose a color theme",
+            SlashCommand::Mouse => "turn mouse capture (scrolling and clicks) on or off",
+            SlashCommand::Cost => "show token usage and estimated cost",
+            SlashCommand::Tab => {
+                "open, switch, or close session tabs (`/tab new [dir]`, `/tab close`)"
+            }
+            SlashCommand::Mcp => "list MCP tools, or attach a server with `/mcp add`",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
             SlashCommand::TestApproval => "test approval request",
@@ -78,13 +114,25 @@ impl SlashCommand {
             | SlashCommand::Undo
             | SlashCommand::Model
             | SlashCommand::Approvals
+            | SlashCommand::AllowOnce
             | SlashCommand::Experimental
             | SlashCommand::Review
+            | SlashCommand::Plan
+            | SlashCommand::Remember
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Git
+            | SlashCommand::Export
+            | SlashCommand::Copy
             | SlashCommand::Mention
+            | SlashCommand::PasteAsFile
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Cost
+            | SlashCommand::Theme
+            | SlashCommand::Mouse
+            | SlashCommand::Tab
+            | SlashCommand::Title
             | SlashCommand::Ps
             | SlashCommand::Mcp
             | SlashCommand::Feedback
@@ -95,6 +143,22 @@ impl SlashCommand {
         }
     }
 
+    /// Whether text typed after the command name is passed to the command
+    /// instead of being submitted as a regular message.
+    pub fn accepts_inline_args(self) -> bool {
+        matches!(
+            self,
+            SlashCommand::Remember
+                | SlashCommand::Plan
+                | SlashCommand::AllowOnce
+                | SlashCommand::Mcp
+                | SlashCommand::Tab
+                | SlashCommand::Export
+                | SlashCommand::Copy
+                | SlashCommand::Title
+        )
+    }
+
     fn is_visible(self) -> bool {
         match self {
             SlashCommand::Rollout | SlashCommand::TestApproval => cfg!(debug_assertions),
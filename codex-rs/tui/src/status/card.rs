@@ -8,6 +8,7 @@ use chrono::Local;
 use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
 use codex_core::openai_models::model_family::ModelFamily;
+use codex_core::protocol::PromptTokenBreakdown;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
 use codex_protocol::ConversationId;
@@ -51,6 +52,8 @@ pub(crate) struct StatusTokenUsageData {
     input: i64,
     output: i64,
     context_window: Option<StatusContextWindowData>,
+    /// Estimated tokens per prompt spent on steering files, when any are loaded.
+    steering: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -61,6 +64,8 @@ struct StatusHistoryCell {
     approval: String,
     sandbox: String,
     agents_summary: String,
+    /// Opt-in experimental features, when any are on.
+    experimental: Option<String>,
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
@@ -74,6 +79,7 @@ pub(crate) fn new_status_output(
     model_family: &ModelFamily,
     total_usage: &TokenUsage,
     context_usage: Option<&TokenUsage>,
+    prompt_breakdown: Option<&PromptTokenBreakdown>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
@@ -87,6 +93,7 @@ pub(crate) fn new_status_output(
         model_family,
         total_usage,
         context_usage,
+        prompt_breakdown,
         session_id,
         rate_limits,
         plan_type,
@@ -105,6 +112,7 @@ impl StatusHistoryCell {
         model_family: &ModelFamily,
         total_usage: &TokenUsage,
         context_usage: Option<&TokenUsage>,
+        prompt_breakdown: Option<&PromptTokenBreakdown>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
         plan_type: Option<PlanType>,
@@ -124,6 +132,10 @@ impl StatusHistoryCell {
             SandboxPolicy::WorkspaceWrite { .. } => "workspace-write".to_string(),
         };
         let agents_summary = compose_agents_summary(config);
+        let experimental = config_entries
+            .iter()
+            .find(|(k, _)| *k == "experimental")
+            .map(|(_, v)| v.clone());
         let account = compose_account_display(auth_manager, plan_type);
         let session_id = session_id.as_ref().map(std::string::ToString::to_string);
         let context_window = model_family.context_window.and_then(|window| {
@@ -139,6 +151,9 @@ impl StatusHistoryCell {
             input: total_usage.non_cached_input(),
             output: total_usage.output_tokens,
             context_window,
+            steering: prompt_breakdown
+                .map(|breakdown| breakdown.steering_tokens)
+                .filter(|tokens| *tokens > 0),
         };
         let rate_limits = compose_rate_limit_data(rate_limits, now);
 
@@ -149,6 +164,7 @@ impl StatusHistoryCell {
             approval,
             sandbox,
             agents_summary,
+            experimental,
             account,
             session_id,
             token_usage,
@@ -190,6 +206,14 @@ impl StatusHistoryCell {
         ])
     }
 
+    fn steering_spans(&self) -> Option<Vec<Span<'static>>> {
+        let steering = self.token_usage.steering?;
+        Some(vec![
+            Span::from(format_tokens_compact(steering)),
+            Span::from(" tokens/turn").dim(),
+        ])
+    }
+
     fn rate_limit_lines(
         &self,
         available_inner_width: usize,
@@ -330,6 +354,9 @@ impl HistoryCell for StatusHistoryCell {
                 .collect();
         let mut seen: BTreeSet<String> = labels.iter().cloned().collect();
 
+        if self.experimental.is_some() {
+            push_label(&mut labels, &mut seen, "Experimental");
+        }
         if account_value.is_some() {
             push_label(&mut labels, &mut seen, "Account");
         }
@@ -340,6 +367,9 @@ impl HistoryCell for StatusHistoryCell {
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
+        if self.token_usage.steering.is_some() {
+            push_label(&mut labels, &mut seen, "Steering");
+        }
         self.collect_rate_limit_labels(&mut seen, &mut labels);
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
@@ -376,6 +406,9 @@ impl HistoryCell for StatusHistoryCell {
         lines.push(formatter.line("Approval", vec![Span::from(self.approval.clone())]));
         lines.push(formatter.line("Sandbox", vec![Span::from(self.sandbox.clone())]));
         lines.push(formatter.line("Agents.md", vec![Span::from(self.agents_summary.clone())]));
+        if let Some(experimental) = &self.experimental {
+            lines.push(formatter.line("Experimental", vec![Span::from(experimental.clone())]));
+        }
 
         if let Some(account_value) = account_value {
             lines.push(formatter.line("Account", vec![Span::from(account_value)]));
@@ -395,6 +428,10 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Context window", spans));
         }
 
+        if let Some(spans) = self.steering_spans() {
+            lines.push(formatter.line("Steering", spans));
+        }
+
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
         let content_width = lines.iter().map(line_display_width).max().unwrap_or(0);
@@ -0,0 +1,130 @@
+//! Estimated cost of token usage, priced with the `model_prices` table.
+
+use codex_core::config::types::ModelPrice;
+use codex_core::protocol::TokenUsage;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use super::format_tokens_compact;
+use crate::history_cell::PlainHistoryCell;
+
+/// The estimated cost of `usage` in US dollars.
+pub(crate) fn estimate_cost(usage: &TokenUsage, price: &ModelPrice) -> f64 {
+    price.cost_usd(usage)
+}
+
+pub(crate) fn format_cost(dollars: f64) -> String {
+    if dollars > 0.0 && dollars < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("${dollars:.2}")
+    }
+}
+
+/// The usage in `total` since `start` was recorded.
+pub(crate) fn usage_since(total: &TokenUsage, start: &TokenUsage) -> TokenUsage {
+    TokenUsage {
+        input_tokens: total.input_tokens - start.input_tokens,
+        cached_input_tokens: total.cached_input_tokens - start.cached_input_tokens,
+        output_tokens: total.output_tokens - start.output_tokens,
+        reasoning_output_tokens: total.reasoning_output_tokens - start.reasoning_output_tokens,
+        total_tokens: total.total_tokens - start.total_tokens,
+    }
+}
+
+/// The `/cost` breakdown: input, cached, and output tokens for the session
+/// and the last turn, each with its estimated cost when `price` is known.
+pub(crate) fn new_cost_output(
+    model: &str,
+    price: Option<&ModelPrice>,
+    session: &TokenUsage,
+    last_turn: Option<&TokenUsage>,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec!["/cost".magenta().into(), "".into()];
+    lines.push(vec!["Session".bold(), format!(" · {model}").dim()].into());
+    lines.extend(usage_lines(session, price));
+    if let Some(turn) = last_turn {
+        lines.push("".into());
+        lines.push("Last turn".bold().into());
+        lines.extend(usage_lines(turn, price));
+    }
+    if price.is_none() {
+        lines.push("".into());
+        lines.push(
+            format!("Add [model_prices.\"{model}\"] to config.toml to estimate cost.")
+                .dim()
+                .into(),
+        );
+    }
+    PlainHistoryCell::new(lines)
+}
+
+fn usage_lines(usage: &TokenUsage, price: Option<&ModelPrice>) -> Vec<Line<'static>> {
+    let cost = |usage: TokenUsage| -> String {
+        price
+            .map(|price| format!("  {}", format_cost(estimate_cost(&usage, price))))
+            .unwrap_or_default()
+    };
+    let input = TokenUsage {
+        input_tokens: usage.input_tokens,
+        cached_input_tokens: usage.cached_input_tokens,
+        ..TokenUsage::default()
+    };
+    let output = TokenUsage {
+        output_tokens: usage.output_tokens,
+        ..TokenUsage::default()
+    };
+    vec![
+        format!(
+            "  Input   {} ({} cached){}",
+            format_tokens_compact(usage.input_tokens),
+            format_tokens_compact(usage.cached_input_tokens),
+            cost(input),
+        )
+        .into(),
+        format!(
+            "  Output  {} ({} reasoning){}",
+            format_tokens_compact(usage.output_tokens),
+            format_tokens_compact(usage.reasoning_output_tokens),
+            cost(output),
+        )
+        .into(),
+        format!(
+            "  Total   {}{}",
+            format_tokens_compact(usage.blended_total()),
+            cost(usage.clone()),
+        )
+        .into(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cached_input_is_priced_separately() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 50_000,
+            total_tokens: 1_100_000,
+        };
+        let price = ModelPrice {
+            input: 1.25,
+            cached_input: Some(0.125),
+            output: 10.0,
+        };
+        // 0.6M × $1.25 + 0.4M × $0.125 + 0.1M × $10
+        assert_eq!(format_cost(estimate_cost(&usage, &price)), "$1.80");
+        let uncached = ModelPrice {
+            cached_input: None,
+            ..price
+        };
+        assert_eq!(format_cost(estimate_cost(&usage, &uncached)), "$2.25");
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.004), "<$0.01");
+    }
+}
@@ -1,10 +1,15 @@
 mod account;
 mod card;
+mod cost;
 mod format;
 mod helpers;
 mod rate_limits;
 
 pub(crate) use card::new_status_output;
+pub(crate) use cost::estimate_cost;
+pub(crate) use cost::format_cost;
+pub(crate) use cost::new_cost_output;
+pub(crate) use cost::usage_since;
 pub(crate) use helpers::format_tokens_compact;
 pub(crate) use rate_limits::RateLimitSnapshotDisplay;
 pub(crate) use rate_limits::rate_limit_snapshot_display;
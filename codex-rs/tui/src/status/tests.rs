@@ -11,6 +11,7 @@ use codex_core::config::ConfigToml;
 use codex_core::openai_models::model_family::ModelFamily;
 use codex_core::openai_models::models_manager::ModelsManager;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::PromptTokenBreakdown;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::SandboxPolicy;
@@ -139,6 +140,7 @@ fn status_snapshot_includes_reasoning_details() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -196,6 +198,7 @@ fn status_snapshot_includes_monthly_limit() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -241,6 +244,7 @@ fn status_snapshot_shows_unlimited_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -285,6 +289,7 @@ fn status_snapshot_shows_positive_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -329,6 +334,7 @@ fn status_snapshot_hides_zero_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -371,6 +377,7 @@ fn status_snapshot_hides_when_has_no_credits_flag() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -413,6 +420,7 @@ fn status_card_token_usage_excludes_cached_tokens() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         None,
         None,
@@ -470,6 +478,7 @@ fn status_snapshot_truncates_in_narrow_terminal() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -516,6 +525,7 @@ fn status_snapshot_shows_missing_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         None,
         None,
@@ -580,6 +590,7 @@ fn status_snapshot_includes_credits_and_limits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -632,6 +643,7 @@ fn status_snapshot_shows_empty_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -693,6 +705,7 @@ fn status_snapshot_shows_stale_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -758,6 +771,7 @@ fn status_snapshot_cached_limits_hide_credits_without_flag() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -809,6 +823,7 @@ fn status_context_window_uses_last_usage() {
         &model_family,
         &total_usage,
         Some(&last_usage),
+        None,
         &None,
         None,
         None,
@@ -830,3 +845,55 @@ fn status_context_window_uses_last_usage() {
         "context line should not use total aggregated tokens, got: {context_line}"
     );
 }
+
+#[test]
+fn status_shows_steering_cost_per_turn() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model_context_window = Some(272_000);
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage {
+        input_tokens: 12_800,
+        cached_input_tokens: 0,
+        output_tokens: 879,
+        reasoning_output_tokens: 0,
+        total_tokens: 13_679,
+    };
+    let breakdown = PromptTokenBreakdown {
+        steering_tokens: 3_200,
+        agents_md_tokens: 800,
+        history_tokens: 8_800,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 6, 1, 12, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let model_family = test_model_family(&model_slug, &config);
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        &model_family,
+        &usage,
+        Some(&usage),
+        Some(&breakdown),
+        &None,
+        None,
+        None,
+        now,
+        &model_slug,
+    );
+    let rendered_lines = render_lines(&composite.display_lines(80));
+    let steering_line = rendered_lines
+        .into_iter()
+        .find(|line| line.contains("Steering"))
+        .expect("steering line");
+
+    assert!(
+        steering_line.contains("3.2K tokens/turn"),
+        "expected steering line to report per-turn cost, got: {steering_line}"
+    );
+}
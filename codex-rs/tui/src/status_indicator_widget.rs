@@ -12,6 +12,7 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::WidgetRef;
 
+use crate::accessibility;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::exec_cell::spinner;
@@ -144,9 +145,14 @@ impl Renderable for StatusIndicatorWidget {
             return;
         }
 
-        // Schedule next animation frame.
-        self.frame_requester
-            .schedule_frame_in(Duration::from_millis(32));
+        // A ticking timer would be re-read by a screen reader every second, and
+        // without it nothing here changes between frames.
+        let accessible = accessibility::enabled();
+        if !accessible {
+            // Schedule next animation frame.
+            self.frame_requester
+                .schedule_frame_in(Duration::from_millis(32));
+        }
         let now = Instant::now();
         let elapsed_duration = self.elapsed_duration_at(now);
         let pretty_elapsed = fmt_elapsed_compact(elapsed_duration.as_secs());
@@ -160,7 +166,15 @@ impl Renderable for StatusIndicatorWidget {
             spans.push(self.header.clone().into());
         }
         spans.push(" ".into());
-        if self.show_interrupt_hint {
+        if accessible {
+            if self.show_interrupt_hint {
+                spans.extend(vec![
+                    "(".dim(),
+                    key_hint::plain(KeyCode::Esc).into(),
+                    " to interrupt)".dim(),
+                ]);
+            }
+        } else if self.show_interrupt_hint {
             spans.extend(vec![
                 format!("({pretty_elapsed} • ").dim(),
                 key_hint::plain(KeyCode::Esc).into(),
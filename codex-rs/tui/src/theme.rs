@@ -0,0 +1,291 @@
+//! Color themes for the TUI (`tui.theme`).
+//!
+//! The style guide keeps the palette to four ANSI colors with fixed meanings:
+//! cyan for accents, green for success, red for errors, and magenta for Codex.
+//! A theme says which color each of those is drawn as, and [`Theme::remap`]
+//! applies it where cells are written to the terminal, so call sites keep
+//! using `.cyan()` and friends. Diffs, borders, the status bar, and the user
+//! message background read their colors from the theme directly.
+
+use std::collections::BTreeMap;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use codex_core::config::types::ThemeToml;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+
+use crate::color::is_light;
+
+/// Names of the built-in themes, in the order `/theme` lists them.
+pub(crate) const BUILTIN_THEMES: &[&str] = &["auto", "dark", "light", "high-contrast"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    pub(crate) accent: Color,
+    pub(crate) success: Color,
+    pub(crate) error: Color,
+    pub(crate) codex: Color,
+    /// `None` tints the terminal's own background, light or dark.
+    pub(crate) user_message_bg: Option<Color>,
+    pub(crate) diff_add: Color,
+    pub(crate) diff_del: Color,
+    /// `None` draws borders dim.
+    pub(crate) border: Option<Color>,
+    /// `None` keeps the footer in the default foreground.
+    pub(crate) status_bar: Option<Color>,
+}
+
+#[allow(clippy::disallowed_methods)]
+impl Theme {
+    /// The palette of the style guide, drawn as the terminal's ANSI colors.
+    pub(crate) const DARK: Theme = Theme {
+        accent: Color::Cyan,
+        success: Color::Green,
+        error: Color::Red,
+        codex: Color::Magenta,
+        user_message_bg: None,
+        diff_add: Color::Green,
+        diff_del: Color::Red,
+        border: None,
+        status_bar: None,
+    };
+
+    /// Darker shades that stay readable on a white background.
+    pub(crate) const LIGHT: Theme = Theme {
+        accent: Color::Indexed(25),
+        success: Color::Indexed(28),
+        error: Color::Indexed(124),
+        codex: Color::Indexed(90),
+        user_message_bg: None,
+        diff_add: Color::Indexed(28),
+        diff_del: Color::Indexed(124),
+        border: Some(Color::Indexed(245)),
+        status_bar: Some(Color::Indexed(240)),
+    };
+
+    /// Bright colors and undimmed borders for a dark background.
+    pub(crate) const HIGH_CONTRAST: Theme = Theme {
+        accent: Color::Indexed(14),
+        success: Color::Indexed(10),
+        error: Color::Indexed(9),
+        codex: Color::Indexed(13),
+        user_message_bg: Some(Color::Indexed(236)),
+        diff_add: Color::Indexed(10),
+        diff_del: Color::Indexed(9),
+        border: Some(Color::Indexed(15)),
+        status_bar: Some(Color::Indexed(15)),
+    };
+
+    /// The color `color` is drawn as: the palette colors become their role
+    /// in this theme and everything else is left alone.
+    pub(crate) fn remap(&self, color: Color) -> Color {
+        match color {
+            Color::Cyan => self.accent,
+            Color::Green => self.success,
+            Color::Red => self.error,
+            Color::Magenta => self.codex,
+            other => other,
+        }
+    }
+}
+
+static CURRENT: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+pub(crate) fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(crate) fn set_current(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(PoisonError::into_inner) = theme;
+}
+
+pub(crate) fn border_style() -> Style {
+    match current().border {
+        Some(color) => Style::default().fg(color),
+        None => Style::default().dim(),
+    }
+}
+
+pub(crate) fn status_bar_style() -> Style {
+    match current().status_bar {
+        Some(color) => Style::default().fg(color),
+        None => Style::default(),
+    }
+}
+
+/// The theme called `name`: a built-in one, or one of `custom` on top of its
+/// `base`. `auto` picks `light` when `terminal_bg` is light and `dark`
+/// otherwise.
+pub(crate) fn resolve(
+    name: &str,
+    custom: &BTreeMap<String, ThemeToml>,
+    terminal_bg: Option<(u8, u8, u8)>,
+) -> Result<Theme, String> {
+    if let Some(theme) = builtin(name, terminal_bg) {
+        return Ok(theme);
+    }
+    let Some(definition) = custom.get(name) else {
+        return Err(format!("unknown theme `{name}`"));
+    };
+    let base = definition.base.as_deref().unwrap_or("dark");
+    let mut theme = builtin(base, terminal_bg)
+        .ok_or_else(|| format!("theme `{name}`: unknown base theme `{base}`"))?;
+    let color = |role: &str, value: &Option<String>| -> Result<Option<Color>, String> {
+        value
+            .as_deref()
+            .map(|value| {
+                parse_color(value)
+                    .ok_or_else(|| format!("theme `{name}`: invalid color `{value}` for `{role}`"))
+            })
+            .transpose()
+    };
+    if let Some(c) = color("accent", &definition.accent)? {
+        theme.accent = c;
+    }
+    if let Some(c) = color("success", &definition.success)? {
+        theme.success = c;
+    }
+    if let Some(c) = color("error", &definition.error)? {
+        theme.error = c;
+    }
+    if let Some(c) = color("codex", &definition.codex)? {
+        theme.codex = c;
+    }
+    if let Some(c) = color("diff_add", &definition.diff_add)? {
+        theme.diff_add = c;
+    }
+    if let Some(c) = color("diff_del", &definition.diff_del)? {
+        theme.diff_del = c;
+    }
+    if let Some(c) = color("user_message_bg", &definition.user_message_bg)? {
+        theme.user_message_bg = Some(c);
+    }
+    if let Some(c) = color("border", &definition.border)? {
+        theme.border = Some(c);
+    }
+    if let Some(c) = color("status_bar", &definition.status_bar)? {
+        theme.status_bar = Some(c);
+    }
+    Ok(theme)
+}
+
+fn builtin(name: &str, terminal_bg: Option<(u8, u8, u8)>) -> Option<Theme> {
+    match name {
+        "auto" if terminal_bg.is_some_and(is_light) => Some(Theme::LIGHT),
+        "auto" | "dark" => Some(Theme::DARK),
+        "light" => Some(Theme::LIGHT),
+        "high-contrast" => Some(Theme::HIGH_CONTRAST),
+        _ => None,
+    }
+}
+
+/// Parses an ANSI color name (`cyan`, `light-red`, ...), `#rrggbb`, or
+/// `reset`. Names become palette indices so that [`Theme::remap`] leaves
+/// them alone.
+#[allow(clippy::disallowed_methods)]
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    let index = match value.to_ascii_lowercase().replace('_', "-").as_str() {
+        "reset" | "default" => return Some(Color::Reset),
+        "black" => 0,
+        "red" => 1,
+        "green" => 2,
+        "yellow" => 3,
+        "blue" => 4,
+        "magenta" => 5,
+        "cyan" => 6,
+        "gray" | "grey" => 7,
+        "dark-gray" | "dark-grey" => 8,
+        "light-red" => 9,
+        "light-green" => 10,
+        "light-yellow" => 11,
+        "light-blue" => 12,
+        "light-magenta" => 13,
+        "light-cyan" => 14,
+        "white" => 15,
+        _ => return None,
+    };
+    Some(Color::Indexed(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn auto_follows_the_terminal_background() {
+        let custom = BTreeMap::new();
+        assert_eq!(resolve("auto", &custom, None), Ok(Theme::DARK));
+        assert_eq!(
+            resolve("auto", &custom, Some((20, 20, 20))),
+            Ok(Theme::DARK)
+        );
+        assert_eq!(
+            resolve("auto", &custom, Some((250, 250, 250))),
+            Ok(Theme::LIGHT)
+        );
+        assert_eq!(
+            resolve("solarized", &custom, None),
+            Err("unknown theme `solarized`".to_string())
+        );
+    }
+
+    #[test]
+    #[allow(clippy::disallowed_methods)]
+    fn custom_themes_override_their_base() {
+        let custom = BTreeMap::from([
+            (
+                "mine".to_string(),
+                ThemeToml {
+                    base: Some("light".to_string()),
+                    accent: Some("#0066cc".to_string()),
+                    diff_add: Some("light_green".to_string()),
+                    border: Some("reset".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "broken".to_string(),
+                ThemeToml {
+                    error: Some("#12345".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let theme = resolve("mine", &custom, None).expect("theme");
+        assert_eq!(
+            theme,
+            Theme {
+                accent: Color::Rgb(0, 0x66, 0xcc),
+                diff_add: Color::Indexed(10),
+                border: Some(Color::Reset),
+                ..Theme::LIGHT
+            }
+        );
+        assert_eq!(
+            resolve("broken", &custom, None),
+            Err("theme `broken`: invalid color `#12345` for `error`".to_string())
+        );
+    }
+
+    #[test]
+    fn remap_only_touches_the_palette() {
+        let theme = Theme::LIGHT;
+        assert_eq!(theme.remap(Color::Cyan), theme.accent);
+        assert_eq!(theme.remap(Color::Magenta), theme.codex);
+        assert_eq!(theme.remap(Color::Blue), Color::Blue);
+        assert_eq!(theme.remap(theme.accent), theme.accent);
+        for color in [Color::Cyan, Color::Green, Color::Red, Color::Magenta] {
+            assert_eq!(Theme::DARK.remap(color), color);
+        }
+    }
+}
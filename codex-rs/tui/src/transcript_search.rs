@@ -0,0 +1,361 @@
+//! Incremental search for the transcript overlay.
+//!
+//! `/` opens a prompt; every keystroke re-runs the query against the
+//! transcript lines at the current width. Matches are plain substrings by
+//! default and case-insensitive unless the case-sensitive mode is on; the
+//! regex mode takes the query as a regular expression instead.
+
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use regex_lite::Regex;
+use regex_lite::RegexBuilder;
+
+/// One occurrence of the query, as byte offsets into the text of a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SearchMatch {
+    pub(crate) cell: usize,
+    pub(crate) line: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TranscriptSearch {
+    query: String,
+    /// The prompt has focus, so keys edit the query.
+    editing: bool,
+    case_sensitive: bool,
+    regex: bool,
+    matches: Vec<SearchMatch>,
+    current: Option<usize>,
+    /// Why the query is not a valid regex.
+    error: Option<String>,
+}
+
+impl TranscriptSearch {
+    /// Opens the prompt with an empty query, keeping the modes.
+    pub(crate) fn start(&mut self) {
+        self.query.clear();
+        self.editing = true;
+        self.matches.clear();
+        self.current = None;
+        self.error = None;
+    }
+
+    /// Closes the prompt and forgets the query and its matches.
+    pub(crate) fn clear(&mut self) {
+        *self = Self {
+            case_sensitive: self.case_sensitive,
+            regex: self.regex,
+            ..Self::default()
+        };
+    }
+
+    /// Closes the prompt, keeping the matches for `n`/`N`.
+    pub(crate) fn confirm(&mut self) {
+        self.editing = false;
+    }
+
+    pub(crate) fn push_char(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    /// Deletes the last character. Returns false when the query was
+    /// already empty.
+    pub(crate) fn pop_char(&mut self) -> bool {
+        self.query.pop().is_some()
+    }
+
+    pub(crate) fn toggle_case_sensitive(&mut self) {
+        self.case_sensitive = !self.case_sensitive;
+    }
+
+    pub(crate) fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+    }
+
+    pub(crate) fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Whether a prompt or a finished query is showing.
+    pub(crate) fn is_active(&self) -> bool {
+        self.editing || !self.query.is_empty()
+    }
+
+    pub(crate) fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub(crate) fn matches(&self) -> &[SearchMatch] {
+        &self.matches
+    }
+
+    pub(crate) fn current(&self) -> Option<SearchMatch> {
+        self.matches.get(self.current?).copied()
+    }
+
+    /// Re-runs the query over `cells`, the lines of every transcript cell.
+    /// The current match becomes the first one at or after `from` (a cell and
+    /// line), so typing moves forward from where the view is rather than
+    /// jumping back to the top. Without `from` it is the last match, the most
+    /// recent one.
+    pub(crate) fn update(&mut self, cells: &[Vec<Line<'static>>], from: Option<(usize, usize)>) {
+        self.matches.clear();
+        self.current = None;
+        self.error = None;
+        let regex = match self.build_regex() {
+            Ok(Some(regex)) => regex,
+            Ok(None) => return,
+            Err(err) => {
+                self.error = Some(err);
+                return;
+            }
+        };
+        for (cell, lines) in cells.iter().enumerate() {
+            for (line, text) in lines.iter().map(line_text).enumerate() {
+                self.matches
+                    .extend(
+                        regex
+                            .find_iter(&text)
+                            .filter(|m| m.start() < m.end())
+                            .map(|m| SearchMatch {
+                                cell,
+                                line,
+                                start: m.start(),
+                                end: m.end(),
+                            }),
+                    );
+            }
+        }
+        if !self.matches.is_empty() {
+            let next = match from {
+                Some(from) => self
+                    .matches
+                    .iter()
+                    .position(|m| (m.cell, m.line) >= from)
+                    .unwrap_or(0),
+                None => self.matches.len() - 1,
+            };
+            self.current = Some(next);
+        }
+    }
+
+    /// Moves to the next match, wrapping around at the end.
+    pub(crate) fn next_match(&mut self) {
+        let len = self.matches.len();
+        if len > 0 {
+            self.current = Some(self.current.map_or(0, |i| (i + 1) % len));
+        }
+    }
+
+    /// Moves to the previous match, wrapping around at the start.
+    pub(crate) fn previous_match(&mut self) {
+        let len = self.matches.len();
+        if len > 0 {
+            self.current = Some(self.current.map_or(len - 1, |i| (i + len - 1) % len));
+        }
+    }
+
+    /// The prompt line: the query, the match count, and the active modes.
+    pub(crate) fn status_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = vec![" /".into(), self.query.clone().into()];
+        if self.editing {
+            spans.push("▏".dim());
+        }
+        spans.push("  ".into());
+        match (&self.error, self.current) {
+            (Some(err), _) => spans.push(format!("invalid regex: {err}").red()),
+            (None, Some(current)) => {
+                spans.push(format!("{}/{}", current + 1, self.matches.len()).dim());
+            }
+            (None, None) if !self.query.is_empty() => spans.push("no matches".red()),
+            (None, None) => {}
+        }
+        for (on, label) in [(self.case_sensitive, "Aa"), (self.regex, ".*")] {
+            spans.push(" ".into());
+            if on {
+                spans.push(label.cyan());
+            } else {
+                spans.push(label.dim());
+            }
+        }
+        Line::from(spans)
+    }
+
+    fn build_regex(&self) -> Result<Option<Regex>, String> {
+        if self.query.is_empty() {
+            return Ok(None);
+        }
+        let pattern = if self.regex {
+            self.query.clone()
+        } else {
+            regex_lite::escape(&self.query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .map(Some)
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// The text of `line` with its styling dropped, which match offsets index.
+pub(crate) fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Restyles the byte ranges of `line` given in `ranges` (sorted and not
+/// overlapping), splitting spans where a range starts or ends.
+pub(crate) fn highlight_line(
+    line: Line<'static>,
+    ranges: &[(usize, usize, Style)],
+) -> Line<'static> {
+    if ranges.is_empty() {
+        return line;
+    }
+    let Line {
+        spans,
+        style,
+        alignment,
+    } = line;
+    let mut highlighted = Vec::with_capacity(spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in spans {
+        let content = span.content.into_owned();
+        let span_end = offset + content.len();
+        let mut cursor = 0;
+        for &(start, end, range_style) in ranges {
+            if end <= offset + cursor || start >= span_end {
+                continue;
+            }
+            let local_start = start.saturating_sub(offset).max(cursor);
+            let local_end = (end - offset).min(content.len());
+            if local_start > cursor {
+                highlighted.push(Span::styled(
+                    content[cursor..local_start].to_string(),
+                    span.style,
+                ));
+            }
+            highlighted.push(Span::styled(
+                content[local_start..local_end].to_string(),
+                span.style.patch(range_style),
+            ));
+            cursor = local_end;
+        }
+        if cursor < content.len() {
+            highlighted.push(Span::styled(content[cursor..].to_string(), span.style));
+        }
+        offset = span_end;
+    }
+    Line {
+        spans: highlighted,
+        style,
+        alignment,
+    }
+}
+
+/// Style of every match but the current one.
+pub(crate) fn match_style() -> Style {
+    Style::default().reversed()
+}
+
+/// Style of the current match.
+pub(crate) fn current_match_style() -> Style {
+    Style::default().cyan().reversed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn cells() -> Vec<Vec<Line<'static>>> {
+        vec![
+            vec![Line::from(vec!["$ ".dim(), "cargo test".into()])],
+            vec![
+                Line::from("running 3 tests"),
+                Line::from("test result: FAILED. 2 passed; 1 failed"),
+            ],
+            vec![Line::from("Cargo rebuilt the crate")],
+        ]
+    }
+
+    fn positions(search: &TranscriptSearch) -> Vec<(usize, usize, usize)> {
+        search
+            .matches()
+            .iter()
+            .map(|m| (m.cell, m.line, m.start))
+            .collect()
+    }
+
+    #[test]
+    fn plain_queries_are_case_insensitive_by_default() {
+        let mut search = TranscriptSearch::default();
+        search.start();
+        for c in "cargo".chars() {
+            search.push_char(c);
+        }
+        search.update(&cells(), Some((0, 0)));
+        assert_eq!(positions(&search), vec![(0, 0, 2), (2, 0, 0)]);
+        assert_eq!(search.current(), Some(search.matches()[0]));
+
+        search.toggle_case_sensitive();
+        search.update(&cells(), Some((0, 0)));
+        assert_eq!(positions(&search), vec![(0, 0, 2)]);
+
+        // Typing continues from where the view is.
+        search.toggle_case_sensitive();
+        search.update(&cells(), Some((1, 0)));
+        assert_eq!(search.current().map(|m| m.cell), Some(2));
+        search.update(&cells(), None);
+        assert_eq!(search.current().map(|m| m.cell), Some(2));
+    }
+
+    #[test]
+    fn regex_mode_and_navigation_wrap_around() {
+        let mut search = TranscriptSearch::default();
+        search.start();
+        search.toggle_regex();
+        for c in r"\d+ (passed|failed)".chars() {
+            search.push_char(c);
+        }
+        search.update(&cells(), Some((0, 0)));
+        assert_eq!(positions(&search), vec![(1, 1, 21), (1, 1, 31)]);
+
+        search.next_match();
+        assert_eq!(search.current().map(|m| m.start), Some(31));
+        search.next_match();
+        assert_eq!(search.current().map(|m| m.start), Some(21));
+        search.previous_match();
+        assert_eq!(search.current().map(|m| m.start), Some(31));
+
+        search.push_char('(');
+        search.update(&cells(), Some((0, 0)));
+        assert_eq!(search.matches(), &[]);
+        assert!(
+            line_text(&search.status_line()).contains("invalid regex"),
+            "{:?}",
+            search.status_line()
+        );
+    }
+
+    #[test]
+    fn highlight_splits_spans_at_match_boundaries() {
+        let line = Line::from(vec!["$ ".dim(), "cargo test".into()]);
+        let highlighted = highlight_line(line, &[(0, 7, match_style())]);
+        assert_eq!(
+            highlighted.spans,
+            vec![
+                Span::styled("$ ", Style::default().dim().reversed()),
+                Span::styled("cargo", Style::default().reversed()),
+                Span::raw(" test"),
+            ]
+        );
+    }
+}
@@ -6,18 +6,23 @@ use std::io::stdin;
 use std::io::stdout;
 use std::panic;
 use std::pin::Pin;
+use std::process::ExitStatus;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
+use codex_core::config::types::NotificationMethod;
 use crossterm::Command;
 use crossterm::SynchronizedUpdate;
 use crossterm::event::DisableBracketedPaste;
 use crossterm::event::DisableFocusChange;
+use crossterm::event::DisableMouseCapture;
 use crossterm::event::EnableBracketedPaste;
 use crossterm::event::EnableFocusChange;
+use crossterm::event::EnableMouseCapture;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyboardEnhancementFlags;
+use crossterm::event::MouseEvent;
 use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
@@ -39,6 +44,7 @@ use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
 use crate::notifications::DesktopNotificationBackend;
 use crate::notifications::NotificationBackendKind;
+use crate::notifications::backend_for;
 use crate::notifications::detect_backend;
 use crate::tui::event_stream::EventBroker;
 use crate::tui::event_stream::TuiEventStream;
@@ -73,9 +79,17 @@ pub fn set_modes() -> Result<()> {
     );
 
     let _ = execute!(stdout(), EnableFocusChange);
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        let _ = execute!(stdout(), EnableMouseCapture);
+    }
     Ok(())
 }
 
+/// Whether the terminal reports mouse events to us (`tui.mouse`, `/mouse`).
+/// Kept outside [`Tui`] so [`set_modes`] turns capture back on after an
+/// external editor or a suspend.
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct EnableAlternateScroll;
 
@@ -125,6 +139,7 @@ pub fn restore() -> Result<()> {
     let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
     execute!(stdout(), DisableBracketedPaste)?;
     let _ = execute!(stdout(), DisableFocusChange);
+    let _ = execute!(stdout(), DisableMouseCapture);
     disable_raw_mode()?;
     let _ = execute!(stdout(), crossterm::cursor::Show);
     Ok(())
@@ -159,6 +174,8 @@ fn set_panic_hook() {
 pub enum TuiEvent {
     Key(KeyEvent),
     Paste(String),
+    /// Only sent while mouse capture is on.
+    Mouse(MouseEvent),
     Draw,
 }
 
@@ -215,18 +232,47 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
-    // todo(sayan) unused for now; intend to use to enable opening external editors
-    #[allow(unused)]
     pub fn pause_events(&mut self) {
         self.event_broker.pause_events();
     }
 
-    // todo(sayan) unused for now; intend to use to enable opening external editors
-    #[allow(unused)]
     pub fn resume_events(&mut self) {
         self.event_broker.resume_events();
     }
 
+    /// Hand the terminal to `command` (e.g. an external editor) until it
+    /// exits, then restore our modes and redraw.
+    pub fn run_external(&mut self, command: &mut std::process::Command) -> Result<ExitStatus> {
+        self.pause_events();
+        restore()?;
+        let status = command.status();
+        set_modes()?;
+        self.resume_events();
+        self.terminal.clear()?;
+        self.frame_requester().schedule_frame();
+        status
+    }
+
+    pub fn mouse_capture(&self) -> bool {
+        MOUSE_CAPTURE.load(Ordering::Relaxed)
+    }
+
+    /// Turns mouse capture on or off. While it is on, the wheel and clicks
+    /// come to us as [`TuiEvent::Mouse`] and the terminal's own text
+    /// selection needs a modifier (usually Shift).
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        MOUSE_CAPTURE.store(enabled, Ordering::Relaxed);
+        if enabled {
+            execute!(self.terminal.backend_mut(), EnableMouseCapture)
+        } else {
+            execute!(self.terminal.backend_mut(), DisableMouseCapture)
+        }
+    }
+
+    pub fn set_notification_method(&mut self, method: NotificationMethod) {
+        self.notification_backend = Some(backend_for(method));
+    }
+
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
@@ -242,10 +288,11 @@ impl Tui {
         match backend.notify(&message) {
             Ok(()) => true,
             Err(err) => match backend.kind() {
-                NotificationBackendKind::WindowsToast => {
+                NotificationBackendKind::WindowsToast | NotificationBackendKind::Native => {
                     tracing::error!(
                         error = %err,
-                        "Failed to send Windows toast notification; falling back to OSC 9"
+                        "Failed to send {:?} notification; falling back to OSC 9",
+                        backend.kind()
                     );
                     self.notification_backend = Some(DesktopNotificationBackend::osc9());
                     if let Some(backend) = self.notification_backend.as_mut() {
@@ -324,6 +371,23 @@ impl Tui {
         Ok(())
     }
 
+    /// Wipe the screen and its scrollback and move the inline viewport to the top, so another
+    /// session's history can be inserted in place of the current one.
+    pub fn clear_history(&mut self) -> Result<()> {
+        self.pending_history_lines.clear();
+        execute!(
+            self.terminal.backend_mut(),
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::Purge),
+        )?;
+        let area = self.terminal.viewport_area;
+        self.terminal
+            .set_viewport_area(Rect::new(0, 0, area.width, area.height));
+        self.terminal.clear()?;
+        self.frame_requester().schedule_frame();
+        Ok(())
+    }
+
     pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
         self.pending_history_lines.extend(lines);
         self.frame_requester().schedule_frame();
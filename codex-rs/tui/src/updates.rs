@@ -6,6 +6,7 @@ use chrono::DateTime;
 use chrono::Duration;
 use chrono::Utc;
 use codex_core::config::Config;
+use codex_core::config::xdg::codex_cache_home;
 use codex_core::default_client::create_client;
 use serde::Deserialize;
 use serde::Serialize;
@@ -66,7 +67,7 @@ struct ReleaseInfo {
 }
 
 fn version_filepath(config: &Config) -> PathBuf {
-    config.codex_home.join(VERSION_FILENAME)
+    codex_cache_home(&config.codex_home).join(VERSION_FILENAME)
 }
 
 fn read_version_info(version_file: &Path) -> anyhow::Result<VersionInfo> {
@@ -16,8 +16,10 @@ use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use codex_core::config::trust_store::suggested_approval_rule;
 use codex_core::features::Feature;
 use codex_core::features::Features;
+use codex_core::protocol::CommandApprovalRule;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
@@ -109,10 +111,11 @@ impl ApprovalOverlay {
     ) -> (Vec<ApprovalOption>, SelectionViewParams) {
         let (options, title) = match &variant {
             ApprovalVariant::Exec {
+                command,
                 proposed_execpolicy_amendment,
                 ..
             } => (
-                exec_options(proposed_execpolicy_amendment.clone(), features),
+                exec_options(command, proposed_execpolicy_amendment.clone(), features),
                 "Would you like to run the following command?".to_string(),
             ),
             ApprovalVariant::ApplyPatch { .. } => (
@@ -448,9 +451,20 @@ impl ApprovalOption {
 }
 
 fn exec_options(
+    command: &[String],
     proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     features: &Features,
 ) -> Vec<ApprovalOption> {
+    let project_rule = suggested_approval_rule(command);
+    let project_label = match &project_rule {
+        CommandApprovalRule::Prefix { command } => format!(
+            "Yes, and always allow `{}` in this project",
+            strip_bash_lc_and_escape(command)
+        ),
+        CommandApprovalRule::Exact { .. } | CommandApprovalRule::Pattern { .. } => {
+            "Yes, and always allow this exact command in this project".to_string()
+        }
+    };
     vec![ApprovalOption {
         label: "Yes, proceed".to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Approved),
@@ -477,12 +491,22 @@ fn exec_options(
                 }
             }),
     )
-    .chain([ApprovalOption {
-        label: "No, and tell Codex what to do differently".to_string(),
-        decision: ApprovalDecision::Review(ReviewDecision::Abort),
-        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-    }])
+    .chain([
+        ApprovalOption {
+            label: project_label,
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForProject {
+                rule: project_rule,
+            }),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ])
     .collect()
 }
 
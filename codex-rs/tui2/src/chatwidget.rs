@@ -22,6 +22,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -688,6 +689,18 @@ impl ChatWidget {
         self.maybe_send_next_queued_input();
     }
 
+    fn on_config_reloaded(&mut self, ev: ConfigReloadedEvent) {
+        if ev.applied.iter().any(|key| key == "model") {
+            self.app_event_tx
+                .send(AppEvent::UpdateModel(ev.model.clone()));
+        }
+        if ev.applied.iter().any(|key| key == "model_reasoning_effort") {
+            self.app_event_tx
+                .send(AppEvent::UpdateReasoningEffort(ev.reasoning_effort));
+        }
+        self.add_info_message(ev.to_string(), None);
+    }
+
     fn on_warning(&mut self, message: impl Into<String>) {
         self.add_to_history(history_cell::new_warning_event(message.into()));
         self.request_redraw();
@@ -1823,6 +1836,7 @@ impl ChatWidget {
 
         match msg {
             EventMsg::SessionConfigured(e) => self.on_session_configured(e),
+            EventMsg::ConfigReloaded(ev) => self.on_config_reloaded(ev),
             EventMsg::AgentMessage(AgentMessageEvent { message }) => self.on_agent_message(message),
             EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { delta }) => {
                 self.on_agent_message_delta(delta)
@@ -1846,6 +1860,7 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            EventMsg::TerminologyWarning(ev) => self.on_warning(ev.to_string()),
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
@@ -1914,6 +1929,9 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::SearchHistoryResponse(_)
+            | EventMsg::SessionTitle(_)
+            | EventMsg::RunLimitReached(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
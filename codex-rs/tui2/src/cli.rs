@@ -47,7 +47,8 @@ pub struct Cli {
     pub oss_provider: Option<String>,
 
     /// Configuration profile from config.toml to specify default options.
-    #[arg(long = "profile", short = 'p')]
+    /// `--preset` is an alias. Explicit flags still win over the profile.
+    #[arg(long = "profile", short = 'p', visible_alias = "preset")]
     pub config_profile: Option<String>,
 
     /// Select the sandbox policy to use when executing model-generated shell
@@ -85,6 +86,12 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Attach an MCP server for this session only, without saving it to
+    /// config.toml. COMMAND is a shell-quoted command line or an `http(s)://`
+    /// URL. May be repeated.
+    #[arg(long = "mcp", value_name = "NAME=COMMAND")]
+    pub mcp_servers: Vec<String>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -109,6 +116,7 @@ impl From<codex_tui::Cli> for Cli {
             cwd: cli.cwd,
             web_search: cli.web_search,
             add_dir: cli.add_dir,
+            mcp_servers: cli.mcp_servers,
             config_overrides: cli.config_overrides,
         }
     }
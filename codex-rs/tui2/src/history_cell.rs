@@ -435,6 +435,27 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedForProject { .. } => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to always run ".into(),
+                    snippet,
+                    " in this project".bold(),
+                ],
+            )
+        }
+        ApprovedWithModifiedPatch { .. } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                " part of the patch".into(),
+            ],
+        ),
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
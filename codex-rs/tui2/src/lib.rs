@@ -19,9 +19,11 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::trust_store::has_project_files;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
 use codex_core::protocol::AskForApproval;
+use codex_core::secrets::RedactingWriter;
 use codex_protocol::config_types::SandboxMode;
 use std::fs::OpenOptions;
 use std::path::PathBuf;
@@ -29,6 +31,7 @@ use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
 
 mod additional_dirs;
@@ -88,7 +91,6 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
-use crate::onboarding::TrustDirectorySelection;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
@@ -133,7 +135,7 @@ pub async fn run_main(
     let raw_overrides = cli.config_overrides.raw_overrides.clone();
     // `oss` model provider.
     let overrides_cli = codex_common::CliConfigOverrides { raw_overrides };
-    let cli_kv_overrides = match overrides_cli.parse_overrides() {
+    let mut cli_kv_overrides = match overrides_cli.parse_overrides() {
         // Parse `-c` overrides from the CLI.
         Ok(v) => v,
         #[allow(clippy::print_stderr)]
@@ -143,6 +145,19 @@ pub async fn run_main(
         }
     };
 
+    // Servers from `--mcp` join `mcp_servers` like `-c` overrides, so they
+    // last for this run only.
+    for spec in &cli.mcp_servers {
+        match codex_core::mcp::session_mcp_server_overrides(spec) {
+            Ok(overrides) => cli_kv_overrides.extend(overrides),
+            #[allow(clippy::print_stderr)]
+            Err(e) => {
+                eprintln!("Error parsing --mcp: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     // we load config.toml here to determine project state.
     #[allow(clippy::print_stderr)]
     let codex_home = match find_codex_home() {
@@ -219,9 +234,13 @@ pub async fn run_main(
         show_raw_agent_reasoning: cli.oss.then_some(true),
         tools_web_search_request: None,
         additional_writable_roots: additional_dirs,
+        workspace_roots: Vec::new(),
+        load_patch_policy: false,
+        run_limits: Default::default(),
     };
 
     let config = load_config_or_exit(cli_kv_overrides.clone(), overrides.clone()).await;
+    codex_core::default_client::set_network_config(config.network.clone());
 
     if let Some(warning) = add_dir_warning_message(&cli.add_dir, &config.sandbox_policy) {
         #[allow(clippy::print_stderr)]
@@ -267,17 +286,18 @@ pub async fn run_main(
     };
 
     let file_layer = tracing_subscriber::fmt::layer()
-        .with_writer(non_blocking)
+        .with_writer(move || RedactingWriter::new(non_blocking.clone()))
         .with_target(false)
         .with_ansi(false)
         .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
         .with_filter(env_filter());
 
     let feedback = codex_feedback::CodexFeedback::new();
+    let feedback_writer = feedback.make_writer();
     let targets = Targets::new().with_default(tracing::Level::TRACE);
 
     let feedback_layer = tracing_subscriber::fmt::layer()
-        .with_writer(feedback.make_writer())
+        .with_writer(move || RedactingWriter::new(feedback_writer.make_writer()))
         .with_ansi(false)
         .with_target(false)
         .with_filter(targets);
@@ -415,12 +435,9 @@ async fn run_ratatui_app(
                 session_lines: Vec::new(),
             });
         }
-        // if the user acknowledged windows or made an explicit decision ato trust the directory, reload the config accordingly
-        if onboarding_result
-            .directory_trust_decision
-            .map(|d| d == TrustDirectorySelection::Trust)
-            .unwrap_or(false)
-        {
+        // Reload after a trust decision: it changes the default policies and
+        // whether the project's .codex/ files are loaded.
+        if onboarding_result.directory_trust_decision.is_some() {
             load_config_or_exit(cli_kv_overrides, overrides).await
         } else {
             initial_config
@@ -592,12 +609,14 @@ fn should_show_trust_screen(config: &Config) -> bool {
         // If the experimental sandbox is not enabled, Native Windows cannot enforce sandboxed write access; skip the trust prompt entirely.
         return false;
     }
-    if config.did_user_set_custom_approval_policy_or_sandbox_mode {
-        // Respect explicit approval/sandbox overrides made by the user.
+    if config.active_project.trust_level.is_some() {
+        // A trust decision has already been made.
         return false;
     }
-    // otherwise, show only if no trust decision has been made
-    config.active_project.trust_level.is_none()
+    // Explicit approval/sandbox overrides make the prompt unnecessary, unless
+    // the project has .codex/ files that should not load without a decision.
+    !config.did_user_set_custom_approval_policy_or_sandbox_mode
+        || has_project_files(&config.codex_home, &config.cwd)
 }
 
 fn should_show_onboarding(
@@ -706,4 +725,33 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    #[serial]
+    fn project_files_prompt_for_trust_despite_custom_policy() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        std::fs::create_dir(project.path().join(".codex"))?;
+        std::fs::write(
+            project.path().join(".codex/config.toml"),
+            "model = \"o3\"\n",
+        )?;
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides {
+                cwd: Some(project.path().to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        config.did_user_set_custom_approval_policy_or_sandbox_mode = true;
+        config.active_project = ProjectConfig { trust_level: None };
+        config.set_windows_sandbox_globally(true);
+
+        assert!(should_show_trust_screen(&config));
+
+        std::fs::remove_dir_all(project.path().join(".codex"))?;
+        assert!(!should_show_trust_screen(&config));
+        Ok(())
+    }
 }
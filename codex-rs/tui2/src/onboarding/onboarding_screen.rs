@@ -1,5 +1,6 @@
 use codex_core::AuthManager;
 use codex_core::config::Config;
+use codex_core::config::trust_store::has_project_files;
 use codex_core::git_info::get_git_repo_root;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -118,6 +119,7 @@ impl OnboardingScreen {
         };
         if show_trust_screen {
             steps.push(Step::TrustDirectory(TrustDirectoryWidget {
+                has_project_files: has_project_files(&codex_home, &cwd),
                 cwd,
                 codex_home,
                 is_git_repo,
@@ -25,6 +25,18 @@ export type Usage = {
   cached_input_tokens: number;
   /** The number of output tokens used during the turn. */
   output_tokens: number;
+  /** Estimated split of the latest prompt between steering files, AGENTS.md, and history. */
+  prompt_breakdown?: PromptBreakdown;
+};
+
+/** Approximate prompt tokens attributable to each source of context. */
+export type PromptBreakdown = {
+  /** Tokens spent on files loaded from the steering directories. */
+  steering_tokens: number;
+  /** Tokens spent on AGENTS.md instructions. */
+  agents_md_tokens: number;
+  /** Tokens spent on the rest of the conversation history. */
+  history_tokens: number;
 };
 
 /** Emitted when a turn is completed. Typically right after the assistant's response. */
@@ -10,6 +10,7 @@ export type {
   ThreadError,
   ThreadErrorEvent,
   Usage,
+  PromptBreakdown,
 } from "./events";
 export type {
   ThreadItem,